@@ -0,0 +1,60 @@
+//! Records what the program was built from for `GetBuildInfo`, see
+//! `src/build_info.rs`, and refuses SBF builds that would let anyone
+//! initialize the pool.
+
+use std::{env, process::Command};
+
+fn main() {
+    // Without `POOL_ADMIN`, `Initialize` accepts any signer, so a deployable
+    // build must either set it or opt in to that for tests.
+    if env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("solana")
+        && env::var_os("POOL_ADMIN").is_none()
+        && env::var_os("CARGO_FEATURE_OPEN_INITIALIZE").is_none()
+    {
+        panic!("set POOL_ADMIN to the pool admin's pubkey, or enable `open-initialize` for tests");
+    }
+
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    // A commit with uncommitted changes on top cannot be rebuilt from the
+    // commit alone, so it is marked dirty.
+    let commit = match git(&["rev-parse", "HEAD"]) {
+        Some(commit) if git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty()) => {
+            format!("{commit}-dirty")
+        }
+        Some(commit) => commit,
+        None => "unknown".to_string(),
+    };
+
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|feature| feature.to_lowercase().replace('_', "-"))
+        })
+        .collect();
+    features.sort();
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let toolchain = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    println!("cargo:rustc-env=BUILD_GIT_COMMIT={commit}");
+    println!("cargo:rustc-env=BUILD_FEATURES={}", features.join(","));
+    println!("cargo:rustc-env=BUILD_TOOLCHAIN={toolchain}");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/index");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-env-changed=POOL_ADMIN");
+}
@@ -0,0 +1,354 @@
+//! Sets up a pool on a local validator from a TOML config, so frontends get
+//! the same localnet environment on every run. Built with the `localnet`
+//! feature:
+//!
+//! ```bash
+//! solana-test-validator --reset --slots-per-epoch 32 \
+//!     --bpf-program 22222222222222222222222222222222222222222222 \
+//!     target/deploy/solana_liquid_staking.so
+//! cargo run --features localnet --bin localnet_fixture -- localnet.toml
+//! ```
+//!
+//! It initializes the pool, makes the configured deposits and runs one crank
+//! cycle (`CrankInitializeReserve`, then `CrankMergeReserve` in the next
+//! epoch), then writes the pool's addresses to the config's `output` file.
+//! Keypairs are derived from `seed`, so the mint and depositor addresses are
+//! the same on every run against a reset validator.
+
+use std::{env, fmt::Write as _, fs, str::FromStr, thread, time::Duration};
+
+use serde::Deserialize;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+use solana_liquid_staking::ids::{
+    ASSOCIATED_TOKEN_PROGRAM_ID, STAKE_HISTORY_SYSVAR_ID, STAKE_PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
+
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+const MINT_LEN: u64 = 82;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FixtureConfig {
+    #[serde(default = "default_rpc_url")]
+    rpc_url: String,
+    /// Derives the admin, mint and depositor keypairs.
+    #[serde(default)]
+    seed: u64,
+    /// Vote account the pool delegates to, the local validator's when unset.
+    vote_account: Option<String>,
+    #[serde(default)]
+    deposits: Vec<DepositConfig>,
+    /// Runs the crank cycle, waiting for the next epoch before the merge.
+    #[serde(default = "default_true")]
+    crank: bool,
+    /// Where the pool's addresses are written, as TOML.
+    #[serde(default = "default_output")]
+    output: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DepositConfig {
+    lamports: u64,
+    /// Number of depositors making this deposit, each with their own key.
+    #[serde(default = "default_count")]
+    count: u32,
+}
+
+fn default_rpc_url() -> String {
+    "http://127.0.0.1:8899".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_output() -> String {
+    "localnet-fixture.toml".to_string()
+}
+
+fn default_count() -> u32 {
+    1
+}
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+fn main() -> Result<()> {
+    let path = env::args()
+        .nth(1)
+        .ok_or("usage: localnet_fixture <config.toml>")?;
+    let config: FixtureConfig = toml::from_str(&fs::read_to_string(&path)?)?;
+    let client = RpcClient::new(config.rpc_url);
+
+    let program_id = Pubkey::new_from_array(solana_liquid_staking::ID);
+    let stake_program = Pubkey::new_from_array(STAKE_PROGRAM_ID);
+    let pda = |seed: &[u8]| Pubkey::find_program_address(&[seed], &program_id).0;
+    let config_pda = pda(b"config");
+    let stake_account_main = pda(b"stake_main");
+    let stake_account_reserve = pda(b"stake_reserve");
+
+    let vote_account = match config.vote_account {
+        Some(vote_account) => Pubkey::from_str(&vote_account)?,
+        None => {
+            let vote_accounts = client.get_vote_accounts()?;
+            let local = vote_accounts
+                .current
+                .first()
+                .ok_or("the validator has no current vote account")?;
+            Pubkey::from_str(&local.vote_pubkey)?
+        }
+    };
+
+    let admin = keypair(config.seed, 0);
+    let mint = keypair(config.seed, 1);
+    airdrop(&client, &admin.pubkey(), 100 * LAMPORTS_PER_SOL)?;
+
+    let mint_rent = client.get_minimum_balance_for_rent_exemption(MINT_LEN as usize)?;
+    let create_mint = [
+        create_account_ix(
+            &admin.pubkey(),
+            &mint.pubkey(),
+            mint_rent,
+            MINT_LEN,
+            &spl_token_id(),
+        ),
+        initialize_mint_ix(&mint.pubkey(), &config_pda),
+    ];
+    send(&client, &create_mint, &admin, &[&mint])?;
+
+    let accounts = &[
+        AccountMeta::new(admin.pubkey(), true),
+        AccountMeta::new(ata(&admin.pubkey(), &mint.pubkey()), false),
+        AccountMeta::new(config_pda, false),
+        AccountMeta::new(stake_account_main, false),
+        AccountMeta::new(stake_account_reserve, false),
+        AccountMeta::new(mint.pubkey(), true),
+        AccountMeta::new(vote_account, false),
+        AccountMeta::new(Keypair::new().pubkey(), false),
+        AccountMeta::new_readonly(system_program_id(), false),
+        AccountMeta::new_readonly(stake_program, false),
+        AccountMeta::new_readonly(spl_token_id(), false),
+        AccountMeta::new_readonly(associated_token_program_id(), false),
+        AccountMeta::new_readonly(solana_sdk::sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_sdk::sysvar::clock::id(), false),
+        AccountMeta::new_readonly(stake_history_id(), false),
+        AccountMeta::new(pda(b"epoch_tasks"), false),
+        AccountMeta::new(pda(b"pool_state"), false),
+        AccountMeta::new(pda(b"changelog"), false),
+    ];
+    send(&client, &[program_ix(0, &[], accounts)], &admin, &[&mint])?;
+    println!("Initialized pool, LST mint {}", mint.pubkey());
+
+    let mut depositors = Vec::new();
+    for deposit in &config.deposits {
+        for _ in 0..deposit.count {
+            let depositor = keypair(config.seed, 2 + depositors.len() as u64);
+            airdrop(
+                &client,
+                &depositor.pubkey(),
+                deposit.lamports + LAMPORTS_PER_SOL,
+            )?;
+
+            let depositor_ata = ata(&depositor.pubkey(), &mint.pubkey());
+            let create_ata = Instruction {
+                program_id: associated_token_program_id(),
+                // CreateIdempotent
+                data: vec![1],
+                accounts: vec![
+                    AccountMeta::new(depositor.pubkey(), true),
+                    AccountMeta::new(depositor_ata, false),
+                    AccountMeta::new_readonly(depositor.pubkey(), false),
+                    AccountMeta::new_readonly(mint.pubkey(), false),
+                    AccountMeta::new_readonly(system_program_id(), false),
+                    AccountMeta::new_readonly(spl_token_id(), false),
+                ],
+            };
+            let accounts = &[
+                AccountMeta::new_readonly(config_pda, false),
+                AccountMeta::new(depositor.pubkey(), true),
+                AccountMeta::new(depositor_ata, false),
+                AccountMeta::new(mint.pubkey(), false),
+                AccountMeta::new_readonly(stake_account_main, false),
+                AccountMeta::new(stake_account_reserve, false),
+                AccountMeta::new_readonly(stake_program, false),
+                AccountMeta::new_readonly(spl_token_id(), false),
+                AccountMeta::new_readonly(system_program_id(), false),
+                AccountMeta::new_readonly(solana_sdk::sysvar::rent::id(), false),
+                AccountMeta::new(pda(b"pool_state"), false),
+            ];
+            let deposit_ix = program_ix(3, &deposit.lamports.to_le_bytes(), accounts);
+            send(&client, &[create_ata, deposit_ix], &depositor, &[])?;
+            println!(
+                "{} deposited {} lamports",
+                depositor.pubkey(),
+                deposit.lamports
+            );
+            depositors.push(depositor);
+        }
+    }
+
+    if config.crank {
+        let accounts = &[
+            AccountMeta::new(config_pda, false),
+            AccountMeta::new(stake_account_reserve, false),
+            AccountMeta::new(vote_account, false),
+            AccountMeta::new_readonly(stake_program, false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(stake_history_id(), false),
+            AccountMeta::new_readonly(system_program_id(), false),
+            AccountMeta::new_readonly(stake_program, false),
+            AccountMeta::new_readonly(stake_account_main, false),
+            AccountMeta::new(pda(b"epoch_tasks"), false),
+        ];
+        send(&client, &[program_ix(1, &[], accounts)], &admin, &[])?;
+        println!("Delegated the reserve, waiting for the next epoch");
+
+        let epoch = client.get_epoch_info()?.epoch;
+        while client.get_epoch_info()?.epoch == epoch {
+            thread::sleep(Duration::from_secs(1));
+        }
+
+        let accounts = &[
+            AccountMeta::new(config_pda, false),
+            AccountMeta::new(stake_account_main, false),
+            AccountMeta::new(stake_account_reserve, false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(stake_history_id(), false),
+            AccountMeta::new_readonly(system_program_id(), false),
+            AccountMeta::new_readonly(stake_program, false),
+            AccountMeta::new(pda(b"epoch_tasks"), false),
+            AccountMeta::new(pda(b"pool_state"), false),
+        ];
+        send(&client, &[program_ix(2, &[], accounts)], &admin, &[])?;
+        println!("Merged the reserve into main");
+    }
+
+    let mut output = String::new();
+    writeln!(output, "program_id = \"{program_id}\"")?;
+    writeln!(output, "config = \"{config_pda}\"")?;
+    writeln!(output, "lst_mint = \"{}\"", mint.pubkey())?;
+    writeln!(output, "stake_account_main = \"{stake_account_main}\"")?;
+    writeln!(
+        output,
+        "stake_account_reserve = \"{stake_account_reserve}\""
+    )?;
+    writeln!(output, "vote_account = \"{vote_account}\"")?;
+    writeln!(output, "admin = \"{}\"", admin.pubkey())?;
+    writeln!(output, "depositors = [")?;
+    for depositor in &depositors {
+        writeln!(output, "    \"{}\",", depositor.pubkey())?;
+    }
+    writeln!(output, "]")?;
+    fs::write(&config.output, output)?;
+    println!("Wrote {}", config.output);
+
+    Ok(())
+}
+
+/// The `index`th keypair of `seed`.
+fn keypair(seed: u64, index: u64) -> Keypair {
+    let mut secret = [0u8; 32];
+    secret[..8].copy_from_slice(&seed.to_le_bytes());
+    secret[8..16].copy_from_slice(&index.to_le_bytes());
+    secret[16] = 1;
+    Keypair::new_from_array(secret)
+}
+
+fn airdrop(client: &RpcClient, to: &Pubkey, lamports: u64) -> Result<()> {
+    let signature = client.request_airdrop(to, lamports)?;
+    while !client.confirm_transaction(&signature)? {
+        thread::sleep(Duration::from_millis(200));
+    }
+    Ok(())
+}
+
+fn send(
+    client: &RpcClient,
+    instructions: &[Instruction],
+    payer: &Keypair,
+    signers: &[&Keypair],
+) -> Result<()> {
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &all_signers,
+        client.get_latest_blockhash()?,
+    );
+    client.send_and_confirm_transaction(&tx)?;
+    Ok(())
+}
+
+fn program_ix(discriminator: u8, data: &[u8], accounts: &[AccountMeta]) -> Instruction {
+    Instruction {
+        program_id: Pubkey::new_from_array(solana_liquid_staking::ID),
+        data: [&[discriminator], data].concat(),
+        accounts: accounts.to_vec(),
+    }
+}
+
+fn create_account_ix(
+    from: &Pubkey,
+    to: &Pubkey,
+    lamports: u64,
+    space: u64,
+    owner: &Pubkey,
+) -> Instruction {
+    let mut data = 0u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&lamports.to_le_bytes());
+    data.extend_from_slice(&space.to_le_bytes());
+    data.extend_from_slice(owner.as_ref());
+
+    Instruction {
+        program_id: system_program_id(),
+        data,
+        accounts: vec![AccountMeta::new(*from, true), AccountMeta::new(*to, true)],
+    }
+}
+
+/// `InitializeMint2` with 9 decimals, `mint_authority` and no freeze
+/// authority.
+fn initialize_mint_ix(mint: &Pubkey, mint_authority: &Pubkey) -> Instruction {
+    let mut data = vec![20, 9];
+    data.extend_from_slice(mint_authority.as_ref());
+    data.push(0);
+
+    Instruction {
+        program_id: spl_token_id(),
+        data,
+        accounts: vec![AccountMeta::new(*mint, false)],
+    }
+}
+
+fn ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), spl_token_id().as_ref(), mint.as_ref()],
+        &associated_token_program_id(),
+    )
+    .0
+}
+
+fn system_program_id() -> Pubkey {
+    Pubkey::default()
+}
+
+fn spl_token_id() -> Pubkey {
+    Pubkey::new_from_array(TOKEN_PROGRAM_ID)
+}
+
+fn associated_token_program_id() -> Pubkey {
+    Pubkey::new_from_array(ASSOCIATED_TOKEN_PROGRAM_ID)
+}
+
+fn stake_history_id() -> Pubkey {
+    Pubkey::new_from_array(STAKE_HISTORY_SYSVAR_ID)
+}
@@ -0,0 +1,243 @@
+//! Imports an existing SPL stake pool from an exported TOML file. Built with
+//! the `migrate` feature:
+//!
+//! ```bash
+//! cargo run --features migrate --bin migrate_spl_pool -- migration.toml
+//! ```
+//!
+//! The export lists the source pool's stake accounts and its depositors'
+//! pool token balances, see `migration.example.toml`. Stake accounts whose
+//! staker and withdrawer are still the admin are first authorized to the
+//! config PDA. The tool then sends `OpenMigration`, one `ImportStakeAccount`
+//! per stake account and one `ImportBalance` per depositor. It skips what an
+//! earlier run already did, so an interrupted migration is resumed by
+//! running it again with the same export.
+
+use std::{env, fs, str::FromStr};
+
+use serde::Deserialize;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signer},
+    transaction::Transaction,
+};
+
+use solana_liquid_staking::{
+    ids::{
+        ASSOCIATED_TOKEN_PROGRAM_ID, STAKE_HISTORY_SYSVAR_ID, STAKE_PROGRAM_ID, TOKEN_PROGRAM_ID,
+    },
+    state::Config,
+};
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MigrationExport {
+    #[serde(default = "default_rpc_url")]
+    rpc_url: String,
+    /// Keypair file of the pool's admin, which pays every transaction.
+    admin_keypair: String,
+    /// The source pool's pool token supply when the export was taken.
+    source_pool_tokens: u64,
+    stake_accounts: Vec<String>,
+    #[serde(default)]
+    balances: Vec<BalanceExport>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BalanceExport {
+    owner: String,
+    pool_tokens: u64,
+}
+
+fn default_rpc_url() -> String {
+    "http://127.0.0.1:8899".to_string()
+}
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+fn main() -> Result<()> {
+    let path = env::args()
+        .nth(1)
+        .ok_or("usage: migrate_spl_pool <export.toml>")?;
+    let export: MigrationExport = toml::from_str(&fs::read_to_string(&path)?)?;
+    let client = RpcClient::new(export.rpc_url);
+    let admin = read_keypair_file(&export.admin_keypair)?;
+
+    let program_id = Pubkey::new_from_array(solana_liquid_staking::ID);
+    let stake_program = Pubkey::new_from_array(STAKE_PROGRAM_ID);
+    let pda = |seed: &[u8]| Pubkey::find_program_address(&[seed], &program_id).0;
+    let config_pda = pda(b"config");
+    let migration_pda = pda(b"migration");
+
+    let config_data = client.get_account_data(&config_pda)?;
+    let mint = Pubkey::new_from_array(
+        Config::load(&config_data)
+            .map_err(|_| "not a pool config")?
+            .lst_mint,
+    );
+    let migration_vault = ata(&migration_pda, &mint);
+
+    if client.get_account(&migration_pda).is_err() {
+        let accounts = &[
+            AccountMeta::new(admin.pubkey(), true),
+            AccountMeta::new_readonly(config_pda, false),
+            AccountMeta::new(migration_pda, false),
+            AccountMeta::new(migration_vault, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(spl_token_id(), false),
+            AccountMeta::new_readonly(associated_token_program_id(), false),
+            AccountMeta::new_readonly(system_program_id(), false),
+        ];
+        let data = export.source_pool_tokens.to_le_bytes();
+        send(&client, &[program_ix(70, &data, accounts)], &admin)?;
+        println!(
+            "Opened the migration for {} pool tokens",
+            export.source_pool_tokens
+        );
+    }
+
+    for stake_account in &export.stake_accounts {
+        let stake_account = Pubkey::from_str(stake_account)?;
+        let Ok(account) = client.get_account(&stake_account) else {
+            println!("{stake_account} is gone, assuming it was imported");
+            continue;
+        };
+
+        let mut authorize = Vec::new();
+        for (kind, authority) in [(0u32, &account.data[12..44]), (1, &account.data[44..76])] {
+            if authority == config_pda.as_ref() {
+                continue;
+            }
+            if authority != admin.pubkey().as_ref() {
+                return Err(format!(
+                    "{stake_account} is not authorized to the admin or the config PDA"
+                )
+                .into());
+            }
+            authorize.push(authorize_ix(
+                &stake_account,
+                &admin.pubkey(),
+                &config_pda,
+                kind,
+            ));
+        }
+        if !authorize.is_empty() {
+            send(&client, &authorize, &admin)?;
+        }
+
+        let accounts = &[
+            AccountMeta::new_readonly(admin.pubkey(), true),
+            AccountMeta::new_readonly(config_pda, false),
+            AccountMeta::new(migration_pda, false),
+            AccountMeta::new(migration_vault, false),
+            AccountMeta::new(stake_account, false),
+            AccountMeta::new(pda(b"stake_main"), false),
+            AccountMeta::new(pda(b"stake_reserve"), false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new(pda(b"pool_state"), false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(stake_history_id(), false),
+            AccountMeta::new_readonly(stake_program, false),
+            AccountMeta::new_readonly(spl_token_id(), false),
+        ];
+        send(&client, &[program_ix(71, &[], accounts)], &admin)?;
+        println!("Imported {stake_account} ({} lamports)", account.lamports);
+    }
+
+    for balance in &export.balances {
+        let owner = Pubkey::from_str(&balance.owner)?;
+        let imported_balance_pda =
+            Pubkey::find_program_address(&[b"imported_balance", owner.as_ref()], &program_id).0;
+        if client.get_account(&imported_balance_pda).is_ok() {
+            continue;
+        }
+
+        let accounts = &[
+            AccountMeta::new(admin.pubkey(), true),
+            AccountMeta::new_readonly(config_pda, false),
+            AccountMeta::new(migration_pda, false),
+            AccountMeta::new(migration_vault, false),
+            AccountMeta::new_readonly(owner, false),
+            AccountMeta::new(ata(&owner, &mint), false),
+            AccountMeta::new(imported_balance_pda, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(spl_token_id(), false),
+            AccountMeta::new_readonly(associated_token_program_id(), false),
+            AccountMeta::new_readonly(system_program_id(), false),
+        ];
+        let data = balance.pool_tokens.to_le_bytes();
+        send(&client, &[program_ix(72, &data, accounts)], &admin)?;
+        println!("Imported {} pool tokens of {owner}", balance.pool_tokens);
+    }
+
+    Ok(())
+}
+
+fn send(client: &RpcClient, instructions: &[Instruction], payer: &Keypair) -> Result<()> {
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &[payer],
+        client.get_latest_blockhash()?,
+    );
+    client.send_and_confirm_transaction(&tx)?;
+    Ok(())
+}
+
+fn program_ix(discriminator: u8, data: &[u8], accounts: &[AccountMeta]) -> Instruction {
+    Instruction {
+        program_id: Pubkey::new_from_array(solana_liquid_staking::ID),
+        data: [&[discriminator], data].concat(),
+        accounts: accounts.to_vec(),
+    }
+}
+
+/// Stake `Authorize` moving the staker (`kind` 0) or withdrawer (1) of
+/// `stake_account` from `authority` to `new_authority`.
+fn authorize_ix(
+    stake_account: &Pubkey,
+    authority: &Pubkey,
+    new_authority: &Pubkey,
+    kind: u32,
+) -> Instruction {
+    let mut data = 1u32.to_le_bytes().to_vec();
+    data.extend_from_slice(new_authority.as_ref());
+    data.extend_from_slice(&kind.to_le_bytes());
+
+    Instruction {
+        program_id: Pubkey::new_from_array(STAKE_PROGRAM_ID),
+        data,
+        accounts: vec![
+            AccountMeta::new(*stake_account, false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+    }
+}
+
+fn ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), spl_token_id().as_ref(), mint.as_ref()],
+        &associated_token_program_id(),
+    )
+    .0
+}
+
+fn system_program_id() -> Pubkey {
+    Pubkey::default()
+}
+
+fn spl_token_id() -> Pubkey {
+    Pubkey::new_from_array(TOKEN_PROGRAM_ID)
+}
+
+fn associated_token_program_id() -> Pubkey {
+    Pubkey::new_from_array(ASSOCIATED_TOKEN_PROGRAM_ID)
+}
+
+fn stake_history_id() -> Pubkey {
+    Pubkey::new_from_array(STAKE_HISTORY_SYSVAR_ID)
+}
@@ -0,0 +1,23 @@
+//! What the program binary was built from, recorded by `build.rs`.
+//!
+//! `GetBuildInfo` returns these fields so integrators can match a deployed
+//! program against a verifiable build before routing funds to it. A client
+//! built from the same checkout with the same features sees the same crate
+//! version, commit and features here; the toolchain is the one that compiled
+//! the binary, `cargo build-sbf`'s for the deployed program.
+
+/// Crate version from `Cargo.toml`.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Full git commit hash, suffixed with `-dirty` when the tree had uncommitted
+/// changes, or `unknown` when built outside a git checkout.
+pub const GIT_COMMIT: &str = env!("BUILD_GIT_COMMIT");
+
+/// Enabled cargo features, sorted and comma-separated.
+pub const FEATURES: &str = env!("BUILD_FEATURES");
+
+/// `rustc --version` of the compiler that built the binary.
+pub const TOOLCHAIN: &str = env!("BUILD_TOOLCHAIN");
+
+/// The fields in `GetBuildInfo` return data order.
+pub const FIELDS: [&str; 4] = [VERSION, GIT_COMMIT, FEATURES, TOOLCHAIN];
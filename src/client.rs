@@ -0,0 +1,206 @@
+//! Off-chain helpers for bots and UIs, compiled with the `client` feature.
+//!
+//! Maps the `ProgramError::Custom(n)` codes the program returns back to a
+//! typed `PinocchioError`, and gives every error a stable string code that
+//! does not change when messages are reworded. `rate_cache` quotes deposits
+//! and instant unstakes from a cached pool snapshot, `deploy` verifies a
+//! deployment under either loader against the local build, and `rent` prices
+//! the accounts the program creates.
+
+pub mod deploy;
+pub mod rate_cache;
+pub mod rent;
+
+pub use crate::errors::PinocchioError;
+
+/// Lists every `PinocchioError` variant in discriminant order. `code`
+/// matches exhaustively, so a variant added to the enum but not here fails
+/// to compile.
+macro_rules! client_errors {
+    ($($variant:ident),* $(,)?) => {
+        impl PinocchioError {
+            /// Every error, indexed by its custom error code.
+            pub const ALL: &'static [PinocchioError] = &[$(PinocchioError::$variant),*];
+
+            /// Stable string code, the variant name.
+            pub fn code(&self) -> &'static str {
+                match self {
+                    $(PinocchioError::$variant => stringify!($variant)),*
+                }
+            }
+        }
+    };
+}
+
+client_errors!(
+    NotRentExempt,
+    NotSigner,
+    InvalidOwner,
+    InvalidAccountData,
+    InvalidAddress,
+    InvalidSystemProgram,
+    InvalidTokenProgram,
+    InvalidStakeProgram,
+    InvalidAssociatedTokenProgram,
+    InvalidValidatorVoteAccount,
+    InvalidConfigPda,
+    InvalidStakeAccountMain,
+    InvalidStakeAccountReserve,
+    InvalidLstMint,
+    InvalidDepositorAta,
+    InvalidWithdrawerAta,
+    InvalidSplitAccountPda,
+    DepositBelowMinimum,
+    SplitBelowMinimum,
+    ReserveAlreadyInitialized,
+    ReserveNotStaked,
+    InsufficientLstBalance,
+    InvalidValidatorVoteKey,
+    RateAlreadyRecorded,
+    InvalidRateHistoryPda,
+    MainBelowMinimum,
+    InvalidSplitRecordPda,
+    NotLastHolder,
+    ReserveStakeActive,
+    PoolClosed,
+    TrancheNotReady,
+    NothingScheduled,
+    NotAdmin,
+    InvalidTrustedCallersPda,
+    UntrustedCaller,
+    TrustedCallersFull,
+    ReserveActivating,
+    InvalidLiquidityPoolPda,
+    InvalidLpMint,
+    InvalidLstVault,
+    InvalidProviderAta,
+    ZeroLiquidity,
+    InsufficientLiquidity,
+    SlippageExceeded,
+    InvalidFeeParams,
+    NothingToRefill,
+    InvalidBufferStakePda,
+    BufferStakeInUse,
+    ReserveDelegated,
+    NotBufferStakeFunder,
+    SwapExceedsImbalance,
+    InvalidValidatorBondPda,
+    InvalidValidatorListPda,
+    NotValidatorOperator,
+    BondBelowMinimum,
+    ValidatorListFull,
+    InvalidValidatorStatus,
+    ValidatorBondLocked,
+    SlashExceedsBond,
+    ValidatorStakeCapExceeded,
+    InvalidValidatorStakeCap,
+    ValidatorNotInList,
+    InvalidEpochTasksPda,
+    PrerequisiteCrankMissing,
+    InvalidDepositKeyPda,
+    DuplicateDeposit,
+    DepositKeyNotExpired,
+    InvalidPoolStatePda,
+    InvalidTreasuryAta,
+    InvalidVestingPda,
+    InvalidVestingVault,
+    InvalidVestingSchedule,
+    NothingVested,
+    InvalidRentPayer,
+    InvalidDepositSessionPda,
+    DepositSessionExpired,
+    DepositSessionBudgetExceeded,
+    InvalidDcaSchedulePda,
+    DcaNotDue,
+    DcaBudgetExhausted,
+    UnexpectedInitializer,
+    BatchTooLarge,
+    InvalidPoolStatusTransition,
+    PoolPaused,
+    InvalidFeeVaultPda,
+    NoFeesToBuyBack,
+    DepositCapExceeded,
+    InvalidQueuedDepositPda,
+    QueuedDepositNotReady,
+    WrapperNotApproved,
+    InvalidWrapperVault,
+    ReserveNotAdopted,
+    InvalidChangelogPda,
+    InstructionDisabled,
+    InvalidReferrerPda,
+    NoReferralFees,
+    InvalidQuote,
+    QuoteExpired,
+    QuoteOutOfBounds,
+    InvalidUnstakeOrderPda,
+    OrderRateNotMet,
+    InvalidValidatorHealthPda,
+    InvalidVoteEscrowPda,
+    InvalidLockDuration,
+    LockNotExpired,
+    InvalidSplitHandoffPda,
+    HandoffTimelocked,
+    NotSplitOwnerOrAdmin,
+    SplitAlreadyPending,
+    SupplyMismatch,
+    InvalidMigrationPda,
+    MigrationSealed,
+    StakeNotImportable,
+    MigrationExceedsSource,
+    InvalidSysvar,
+    InvalidRewardPayoutPda,
+    InvalidPayoutPositionPda,
+    NoPayoutToClaim,
+    PayoutAlreadyCranked,
+    InsufficientPayoutShares,
+    PayoutNotCranked,
+    PoolBootstrapping,
+    InvalidValidatorStakePda,
+    NothingToDelegate,
+    ValidatorStakeOutstanding,
+    ValidatorAlreadyListed,
+    ValidatorStakeCoolingDown,
+    RebalanceInProgress,
+    NothingToRebalance,
+    ValidatorStakeNotDelegated,
+    MainNotRedelegating,
+    MainCoolingDown,
+    NotPendingAdmin,
+    ManagementFeeAlreadyCharged,
+    MinLstOutNotMet,
+    StakeNotDepositable,
+    SplitAlreadyClaimed,
+    InvalidSplitCounterPda,
+    RateAlreadyUpdated,
+    PoolCapExceeded,
+    MissingDepositAuthority,
+    InvalidReferrerAta,
+    ValidatorStakeStale,
+    QuoteAmountExceeded,
+    QuoteAlreadyUsed,
+    InvalidUsedQuotePda,
+    QuoteNotExpired,
+    ReserveRotationPending,
+    InvalidOutgoingReservePda,
+    InvalidUserStatsPda,
+);
+
+impl TryFrom<u32> for PinocchioError {
+    type Error = u32;
+
+    /// Returns the error for a `ProgramError::Custom` code, or the code back
+    /// if it is not one of the program's.
+    fn try_from(code: u32) -> Result<Self, Self::Error> {
+        PinocchioError::ALL.get(code as usize).cloned().ok_or(code)
+    }
+}
+
+impl PinocchioError {
+    /// Looks up an error by its stable string code.
+    pub fn from_code(code: &str) -> Option<Self> {
+        PinocchioError::ALL
+            .iter()
+            .find(|error| error.code() == code)
+            .cloned()
+    }
+}
@@ -0,0 +1,169 @@
+//! Deployment checks under loader-v3 and loader-v4.
+//!
+//! The program deploys with the upgradeable loader (loader-v3) everywhere,
+//! and with loader-v4 on clusters that enable it. `verify_deployment` reads
+//! the program through an `AccountFetcher` under either loader and checks the
+//! deployed ELF byte for byte against a local build, so operators can confirm
+//! what actually runs and who may still upgrade it.
+
+use pinocchio::pubkey::Pubkey;
+
+use super::rate_cache::AccountFetcher;
+
+/// `BPFLoaderUpgradeab1e11111111111111111111111`
+pub const LOADER_V3_ID: Pubkey = [
+    0x02, 0xa8, 0xf6, 0x91, 0x4e, 0x88, 0xa1, 0xb0, 0xe2, 0x10, 0x15, 0x3e, 0xf7, 0x63, 0xae, 0x2b,
+    0x00, 0xc2, 0xb9, 0x3d, 0x16, 0xc1, 0x24, 0xd2, 0xc0, 0x53, 0x7a, 0x10, 0x04, 0x80, 0x00, 0x00,
+];
+
+/// `LoaderV411111111111111111111111111111111111`
+pub const LOADER_V4_ID: Pubkey = [
+    0x05, 0x12, 0xb4, 0x11, 0x51, 0x51, 0xe3, 0x7a, 0xad, 0x0a, 0x8b, 0xc5, 0xd3, 0x88, 0x2e, 0x7b,
+    0x7f, 0xda, 0x4c, 0xf3, 0xd2, 0xc0, 0x28, 0xc8, 0xcf, 0x83, 0x36, 0x18, 0x00, 0x00, 0x00, 0x00,
+];
+
+const LOADER_V3_PROGRAM_TAG: u32 = 2;
+const LOADER_V3_PROGRAM_DATA_TAG: u32 = 3;
+/// Tag (4), slot (8), then the upgrade authority as an option (1 + 32).
+const LOADER_V3_PROGRAM_DATA_HEADER: usize = 45;
+
+const LOADER_V4_STATUS_RETRACTED: u64 = 0;
+const LOADER_V4_STATUS_FINALIZED: u64 = 2;
+/// Slot (8), authority or next version (32), status (8).
+const LOADER_V4_HEADER: usize = 48;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Loader {
+    /// The upgradeable loader: a program account pointing at a program data
+    /// account that holds the ELF.
+    V3,
+    /// Loader-v4: the program account holds the ELF itself.
+    V4,
+}
+
+impl Loader {
+    /// The loader's program id, the owner of programs it deployed.
+    pub fn id(self) -> Pubkey {
+        match self {
+            Loader::V3 => LOADER_V3_ID,
+            Loader::V4 => LOADER_V4_ID,
+        }
+    }
+}
+
+/// A program deployment whose ELF matches the local build.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Deployment {
+    pub loader: Loader,
+    /// Slot the program was last deployed or upgraded in.
+    pub slot: u64,
+    /// Key that may upgrade the program, `None` once it is immutable.
+    pub upgrade_authority: Option<Pubkey>,
+    /// Account holding the ELF: the program data account under loader-v3,
+    /// the program account itself under loader-v4.
+    pub elf_account: Pubkey,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum VerifyError<E> {
+    /// The fetcher failed.
+    Fetcher(E),
+    /// A program account is missing or does not decode for the loader.
+    InvalidAccount(Pubkey),
+    /// The loader-v4 program is retracted, so it cannot be invoked.
+    NotDeployed,
+    /// The deployed ELF differs from the local build.
+    ElfMismatch,
+}
+
+/// Reads `program_id` as deployed by `loader` and checks its ELF against
+/// `local_elf`, usually `target/deploy/solana_liquid_staking.so`. Loader-v3
+/// may allocate more space than the ELF needs, so zero bytes after it are
+/// accepted.
+pub fn verify_deployment<F: AccountFetcher>(
+    fetcher: &mut F,
+    loader: Loader,
+    program_id: &Pubkey,
+    local_elf: &[u8],
+) -> Result<Deployment, VerifyError<F::Error>> {
+    let mut program_data = fetch(fetcher, program_id)?;
+
+    let (deployment, elf) = match loader {
+        Loader::V3 => {
+            let elf_account: Pubkey = match program_data.get(..36) {
+                Some(header) if read_u32(header, 0) == LOADER_V3_PROGRAM_TAG => {
+                    header[4..36].try_into().unwrap()
+                }
+                _ => return Err(VerifyError::InvalidAccount(*program_id)),
+            };
+
+            let mut elf_data = fetch(fetcher, &elf_account)?;
+            let header = match elf_data.get(..LOADER_V3_PROGRAM_DATA_HEADER) {
+                Some(header) if read_u32(header, 0) == LOADER_V3_PROGRAM_DATA_TAG => header,
+                _ => return Err(VerifyError::InvalidAccount(elf_account)),
+            };
+            let upgrade_authority = match header[12] {
+                0 => None,
+                1 => Some(header[13..45].try_into().unwrap()),
+                _ => return Err(VerifyError::InvalidAccount(elf_account)),
+            };
+            let deployment = Deployment {
+                loader,
+                slot: read_u64(header, 4),
+                upgrade_authority,
+                elf_account,
+            };
+
+            (
+                deployment,
+                elf_data.split_off(LOADER_V3_PROGRAM_DATA_HEADER),
+            )
+        }
+        Loader::V4 => {
+            let header = program_data
+                .get(..LOADER_V4_HEADER)
+                .ok_or(VerifyError::InvalidAccount(*program_id))?;
+            let upgrade_authority = match read_u64(header, 40) {
+                LOADER_V4_STATUS_RETRACTED => return Err(VerifyError::NotDeployed),
+                LOADER_V4_STATUS_FINALIZED => None,
+                _ => Some(header[8..40].try_into().unwrap()),
+            };
+            let deployment = Deployment {
+                loader,
+                slot: read_u64(header, 0),
+                upgrade_authority,
+                elf_account: *program_id,
+            };
+
+            (deployment, program_data.split_off(LOADER_V4_HEADER))
+        }
+    };
+
+    let matches = elf.len() >= local_elf.len()
+        && elf[..local_elf.len()] == *local_elf
+        && elf[local_elf.len()..].iter().all(|byte| *byte == 0);
+    if local_elf.is_empty() || !matches {
+        return Err(VerifyError::ElfMismatch);
+    }
+
+    Ok(deployment)
+}
+
+fn fetch<F: AccountFetcher>(
+    fetcher: &mut F,
+    address: &Pubkey,
+) -> Result<Vec<u8>, VerifyError<F::Error>> {
+    fetcher
+        .account(address)
+        .map_err(VerifyError::Fetcher)?
+        .map(|(_, data)| data)
+        .ok_or(VerifyError::InvalidAccount(*address))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
@@ -0,0 +1,246 @@
+//! Cached pool state for quoting off-chain.
+//!
+//! `PoolStateCache` reads the config, pool state, stake accounts, LST mint,
+//! liquidity pool and clock sysvar through an `AccountFetcher`, usually an RPC client,
+//! and keeps the decoded values as a `PoolSnapshot`. Quotes run against the snapshot with
+//! the program's own math until it is more than `max_staleness_slots` old,
+//! so integrators quoting often fetch once per staleness window instead of
+//! once per quote.
+
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    ids::CLOCK_SYSVAR_ID,
+    instructions::helpers::SOL_DECIMALS,
+    math::{deposit_fee_lst, lamports_to_lst, lst_to_lamports, unstake_lamports_out},
+    stake_state::StakeState,
+    state::{Config, LiquidityPool, PoolState},
+};
+
+/// Offset of `supply` in an SPL token mint.
+const MINT_SUPPLY_OFFSET: usize = 36;
+/// Offset of `decimals` in an SPL token mint.
+const MINT_DECIMALS_OFFSET: usize = 44;
+/// Offset of `epoch` in the clock sysvar.
+const CLOCK_EPOCH_OFFSET: usize = 16;
+
+/// A quoted amount in raw base units, with the decimals to display it in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Amount {
+    pub base_units: u64,
+    pub decimals: u8,
+}
+
+/// Source of on-chain state for `PoolStateCache`.
+pub trait AccountFetcher {
+    type Error;
+
+    /// Returns the current slot.
+    fn slot(&mut self) -> Result<u64, Self::Error>;
+
+    /// Returns an account's lamports and data, or `None` if it does not exist.
+    fn account(&mut self, address: &Pubkey) -> Result<Option<(u64, Vec<u8>)>, Self::Error>;
+
+    /// Returns the rent-exempt minimum for `data_len` bytes.
+    fn minimum_balance(&mut self, data_len: usize) -> Result<u64, Self::Error>;
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FetchError<E> {
+    /// The fetcher failed.
+    Fetcher(E),
+    /// An account the pool needs is missing or does not decode.
+    InvalidAccount(Pubkey),
+}
+
+/// Pool values needed to quote, as of `slot`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoolSnapshot {
+    pub slot: u64,
+    pub lst_supply: u64,
+    /// Decimals of the LST mint.
+    pub lst_decimals: u8,
+    /// The pool state's managed lamports, or the epoch's cached rate.
+    pub pool_lamports: u64,
+    /// Instant-unstake buffer, zero without a liquidity pool.
+    pub sol_reserves: u64,
+    pub unstake_fee_min_bps: u16,
+    pub unstake_fee_max_bps: u16,
+    pub liquidity_target_lamports: u64,
+    pub deposit_fee_bps: u16,
+}
+
+impl PoolSnapshot {
+    /// LST a `Deposit` of `lamports` mints to the depositor, after the
+    /// deposit fee. Referred deposits also pay the referral fee, which is not
+    /// taken off here.
+    pub fn quote_deposit(&self, lamports: u64) -> Result<Amount, ProgramError> {
+        let lst_minted = lamports_to_lst(lamports, self.lst_supply, self.pool_lamports)?;
+        Ok(Amount {
+            base_units: lst_minted - deposit_fee_lst(lst_minted, self.deposit_fee_bps),
+            decimals: self.lst_decimals,
+        })
+    }
+
+    /// Lamports paid out by a `LiquidUnstake` of `lst_amount` base units,
+    /// after the fee.
+    pub fn quote_unstake(&self, lst_amount: u64) -> Result<Amount, ProgramError> {
+        Ok(Amount {
+            base_units: unstake_lamports_out(
+                lst_to_lamports(lst_amount, self.lst_supply, self.pool_lamports)?,
+                self.sol_reserves,
+                self.unstake_fee_min_bps,
+                self.unstake_fee_max_bps,
+                self.liquidity_target_lamports,
+            )?,
+            decimals: SOL_DECIMALS,
+        })
+    }
+}
+
+pub struct PoolStateCache {
+    config_address: Pubkey,
+    pool_state_address: Pubkey,
+    liquidity_pool_address: Pubkey,
+    max_staleness_slots: u64,
+    snapshot: Option<PoolSnapshot>,
+}
+
+impl PoolStateCache {
+    pub fn new(
+        config_address: Pubkey,
+        pool_state_address: Pubkey,
+        liquidity_pool_address: Pubkey,
+        max_staleness_slots: u64,
+    ) -> Self {
+        Self {
+            config_address,
+            pool_state_address,
+            liquidity_pool_address,
+            max_staleness_slots,
+            snapshot: None,
+        }
+    }
+
+    /// The last fetched snapshot, however old.
+    pub fn cached(&self) -> Option<&PoolSnapshot> {
+        self.snapshot.as_ref()
+    }
+
+    /// Whether the snapshot is missing or more than `max_staleness_slots`
+    /// behind `current_slot`.
+    pub fn is_stale(&self, current_slot: u64) -> bool {
+        match &self.snapshot {
+            Some(snapshot) => current_slot.saturating_sub(snapshot.slot) > self.max_staleness_slots,
+            None => true,
+        }
+    }
+
+    /// Returns the snapshot, refetching it first if it is stale at
+    /// `current_slot`.
+    pub fn get<F: AccountFetcher>(
+        &mut self,
+        fetcher: &mut F,
+        current_slot: u64,
+    ) -> Result<&PoolSnapshot, FetchError<F::Error>> {
+        if self.is_stale(current_slot) {
+            self.refresh(fetcher)?;
+        }
+        Ok(self.snapshot.as_ref().unwrap())
+    }
+
+    /// Fetches a new snapshot. Its slot is read before the accounts, so the
+    /// snapshot is never newer than it claims.
+    pub fn refresh<F: AccountFetcher>(
+        &mut self,
+        fetcher: &mut F,
+    ) -> Result<&PoolSnapshot, FetchError<F::Error>> {
+        let slot = fetcher.slot().map_err(FetchError::Fetcher)?;
+
+        let (_, config_data) = fetch_existing(fetcher, &self.config_address)?;
+        let config = Config::load(&config_data)
+            .map_err(|_| FetchError::InvalidAccount(self.config_address))?;
+        let lst_mint = config.lst_mint;
+        let stake_account_main = config.stake_account_main;
+        let stake_account_reserve = config.stake_account_reserve;
+        let unstake_fee_min_bps = config.unstake_fee_min_bps;
+        let unstake_fee_max_bps = config.unstake_fee_max_bps;
+        let liquidity_target_lamports = config.liquidity_target_lamports;
+        let deposit_fee_bps = config.deposit_fee_bps;
+        let validator_stake_lamports = config.validator_stake_lamports;
+
+        // The program prices against the rate `CrankUpdateRate` cached this
+        // epoch, see `helpers::epoch_rate`.
+        let (_, clock_data) = fetch_existing(fetcher, &CLOCK_SYSVAR_ID)?;
+        let epoch = clock_data
+            .get(CLOCK_EPOCH_OFFSET..CLOCK_EPOCH_OFFSET + 8)
+            .ok_or(FetchError::InvalidAccount(CLOCK_SYSVAR_ID))?;
+        let cached_rate = config.cached_rate(u64::from_le_bytes(epoch.try_into().unwrap()));
+
+        let (_, mint_data) = fetch_existing(fetcher, &lst_mint)?;
+        let lst_supply = mint_data
+            .get(MINT_SUPPLY_OFFSET..MINT_SUPPLY_OFFSET + 8)
+            .ok_or(FetchError::InvalidAccount(lst_mint))?;
+        let lst_supply = u64::from_le_bytes(lst_supply.try_into().unwrap());
+        let lst_decimals = *mint_data
+            .get(MINT_DECIMALS_OFFSET)
+            .ok_or(FetchError::InvalidAccount(lst_mint))?;
+
+        let pool_stake = staked_lamports(fetcher, &stake_account_main)?
+            .checked_add(staked_lamports(fetcher, &stake_account_reserve)?)
+            .and_then(|lamports| lamports.checked_add(validator_stake_lamports))
+            .ok_or(FetchError::InvalidAccount(stake_account_reserve))?;
+        let (_, pool_state_data) = fetch_existing(fetcher, &self.pool_state_address)?;
+        let pool_lamports = PoolState::load(&pool_state_data)
+            .and_then(|pool_state| pool_state.managed_lamports(pool_stake))
+            .map_err(|_| FetchError::InvalidAccount(self.pool_state_address))?;
+        let (pool_lamports, lst_supply) = cached_rate.unwrap_or((pool_lamports, lst_supply));
+
+        let sol_reserves = match fetcher
+            .account(&self.liquidity_pool_address)
+            .map_err(FetchError::Fetcher)?
+        {
+            Some((_, data)) => {
+                LiquidityPool::load(&data)
+                    .map_err(|_| FetchError::InvalidAccount(self.liquidity_pool_address))?
+                    .sol_reserves
+            }
+            None => 0,
+        };
+
+        Ok(self.snapshot.insert(PoolSnapshot {
+            slot,
+            lst_supply,
+            lst_decimals,
+            pool_lamports,
+            sol_reserves,
+            unstake_fee_min_bps,
+            unstake_fee_max_bps,
+            liquidity_target_lamports,
+            deposit_fee_bps,
+        }))
+    }
+}
+
+fn fetch_existing<F: AccountFetcher>(
+    fetcher: &mut F,
+    address: &Pubkey,
+) -> Result<(u64, Vec<u8>), FetchError<F::Error>> {
+    fetcher
+        .account(address)
+        .map_err(FetchError::Fetcher)?
+        .ok_or(FetchError::InvalidAccount(*address))
+}
+
+/// Off-chain counterpart of `helpers::staked_lamports`.
+fn staked_lamports<F: AccountFetcher>(
+    fetcher: &mut F,
+    address: &Pubkey,
+) -> Result<u64, FetchError<F::Error>> {
+    let Some((_, data)) = fetcher.account(address).map_err(FetchError::Fetcher)? else {
+        return Ok(0);
+    };
+
+    let state = StakeState::parse(&data).map_err(|_| FetchError::InvalidAccount(*address))?;
+    Ok(state.delegation().map_or(0, |delegation| delegation.stake))
+}
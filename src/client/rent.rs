@@ -0,0 +1,216 @@
+//! Account sizes and rent for everything the program creates.
+//!
+//! Every account the program creates is funded with its rent-exempt minimum
+//! by the signer that caused it, and split stake accounts also carry
+//! `LAMPORTS_PER_SOL` of funding on top. `account_cost` prices one account
+//! with the cluster's rent through an `AccountFetcher`, and `upfront_cost`
+//! sums a whole flow such as `WITHDRAW`, so UIs can show users what an
+//! unstake costs before they sign. `default_minimum_balance` prices offline
+//! with the default rent of every public cluster.
+
+use pinocchio_token::state::{Mint, TokenAccount};
+
+use super::rate_cache::AccountFetcher;
+use crate::{
+    instructions::helpers::{DEFAULT_STAKE_ACCOUNT_SPACE, LAMPORTS_PER_SOL},
+    state::{
+        Changelog, Config, DcaSchedule, DepositKey, DepositSession, EpochTasks, ImportedBalance,
+        LiquidityPool, Migration, PayoutPosition, PoolState, QueuedDeposit, RateHistory,
+        ReferrerRecord, RewardPayout, SplitCounter, SplitHandoff, SplitRecord, TrustedCallers,
+        UnstakeOrder, UsedQuote, UserStats, ValidatorBond, ValidatorHealth, ValidatorList,
+        VestingEscrow, VoteEscrow,
+    },
+};
+
+/// Bytes of account metadata that rent is charged for on top of the data.
+pub const ACCOUNT_STORAGE_OVERHEAD: usize = 128;
+
+/// Rent-exempt lamports per byte under the default rent: 3,480 lamports per
+/// byte-year over the two-year exemption threshold.
+pub const DEFAULT_LAMPORTS_PER_BYTE: u64 = 6_960;
+
+/// An account the program creates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProtocolAccount {
+    Config,
+    /// The main, reserve and outgoing reserve stake accounts.
+    PoolStakeAccount,
+    /// The stake account `CrankSplit`, `CrankSplitTranche` and `ExitPool`
+    /// split off main for a withdrawal.
+    SplitStakeAccount,
+    SplitRecord,
+    SplitCounter,
+    RateHistory,
+    TrustedCallers,
+    LiquidityPool,
+    ValidatorBond,
+    ValidatorList,
+    EpochTasks,
+    QueuedDeposit,
+    ReferrerRecord,
+    DepositKey,
+    PoolState,
+    VestingEscrow,
+    DepositSession,
+    DcaSchedule,
+    ValidatorHealth,
+    /// An unstake order ticket.
+    UnstakeOrder,
+    VoteEscrow,
+    SplitHandoff,
+    Changelog,
+    Migration,
+    ImportedBalance,
+    RewardPayout,
+    PayoutPosition,
+    UsedQuote,
+    UserStats,
+    /// The LST and LP mints.
+    Mint,
+    /// Token accounts the program creates, such as order and vesting vaults.
+    TokenAccount,
+}
+
+impl ProtocolAccount {
+    pub const ALL: &'static [ProtocolAccount] = &[
+        ProtocolAccount::Config,
+        ProtocolAccount::PoolStakeAccount,
+        ProtocolAccount::SplitStakeAccount,
+        ProtocolAccount::SplitRecord,
+        ProtocolAccount::SplitCounter,
+        ProtocolAccount::RateHistory,
+        ProtocolAccount::TrustedCallers,
+        ProtocolAccount::LiquidityPool,
+        ProtocolAccount::ValidatorBond,
+        ProtocolAccount::ValidatorList,
+        ProtocolAccount::EpochTasks,
+        ProtocolAccount::QueuedDeposit,
+        ProtocolAccount::ReferrerRecord,
+        ProtocolAccount::DepositKey,
+        ProtocolAccount::PoolState,
+        ProtocolAccount::VestingEscrow,
+        ProtocolAccount::DepositSession,
+        ProtocolAccount::DcaSchedule,
+        ProtocolAccount::ValidatorHealth,
+        ProtocolAccount::UnstakeOrder,
+        ProtocolAccount::VoteEscrow,
+        ProtocolAccount::SplitHandoff,
+        ProtocolAccount::Changelog,
+        ProtocolAccount::Migration,
+        ProtocolAccount::ImportedBalance,
+        ProtocolAccount::RewardPayout,
+        ProtocolAccount::PayoutPosition,
+        ProtocolAccount::UsedQuote,
+        ProtocolAccount::UserStats,
+        ProtocolAccount::Mint,
+        ProtocolAccount::TokenAccount,
+    ];
+
+    /// Data length the account is created with.
+    pub fn space(self) -> usize {
+        match self {
+            ProtocolAccount::Config => Config::LEN,
+            ProtocolAccount::PoolStakeAccount | ProtocolAccount::SplitStakeAccount => {
+                DEFAULT_STAKE_ACCOUNT_SPACE
+            }
+            ProtocolAccount::SplitRecord => SplitRecord::LEN,
+            ProtocolAccount::SplitCounter => SplitCounter::LEN,
+            ProtocolAccount::RateHistory => RateHistory::LEN,
+            ProtocolAccount::TrustedCallers => TrustedCallers::LEN,
+            ProtocolAccount::LiquidityPool => LiquidityPool::LEN,
+            ProtocolAccount::ValidatorBond => ValidatorBond::LEN,
+            ProtocolAccount::ValidatorList => ValidatorList::LEN,
+            ProtocolAccount::EpochTasks => EpochTasks::LEN,
+            ProtocolAccount::QueuedDeposit => QueuedDeposit::LEN,
+            ProtocolAccount::ReferrerRecord => ReferrerRecord::LEN,
+            ProtocolAccount::DepositKey => DepositKey::LEN,
+            ProtocolAccount::PoolState => PoolState::LEN,
+            ProtocolAccount::VestingEscrow => VestingEscrow::LEN,
+            ProtocolAccount::DepositSession => DepositSession::LEN,
+            ProtocolAccount::DcaSchedule => DcaSchedule::LEN,
+            ProtocolAccount::ValidatorHealth => ValidatorHealth::LEN,
+            ProtocolAccount::UnstakeOrder => UnstakeOrder::LEN,
+            ProtocolAccount::VoteEscrow => VoteEscrow::LEN,
+            ProtocolAccount::SplitHandoff => SplitHandoff::LEN,
+            ProtocolAccount::Changelog => Changelog::LEN,
+            ProtocolAccount::Migration => Migration::LEN,
+            ProtocolAccount::ImportedBalance => ImportedBalance::LEN,
+            ProtocolAccount::RewardPayout => RewardPayout::LEN,
+            ProtocolAccount::PayoutPosition => PayoutPosition::LEN,
+            ProtocolAccount::UsedQuote => UsedQuote::LEN,
+            ProtocolAccount::UserStats => UserStats::LEN,
+            ProtocolAccount::Mint => Mint::LEN,
+            ProtocolAccount::TokenAccount => TokenAccount::LEN,
+        }
+    }
+
+    /// Lamports the payer funds beyond rent. Split stake accounts are
+    /// created with `LAMPORTS_PER_SOL` so the split leaves them delegable.
+    pub fn funding(self) -> u64 {
+        match self {
+            ProtocolAccount::SplitStakeAccount => LAMPORTS_PER_SOL,
+            _ => 0,
+        }
+    }
+}
+
+/// Accounts `CrankSplit` creates for a withdrawal, paid by its payer.
+pub const WITHDRAW: &[ProtocolAccount] = &[
+    ProtocolAccount::SplitStakeAccount,
+    ProtocolAccount::SplitRecord,
+];
+
+/// Accounts `CreateUnstakeOrder` creates, paid by the order's owner.
+pub const CREATE_UNSTAKE_ORDER: &[ProtocolAccount] =
+    &[ProtocolAccount::UnstakeOrder, ProtocolAccount::TokenAccount];
+
+/// Accounts `ProposeSplitHandoff` creates, paid by the admin.
+pub const PROPOSE_SPLIT_HANDOFF: &[ProtocolAccount] = &[ProtocolAccount::SplitHandoff];
+
+/// What creating one account costs its payer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AccountCost {
+    pub account: ProtocolAccount,
+    pub space: usize,
+    /// Rent-exempt minimum for `space`.
+    pub rent: u64,
+    /// Lamports funded beyond rent, see `ProtocolAccount::funding`.
+    pub funding: u64,
+}
+
+impl AccountCost {
+    pub fn total(&self) -> u64 {
+        self.rent.saturating_add(self.funding)
+    }
+}
+
+/// Rent-exempt minimum for `data_len` bytes under the default rent.
+pub fn default_minimum_balance(data_len: usize) -> u64 {
+    (ACCOUNT_STORAGE_OVERHEAD + data_len) as u64 * DEFAULT_LAMPORTS_PER_BYTE
+}
+
+/// Prices `account` with the rent `fetcher` reports.
+pub fn account_cost<F: AccountFetcher>(
+    fetcher: &mut F,
+    account: ProtocolAccount,
+) -> Result<AccountCost, F::Error> {
+    let space = account.space();
+
+    Ok(AccountCost {
+        account,
+        space,
+        rent: fetcher.minimum_balance(space)?,
+        funding: account.funding(),
+    })
+}
+
+/// Total lamports the payer needs to create all of `accounts`, for example
+/// `WITHDRAW`.
+pub fn upfront_cost<F: AccountFetcher>(
+    fetcher: &mut F,
+    accounts: &[ProtocolAccount],
+) -> Result<u64, F::Error> {
+    accounts.iter().try_fold(0u64, |total, account| {
+        Ok(total.saturating_add(account_cost(fetcher, *account)?.total()))
+    })
+}
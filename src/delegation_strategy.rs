@@ -0,0 +1,130 @@
+//! Target stake computation for the validator set.
+//!
+//! A `DelegationStrategy` splits the pool's lamports across the validator
+//! list. The config's `delegation_strategy` byte selects the implementation,
+//! so the cranks that move stake only ever call `DelegationStrategyKind::targets`.
+
+use pinocchio::program_error::ProgramError;
+
+use crate::state::ValidatorEntry;
+
+pub trait DelegationStrategy {
+    /// Fills `targets[i]` with the lamports `validators[i]` should hold out of
+    /// `total_lamports`. Targets always sum to `total_lamports` when there is
+    /// at least one validator.
+    fn targets(&self, validators: &[ValidatorEntry], total_lamports: u64, targets: &mut [u64]);
+}
+
+/// Every validator gets the same stake.
+pub struct EvenSplit;
+
+/// Stake proportional to each validator's admin-assigned weight.
+pub struct WeightBased;
+
+/// Stake proportional to each validator's performance score.
+pub struct ScoreBased;
+
+impl DelegationStrategy for EvenSplit {
+    fn targets(&self, validators: &[ValidatorEntry], total_lamports: u64, targets: &mut [u64]) {
+        split_proportionally(validators.len(), |_| 1, total_lamports, targets);
+    }
+}
+
+impl DelegationStrategy for WeightBased {
+    fn targets(&self, validators: &[ValidatorEntry], total_lamports: u64, targets: &mut [u64]) {
+        split_proportionally(
+            validators.len(),
+            |index| validators[index].weight as u64,
+            total_lamports,
+            targets,
+        );
+    }
+}
+
+impl DelegationStrategy for ScoreBased {
+    fn targets(&self, validators: &[ValidatorEntry], total_lamports: u64, targets: &mut [u64]) {
+        split_proportionally(
+            validators.len(),
+            |index| validators[index].score as u64,
+            total_lamports,
+            targets,
+        );
+    }
+}
+
+/// Splits `total_lamports` across `count` validators in proportion to
+/// `share(i)`, flooring each target and handing the rounding remainder out
+/// one lamport at a time from the first validator with a share. Falls back to
+/// an even split when every share is zero, so stake is never left unassigned.
+fn split_proportionally(
+    count: usize,
+    share: impl Fn(usize) -> u64,
+    total_lamports: u64,
+    targets: &mut [u64],
+) {
+    let targets = &mut targets[..count];
+    targets.fill(0);
+
+    if count == 0 {
+        return;
+    }
+
+    let mut total_shares: u128 = (0..count).map(|index| share(index) as u128).sum();
+    let even = total_shares == 0;
+    if even {
+        total_shares = count as u128;
+    }
+    let share = |index: usize| if even { 1 } else { share(index) };
+
+    let mut assigned = 0u64;
+    for (index, target) in targets.iter_mut().enumerate() {
+        *target = (total_lamports as u128 * share(index) as u128 / total_shares) as u64;
+        assigned += *target;
+    }
+
+    let mut remainder = total_lamports - assigned;
+    for (index, target) in targets.iter_mut().enumerate() {
+        if remainder == 0 {
+            break;
+        }
+        if share(index) > 0 {
+            *target += 1;
+            remainder -= 1;
+        }
+    }
+}
+
+/// Strategy selected by the config's `delegation_strategy` byte.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DelegationStrategyKind {
+    EvenSplit = 0,
+    WeightBased = 1,
+    ScoreBased = 2,
+}
+
+impl TryFrom<u8> for DelegationStrategyKind {
+    type Error = ProgramError;
+
+    fn try_from(kind: u8) -> Result<Self, Self::Error> {
+        match kind {
+            0 => Ok(Self::EvenSplit),
+            1 => Ok(Self::WeightBased),
+            2 => Ok(Self::ScoreBased),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+impl DelegationStrategyKind {
+    pub fn strategy(&self) -> &'static dyn DelegationStrategy {
+        match self {
+            Self::EvenSplit => &EvenSplit,
+            Self::WeightBased => &WeightBased,
+            Self::ScoreBased => &ScoreBased,
+        }
+    }
+
+    pub fn targets(&self, validators: &[ValidatorEntry], total_lamports: u64, targets: &mut [u64]) {
+        self.strategy().targets(validators, total_lamports, targets);
+    }
+}
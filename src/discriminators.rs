@@ -0,0 +1,142 @@
+//! Hashed 8-byte instruction discriminators.
+//!
+//! Instructions are dispatched on a single leading byte. With the
+//! `hashed-discriminators` feature the dispatcher also accepts Anchor-style
+//! discriminators, the first 8 bytes of `sha256("global:<instruction_name>")`,
+//! so Anchor tooling can call the program. Both forms are accepted while the
+//! feature is on, so existing callers keep working through a migration.
+//!
+//! The table is always built so clients can encode either form.
+
+/// `(instruction_name, hashed_discriminator)` indexed by the single-byte
+/// discriminator. The hash bytes are stored as a big-endian `u64`.
+pub const HASHED_DISCRIMINATORS: [(&str, u64); 100] = [
+    ("initialize", 0xafaf6d1f0d989bed),
+    ("crank_initialize_reserve", 0xdde9aa2a91668645),
+    ("crank_merge_reserve", 0xafeb567563b9054e),
+    ("deposit", 0xf223c68952e1f2b6),
+    ("crank_split", 0xe7709d789ad57121),
+    ("withdraw", 0xb712469c946da122),
+    ("crank_record_rate", 0x0a5d90058885d088),
+    ("get_apy", 0xc27bb736b54ac261),
+    ("exit_pool", 0x069c57bbc365de8f),
+    ("crank_split_tranche", 0xd2c20c4fa9ad3e8b),
+    ("set_trusted_caller", 0x1cd2a597fc61f496),
+    ("initialize_liquidity_pool", 0x9b128a6b6f17b2b2),
+    ("add_liquidity", 0xb59d59438fb63448),
+    ("remove_liquidity", 0x5055d14818ceb16c),
+    ("liquid_unstake", 0x1e1e77f0bfe30c10),
+    ("set_unstake_fee_params", 0xf3025fb067654f87),
+    ("crank_refill_buffer", 0x6df5f83fff701c95),
+    ("swap_buffer", 0x4a64cf8498196a04),
+    ("join_validator_set", 0x28a2c11df96a409d),
+    ("set_validator_status", 0xa8739dbaed864555),
+    ("slash_validator_bond", 0x3ef5123079bbb20c),
+    ("leave_validator_set", 0x6d744ff112f2483a),
+    ("set_validator_stake_cap", 0xe869fbf0698c5f54),
+    ("set_delegation_strategy", 0x8f22df08461dc433),
+    ("set_validator_metrics", 0x7ffb74003859d2bb),
+    ("get_stake_distribution", 0xc2493d0a1d83ce96),
+    ("set_split_minimum", 0x11ab443c8e7b49cd),
+    ("close_deposit_key", 0x91c3d704cda664b7),
+    ("burn_and_donate", 0x4330ebe5bb7166d0),
+    ("distribute_treasury", 0x9a60ba2a7cbdce2f),
+    ("create_vesting", 0x87b8ab9cc5a2f62c),
+    ("claim_vested", 0xd0bea672cbe18cd0),
+    ("create_deposit_session", 0xd43d4505b84d9d36),
+    ("deposit_with_session", 0xec859e1b58d9b100),
+    ("close_deposit_session", 0x07a60f8108a03113),
+    ("create_dca_schedule", 0xb10cbefb34adcee2),
+    ("crank_dca", 0x4cc7e65a12c39db4),
+    ("close_dca_schedule", 0x6bd7ac44aa0b65a7),
+    ("set_pool_status", 0x705787df53cc8435),
+    ("verify_authorities", 0xd4264e6634462d19),
+    ("set_buyback_policy", 0xf4cecf33ddea046a),
+    ("buyback", 0x6a75401e3845072d),
+    ("get_balance_sheet", 0x9bc387b3dd605f94),
+    ("set_deposit_cap", 0x1e2bdb5afe0455ec),
+    ("claim_queued_deposit", 0x91871256d5d94012),
+    ("set_bonus_schedule", 0x201346b4f890f6f9),
+    ("simulate_params", 0x8965a587cd3b7790),
+    ("set_wrapper_program", 0xeb96b50b42f86d93),
+    ("mint_to_wrapper", 0xc3d2da914e82444a),
+    ("burn_from_wrapper", 0x704faf74773b88d2),
+    ("get_pending_cranks", 0x78e2bf8993f18b90),
+    ("adopt_reserve", 0xa67a583e1bc48b71),
+    ("set_instruction_enabled", 0x5bb2c7544b682b7f),
+    ("register_referrer", 0x7ae5d7a96491c678),
+    ("claim_referral_fees", 0xd0d8894e2467a231),
+    ("set_referral_fee", 0x897134befd02aa6d),
+    ("set_quote_signer", 0x2e53c028b5f2346a),
+    ("create_unstake_order", 0x56872f8c88cd4fd4),
+    ("fill_unstake_order", 0xdb1d3841d3f9fad3),
+    ("cancel_unstake_order", 0xef63aca2a872b514),
+    ("crank_validator_health", 0x3814064e08fee34d),
+    ("lock_lst", 0xa837e3f87b6f6bc2),
+    ("unlock_lst", 0xe97211ddbbb7a49e),
+    ("get_build_info", 0xfb93bf08fdfd9c9d),
+    ("propose_split_handoff", 0x3a2cab463a2cfce6),
+    ("cancel_split_handoff", 0x6b20089d158f27ea),
+    ("execute_split_handoff", 0x306a58014792de8c),
+    ("set_main_headroom", 0x4d82d4f1155dfc2a),
+    ("crank_split_single", 0xcc6def93bb0d7999),
+    ("get_yield_report", 0x88f1a0ac446f0979),
+    ("open_migration", 0x12d2fa8bbce8d499),
+    ("import_stake_account", 0x1346ed145f197d8a),
+    ("import_balance", 0x6ed41b905fae55ab),
+    ("set_reward_payout", 0xe2fe7f1dd72e6a11),
+    ("enroll_payout", 0x902f57803b596844),
+    ("withdraw_payout", 0x729230f031396cbc),
+    ("claim_payout", 0x7ff0843ee3c69285),
+    ("crank_reward_payout", 0x2fb19a17bb311b23),
+    ("crank_validator_stake", 0xeca9d39ad1164166),
+    ("add_validator", 0xfa7135368d75d7b9),
+    ("remove_validator", 0x1960d39ba10ea8bc),
+    ("crank_rebalance", 0x8a1e68c9d8f328ba),
+    ("update_config", 0x1d9efcbf0a53db63),
+    ("crank_redelegate_main", 0x743b84a06c2cf829),
+    ("propose_admin", 0x79d6c7d4572775ea),
+    ("accept_admin", 0x702a2d5a74b50daa),
+    ("set_deposit_fee", 0x486be302e31fca52),
+    ("set_withdraw_fee", 0x21df6676e17408ee),
+    ("set_management_fee", 0x30176f8350cc4591),
+    ("crank_management_fee", 0x41ab02cbccee590b),
+    ("deposit_stake_account", 0x6e827329a466023b),
+    ("withdraw_stake", 0x9908168a69b05742),
+    ("instant_withdraw", 0xab3191b0306570a2),
+    ("crank_split_next", 0x340dcb67da04879a),
+    ("set_crank_tip", 0x0a501432a348c1b8),
+    ("crank_update_rate", 0xb5b19f19840f1abe),
+    ("set_pool_cap", 0x52d557ec2b0c3fec),
+    ("set_deposit_authority", 0xefab3a372bb4aee7),
+    ("close_used_quote", 0xaf7f6553a811c0df),
+    ("set_holding_rebate", 0xa275923a31064cef),
+];
+
+/// Hashed discriminator of the instruction with single-byte `discriminator`.
+pub fn hashed_discriminator(discriminator: u8) -> Option<[u8; 8]> {
+    HASHED_DISCRIMINATORS
+        .get(discriminator as usize)
+        .map(|(_, hashed)| hashed.to_be_bytes())
+}
+
+/// Splits instruction data that starts with a hashed discriminator into the
+/// matching single-byte discriminator and the rest of the data.
+pub fn split_hashed(instruction_data: &[u8]) -> Option<(&'static u8, &[u8])> {
+    static SINGLE_BYTE: [u8; HASHED_DISCRIMINATORS.len()] = {
+        let mut bytes = [0u8; HASHED_DISCRIMINATORS.len()];
+        let mut index = 0;
+        while index < bytes.len() {
+            bytes[index] = index as u8;
+            index += 1;
+        }
+        bytes
+    };
+
+    let (prefix, data) = instruction_data.split_first_chunk::<8>()?;
+    let prefix = u64::from_be_bytes(*prefix);
+    let index = HASHED_DISCRIMINATORS
+        .iter()
+        .position(|(_, hashed)| *hashed == prefix)?;
+    Some((&SINGLE_BYTE[index], data))
+}
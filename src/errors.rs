@@ -97,6 +97,537 @@ pub enum PinocchioError {
     /// Invalid validator vote key
     #[error("Invalid validator vote key")]
     InvalidValidatorVoteKey,
+    // 23
+    /// Rate already recorded for this epoch
+    #[error("Rate already recorded for this epoch")]
+    RateAlreadyRecorded,
+    // 24
+    /// Invalid rate history PDA
+    #[error("Invalid rate history PDA")]
+    InvalidRateHistoryPda,
+    // 25
+    /// Split would leave main stake account below its minimum delegation and
+    /// headroom. The pool's liquidity (`LiquidUnstake`) can still pay out.
+    #[error("Split would leave main stake account below minimum, use LiquidUnstake instead")]
+    MainBelowMinimum,
+    // 26
+    /// Invalid split record PDA
+    #[error("Invalid split record PDA")]
+    InvalidSplitRecordPda,
+    // 27
+    /// Withdrawer does not hold the entire LST supply
+    #[error("Withdrawer does not hold the entire LST supply")]
+    NotLastHolder,
+    // 28
+    /// Reserve stake must be merged before exiting the pool
+    #[error("Reserve stake must be merged before exiting the pool")]
+    ReserveStakeActive,
+    // 29
+    /// Pool has been fully exited
+    #[error("Pool has been fully exited")]
+    PoolClosed,
+    // 30
+    /// Next tranche of the split schedule is not due yet
+    #[error("Next tranche of the split schedule is not due yet")]
+    TrancheNotReady,
+    // 31
+    /// Split record has no scheduled lamports left
+    #[error("Split record has no scheduled lamports left")]
+    NothingScheduled,
+    // 32
+    /// Signer is not the pool admin
+    #[error("Signer is not the pool admin")]
+    NotAdmin,
+    // 33
+    /// Invalid trusted callers PDA
+    #[error("Invalid trusted callers PDA")]
+    InvalidTrustedCallersPda,
+    // 34
+    /// Calling program is not approved to skip checks
+    #[error("Calling program is not approved to skip checks")]
+    UntrustedCaller,
+    // 35
+    /// Trusted callers list is full
+    #[error("Trusted callers list is full")]
+    TrustedCallersFull,
+    // 36
+    /// Reserve stake is still activating
+    #[error("Reserve stake is still activating")]
+    ReserveActivating,
+    // 37
+    /// Invalid liquidity pool PDA
+    #[error("Invalid liquidity pool PDA")]
+    InvalidLiquidityPoolPda,
+    // 38
+    /// Invalid LP mint
+    #[error("Invalid LP mint")]
+    InvalidLpMint,
+    // 39
+    /// Invalid liquidity pool LST vault
+    #[error("Invalid liquidity pool LST vault")]
+    InvalidLstVault,
+    // 40
+    /// Invalid liquidity provider ATA
+    #[error("Invalid liquidity provider ATA")]
+    InvalidProviderAta,
+    // 41
+    /// Liquidity amount is zero
+    #[error("Liquidity amount is zero")]
+    ZeroLiquidity,
+    // 42
+    /// Liquidity pool cannot cover the unstake
+    #[error("Liquidity pool cannot cover the unstake")]
+    InsufficientLiquidity,
+    // 43
+    /// Unstake output below the caller's minimum
+    #[error("Unstake output below the caller's minimum")]
+    SlippageExceeded,
+    // 44
+    /// Invalid unstake fee parameters
+    #[error("Invalid unstake fee parameters")]
+    InvalidFeeParams,
+    // 45
+    /// Liquidity buffer needs no refill from this source
+    #[error("Liquidity buffer needs no refill from this source")]
+    NothingToRefill,
+    // 46
+    /// Invalid buffer stake account PDA
+    #[error("Invalid buffer stake account PDA")]
+    InvalidBufferStakePda,
+    // 47
+    /// Buffer stake account is already cooling down
+    #[error("Buffer stake account is already cooling down")]
+    BufferStakeInUse,
+    // 48
+    /// Reserve is already delegated
+    #[error("Reserve is already delegated")]
+    ReserveDelegated,
+    // 49
+    /// Only the keeper that funded the buffer stake account may collect it
+    #[error("Only the keeper that funded the buffer stake account may collect it")]
+    NotBufferStakeFunder,
+    // 50
+    /// Swap exceeds the buffer's deviation from its target
+    #[error("Swap exceeds the buffer's deviation from its target")]
+    SwapExceedsImbalance,
+    // 51
+    /// Invalid validator bond PDA
+    #[error("Invalid validator bond PDA")]
+    InvalidValidatorBondPda,
+    // 52
+    /// Invalid validator list PDA
+    #[error("Invalid validator list PDA")]
+    InvalidValidatorListPda,
+    // 53
+    /// Signer is not the validator's identity
+    #[error("Signer is not the validator's identity")]
+    NotValidatorOperator,
+    // 54
+    /// Validator bond below minimum
+    #[error("Validator bond below minimum")]
+    BondBelowMinimum,
+    // 55
+    /// Validator list is full
+    #[error("Validator list is full")]
+    ValidatorListFull,
+    // 56
+    /// Validator bond is not in the required status
+    #[error("Validator bond is not in the required status")]
+    InvalidValidatorStatus,
+    // 57
+    /// Validator bond is still locked
+    #[error("Validator bond is still locked")]
+    ValidatorBondLocked,
+    // 58
+    /// Slash exceeds the validator's bond
+    #[error("Slash exceeds the validator's bond")]
+    SlashExceedsBond,
+    // 59
+    /// Delegation would exceed the per-validator stake cap
+    #[error("Delegation would exceed the per-validator stake cap")]
+    ValidatorStakeCapExceeded,
+    // 60
+    /// Invalid validator stake cap
+    #[error("Invalid validator stake cap")]
+    InvalidValidatorStakeCap,
+    // 61
+    /// Validator is not in the validator list
+    #[error("Validator is not in the validator list")]
+    ValidatorNotInList,
+    // 62
+    /// Invalid epoch tasks PDA
+    #[error("Invalid epoch tasks PDA")]
+    InvalidEpochTasksPda,
+    // 63
+    /// A prerequisite crank has not run this epoch
+    #[error("A prerequisite crank has not run this epoch")]
+    PrerequisiteCrankMissing,
+    // 64
+    /// Invalid deposit key PDA
+    #[error("Invalid deposit key PDA")]
+    InvalidDepositKeyPda,
+    // 65
+    /// Deposit with this idempotency key was already made
+    #[error("Deposit with this idempotency key was already made")]
+    DuplicateDeposit,
+    // 66
+    /// Deposit key has not expired yet
+    #[error("Deposit key has not expired yet")]
+    DepositKeyNotExpired,
+    // 67
+    /// Invalid pool state PDA
+    #[error("Invalid pool state PDA")]
+    InvalidPoolStatePda,
+    // 68
+    /// Invalid treasury ATA
+    #[error("Invalid treasury ATA")]
+    InvalidTreasuryAta,
+    // 69
+    /// Invalid vesting escrow PDA
+    #[error("Invalid vesting escrow PDA")]
+    InvalidVestingPda,
+    // 70
+    /// Invalid vesting vault
+    #[error("Invalid vesting vault")]
+    InvalidVestingVault,
+    // 71
+    /// Vesting schedule must have start <= cliff <= end and start < end
+    #[error("Vesting schedule must have start <= cliff <= end and start < end")]
+    InvalidVestingSchedule,
+    // 72
+    /// Nothing has vested since the last claim
+    #[error("Nothing has vested since the last claim")]
+    NothingVested,
+    // 73
+    /// Rent payer account does not match the split record
+    #[error("Rent payer account does not match the split record")]
+    InvalidRentPayer,
+    // 74
+    /// Invalid deposit session PDA
+    #[error("Invalid deposit session PDA")]
+    InvalidDepositSessionPda,
+    // 75
+    /// Deposit session has expired
+    #[error("Deposit session has expired")]
+    DepositSessionExpired,
+    // 76
+    /// Deposit exceeds the session's remaining budget
+    #[error("Deposit exceeds the session's remaining budget")]
+    DepositSessionBudgetExceeded,
+    // 77
+    /// Invalid DCA schedule PDA
+    #[error("Invalid DCA schedule PDA")]
+    InvalidDcaSchedulePda,
+    // 78
+    /// DCA deposit is not due yet
+    #[error("DCA deposit is not due yet")]
+    DcaNotDue,
+    // 79
+    /// DCA budget cannot cover another deposit and tip
+    #[error("DCA budget cannot cover another deposit and tip")]
+    DcaBudgetExhausted,
+    // 80
+    /// Initializer is not the admin this program was built for
+    #[error("Initializer is not the admin this program was built for")]
+    UnexpectedInitializer,
+    // 81
+    /// Too many items for one call
+    #[error("Too many items for one call")]
+    BatchTooLarge,
+    // 82
+    /// Pool status cannot move to the requested status
+    #[error("Pool status cannot move to the requested status")]
+    InvalidPoolStatusTransition,
+    // 83
+    /// Pool is paused
+    #[error("Pool is paused")]
+    PoolPaused,
+    // 84
+    /// Invalid fee vault PDA
+    #[error("Invalid fee vault PDA")]
+    InvalidFeeVaultPda,
+    // 85
+    /// Fee vault holds no SOL to buy back LST with
+    #[error("Fee vault holds no SOL to buy back LST with")]
+    NoFeesToBuyBack,
+    // 86
+    /// Deposit exceeds this epoch's deposit cap
+    #[error("Deposit exceeds this epoch's deposit cap")]
+    DepositCapExceeded,
+    // 87
+    /// Invalid queued deposit PDA
+    #[error("Invalid queued deposit PDA")]
+    InvalidQueuedDepositPda,
+    // 88
+    /// Queued deposit can only be claimed in a later epoch
+    #[error("Queued deposit can only be claimed in a later epoch")]
+    QueuedDepositNotReady,
+    // 89
+    /// Signer is not the approved wrapper program's authority PDA
+    #[error("Signer is not the approved wrapper program's authority PDA")]
+    WrapperNotApproved,
+    // 90
+    /// Invalid wrapper vault
+    #[error("Invalid wrapper vault")]
+    InvalidWrapperVault,
+    // 91
+    /// Reserve address holds no stake account
+    #[error("Reserve address holds no stake account")]
+    ReserveNotAdopted,
+    // 92
+    /// Invalid changelog PDA
+    #[error("Invalid changelog PDA")]
+    InvalidChangelogPda,
+    // 93
+    /// Instruction disabled by the admin
+    #[error("Instruction disabled by the admin")]
+    InstructionDisabled,
+    // 94
+    /// Invalid referrer PDA
+    #[error("Invalid referrer PDA")]
+    InvalidReferrerPda,
+    // 95
+    /// No referral fees to claim
+    #[error("No referral fees to claim")]
+    NoReferralFees,
+    // 96
+    /// Deposit quote missing or not signed by the quote signer
+    #[error("Deposit quote missing or not signed by the quote signer")]
+    InvalidQuote,
+    // 97
+    /// Deposit quote expired
+    #[error("Deposit quote expired")]
+    QuoteExpired,
+    // 98
+    /// Quoted rate too far from the pool's rate
+    #[error("Quoted rate too far from the pool's rate")]
+    QuoteOutOfBounds,
+    // 99
+    /// Invalid unstake order PDA
+    #[error("Invalid unstake order PDA")]
+    InvalidUnstakeOrderPda,
+    // 100
+    /// Unstake order's minimum rate is not met
+    #[error("Unstake order's minimum rate is not met")]
+    OrderRateNotMet,
+    // 101
+    /// Invalid validator health PDA
+    #[error("Invalid validator health PDA")]
+    InvalidValidatorHealthPda,
+    // 102
+    /// Invalid vote escrow PDA
+    #[error("Invalid vote escrow PDA")]
+    InvalidVoteEscrowPda,
+    // 103
+    /// Lock duration out of bounds or shorter than the current lock
+    #[error("Lock duration out of bounds or shorter than the current lock")]
+    InvalidLockDuration,
+    // 104
+    /// Locked LST is not unlocked yet
+    #[error("Locked LST is not unlocked yet")]
+    LockNotExpired,
+    // 105
+    /// Invalid split handoff PDA
+    #[error("Invalid split handoff PDA")]
+    InvalidSplitHandoffPda,
+    // 106
+    /// Split handoff timelock has not passed
+    #[error("Split handoff timelock has not passed")]
+    HandoffTimelocked,
+    // 107
+    /// Signer is neither the split's owner nor the admin
+    #[error("Signer is neither the split's owner nor the admin")]
+    NotSplitOwnerOrAdmin,
+    // 108
+    /// Split account already holds a pending withdrawal
+    #[error("Split account already holds a pending withdrawal")]
+    SplitAlreadyPending,
+    // 109
+    /// Mint supply did not change by the amount minted or burned
+    #[error("Mint supply did not change by the amount minted or burned")]
+    SupplyMismatch,
+    // 110
+    /// Invalid migration PDA
+    #[error("Invalid migration PDA")]
+    InvalidMigrationPda,
+    // 111
+    /// Migration already started importing balances
+    #[error("Migration already started importing balances")]
+    MigrationSealed,
+    // 112
+    /// Stake account is not authorized to the config PDA, or is neither
+    /// active on the pool's validator nor inactive
+    #[error("Stake account cannot be imported")]
+    StakeNotImportable,
+    // 113
+    /// Imported balances exceed the source pool's token supply
+    #[error("Imported balances exceed the source pool's token supply")]
+    MigrationExceedsSource,
+
+    // 114
+    /// Invalid sysvar account
+    #[error("Invalid sysvar account")]
+    InvalidSysvar,
+
+    // 115
+    /// Invalid reward payout PDA
+    #[error("Invalid reward payout PDA")]
+    InvalidRewardPayoutPda,
+
+    // 116
+    /// Invalid payout position PDA
+    #[error("Invalid payout position PDA")]
+    InvalidPayoutPositionPda,
+
+    // 117
+    /// No SOL payout to claim
+    #[error("No SOL payout to claim")]
+    NoPayoutToClaim,
+
+    // 118
+    /// Reward payout already cranked this epoch
+    #[error("Reward payout already cranked this epoch")]
+    PayoutAlreadyCranked,
+
+    // 119
+    /// Payout position has fewer shares than requested
+    #[error("Payout position has fewer shares than requested")]
+    InsufficientPayoutShares,
+
+    // 120
+    /// Reward payout not cranked yet this epoch
+    #[error("Reward payout not cranked yet this epoch")]
+    PayoutNotCranked,
+
+    // 121
+    /// Unstakes are disabled while the pool is bootstrapping
+    #[error("Unstakes are disabled while the pool is bootstrapping")]
+    PoolBootstrapping,
+
+    // 122
+    /// Invalid validator stake PDA
+    #[error("Invalid validator stake PDA")]
+    InvalidValidatorStakePda,
+
+    // 123
+    /// Nothing to delegate to the validator
+    #[error("Nothing to delegate to the validator")]
+    NothingToDelegate,
+
+    // 124
+    /// Validator stake accounts still hold pool stake
+    #[error("Validator stake accounts still hold pool stake")]
+    ValidatorStakeOutstanding,
+
+    // 125
+    /// Validator is already in the validator list
+    #[error("Validator is already in the validator list")]
+    ValidatorAlreadyListed,
+
+    // 126
+    /// Validator stake is still cooling down
+    #[error("Validator stake is still cooling down")]
+    ValidatorStakeCoolingDown,
+
+    // 127
+    /// Transient stake is still activating or cooling down
+    #[error("Transient stake is still activating or cooling down")]
+    RebalanceInProgress,
+
+    // 128
+    /// Validator stake is already within its target
+    #[error("Validator stake is already within its target")]
+    NothingToRebalance,
+
+    // 129
+    /// Validator stake PDA is not delegated yet
+    #[error("Validator stake PDA is not delegated yet")]
+    ValidatorStakeNotDelegated,
+
+    // 130
+    /// Main is already delegated to the pool's validator
+    #[error("Main is already delegated to the pool's validator")]
+    MainNotRedelegating,
+
+    // 131
+    /// Main stake is still cooling down
+    #[error("Main stake is still cooling down")]
+    MainCoolingDown,
+
+    // 132
+    /// Signer is not the pending admin
+    #[error("Signer is not the pending admin")]
+    NotPendingAdmin,
+
+    // 133
+    /// Management fee already charged this epoch
+    #[error("Management fee already charged this epoch")]
+    ManagementFeeAlreadyCharged,
+    // 134
+    /// Deposit mints less LST than the caller's minimum
+    #[error("Deposit mints less LST than the caller's minimum")]
+    MinLstOutNotMet,
+    // 135
+    /// Stake account is not authorized to the depositor, or is not fully
+    /// active on the pool's validator
+    #[error("Stake account cannot be deposited")]
+    StakeNotDepositable,
+    // 136
+    /// Split record was already paid out by `Withdraw`
+    #[error("Split already claimed")]
+    SplitAlreadyClaimed,
+    // 137
+    /// Split counter PDA does not match the withdrawer
+    #[error("Invalid split counter PDA")]
+    InvalidSplitCounterPda,
+    // 138
+    /// `CrankUpdateRate` already cached the rate this epoch
+    #[error("Rate already updated this epoch")]
+    RateAlreadyUpdated,
+    // 139
+    /// Deposit would push the pool's lamports past `max_pool_lamports`
+    #[error("Deposit exceeds the pool cap")]
+    PoolCapExceeded,
+    // 140
+    /// The config's deposit authority did not sign the deposit
+    #[error("Missing deposit authority signature")]
+    MissingDepositAuthority,
+    // 141
+    /// Referrer token account is not the referrer's LST ATA
+    #[error("Invalid referrer ATA")]
+    InvalidReferrerAta,
+    // 142
+    /// A validator holding pool stake has not been recorded this epoch
+    #[error("Validator stake not recorded this epoch")]
+    ValidatorStakeStale,
+    // 143
+    /// Quoted deposit is larger than the quote's `max_lamports`
+    #[error("Deposit exceeds the quoted amount")]
+    QuoteAmountExceeded,
+    // 144
+    /// A deposit already used the quote's nonce
+    #[error("Quote already used")]
+    QuoteAlreadyUsed,
+    // 145
+    /// Invalid used quote PDA
+    #[error("Invalid used quote PDA")]
+    InvalidUsedQuotePda,
+    // 146
+    /// The quote a used quote PDA records has not expired yet
+    #[error("Quote not expired")]
+    QuoteNotExpired,
+    // 147
+    /// The outgoing reserve of the last validator change has not been merged
+    #[error("Reserve rotation pending")]
+    ReserveRotationPending,
+    // 148
+    /// Invalid outgoing reserve PDA
+    #[error("Invalid outgoing reserve PDA")]
+    InvalidOutgoingReservePda,
+    // 149
+    /// Invalid user stats PDA
+    #[error("Invalid user stats PDA")]
+    InvalidUserStatsPda,
 }
 
 impl From<PinocchioError> for ProgramError {
@@ -0,0 +1,143 @@
+//! Structured events for off-chain consumers.
+//!
+//! Every event is logged with `sol_log_data` as four fields: the event kind,
+//! the global sequence number, the kind-specific payload and the LST's
+//! decimals as one byte. Payload amounts are raw base units: lamports have
+//! `SOL_DECIMALS`, LST amounts the logged decimals. The sequence
+//! number comes from `PoolState::event_sequence` and increases by one for each
+//! event, so a consumer that sees a gap knows it missed logs and can re-sync
+//! from the last sequence number it processed.
+//!
+//! The pool state PDA also keeps a compact copy of the last
+//! `PoolState::EVENT_LOG_CAPACITY` events, so light clients can poll one
+//! account instead of subscribing to logs.
+
+use pinocchio::{
+    account_info::AccountInfo,
+    log::sol_log_data,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    errors::PinocchioError,
+    instructions::helpers::LST_DECIMALS,
+    state::{EventRecord, PoolState},
+};
+
+/// Payload: depositor (32), lamports deposited (8), LST minted (8). `Deposit`
+/// adds a flags byte and two fixed fields, the memo hash (32) and the
+/// referrer (32), each zero unless its `DEPOSIT_FLAG_*` bit is set.
+pub const EVENT_DEPOSIT: u8 = 0;
+/// Payload: withdrawer (32), split account (32), lamports split (8), LST burned (8).
+pub const EVENT_SPLIT: u8 = 1;
+/// Payload: withdrawer (32), split account (32), lamports withdrawn (8).
+pub const EVENT_WITHDRAW: u8 = 2;
+/// Payload: donor (32), LST burned (8).
+pub const EVENT_DONATE: u8 = 3;
+/// Payload: previous `PoolStatus` (1), new `PoolStatus` (1).
+pub const EVENT_STATUS: u8 = 4;
+/// Payload: wrapper authority (32), LST burned (8), lamports paid (8).
+pub const EVENT_UNWRAP: u8 = 5;
+/// Payload: signer (32), `ROLE_*` it acted as (1), instruction
+/// `DISCRIMINATOR` (1), slot (8). Logged by `emit_admin_action`.
+pub const EVENT_ADMIN_ACTION: u8 = 6;
+/// Payload: split account (32), new owner (32), `HANDOFF_*` stage (1),
+/// unix timestamp the handoff is executable from (8).
+pub const EVENT_SPLIT_HANDOFF: u8 = 7;
+
+/// Flags of a `Deposit`'s `EVENT_DEPOSIT`.
+pub const DEPOSIT_FLAG_MEMO: u8 = 1;
+pub const DEPOSIT_FLAG_REFERRER: u8 = 2;
+
+/// Stages of an `EVENT_SPLIT_HANDOFF`.
+pub const HANDOFF_PROPOSED: u8 = 0;
+pub const HANDOFF_CANCELLED: u8 = 1;
+pub const HANDOFF_EXECUTED: u8 = 2;
+
+/// Roles an `EVENT_ADMIN_ACTION` signer can act as.
+pub const ROLE_ADMIN: u8 = 0;
+
+/// Takes the next sequence number from the pool state PDA and logs the
+/// event.
+pub fn emit(pool_state_pda: &AccountInfo, kind: u8, payload: &[&[u8]]) -> Result<(), ProgramError> {
+    let (expected_pool_state_pda, _) = find_program_address(&[b"pool_state"], &crate::ID);
+    if expected_pool_state_pda != *pool_state_pda.key() || !pool_state_pda.is_owned_by(&crate::ID) {
+        return Err(PinocchioError::InvalidPoolStatePda.into());
+    }
+
+    let mut data = pool_state_pda.try_borrow_mut_data()?;
+    let pool_state = PoolState::load_mut(&mut data)?;
+    let sequence = pool_state.next_event_sequence()?;
+    pool_state.record_event(event_record(kind, sequence, Clock::get()?.slot, payload));
+
+    let mut buffer = [0u8; 128];
+    let mut len = 0;
+    for field in payload {
+        buffer
+            .get_mut(len..len + field.len())
+            .ok_or(ProgramError::InvalidArgument)?
+            .copy_from_slice(field);
+        len += field.len();
+    }
+
+    sol_log_data(&[
+        &[kind],
+        &sequence.to_le_bytes(),
+        &buffer[..len],
+        &[LST_DECIMALS],
+    ]);
+    Ok(())
+}
+
+/// Logs a high-severity `EVENT_ADMIN_ACTION` for an admin-gated instruction,
+/// right after its signer has been checked against the config, so monitors
+/// can alert on any admin activity they did not expect. It takes the next
+/// sequence number like any other event, so a gap shows a missed admin
+/// action too.
+pub fn emit_admin_action(
+    pool_state_pda: &AccountInfo,
+    signer: &AccountInfo,
+    role: u8,
+    discriminator: u8,
+) -> ProgramResult {
+    emit(
+        pool_state_pda,
+        EVENT_ADMIN_ACTION,
+        &[
+            signer.key(),
+            &[role],
+            &[discriminator],
+            &Clock::get()?.slot.to_le_bytes(),
+        ],
+    )
+}
+
+/// Compacts an event for the pool state's ring buffer: the first 32-byte
+/// field is the actor and the first two shorter fields are the amounts.
+fn event_record(kind: u8, sequence: u64, slot: u64, payload: &[&[u8]]) -> EventRecord {
+    let mut actor = None;
+    let mut amounts = [0u64; 2];
+    let mut amount_count = 0;
+
+    for field in payload {
+        if field.len() == 32 {
+            actor.get_or_insert(*field);
+        } else if field.len() <= 8 && amount_count < amounts.len() {
+            let mut bytes = [0u8; 8];
+            bytes[..field.len()].copy_from_slice(field);
+            amounts[amount_count] = u64::from_le_bytes(bytes);
+            amount_count += 1;
+        }
+    }
+
+    EventRecord {
+        sequence,
+        slot,
+        kind,
+        actor: actor.map_or([0u8; 32], |actor| actor.try_into().unwrap()),
+        amounts,
+    }
+}
@@ -0,0 +1,122 @@
+//! Addresses of the foreign programs and sysvars the pool talks to, and the
+//! checks instructions run on the accounts passed for them. These addresses
+//! are the same on every cluster, so mainnet, devnet and a local validator
+//! share one table; only the program's own `crate::ID` differs per
+//! deployment.
+
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::errors::PinocchioError;
+
+pub const SYSTEM_PROGRAM_ID: Pubkey = pinocchio_system::ID;
+
+pub const TOKEN_PROGRAM_ID: Pubkey = pinocchio_token::ID;
+
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = [
+    0x06, 0xdd, 0xf6, 0xe1, 0xee, 0x75, 0x8f, 0xde, 0x18, 0x42, 0x5d, 0xbc, 0xe4, 0x6c, 0xcd, 0xda,
+    0xb6, 0x1a, 0xfc, 0x4d, 0x83, 0xb9, 0x0d, 0x27, 0xfe, 0xbd, 0xf9, 0x28, 0xd8, 0xa1, 0x8b, 0xfc,
+];
+
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = pinocchio_associated_token_account::ID;
+
+pub const STAKE_PROGRAM_ID: Pubkey = [
+    6, 161, 216, 23, 145, 55, 84, 42, 152, 52, 55, 189, 254, 42, 122, 178, 85, 127, 83, 92, 138,
+    120, 114, 43, 104, 164, 157, 192, 0, 0, 0, 0,
+];
+
+pub const VOTE_PROGRAM_ID: Pubkey = [
+    7, 97, 72, 29, 53, 116, 116, 187, 124, 77, 118, 36, 235, 211, 189, 179, 216, 53, 94, 115, 209,
+    16, 67, 252, 13, 163, 83, 128, 0, 0, 0, 0,
+];
+
+pub const ED25519_PROGRAM_ID: Pubkey = [
+    3, 125, 70, 214, 124, 147, 251, 190, 18, 249, 66, 143, 131, 141, 64, 255, 5, 112, 116, 73, 39,
+    244, 138, 100, 252, 202, 112, 68, 128, 0, 0, 0,
+];
+
+pub const CLOCK_SYSVAR_ID: Pubkey = [
+    6, 167, 213, 23, 24, 199, 116, 201, 40, 86, 99, 152, 105, 29, 94, 182, 139, 94, 184, 163, 155,
+    75, 109, 92, 115, 85, 91, 33, 0, 0, 0, 0,
+];
+
+pub const RENT_SYSVAR_ID: Pubkey = [
+    6, 167, 213, 23, 25, 44, 92, 81, 33, 140, 201, 76, 61, 74, 241, 127, 88, 218, 238, 8, 155, 161,
+    253, 68, 227, 219, 217, 138, 0, 0, 0, 0,
+];
+
+pub const STAKE_HISTORY_SYSVAR_ID: Pubkey = [
+    6, 167, 213, 23, 25, 53, 132, 208, 254, 237, 155, 179, 67, 29, 19, 32, 107, 229, 68, 40, 27,
+    87, 184, 86, 108, 197, 55, 95, 244, 0, 0, 0,
+];
+
+pub const INSTRUCTIONS_SYSVAR_ID: Pubkey = [
+    6, 167, 213, 23, 24, 123, 209, 102, 53, 218, 212, 4, 85, 253, 194, 192, 193, 36, 198, 143, 33,
+    86, 117, 165, 219, 186, 203, 95, 8, 0, 0, 0,
+];
+
+fn assert_key(
+    account: &AccountInfo,
+    id: &Pubkey,
+    error: PinocchioError,
+) -> Result<(), ProgramError> {
+    if account.key() != id {
+        return Err(error.into());
+    }
+    Ok(())
+}
+
+pub fn assert_system_program(account: &AccountInfo) -> Result<(), ProgramError> {
+    assert_key(
+        account,
+        &SYSTEM_PROGRAM_ID,
+        PinocchioError::InvalidSystemProgram,
+    )
+}
+
+pub fn assert_token_program(account: &AccountInfo) -> Result<(), ProgramError> {
+    assert_key(
+        account,
+        &TOKEN_PROGRAM_ID,
+        PinocchioError::InvalidTokenProgram,
+    )
+}
+
+pub fn assert_associated_token_program(account: &AccountInfo) -> Result<(), ProgramError> {
+    assert_key(
+        account,
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+        PinocchioError::InvalidAssociatedTokenProgram,
+    )
+}
+
+pub fn assert_stake_program(account: &AccountInfo) -> Result<(), ProgramError> {
+    assert_key(
+        account,
+        &STAKE_PROGRAM_ID,
+        PinocchioError::InvalidStakeProgram,
+    )
+}
+
+pub fn assert_clock_sysvar(account: &AccountInfo) -> Result<(), ProgramError> {
+    assert_key(account, &CLOCK_SYSVAR_ID, PinocchioError::InvalidSysvar)
+}
+
+pub fn assert_rent_sysvar(account: &AccountInfo) -> Result<(), ProgramError> {
+    assert_key(account, &RENT_SYSVAR_ID, PinocchioError::InvalidSysvar)
+}
+
+pub fn assert_stake_history_sysvar(account: &AccountInfo) -> Result<(), ProgramError> {
+    assert_key(
+        account,
+        &STAKE_HISTORY_SYSVAR_ID,
+        PinocchioError::InvalidSysvar,
+    )
+}
+
+/// Checks that `account` is a vote account, i.e. owned by the vote program.
+pub fn assert_vote_account(account: &AccountInfo) -> Result<(), ProgramError> {
+    if !account.is_owned_by(&VOTE_PROGRAM_ID) {
+        return Err(PinocchioError::InvalidValidatorVoteAccount.into());
+    }
+    Ok(())
+}
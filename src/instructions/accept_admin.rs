@@ -0,0 +1,94 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    instructions::helpers::{check_config_pda, record_param_change, AccountCheck, SignerAccount},
+    state::Config,
+};
+
+pub struct AcceptAdminAccounts<'a> {
+    pub pending_admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub changelog_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for AcceptAdminAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [pending_admin, config_pda, changelog_pda, pool_state_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(pending_admin)?;
+
+        Ok(Self {
+            pending_admin,
+            config_pda,
+            changelog_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+/// Completes an admin transfer started by `ProposeAdmin`: the pending admin
+/// signs to become the admin, proving the key is held before the old admin
+/// loses its rights. Fails with `NotPendingAdmin` for any other signer or
+/// when no transfer is pending.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Pending admin
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Changelog PDA
+/// 3. `[WRITE]` Pool state PDA
+pub struct AcceptAdmin<'a> {
+    pub accounts: AcceptAdminAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for AcceptAdmin<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            accounts: AcceptAdminAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> AcceptAdmin<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &85;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.pending_admin == [0; 32]
+            || config.pending_admin != *self.accounts.pending_admin.key()
+        {
+            return Err(PinocchioError::NotPendingAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.pending_admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let old_value = config.admin;
+
+        config.accept_admin();
+
+        record_param_change(
+            self.accounts.changelog_pda,
+            self.accounts.pending_admin,
+            *Self::DISCRIMINATOR,
+            &[&old_value],
+            &[self.accounts.pending_admin.key()],
+        )
+    }
+}
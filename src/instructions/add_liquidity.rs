@@ -0,0 +1,253 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::find_program_address,
+};
+use pinocchio_system::instructions::Transfer;
+use pinocchio_token::{
+    instructions::MintTo,
+    state::{Mint, TokenAccount},
+};
+
+use crate::{
+    errors::PinocchioError,
+    ids::{assert_system_program, assert_token_program, ASSOCIATED_TOKEN_PROGRAM_ID},
+    instructions::helpers::{
+        check_config_pda, epoch_rate, expect_supply_change, managed_lamports, AccountCheck,
+        SignerAccount, SupplyChange,
+    },
+    math::lst_to_lamports,
+    state::{Config, LiquidityPool},
+};
+
+pub struct AddLiquidityAccounts<'a> {
+    pub provider: &'a AccountInfo,
+    pub provider_lp_ata: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub liquidity_pool_pda: &'a AccountInfo,
+    pub lp_mint: &'a AccountInfo,
+    pub lst_vault: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for AddLiquidityAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [provider, provider_lp_ata, config_pda, liquidity_pool_pda, lp_mint, lst_vault, stake_account_main, stake_account_reserve, lst_mint, token_program, system_program, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(provider)?;
+
+        assert_system_program(system_program)?;
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            provider,
+            provider_lp_ata,
+            config_pda,
+            liquidity_pool_pda,
+            lp_mint,
+            lst_vault,
+            stake_account_main,
+            stake_account_reserve,
+            lst_mint,
+            token_program,
+            system_program,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct AddLiquidityInstructionData {
+    pub lamports: u64,
+}
+
+impl TryFrom<&[u8]> for AddLiquidityInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let lamports = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+        if lamports == 0 {
+            return Err(PinocchioError::ZeroLiquidity.into());
+        }
+
+        Ok(Self { lamports })
+    }
+}
+
+/// Adds SOL to the liquidity pool and mints LP tokens for the provider's
+/// share. The pool is valued as its SOL reserves, the SOL cooling down in its
+/// buffer stake account and its LST vault at the current exchange rate, so
+/// fees retained by the pool raise the LP value.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Provider
+/// 1. `[WRITE]` Provider LP ATA
+/// 2. `[]` Config PDA
+/// 3. `[WRITE]` Liquidity pool PDA
+/// 4. `[WRITE]` LP mint
+/// 5. `[]` LST vault
+/// 6. `[]` Stake account main
+/// 7. `[]` Stake account reserve
+/// 8. `[]` LST mint
+/// 9. `[]` Token program
+/// 10. `[]` System program
+/// 11. `[]` Pool state PDA
+pub struct AddLiquidity<'a> {
+    pub accounts: AddLiquidityAccounts<'a>,
+    pub data: AddLiquidityInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for AddLiquidity<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: AddLiquidityAccounts::try_from(accounts)?,
+            data: AddLiquidityInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> AddLiquidity<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &12;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        let validator_stake_lamports = config.validator_stake_lamports;
+        let cached_rate = epoch_rate(config)?;
+        drop(config_data);
+
+        let (expected_liquidity_pool_pda, liquidity_pool_bump) =
+            find_program_address(&[b"liquidity_pool"], &crate::ID);
+        if expected_liquidity_pool_pda != *self.accounts.liquidity_pool_pda.key() {
+            return Err(PinocchioError::InvalidLiquidityPoolPda.into());
+        }
+
+        let mut liquidity_pool_data = self.accounts.liquidity_pool_pda.try_borrow_mut_data()?;
+        let liquidity_pool = LiquidityPool::load_mut(&mut liquidity_pool_data)?;
+
+        if liquidity_pool.lp_mint != *self.accounts.lp_mint.key() {
+            return Err(PinocchioError::InvalidLpMint.into());
+        }
+
+        if liquidity_pool.lst_vault != *self.accounts.lst_vault.key() {
+            return Err(PinocchioError::InvalidLstVault.into());
+        }
+
+        let expected_ata = find_program_address(
+            &[
+                self.accounts.provider.key(),
+                self.accounts.token_program.key(),
+                self.accounts.lp_mint.key(),
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .0;
+        if expected_ata != *self.accounts.provider_lp_ata.key() {
+            return Err(PinocchioError::InvalidProviderAta.into());
+        }
+
+        let (total_lamports, lst_supply) = cached_rate.unwrap_or((
+            managed_lamports(
+                self.accounts.pool_state_pda,
+                self.accounts.stake_account_main,
+                self.accounts.stake_account_reserve,
+                validator_stake_lamports,
+            )?,
+            Mint::from_account_info(self.accounts.lst_mint)?.supply(),
+        ));
+        let lst_vault_amount = TokenAccount::from_account_info(self.accounts.lst_vault)?.amount();
+        let lst_vault_value = lst_to_lamports(lst_vault_amount, lst_supply, total_lamports)?;
+
+        let liquidity_pool_value = liquidity_pool
+            .sol_reserves
+            .checked_add(liquidity_pool.pending_unstake_lamports)
+            .and_then(|value| value.checked_add(lst_vault_value))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let lp_supply = Mint::from_account_info(self.accounts.lp_mint)?.supply();
+
+        let lp_to_mint = if lp_supply == 0 || liquidity_pool_value == 0 {
+            self.data.lamports
+        } else {
+            (self.data.lamports as u128)
+                .checked_mul(lp_supply as u128)
+                .ok_or(ProgramError::ArithmeticOverflow)?
+                .checked_div(liquidity_pool_value as u128)
+                .ok_or(ProgramError::ArithmeticOverflow)? as u64
+        };
+
+        if lp_to_mint == 0 {
+            return Err(PinocchioError::ZeroLiquidity.into());
+        }
+
+        liquidity_pool.sol_reserves = liquidity_pool
+            .sol_reserves
+            .checked_add(self.data.lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        drop(liquidity_pool_data);
+
+        Transfer {
+            from: self.accounts.provider,
+            to: self.accounts.liquidity_pool_pda,
+            lamports: self.data.lamports,
+        }
+        .invoke()?;
+
+        let liquidity_pool_bump_binding = [liquidity_pool_bump];
+        let liquidity_pool_seeds = &[
+            Seed::from(b"liquidity_pool"),
+            Seed::from(&liquidity_pool_bump_binding),
+        ];
+
+        expect_supply_change(
+            self.accounts.lp_mint,
+            SupplyChange::Minted(lp_to_mint),
+            || {
+                MintTo {
+                    mint: self.accounts.lp_mint,
+                    account: self.accounts.provider_lp_ata,
+                    mint_authority: self.accounts.liquidity_pool_pda,
+                    amount: lp_to_mint,
+                }
+                .invoke_signed(&[Signer::from(liquidity_pool_seeds)])
+            },
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{rent::Rent, Sysvar},
+};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    ids::{assert_system_program, assert_vote_account, STAKE_PROGRAM_ID},
+    instructions::helpers::{
+        check_config_pda, check_validator_stake, create_pda_account, AccountCheck, ProgramAccount,
+        ProgramAccountInit, SignerAccount, DEFAULT_STAKE_ACCOUNT_SPACE,
+    },
+    state::{Config, ValidatorList},
+};
+
+pub struct AddValidatorAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub validator_list_pda: &'a AccountInfo,
+    pub validator_stake_pda: &'a AccountInfo,
+    pub validator_vote_account: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for AddValidatorAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, validator_list_pda, validator_stake_pda, validator_vote_account, system_program, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        assert_system_program(system_program)?;
+        assert_vote_account(validator_vote_account)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            validator_list_pda,
+            validator_stake_pda,
+            validator_vote_account,
+            system_program,
+            pool_state_pda,
+        })
+    }
+}
+
+/// Appends a vote account to the validator list without a bond and creates
+/// its validator stake PDA (`b"validator_stake"`, vote account) as an
+/// uninitialized stake account, which `CrankValidatorStake` then funds from
+/// the reserve. The admin pays the PDA's rent. Creates the validator list PDA
+/// on first use. Fails with `ValidatorAlreadyListed` if the vote account is
+/// listed, and rejects the config's validator with `InvalidValidatorVoteKey`.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Admin
+/// 1. `[]` Config PDA
+/// 2. `[WRITE]` Validator list PDA
+/// 3. `[WRITE]` Validator stake PDA
+/// 4. `[]` Validator vote account
+/// 5. `[]` System program
+/// 6. `[WRITE]` Pool state PDA
+pub struct AddValidator<'a> {
+    pub accounts: AddValidatorAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for AddValidator<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            accounts: AddValidatorAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> AddValidator<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &79;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let vote_account = self.accounts.validator_vote_account.key();
+        if config.validator_vote_pubkey == *vote_account {
+            return Err(PinocchioError::InvalidValidatorVoteKey.into());
+        }
+        drop(config_data);
+
+        let (expected_validator_list_pda, validator_list_bump) =
+            find_program_address(&[b"validator_list"], &crate::ID);
+        if expected_validator_list_pda != *self.accounts.validator_list_pda.key() {
+            return Err(PinocchioError::InvalidValidatorListPda.into());
+        }
+
+        if self.accounts.validator_list_pda.data_is_empty() {
+            let validator_list_bump_binding = [validator_list_bump];
+            let validator_list_seeds = &[
+                Seed::from(b"validator_list"),
+                Seed::from(&validator_list_bump_binding),
+            ];
+            ProgramAccount::init::<ValidatorList>(
+                self.accounts.admin,
+                self.accounts.validator_list_pda,
+                validator_list_seeds,
+                ValidatorList::LEN,
+            )?;
+        }
+
+        let mut validator_list_data = self.accounts.validator_list_pda.try_borrow_mut_data()?;
+        let validator_list = ValidatorList::load_mut(&mut validator_list_data)?;
+        if validator_list.contains(vote_account) {
+            return Err(PinocchioError::ValidatorAlreadyListed.into());
+        }
+        if !validator_list.add(*vote_account) {
+            return Err(PinocchioError::ValidatorListFull.into());
+        }
+        drop(validator_list_data);
+
+        let validator_stake_bump =
+            check_validator_stake(self.accounts.validator_stake_pda, vote_account)?;
+
+        // A PDA left from an earlier listing is reused as it is.
+        if self
+            .accounts
+            .validator_stake_pda
+            .is_owned_by(&STAKE_PROGRAM_ID)
+        {
+            return Ok(());
+        }
+
+        let validator_stake_bump_binding = [validator_stake_bump];
+        let validator_stake_seeds = &[
+            Seed::from(b"validator_stake"),
+            Seed::from(vote_account),
+            Seed::from(&validator_stake_bump_binding),
+        ];
+        create_pda_account(
+            self.accounts.admin,
+            self.accounts.validator_stake_pda,
+            validator_stake_seeds,
+            Rent::get()?.minimum_balance(DEFAULT_STAKE_ACCOUNT_SPACE),
+            DEFAULT_STAKE_ACCOUNT_SPACE,
+            &STAKE_PROGRAM_ID,
+        )
+    }
+}
@@ -0,0 +1,139 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+};
+use pinocchio_system::instructions::{Allocate, Assign, Transfer};
+
+use crate::{
+    errors::PinocchioError,
+    ids::{assert_system_program, STAKE_PROGRAM_ID},
+    instructions::helpers::{
+        check_config_pda, stake_account_space, AccountCheck, SignerAccount, SystemAccount,
+    },
+    state::Config,
+};
+
+pub struct AdoptReserveAccounts<'a> {
+    pub payer: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for AdoptReserveAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [payer, config_pda, stake_account_main, stake_account_reserve, system_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(payer)?;
+
+        assert_system_program(system_program)?;
+
+        Ok(Self {
+            payer,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            system_program,
+        })
+    }
+}
+
+/// Turns whatever sits at the reserve address into an uninitialized stake
+/// account, so `CrankInitializeReserve` can delegate it. The reserve is a
+/// fixed PDA: after a merge it is drained and closed, and deposits (or anyone
+/// else) then send lamports to a plain system account there. Permissionless.
+///
+/// The lamports already at the address stay in the reserve. The payer funds
+/// the new account's rent-exempt reserve, which the exchange rate excludes,
+/// so adopting does not move the rate.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Payer
+/// 1. `[]` Config PDA
+/// 2. `[]` Stake account main
+/// 3. `[WRITE]` Stake account reserve
+/// 4. `[]` System program
+pub struct AdoptReserve<'a> {
+    pub accounts: AdoptReserveAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for AdoptReserve<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = AdoptReserveAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> AdoptReserve<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &51;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+        drop(config_data);
+
+        let reserve = self.accounts.stake_account_reserve;
+        if reserve.is_owned_by(&STAKE_PROGRAM_ID) {
+            return Err(PinocchioError::ReserveAlreadyInitialized.into());
+        }
+
+        SystemAccount::check(reserve)?;
+
+        if !reserve.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let space = stake_account_space(self.accounts.stake_account_main);
+
+        Transfer {
+            from: self.accounts.payer,
+            to: reserve,
+            lamports: Rent::get()?.minimum_balance(space),
+        }
+        .invoke()?;
+
+        let stake_reserve_bump =
+            Config::load(&self.accounts.config_pda.try_borrow_data()?)?.stake_reserve_bump;
+        let stake_reserve_bump_binding = [stake_reserve_bump];
+        let stake_reserve_seeds = &[
+            Seed::from(b"stake_reserve"),
+            Seed::from(&stake_reserve_bump_binding),
+        ];
+        let signer = [Signer::from(stake_reserve_seeds)];
+
+        Allocate {
+            account: reserve,
+            space: space as u64,
+        }
+        .invoke_signed(&signer)?;
+
+        Assign {
+            account: reserve,
+            owner: &STAKE_PROGRAM_ID,
+        }
+        .invoke_signed(&signer)
+    }
+}
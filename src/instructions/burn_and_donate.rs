@@ -0,0 +1,132 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+use pinocchio_token::instructions::Burn;
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit, EVENT_DONATE},
+    ids::assert_token_program,
+    instructions::helpers::{
+        check_config_pda, expect_lst_supply_change, AccountCheck, SignerAccount, SupplyChange,
+    },
+    state::Config,
+};
+
+pub struct BurnAndDonateAccounts<'a> {
+    pub donor: &'a AccountInfo,
+    pub donor_ata: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for BurnAndDonateAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [donor, donor_ata, lst_mint, config_pda, token_program, pool_state_pda] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(donor)?;
+
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            donor,
+            donor_ata,
+            lst_mint,
+            config_pda,
+            token_program,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct BurnAndDonateInstructionData {
+    pub lst_amount: u64,
+}
+
+impl TryFrom<&[u8]> for BurnAndDonateInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let lst_amount = u64::from_le_bytes(data.try_into().unwrap());
+        if lst_amount == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { lst_amount })
+    }
+}
+
+/// Burns the donor's LST without paying out any SOL. The pool's lamports stay
+/// the same while the supply shrinks, so the rate rises for every remaining
+/// holder. Logs an `EVENT_DONATE` so buyback-and-burn programs can account
+/// for it separately from splits.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Donor
+/// 1. `[WRITE]` Donor LST token account
+/// 2. `[WRITE]` LST mint
+/// 3. `[]` Config PDA
+/// 4. `[]` Token program
+/// 5. `[WRITE]` Pool state PDA
+pub struct BurnAndDonate<'a> {
+    pub accounts: BurnAndDonateAccounts<'a>,
+    pub data: BurnAndDonateInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for BurnAndDonate<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: BurnAndDonateAccounts::try_from(accounts)?,
+            data: BurnAndDonateInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> BurnAndDonate<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &28;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        if Config::load(&config_data)?.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        expect_lst_supply_change(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            SupplyChange::Burned(self.data.lst_amount),
+            || {
+                Burn {
+                    account: self.accounts.donor_ata,
+                    mint: self.accounts.lst_mint,
+                    authority: self.accounts.donor,
+                    amount: self.data.lst_amount,
+                }
+                .invoke()
+            },
+        )?;
+
+        emit(
+            self.accounts.pool_state_pda,
+            EVENT_DONATE,
+            &[
+                self.accounts.donor.key(),
+                &self.data.lst_amount.to_le_bytes(),
+            ],
+        )
+    }
+}
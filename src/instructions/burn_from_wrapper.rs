@@ -0,0 +1,238 @@
+use pinocchio::{account_info::AccountInfo, instruction::Seed, program_error::ProgramError};
+use pinocchio_token::{instructions::Burn, state::Mint};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit, EVENT_UNWRAP},
+    ids::{
+        assert_clock_sysvar, assert_stake_history_sysvar, assert_stake_program,
+        assert_token_program,
+    },
+    instructions::helpers::{
+        check_config_pda, check_wrapper, epoch_rate, expect_lst_supply_change, managed_lamports,
+        record_managed_change, reserve_spare_lamports, AccountCheck, ProgramAccount, SignerAccount,
+        StakeAccountWithdraw, SupplyChange,
+    },
+    math::lst_to_lamports,
+    state::{Config, ManagedChange},
+};
+
+pub struct BurnFromWrapperAccounts<'a> {
+    pub wrapper_authority: &'a AccountInfo,
+    pub wrapper_vault: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub clock_sysvar: &'a AccountInfo,
+    pub history_sysvar: &'a AccountInfo,
+    pub stake_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for BurnFromWrapperAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [wrapper_authority, wrapper_vault, config_pda, lst_mint, stake_account_main, stake_account_reserve, clock_sysvar, history_sysvar, stake_program, token_program, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(wrapper_authority)?;
+
+        assert_stake_program(stake_program)?;
+        assert_clock_sysvar(clock_sysvar)?;
+        assert_stake_history_sysvar(history_sysvar)?;
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            wrapper_authority,
+            wrapper_vault,
+            config_pda,
+            lst_mint,
+            stake_account_main,
+            stake_account_reserve,
+            clock_sysvar,
+            history_sysvar,
+            stake_program,
+            token_program,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct BurnFromWrapperInstructionData {
+    pub lst_amount: u64,
+    pub min_lamports_out: u64,
+}
+
+impl TryFrom<&[u8]> for BurnFromWrapperInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 16 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let lst_amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        if lst_amount == 0 {
+            return Err(PinocchioError::ZeroLiquidity.into());
+        }
+
+        Ok(Self {
+            lst_amount,
+            min_lamports_out: u64::from_le_bytes(data[8..16].try_into().unwrap()),
+        })
+    }
+}
+
+/// Burns LST from the approved wrapper program's vault and pays its value in
+/// SOL to the wrapper authority, so the wrapper can redeem its own token
+/// without a stake cooldown. Only callable by CPI from the wrapper program,
+/// signing with its `WRAPPER_AUTHORITY_SEED` PDA.
+///
+/// The LST is valued at the current exchange rate without a fee, and paid
+/// from the reserve's undelegated lamports above its rent. A delegated
+/// reserve fails with `ReserveDelegated` and a short one with
+/// `InsufficientLiquidity`; the wrapper can fall back to `CrankSplit`.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Wrapper authority PDA (receives the lamports)
+/// 1. `[WRITE]` Wrapper vault (the authority's LST ATA)
+/// 2. `[]` Config PDA
+/// 3. `[WRITE]` LST mint
+/// 4. `[]` Stake account main
+/// 5. `[WRITE]` Stake account reserve
+/// 6. `[]` Clock sysvar
+/// 7. `[]` History sysvar
+/// 8. `[]` Stake program
+/// 9. `[]` Token program
+/// 10. `[WRITE]` Pool state PDA
+pub struct BurnFromWrapper<'a> {
+    pub accounts: BurnFromWrapperAccounts<'a>,
+    pub data: BurnFromWrapperInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for BurnFromWrapper<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: BurnFromWrapperAccounts::try_from(accounts)?,
+            data: BurnFromWrapperInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> BurnFromWrapper<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &49;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        config.status()?.check_unstakes()?;
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        check_wrapper(
+            &config.wrapper_program,
+            self.accounts.wrapper_authority,
+            self.accounts.wrapper_vault,
+            self.accounts.lst_mint,
+            self.accounts.token_program,
+        )?;
+        let validator_stake_lamports = config.validator_stake_lamports;
+        let cached_rate = epoch_rate(config)?;
+        drop(config_data);
+
+        let (total_lamports, lst_supply) = cached_rate.unwrap_or((
+            managed_lamports(
+                self.accounts.pool_state_pda,
+                self.accounts.stake_account_main,
+                self.accounts.stake_account_reserve,
+                validator_stake_lamports,
+            )?,
+            Mint::from_account_info(self.accounts.lst_mint)?.supply(),
+        ));
+        let lamports_out = lst_to_lamports(self.data.lst_amount, lst_supply, total_lamports)?;
+
+        if lamports_out == 0 {
+            return Err(PinocchioError::ZeroLiquidity.into());
+        }
+
+        if lamports_out < self.data.min_lamports_out {
+            return Err(PinocchioError::SlippageExceeded.into());
+        }
+
+        let reserve = self.accounts.stake_account_reserve;
+        let reserve_spare = reserve_spare_lamports(reserve, PinocchioError::InsufficientLiquidity)?;
+        if lamports_out > reserve_spare {
+            return Err(PinocchioError::InsufficientLiquidity.into());
+        }
+
+        expect_lst_supply_change(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            SupplyChange::Burned(self.data.lst_amount),
+            || {
+                Burn {
+                    account: self.accounts.wrapper_vault,
+                    mint: self.accounts.lst_mint,
+                    authority: self.accounts.wrapper_authority,
+                    amount: self.data.lst_amount,
+                }
+                .invoke()
+            },
+        )?;
+
+        let stake_reserve_bump =
+            Config::load(&self.accounts.config_pda.try_borrow_data()?)?.stake_reserve_bump;
+        let stake_reserve_bump_binding = [stake_reserve_bump];
+        let stake_reserve_seeds = &[
+            Seed::from(b"stake_reserve"),
+            Seed::from(&stake_reserve_bump_binding),
+        ];
+
+        // An uninitialized stake account is its own withdraw authority.
+        ProgramAccount::withdraw_stake_account_lamports(
+            reserve,
+            self.accounts.wrapper_authority,
+            self.accounts.clock_sysvar,
+            self.accounts.history_sysvar,
+            reserve,
+            lamports_out,
+            stake_reserve_seeds,
+        )?;
+        record_managed_change(
+            self.accounts.pool_state_pda,
+            ManagedChange::Withdrawn(lamports_out),
+        )?;
+
+        emit(
+            self.accounts.pool_state_pda,
+            EVENT_UNWRAP,
+            &[
+                self.accounts.wrapper_authority.key(),
+                &self.data.lst_amount.to_le_bytes(),
+                &lamports_out.to_le_bytes(),
+            ],
+        )
+    }
+}
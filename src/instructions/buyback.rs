@@ -0,0 +1,231 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::find_program_address,
+};
+use pinocchio_system::instructions::Transfer;
+use pinocchio_token::{instructions::MintTo, state::Mint};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit, EVENT_DEPOSIT, EVENT_DONATE},
+    ids::{assert_system_program, assert_token_program, ASSOCIATED_TOKEN_PROGRAM_ID},
+    instructions::helpers::{
+        check_config_pda, check_pool_cap, epoch_rate, expect_lst_supply_change, managed_lamports,
+        pool_lamports, record_managed_change, record_principal, SupplyChange,
+    },
+    math::lamports_to_lst,
+    state::{Config, ManagedChange, BUYBACK_BURN},
+};
+
+pub struct BuybackAccounts<'a> {
+    pub config_pda: &'a AccountInfo,
+    pub fee_vault_pda: &'a AccountInfo,
+    pub treasury_ata: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for BuybackAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [config_pda, fee_vault_pda, treasury_ata, lst_mint, stake_account_main, stake_account_reserve, token_program, system_program, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        assert_token_program(token_program)?;
+        assert_system_program(system_program)?;
+
+        Ok(Self {
+            config_pda,
+            fee_vault_pda,
+            treasury_ata,
+            lst_mint,
+            stake_account_main,
+            stake_account_reserve,
+            token_program,
+            system_program,
+            pool_state_pda,
+        })
+    }
+}
+
+/// Buys LST with the SOL in the fee vault through the deposit path.
+///
+/// The fee vault is a system-owned PDA (`b"fee_vault"`) that collects the
+/// protocol's SOL fees. Its whole balance moves into the reserve at the
+/// current exchange rate. Under `BUYBACK_TO_TREASURY` the LST is minted to
+/// the treasury ATA and logged as a deposit by the fee vault. Under
+/// `BUYBACK_BURN` nothing is minted, which is the same as minting and burning
+/// it: the SOL accrues to every holder, and it is logged as a donation of the
+/// LST the deposit would have minted. Permissionless.
+///
+/// A buyback to the treasury is a deposit and fails with `PoolCapExceeded`
+/// past the pool cap. A burn mints nothing and, like rewards, is exempt.
+///
+/// Accounts expected:
+///
+/// 0. `[]` Config PDA
+/// 1. `[WRITE]` Fee vault PDA
+/// 2. `[WRITE]` Treasury ATA (config PDA's LST token account)
+/// 3. `[WRITE]` LST mint
+/// 4. `[]` Stake account main
+/// 5. `[WRITE]` Stake account reserve
+/// 6. `[]` Token program
+/// 7. `[]` System program
+/// 8. `[WRITE]` Pool state PDA
+pub struct Buyback<'a> {
+    pub accounts: BuybackAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for Buyback<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            accounts: BuybackAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> Buyback<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &41;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let bump = check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        config.status()?.check_deposits()?;
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        let expected_treasury_ata = find_program_address(
+            &[
+                self.accounts.config_pda.key(),
+                self.accounts.token_program.key(),
+                &config.lst_mint,
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .0;
+        if expected_treasury_ata != *self.accounts.treasury_ata.key() {
+            return Err(PinocchioError::InvalidTreasuryAta.into());
+        }
+
+        let buyback_policy = config.buyback_policy;
+        let validator_stake_lamports = config.validator_stake_lamports;
+        let max_pool_lamports = config.max_pool_lamports;
+        let cached_rate = epoch_rate(config)?;
+        drop(config_data);
+
+        let (expected_fee_vault_pda, fee_vault_bump) =
+            find_program_address(&[b"fee_vault"], &crate::ID);
+        if expected_fee_vault_pda != *self.accounts.fee_vault_pda.key() {
+            return Err(PinocchioError::InvalidFeeVaultPda.into());
+        }
+
+        let fees = self.accounts.fee_vault_pda.lamports();
+        if fees == 0 {
+            return Err(PinocchioError::NoFeesToBuyBack.into());
+        }
+
+        let live_pool_lamports = pool_lamports(
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+        if buyback_policy != BUYBACK_BURN {
+            check_pool_cap(max_pool_lamports, live_pool_lamports, fees)?;
+        }
+
+        let managed = managed_lamports(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+
+        let (total_sol_in_pool, total_lst_supply) = cached_rate.unwrap_or((
+            managed,
+            Mint::from_account_info(self.accounts.lst_mint)?.supply(),
+        ));
+        let lst_bought = lamports_to_lst(fees, total_lst_supply, total_sol_in_pool)?;
+
+        let fee_vault_bump_binding = [fee_vault_bump];
+        let fee_vault_seeds = &[
+            Seed::from(b"fee_vault"),
+            Seed::from(&fee_vault_bump_binding),
+        ];
+
+        Transfer {
+            from: self.accounts.fee_vault_pda,
+            to: self.accounts.stake_account_reserve,
+            lamports: fees,
+        }
+        .invoke_signed(&[Signer::from(fee_vault_seeds)])?;
+        record_managed_change(self.accounts.pool_state_pda, ManagedChange::Deposited(fees))?;
+
+        if buyback_policy == BUYBACK_BURN {
+            return emit(
+                self.accounts.pool_state_pda,
+                EVENT_DONATE,
+                &[self.accounts.fee_vault_pda.key(), &lst_bought.to_le_bytes()],
+            );
+        }
+
+        let bump_binding = [bump];
+        let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
+
+        expect_lst_supply_change(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            SupplyChange::Minted(lst_bought),
+            || {
+                MintTo {
+                    mint: self.accounts.lst_mint,
+                    account: self.accounts.treasury_ata,
+                    mint_authority: self.accounts.config_pda,
+                    amount: lst_bought,
+                }
+                .invoke_signed(&[Signer::from(config_seeds)])
+            },
+        )?;
+
+        record_principal(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            fees,
+            lst_bought,
+        )?;
+
+        emit(
+            self.accounts.pool_state_pda,
+            EVENT_DEPOSIT,
+            &[
+                self.accounts.fee_vault_pda.key(),
+                &fees.to_le_bytes(),
+                &lst_bought.to_le_bytes(),
+            ],
+        )
+    }
+}
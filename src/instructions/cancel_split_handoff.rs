@@ -0,0 +1,122 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit, emit_admin_action, EVENT_SPLIT_HANDOFF, HANDOFF_CANCELLED, ROLE_ADMIN},
+    instructions::helpers::{
+        check_config_pda, check_split_handoff, AccountCheck, AccountClose, ProgramAccount,
+        SignerAccount,
+    },
+    state::{Config, SplitHandoff, SplitRecord},
+};
+
+pub struct CancelSplitHandoffAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub proposer: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub split_record: &'a AccountInfo,
+    pub handoff_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CancelSplitHandoffAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, proposer, config_pda, split_record, handoff_pda, pool_state_pda] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(authority)?;
+
+        Ok(Self {
+            authority,
+            proposer,
+            config_pda,
+            split_record,
+            handoff_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+/// Cancels a pending split handoff and refunds its rent to the admin who
+/// proposed it. The split's current owner or the admin may cancel, at any
+/// time before `ExecuteSplitHandoff`. The admin can cancel even after the
+/// split record was closed by a `Withdraw`.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Split owner or admin
+/// 1. `[WRITE]` Proposer (the handoff's rent payer)
+/// 2. `[]` Config PDA
+/// 3. `[]` Split record PDA
+/// 4. `[WRITE]` Split handoff PDA
+/// 5. `[WRITE]` Pool state PDA
+pub struct CancelSplitHandoff<'a> {
+    pub accounts: CancelSplitHandoffAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CancelSplitHandoff<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: CancelSplitHandoffAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> CancelSplitHandoff<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &65;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        check_split_handoff(self.accounts.handoff_pda, self.accounts.split_record)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let is_admin = Config::load(&config_data)?.admin == *self.accounts.authority.key();
+        drop(config_data);
+
+        if is_admin {
+            emit_admin_action(
+                self.accounts.pool_state_pda,
+                self.accounts.authority,
+                ROLE_ADMIN,
+                *Self::DISCRIMINATOR,
+            )?;
+        } else {
+            ProgramAccount::check(self.accounts.split_record)?;
+
+            let split_record_data = self.accounts.split_record.try_borrow_data()?;
+            if SplitRecord::load(&split_record_data)?.owner != *self.accounts.authority.key() {
+                return Err(PinocchioError::NotSplitOwnerOrAdmin.into());
+            }
+        }
+
+        let handoff_data = self.accounts.handoff_pda.try_borrow_data()?;
+        let handoff = SplitHandoff::load(&handoff_data)?;
+        if handoff.proposer != *self.accounts.proposer.key() {
+            return Err(PinocchioError::InvalidRentPayer.into());
+        }
+        let split_account = handoff.split_account;
+        let new_owner = handoff.new_owner;
+        let executable_at = handoff.executable_at;
+        drop(handoff_data);
+
+        ProgramAccount::close(self.accounts.handoff_pda, self.accounts.proposer)?;
+
+        emit(
+            self.accounts.pool_state_pda,
+            EVENT_SPLIT_HANDOFF,
+            &[
+                &split_account,
+                &new_owner,
+                &[HANDOFF_CANCELLED],
+                &executable_at.to_le_bytes(),
+            ],
+        )
+    }
+}
@@ -0,0 +1,130 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+};
+use pinocchio_token::instructions::Transfer;
+
+use crate::{
+    ids::assert_token_program,
+    instructions::helpers::{
+        check_unstake_order, close_unstake_order, AccountCheck, SignerAccount,
+    },
+    state::UnstakeOrder,
+};
+
+pub struct CancelUnstakeOrderAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub owner_ata: &'a AccountInfo,
+    pub order_pda: &'a AccountInfo,
+    pub order_vault: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CancelUnstakeOrderAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [owner, owner_ata, order_pda, order_vault, token_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(owner)?;
+
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            owner,
+            owner_ata,
+            order_pda,
+            order_vault,
+            token_program,
+        })
+    }
+}
+
+pub struct CancelUnstakeOrderInstructionData {
+    pub nonce: u64,
+}
+
+impl TryFrom<&[u8]> for CancelUnstakeOrderInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            nonce: u64::from_le_bytes(data.try_into().unwrap()),
+        })
+    }
+}
+
+/// Cancels an unfilled unstake order, returning the locked LST to the
+/// owner's ATA and the tip and the rent of the order PDA and its vault to the
+/// owner.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Owner
+/// 1. `[WRITE]` Owner LST ATA
+/// 2. `[WRITE]` Unstake order PDA
+/// 3. `[WRITE]` Order vault
+/// 4. `[]` Token program
+pub struct CancelUnstakeOrder<'a> {
+    pub accounts: CancelUnstakeOrderAccounts<'a>,
+    pub data: CancelUnstakeOrderInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CancelUnstakeOrder<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: CancelUnstakeOrderAccounts::try_from(accounts)?,
+            data: CancelUnstakeOrderInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> CancelUnstakeOrder<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &59;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let order_bump = check_unstake_order(
+            self.accounts.order_pda,
+            self.accounts.order_vault,
+            self.accounts.owner,
+            self.data.nonce,
+        )?;
+
+        let order_data = self.accounts.order_pda.try_borrow_data()?;
+        let lst_amount = UnstakeOrder::load(&order_data)?.lst_amount;
+        drop(order_data);
+
+        let nonce_bytes = self.data.nonce.to_le_bytes();
+        let order_bump_binding = [order_bump];
+        let order_seeds = &[
+            Seed::from(b"unstake_order"),
+            Seed::from(self.accounts.owner.key()),
+            Seed::from(&nonce_bytes),
+            Seed::from(&order_bump_binding),
+        ];
+
+        Transfer {
+            from: self.accounts.order_vault,
+            to: self.accounts.owner_ata,
+            authority: self.accounts.order_pda,
+            amount: lst_amount,
+        }
+        .invoke_signed(&[Signer::from(order_seeds)])?;
+
+        close_unstake_order(
+            self.accounts.order_pda,
+            self.accounts.order_vault,
+            self.accounts.owner,
+            order_seeds,
+        )
+    }
+}
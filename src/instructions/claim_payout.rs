@@ -0,0 +1,97 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::find_program_address,
+};
+
+use crate::{
+    errors::PinocchioError,
+    instructions::helpers::{check_payout_position, AccountCheck, SignerAccount},
+    state::{PayoutPosition, RewardPayout},
+};
+
+pub struct ClaimPayoutAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub position_pda: &'a AccountInfo,
+    pub reward_payout_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ClaimPayoutAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [owner, position_pda, reward_payout_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(owner)?;
+
+        Ok(Self {
+            owner,
+            position_pda,
+            reward_payout_pda,
+        })
+    }
+}
+
+/// Pays the SOL accrued to the owner's payout position out of the reward
+/// payout PDA. Fails with `NoPayoutToClaim` when nothing has accrued since
+/// the last claim.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Owner
+/// 1. `[WRITE]` Payout position PDA
+/// 2. `[WRITE]` Reward payout PDA
+pub struct ClaimPayout<'a> {
+    pub accounts: ClaimPayoutAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ClaimPayout<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            accounts: ClaimPayoutAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> ClaimPayout<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &76;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let reward_payout_pda = self.accounts.reward_payout_pda;
+        let (expected_reward_payout_pda, _) = find_program_address(&[b"reward_payout"], &crate::ID);
+        if expected_reward_payout_pda != *reward_payout_pda.key()
+            || !reward_payout_pda.is_owned_by(&crate::ID)
+        {
+            return Err(PinocchioError::InvalidRewardPayoutPda.into());
+        }
+        check_payout_position(self.accounts.position_pda, self.accounts.owner)?;
+
+        let mut reward_payout_data = reward_payout_pda.try_borrow_mut_data()?;
+        let reward_payout = RewardPayout::load_mut(&mut reward_payout_data)?;
+
+        let mut position_data = self.accounts.position_pda.try_borrow_mut_data()?;
+        let position = PayoutPosition::load_mut(&mut position_data)?;
+        position.settle(reward_payout.rewards_per_share)?;
+
+        // Rounding down in `distribute` and `settle` keeps the claims within
+        // what was paid in.
+        let claimed = position
+            .claimable_lamports
+            .min(reward_payout.unclaimed_lamports);
+        if claimed == 0 {
+            return Err(PinocchioError::NoPayoutToClaim.into());
+        }
+
+        position.claimable_lamports -= claimed;
+        reward_payout.unclaimed_lamports -= claimed;
+        drop(position_data);
+        drop(reward_payout_data);
+
+        *reward_payout_pda.try_borrow_mut_lamports()? -= claimed;
+        *self.accounts.owner.try_borrow_mut_lamports()? += claimed;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,238 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+use pinocchio_token::{instructions::MintTo, state::Mint};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit, EVENT_DEPOSIT},
+    ids::{assert_token_program, ASSOCIATED_TOKEN_PROGRAM_ID},
+    instructions::helpers::{
+        check_config_pda, check_pool_cap, epoch_rate, expect_lst_supply_change, managed_lamports,
+        pool_lamports, record_inflow, record_managed_change, record_principal, AccountClose,
+        ProgramAccount, SupplyChange,
+    },
+    math::lamports_to_lst,
+    state::{Config, ManagedChange, QueuedDeposit},
+};
+
+pub struct ClaimQueuedDepositAccounts<'a> {
+    pub config_pda: &'a AccountInfo,
+    pub queued_deposit_pda: &'a AccountInfo,
+    pub depositor: &'a AccountInfo,
+    pub depositor_ata: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ClaimQueuedDepositAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [config_pda, queued_deposit_pda, depositor, depositor_ata, lst_mint, stake_account_main, stake_account_reserve, token_program, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            config_pda,
+            queued_deposit_pda,
+            depositor,
+            depositor_ata,
+            lst_mint,
+            stake_account_main,
+            stake_account_reserve,
+            token_program,
+            pool_state_pda,
+        })
+    }
+}
+
+/// Deposits lamports queued by `Deposit` beyond an earlier epoch's deposit
+/// cap. Permissionless.
+///
+/// As much of the queue as fits under this epoch's cap moves into the
+/// reserve, and LST is minted to the depositor's ATA at the current rate.
+/// Whatever does not fit stays queued for a later claim. A claim that would
+/// push the pool past its cap fails with `PoolCapExceeded`. Once the queue is
+/// empty the PDA is closed and its rent returned to the depositor.
+///
+/// Accounts expected:
+///
+/// 0. `[]` Config PDA
+/// 1. `[WRITE]` Queued deposit PDA
+/// 2. `[WRITE]` Depositor
+/// 3. `[WRITE]` Depositor ATA
+/// 4. `[WRITE]` LST mint
+/// 5. `[]` Stake account main
+/// 6. `[WRITE]` Stake account reserve
+/// 7. `[]` Token program
+/// 8. `[WRITE]` Pool state PDA
+pub struct ClaimQueuedDeposit<'a> {
+    pub accounts: ClaimQueuedDepositAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ClaimQueuedDeposit<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            accounts: ClaimQueuedDepositAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> ClaimQueuedDeposit<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &44;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let bump = check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        config.status()?.check_deposits()?;
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        let deposit_cap_lamports = config.deposit_cap_lamports;
+        let max_pool_lamports = config.max_pool_lamports;
+        let validator_stake_lamports = config.validator_stake_lamports;
+        let cached_rate = epoch_rate(config)?;
+        drop(config_data);
+
+        let expected_queued_deposit_pda = find_program_address(
+            &[b"queued_deposit", self.accounts.depositor.key()],
+            &crate::ID,
+        )
+        .0;
+        if expected_queued_deposit_pda != *self.accounts.queued_deposit_pda.key()
+            || !self.accounts.queued_deposit_pda.is_owned_by(&crate::ID)
+        {
+            return Err(PinocchioError::InvalidQueuedDepositPda.into());
+        }
+
+        let expected_ata = find_program_address(
+            &[
+                self.accounts.depositor.key(),
+                self.accounts.token_program.key(),
+                self.accounts.lst_mint.key(),
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .0;
+        if expected_ata != *self.accounts.depositor_ata.key() {
+            return Err(PinocchioError::InvalidDepositorAta.into());
+        }
+
+        let mut queued_deposit_data = self.accounts.queued_deposit_pda.try_borrow_mut_data()?;
+        let queued_deposit = QueuedDeposit::load_mut(&mut queued_deposit_data)?;
+
+        if Clock::get()?.epoch <= queued_deposit.epoch {
+            return Err(PinocchioError::QueuedDepositNotReady.into());
+        }
+
+        let deposited = record_inflow(
+            self.accounts.pool_state_pda,
+            deposit_cap_lamports,
+            queued_deposit.lamports,
+            true,
+        )?;
+        if deposited == 0 {
+            return Err(PinocchioError::DepositCapExceeded.into());
+        }
+
+        queued_deposit.lamports -= deposited;
+        let emptied = queued_deposit.lamports == 0;
+        drop(queued_deposit_data);
+
+        let live_pool_lamports = pool_lamports(
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+        check_pool_cap(max_pool_lamports, live_pool_lamports, deposited)?;
+        let managed = managed_lamports(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+        let (total_sol_in_pool, total_lst_supply) = cached_rate.unwrap_or((
+            managed,
+            Mint::from_account_info(self.accounts.lst_mint)?.supply(),
+        ));
+        let lst_to_mint = lamports_to_lst(deposited, total_lst_supply, total_sol_in_pool)?;
+
+        // The queued deposit PDA is program-owned, so its lamports are moved
+        // directly.
+        *self.accounts.queued_deposit_pda.try_borrow_mut_lamports()? -= deposited;
+        *self
+            .accounts
+            .stake_account_reserve
+            .try_borrow_mut_lamports()? += deposited;
+        record_managed_change(
+            self.accounts.pool_state_pda,
+            ManagedChange::Deposited(deposited),
+        )?;
+
+        let bump_binding = [bump];
+        let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
+
+        expect_lst_supply_change(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            SupplyChange::Minted(lst_to_mint),
+            || {
+                MintTo {
+                    mint: self.accounts.lst_mint,
+                    account: self.accounts.depositor_ata,
+                    mint_authority: self.accounts.config_pda,
+                    amount: lst_to_mint,
+                }
+                .invoke_signed(&[Signer::from(config_seeds)])
+            },
+        )?;
+
+        record_principal(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            deposited,
+            lst_to_mint,
+        )?;
+
+        if emptied {
+            ProgramAccount::close(self.accounts.queued_deposit_pda, self.accounts.depositor)?;
+        }
+
+        emit(
+            self.accounts.pool_state_pda,
+            EVENT_DEPOSIT,
+            &[
+                self.accounts.depositor.key(),
+                &deposited.to_le_bytes(),
+                &lst_to_mint.to_le_bytes(),
+            ],
+        )
+    }
+}
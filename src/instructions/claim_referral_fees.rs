@@ -0,0 +1,128 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::find_program_address,
+};
+use pinocchio_token::instructions::Transfer;
+
+use crate::{
+    errors::PinocchioError,
+    ids::{assert_token_program, ASSOCIATED_TOKEN_PROGRAM_ID},
+    instructions::helpers::{check_config_pda, AccountCheck, SignerAccount},
+    state::{Config, ReferrerRecord},
+};
+
+pub struct ClaimReferralFeesAccounts<'a> {
+    pub referrer: &'a AccountInfo,
+    pub referrer_ata: &'a AccountInfo,
+    pub referrer_pda: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub treasury_ata: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ClaimReferralFeesAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [referrer, referrer_ata, referrer_pda, config_pda, treasury_ata, token_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(referrer)?;
+
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            referrer,
+            referrer_ata,
+            referrer_pda,
+            config_pda,
+            treasury_ata,
+            token_program,
+        })
+    }
+}
+
+/// Transfers the referrer's unclaimed referral fees from the treasury ATA,
+/// where referred deposits mint them, to an LST token account of theirs.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Referrer
+/// 1. `[WRITE]` Referrer LST token account
+/// 2. `[WRITE]` Referrer PDA
+/// 3. `[]` Config PDA
+/// 4. `[WRITE]` Treasury ATA
+/// 5. `[]` Token program
+pub struct ClaimReferralFees<'a> {
+    pub accounts: ClaimReferralFeesAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ClaimReferralFees<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = ClaimReferralFeesAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> ClaimReferralFees<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &54;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let bump = check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        let expected_treasury_ata = find_program_address(
+            &[
+                self.accounts.config_pda.key(),
+                self.accounts.token_program.key(),
+                &config.lst_mint,
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .0;
+        if expected_treasury_ata != *self.accounts.treasury_ata.key() {
+            return Err(PinocchioError::InvalidTreasuryAta.into());
+        }
+        drop(config_data);
+
+        let (expected_referrer_pda, _) =
+            find_program_address(&[b"referrer", self.accounts.referrer.key()], &crate::ID);
+        if expected_referrer_pda != *self.accounts.referrer_pda.key()
+            || !self.accounts.referrer_pda.is_owned_by(&crate::ID)
+        {
+            return Err(PinocchioError::InvalidReferrerPda.into());
+        }
+
+        let mut referrer_data = self.accounts.referrer_pda.try_borrow_mut_data()?;
+        let referrer = ReferrerRecord::load_mut(&mut referrer_data)?;
+
+        let unclaimed = referrer.unclaimed_fees();
+        if unclaimed == 0 {
+            return Err(PinocchioError::NoReferralFees.into());
+        }
+
+        referrer.fees_claimed = referrer.fees_earned;
+        drop(referrer_data);
+
+        let bump_binding = [bump];
+        let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
+
+        Transfer {
+            from: self.accounts.treasury_ata,
+            to: self.accounts.referrer_ata,
+            authority: self.accounts.config_pda,
+            amount: unclaimed,
+        }
+        .invoke_signed(&[Signer::from(config_seeds)])
+    }
+}
@@ -0,0 +1,146 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+use pinocchio_token::instructions::Transfer;
+
+use crate::{
+    errors::PinocchioError,
+    ids::assert_token_program,
+    instructions::helpers::{AccountCheck, SignerAccount},
+    math::vested_amount,
+    state::VestingEscrow,
+};
+
+pub struct ClaimVestedAccounts<'a> {
+    pub beneficiary: &'a AccountInfo,
+    pub beneficiary_ata: &'a AccountInfo,
+    pub vesting_pda: &'a AccountInfo,
+    pub vesting_vault: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ClaimVestedAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [beneficiary, beneficiary_ata, vesting_pda, vesting_vault, token_program] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(beneficiary)?;
+
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            beneficiary,
+            beneficiary_ata,
+            vesting_pda,
+            vesting_vault,
+            token_program,
+        })
+    }
+}
+
+pub struct ClaimVestedInstructionData {
+    pub nonce: u64,
+}
+
+impl TryFrom<&[u8]> for ClaimVestedInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            nonce: u64::from_le_bytes(data.try_into().unwrap()),
+        })
+    }
+}
+
+/// Transfers the LST vested since the last claim from a vesting escrow to
+/// the beneficiary.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Beneficiary
+/// 1. `[WRITE]` Beneficiary LST token account
+/// 2. `[WRITE]` Vesting escrow PDA
+/// 3. `[WRITE]` Vesting vault
+/// 4. `[]` Token program
+pub struct ClaimVested<'a> {
+    pub accounts: ClaimVestedAccounts<'a>,
+    pub data: ClaimVestedInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for ClaimVested<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: ClaimVestedAccounts::try_from(accounts)?,
+            data: ClaimVestedInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> ClaimVested<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &31;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let nonce_bytes = self.data.nonce.to_le_bytes();
+        let (expected_vesting_pda, vesting_bump) = find_program_address(
+            &[b"vesting", self.accounts.beneficiary.key(), &nonce_bytes],
+            &crate::ID,
+        );
+        if expected_vesting_pda != *self.accounts.vesting_pda.key()
+            || !self.accounts.vesting_pda.is_owned_by(&crate::ID)
+        {
+            return Err(PinocchioError::InvalidVestingPda.into());
+        }
+
+        let mut vesting_data = self.accounts.vesting_pda.try_borrow_mut_data()?;
+        let vesting = VestingEscrow::load_mut(&mut vesting_data)?;
+
+        if vesting.vault != *self.accounts.vesting_vault.key() {
+            return Err(PinocchioError::InvalidVestingVault.into());
+        }
+
+        let vested = vested_amount(
+            vesting.total_amount,
+            vesting.start_ts,
+            vesting.cliff_ts,
+            vesting.end_ts,
+            Clock::get()?.unix_timestamp,
+        );
+        let claimable = vested.saturating_sub(vesting.claimed_amount);
+        if claimable == 0 {
+            return Err(PinocchioError::NothingVested.into());
+        }
+
+        vesting.claimed_amount = vested;
+        drop(vesting_data);
+
+        let vesting_bump_binding = [vesting_bump];
+        let vesting_seeds = &[
+            Seed::from(b"vesting"),
+            Seed::from(self.accounts.beneficiary.key()),
+            Seed::from(&nonce_bytes),
+            Seed::from(&vesting_bump_binding),
+        ];
+
+        Transfer {
+            from: self.accounts.vesting_vault,
+            to: self.accounts.beneficiary_ata,
+            authority: self.accounts.vesting_pda,
+            amount: claimable,
+        }
+        .invoke_signed(&[Signer::from(vesting_seeds)])
+    }
+}
@@ -0,0 +1,68 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    errors::PinocchioError,
+    instructions::helpers::{AccountCheck, AccountClose, ProgramAccount, SignerAccount},
+    state::DcaSchedule,
+};
+
+pub struct CloseDcaScheduleAccounts<'a> {
+    pub user: &'a AccountInfo,
+    pub dca_schedule_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CloseDcaScheduleAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [user, dca_schedule_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(user)?;
+
+        Ok(Self {
+            user,
+            dca_schedule_pda,
+        })
+    }
+}
+
+/// Cancels a DCA schedule, returning the remaining budget and the rent of
+/// the schedule PDA to the user.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` User
+/// 1. `[WRITE]` DCA schedule PDA
+pub struct CloseDcaSchedule<'a> {
+    pub accounts: CloseDcaScheduleAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CloseDcaSchedule<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: CloseDcaScheduleAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> CloseDcaSchedule<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &37;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        if !self.accounts.dca_schedule_pda.is_owned_by(&crate::ID) {
+            return Err(PinocchioError::InvalidDcaSchedulePda.into());
+        }
+
+        let dca_schedule_data = self.accounts.dca_schedule_pda.try_borrow_data()?;
+        if DcaSchedule::load(&dca_schedule_data)?.user != *self.accounts.user.key() {
+            return Err(PinocchioError::InvalidDcaSchedulePda.into());
+        }
+        drop(dca_schedule_data);
+
+        ProgramAccount::close(self.accounts.dca_schedule_pda, self.accounts.user)
+    }
+}
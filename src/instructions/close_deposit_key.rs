@@ -0,0 +1,109 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    errors::PinocchioError,
+    instructions::helpers::{AccountClose, ProgramAccount},
+    state::DepositKey,
+};
+
+/// Slots a deposit key is kept for. A transaction's blockhash expires after
+/// about 150 slots, so by then a retried deposit can no longer land.
+pub const DEPOSIT_KEY_TTL_SLOTS: u64 = 300;
+
+pub struct CloseDepositKeyAccounts<'a> {
+    pub depositor: &'a AccountInfo,
+    pub deposit_key_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CloseDepositKeyAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [depositor, deposit_key_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            depositor,
+            deposit_key_pda,
+        })
+    }
+}
+
+pub struct CloseDepositKeyInstructionData {
+    pub idempotency_key: [u8; 32],
+}
+
+impl TryFrom<&[u8]> for CloseDepositKeyInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 32 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            idempotency_key: data.try_into().unwrap(),
+        })
+    }
+}
+
+/// Closes a deposit key PDA once `DEPOSIT_KEY_TTL_SLOTS` have passed since
+/// the deposit, refunding its rent to the depositor. Permissionless.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE]` Depositor
+/// 1. `[WRITE]` Deposit key PDA
+pub struct CloseDepositKey<'a> {
+    pub accounts: CloseDepositKeyAccounts<'a>,
+    pub data: CloseDepositKeyInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CloseDepositKey<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: CloseDepositKeyAccounts::try_from(accounts)?,
+            data: CloseDepositKeyInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> CloseDepositKey<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &27;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let (expected_deposit_key_pda, _) = find_program_address(
+            &[
+                b"deposit_key",
+                self.accounts.depositor.key(),
+                &self.data.idempotency_key,
+            ],
+            &crate::ID,
+        );
+        if expected_deposit_key_pda != *self.accounts.deposit_key_pda.key()
+            || !self.accounts.deposit_key_pda.is_owned_by(&crate::ID)
+        {
+            return Err(PinocchioError::InvalidDepositKeyPda.into());
+        }
+
+        let deposit_key_data = self.accounts.deposit_key_pda.try_borrow_data()?;
+        let expires_at = DepositKey::load(&deposit_key_data)?
+            .created_slot
+            .saturating_add(DEPOSIT_KEY_TTL_SLOTS);
+        drop(deposit_key_data);
+
+        if Clock::get()?.slot < expires_at {
+            return Err(PinocchioError::DepositKeyNotExpired.into());
+        }
+
+        ProgramAccount::close(self.accounts.deposit_key_pda, self.accounts.depositor)
+    }
+}
@@ -0,0 +1,69 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    errors::PinocchioError,
+    instructions::helpers::{AccountCheck, AccountClose, ProgramAccount, SignerAccount},
+    state::DepositSession,
+};
+
+pub struct CloseDepositSessionAccounts<'a> {
+    pub user: &'a AccountInfo,
+    pub deposit_session_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CloseDepositSessionAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [user, deposit_session_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(user)?;
+
+        Ok(Self {
+            user,
+            deposit_session_pda,
+        })
+    }
+}
+
+/// Revokes a session key, returning the unspent budget and the rent of the
+/// deposit session PDA to the user. Works whether or not the session has
+/// expired.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` User
+/// 1. `[WRITE]` Deposit session PDA
+pub struct CloseDepositSession<'a> {
+    pub accounts: CloseDepositSessionAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CloseDepositSession<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: CloseDepositSessionAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> CloseDepositSession<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &34;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        if !self.accounts.deposit_session_pda.is_owned_by(&crate::ID) {
+            return Err(PinocchioError::InvalidDepositSessionPda.into());
+        }
+
+        let deposit_session_data = self.accounts.deposit_session_pda.try_borrow_data()?;
+        if DepositSession::load(&deposit_session_data)?.user != *self.accounts.user.key() {
+            return Err(PinocchioError::InvalidDepositSessionPda.into());
+        }
+        drop(deposit_session_data);
+
+        ProgramAccount::close(self.accounts.deposit_session_pda, self.accounts.user)
+    }
+}
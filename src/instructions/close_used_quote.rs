@@ -0,0 +1,104 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    errors::PinocchioError,
+    instructions::helpers::{AccountClose, ProgramAccount},
+    state::UsedQuote,
+};
+
+pub struct CloseUsedQuoteAccounts<'a> {
+    pub depositor: &'a AccountInfo,
+    pub used_quote_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CloseUsedQuoteAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [depositor, used_quote_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            depositor,
+            used_quote_pda,
+        })
+    }
+}
+
+pub struct CloseUsedQuoteInstructionData {
+    pub nonce: u64,
+}
+
+impl TryFrom<&[u8]> for CloseUsedQuoteInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            nonce: u64::from_le_bytes(data.try_into().unwrap()),
+        })
+    }
+}
+
+/// Closes a used quote PDA once the quote it records has expired, refunding
+/// its rent to the depositor. An expired quote can no longer price a
+/// deposit, so its nonce need not stay consumed. Permissionless.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE]` Depositor
+/// 1. `[WRITE]` Used quote PDA
+pub struct CloseUsedQuote<'a> {
+    pub accounts: CloseUsedQuoteAccounts<'a>,
+    pub data: CloseUsedQuoteInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CloseUsedQuote<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: CloseUsedQuoteAccounts::try_from(accounts)?,
+            data: CloseUsedQuoteInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> CloseUsedQuote<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &98;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let (expected_used_quote_pda, _) = find_program_address(
+            &[
+                b"used_quote",
+                self.accounts.depositor.key(),
+                &self.data.nonce.to_le_bytes(),
+            ],
+            &crate::ID,
+        );
+        if expected_used_quote_pda != *self.accounts.used_quote_pda.key()
+            || !self.accounts.used_quote_pda.is_owned_by(&crate::ID)
+        {
+            return Err(PinocchioError::InvalidUsedQuotePda.into());
+        }
+
+        let used_quote_data = self.accounts.used_quote_pda.try_borrow_data()?;
+        let expires_at_slot = UsedQuote::load(&used_quote_data)?.expires_at_slot;
+        drop(used_quote_data);
+
+        if Clock::get()?.slot <= expires_at_slot {
+            return Err(PinocchioError::QuoteNotExpired.into());
+        }
+
+        ProgramAccount::close(self.accounts.used_quote_pda, self.accounts.depositor)
+    }
+}
@@ -0,0 +1,280 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+use pinocchio_token::{instructions::MintTo, state::Mint};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit, EVENT_DEPOSIT},
+    ids::{assert_token_program, ASSOCIATED_TOKEN_PROGRAM_ID},
+    instructions::helpers::{
+        check_config_pda, check_deposit_authority, check_pool_cap, epoch_rate,
+        expect_lst_supply_change, managed_lamports, pool_lamports, record_inflow,
+        record_managed_change, record_principal, AccountCheck, SignerAccount, SupplyChange,
+    },
+    math::lamports_to_lst,
+    state::{Config, DcaSchedule, ManagedChange},
+};
+
+pub struct CrankDcaAccounts<'a> {
+    pub keeper: &'a AccountInfo,
+    pub dca_schedule_pda: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub user_ata: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+    /// Only needed in a permissioned pool.
+    pub deposit_authority: Option<&'a AccountInfo>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CrankDcaAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [keeper, dca_schedule_pda, config_pda, user_ata, lst_mint, stake_account_main, stake_account_reserve, token_program, pool_state_pda, optional_accounts @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let deposit_authority = match optional_accounts {
+            [] => None,
+            [deposit_authority] => Some(deposit_authority),
+            _ => return Err(ProgramError::NotEnoughAccountKeys),
+        };
+
+        SignerAccount::check(keeper)?;
+
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            keeper,
+            dca_schedule_pda,
+            config_pda,
+            user_ata,
+            lst_mint,
+            stake_account_main,
+            stake_account_reserve,
+            token_program,
+            pool_state_pda,
+            deposit_authority,
+        })
+    }
+}
+
+pub struct CrankDcaInstructionData {
+    pub nonce: u64,
+}
+
+impl TryFrom<&[u8]> for CrankDcaInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            nonce: u64::from_le_bytes(data.try_into().unwrap()),
+        })
+    }
+}
+
+/// Executes a due DCA deposit. Permissionless.
+///
+/// Moves `amount_per_interval` from the schedule's escrow into the reserve,
+/// mints LST to the user's ATA at the `Deposit` rate, and pays the keeper
+/// `tip_lamports`. The next deposit is due one interval later; missed
+/// intervals are not caught up. Fails with `DepositCapExceeded` while the
+/// epoch's deposit cap is used up, so keepers retry next epoch, and with
+/// `PoolCapExceeded` while the pool is at its cap. In a permissioned pool the
+/// deposit authority must co-sign, as for `Deposit`, so only keepers the
+/// authority runs or signs for can execute schedules.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Keeper
+/// 1. `[WRITE]` DCA schedule PDA
+/// 2. `[]` Config PDA
+/// 3. `[WRITE]` User ATA
+/// 4. `[WRITE]` LST mint
+/// 5. `[]` Stake account main
+/// 6. `[WRITE]` Stake account reserve
+/// 7. `[]` Token program
+/// 8. `[WRITE]` Pool state PDA
+/// 9. `[SIGNER]` Deposit authority (only in a permissioned pool, unless the
+///    authority is the keeper)
+pub struct CrankDca<'a> {
+    pub accounts: CrankDcaAccounts<'a>,
+    pub data: CrankDcaInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CrankDca<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: CrankDcaAccounts::try_from(accounts)?,
+            data: CrankDcaInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> CrankDca<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &36;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let bump = check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        config.status()?.check_deposits()?;
+
+        check_deposit_authority(
+            &config.deposit_authority,
+            self.accounts
+                .deposit_authority
+                .unwrap_or(self.accounts.keeper),
+        )?;
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        let deposit_cap_lamports = config.deposit_cap_lamports;
+        let max_pool_lamports = config.max_pool_lamports;
+        let validator_stake_lamports = config.validator_stake_lamports;
+        let cached_rate = epoch_rate(config)?;
+        drop(config_data);
+
+        if !self.accounts.dca_schedule_pda.is_owned_by(&crate::ID) {
+            return Err(PinocchioError::InvalidDcaSchedulePda.into());
+        }
+
+        let mut dca_schedule_data = self.accounts.dca_schedule_pda.try_borrow_mut_data()?;
+        let dca_schedule = DcaSchedule::load_mut(&mut dca_schedule_data)?;
+        let user = dca_schedule.user;
+
+        let expected_dca_schedule_pda = find_program_address(
+            &[b"dca_schedule", &user, &self.data.nonce.to_le_bytes()],
+            &crate::ID,
+        )
+        .0;
+        if expected_dca_schedule_pda != *self.accounts.dca_schedule_pda.key() {
+            return Err(PinocchioError::InvalidDcaSchedulePda.into());
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        if now < dca_schedule.next_execution_ts {
+            return Err(PinocchioError::DcaNotDue.into());
+        }
+
+        let amount = dca_schedule.amount_per_interval;
+        let tip = dca_schedule.tip_lamports;
+        dca_schedule.budget_lamports = amount
+            .checked_add(tip)
+            .and_then(|cost| dca_schedule.budget_lamports.checked_sub(cost))
+            .ok_or(PinocchioError::DcaBudgetExhausted)?;
+        dca_schedule.next_execution_ts = now.saturating_add(dca_schedule.interval_secs);
+        drop(dca_schedule_data);
+
+        let expected_ata = find_program_address(
+            &[
+                &user,
+                self.accounts.token_program.key(),
+                self.accounts.lst_mint.key(),
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .0;
+        if expected_ata != *self.accounts.user_ata.key() {
+            return Err(PinocchioError::InvalidDepositorAta.into());
+        }
+
+        let live_pool_lamports = pool_lamports(
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+        check_pool_cap(max_pool_lamports, live_pool_lamports, amount)?;
+        let managed = managed_lamports(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+        let (total_sol_in_pool, total_lst_supply) = cached_rate.unwrap_or((
+            managed,
+            Mint::from_account_info(self.accounts.lst_mint)?.supply(),
+        ));
+        let lst_to_mint = lamports_to_lst(amount, total_lst_supply, total_sol_in_pool)?;
+
+        record_inflow(
+            self.accounts.pool_state_pda,
+            deposit_cap_lamports,
+            amount,
+            false,
+        )?;
+
+        // The schedule PDA is program-owned, so its escrow is moved directly.
+        *self.accounts.dca_schedule_pda.try_borrow_mut_lamports()? -= amount + tip;
+        *self
+            .accounts
+            .stake_account_reserve
+            .try_borrow_mut_lamports()? += amount;
+        *self.accounts.keeper.try_borrow_mut_lamports()? += tip;
+        record_managed_change(
+            self.accounts.pool_state_pda,
+            ManagedChange::Deposited(amount),
+        )?;
+
+        let bump_binding = [bump];
+        let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
+
+        expect_lst_supply_change(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            SupplyChange::Minted(lst_to_mint),
+            || {
+                MintTo {
+                    mint: self.accounts.lst_mint,
+                    account: self.accounts.user_ata,
+                    mint_authority: self.accounts.config_pda,
+                    amount: lst_to_mint,
+                }
+                .invoke_signed(&[Signer::from(config_seeds)])
+            },
+        )?;
+
+        record_principal(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            amount,
+            lst_to_mint,
+        )?;
+
+        emit(
+            self.accounts.pool_state_pda,
+            EVENT_DEPOSIT,
+            &[&user, &amount.to_le_bytes(), &lst_to_mint.to_le_bytes()],
+        )?;
+
+        Ok(())
+    }
+}
@@ -1,15 +1,17 @@
-use pinocchio::{
-    account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
-    pubkey::find_program_address,
-};
+use pinocchio::{account_info::AccountInfo, instruction::Seed, program_error::ProgramError};
 
 use crate::{
     errors::PinocchioError,
+    ids::{
+        assert_clock_sysvar, assert_rent_sysvar, assert_stake_history_sysvar, assert_stake_program,
+        assert_system_program, assert_vote_account,
+    },
     instructions::helpers::{
-        ProgramAccount, StakeAccountDelegate, StakeAccountInitialize, STAKE_PROGRAM_ID,
-        VOTE_PROGRAM_ID,
+        accrue_rewards, check_config_pda, mark_epoch_task, pay_crank_tip, record_pool_stake,
+        stake_lamports_excluding_rent, ProgramAccount, StakeAccountDelegate,
+        StakeAccountInitialize,
     },
-    state::Config,
+    state::{Config, EPOCH_TASK_INITIALIZE_RESERVE},
 };
 
 pub struct CrankInitializeReserveAccounts<'a> {
@@ -22,29 +24,37 @@ pub struct CrankInitializeReserveAccounts<'a> {
     pub history_sysvar: &'a AccountInfo,
     pub system_program: &'a AccountInfo,
     pub stake_program: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub epoch_tasks_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+    pub cranker: Option<&'a AccountInfo>,
+    pub fee_vault_pda: Option<&'a AccountInfo>,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for CrankInitializeReserveAccounts<'a> {
     type Error = ProgramError;
 
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, ProgramError> {
-        let [config_pda, stake_account_reserve, validator_vote_account, unused_account, rent_sysvar, clock_sysvar, history_sysvar, system_program, stake_program] =
+        let [config_pda, stake_account_reserve, validator_vote_account, unused_account, rent_sysvar, clock_sysvar, history_sysvar, system_program, stake_program, stake_account_main, epoch_tasks_pda, pool_state_pda, optional_accounts @ ..] =
             accounts
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
-        if system_program.key() != &pinocchio_system::ID {
-            return Err(PinocchioError::InvalidSystemProgram.into());
-        }
-
-        if stake_program.key() != &STAKE_PROGRAM_ID {
-            return Err(PinocchioError::InvalidStakeProgram.into());
-        }
+        // The cranker and the fee vault PDA follow when the crank claims its
+        // tip.
+        let (cranker, fee_vault_pda) = match optional_accounts {
+            [] => (None, None),
+            [cranker, fee_vault_pda] => (Some(cranker), Some(fee_vault_pda)),
+            _ => return Err(ProgramError::NotEnoughAccountKeys),
+        };
 
-        if !validator_vote_account.is_owned_by(&VOTE_PROGRAM_ID) {
-            return Err(PinocchioError::InvalidValidatorVoteAccount.into());
-        }
+        assert_system_program(system_program)?;
+        assert_stake_program(stake_program)?;
+        assert_rent_sysvar(rent_sysvar)?;
+        assert_clock_sysvar(clock_sysvar)?;
+        assert_stake_history_sysvar(history_sysvar)?;
+        assert_vote_account(validator_vote_account)?;
 
         Ok(Self {
             config_pda,
@@ -56,11 +66,23 @@ impl<'a> TryFrom<&'a [AccountInfo]> for CrankInitializeReserveAccounts<'a> {
             history_sysvar,
             system_program,
             stake_program,
+            stake_account_main,
+            epoch_tasks_pda,
+            pool_state_pda,
+            cranker,
+            fee_vault_pda,
         })
     }
 }
 
-/// Initializes and delegates reserve stake account to validator.
+/// Initializes and delegates reserve stake account to validator. Fails while
+/// the validator's resulting stake, main plus reserve, would exceed the
+/// per-validator stake cap; the reserve then stays undelegated. Fails with
+/// `ReserveNotAdopted` while the reserve address holds no stake account, as
+/// after a merge; `AdoptReserve` recreates it first. Marks the
+/// initialize-reserve step in the epoch task checklist. When the cranker and
+/// the fee vault PDA are passed, the fee vault pays the cranker the config's
+/// `crank_tip_lamports`, see `pay_crank_tip`.
 ///
 /// Accounts expected:
 ///
@@ -73,6 +95,11 @@ impl<'a> TryFrom<&'a [AccountInfo]> for CrankInitializeReserveAccounts<'a> {
 /// 6. `[]` History sysvar
 /// 7. `[]` System program
 /// 8. `[]` Stake program
+/// 9. `[]` Stake account main
+/// 10. `[WRITE]` Epoch tasks PDA
+/// 11. `[WRITE]` Pool state PDA
+/// 12. `[WRITE, SIGNER]` Cranker (optional)
+/// 13. `[WRITE]` Fee vault PDA (optional, with the cranker)
 pub struct CrankInitializeReserve<'a> {
     pub accounts: CrankInitializeReserveAccounts<'a>,
 }
@@ -93,20 +120,19 @@ impl<'a> CrankInitializeReserve<'a> {
     pub fn process(&self) -> Result<(), ProgramError> {
         //this prevents double invocation
         let reserve_data = self.accounts.stake_account_reserve.try_borrow_data()?;
+        if reserve_data.len() < 4 {
+            return Err(PinocchioError::ReserveNotAdopted.into());
+        }
         let stake_state = u32::from_le_bytes(reserve_data[0..4].try_into().unwrap());
         if stake_state != 0 {
             return Err(PinocchioError::ReserveAlreadyInitialized.into());
         }
         drop(reserve_data);
 
-        let (expected_config_pda, bump) = find_program_address(&[b"config"], &crate::ID);
+        let bump = check_config_pda(self.accounts.config_pda)?;
         let bump_binding = [bump];
         let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
 
-        if expected_config_pda != *self.accounts.config_pda.key() {
-            return Err(PinocchioError::InvalidConfigPda.into());
-        }
-
         let config_data = self.accounts.config_pda.try_borrow_data()?;
         let config = Config::load(&config_data)?;
 
@@ -118,6 +144,35 @@ impl<'a> CrankInitializeReserve<'a> {
             return Err(PinocchioError::InvalidValidatorVoteKey.into());
         }
 
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        // Main and the reserve both end up on the config's validator; the
+        // validator stake PDAs make up the rest of the pool.
+        let validator_stake = stake_lamports_excluding_rent(self.accounts.stake_account_main)?
+            .checked_add(stake_lamports_excluding_rent(
+                self.accounts.stake_account_reserve,
+            )?)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let total_lamports = validator_stake
+            .checked_add(config.validator_stake_lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        if validator_stake > config.validator_stake_cap(total_lamports) {
+            return Err(PinocchioError::ValidatorStakeCapExceeded.into());
+        }
+
+        let crank_tip_lamports = config.crank_tip_lamports;
+        let validator_stake_lamports = config.validator_stake_lamports;
+        drop(config_data);
+
+        accrue_rewards(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+
         ProgramAccount::initialize_stake_account_no_lockup(
             self.accounts.stake_account_reserve,
             self.accounts.config_pda,
@@ -136,6 +191,21 @@ impl<'a> CrankInitializeReserve<'a> {
             config_seeds,
         )?;
 
+        record_pool_stake(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+
+        mark_epoch_task(self.accounts.epoch_tasks_pda, EPOCH_TASK_INITIALIZE_RESERVE)?;
+
+        if let (Some(cranker), Some(fee_vault_pda)) =
+            (self.accounts.cranker, self.accounts.fee_vault_pda)
+        {
+            pay_crank_tip(crank_tip_lamports, cranker, fee_vault_pda)?;
+        }
+
         Ok(())
     }
 }
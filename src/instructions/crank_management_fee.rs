@@ -0,0 +1,192 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+use pinocchio_token::{instructions::MintTo, state::Mint};
+
+use crate::{
+    errors::PinocchioError,
+    ids::{assert_token_program, ASSOCIATED_TOKEN_PROGRAM_ID},
+    instructions::helpers::{
+        check_config_pda, check_validator_stake_current, expect_lst_supply_change,
+        managed_lamports, mark_epoch_task, record_principal, SupplyChange,
+    },
+    math::management_fee,
+    state::{Config, PoolState, EPOCH_TASK_FEE_ACCRUAL},
+};
+
+pub struct CrankManagementFeeAccounts<'a> {
+    pub config_pda: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub treasury_ata: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+    pub epoch_tasks_pda: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CrankManagementFeeAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [config_pda, stake_account_main, stake_account_reserve, lst_mint, treasury_ata, pool_state_pda, epoch_tasks_pda, token_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            lst_mint,
+            treasury_ata,
+            pool_state_pda,
+            epoch_tasks_pda,
+            token_program,
+        })
+    }
+}
+
+/// Charges the epoch's management fee. Permissionless.
+///
+/// Mints LST worth `management_fee_bps` of the pool's managed lamports to the
+/// treasury ATA, diluting the other holders by that value. The fee never
+/// exceeds the pool's yield, so depositors' principal is not charged, and
+/// the minted LST is added to the principal so the same yield is not charged
+/// twice. Runs once per epoch, recorded in the config's `last_fee_epoch`;
+/// epochs nobody cranked are not charged later. A second call in the same
+/// epoch fails with `ManagementFeeAlreadyCharged`. Marks the fee accrual step
+/// of the epoch task checklist. Fails with `ValidatorStakeStale` until
+/// `CrankValidatorStake` has recorded every staked validator this epoch.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE]` Config PDA
+/// 1. `[]` Stake account main
+/// 2. `[]` Stake account reserve
+/// 3. `[WRITE]` LST mint
+/// 4. `[WRITE]` Treasury ATA
+/// 5. `[WRITE]` Pool state PDA
+/// 6. `[WRITE]` Epoch tasks PDA
+/// 7. `[]` Token program
+pub struct CrankManagementFee<'a> {
+    pub accounts: CrankManagementFeeAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CrankManagementFee<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            accounts: CrankManagementFeeAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> CrankManagementFee<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &89;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let bump = check_config_pda(self.accounts.config_pda)?;
+
+        let epoch = Clock::get()?.epoch;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        let expected_treasury_ata = find_program_address(
+            &[
+                self.accounts.config_pda.key(),
+                self.accounts.token_program.key(),
+                &config.lst_mint,
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .0;
+        if expected_treasury_ata != *self.accounts.treasury_ata.key() {
+            return Err(PinocchioError::InvalidTreasuryAta.into());
+        }
+
+        if config.last_fee_epoch >= epoch {
+            return Err(PinocchioError::ManagementFeeAlreadyCharged.into());
+        }
+        check_validator_stake_current(config, epoch)?;
+        config.last_fee_epoch = epoch;
+
+        let management_fee_bps = config.management_fee_bps;
+        let validator_stake_lamports = config.validator_stake_lamports;
+        drop(config_data);
+
+        mark_epoch_task(self.accounts.epoch_tasks_pda, EPOCH_TASK_FEE_ACCRUAL)?;
+
+        let (expected_pool_state_pda, _) = find_program_address(&[b"pool_state"], &crate::ID);
+        if expected_pool_state_pda != *self.accounts.pool_state_pda.key()
+            || !self.accounts.pool_state_pda.is_owned_by(&crate::ID)
+        {
+            return Err(PinocchioError::InvalidPoolStatePda.into());
+        }
+
+        let lst_supply = Mint::from_account_info(self.accounts.lst_mint)?.supply();
+        let total_sol_in_pool = managed_lamports(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+        let yield_lamports = PoolState::load(&self.accounts.pool_state_pda.try_borrow_data()?)?
+            .yield_lamports(total_sol_in_pool, lst_supply);
+
+        let (fee_lamports, fee_lst) = management_fee(
+            total_sol_in_pool,
+            lst_supply,
+            yield_lamports,
+            management_fee_bps,
+        )?;
+        if fee_lst == 0 {
+            return Ok(());
+        }
+
+        let bump_binding = [bump];
+        let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
+        expect_lst_supply_change(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            SupplyChange::Minted(fee_lst),
+            || {
+                MintTo {
+                    mint: self.accounts.lst_mint,
+                    account: self.accounts.treasury_ata,
+                    mint_authority: self.accounts.config_pda,
+                    amount: fee_lst,
+                }
+                .invoke_signed(&[Signer::from(config_seeds)])
+            },
+        )?;
+
+        record_principal(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            fee_lamports,
+            fee_lst,
+        )
+    }
+}
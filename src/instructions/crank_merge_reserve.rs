@@ -1,12 +1,22 @@
 use pinocchio::{
-    account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
-    pubkey::find_program_address,
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
 };
 
 use crate::{
     errors::PinocchioError,
-    instructions::helpers::{ProgramAccount, StakeAccountMerge, STAKE_PROGRAM_ID},
-    state::Config,
+    events::{emit, EVENT_STATUS},
+    ids::{
+        assert_clock_sysvar, assert_stake_history_sysvar, assert_stake_program,
+        assert_system_program,
+    },
+    instructions::helpers::{
+        accrue_rewards, check_config_pda, mark_epoch_task, pay_crank_tip, record_pool_stake,
+        stake_is_activating, ProgramAccount, StakeAccountMerge,
+    },
+    state::{Config, PoolStatus, EPOCH_TASK_MERGE_RESERVE},
 };
 
 pub struct CrankMergeReserveAccounts<'a> {
@@ -17,25 +27,34 @@ pub struct CrankMergeReserveAccounts<'a> {
     pub history_sysvar: &'a AccountInfo,
     pub system_program: &'a AccountInfo,
     pub stake_program: &'a AccountInfo,
+    pub epoch_tasks_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+    pub cranker: Option<&'a AccountInfo>,
+    pub fee_vault_pda: Option<&'a AccountInfo>,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for CrankMergeReserveAccounts<'a> {
     type Error = ProgramError;
 
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [config_pda, stake_account_main, stake_account_reserve, clock_sysvar, history_sysvar, system_program, stake_program] =
+        let [config_pda, stake_account_main, stake_account_reserve, clock_sysvar, history_sysvar, system_program, stake_program, epoch_tasks_pda, pool_state_pda, optional_accounts @ ..] =
             accounts
         else {
             return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
         };
 
-        if system_program.key() != &pinocchio_system::ID {
-            return Err(PinocchioError::InvalidSystemProgram.into());
-        }
+        // The cranker and the fee vault PDA follow when the crank claims its
+        // tip.
+        let (cranker, fee_vault_pda) = match optional_accounts {
+            [] => (None, None),
+            [cranker, fee_vault_pda] => (Some(cranker), Some(fee_vault_pda)),
+            _ => return Err(ProgramError::NotEnoughAccountKeys),
+        };
 
-        if stake_program.key() != &STAKE_PROGRAM_ID {
-            return Err(PinocchioError::InvalidStakeProgram.into());
-        }
+        assert_system_program(system_program)?;
+        assert_stake_program(stake_program)?;
+        assert_clock_sysvar(clock_sysvar)?;
+        assert_stake_history_sysvar(history_sysvar)?;
 
         Ok(Self {
             config_pda,
@@ -45,12 +64,23 @@ impl<'a> TryFrom<&'a [AccountInfo]> for CrankMergeReserveAccounts<'a> {
             history_sysvar,
             system_program,
             stake_program,
+            epoch_tasks_pda,
+            pool_state_pda,
+            cranker,
+            fee_vault_pda,
         })
     }
 }
 
 /// Merges reserve stake account into main stake account.
 ///
+/// A reserve still warming up can only merge into a main account that is
+/// warming up in the same epoch, so the crank fails early otherwise. Marks the
+/// merge step in the epoch task checklist. The first merge moves a
+/// bootstrapping pool to `Active`. When the cranker and the fee vault PDA are
+/// passed, the fee vault pays the cranker the config's `crank_tip_lamports`,
+/// see `pay_crank_tip`.
+///
 /// Accounts expected:
 ///
 /// 0. `[WRITE]` Config PDA
@@ -60,6 +90,10 @@ impl<'a> TryFrom<&'a [AccountInfo]> for CrankMergeReserveAccounts<'a> {
 /// 4. `[]` History sysvar
 /// 5. `[]` System program
 /// 6. `[]` Stake program
+/// 7. `[WRITE]` Epoch tasks PDA
+/// 8. `[WRITE]` Pool state PDA
+/// 9. `[WRITE, SIGNER]` Cranker (optional)
+/// 10. `[WRITE]` Fee vault PDA (optional, with the cranker)
 pub struct CrankMergeReserve<'a> {
     pub accounts: CrankMergeReserveAccounts<'a>,
 }
@@ -77,20 +111,19 @@ impl<'a> CrankMergeReserve<'a> {
 
     pub fn process(&self) -> Result<(), ProgramError> {
         let reserve_data = self.accounts.stake_account_reserve.try_borrow_data()?;
+        if reserve_data.len() < 4 {
+            return Err(PinocchioError::ReserveNotStaked.into());
+        }
         let stake_state = u32::from_le_bytes(reserve_data[0..4].try_into().unwrap());
         if stake_state != 2 {
             return Err(PinocchioError::ReserveNotStaked.into());
         }
         drop(reserve_data);
 
-        let (expected_config_pda, bump) = find_program_address(&[b"config"], &crate::ID);
+        let bump = check_config_pda(self.accounts.config_pda)?;
         let bump_binding = [bump];
         let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
 
-        if expected_config_pda != *self.accounts.config_pda.key() {
-            return Err(PinocchioError::InvalidConfigPda.into());
-        }
-
         let config_data = self.accounts.config_pda.try_borrow_data()?;
         let config = Config::load(&config_data)?;
 
@@ -102,6 +135,21 @@ impl<'a> CrankMergeReserve<'a> {
             return Err(PinocchioError::InvalidStakeAccountReserve.into());
         }
 
+        let epoch = Clock::get()?.epoch;
+        if stake_is_activating(self.accounts.stake_account_reserve, epoch)?
+            && !stake_is_activating(self.accounts.stake_account_main, epoch)?
+        {
+            return Err(PinocchioError::ReserveActivating.into());
+        }
+        let validator_stake_lamports = config.validator_stake_lamports;
+
+        accrue_rewards(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+
         ProgramAccount::merge_stake_account(
             self.accounts.stake_account_main,
             self.accounts.stake_account_reserve,
@@ -111,6 +159,37 @@ impl<'a> CrankMergeReserve<'a> {
             config_seeds,
         )?;
 
+        record_pool_stake(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+
+        mark_epoch_task(self.accounts.epoch_tasks_pda, EPOCH_TASK_MERGE_RESERVE)?;
+
+        drop(config_data);
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.status()? == PoolStatus::Bootstrapping {
+            let previous = config.transition(PoolStatus::Active)?;
+            emit(
+                self.accounts.pool_state_pda,
+                EVENT_STATUS,
+                &[&[previous as u8], &[PoolStatus::Active as u8]],
+            )?;
+        }
+
+        let crank_tip_lamports = config.crank_tip_lamports;
+        drop(config_data);
+
+        if let (Some(cranker), Some(fee_vault_pda)) =
+            (self.accounts.cranker, self.accounts.fee_vault_pda)
+        {
+            pay_crank_tip(crank_tip_lamports, cranker, fee_vault_pda)?;
+        }
+
         Ok(())
     }
 }
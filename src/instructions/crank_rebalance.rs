@@ -0,0 +1,464 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use crate::{
+    errors::PinocchioError,
+    ids::{
+        assert_clock_sysvar, assert_rent_sysvar, assert_stake_history_sysvar, assert_stake_program,
+        assert_system_program, assert_vote_account, STAKE_PROGRAM_ID,
+    },
+    instructions::helpers::check_config_pda,
+    instructions::{
+        crank_split::main_minimum,
+        helpers::{
+            accrue_rewards, check_validator_list, check_validator_stake, check_validator_transient,
+            create_pda_account, mark_epoch_task, minimum_delegation, pool_lamports,
+            record_pool_stake, record_transient_stake, record_validator_stake,
+            reserve_spare_lamports, stake_account_breakdown, stake_account_space,
+            stake_authorities, AccountCheck, ProgramAccount, SignerAccount, StakeAccountCreate,
+            StakeAccountDeactivate, StakeAccountDelegate, StakeAccountInitialize,
+            StakeAccountMerge, StakeAccountSplit, StakeAccountWithdraw,
+        },
+    },
+    state::{Config, ValidatorEntry, ValidatorList, EPOCH_TASK_REBALANCE},
+};
+
+pub struct CrankRebalanceAccounts<'a> {
+    pub payer: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub validator_list_pda: &'a AccountInfo,
+    pub validator_stake_pda: &'a AccountInfo,
+    pub transient_pda: &'a AccountInfo,
+    pub validator_vote_account: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub unused_account: &'a AccountInfo,
+    pub rent_sysvar: &'a AccountInfo,
+    pub clock_sysvar: &'a AccountInfo,
+    pub history_sysvar: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub stake_program: &'a AccountInfo,
+    pub epoch_tasks_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CrankRebalanceAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [payer, config_pda, validator_list_pda, validator_stake_pda, transient_pda, validator_vote_account, stake_account_main, stake_account_reserve, unused_account, rent_sysvar, clock_sysvar, history_sysvar, system_program, stake_program, epoch_tasks_pda, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(payer)?;
+
+        assert_system_program(system_program)?;
+        assert_stake_program(stake_program)?;
+        assert_rent_sysvar(rent_sysvar)?;
+        assert_clock_sysvar(clock_sysvar)?;
+        assert_stake_history_sysvar(history_sysvar)?;
+        assert_vote_account(validator_vote_account)?;
+
+        Ok(Self {
+            payer,
+            config_pda,
+            validator_list_pda,
+            validator_stake_pda,
+            transient_pda,
+            validator_vote_account,
+            stake_account_main,
+            stake_account_reserve,
+            unused_account,
+            rent_sysvar,
+            clock_sysvar,
+            history_sysvar,
+            system_program,
+            stake_program,
+            epoch_tasks_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+/// Moves a listed validator's stake toward its target from the config's
+/// delegation strategy, one step per call. Permissionless.
+///
+/// Stake moves through the validator's transient stake PDA
+/// (`b"validator_transient"`, vote account), whose lamports count toward
+/// the pool while it exists:
+///
+/// - Over target, the excess is split from the validator stake PDA into the
+///   transient and deactivated. Once cooled down, the next call withdraws
+///   the transient into the reserve.
+/// - Under target, up to the shortfall is moved from the reserve's
+///   undelegated lamports into the transient and delegated to the
+///   validator, bounded by the per-validator stake cap. Once active, the
+///   next call merges it into the validator stake PDA.
+/// - Under target with less than the minimum delegation spare in the
+///   reserve, up to the shortfall is split from main's active stake beyond
+///   `main_minimum` into the transient and deactivated. Once cooled down, the
+///   next call delegates it to the validator rather than withdrawing it, as
+///   the validator's stake and transient stake are still within target.
+///
+/// Calls while the transient is warming up or cooling down fail with
+/// `RebalanceInProgress`, and calls with less than the minimum delegation to
+/// move fail with `NothingToRebalance`. The payer funds the transient's
+/// rent, which ends up in the pool. Validator stake PDAs must have been
+/// delegated by `CrankValidatorStake` first (`ValidatorStakeNotDelegated`).
+///
+/// Marks the rebalance step of the epoch task checklist, and fails with
+/// `PrerequisiteCrankMissing` until `CrankMergeReserve` has run this epoch.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Payer
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Validator list PDA
+/// 3. `[WRITE]` Validator stake PDA
+/// 4. `[WRITE]` Validator transient stake PDA
+/// 5. `[]` Validator vote account
+/// 6. `[WRITE]` Stake account main
+/// 7. `[WRITE]` Stake account reserve
+/// 8. `[]` Unused account
+/// 9. `[]` Rent sysvar
+/// 10. `[]` Clock sysvar
+/// 11. `[]` Stake history sysvar
+/// 12. `[]` System program
+/// 13. `[]` Stake program
+/// 14. `[WRITE]` Epoch tasks PDA
+/// 15. `[WRITE]` Pool state PDA
+pub struct CrankRebalance<'a> {
+    pub accounts: CrankRebalanceAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CrankRebalance<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            accounts: CrankRebalanceAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> CrankRebalance<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &81;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let bump = check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+        drop(config_data);
+
+        let vote_account = self.accounts.validator_vote_account.key();
+        check_validator_list(self.accounts.validator_list_pda)?;
+        check_validator_stake(self.accounts.validator_stake_pda, vote_account)?;
+        let transient_bump = check_validator_transient(self.accounts.transient_pda, vote_account)?;
+
+        if stake_authorities(self.accounts.validator_stake_pda)?.is_none() {
+            return Err(PinocchioError::ValidatorStakeNotDelegated.into());
+        }
+
+        mark_epoch_task(self.accounts.epoch_tasks_pda, EPOCH_TASK_REBALANCE)?;
+
+        let bump_binding = [bump];
+        let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
+
+        // Rewards the validator's stake earned are credited before the move,
+        // which is then recorded without crediting it.
+        self.record()?;
+        accrue_rewards(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            Config::load(&self.accounts.config_pda.try_borrow_data()?)?.validator_stake_lamports,
+        )?;
+
+        if self.accounts.transient_pda.is_owned_by(&STAKE_PROGRAM_ID) {
+            self.settle_transient(config_seeds)?;
+        } else {
+            self.start_transient(transient_bump, config_seeds)?;
+        }
+
+        self.record()?;
+        record_pool_stake(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            Config::load(&self.accounts.config_pda.try_borrow_data()?)?.validator_stake_lamports,
+        )
+    }
+
+    /// Records the validator stake and transient stake PDAs' lamports.
+    fn record(&self) -> Result<(), ProgramError> {
+        let vote_account = self.accounts.validator_vote_account.key();
+        record_validator_stake(
+            self.accounts.config_pda,
+            self.accounts.validator_list_pda,
+            self.accounts.validator_stake_pda,
+            vote_account,
+        )?;
+        record_transient_stake(
+            self.accounts.config_pda,
+            self.accounts.validator_list_pda,
+            self.accounts.transient_pda,
+            vote_account,
+        )
+    }
+
+    /// Merges an active transient into the validator stake PDA. An inactive
+    /// one is delegated to the validator while it and the validator's stake
+    /// are within target, as stake split from main is, and withdrawn into
+    /// the reserve otherwise.
+    fn settle_transient(&self, config_seeds: &[Seed]) -> Result<(), ProgramError> {
+        let breakdown = stake_account_breakdown(self.accounts.transient_pda, Clock::get()?.epoch)?;
+
+        if breakdown.activating > 0 || breakdown.deactivating > 0 {
+            return Err(PinocchioError::RebalanceInProgress.into());
+        }
+
+        if breakdown.active > 0 {
+            return ProgramAccount::merge_stake_account(
+                self.accounts.validator_stake_pda,
+                self.accounts.transient_pda,
+                self.accounts.clock_sysvar,
+                self.accounts.history_sysvar,
+                self.accounts.config_pda,
+                config_seeds,
+            );
+        }
+
+        let (target, entry) = self.target()?;
+        if entry
+            .stake_lamports
+            .saturating_add(entry.transient_lamports)
+            <= target
+        {
+            return ProgramAccount::delegate_stake_account(
+                self.accounts.transient_pda,
+                self.accounts.validator_vote_account,
+                self.accounts.clock_sysvar,
+                self.accounts.history_sysvar,
+                self.accounts.unused_account,
+                self.accounts.config_pda,
+                config_seeds,
+            );
+        }
+
+        ProgramAccount::withdraw_stake_account(
+            self.accounts.transient_pda,
+            self.accounts.stake_account_reserve,
+            self.accounts.clock_sysvar,
+            self.accounts.history_sysvar,
+            self.accounts.config_pda,
+            config_seeds,
+        )
+    }
+
+    /// The validator's target from the config's delegation strategy, bounded
+    /// by the stake cap, and its list entry as last recorded.
+    fn target(&self) -> Result<(u64, ValidatorEntry), ProgramError> {
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+        let total_lamports = pool_lamports(
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            config.validator_stake_lamports,
+        )?;
+
+        let validator_list_data = self.accounts.validator_list_pda.try_borrow_data()?;
+        let entries = ValidatorList::load(&validator_list_data)?.entries();
+        let index = entries
+            .iter()
+            .position(|entry| entry.vote_account == *self.accounts.validator_vote_account.key())
+            .ok_or(PinocchioError::ValidatorNotInList)?;
+        let mut targets = [0u64; ValidatorList::CAPACITY];
+        config
+            .delegation_strategy()?
+            .targets(entries, total_lamports, &mut targets);
+
+        Ok((
+            targets[index].min(config.validator_stake_cap(total_lamports)),
+            entries[index],
+        ))
+    }
+
+    /// Opens a transient moving the validator toward its target.
+    fn start_transient(
+        &self,
+        transient_bump: u8,
+        config_seeds: &[Seed],
+    ) -> Result<(), ProgramError> {
+        let (target, entry) = self.target()?;
+        let stake_lamports = entry.stake_lamports;
+
+        let transient_bump_binding = [transient_bump];
+        let transient_seeds = &[
+            Seed::from(b"validator_transient"),
+            Seed::from(self.accounts.validator_vote_account.key()),
+            Seed::from(&transient_bump_binding),
+        ];
+        let minimum_delegation = minimum_delegation()?;
+
+        if stake_lamports > target {
+            // The validator stake PDA keeps at least the stake minimum; a
+            // validator leaving the set is drained by `RemoveValidator`.
+            let lamports = (stake_lamports - target).min(
+                self.accounts
+                    .validator_stake_pda
+                    .lamports()
+                    .saturating_sub(main_minimum(self.accounts.validator_stake_pda, 0)?),
+            );
+            if lamports < minimum_delegation {
+                return Err(PinocchioError::NothingToRebalance.into());
+            }
+
+            ProgramAccount::stake_account_create(
+                self.accounts.payer,
+                self.accounts.transient_pda,
+                transient_seeds,
+                stake_account_space(self.accounts.validator_stake_pda),
+            )?;
+
+            ProgramAccount::split_stake_account(
+                self.accounts.validator_stake_pda,
+                self.accounts.transient_pda,
+                &lamports,
+                self.accounts.config_pda,
+                config_seeds,
+            )?;
+
+            return ProgramAccount::deactivate_stake_account(
+                self.accounts.transient_pda,
+                self.accounts.clock_sysvar,
+                self.accounts.config_pda,
+                config_seeds,
+            );
+        }
+
+        let reserve = self.accounts.stake_account_reserve;
+        let reserve_spare =
+            reserve_spare_lamports(reserve, PinocchioError::ReserveNotAdopted).unwrap_or_default();
+        if reserve_spare < minimum_delegation {
+            return self.split_from_main(target - stake_lamports, transient_seeds, config_seeds);
+        }
+
+        let lamports = (target - stake_lamports).min(reserve_spare);
+        if lamports == 0 || lamports < minimum_delegation {
+            return Err(PinocchioError::NothingToRebalance.into());
+        }
+
+        let space = stake_account_space(reserve);
+        create_pda_account(
+            self.accounts.payer,
+            self.accounts.transient_pda,
+            transient_seeds,
+            Rent::get()?.minimum_balance(space),
+            space,
+            &STAKE_PROGRAM_ID,
+        )?;
+
+        let stake_reserve_bump =
+            Config::load(&self.accounts.config_pda.try_borrow_data()?)?.stake_reserve_bump;
+        let stake_reserve_bump_binding = [stake_reserve_bump];
+        let stake_reserve_seeds = &[
+            Seed::from(b"stake_reserve"),
+            Seed::from(&stake_reserve_bump_binding),
+        ];
+
+        // An uninitialized stake account is its own withdraw authority.
+        ProgramAccount::withdraw_stake_account_lamports(
+            reserve,
+            self.accounts.transient_pda,
+            self.accounts.clock_sysvar,
+            self.accounts.history_sysvar,
+            reserve,
+            lamports,
+            stake_reserve_seeds,
+        )?;
+
+        ProgramAccount::initialize_stake_account_no_lockup(
+            self.accounts.transient_pda,
+            self.accounts.config_pda,
+            self.accounts.config_pda,
+            self.accounts.rent_sysvar,
+            config_seeds,
+        )?;
+
+        ProgramAccount::delegate_stake_account(
+            self.accounts.transient_pda,
+            self.accounts.validator_vote_account,
+            self.accounts.clock_sysvar,
+            self.accounts.history_sysvar,
+            self.accounts.unused_account,
+            self.accounts.config_pda,
+            config_seeds,
+        )
+    }
+
+    /// Splits up to `shortfall` of main's active stake beyond `main_minimum`
+    /// into the transient and deactivates it, for `settle_transient` to
+    /// delegate to the validator once cooled down.
+    fn split_from_main(
+        &self,
+        shortfall: u64,
+        transient_seeds: &[Seed],
+        config_seeds: &[Seed],
+    ) -> Result<(), ProgramError> {
+        let main = self.accounts.stake_account_main;
+        let breakdown = stake_account_breakdown(main, Clock::get()?.epoch)?;
+        if breakdown.activating > 0 || breakdown.deactivating > 0 {
+            return Err(PinocchioError::NothingToRebalance.into());
+        }
+
+        let main_headroom_lamports =
+            Config::load(&self.accounts.config_pda.try_borrow_data()?)?.main_headroom_lamports;
+        let lamports = shortfall.min(
+            main.lamports()
+                .saturating_sub(main_minimum(main, main_headroom_lamports)?),
+        );
+        if lamports == 0 || lamports < minimum_delegation()? {
+            return Err(PinocchioError::NothingToRebalance.into());
+        }
+
+        let space = stake_account_space(main);
+        create_pda_account(
+            self.accounts.payer,
+            self.accounts.transient_pda,
+            transient_seeds,
+            Rent::get()?.minimum_balance(space),
+            space,
+            &STAKE_PROGRAM_ID,
+        )?;
+
+        ProgramAccount::split_stake_account(
+            main,
+            self.accounts.transient_pda,
+            &lamports,
+            self.accounts.config_pda,
+            config_seeds,
+        )?;
+
+        ProgramAccount::deactivate_stake_account(
+            self.accounts.transient_pda,
+            self.accounts.clock_sysvar,
+            self.accounts.config_pda,
+            config_seeds,
+        )
+    }
+}
@@ -0,0 +1,164 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+use pinocchio_token::state::Mint;
+
+use crate::{
+    errors::PinocchioError,
+    ids::assert_system_program,
+    instructions::helpers::{
+        check_config_pda, managed_lamports, mark_epoch_task, AccountCheck, ProgramAccount,
+        ProgramAccountInit, SignerAccount,
+    },
+    state::{Config, RateHistory, RateSnapshot, EPOCH_TASK_ORACLE_PUSH},
+};
+
+pub struct CrankRecordRateAccounts<'a> {
+    pub payer: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub rate_history_pda: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub epoch_tasks_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CrankRecordRateAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [payer, config_pda, rate_history_pda, stake_account_main, stake_account_reserve, lst_mint, system_program, epoch_tasks_pda, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(payer)?;
+
+        assert_system_program(system_program)?;
+
+        Ok(Self {
+            payer,
+            config_pda,
+            rate_history_pda,
+            stake_account_main,
+            stake_account_reserve,
+            lst_mint,
+            system_program,
+            epoch_tasks_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+/// Records the current exchange rate into the rate history ring buffer.
+/// Creates the rate history PDA on first use. Callable once per epoch. Marks
+/// the oracle push step in the epoch task checklist.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Payer
+/// 1. `[]` Config PDA
+/// 2. `[WRITE]` Rate history PDA
+/// 3. `[]` Stake account main
+/// 4. `[]` Stake account reserve
+/// 5. `[]` LST mint
+/// 6. `[]` System program
+/// 7. `[WRITE]` Epoch tasks PDA
+/// 8. `[]` Pool state PDA
+pub struct CrankRecordRate<'a> {
+    pub accounts: CrankRecordRateAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CrankRecordRate<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: CrankRecordRateAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> CrankRecordRate<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &6;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        let validator_stake_lamports = config.validator_stake_lamports;
+        drop(config_data);
+
+        let (expected_rate_history_pda, rate_history_bump) =
+            find_program_address(&[b"rate_history"], &crate::ID);
+        if expected_rate_history_pda != *self.accounts.rate_history_pda.key() {
+            return Err(PinocchioError::InvalidRateHistoryPda.into());
+        }
+
+        if self.accounts.rate_history_pda.data_is_empty() {
+            let rate_history_bump_binding = [rate_history_bump];
+            let rate_history_seeds = &[
+                Seed::from(b"rate_history"),
+                Seed::from(&rate_history_bump_binding),
+            ];
+            ProgramAccount::init::<RateHistory>(
+                self.accounts.payer,
+                self.accounts.rate_history_pda,
+                rate_history_seeds,
+                RateHistory::LEN,
+            )?;
+        }
+
+        let epoch = Clock::get()?.epoch;
+
+        let total_lamports = managed_lamports(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+
+        let lst_supply = Mint::from_account_info(self.accounts.lst_mint)?.supply();
+
+        let mut rate_history_data = self.accounts.rate_history_pda.try_borrow_mut_data()?;
+        let rate_history = RateHistory::load_mut(&mut rate_history_data)?;
+
+        if let Some(latest) = rate_history.latest() {
+            if latest.epoch >= epoch {
+                return Err(PinocchioError::RateAlreadyRecorded.into());
+            }
+        }
+
+        rate_history.push(RateSnapshot {
+            epoch,
+            total_lamports,
+            lst_supply,
+        });
+        drop(rate_history_data);
+
+        mark_epoch_task(self.accounts.epoch_tasks_pda, EPOCH_TASK_ORACLE_PUSH)?;
+
+        Ok(())
+    }
+}
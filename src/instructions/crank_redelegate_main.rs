@@ -0,0 +1,192 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    errors::PinocchioError,
+    ids::{
+        assert_clock_sysvar, assert_stake_history_sysvar, assert_stake_program, assert_vote_account,
+    },
+    instructions::helpers::{
+        accrue_rewards, check_config_pda, check_outgoing_reserve, record_managed_change,
+        record_pool_stake, stake_account_breakdown, staked_lamports, ProgramAccount,
+        StakeAccountDelegate, StakeAccountMerge,
+    },
+    state::{Config, ManagedChange},
+};
+
+pub struct CrankRedelegateMainAccounts<'a> {
+    pub config_pda: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub validator_vote_account: &'a AccountInfo,
+    pub clock_sysvar: &'a AccountInfo,
+    pub history_sysvar: &'a AccountInfo,
+    pub unused_account: &'a AccountInfo,
+    pub stake_program: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+    pub stake_account_outgoing: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CrankRedelegateMainAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [config_pda, stake_account_main, validator_vote_account, clock_sysvar, history_sysvar, unused_account, stake_program, stake_account_reserve, pool_state_pda, stake_account_outgoing] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        assert_vote_account(validator_vote_account)?;
+        assert_clock_sysvar(clock_sysvar)?;
+        assert_stake_history_sysvar(history_sysvar)?;
+        assert_stake_program(stake_program)?;
+
+        Ok(Self {
+            config_pda,
+            stake_account_main,
+            validator_vote_account,
+            clock_sysvar,
+            history_sysvar,
+            unused_account,
+            stake_program,
+            stake_account_reserve,
+            pool_state_pda,
+            stake_account_outgoing,
+        })
+    }
+}
+
+/// Delegates main to the config's validator once `UpdateConfig` has moved the
+/// pool and main's stake has cooled down. Permissionless. The outgoing
+/// reserve a rotation left, see `UpdateConfig`, is merged into main first,
+/// and the rewards its stake earned since are credited to the pool.
+///
+/// Fails with `MainCoolingDown` while main or the outgoing reserve is still
+/// deactivating and with `MainNotRedelegating` while main is active or
+/// activating, which is the case whenever the pool has not been moved.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE]` Config PDA
+/// 1. `[WRITE]` Stake account main
+/// 2. `[]` Validator vote account (the config's)
+/// 3. `[]` Clock sysvar
+/// 4. `[]` Stake history sysvar
+/// 5. `[]` Unused account
+/// 6. `[]` Stake program
+/// 7. `[]` Stake account reserve
+/// 8. `[WRITE]` Pool state PDA
+/// 9. `[WRITE]` Stake account outgoing
+pub struct CrankRedelegateMain<'a> {
+    pub accounts: CrankRedelegateMainAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CrankRedelegateMain<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            accounts: CrankRedelegateMainAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> CrankRedelegateMain<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &83;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let bump = check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.validator_vote_pubkey != *self.accounts.validator_vote_account.key() {
+            return Err(PinocchioError::InvalidValidatorVoteKey.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+        let validator_stake_lamports = config.validator_stake_lamports;
+        let outgoing_stake_lamports = config.outgoing_stake_lamports;
+        drop(config_data);
+
+        let epoch = Clock::get()?.epoch;
+        let breakdown = stake_account_breakdown(self.accounts.stake_account_main, epoch)?;
+        if breakdown.deactivating > 0 {
+            return Err(PinocchioError::MainCoolingDown.into());
+        }
+        if breakdown.active > 0 || breakdown.activating > 0 {
+            return Err(PinocchioError::MainNotRedelegating.into());
+        }
+
+        check_outgoing_reserve(self.accounts.stake_account_outgoing)?;
+        let merging = outgoing_stake_lamports != 0;
+        if merging
+            && stake_account_breakdown(self.accounts.stake_account_outgoing, epoch)?.deactivating
+                > 0
+        {
+            return Err(PinocchioError::MainCoolingDown.into());
+        }
+
+        accrue_rewards(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+
+        let bump_binding = [bump];
+        let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
+        if merging {
+            // The outgoing stake left the recorded stake when it rotated out,
+            // so what it earned while cooling down is credited here.
+            let rewards = staked_lamports(self.accounts.stake_account_outgoing)?
+                .saturating_sub(outgoing_stake_lamports);
+            record_managed_change(
+                self.accounts.pool_state_pda,
+                ManagedChange::Rewarded(rewards),
+            )?;
+
+            ProgramAccount::merge_stake_account(
+                self.accounts.stake_account_main,
+                self.accounts.stake_account_outgoing,
+                self.accounts.clock_sysvar,
+                self.accounts.history_sysvar,
+                self.accounts.config_pda,
+                config_seeds,
+            )?;
+
+            let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+            Config::load_mut(&mut config_data)?.outgoing_stake_lamports = 0;
+        }
+
+        ProgramAccount::delegate_stake_account(
+            self.accounts.stake_account_main,
+            self.accounts.validator_vote_account,
+            self.accounts.clock_sysvar,
+            self.accounts.history_sysvar,
+            self.accounts.unused_account,
+            self.accounts.config_pda,
+            config_seeds,
+        )?;
+
+        // Delegating main again stakes its whole balance, which is a move and
+        // not rewards.
+        record_pool_stake(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )
+    }
+}
@@ -0,0 +1,476 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{rent::Rent, Sysvar},
+};
+use pinocchio_token::{
+    instructions::Burn,
+    state::{Mint, TokenAccount},
+};
+
+use crate::{
+    errors::PinocchioError,
+    ids::{
+        assert_clock_sysvar, assert_stake_history_sysvar, assert_stake_program,
+        assert_system_program, assert_token_program,
+    },
+    instructions::helpers::check_config_pda,
+    instructions::{
+        crank_split::{main_minimum, split_minimum},
+        helpers::{
+            accrue_rewards, epoch_rate, expect_lst_supply_change, managed_lamports,
+            record_managed_change, record_pool_stake, reserve_spare_lamports, stake_account_space,
+            AccountCheck, ProgramAccount, SignerAccount, StakeAccountCreate,
+            StakeAccountDeactivate, StakeAccountSplit, StakeAccountWithdraw, SupplyChange,
+            LAMPORTS_PER_SOL,
+        },
+    },
+    math::lst_to_lamports,
+    state::{Config, LiquidityPool, ManagedChange},
+};
+
+/// Where `CrankRefillBuffer` takes SOL from.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RefillSource {
+    /// Deposits waiting in the reserve before it is delegated.
+    Reserve,
+    /// Split stake from main into the buffer stake account and deactivate it.
+    Unstake,
+    /// Withdraw the buffer stake account once its cooldown completed.
+    Collect,
+}
+
+impl TryFrom<u8> for RefillSource {
+    type Error = ProgramError;
+
+    fn try_from(source: u8) -> Result<Self, Self::Error> {
+        match source {
+            0 => Ok(Self::Reserve),
+            1 => Ok(Self::Unstake),
+            2 => Ok(Self::Collect),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+pub struct CrankRefillBufferAccounts<'a> {
+    pub keeper: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub liquidity_pool_pda: &'a AccountInfo,
+    pub lst_vault: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub buffer_stake_account: &'a AccountInfo,
+    pub clock_sysvar: &'a AccountInfo,
+    pub history_sysvar: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub stake_program: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CrankRefillBufferAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [keeper, config_pda, liquidity_pool_pda, lst_vault, lst_mint, stake_account_main, stake_account_reserve, buffer_stake_account, clock_sysvar, history_sysvar, token_program, stake_program, system_program, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(keeper)?;
+
+        assert_system_program(system_program)?;
+        assert_stake_program(stake_program)?;
+        assert_clock_sysvar(clock_sysvar)?;
+        assert_stake_history_sysvar(history_sysvar)?;
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            keeper,
+            config_pda,
+            liquidity_pool_pda,
+            lst_vault,
+            lst_mint,
+            stake_account_main,
+            stake_account_reserve,
+            buffer_stake_account,
+            clock_sysvar,
+            history_sysvar,
+            token_program,
+            stake_program,
+            system_program,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct CrankRefillBufferInstructionData {
+    pub source: RefillSource,
+}
+
+impl TryFrom<&[u8]> for CrankRefillBufferInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        let [source] = data else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        Ok(Self {
+            source: RefillSource::try_from(*source)?,
+        })
+    }
+}
+
+/// Tops the liquidity pool's SOL buffer back up towards
+/// `liquidity_target_lamports` by redeeming LST from its vault at the fair
+/// exchange rate. Both the LST holders' rate and the LP value are unchanged.
+///
+/// - `Reserve`: takes SOL deposited into the reserve while it is not yet
+///   delegated and burns the matching vault LST.
+/// - `Unstake`: splits the matching stake from main into the buffer stake
+///   PDA (`b"buffer_stake"`), deactivates it and burns the vault LST. The
+///   keeper funds the account's rent and stake minimum.
+/// - `Collect`: once cooled down, withdraws the buffer stake account into the
+///   buffer and refunds the keeper that funded it.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Keeper
+/// 1. `[]` Config PDA
+/// 2. `[WRITE]` Liquidity pool PDA
+/// 3. `[WRITE]` LST vault
+/// 4. `[WRITE]` LST mint
+/// 5. `[WRITE]` Stake account main
+/// 6. `[WRITE]` Stake account reserve
+/// 7. `[WRITE]` Buffer stake PDA
+/// 8. `[]` Clock sysvar
+/// 9. `[]` History sysvar
+/// 10. `[]` Token program
+/// 11. `[]` Stake program
+/// 12. `[]` System program
+/// 13. `[WRITE]` Pool state PDA
+pub struct CrankRefillBuffer<'a> {
+    pub accounts: CrankRefillBufferAccounts<'a>,
+    pub data: CrankRefillBufferInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CrankRefillBuffer<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: CrankRefillBufferAccounts::try_from(accounts)?,
+            data: CrankRefillBufferInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> CrankRefillBuffer<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &16;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let bump = check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        let liquidity_target_lamports = config.liquidity_target_lamports;
+        let min_split_stake_lamports = config.min_split_stake_lamports;
+        let main_headroom_lamports = config.main_headroom_lamports;
+        let validator_stake_lamports = config.validator_stake_lamports;
+
+        drop(config_data);
+
+        let (expected_liquidity_pool_pda, _) =
+            find_program_address(&[b"liquidity_pool"], &crate::ID);
+        if expected_liquidity_pool_pda != *self.accounts.liquidity_pool_pda.key() {
+            return Err(PinocchioError::InvalidLiquidityPoolPda.into());
+        }
+
+        let (expected_buffer_stake_account, _) =
+            find_program_address(&[b"buffer_stake"], &crate::ID);
+        if expected_buffer_stake_account != *self.accounts.buffer_stake_account.key() {
+            return Err(PinocchioError::InvalidBufferStakePda.into());
+        }
+
+        ProgramAccount::check(self.accounts.liquidity_pool_pda)?;
+
+        let liquidity_pool_data = self.accounts.liquidity_pool_pda.try_borrow_data()?;
+        let liquidity_pool = LiquidityPool::load(&liquidity_pool_data)?;
+        if liquidity_pool.lst_vault != *self.accounts.lst_vault.key() {
+            return Err(PinocchioError::InvalidLstVault.into());
+        }
+
+        let deficit = liquidity_target_lamports.saturating_sub(
+            liquidity_pool
+                .sol_reserves
+                .saturating_add(liquidity_pool.pending_unstake_lamports),
+        );
+
+        drop(liquidity_pool_data);
+
+        let bump_binding = [bump];
+        let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
+
+        match self.data.source {
+            RefillSource::Reserve => self.refill_from_reserve(deficit),
+            RefillSource::Unstake => self.refill_from_unstake(
+                deficit,
+                min_split_stake_lamports,
+                main_headroom_lamports,
+                validator_stake_lamports,
+                config_seeds,
+            ),
+            RefillSource::Collect => self.collect_buffer_stake(config_seeds),
+        }
+    }
+
+    fn refill_from_reserve(&self, deficit: u64) -> Result<(), ProgramError> {
+        let reserve = self.accounts.stake_account_reserve;
+        let reserve_spare = reserve_spare_lamports(reserve, PinocchioError::NothingToRefill)?;
+
+        let lamports = deficit.min(reserve_spare).min(self.lst_vault_value()?);
+        if lamports == 0 {
+            return Err(PinocchioError::NothingToRefill.into());
+        }
+
+        self.burn_vault_lst(lamports)?;
+
+        let stake_reserve_bump =
+            Config::load(&self.accounts.config_pda.try_borrow_data()?)?.stake_reserve_bump;
+        let stake_reserve_bump_binding = [stake_reserve_bump];
+        let stake_reserve_seeds = &[
+            Seed::from(b"stake_reserve"),
+            Seed::from(&stake_reserve_bump_binding),
+        ];
+
+        // An uninitialized stake account is its own withdraw authority.
+        ProgramAccount::withdraw_stake_account_lamports(
+            reserve,
+            self.accounts.liquidity_pool_pda,
+            self.accounts.clock_sysvar,
+            self.accounts.history_sysvar,
+            reserve,
+            lamports,
+            stake_reserve_seeds,
+        )?;
+
+        let mut liquidity_pool_data = self.accounts.liquidity_pool_pda.try_borrow_mut_data()?;
+        let liquidity_pool = LiquidityPool::load_mut(&mut liquidity_pool_data)?;
+        liquidity_pool.sol_reserves = liquidity_pool
+            .sol_reserves
+            .checked_add(lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    fn refill_from_unstake(
+        &self,
+        deficit: u64,
+        min_split_stake_lamports: u64,
+        main_headroom_lamports: u64,
+        validator_stake_lamports: u64,
+        config_seeds: &[Seed],
+    ) -> Result<(), ProgramError> {
+        if self.accounts.buffer_stake_account.lamports() != 0 {
+            return Err(PinocchioError::BufferStakeInUse.into());
+        }
+
+        let main = self.accounts.stake_account_main;
+        let main_minimum = main_minimum(main, main_headroom_lamports)?;
+        let main_available = main.lamports().saturating_sub(main_minimum);
+
+        let lamports = deficit.min(main_available).min(self.lst_vault_value()?);
+        if lamports < split_minimum(stake_account_space(main), min_split_stake_lamports)? {
+            return Err(PinocchioError::NothingToRefill.into());
+        }
+
+        self.burn_vault_lst(lamports)?;
+
+        let (_, buffer_stake_bump) = find_program_address(&[b"buffer_stake"], &crate::ID);
+        let buffer_stake_bump_binding = [buffer_stake_bump];
+        let buffer_stake_seeds = &[
+            Seed::from(b"buffer_stake"),
+            Seed::from(&buffer_stake_bump_binding),
+        ];
+
+        ProgramAccount::stake_account_create(
+            self.accounts.keeper,
+            self.accounts.buffer_stake_account,
+            buffer_stake_seeds,
+            stake_account_space(main),
+        )?;
+
+        accrue_rewards(
+            self.accounts.pool_state_pda,
+            main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+
+        ProgramAccount::split_stake_account(
+            main,
+            self.accounts.buffer_stake_account,
+            &lamports,
+            self.accounts.config_pda,
+            config_seeds,
+        )?;
+
+        record_pool_stake(
+            self.accounts.pool_state_pda,
+            main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+
+        ProgramAccount::deactivate_stake_account(
+            self.accounts.buffer_stake_account,
+            self.accounts.clock_sysvar,
+            self.accounts.config_pda,
+            config_seeds,
+        )?;
+
+        let mut liquidity_pool_data = self.accounts.liquidity_pool_pda.try_borrow_mut_data()?;
+        let liquidity_pool = LiquidityPool::load_mut(&mut liquidity_pool_data)?;
+        liquidity_pool.pending_unstake_lamports = lamports;
+        liquidity_pool.buffer_stake_funder = *self.accounts.keeper.key();
+
+        Ok(())
+    }
+
+    fn collect_buffer_stake(&self, config_seeds: &[Seed]) -> Result<(), ProgramError> {
+        let buffer_stake_account = self.accounts.buffer_stake_account;
+        let withdrawn = buffer_stake_account.lamports();
+        if withdrawn == 0 {
+            return Err(PinocchioError::NothingToRefill.into());
+        }
+
+        let liquidity_pool_data = self.accounts.liquidity_pool_pda.try_borrow_data()?;
+        if LiquidityPool::load(&liquidity_pool_data)?.buffer_stake_funder
+            != *self.accounts.keeper.key()
+        {
+            return Err(PinocchioError::NotBufferStakeFunder.into());
+        }
+        drop(liquidity_pool_data);
+
+        // What the keeper put in when creating the account.
+        let funding = Rent::get()?
+            .minimum_balance(buffer_stake_account.data_len())
+            .checked_add(LAMPORTS_PER_SOL)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        ProgramAccount::withdraw_stake_account(
+            buffer_stake_account,
+            self.accounts.liquidity_pool_pda,
+            self.accounts.clock_sysvar,
+            self.accounts.history_sysvar,
+            self.accounts.config_pda,
+            config_seeds,
+        )?;
+
+        let mut liquidity_pool_data = self.accounts.liquidity_pool_pda.try_borrow_mut_data()?;
+        let liquidity_pool = LiquidityPool::load_mut(&mut liquidity_pool_data)?;
+        liquidity_pool.sol_reserves = liquidity_pool
+            .sol_reserves
+            .checked_add(withdrawn.saturating_sub(funding))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        liquidity_pool.pending_unstake_lamports = 0;
+        liquidity_pool.buffer_stake_funder = [0; 32];
+        drop(liquidity_pool_data);
+
+        let refund = funding.min(withdrawn);
+        *self.accounts.liquidity_pool_pda.try_borrow_mut_lamports()? -= refund;
+        *self.accounts.keeper.try_borrow_mut_lamports()? += refund;
+
+        Ok(())
+    }
+
+    /// The `(managed_lamports, lst_supply)` rate the vault LST is priced at,
+    /// see `epoch_rate`.
+    fn rate(&self) -> Result<(u64, u64), ProgramError> {
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+        match epoch_rate(config)? {
+            Some(rate) => Ok(rate),
+            None => Ok((
+                managed_lamports(
+                    self.accounts.pool_state_pda,
+                    self.accounts.stake_account_main,
+                    self.accounts.stake_account_reserve,
+                    config.validator_stake_lamports,
+                )?,
+                Mint::from_account_info(self.accounts.lst_mint)?.supply(),
+            )),
+        }
+    }
+
+    /// Value of the LST vault at the epoch's exchange rate.
+    fn lst_vault_value(&self) -> Result<u64, ProgramError> {
+        let (total_lamports, lst_supply) = self.rate()?;
+        lst_to_lamports(
+            TokenAccount::from_account_info(self.accounts.lst_vault)?.amount(),
+            lst_supply,
+            total_lamports,
+        )
+    }
+
+    /// Burns the vault LST worth `lamports`, rounded up so the remaining
+    /// holders' rate never drops, and takes `lamports` off the managed
+    /// lamports. Must run before `lamports` leave the pool.
+    fn burn_vault_lst(&self, lamports: u64) -> Result<(), ProgramError> {
+        let (total_lamports, lst_supply) = self.rate()?;
+        let lst_vault_amount = TokenAccount::from_account_info(self.accounts.lst_vault)?.amount();
+
+        let lst_to_burn = ((lamports as u128)
+            .checked_mul(lst_supply as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .div_ceil(total_lamports as u128) as u64)
+            .min(lst_vault_amount);
+
+        let (_, liquidity_pool_bump) = find_program_address(&[b"liquidity_pool"], &crate::ID);
+        let liquidity_pool_bump_binding = [liquidity_pool_bump];
+        let liquidity_pool_seeds = &[
+            Seed::from(b"liquidity_pool"),
+            Seed::from(&liquidity_pool_bump_binding),
+        ];
+
+        expect_lst_supply_change(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            SupplyChange::Burned(lst_to_burn),
+            || {
+                Burn {
+                    account: self.accounts.lst_vault,
+                    mint: self.accounts.lst_mint,
+                    authority: self.accounts.liquidity_pool_pda,
+                    amount: lst_to_burn,
+                }
+                .invoke_signed(&[Signer::from(liquidity_pool_seeds)])
+            },
+        )?;
+
+        record_managed_change(
+            self.accounts.pool_state_pda,
+            ManagedChange::Withdrawn(lamports),
+        )
+    }
+}
@@ -0,0 +1,272 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+};
+use pinocchio_token::{
+    instructions::Burn,
+    state::{Mint, TokenAccount},
+};
+
+use crate::{
+    errors::PinocchioError,
+    ids::{
+        assert_clock_sysvar, assert_stake_history_sysvar, assert_stake_program,
+        assert_token_program,
+    },
+    instructions::helpers::{
+        check_config_pda, check_reward_payout, epoch_rate, expect_lst_supply_change,
+        managed_lamports, record_managed_change, reserve_spare_lamports, ProgramAccount,
+        StakeAccountWithdraw, SupplyChange,
+    },
+    math::reward_payout_lamports,
+    state::{Config, ManagedChange, RewardPayout},
+};
+
+pub struct CrankRewardPayoutAccounts<'a> {
+    pub config_pda: &'a AccountInfo,
+    pub reward_payout_pda: &'a AccountInfo,
+    pub payout_vault: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub clock_sysvar: &'a AccountInfo,
+    pub history_sysvar: &'a AccountInfo,
+    pub stake_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CrankRewardPayoutAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [config_pda, reward_payout_pda, payout_vault, stake_account_main, stake_account_reserve, lst_mint, clock_sysvar, history_sysvar, stake_program, token_program, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        assert_stake_program(stake_program)?;
+        assert_clock_sysvar(clock_sysvar)?;
+        assert_stake_history_sysvar(history_sysvar)?;
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            config_pda,
+            reward_payout_pda,
+            payout_vault,
+            stake_account_main,
+            stake_account_reserve,
+            lst_mint,
+            clock_sysvar,
+            history_sysvar,
+            stake_program,
+            token_program,
+            pool_state_pda,
+        })
+    }
+}
+
+/// Pays out the SOL share of the rewards earned on the payout vault's LST
+/// since the last crank. Permissionless, once per epoch.
+///
+/// The rewards are the rise in the vault's value between the exchange rate
+/// checkpointed by the last crank and the current one. `reward_payout_bps`
+/// of them is paid as SOL: LST worth the payout is burned from the vault,
+/// rounded up so the rate does not fall for anyone else, and the payout's
+/// lamports are withdrawn from the reserve's undelegated lamports into the
+/// reward payout PDA, where they accrue to every share for `ClaimPayout`.
+/// The rest stays in the vault and compounds. Fails with
+/// `ReserveDelegated` or `InsufficientLiquidity` while the reserve cannot
+/// pay, and with `PayoutAlreadyCranked` when this epoch's crank already ran.
+/// The first crank only records the checkpoint.
+///
+/// Accounts expected:
+///
+/// 0. `[]` Config PDA
+/// 1. `[WRITE]` Reward payout PDA
+/// 2. `[WRITE]` Payout vault
+/// 3. `[]` Stake account main
+/// 4. `[WRITE]` Stake account reserve
+/// 5. `[WRITE]` LST mint
+/// 6. `[]` Clock sysvar
+/// 7. `[]` Stake history sysvar
+/// 8. `[]` Stake program
+/// 9. `[]` Token program
+/// 10. `[WRITE]` Pool state PDA
+pub struct CrankRewardPayout<'a> {
+    pub accounts: CrankRewardPayoutAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CrankRewardPayout<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            accounts: CrankRewardPayoutAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> CrankRewardPayout<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &77;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+        let reward_payout_bps = config.reward_payout_bps;
+        let validator_stake_lamports = config.validator_stake_lamports;
+        let cached_rate = epoch_rate(config)?;
+        drop(config_data);
+
+        let reward_payout_bump = check_reward_payout(
+            self.accounts.reward_payout_pda,
+            self.accounts.payout_vault,
+            self.accounts.lst_mint,
+            self.accounts.token_program,
+        )?;
+
+        let epoch = Clock::get()?.epoch;
+        let reward_payout_data = self.accounts.reward_payout_pda.try_borrow_data()?;
+        let reward_payout = RewardPayout::load(&reward_payout_data)?;
+        if reward_payout.checkpoint_lst_supply != 0 && reward_payout.last_epoch >= epoch {
+            return Err(PinocchioError::PayoutAlreadyCranked.into());
+        }
+        let total_shares = reward_payout.total_shares;
+        let checkpoint_lamports = reward_payout.checkpoint_lamports;
+        let checkpoint_lst_supply = reward_payout.checkpoint_lst_supply;
+        drop(reward_payout_data);
+
+        let vault_lst = TokenAccount::from_account_info(self.accounts.payout_vault)?.amount();
+        let lst_supply = Mint::from_account_info(self.accounts.lst_mint)?.supply();
+        let lamports = managed_lamports(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+
+        let payout = if total_shares == 0 {
+            0
+        } else {
+            reward_payout_lamports(
+                vault_lst,
+                checkpoint_lamports,
+                checkpoint_lst_supply,
+                lamports,
+                lst_supply,
+                reward_payout_bps,
+            )
+        };
+
+        if payout > 0 {
+            let (rate_lamports, rate_lst_supply) = cached_rate.unwrap_or((lamports, lst_supply));
+            self.pay_out(
+                payout,
+                rate_lamports,
+                rate_lst_supply,
+                vault_lst,
+                reward_payout_bump,
+            )?;
+        }
+
+        let lamports_after = managed_lamports(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+        let lst_supply_after = Mint::from_account_info(self.accounts.lst_mint)?.supply();
+
+        let mut reward_payout_data = self.accounts.reward_payout_pda.try_borrow_mut_data()?;
+        let reward_payout = RewardPayout::load_mut(&mut reward_payout_data)?;
+        reward_payout.distribute(payout)?;
+        reward_payout.checkpoint_lamports = lamports_after;
+        reward_payout.checkpoint_lst_supply = lst_supply_after;
+        reward_payout.last_epoch = epoch;
+
+        Ok(())
+    }
+
+    /// Burns LST worth `payout` from the vault and withdraws `payout` from
+    /// the reserve into the reward payout PDA, off the managed lamports.
+    fn pay_out(
+        &self,
+        payout: u64,
+        lamports: u64,
+        lst_supply: u64,
+        vault_lst: u64,
+        reward_payout_bump: u8,
+    ) -> Result<(), ProgramError> {
+        let lst_to_burn =
+            u64::try_from((payout as u128 * lst_supply as u128).div_ceil(lamports.max(1) as u128))
+                .map_err(|_| ProgramError::ArithmeticOverflow)?
+                .min(vault_lst);
+
+        let reserve = self.accounts.stake_account_reserve;
+        let reserve_spare = reserve_spare_lamports(reserve, PinocchioError::InsufficientLiquidity)?;
+        if payout > reserve_spare {
+            return Err(PinocchioError::InsufficientLiquidity.into());
+        }
+
+        let reward_payout_bump_binding = [reward_payout_bump];
+        let reward_payout_seeds = &[
+            Seed::from(b"reward_payout"),
+            Seed::from(&reward_payout_bump_binding),
+        ];
+        expect_lst_supply_change(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            SupplyChange::Burned(lst_to_burn),
+            || {
+                Burn {
+                    account: self.accounts.payout_vault,
+                    mint: self.accounts.lst_mint,
+                    authority: self.accounts.reward_payout_pda,
+                    amount: lst_to_burn,
+                }
+                .invoke_signed(&[Signer::from(reward_payout_seeds)])
+            },
+        )?;
+
+        let stake_reserve_bump =
+            Config::load(&self.accounts.config_pda.try_borrow_data()?)?.stake_reserve_bump;
+        let stake_reserve_bump_binding = [stake_reserve_bump];
+        let stake_reserve_seeds = &[
+            Seed::from(b"stake_reserve"),
+            Seed::from(&stake_reserve_bump_binding),
+        ];
+
+        // An uninitialized stake account is its own withdraw authority.
+        ProgramAccount::withdraw_stake_account_lamports(
+            reserve,
+            self.accounts.reward_payout_pda,
+            self.accounts.clock_sysvar,
+            self.accounts.history_sysvar,
+            reserve,
+            payout,
+            stake_reserve_seeds,
+        )?;
+
+        record_managed_change(
+            self.accounts.pool_state_pda,
+            ManagedChange::Withdrawn(payout),
+        )
+    }
+}
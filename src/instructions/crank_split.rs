@@ -3,7 +3,7 @@ use pinocchio::{
     instruction::Seed,
     program_error::ProgramError,
     pubkey::find_program_address,
-    sysvars::{rent::Rent, Sysvar},
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
 };
 use pinocchio_token::{
     instructions::Burn,
@@ -12,13 +12,74 @@ use pinocchio_token::{
 
 use crate::{
     errors::PinocchioError,
+    events::{emit, EVENT_SPLIT},
+    ids::{
+        assert_clock_sysvar, assert_rent_sysvar, assert_stake_program, assert_system_program,
+        assert_token_program, ASSOCIATED_TOKEN_PROGRAM_ID, SYSTEM_PROGRAM_ID,
+    },
     instructions::helpers::{
-        AccountCheck, ProgramAccount, SignerAccount, StakeAccountCreate, StakeAccountDeactivate,
-        StakeAccountSplit, STAKE_PROGRAM_ID,
+        accrue_rewards, check_config_pda, epoch_rate, expect_lst_supply_change, minimum_delegation,
+        rebated_withdraw_fee_bps, record_managed_change, record_pool_stake, record_validator_stake,
+        stake_account_breakdown, stake_account_space, stake_rent_exempt_reserve, AccountCheck,
+        ProgramAccount, ProgramAccountInit, SignerAccount, StakeAccountCreate,
+        StakeAccountDeactivate, StakeAccountSplit, SupplyChange, LAMPORTS_PER_SOL,
     },
-    state::Config,
+    math::lst_to_burn_with_fee,
+    state::{Config, ManagedChange, SplitRecord},
 };
 
+/// Largest share of main, in basis points, that one epoch's split may take.
+pub const MAX_SPLIT_PER_EPOCH_BPS: u64 = 1_000;
+
+/// Smallest balance a split account of `space` bytes can be left with: its
+/// rent plus the config's `min_split_stake_lamports`, raised to the cluster's
+/// minimum delegation when that is higher.
+pub fn split_minimum(space: usize, min_split_stake_lamports: u64) -> Result<u64, ProgramError> {
+    Rent::get()?
+        .minimum_balance(space)
+        .checked_add(min_split_stake_lamports.max(minimum_delegation()?))
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+/// Least `stake_account_main` must keep after a split: its rent plus the
+/// cluster's minimum delegation, never below `LAMPORTS_PER_SOL`, plus the
+/// config's `main_headroom_lamports`. Checking it here fails a split that
+/// would leave main undelegable with `MainBelowMinimum` instead of a
+/// generic stake program error.
+pub fn main_minimum(
+    stake_account_main: &AccountInfo,
+    main_headroom_lamports: u64,
+) -> Result<u64, ProgramError> {
+    stake_rent_exempt_reserve(stake_account_main)?
+        .checked_add(minimum_delegation()?.max(LAMPORTS_PER_SOL))
+        .and_then(|minimum| minimum.checked_add(main_headroom_lamports))
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+/// Divides `lamports` into the tranche split now and the lamports left for
+/// later epochs, taking at most `MAX_SPLIT_PER_EPOCH_BPS` of main per
+/// tranche. Neither part is left below `split_minimum`; when the cap is too
+/// small for that the whole amount is split at once.
+pub fn next_tranche(lamports: u64, main_lamports: u64, split_minimum: u64) -> (u64, u64) {
+    let cap = ((main_lamports as u128) * (MAX_SPLIT_PER_EPOCH_BPS as u128) / 10_000) as u64;
+
+    if lamports <= cap || cap < split_minimum {
+        return (lamports, 0);
+    }
+
+    let remaining = lamports - cap;
+    if remaining >= split_minimum {
+        return (cap, remaining);
+    }
+
+    // Leave exactly one minimum-sized tranche for the next epoch.
+    let tranche = lamports - split_minimum;
+    if tranche < split_minimum {
+        return (lamports, 0);
+    }
+    (tranche, split_minimum)
+}
+
 pub struct CrankSplitAccounts<'a> {
     pub stake_account_main: &'a AccountInfo,
     pub stake_account_reserve: &'a AccountInfo,
@@ -32,31 +93,61 @@ pub struct CrankSplitAccounts<'a> {
     pub token_program: &'a AccountInfo,
     pub stake_program: &'a AccountInfo,
     pub system_program: &'a AccountInfo,
+    pub split_record: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+    /// Funds the new split account and split record. The withdrawer unless
+    /// a separate payer is passed.
+    pub payer: &'a AccountInfo,
+    /// Validator list PDA and the validator stake PDA to split from instead
+    /// of main, when passed.
+    pub validator_stake: Option<(&'a AccountInfo, &'a AccountInfo)>,
+    /// The withdrawer's user stats PDA, for the holding rebate on the
+    /// withdrawal fee.
+    pub user_stats_pda: Option<&'a AccountInfo>,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for CrankSplitAccounts<'a> {
     type Error = pinocchio::program_error::ProgramError;
 
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [stake_account_main, stake_account_reserve, withdrawer, new_stake_account, config_pda, withdrawer_ata, lst_mint, rent_sysvar, clock_sysvar, token_program, stake_program, system_program] =
+        let [stake_account_main, stake_account_reserve, withdrawer, new_stake_account, config_pda, withdrawer_ata, lst_mint, rent_sysvar, clock_sysvar, token_program, stake_program, system_program, split_record, pool_state_pda, optional_accounts @ ..] =
             accounts
         else {
             return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys);
         };
 
-        SignerAccount::check(withdrawer)?;
-
-        if system_program.key() != &pinocchio_system::ID {
-            return Err(PinocchioError::InvalidSystemProgram.into());
-        }
+        let (payer, validator_stake, user_stats_pda) = match optional_accounts {
+            [] => (withdrawer, None, None),
+            [payer] => {
+                SignerAccount::check(payer)?;
+                (payer, None, None)
+            }
+            [payer, user_stats_pda] => {
+                SignerAccount::check(payer)?;
+                (payer, None, Some(user_stats_pda))
+            }
+            [payer, validator_list_pda, validator_stake_pda] => {
+                SignerAccount::check(payer)?;
+                (payer, Some((validator_list_pda, validator_stake_pda)), None)
+            }
+            [payer, validator_list_pda, validator_stake_pda, user_stats_pda] => {
+                SignerAccount::check(payer)?;
+                (
+                    payer,
+                    Some((validator_list_pda, validator_stake_pda)),
+                    Some(user_stats_pda),
+                )
+            }
+            _ => return Err(ProgramError::NotEnoughAccountKeys),
+        };
 
-        if stake_program.key() != &STAKE_PROGRAM_ID {
-            return Err(PinocchioError::InvalidStakeProgram.into());
-        }
+        SignerAccount::check(withdrawer)?;
 
-        if token_program.key() != &pinocchio_token::ID {
-            return Err(PinocchioError::InvalidTokenProgram.into());
-        }
+        assert_system_program(system_program)?;
+        assert_stake_program(stake_program)?;
+        assert_rent_sysvar(rent_sysvar)?;
+        assert_clock_sysvar(clock_sysvar)?;
+        assert_token_program(token_program)?;
 
         Ok(Self {
             stake_account_main,
@@ -71,42 +162,63 @@ impl<'a> TryFrom<&'a [AccountInfo]> for CrankSplitAccounts<'a> {
             token_program,
             stake_program,
             system_program,
+            split_record,
+            pool_state_pda,
+            payer,
+            validator_stake,
+            user_stats_pda,
         })
     }
 }
 
 pub struct CrankSplitInstructionData {
     pub lamports_to_split: u64,
-    pub nonce: u64,
+    /// Distinguishes the withdrawer's split accounts. `None` for the
+    /// single-pending split of `CrankSplitSingle`.
+    pub nonce: Option<u64>,
+    /// Lamports the split may be rounded up or down by to satisfy stake minimums.
+    pub dust_tolerance: u64,
 }
 
 impl TryFrom<&[u8]> for CrankSplitInstructionData {
     type Error = ProgramError;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
-        if data.len() != 8 + 8 {
-            return Err(ProgramError::InvalidInstructionData);
-        }
+        let dust_tolerance = match data.len() {
+            16 => 0,
+            24 => u64::from_le_bytes(data[16..24].try_into().unwrap()),
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
 
         let lamports_to_split = u64::from_le_bytes(data[0..8].try_into().unwrap());
         let nonce = u64::from_le_bytes(data[8..16].try_into().unwrap());
 
-        let stake_account_length = 200;
-        let mut minimum_lamports = Rent::get()?.minimum_balance(stake_account_length);
-        minimum_lamports += 1_000_000_000;
-
-        if lamports_to_split < minimum_lamports {
-            return Err(PinocchioError::SplitBelowMinimum.into());
-        }
-
         Ok(Self {
             lamports_to_split,
-            nonce,
+            nonce: Some(nonce),
+            dust_tolerance,
         })
     }
 }
 
-/// Splits stake from main account, deactivates it, and burns LST.
+/// Splits stake from main account, deactivates it, and burns LST: the
+/// split's pro-rata LST plus the admin's `withdraw_fee_bps` of it.
+///
+/// A split that would leave the split account or the main account below the
+/// stake minimum is rounded to the minimum when the difference is within the
+/// withdrawer's dust tolerance. The adjustment is kept in the split record.
+///
+/// Splits larger than `MAX_SPLIT_PER_EPOCH_BPS` of main are served in
+/// tranches: the first is split now and the rest is scheduled in the split
+/// record, to be split one tranche per epoch with `CrankSplitTranche`.
+/// LST is burned for each tranche as it is split.
+///
+/// Passing a validator list and validator stake PDA splits from that
+/// validator's stake instead of main. Its stake is recorded before and after
+/// the split, and the split is never served in tranches.
+///
+/// Passing the withdrawer's user stats PDA rebates the withdrawal fee by the
+/// epochs held since their first deposit, see `SetHoldingRebate`.
 ///
 /// Accounts expected:
 ///
@@ -122,6 +234,13 @@ impl TryFrom<&[u8]> for CrankSplitInstructionData {
 /// 9. `[]` Token program
 /// 10. `[]` Stake program
 /// 11. `[]` System program
+/// 12. `[WRITE]` Split record PDA
+/// 13. `[WRITE]` Pool state PDA
+/// 14. `[WRITE, SIGNER]` Payer (optional, defaults to the withdrawer)
+/// 15. `[WRITE]` Validator list PDA (optional, requires the payer)
+/// 16. `[WRITE]` Validator stake PDA (optional, requires the payer)
+/// 17. `[]` User stats PDA (optional, requires the payer; directly after it
+///     when no validator stake PDA is passed)
 pub struct CrankSplit<'a> {
     pub accounts: CrankSplitAccounts<'a>,
     pub data: CrankSplitInstructionData,
@@ -141,10 +260,7 @@ impl<'a> CrankSplit<'a> {
     pub const DISCRIMINATOR: &'static u8 = &4;
 
     pub fn process(&self) -> Result<(), ProgramError> {
-        let (expected_config_pda, bump) = find_program_address(&[b"config"], &crate::ID);
-        if *self.accounts.config_pda.key() != expected_config_pda {
-            return Err(PinocchioError::InvalidConfigPda.into());
-        }
+        let bump = check_config_pda(self.accounts.config_pda)?;
 
         let data = self.accounts.config_pda.try_borrow_data()?;
         let config = Config::load(&data)?;
@@ -157,17 +273,67 @@ impl<'a> CrankSplit<'a> {
             return Err(PinocchioError::InvalidStakeAccountReserve.into());
         }
 
+        config.status()?.check_unstakes()?;
+
         if config.lst_mint != *self.accounts.lst_mint.key() {
             return Err(PinocchioError::InvalidLstMint.into());
         }
 
+        let min_split_stake_lamports = config.min_split_stake_lamports;
+        let main_headroom_lamports = config.main_headroom_lamports;
+        let withdraw_fee_bps = rebated_withdraw_fee_bps(
+            config,
+            self.accounts.user_stats_pda,
+            self.accounts.withdrawer,
+            Clock::get()?.epoch,
+        )?;
+        drop(data);
+
+        // The validator stake PDA is split like main, but only keeps the
+        // stake minimum: the pool's headroom is held in main.
+        let (source, source_minimum, vote_account) = match self.accounts.validator_stake {
+            Some((validator_list_pda, validator_stake_pda)) => {
+                let vote_account =
+                    stake_account_breakdown(validator_stake_pda, Clock::get()?.epoch)?
+                        .voter
+                        .ok_or(PinocchioError::InvalidValidatorStakePda)?;
+                record_validator_stake(
+                    self.accounts.config_pda,
+                    validator_list_pda,
+                    validator_stake_pda,
+                    &vote_account,
+                )?;
+                (
+                    validator_stake_pda,
+                    main_minimum(validator_stake_pda, 0)?,
+                    Some(vote_account),
+                )
+            }
+            None => (
+                self.accounts.stake_account_main,
+                main_minimum(self.accounts.stake_account_main, main_headroom_lamports)?,
+                None,
+            ),
+        };
+
+        let validator_stake_lamports =
+            Config::load(&self.accounts.config_pda.try_borrow_data()?)?.validator_stake_lamports;
+        let managed = accrue_rewards(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+
+        let split_minimum = split_minimum(stake_account_space(source), min_split_stake_lamports)?;
+
         let expected_ata = find_program_address(
             &[
                 self.accounts.withdrawer.key(),
                 self.accounts.token_program.key(),
                 self.accounts.lst_mint.key(),
             ],
-            &pinocchio_associated_token_account::ID,
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
         )
         .0;
         if expected_ata != *self.accounts.withdrawer_ata.key() {
@@ -177,24 +343,18 @@ impl<'a> CrankSplit<'a> {
         let bump_binding = [bump];
         let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
 
-        // let (_, new_stake_account_bump) = find_program_address(
-        //     &[b"split_account", self.accounts.withdrawer.key()],
-        //     &crate::ID,
-        // );
-
-        // let new_stake_account_bump_binding = [new_stake_account_bump];
-        // let new_stake_seeds = &[
-        //     Seed::from(b"split_account"),
-        //     Seed::from(self.accounts.withdrawer.key()),
-        //     Seed::from(&new_stake_account_bump_binding),
-        // ];
-
-        let nonce_bytes = self.data.nonce.to_le_bytes();
+        // An empty seed adds nothing to the derivation, so without a nonce
+        // this is the single-pending split PDA `[b"split_account", withdrawer]`.
+        let nonce_bytes = self.data.nonce.map(u64::to_le_bytes);
+        let nonce_bytes: &[u8] = match &nonce_bytes {
+            Some(nonce_bytes) => nonce_bytes,
+            None => &[],
+        };
         let (expected_new_stake_account, new_stake_account_bump) = find_program_address(
             &[
                 b"split_account",
                 self.accounts.withdrawer.key(),
-                &nonce_bytes,
+                nonce_bytes,
             ],
             &crate::ID,
         );
@@ -203,28 +363,73 @@ impl<'a> CrankSplit<'a> {
             return Err(PinocchioError::InvalidSplitAccountPda.into());
         }
 
+        if !self
+            .accounts
+            .new_stake_account
+            .is_owned_by(&SYSTEM_PROGRAM_ID)
+        {
+            return Err(PinocchioError::SplitAlreadyPending.into());
+        }
+
         let new_stake_account_bump_binding = [new_stake_account_bump];
         let new_stake_seeds = &[
             Seed::from(b"split_account"),
             Seed::from(self.accounts.withdrawer.key()),
-            Seed::from(&nonce_bytes),
+            Seed::from(nonce_bytes),
             Seed::from(&new_stake_account_bump_binding),
         ];
 
+        let (expected_split_record, split_record_bump) = find_program_address(
+            &[b"split_record", self.accounts.new_stake_account.key()],
+            &crate::ID,
+        );
+
+        if expected_split_record != *self.accounts.split_record.key() {
+            return Err(PinocchioError::InvalidSplitRecordPda.into());
+        }
+
+        let lamports_to_split =
+            self.lamports_to_split_with_dust(source, split_minimum, source_minimum)?;
+        let (lamports_to_split, lamports_scheduled) = match vote_account {
+            Some(_) => (lamports_to_split, 0),
+            None => next_tranche(lamports_to_split, source.lamports(), split_minimum),
+        };
+
         ProgramAccount::stake_account_create(
-            self.accounts.withdrawer,
+            self.accounts.payer,
             self.accounts.new_stake_account,
             new_stake_seeds,
+            stake_account_space(source),
         )?;
 
         ProgramAccount::split_stake_account(
-            self.accounts.stake_account_main,
+            source,
             self.accounts.new_stake_account,
-            &self.data.lamports_to_split,
+            &lamports_to_split,
             self.accounts.config_pda,
             config_seeds,
         )?;
 
+        if let (Some((validator_list_pda, validator_stake_pda)), Some(vote_account)) =
+            (self.accounts.validator_stake, vote_account)
+        {
+            record_validator_stake(
+                self.accounts.config_pda,
+                validator_list_pda,
+                validator_stake_pda,
+                &vote_account,
+            )?;
+        }
+
+        let validator_stake_lamports =
+            Config::load(&self.accounts.config_pda.try_borrow_data()?)?.validator_stake_lamports;
+        record_pool_stake(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+
         ProgramAccount::deactivate_stake_account(
             self.accounts.new_stake_account,
             self.accounts.clock_sysvar,
@@ -235,24 +440,24 @@ impl<'a> CrankSplit<'a> {
         //burn lst
         let mint = Mint::from_account_info(self.accounts.lst_mint)?;
         let total_supply_mint = mint.supply();
+        let epoch = Clock::get()?.epoch;
 
-        // In process(), replace the exchange rate calculation (lines 155-166):
-        let main_account_lamports = self.accounts.stake_account_main.lamports();
-        let reserve_account_lamports = self.accounts.stake_account_reserve.lamports();
-        let new_account_lamports = self.accounts.new_stake_account.lamports();
-
-        let total_lamports_managed = main_account_lamports
-            .checked_add(reserve_account_lamports)
-            .ok_or(ProgramError::ArithmeticOverflow)?
-            .checked_add(new_account_lamports)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
+        // `managed` was read before the split, so it still counts the split
+        // lamports.
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+        let (total_lamports_managed, total_supply_mint) =
+            epoch_rate(config)?.unwrap_or((managed, total_supply_mint));
+        drop(config_data);
 
         // Also replace the f64 math with u128 integer math:
-        let lst_to_burn = (self.data.lamports_to_split as u128)
+        let lst_value = (lamports_to_split as u128)
             .checked_mul(total_supply_mint as u128)
             .ok_or(ProgramError::ArithmeticOverflow)?
             .checked_div(total_lamports_managed as u128)
             .ok_or(ProgramError::ArithmeticOverflow)? as u64;
+        // The withdrawal fee is burned on top, accruing to the other holders.
+        let lst_to_burn = lst_to_burn_with_fee(lst_value, withdraw_fee_bps)?;
 
         let withdrawer_ata_amount =
             TokenAccount::from_account_info(self.accounts.withdrawer_ata)?.amount();
@@ -262,14 +467,98 @@ impl<'a> CrankSplit<'a> {
 
         drop(mint);
 
-        Burn {
-            account: self.accounts.withdrawer_ata,
-            mint: self.accounts.lst_mint,
-            authority: self.accounts.withdrawer,
-            amount: lst_to_burn,
-        }
-        .invoke()?;
+        expect_lst_supply_change(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            SupplyChange::Burned(lst_to_burn),
+            || {
+                Burn {
+                    account: self.accounts.withdrawer_ata,
+                    mint: self.accounts.lst_mint,
+                    authority: self.accounts.withdrawer,
+                    amount: lst_to_burn,
+                }
+                .invoke()
+            },
+        )?;
+        record_managed_change(
+            self.accounts.pool_state_pda,
+            ManagedChange::Withdrawn(lamports_to_split),
+        )?;
+
+        let split_record_bump_binding = [split_record_bump];
+        let split_record_seeds = &[
+            Seed::from(b"split_record"),
+            Seed::from(self.accounts.new_stake_account.key()),
+            Seed::from(&split_record_bump_binding),
+        ];
+
+        ProgramAccount::init::<SplitRecord>(
+            self.accounts.payer,
+            self.accounts.split_record,
+            split_record_seeds,
+            SplitRecord::LEN,
+        )?;
+
+        let mut split_record_data = self.accounts.split_record.try_borrow_mut_data()?;
+        let split_record = SplitRecord::load_mut(&mut split_record_data)?;
+        split_record.set_inner(
+            *self.accounts.withdrawer.key(),
+            *self.accounts.new_stake_account.key(),
+            self.data.lamports_to_split,
+            lamports_to_split,
+            lst_to_burn,
+            lamports_scheduled,
+            epoch + 1,
+        );
+        split_record.rent_payer = *self.accounts.payer.key();
+        split_record.created_epoch = epoch;
+
+        emit(
+            self.accounts.pool_state_pda,
+            EVENT_SPLIT,
+            &[
+                self.accounts.withdrawer.key(),
+                self.accounts.new_stake_account.key(),
+                &lamports_to_split.to_le_bytes(),
+                &lst_to_burn.to_le_bytes(),
+            ],
+        )?;
 
         Ok(())
     }
+
+    /// Rounds the requested split up to the split minimum, or down so the
+    /// source keeps its minimum, when the difference is within the dust
+    /// tolerance.
+    fn lamports_to_split_with_dust(
+        &self,
+        source: &AccountInfo,
+        split_minimum: u64,
+        main_minimum: u64,
+    ) -> Result<u64, ProgramError> {
+        let main_available = source.lamports().saturating_sub(main_minimum);
+
+        let mut lamports_to_split = self.data.lamports_to_split;
+
+        if lamports_to_split < split_minimum {
+            if split_minimum - lamports_to_split > self.data.dust_tolerance {
+                return Err(PinocchioError::SplitBelowMinimum.into());
+            }
+            lamports_to_split = split_minimum;
+        }
+
+        if lamports_to_split > main_available {
+            if lamports_to_split - main_available > self.data.dust_tolerance {
+                return Err(PinocchioError::MainBelowMinimum.into());
+            }
+            lamports_to_split = main_available;
+
+            if lamports_to_split < split_minimum {
+                return Err(PinocchioError::SplitBelowMinimum.into());
+            }
+        }
+
+        Ok(lamports_to_split)
+    }
 }
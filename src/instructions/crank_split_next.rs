@@ -0,0 +1,96 @@
+use pinocchio::{
+    account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+    pubkey::find_program_address,
+};
+
+use crate::{
+    errors::PinocchioError,
+    instructions::{
+        crank_split::{CrankSplit, CrankSplitAccounts, CrankSplitInstructionData},
+        crank_split_single::CrankSplitSingleInstructionData,
+        helpers::{ProgramAccount, ProgramAccountInit},
+    },
+    state::SplitCounter,
+};
+
+/// `CrankSplit` with the nonce taken from the withdrawer's split counter PDA
+/// (`[b"split_counter", withdrawer]`) instead of the instruction data. The
+/// counter is created on first use, paid by the payer, and incremented after
+/// each split, so clients derive the next split PDA from the counter's
+/// `next_nonce` (zero while it does not exist) without tracking nonces.
+/// `Withdraw` then takes the nonce the split was made with.
+///
+/// Data: `lamports_to_split` (u64), then optionally `dust_tolerance` (u64).
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE]` Split counter PDA
+///
+/// followed by the accounts of `CrankSplit`, with the split PDA for the
+/// counter's `next_nonce` as the new stake account.
+pub struct CrankSplitNext<'a> {
+    pub split_counter: &'a AccountInfo,
+    pub split: CrankSplit<'a>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CrankSplitNext<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let [split_counter, split_accounts @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let data = CrankSplitSingleInstructionData::try_from(data)?;
+
+        Ok(Self {
+            split_counter,
+            split: CrankSplit {
+                accounts: CrankSplitAccounts::try_from(split_accounts)?,
+                data: CrankSplitInstructionData {
+                    lamports_to_split: data.lamports_to_split,
+                    nonce: None,
+                    dust_tolerance: data.dust_tolerance,
+                },
+            },
+        })
+    }
+}
+
+impl<'a> CrankSplitNext<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &93;
+
+    pub fn process(&mut self) -> Result<(), ProgramError> {
+        let withdrawer = self.split.accounts.withdrawer;
+        let (expected_split_counter, split_counter_bump) =
+            find_program_address(&[b"split_counter", withdrawer.key()], &crate::ID);
+        if expected_split_counter != *self.split_counter.key() {
+            return Err(PinocchioError::InvalidSplitCounterPda.into());
+        }
+
+        if self.split_counter.data_is_empty() {
+            let split_counter_bump_binding = [split_counter_bump];
+            let split_counter_seeds = &[
+                Seed::from(b"split_counter"),
+                Seed::from(withdrawer.key()),
+                Seed::from(&split_counter_bump_binding),
+            ];
+            ProgramAccount::init::<SplitCounter>(
+                self.split.accounts.payer,
+                self.split_counter,
+                split_counter_seeds,
+                SplitCounter::LEN,
+            )?;
+        }
+
+        let nonce = SplitCounter::load(&self.split_counter.try_borrow_data()?)?.next_nonce;
+        self.split.data.nonce = Some(nonce);
+        self.split.process()?;
+
+        let mut split_counter_data = self.split_counter.try_borrow_mut_data()?;
+        SplitCounter::load_mut(&mut split_counter_data)?.next_nonce = nonce
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::instructions::crank_split::{CrankSplit, CrankSplitAccounts, CrankSplitInstructionData};
+
+pub struct CrankSplitSingleInstructionData {
+    pub lamports_to_split: u64,
+    /// Lamports the split may be rounded up or down by to satisfy stake minimums.
+    pub dust_tolerance: u64,
+}
+
+impl TryFrom<&[u8]> for CrankSplitSingleInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        let dust_tolerance = match data.len() {
+            8 => 0,
+            16 => u64::from_le_bytes(data[8..16].try_into().unwrap()),
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        Ok(Self {
+            lamports_to_split: u64::from_le_bytes(data[0..8].try_into().unwrap()),
+            dust_tolerance,
+        })
+    }
+}
+
+/// `CrankSplit` for wallets: the split account is the withdrawer's fixed
+/// `[b"split_account", withdrawer]` PDA instead of one per nonce, so a wallet
+/// can find it without tracking nonces. The withdrawer can have one such
+/// split pending at a time: a second fails with `SplitAlreadyPending` until
+/// `Withdraw` empties the account. Splits served in tranches are continued by
+/// `CrankSplitTranche` as usual.
+///
+/// Data: `lamports_to_split` (u64), then optionally `dust_tolerance` (u64).
+///
+/// Accounts expected: the same as `CrankSplit`, with the single split PDA as
+/// the new stake account.
+pub struct CrankSplitSingle<'a> {
+    pub split: CrankSplit<'a>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CrankSplitSingle<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let data = CrankSplitSingleInstructionData::try_from(data)?;
+
+        Ok(Self {
+            split: CrankSplit {
+                accounts: CrankSplitAccounts::try_from(accounts)?,
+                data: CrankSplitInstructionData {
+                    lamports_to_split: data.lamports_to_split,
+                    nonce: None,
+                    dust_tolerance: data.dust_tolerance,
+                },
+            },
+        })
+    }
+}
+
+impl<'a> CrankSplitSingle<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &68;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        self.split.process()
+    }
+}
@@ -0,0 +1,439 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+use pinocchio_token::{
+    instructions::Burn,
+    state::{Mint, TokenAccount},
+};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit, EVENT_SPLIT},
+    ids::{
+        assert_clock_sysvar, assert_stake_program, assert_system_program, assert_token_program,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+    },
+    instructions::helpers::{
+        accrue_rewards, check_config_pda, epoch_rate, rebated_withdraw_fee_bps,
+        record_managed_change, record_pool_stake,
+    },
+    instructions::{
+        crank_split::{main_minimum, next_tranche, split_minimum},
+        helpers::{
+            expect_lst_supply_change, stake_account_space, AccountCheck, AccountClose,
+            ProgramAccount, ProgramAccountInit, SignerAccount, StakeAccountCreate,
+            StakeAccountDeactivate, StakeAccountSplit, SupplyChange,
+        },
+    },
+    math::lst_to_burn_with_fee,
+    state::{Config, ManagedChange, SplitRecord},
+};
+
+pub struct CrankSplitTrancheAccounts<'a> {
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub withdrawer: &'a AccountInfo,
+    pub new_stake_account: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub withdrawer_ata: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub clock_sysvar: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub stake_program: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub schedule_split_account: &'a AccountInfo,
+    pub schedule_record: &'a AccountInfo,
+    pub split_record: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+    /// Funds the new split account and split record. The withdrawer unless
+    /// a separate payer is passed.
+    pub payer: &'a AccountInfo,
+    /// The withdrawer's user stats PDA, for the holding rebate on the
+    /// withdrawal fee.
+    pub user_stats_pda: Option<&'a AccountInfo>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CrankSplitTrancheAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [stake_account_main, stake_account_reserve, withdrawer, new_stake_account, config_pda, withdrawer_ata, lst_mint, clock_sysvar, token_program, stake_program, system_program, schedule_split_account, schedule_record, split_record, pool_state_pda, optional_accounts @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let (payer, user_stats_pda) = match optional_accounts {
+            [] => (withdrawer, None),
+            [payer] => {
+                SignerAccount::check(payer)?;
+                (payer, None)
+            }
+            [payer, user_stats_pda] => {
+                SignerAccount::check(payer)?;
+                (payer, Some(user_stats_pda))
+            }
+            _ => return Err(ProgramError::NotEnoughAccountKeys),
+        };
+
+        SignerAccount::check(withdrawer)?;
+
+        assert_system_program(system_program)?;
+        assert_stake_program(stake_program)?;
+        assert_clock_sysvar(clock_sysvar)?;
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            stake_account_main,
+            stake_account_reserve,
+            withdrawer,
+            new_stake_account,
+            config_pda,
+            withdrawer_ata,
+            lst_mint,
+            clock_sysvar,
+            token_program,
+            stake_program,
+            system_program,
+            schedule_split_account,
+            schedule_record,
+            split_record,
+            pool_state_pda,
+            payer,
+            user_stats_pda,
+        })
+    }
+}
+
+pub struct CrankSplitTrancheInstructionData {
+    /// Nonce of the split that created the schedule. Not checked, see
+    /// `process`.
+    pub schedule_nonce: u64,
+    /// Nonce of the split account this tranche is split into.
+    pub nonce: u64,
+}
+
+impl TryFrom<&[u8]> for CrankSplitTrancheInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 16 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let schedule_nonce = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let nonce = u64::from_le_bytes(data[8..16].try_into().unwrap());
+
+        Ok(Self {
+            schedule_nonce,
+            nonce,
+        })
+    }
+}
+
+/// Splits the next scheduled tranche of a large `CrankSplit` into a new
+/// split account, deactivates it, and burns LST for it at the current rate,
+/// withdrawal fee included. Passing the withdrawer's user stats PDA
+/// rebates the fee by the epochs held, as in `CrankSplit`.
+///
+/// One tranche may be split per epoch. Each tranche gets its own split
+/// record and is claimed with `Withdraw` once cooled down. The schedule's
+/// record is closed after its last tranche if its own split was already
+/// withdrawn.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE]` Stake account main
+/// 1. `[]` Stake account reserve
+/// 2. `[WRITE, SIGNER]` Withdrawer
+/// 3. `[WRITE]` New stake account (split PDA)
+/// 4. `[]` Config PDA
+/// 5. `[WRITE]` Withdrawer ATA
+/// 6. `[WRITE]` LST mint
+/// 7. `[]` Clock sysvar
+/// 8. `[]` Token program
+/// 9. `[]` Stake program
+/// 10. `[]` System program
+/// 11. `[]` Schedule split account (split PDA of the original split)
+/// 12. `[WRITE]` Schedule split record PDA
+/// 13. `[WRITE]` Split record PDA
+/// 14. `[WRITE]` Pool state PDA
+/// 15. `[WRITE, SIGNER]` Payer (optional, defaults to the withdrawer)
+/// 16. `[]` User stats PDA (optional, requires the payer)
+pub struct CrankSplitTranche<'a> {
+    pub accounts: CrankSplitTrancheAccounts<'a>,
+    pub data: CrankSplitTrancheInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CrankSplitTranche<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: CrankSplitTrancheAccounts::try_from(accounts)?,
+            data: CrankSplitTrancheInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> CrankSplitTranche<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &9;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let bump = check_config_pda(self.accounts.config_pda)?;
+
+        let data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        config.status()?.check_unstakes()?;
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        let min_split_stake_lamports = config.min_split_stake_lamports;
+        let main_headroom_lamports = config.main_headroom_lamports;
+
+        let expected_ata = find_program_address(
+            &[
+                self.accounts.withdrawer.key(),
+                self.accounts.token_program.key(),
+                self.accounts.lst_mint.key(),
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .0;
+        if expected_ata != *self.accounts.withdrawer_ata.key() {
+            return Err(PinocchioError::InvalidWithdrawerAta.into());
+        }
+
+        // As in `Withdraw`, a handed-off schedule keeps the address derived
+        // from its first owner, so the record below identifies it.
+        let expected_schedule_record = find_program_address(
+            &[b"split_record", self.accounts.schedule_split_account.key()],
+            &crate::ID,
+        )
+        .0;
+
+        if expected_schedule_record != *self.accounts.schedule_record.key() {
+            return Err(PinocchioError::InvalidSplitRecordPda.into());
+        }
+
+        ProgramAccount::check(self.accounts.schedule_record)?;
+
+        let epoch = Clock::get()?.epoch;
+
+        let schedule_record_data = self.accounts.schedule_record.try_borrow_data()?;
+        let schedule = SplitRecord::load(&schedule_record_data)?;
+
+        if schedule.owner != *self.accounts.withdrawer.key() {
+            return Err(PinocchioError::InvalidSplitAccountPda.into());
+        }
+
+        let lamports_scheduled = schedule.lamports_scheduled;
+        if lamports_scheduled == 0 {
+            return Err(PinocchioError::NothingScheduled.into());
+        }
+
+        if epoch < schedule.next_tranche_epoch {
+            return Err(PinocchioError::TrancheNotReady.into());
+        }
+
+        drop(schedule_record_data);
+
+        let nonce_bytes = self.data.nonce.to_le_bytes();
+        let (expected_new_stake_account, new_stake_account_bump) = find_program_address(
+            &[
+                b"split_account",
+                self.accounts.withdrawer.key(),
+                &nonce_bytes,
+            ],
+            &crate::ID,
+        );
+
+        if expected_new_stake_account != *self.accounts.new_stake_account.key() {
+            return Err(PinocchioError::InvalidSplitAccountPda.into());
+        }
+
+        let (expected_split_record, split_record_bump) = find_program_address(
+            &[b"split_record", self.accounts.new_stake_account.key()],
+            &crate::ID,
+        );
+
+        if expected_split_record != *self.accounts.split_record.key() {
+            return Err(PinocchioError::InvalidSplitRecordPda.into());
+        }
+
+        let space = stake_account_space(self.accounts.stake_account_main);
+        let main_lamports = self.accounts.stake_account_main.lamports();
+        let (lamports_to_split, lamports_remaining) = next_tranche(
+            lamports_scheduled,
+            main_lamports,
+            split_minimum(space, min_split_stake_lamports)?,
+        );
+
+        let main_minimum = main_minimum(self.accounts.stake_account_main, main_headroom_lamports)?;
+        if main_lamports.saturating_sub(lamports_to_split) < main_minimum {
+            return Err(PinocchioError::MainBelowMinimum.into());
+        }
+
+        let managed = accrue_rewards(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            config.validator_stake_lamports,
+        )?;
+
+        let bump_binding = [bump];
+        let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
+
+        let new_stake_account_bump_binding = [new_stake_account_bump];
+        let new_stake_seeds = &[
+            Seed::from(b"split_account"),
+            Seed::from(self.accounts.withdrawer.key()),
+            Seed::from(&nonce_bytes),
+            Seed::from(&new_stake_account_bump_binding),
+        ];
+
+        ProgramAccount::stake_account_create(
+            self.accounts.payer,
+            self.accounts.new_stake_account,
+            new_stake_seeds,
+            space,
+        )?;
+
+        ProgramAccount::split_stake_account(
+            self.accounts.stake_account_main,
+            self.accounts.new_stake_account,
+            &lamports_to_split,
+            self.accounts.config_pda,
+            config_seeds,
+        )?;
+
+        record_pool_stake(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            config.validator_stake_lamports,
+        )?;
+
+        ProgramAccount::deactivate_stake_account(
+            self.accounts.new_stake_account,
+            self.accounts.clock_sysvar,
+            self.accounts.config_pda,
+            config_seeds,
+        )?;
+
+        // `managed` was read before the split, so it still counts the split
+        // lamports.
+        let (total_lamports_managed, total_supply_mint) = epoch_rate(config)?.unwrap_or((
+            managed,
+            Mint::from_account_info(self.accounts.lst_mint)?.supply(),
+        ));
+
+        let lst_value = (lamports_to_split as u128)
+            .checked_mul(total_supply_mint as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .checked_div(total_lamports_managed as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)? as u64;
+        // The withdrawal fee is burned on top, accruing to the other holders.
+        let withdraw_fee_bps = rebated_withdraw_fee_bps(
+            config,
+            self.accounts.user_stats_pda,
+            self.accounts.withdrawer,
+            epoch,
+        )?;
+        let lst_to_burn = lst_to_burn_with_fee(lst_value, withdraw_fee_bps)?;
+
+        let withdrawer_ata_amount =
+            TokenAccount::from_account_info(self.accounts.withdrawer_ata)?.amount();
+        if withdrawer_ata_amount < lst_to_burn {
+            return Err(PinocchioError::InsufficientLstBalance.into());
+        }
+
+        expect_lst_supply_change(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            SupplyChange::Burned(lst_to_burn),
+            || {
+                Burn {
+                    account: self.accounts.withdrawer_ata,
+                    mint: self.accounts.lst_mint,
+                    authority: self.accounts.withdrawer,
+                    amount: lst_to_burn,
+                }
+                .invoke()
+            },
+        )?;
+        record_managed_change(
+            self.accounts.pool_state_pda,
+            ManagedChange::Withdrawn(lamports_to_split),
+        )?;
+
+        let split_record_bump_binding = [split_record_bump];
+        let split_record_seeds = &[
+            Seed::from(b"split_record"),
+            Seed::from(self.accounts.new_stake_account.key()),
+            Seed::from(&split_record_bump_binding),
+        ];
+
+        ProgramAccount::init::<SplitRecord>(
+            self.accounts.payer,
+            self.accounts.split_record,
+            split_record_seeds,
+            SplitRecord::LEN,
+        )?;
+
+        let mut split_record_data = self.accounts.split_record.try_borrow_mut_data()?;
+        let split_record = SplitRecord::load_mut(&mut split_record_data)?;
+        split_record.set_inner(
+            *self.accounts.withdrawer.key(),
+            *self.accounts.new_stake_account.key(),
+            lamports_to_split,
+            lamports_to_split,
+            lst_to_burn,
+            0,
+            0,
+        );
+        split_record.rent_payer = *self.accounts.payer.key();
+        split_record.created_epoch = epoch;
+        drop(split_record_data);
+
+        let mut schedule_record_data = self.accounts.schedule_record.try_borrow_mut_data()?;
+        let schedule = SplitRecord::load_mut(&mut schedule_record_data)?;
+        schedule.lamports_scheduled = lamports_remaining;
+        schedule.next_tranche_epoch = epoch + 1;
+        schedule.tranches_split += 1;
+        drop(schedule_record_data);
+
+        // `Withdraw` keeps the schedule's record open while tranches remain.
+        if lamports_remaining == 0 && self.accounts.schedule_split_account.lamports() == 0 {
+            ProgramAccount::close(self.accounts.schedule_record, self.accounts.withdrawer)?;
+        }
+
+        emit(
+            self.accounts.pool_state_pda,
+            EVENT_SPLIT,
+            &[
+                self.accounts.withdrawer.key(),
+                self.accounts.new_stake_account.key(),
+                &lamports_to_split.to_le_bytes(),
+                &lst_to_burn.to_le_bytes(),
+            ],
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+};
+use pinocchio_token::state::Mint;
+
+use crate::{
+    errors::PinocchioError,
+    instructions::helpers::{
+        check_config_pda, check_validator_stake_current, managed_lamports, mark_epoch_task,
+    },
+    state::{Config, EPOCH_TASK_RATE_UPDATE},
+};
+
+pub struct CrankUpdateRateAccounts<'a> {
+    pub config_pda: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub epoch_tasks_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CrankUpdateRateAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [config_pda, stake_account_main, stake_account_reserve, lst_mint, epoch_tasks_pda, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            lst_mint,
+            epoch_tasks_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+/// Caches the exchange rate for the epoch: snapshots the pool's managed
+/// lamports and the LST supply into the config's `rate_lamports` and
+/// `rate_lst_supply`, with the epoch in `rate_epoch`. For the rest of the
+/// epoch every instruction that mints or burns LST prices against the
+/// snapshot, so rewards credited mid-epoch do not move the rate one trade
+/// pays and not another. Before the first call of an epoch they price
+/// against the live managed lamports. Callable once per epoch, a second
+/// call fails with `RateAlreadyUpdated`. Permissionless.
+///
+/// Marks the epoch's rate update task, which waits for the epoch's
+/// `CrankManagementFee` so the snapshot is never taken before the fee is
+/// charged. Fails with `ValidatorStakeStale` until `CrankValidatorStake` has
+/// recorded every staked validator this epoch, so the snapshot includes
+/// their rewards.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE]` Config PDA
+/// 1. `[]` Stake account main
+/// 2. `[]` Stake account reserve
+/// 3. `[]` LST mint
+/// 4. `[WRITE]` Epoch tasks PDA
+/// 5. `[]` Pool state PDA
+pub struct CrankUpdateRate<'a> {
+    pub accounts: CrankUpdateRateAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CrankUpdateRate<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            accounts: CrankUpdateRateAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> CrankUpdateRate<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &95;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let epoch = Clock::get()?.epoch;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        if config.cached_rate(epoch).is_some() {
+            return Err(PinocchioError::RateAlreadyUpdated.into());
+        }
+        check_validator_stake_current(config, epoch)?;
+
+        let total_lamports = managed_lamports(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            config.validator_stake_lamports,
+        )?;
+        let lst_supply = Mint::from_account_info(self.accounts.lst_mint)?.supply();
+
+        mark_epoch_task(self.accounts.epoch_tasks_pda, EPOCH_TASK_RATE_UPDATE)?;
+
+        config.set_cached_rate(total_lamports, lst_supply, epoch);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    errors::PinocchioError,
+    ids::assert_system_program,
+    instructions::helpers::{
+        check_config_pda, vote_account_summary, AccountCheck, ProgramAccount, ProgramAccountInit,
+        SignerAccount,
+    },
+    state::{Config, ValidatorHealth, ValidatorList, HEALTH_WINDOW_EPOCHS},
+};
+
+pub struct CrankValidatorHealthAccounts<'a> {
+    pub payer: &'a AccountInfo,
+    pub vote_account: &'a AccountInfo,
+    pub health_pda: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub validator_list_pda: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CrankValidatorHealthAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [payer, vote_account, health_pda, config_pda, validator_list_pda, system_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(payer)?;
+
+        assert_system_program(system_program)?;
+
+        Ok(Self {
+            payer,
+            vote_account,
+            health_pda,
+            config_pda,
+            validator_list_pda,
+            system_program,
+        })
+    }
+}
+
+/// Copies a pool validator's commission, latest vote and root slots and
+/// recent epoch credits from its vote account into its validator health PDA
+/// (`b"validator_health"`, vote account), creating the PDA on first use at
+/// the payer's expense. Permissionless, so any frontend or keeper can keep
+/// the figures fresh.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Payer
+/// 1. `[]` Vote account
+/// 2. `[WRITE]` Validator health PDA
+/// 3. `[]` Config PDA
+/// 4. `[]` Validator list PDA
+/// 5. `[]` System program
+pub struct CrankValidatorHealth<'a> {
+    pub accounts: CrankValidatorHealthAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CrankValidatorHealth<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: CrankValidatorHealthAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> CrankValidatorHealth<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &60;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let (expected_validator_list_pda, _) =
+            find_program_address(&[b"validator_list"], &crate::ID);
+        if expected_validator_list_pda != *self.accounts.validator_list_pda.key() {
+            return Err(PinocchioError::InvalidValidatorListPda.into());
+        }
+
+        let vote_account = self.accounts.vote_account.key();
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let is_pool_validator = Config::load(&config_data)?.validator_vote_pubkey == *vote_account
+            || (!self.accounts.validator_list_pda.data_is_empty()
+                && ValidatorList::load(&self.accounts.validator_list_pda.try_borrow_data()?)?
+                    .contains(vote_account));
+        drop(config_data);
+        if !is_pool_validator {
+            return Err(PinocchioError::ValidatorNotInList.into());
+        }
+
+        let summary = vote_account_summary(self.accounts.vote_account, HEALTH_WINDOW_EPOCHS)?;
+
+        let (expected_health_pda, health_bump) =
+            find_program_address(&[b"validator_health", vote_account], &crate::ID);
+        if expected_health_pda != *self.accounts.health_pda.key() {
+            return Err(PinocchioError::InvalidValidatorHealthPda.into());
+        }
+
+        if self.accounts.health_pda.data_is_empty() {
+            let health_bump_binding = [health_bump];
+            let health_seeds = &[
+                Seed::from(b"validator_health"),
+                Seed::from(vote_account),
+                Seed::from(&health_bump_binding),
+            ];
+
+            ProgramAccount::init::<ValidatorHealth>(
+                self.accounts.payer,
+                self.accounts.health_pda,
+                health_seeds,
+                ValidatorHealth::LEN,
+            )?;
+        } else {
+            ProgramAccount::check(self.accounts.health_pda)?;
+        }
+
+        let mut health_data = self.accounts.health_pda.try_borrow_mut_data()?;
+        let health = ValidatorHealth::load_mut(&mut health_data)?;
+        health.vote_account = *vote_account;
+        health.commission = summary.commission;
+        health.last_vote_slot = summary.last_vote_slot;
+        health.root_slot = summary.root_slot;
+        health.last_epoch = summary.last_epoch;
+        health.last_epoch_credits = summary.last_epoch_credits;
+        health.window_epochs = summary.window_epochs;
+        health.window_credits = summary.window_credits;
+        health.refreshed_slot = Clock::get()?.slot;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,298 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+};
+
+use crate::{
+    errors::PinocchioError,
+    ids::{
+        assert_clock_sysvar, assert_rent_sysvar, assert_stake_history_sysvar, assert_stake_program,
+        assert_system_program, assert_vote_account, STAKE_PROGRAM_ID,
+    },
+    instructions::helpers::{
+        accrue_rewards, check_config_pda, check_validator_list, check_validator_stake,
+        create_pda_account, minimum_delegation, pool_lamports, record_pool_stake,
+        record_validator_stake, reserve_spare_lamports, stake_account_space, stake_authorities,
+        AccountCheck, ProgramAccount, SignerAccount, StakeAccountDelegate, StakeAccountInitialize,
+        StakeAccountWithdraw,
+    },
+    state::{Config, ValidatorList},
+};
+
+pub struct CrankValidatorStakeAccounts<'a> {
+    pub payer: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub validator_list_pda: &'a AccountInfo,
+    pub validator_stake_pda: &'a AccountInfo,
+    pub validator_vote_account: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub unused_account: &'a AccountInfo,
+    pub rent_sysvar: &'a AccountInfo,
+    pub clock_sysvar: &'a AccountInfo,
+    pub history_sysvar: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub stake_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CrankValidatorStakeAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [payer, config_pda, validator_list_pda, validator_stake_pda, validator_vote_account, stake_account_main, stake_account_reserve, unused_account, rent_sysvar, clock_sysvar, history_sysvar, system_program, stake_program, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(payer)?;
+
+        assert_system_program(system_program)?;
+        assert_stake_program(stake_program)?;
+        assert_rent_sysvar(rent_sysvar)?;
+        assert_clock_sysvar(clock_sysvar)?;
+        assert_stake_history_sysvar(history_sysvar)?;
+        assert_vote_account(validator_vote_account)?;
+
+        Ok(Self {
+            payer,
+            config_pda,
+            validator_list_pda,
+            validator_stake_pda,
+            validator_vote_account,
+            stake_account_main,
+            stake_account_reserve,
+            unused_account,
+            rent_sysvar,
+            clock_sysvar,
+            history_sysvar,
+            system_program,
+            stake_program,
+            pool_state_pda,
+        })
+    }
+}
+
+/// Spreads the pool's stake to a listed validator through its validator
+/// stake PDA (`b"validator_stake"`, vote account). Permissionless.
+///
+/// While the PDA is not an initialized stake account, moves the validator's
+/// target stake, from the config's delegation strategy and bounded by the
+/// per-validator stake cap, out of the reserve's undelegated lamports into
+/// the PDA and delegates it. The payer funds the PDA's rent unless
+/// `AddValidator` already created it. Fails with `ReserveDelegated` once
+/// `CrankInitializeReserve` has run this epoch, and with `NothingToDelegate`
+/// when less than the minimum delegation would move.
+///
+/// Once the PDA holds stake, records its lamports in the validator list and
+/// the config's `validator_stake_lamports` instead, crediting the rewards it
+/// earned to the pool state's managed lamports. Run it for every staked validator each
+/// epoch. Moving stake between validators afterwards is left to
+/// rebalancing. The config's validator is staked through main and is
+/// rejected with `InvalidValidatorVoteKey`.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Payer
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Validator list PDA
+/// 3. `[WRITE]` Validator stake PDA
+/// 4. `[]` Validator vote account
+/// 5. `[]` Stake account main
+/// 6. `[WRITE]` Stake account reserve
+/// 7. `[]` Unused account
+/// 8. `[]` Rent sysvar
+/// 9. `[]` Clock sysvar
+/// 10. `[]` Stake history sysvar
+/// 11. `[]` System program
+/// 12. `[]` Stake program
+/// 13. `[WRITE]` Pool state PDA
+pub struct CrankValidatorStake<'a> {
+    pub accounts: CrankValidatorStakeAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CrankValidatorStake<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            accounts: CrankValidatorStakeAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> CrankValidatorStake<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &78;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let bump = check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        let vote_account = self.accounts.validator_vote_account.key();
+        if config.validator_vote_pubkey == *vote_account {
+            return Err(PinocchioError::InvalidValidatorVoteKey.into());
+        }
+        let validator_stake_lamports = config.validator_stake_lamports;
+        drop(config_data);
+
+        check_validator_list(self.accounts.validator_list_pda)?;
+        let validator_stake_bump =
+            check_validator_stake(self.accounts.validator_stake_pda, vote_account)?;
+
+        let delegating = stake_authorities(self.accounts.validator_stake_pda)?.is_none();
+        if delegating {
+            accrue_rewards(
+                self.accounts.pool_state_pda,
+                self.accounts.stake_account_main,
+                self.accounts.stake_account_reserve,
+                validator_stake_lamports,
+            )?;
+
+            let bump_binding = [bump];
+            let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
+            self.delegate_from_reserve(validator_stake_bump, config_seeds)?;
+        }
+
+        record_validator_stake(
+            self.accounts.config_pda,
+            self.accounts.validator_list_pda,
+            self.accounts.validator_stake_pda,
+            vote_account,
+        )?;
+
+        // A delegation moved the reserve's lamports; otherwise the recorded
+        // stake grew by the validator's rewards alone.
+        let validator_stake_lamports =
+            Config::load(&self.accounts.config_pda.try_borrow_data()?)?.validator_stake_lamports;
+        if delegating {
+            record_pool_stake(
+                self.accounts.pool_state_pda,
+                self.accounts.stake_account_main,
+                self.accounts.stake_account_reserve,
+                validator_stake_lamports,
+            )
+        } else {
+            accrue_rewards(
+                self.accounts.pool_state_pda,
+                self.accounts.stake_account_main,
+                self.accounts.stake_account_reserve,
+                validator_stake_lamports,
+            )
+            .map(|_| ())
+        }
+    }
+
+    /// Moves the validator's target stake from the reserve into its empty
+    /// stake PDA and delegates it to the validator.
+    fn delegate_from_reserve(
+        &self,
+        validator_stake_bump: u8,
+        config_seeds: &[Seed],
+    ) -> Result<(), ProgramError> {
+        let reserve = self.accounts.stake_account_reserve;
+        let reserve_spare = reserve_spare_lamports(reserve, PinocchioError::ReserveNotAdopted)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+        let total_lamports = pool_lamports(
+            self.accounts.stake_account_main,
+            reserve,
+            config.validator_stake_lamports,
+        )?;
+
+        let validator_list_data = self.accounts.validator_list_pda.try_borrow_data()?;
+        let entries = ValidatorList::load(&validator_list_data)?.entries();
+        let index = entries
+            .iter()
+            .position(|entry| entry.vote_account == *self.accounts.validator_vote_account.key())
+            .ok_or(PinocchioError::ValidatorNotInList)?;
+        let mut targets = [0u64; ValidatorList::CAPACITY];
+        config
+            .delegation_strategy()?
+            .targets(entries, total_lamports, &mut targets);
+
+        let lamports = targets[index]
+            .min(config.validator_stake_cap(total_lamports))
+            .min(reserve_spare);
+        drop(validator_list_data);
+        drop(config_data);
+
+        if lamports == 0 || lamports < minimum_delegation()? {
+            return Err(PinocchioError::NothingToDelegate.into());
+        }
+
+        // `AddValidator` creates the PDA up front; validators activated
+        // through their bond have it created here.
+        if !self
+            .accounts
+            .validator_stake_pda
+            .is_owned_by(&STAKE_PROGRAM_ID)
+        {
+            let space = stake_account_space(reserve);
+            let validator_stake_bump_binding = [validator_stake_bump];
+            let validator_stake_seeds = &[
+                Seed::from(b"validator_stake"),
+                Seed::from(self.accounts.validator_vote_account.key()),
+                Seed::from(&validator_stake_bump_binding),
+            ];
+            create_pda_account(
+                self.accounts.payer,
+                self.accounts.validator_stake_pda,
+                validator_stake_seeds,
+                Rent::get()?.minimum_balance(space),
+                space,
+                &STAKE_PROGRAM_ID,
+            )?;
+        }
+
+        let stake_reserve_bump =
+            Config::load(&self.accounts.config_pda.try_borrow_data()?)?.stake_reserve_bump;
+        let stake_reserve_bump_binding = [stake_reserve_bump];
+        let stake_reserve_seeds = &[
+            Seed::from(b"stake_reserve"),
+            Seed::from(&stake_reserve_bump_binding),
+        ];
+
+        // An uninitialized stake account is its own withdraw authority.
+        ProgramAccount::withdraw_stake_account_lamports(
+            reserve,
+            self.accounts.validator_stake_pda,
+            self.accounts.clock_sysvar,
+            self.accounts.history_sysvar,
+            reserve,
+            lamports,
+            stake_reserve_seeds,
+        )?;
+
+        ProgramAccount::initialize_stake_account_no_lockup(
+            self.accounts.validator_stake_pda,
+            self.accounts.config_pda,
+            self.accounts.config_pda,
+            self.accounts.rent_sysvar,
+            config_seeds,
+        )?;
+
+        ProgramAccount::delegate_stake_account(
+            self.accounts.validator_stake_pda,
+            self.accounts.validator_vote_account,
+            self.accounts.clock_sysvar,
+            self.accounts.history_sysvar,
+            self.accounts.unused_account,
+            self.accounts.config_pda,
+            config_seeds,
+        )
+    }
+}
@@ -0,0 +1,174 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+use pinocchio_system::instructions::Transfer;
+
+use crate::{
+    errors::PinocchioError,
+    ids::assert_system_program,
+    instructions::helpers::{
+        AccountCheck, ProgramAccount, ProgramAccountInit, SignerAccount, SystemAccount,
+        LAMPORTS_PER_SOL,
+    },
+    state::DcaSchedule,
+};
+
+pub struct CreateDcaScheduleAccounts<'a> {
+    pub user: &'a AccountInfo,
+    pub dca_schedule_pda: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CreateDcaScheduleAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [user, dca_schedule_pda, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(user)?;
+
+        assert_system_program(system_program)?;
+
+        SystemAccount::check(dca_schedule_pda)?;
+
+        if !dca_schedule_pda.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        Ok(Self {
+            user,
+            dca_schedule_pda,
+            system_program,
+        })
+    }
+}
+
+pub struct CreateDcaScheduleInstructionData {
+    pub nonce: u64,
+    pub amount_per_interval: u64,
+    pub interval_secs: i64,
+    pub tip_lamports: u64,
+    pub budget_lamports: u64,
+}
+
+impl TryFrom<&[u8]> for CreateDcaScheduleInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 40 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let nonce = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let amount_per_interval = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let interval_secs = i64::from_le_bytes(data[16..24].try_into().unwrap());
+        let tip_lamports = u64::from_le_bytes(data[24..32].try_into().unwrap());
+        let budget_lamports = u64::from_le_bytes(data[32..40].try_into().unwrap());
+
+        if amount_per_interval < LAMPORTS_PER_SOL {
+            return Err(PinocchioError::DepositBelowMinimum.into());
+        }
+
+        if interval_secs <= 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if budget_lamports
+            < amount_per_interval
+                .checked_add(tip_lamports)
+                .ok_or(ProgramError::ArithmeticOverflow)?
+        {
+            return Err(PinocchioError::DcaBudgetExhausted.into());
+        }
+
+        Ok(Self {
+            nonce,
+            amount_per_interval,
+            interval_secs,
+            tip_lamports,
+            budget_lamports,
+        })
+    }
+}
+
+/// Creates a recurring deposit of `amount_per_interval` lamports every
+/// `interval_secs`, funded by escrowing `budget_lamports` in a DCA schedule
+/// PDA (`b"dca_schedule"`, user, nonce). Keepers execute it with `CrankDca`
+/// and are paid `tip_lamports` from the budget per deposit. The first deposit
+/// is due immediately.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` User
+/// 1. `[WRITE]` DCA schedule PDA
+/// 2. `[]` System program
+pub struct CreateDcaSchedule<'a> {
+    pub accounts: CreateDcaScheduleAccounts<'a>,
+    pub data: CreateDcaScheduleInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CreateDcaSchedule<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: CreateDcaScheduleAccounts::try_from(accounts)?,
+            data: CreateDcaScheduleInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> CreateDcaSchedule<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &35;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let nonce_bytes = self.data.nonce.to_le_bytes();
+        let (expected_dca_schedule_pda, dca_schedule_bump) = find_program_address(
+            &[b"dca_schedule", self.accounts.user.key(), &nonce_bytes],
+            &crate::ID,
+        );
+        if expected_dca_schedule_pda != *self.accounts.dca_schedule_pda.key() {
+            return Err(PinocchioError::InvalidDcaSchedulePda.into());
+        }
+
+        let dca_schedule_bump_binding = [dca_schedule_bump];
+        let dca_schedule_seeds = &[
+            Seed::from(b"dca_schedule"),
+            Seed::from(self.accounts.user.key()),
+            Seed::from(&nonce_bytes),
+            Seed::from(&dca_schedule_bump_binding),
+        ];
+
+        ProgramAccount::init::<DcaSchedule>(
+            self.accounts.user,
+            self.accounts.dca_schedule_pda,
+            dca_schedule_seeds,
+            DcaSchedule::LEN,
+        )?;
+
+        Transfer {
+            from: self.accounts.user,
+            to: self.accounts.dca_schedule_pda,
+            lamports: self.data.budget_lamports,
+        }
+        .invoke()?;
+
+        let mut dca_schedule_data = self.accounts.dca_schedule_pda.try_borrow_mut_data()?;
+        DcaSchedule::load_mut(&mut dca_schedule_data)?.set_inner(
+            *self.accounts.user.key(),
+            self.data.amount_per_interval,
+            self.data.interval_secs,
+            Clock::get()?.unix_timestamp,
+            self.data.budget_lamports,
+            self.data.tip_lamports,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+use pinocchio_system::instructions::Transfer;
+
+use crate::{
+    errors::PinocchioError,
+    ids::assert_system_program,
+    instructions::helpers::{
+        AccountCheck, ProgramAccount, ProgramAccountInit, SignerAccount, SystemAccount,
+    },
+    state::DepositSession,
+};
+
+pub struct CreateDepositSessionAccounts<'a> {
+    pub user: &'a AccountInfo,
+    pub session_key: &'a AccountInfo,
+    pub deposit_session_pda: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CreateDepositSessionAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [user, session_key, deposit_session_pda, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(user)?;
+
+        assert_system_program(system_program)?;
+
+        SystemAccount::check(deposit_session_pda)?;
+
+        if !deposit_session_pda.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        Ok(Self {
+            user,
+            session_key,
+            deposit_session_pda,
+            system_program,
+        })
+    }
+}
+
+pub struct CreateDepositSessionInstructionData {
+    pub budget_lamports: u64,
+    pub expires_at: i64,
+}
+
+impl TryFrom<&[u8]> for CreateDepositSessionInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 16 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let budget_lamports = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let expires_at = i64::from_le_bytes(data[8..16].try_into().unwrap());
+
+        if budget_lamports == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            budget_lamports,
+            expires_at,
+        })
+    }
+}
+
+/// Authorizes a session key to deposit on the user's behalf until
+/// `expires_at`, up to `budget_lamports` in total.
+///
+/// The budget is moved from the user into the deposit session PDA
+/// (`b"deposit_session"`, user, session key), so the session key never
+/// needs the user's signature. It can only turn the escrowed SOL into LST
+/// for the user with `DepositWithSession`; the user gets back whatever is
+/// left with `CloseDepositSession`.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` User
+/// 1. `[]` Session key
+/// 2. `[WRITE]` Deposit session PDA
+/// 3. `[]` System program
+pub struct CreateDepositSession<'a> {
+    pub accounts: CreateDepositSessionAccounts<'a>,
+    pub data: CreateDepositSessionInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CreateDepositSession<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: CreateDepositSessionAccounts::try_from(accounts)?,
+            data: CreateDepositSessionInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> CreateDepositSession<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &32;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        if self.data.expires_at <= Clock::get()?.unix_timestamp {
+            return Err(PinocchioError::DepositSessionExpired.into());
+        }
+
+        let (expected_deposit_session_pda, deposit_session_bump) = find_program_address(
+            &[
+                b"deposit_session",
+                self.accounts.user.key(),
+                self.accounts.session_key.key(),
+            ],
+            &crate::ID,
+        );
+        if expected_deposit_session_pda != *self.accounts.deposit_session_pda.key() {
+            return Err(PinocchioError::InvalidDepositSessionPda.into());
+        }
+
+        let deposit_session_bump_binding = [deposit_session_bump];
+        let deposit_session_seeds = &[
+            Seed::from(b"deposit_session"),
+            Seed::from(self.accounts.user.key()),
+            Seed::from(self.accounts.session_key.key()),
+            Seed::from(&deposit_session_bump_binding),
+        ];
+
+        ProgramAccount::init::<DepositSession>(
+            self.accounts.user,
+            self.accounts.deposit_session_pda,
+            deposit_session_seeds,
+            DepositSession::LEN,
+        )?;
+
+        Transfer {
+            from: self.accounts.user,
+            to: self.accounts.deposit_session_pda,
+            lamports: self.data.budget_lamports,
+        }
+        .invoke()?;
+
+        let mut deposit_session_data = self.accounts.deposit_session_pda.try_borrow_mut_data()?;
+        DepositSession::load_mut(&mut deposit_session_data)?.set_inner(
+            *self.accounts.user.key(),
+            *self.accounts.session_key.key(),
+            self.data.budget_lamports,
+            self.data.expires_at,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,222 @@
+use pinocchio::{
+    account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+    pubkey::find_program_address,
+};
+use pinocchio_system::instructions::Transfer;
+use pinocchio_token::instructions::Transfer as TokenTransfer;
+
+use crate::{
+    errors::PinocchioError,
+    ids::{
+        assert_associated_token_program, assert_system_program, assert_token_program,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+    },
+    instructions::helpers::{
+        check_config_pda, AccountCheck, AssociatedTokenAccount, AssociatedTokenAccountInit,
+        ProgramAccount, ProgramAccountInit, SignerAccount, SystemAccount,
+    },
+    state::{Config, UnstakeOrder},
+};
+
+pub struct CreateUnstakeOrderAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub owner_ata: &'a AccountInfo,
+    pub order_pda: &'a AccountInfo,
+    pub order_vault: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub associated_token_program: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CreateUnstakeOrderAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [owner, owner_ata, order_pda, order_vault, lst_mint, config_pda, token_program, associated_token_program, system_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(owner)?;
+
+        assert_system_program(system_program)?;
+        assert_token_program(token_program)?;
+        assert_associated_token_program(associated_token_program)?;
+
+        SystemAccount::check(order_pda)?;
+
+        if !order_pda.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        Ok(Self {
+            owner,
+            owner_ata,
+            order_pda,
+            order_vault,
+            lst_mint,
+            config_pda,
+            token_program,
+            associated_token_program,
+            system_program,
+        })
+    }
+}
+
+pub struct CreateUnstakeOrderInstructionData {
+    pub nonce: u64,
+    pub lst_amount: u64,
+    pub min_lamports_per_lst: u64,
+    pub tip_lamports: u64,
+}
+
+impl TryFrom<&[u8]> for CreateUnstakeOrderInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 32 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let nonce = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let lst_amount = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let min_lamports_per_lst = u64::from_le_bytes(data[16..24].try_into().unwrap());
+        let tip_lamports = u64::from_le_bytes(data[24..32].try_into().unwrap());
+
+        if lst_amount == 0 {
+            return Err(PinocchioError::ZeroLiquidity.into());
+        }
+
+        Ok(Self {
+            nonce,
+            lst_amount,
+            min_lamports_per_lst,
+            tip_lamports,
+        })
+    }
+}
+
+/// Locks `lst_amount` of the owner's LST in an unstake order PDA
+/// (`b"unstake_order"`, owner, nonce) until it fetches at least
+/// `min_lamports_per_lst` lamports per `LAMPORTS_PER_SOL` LST. The LST is
+/// held in the order PDA's LST ATA, and `tip_lamports` is escrowed in the
+/// PDA for the keeper that fills the order with `FillUnstakeOrder`. The
+/// owner can take both back with `CancelUnstakeOrder` at any time.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Owner
+/// 1. `[WRITE]` Owner LST ATA
+/// 2. `[WRITE]` Unstake order PDA
+/// 3. `[WRITE]` Order vault (order PDA's LST ATA)
+/// 4. `[]` LST mint
+/// 5. `[]` Config PDA
+/// 6. `[]` Token program
+/// 7. `[]` Associated token program
+/// 8. `[]` System program
+pub struct CreateUnstakeOrder<'a> {
+    pub accounts: CreateUnstakeOrderAccounts<'a>,
+    pub data: CreateUnstakeOrderInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CreateUnstakeOrder<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: CreateUnstakeOrderAccounts::try_from(accounts)?,
+            data: CreateUnstakeOrderInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> CreateUnstakeOrder<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &57;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        if Config::load(&config_data)?.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+        drop(config_data);
+
+        let nonce_bytes = self.data.nonce.to_le_bytes();
+        let (expected_order_pda, order_bump) = find_program_address(
+            &[b"unstake_order", self.accounts.owner.key(), &nonce_bytes],
+            &crate::ID,
+        );
+        if expected_order_pda != *self.accounts.order_pda.key() {
+            return Err(PinocchioError::InvalidUnstakeOrderPda.into());
+        }
+
+        let expected_order_vault = find_program_address(
+            &[
+                self.accounts.order_pda.key(),
+                self.accounts.token_program.key(),
+                self.accounts.lst_mint.key(),
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .0;
+        if expected_order_vault != *self.accounts.order_vault.key() {
+            return Err(PinocchioError::InvalidAddress.into());
+        }
+
+        let order_bump_binding = [order_bump];
+        let order_seeds = &[
+            Seed::from(b"unstake_order"),
+            Seed::from(self.accounts.owner.key()),
+            Seed::from(&nonce_bytes),
+            Seed::from(&order_bump_binding),
+        ];
+
+        ProgramAccount::init::<UnstakeOrder>(
+            self.accounts.owner,
+            self.accounts.order_pda,
+            order_seeds,
+            UnstakeOrder::LEN,
+        )?;
+
+        AssociatedTokenAccount::init(
+            self.accounts.order_vault,
+            self.accounts.lst_mint,
+            self.accounts.owner,
+            self.accounts.order_pda,
+            self.accounts.system_program,
+            self.accounts.token_program,
+        )?;
+
+        TokenTransfer {
+            from: self.accounts.owner_ata,
+            to: self.accounts.order_vault,
+            authority: self.accounts.owner,
+            amount: self.data.lst_amount,
+        }
+        .invoke()?;
+
+        if self.data.tip_lamports > 0 {
+            Transfer {
+                from: self.accounts.owner,
+                to: self.accounts.order_pda,
+                lamports: self.data.tip_lamports,
+            }
+            .invoke()?;
+        }
+
+        let mut order_data = self.accounts.order_pda.try_borrow_mut_data()?;
+        UnstakeOrder::load_mut(&mut order_data)?.set_inner(
+            *self.accounts.owner.key(),
+            *self.accounts.order_vault.key(),
+            self.data.lst_amount,
+            self.data.min_lamports_per_lst,
+            self.data.tip_lamports,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,222 @@
+use pinocchio::{
+    account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+    pubkey::find_program_address,
+};
+use pinocchio_token::instructions::Transfer;
+
+use crate::{
+    errors::PinocchioError,
+    ids::{
+        assert_associated_token_program, assert_system_program, assert_token_program,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+    },
+    instructions::helpers::{
+        check_config_pda, AccountCheck, AssociatedTokenAccount, AssociatedTokenAccountInit,
+        ProgramAccount, ProgramAccountInit, SignerAccount, SystemAccount,
+    },
+    state::{Config, VestingEscrow},
+};
+
+pub struct CreateVestingAccounts<'a> {
+    pub funder: &'a AccountInfo,
+    pub funder_ata: &'a AccountInfo,
+    pub beneficiary: &'a AccountInfo,
+    pub vesting_pda: &'a AccountInfo,
+    pub vesting_vault: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub associated_token_program: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CreateVestingAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [funder, funder_ata, beneficiary, vesting_pda, vesting_vault, lst_mint, config_pda, token_program, associated_token_program, system_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(funder)?;
+
+        assert_system_program(system_program)?;
+        assert_token_program(token_program)?;
+        assert_associated_token_program(associated_token_program)?;
+
+        SystemAccount::check(vesting_pda)?;
+
+        if !vesting_pda.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        Ok(Self {
+            funder,
+            funder_ata,
+            beneficiary,
+            vesting_pda,
+            vesting_vault,
+            lst_mint,
+            config_pda,
+            token_program,
+            associated_token_program,
+            system_program,
+        })
+    }
+}
+
+pub struct CreateVestingInstructionData {
+    pub nonce: u64,
+    pub amount: u64,
+    pub start_ts: i64,
+    pub cliff_ts: i64,
+    pub end_ts: i64,
+}
+
+impl TryFrom<&[u8]> for CreateVestingInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 40 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let nonce = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let amount = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let start_ts = i64::from_le_bytes(data[16..24].try_into().unwrap());
+        let cliff_ts = i64::from_le_bytes(data[24..32].try_into().unwrap());
+        let end_ts = i64::from_le_bytes(data[32..40].try_into().unwrap());
+
+        if amount == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if start_ts > cliff_ts || cliff_ts > end_ts || start_ts >= end_ts {
+            return Err(PinocchioError::InvalidVestingSchedule.into());
+        }
+
+        Ok(Self {
+            nonce,
+            amount,
+            start_ts,
+            cliff_ts,
+            end_ts,
+        })
+    }
+}
+
+/// Locks the funder's LST in a vesting escrow PDA
+/// (`b"vesting"`, beneficiary, nonce) for the beneficiary. The LST is held in
+/// the escrow's LST ATA and released linearly from `start_ts` to `end_ts`
+/// through `ClaimVested`, with nothing claimable before `cliff_ts`. Used for
+/// operator and DAO allocations that everyone can audit on-chain.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Funder
+/// 1. `[WRITE]` Funder LST token account
+/// 2. `[]` Beneficiary
+/// 3. `[WRITE]` Vesting escrow PDA
+/// 4. `[WRITE]` Vesting vault (escrow PDA's LST ATA)
+/// 5. `[]` LST mint
+/// 6. `[]` Config PDA
+/// 7. `[]` Token program
+/// 8. `[]` Associated token program
+/// 9. `[]` System program
+pub struct CreateVesting<'a> {
+    pub accounts: CreateVestingAccounts<'a>,
+    pub data: CreateVestingInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CreateVesting<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: CreateVestingAccounts::try_from(accounts)?,
+            data: CreateVestingInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> CreateVesting<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &30;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        if Config::load(&config_data)?.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+        drop(config_data);
+
+        let nonce_bytes = self.data.nonce.to_le_bytes();
+        let (expected_vesting_pda, vesting_bump) = find_program_address(
+            &[b"vesting", self.accounts.beneficiary.key(), &nonce_bytes],
+            &crate::ID,
+        );
+        if expected_vesting_pda != *self.accounts.vesting_pda.key() {
+            return Err(PinocchioError::InvalidVestingPda.into());
+        }
+
+        let expected_vesting_vault = find_program_address(
+            &[
+                self.accounts.vesting_pda.key(),
+                self.accounts.token_program.key(),
+                self.accounts.lst_mint.key(),
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .0;
+        if expected_vesting_vault != *self.accounts.vesting_vault.key() {
+            return Err(PinocchioError::InvalidVestingVault.into());
+        }
+
+        let vesting_bump_binding = [vesting_bump];
+        let vesting_seeds = &[
+            Seed::from(b"vesting"),
+            Seed::from(self.accounts.beneficiary.key()),
+            Seed::from(&nonce_bytes),
+            Seed::from(&vesting_bump_binding),
+        ];
+
+        ProgramAccount::init::<VestingEscrow>(
+            self.accounts.funder,
+            self.accounts.vesting_pda,
+            vesting_seeds,
+            VestingEscrow::LEN,
+        )?;
+
+        AssociatedTokenAccount::init(
+            self.accounts.vesting_vault,
+            self.accounts.lst_mint,
+            self.accounts.funder,
+            self.accounts.vesting_pda,
+            self.accounts.system_program,
+            self.accounts.token_program,
+        )?;
+
+        Transfer {
+            from: self.accounts.funder_ata,
+            to: self.accounts.vesting_vault,
+            authority: self.accounts.funder,
+            amount: self.data.amount,
+        }
+        .invoke()?;
+
+        let mut vesting_data = self.accounts.vesting_pda.try_borrow_mut_data()?;
+        VestingEscrow::load_mut(&mut vesting_data)?.set_inner(
+            *self.accounts.beneficiary.key(),
+            *self.accounts.vesting_vault.key(),
+            self.data.amount,
+            self.data.start_ts,
+            self.data.cliff_ts,
+            self.data.end_ts,
+        );
+
+        Ok(())
+    }
+}
@@ -2,17 +2,38 @@ use pinocchio::{
     account_info::AccountInfo,
     instruction::{Seed, Signer},
     program_error::ProgramError,
-    pubkey::find_program_address,
+    pubkey::{find_program_address, Pubkey},
+    sysvars::{clock::Clock, instructions::Instructions, Sysvar},
 };
 use pinocchio_system::instructions::Transfer;
-use pinocchio_token::{instructions::MintTo, state::Mint};
+use pinocchio_token::{
+    instructions::{MintTo, Transfer as TokenTransfer},
+    state::{Mint, TokenAccount},
+};
 
 use crate::{
     errors::PinocchioError,
-    instructions::helpers::{LAMPORTS_PER_SOL, STAKE_PROGRAM_ID},
-    state::Config,
+    events::{emit, DEPOSIT_FLAG_MEMO, DEPOSIT_FLAG_REFERRER, EVENT_DEPOSIT},
+    ids::{
+        assert_rent_sysvar, assert_stake_program, assert_system_program, assert_token_program,
+        ASSOCIATED_TOKEN_PROGRAM_ID, ED25519_PROGRAM_ID,
+    },
+    instructions::helpers::{
+        check_config_pda, check_deposit_authority, check_pool_cap, check_user_stats,
+        ed25519_signed_message, epoch_rate, expect_lst_supply_change, managed_lamports,
+        pool_lamports, record_inflow, record_managed_change, record_principal, ProgramAccount,
+        ProgramAccountInit, SupplyChange, LAMPORTS_PER_SOL,
+    },
+    math::{deposit_bonus_lst, deposit_fee_lst, lamports_to_lst},
+    state::{
+        Config, DepositKey, ManagedChange, QueuedDeposit, ReferrerRecord, TrustedCallers,
+        UsedQuote, UserStats, DEPOSIT_CAP_QUEUE,
+    },
 };
 
+/// Number of optional accounts `Deposit` takes after the pool state PDA.
+const OPTIONAL_ACCOUNTS: usize = 10;
+
 pub struct DepositAccounts<'a> {
     pub config_pda: &'a AccountInfo,
     pub depositor: &'a AccountInfo,
@@ -24,33 +45,64 @@ pub struct DepositAccounts<'a> {
     pub token_program: &'a AccountInfo,
     pub system_program: &'a AccountInfo,
     pub rent_sysvar: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+    /// Only passed on the skip-checks path or with a quote.
+    pub instructions_sysvar: Option<&'a AccountInfo>,
+    /// Only passed on the skip-checks path.
+    pub trusted_callers_pda: Option<&'a AccountInfo>,
+    /// Only passed with an idempotency key.
+    pub deposit_key_pda: Option<&'a AccountInfo>,
+    /// Only needed for the early-supporter bonus, a referral fee not paid
+    /// into the referrer ATA or the deposit fee.
+    pub treasury_ata: Option<&'a AccountInfo>,
+    /// Only passed by referred deposits.
+    pub referrer_pda: Option<&'a AccountInfo>,
+    /// Only passed by referred deposits that pay the referrer directly.
+    pub referrer_ata: Option<&'a AccountInfo>,
+    /// Only needed in a permissioned pool.
+    pub deposit_authority: Option<&'a AccountInfo>,
+    /// Only needed when the deposit cap queues the excess.
+    pub queued_deposit_pda: Option<&'a AccountInfo>,
+    /// Only passed with a quote.
+    pub used_quote_pda: Option<&'a AccountInfo>,
+    /// Only passed by depositors recording their first deposit for the
+    /// withdrawal fee's holding rebate.
+    pub user_stats_pda: Option<&'a AccountInfo>,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for DepositAccounts<'a> {
     type Error = pinocchio::program_error::ProgramError;
 
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [config_pda, depositor, depositor_ata, lst_mint, stake_account_main, stake_account_reserve, stake_program, token_program, system_program, rent_sysvar] =
+        let [config_pda, depositor, depositor_ata, lst_mint, stake_account_main, stake_account_reserve, stake_program, token_program, system_program, rent_sysvar, pool_state_pda, optional_accounts @ ..] =
             accounts
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
-        if !depositor.is_signer() {
-            return Err(PinocchioError::NotSigner.into());
+        // Each optional account has a fixed position. The program ID stands
+        // in for one left out before a passed one, and the ones after the
+        // last passed may be dropped.
+        if optional_accounts.len() > OPTIONAL_ACCOUNTS {
+            return Err(ProgramError::NotEnoughAccountKeys);
         }
-
-        if system_program.key() != &pinocchio_system::ID {
-            return Err(PinocchioError::InvalidSystemProgram.into());
+        let mut optional = [None; OPTIONAL_ACCOUNTS];
+        for (slot, account) in optional.iter_mut().zip(optional_accounts) {
+            if *account.key() != crate::ID {
+                *slot = Some(account);
+            }
         }
+        let [instructions_sysvar, trusted_callers_pda, deposit_key_pda, treasury_ata, referrer_pda, referrer_ata, deposit_authority, queued_deposit_pda, used_quote_pda, user_stats_pda] =
+            optional;
 
-        if token_program.key() != &pinocchio_token::ID {
-            return Err(PinocchioError::InvalidTokenProgram.into());
+        if !depositor.is_signer() {
+            return Err(PinocchioError::NotSigner.into());
         }
 
-        if stake_program.key() != &STAKE_PROGRAM_ID {
-            return Err(PinocchioError::InvalidStakeProgram.into());
-        }
+        assert_system_program(system_program)?;
+        assert_token_program(token_program)?;
+        assert_stake_program(stake_program)?;
+        assert_rent_sysvar(rent_sysvar)?;
 
         Ok(Self {
             config_pda,
@@ -63,21 +115,71 @@ impl<'a> TryFrom<&'a [AccountInfo]> for DepositAccounts<'a> {
             token_program,
             system_program,
             rent_sysvar,
+            pool_state_pda,
+            instructions_sysvar,
+            trusted_callers_pda,
+            deposit_key_pda,
+            treasury_ata,
+            referrer_pda,
+            referrer_ata,
+            deposit_authority,
+            queued_deposit_pda,
+            used_quote_pda,
+            user_stats_pda,
         })
     }
 }
 
 pub struct DepositData {
     pub amount_in_lamports: u64,
+    /// Skip PDA/ATA re-derivations. Only honored for trusted CPI callers.
+    pub skip_checks: bool,
+    /// Config PDA bump, supplied by the caller on the skip-checks path.
+    pub config_bump: u8,
+    /// Client-supplied key that makes retries of this deposit fail.
+    pub idempotency_key: Option<[u8; 32]>,
+    /// Client-supplied hash of an off-chain memo, e.g. an exchange's internal
+    /// deposit id, logged in the deposit event and kept in the deposit key.
+    pub memo_hash: Option<[u8; 32]>,
+    /// Price the deposit at the signed quote preceding the instruction.
+    pub use_quote: bool,
+    /// Least LST the depositor accepts, zero for no bound.
+    pub min_lst_out: u64,
 }
 
 impl TryFrom<&[u8]> for DepositData {
     type Error = ProgramError;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
-        if data.len() != 8 {
-            return Err(ProgramError::InvalidInstructionData);
-        }
+        // The quote flag is a trailing byte after any other extension, so it
+        // is the only one that makes the length odd.
+        let (data, use_quote) = match data.split_last() {
+            Some((1, rest)) if data.len() % 2 == 1 => (rest, true),
+            _ => (data, false),
+        };
+
+        let (skip_checks, config_bump, key_offset) = match data.len() {
+            8 | 40 | 72 | 80 => (false, 0, 8),
+            10 | 42 | 74 | 82 if data[8] == 1 => (true, data[9], 10),
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        // The minimum LST out follows the memo slot. With a minimum, an
+        // all-zero memo slot means the deposit has no memo.
+        let min_lst_out = data
+            .get(key_offset + 64..key_offset + 72)
+            .map(|min_lst_out| u64::from_le_bytes(min_lst_out.try_into().unwrap()));
+
+        // The memo hash follows the key slot. With a memo, an all-zero key
+        // slot means the deposit has no idempotency key.
+        let memo_hash: Option<[u8; 32]> = data
+            .get(key_offset + 32..key_offset + 64)
+            .map(|memo_hash| <[u8; 32]>::try_from(memo_hash).unwrap())
+            .filter(|memo_hash| min_lst_out.is_none() || *memo_hash != [0u8; 32]);
+        let idempotency_key = data
+            .get(key_offset..key_offset + 32)
+            .map(|key| <[u8; 32]>::try_from(key).unwrap())
+            .filter(|key| memo_hash.is_none() || *key != [0u8; 32]);
 
         let amount_in_lamports = u64::from_le_bytes(data[0..8].try_into().unwrap());
 
@@ -85,24 +187,166 @@ impl TryFrom<&[u8]> for DepositData {
             return Err(PinocchioError::DepositBelowMinimum.into());
         }
 
-        Ok(Self { amount_in_lamports })
+        Ok(Self {
+            amount_in_lamports,
+            skip_checks,
+            config_bump,
+            idempotency_key,
+            memo_hash,
+            use_quote,
+            min_lst_out: min_lst_out.unwrap_or(0),
+        })
+    }
+}
+
+/// Rate quote signed off-chain by the config's quote signer for one
+/// depositor, carried as the message of an ed25519 program instruction.
+pub struct DepositQuote {
+    pub program_id: [u8; 32],
+    pub depositor: [u8; 32],
+    /// LST minted per `LAMPORTS_PER_SOL` lamports deposited.
+    pub lst_per_sol: u64,
+    /// Last slot the quote may be used in.
+    pub expires_at_slot: u64,
+    /// Most lamports the quote prices.
+    pub max_lamports: u64,
+    /// Signer-chosen number that makes the quote single-use, consumed in the
+    /// used quote PDA (`b"used_quote"`, depositor, nonce).
+    pub nonce: u64,
+}
+
+impl DepositQuote {
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 8 + 8;
+}
+
+impl TryFrom<&[u8]> for DepositQuote {
+    type Error = ProgramError;
+
+    fn try_from(message: &[u8]) -> Result<Self, Self::Error> {
+        if message.len() != DepositQuote::LEN {
+            return Err(PinocchioError::InvalidQuote.into());
+        }
+
+        Ok(Self {
+            program_id: message[0..32].try_into().unwrap(),
+            depositor: message[32..64].try_into().unwrap(),
+            lst_per_sol: u64::from_le_bytes(message[64..72].try_into().unwrap()),
+            expires_at_slot: u64::from_le_bytes(message[72..80].try_into().unwrap()),
+            max_lamports: u64::from_le_bytes(message[80..88].try_into().unwrap()),
+            nonce: u64::from_le_bytes(message[88..96].try_into().unwrap()),
+        })
     }
 }
 
 /// Deposits SOL to reserve and mints LST tokens.
 ///
+/// Programs on the admin's trusted callers list may CPI with the trailing
+/// `skip_checks` flag and config bump to avoid the config PDA and ATA
+/// re-derivations. The caller is verified through instruction introspection;
+/// the config is then checked by owner and size and the depositor's token
+/// account by owner and mint.
+///
+/// An optional trailing 32-byte idempotency key is recorded in a deposit key
+/// PDA (`b"deposit_key"`, depositor, key), and a second deposit with the same
+/// key fails with `DuplicateDeposit`. Integrators retrying after an ambiguous
+/// RPC response reuse the key. `CloseDepositKey` reclaims the rent once
+/// `DEPOSIT_KEY_TTL_SLOTS` have passed.
+///
+/// A 32-byte memo hash may follow the key slot. It is appended to the deposit
+/// event and stored in the deposit key PDA, so exchanges can match deposits to
+/// their ledgers from on-chain data. An all-zero key slot before a memo means
+/// no idempotency key.
+///
+/// A `u64` minimum may follow the memo slot. The deposit fails with
+/// `MinLstOutNotMet` when the LST minted to the depositor, after any fees and
+/// before any bonus, is below it, so a rate that moved between signing and
+/// execution cannot cost the depositor more than they allowed. An all-zero
+/// memo slot before a minimum means no memo.
+///
+/// The config and the main stake account are only read for pricing, so they
+/// are passed read-only and do not write-lock against other transactions.
+///
+/// When the admin sets a per-epoch deposit cap, lamports beyond what is left
+/// of it this epoch fail with `DepositCapExceeded` under `DEPOSIT_CAP_REJECT`.
+/// Under `DEPOSIT_CAP_QUEUE` the part that fits is deposited and the rest is
+/// parked in the depositor's queued deposit PDA (`b"queued_deposit"`,
+/// depositor), which `ClaimQueuedDeposit` moves into the reserve from the
+/// next epoch. Queuing more moves the whole entry to the current epoch.
+///
+/// When the admin sets a pool cap, a deposit that would push the pool's
+/// lamports past `max_pool_lamports` fails with `PoolCapExceeded`. Only the
+/// part deposited now counts; queued lamports are checked when claimed.
+///
+/// While the admin sets a `deposit_authority`, the pool is permissioned: the
+/// authority must co-sign, or the deposit fails with
+/// `MissingDepositAuthority`.
+///
+/// Before the admin's bonus cutoff epoch, a deposit that passes the treasury
+/// ATA also receives `bonus_bps` of the LST it mints as a bonus transferred
+/// from the treasury, as far as the treasury's balance allows.
+///
+/// A deposit that passes a registered referrer PDA (`b"referrer"`, referrer)
+/// adds to the referrer's stats, and `referral_fee_bps` of the LST it mints
+/// goes to the referrer instead of to the depositor: minted straight into
+/// the referrer's LST ATA when it is passed, otherwise into the treasury ATA
+/// for the referrer to claim with `ClaimReferralFees`. The referrer is logged
+/// in the deposit event, so integrators can attribute deposits on-chain.
+///
+/// The admin's `deposit_fee_bps` of the LST left after any referral fee is
+/// minted into the treasury ATA as well. The treasury ATA must be passed
+/// whenever a fee it receives is not zero.
+///
+/// With a trailing quote flag (`1`) the deposit is priced at a rate quote
+/// signed by the admin's quote signer instead of the pool's rate, so onramps
+/// can promise a fixed price. The quote is the message of an ed25519 program
+/// instruction placed right before the transaction's current top-level
+/// instruction: a `DepositQuote` naming this program, the depositor, the
+/// quoted LST per SOL, the last slot it is valid in, the most lamports it
+/// prices and a nonce. A quoted rate further than `quote_max_deviation_bps`
+/// from the pool's fails with `QuoteOutOfBounds`, and a deposit above
+/// `max_lamports` with `QuoteAmountExceeded`. The deposit creates the used
+/// quote PDA (`b"used_quote"`, depositor, nonce), with the depositor paying
+/// rent, so a second deposit with the same quote fails with
+/// `QuoteAlreadyUsed`. `CloseUsedQuote` reclaims the rent once the quote
+/// expired.
+///
+/// A deposit that passes the depositor's user stats PDA (`b"user_stats"`,
+/// depositor) creates it on the first such deposit, with the depositor
+/// paying rent, and records the epoch. Later deposits leave it unchanged.
+/// `CrankSplit` and `CrankSplitTranche` rebate the withdrawal fee by the
+/// epochs held since.
+///
 /// Accounts expected:
 ///
-/// 0. `[WRITE]` Config PDA
+/// 0. `[]` Config PDA
 /// 1. `[WRITE, SIGNER]` Depositor
 /// 2. `[WRITE]` Depositor ATA
 /// 3. `[WRITE]` LST mint
-/// 4. `[WRITE]` Stake account main
+/// 4. `[]` Stake account main
 /// 5. `[WRITE]` Stake account reserve
 /// 6. `[]` Stake program
 /// 7. `[]` Token program
 /// 8. `[]` System program
 /// 9. `[]` Rent sysvar
+/// 10. `[WRITE]` Pool state PDA
+/// 11. `[]` Instructions sysvar (skip-checks path or quoted deposits only)
+/// 12. `[]` Trusted callers PDA (skip-checks path only)
+/// 13. `[WRITE]` Deposit key PDA (with an idempotency key only)
+/// 14. `[WRITE]` Treasury ATA (only for the early-supporter bonus, a
+///     referral fee not paid into the referrer ATA or the deposit fee)
+/// 15. `[WRITE]` Referrer PDA (only for a referred deposit)
+/// 16. `[WRITE]` Referrer ATA (only for a referred deposit paying the
+///     referrer directly)
+/// 17. `[SIGNER]` Deposit authority (only in a permissioned pool, unless the
+///     authority is the depositor)
+/// 18. `[WRITE]` Queued deposit PDA (only needed when the excess over the
+///     deposit cap is queued)
+/// 19. `[WRITE]` Used quote PDA (quoted deposits only)
+/// 20. `[WRITE]` User stats PDA (only to record the first deposit)
+///
+/// Accounts 11 to 20 keep their positions: pass the program ID in place of
+/// one left out before an account that is passed. Left-out accounts after
+/// the last one passed may be dropped.
 pub struct Deposit<'a> {
     pub accounts: DepositAccounts<'a>,
     pub data: DepositData,
@@ -123,16 +367,37 @@ impl<'a> Deposit<'a> {
     pub const DISCRIMINATOR: &'static u8 = &3;
 
     pub fn process(&self) -> Result<(), ProgramError> {
-        let (expected_config_pda, bump) = find_program_address(&[b"config"], &crate::ID);
-        if expected_config_pda != *self.accounts.config_pda.key() {
-            return Err(PinocchioError::InvalidConfigPda.into());
-        }
+        let bump = if self.data.skip_checks {
+            self.check_trusted_caller()?;
+
+            // Config is the only account of its size this program creates.
+            if !self.accounts.config_pda.is_owned_by(&crate::ID) {
+                return Err(PinocchioError::InvalidConfigPda.into());
+            }
+            self.data.config_bump
+        } else {
+            check_config_pda(self.accounts.config_pda)?
+        };
 
         let bump_binding = [bump];
         let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
         let data = self.accounts.config_pda.try_borrow_data()?;
         let config = Config::load(&data)?;
 
+        if !(*self.accounts.stake_account_main.key() == config.stake_account_main) {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        config.status()?.check_deposits()?;
+
+        // A deposit authority depositing for itself need not be passed twice.
+        check_deposit_authority(
+            &config.deposit_authority,
+            self.accounts
+                .deposit_authority
+                .unwrap_or(self.accounts.depositor),
+        )?;
+
         if !(*self.accounts.stake_account_reserve.key() == config.stake_account_reserve) {
             return Err(PinocchioError::InvalidStakeAccountReserve.into());
         }
@@ -141,55 +406,556 @@ impl<'a> Deposit<'a> {
             return Err(PinocchioError::InvalidLstMint.into());
         }
 
-        let expected_ata = find_program_address(
+        if self.data.skip_checks {
+            let depositor_ata = TokenAccount::from_account_info(self.accounts.depositor_ata)?;
+            if depositor_ata.owner() != self.accounts.depositor.key()
+                || depositor_ata.mint() != self.accounts.lst_mint.key()
+            {
+                return Err(PinocchioError::InvalidDepositorAta.into());
+            }
+        } else {
+            let expected_ata = find_program_address(
+                &[
+                    self.accounts.depositor.key(),
+                    self.accounts.token_program.key(),
+                    self.accounts.lst_mint.key(),
+                ],
+                &ASSOCIATED_TOKEN_PROGRAM_ID,
+            )
+            .0;
+            if expected_ata != *self.accounts.depositor_ata.key() {
+                return Err(PinocchioError::InvalidDepositorAta.into());
+            }
+        }
+
+        let deposited = record_inflow(
+            self.accounts.pool_state_pda,
+            config.deposit_cap_lamports,
+            self.data.amount_in_lamports,
+            config.deposit_cap_mode == DEPOSIT_CAP_QUEUE,
+        )?;
+
+        self.record_idempotency_key()?;
+
+        let queued = self.data.amount_in_lamports - deposited;
+        if queued > 0 {
+            self.queue_deposit(queued)?;
+        }
+
+        if deposited == 0 {
+            return Ok(());
+        }
+
+        let mint = Mint::from_account_info(self.accounts.lst_mint)?;
+
+        let live_pool_lamports = pool_lamports(
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            config.validator_stake_lamports,
+        )?;
+        check_pool_cap(config.max_pool_lamports, live_pool_lamports, deposited)?;
+        let managed = managed_lamports(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            config.validator_stake_lamports,
+        )?;
+
+        let (total_sol_in_pool, total_lst_supply) =
+            epoch_rate(config)?.unwrap_or((managed, mint.supply()));
+
+        let lst_minted = lamports_to_lst(deposited, total_lst_supply, total_sol_in_pool)?;
+        let lst_minted = if self.data.use_quote {
+            let pool_lst_per_sol =
+                lamports_to_lst(LAMPORTS_PER_SOL, total_lst_supply, total_sol_in_pool)?;
+            self.quoted_lst(config, deposited, pool_lst_per_sol)?
+        } else {
+            lst_minted
+        };
+
+        drop(mint);
+
+        let (referral_fee, referrer, referrer_ata) =
+            self.record_referral(config, deposited, lst_minted)?;
+        let deposit_fee = deposit_fee_lst(lst_minted - referral_fee, config.deposit_fee_bps);
+        let treasury_lst = match referrer_ata {
+            Some(_) => deposit_fee,
+            None => referral_fee + deposit_fee,
+        };
+        let lst_to_mint = lst_minted - referral_fee - deposit_fee;
+        if lst_to_mint < self.data.min_lst_out {
+            return Err(PinocchioError::MinLstOutNotMet.into());
+        }
+
+        Transfer {
+            from: self.accounts.depositor,
+            to: self.accounts.stake_account_reserve,
+            lamports: deposited,
+        }
+        .invoke()?;
+        record_managed_change(
+            self.accounts.pool_state_pda,
+            ManagedChange::Deposited(deposited),
+        )?;
+        self.record_first_deposit()?;
+
+        expect_lst_supply_change(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            SupplyChange::Minted(lst_to_mint),
+            || {
+                MintTo {
+                    mint: self.accounts.lst_mint,
+                    account: self.accounts.depositor_ata,
+                    mint_authority: self.accounts.config_pda,
+                    amount: lst_to_mint,
+                }
+                .invoke_signed(&[Signer::from(config_seeds)])
+            },
+        )?;
+
+        if let Some(referrer_ata) = referrer_ata.filter(|_| referral_fee > 0) {
+            expect_lst_supply_change(
+                self.accounts.pool_state_pda,
+                self.accounts.lst_mint,
+                SupplyChange::Minted(referral_fee),
+                || {
+                    MintTo {
+                        mint: self.accounts.lst_mint,
+                        account: referrer_ata,
+                        mint_authority: self.accounts.config_pda,
+                        amount: referral_fee,
+                    }
+                    .invoke_signed(&[Signer::from(config_seeds)])
+                },
+            )?;
+        }
+
+        if treasury_lst > 0 {
+            expect_lst_supply_change(
+                self.accounts.pool_state_pda,
+                self.accounts.lst_mint,
+                SupplyChange::Minted(treasury_lst),
+                || {
+                    MintTo {
+                        mint: self.accounts.lst_mint,
+                        account: self
+                            .treasury_ata(config)?
+                            .ok_or(PinocchioError::InvalidTreasuryAta)?,
+                        mint_authority: self.accounts.config_pda,
+                        amount: treasury_lst,
+                    }
+                    .invoke_signed(&[Signer::from(config_seeds)])
+                },
+            )?;
+        }
+
+        record_principal(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            deposited,
+            lst_minted,
+        )?;
+
+        self.pay_bonus(config, lst_to_mint, &[Signer::from(config_seeds)])?;
+
+        let mut flags = 0;
+        if self.data.memo_hash.is_some() {
+            flags |= DEPOSIT_FLAG_MEMO;
+        }
+        if referrer.is_some() {
+            flags |= DEPOSIT_FLAG_REFERRER;
+        }
+        emit(
+            self.accounts.pool_state_pda,
+            EVENT_DEPOSIT,
             &[
                 self.accounts.depositor.key(),
+                &deposited.to_le_bytes(),
+                &lst_to_mint.to_le_bytes(),
+                &[flags],
+                &self.data.memo_hash.unwrap_or_default(),
+                &referrer.unwrap_or_default(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Transfers the early-supporter bonus on `lst_minted` from the treasury
+    /// to the depositor, if the bonus window is open and the treasury ATA was
+    /// passed.
+    fn pay_bonus(
+        &self,
+        config: &Config,
+        lst_minted: u64,
+        config_signer: &[Signer],
+    ) -> Result<(), ProgramError> {
+        let Some(treasury_ata) = self.treasury_ata(config)? else {
+            return Ok(());
+        };
+
+        let treasury_balance = TokenAccount::from_account_info(treasury_ata)?.amount();
+        let bonus = deposit_bonus_lst(
+            lst_minted,
+            config.bonus_bps,
+            Clock::get()?.epoch,
+            config.bonus_cutoff_epoch,
+            treasury_balance,
+        );
+        if bonus == 0 {
+            return Ok(());
+        }
+
+        TokenTransfer {
+            from: treasury_ata,
+            to: self.accounts.depositor_ata,
+            authority: self.accounts.config_pda,
+            amount: bonus,
+        }
+        .invoke_signed(config_signer)
+    }
+
+    /// LST minted for `lamports` at the signed quote preceding the current
+    /// top-level instruction, checked against the config's quote signer and
+    /// bound around the pool's `pool_lst_per_sol`.
+    fn quoted_lst(
+        &self,
+        config: &Config,
+        lamports: u64,
+        pool_lst_per_sol: u64,
+    ) -> Result<u64, ProgramError> {
+        let Some(instructions_sysvar) = self.accounts.instructions_sysvar else {
+            return Err(PinocchioError::InvalidQuote.into());
+        };
+        if config.quote_signer == [0u8; 32] {
+            return Err(PinocchioError::InvalidQuote.into());
+        }
+
+        let instructions = Instructions::try_from(instructions_sysvar)?;
+        let quote_index = (instructions.load_current_index() as usize)
+            .checked_sub(1)
+            .ok_or(PinocchioError::InvalidQuote)?;
+        let quote_instruction = instructions.load_instruction_at(quote_index)?;
+        if quote_instruction.get_program_id() != &ED25519_PROGRAM_ID {
+            return Err(PinocchioError::InvalidQuote.into());
+        }
+
+        let message = ed25519_signed_message(
+            quote_instruction.get_instruction_data(),
+            &config.quote_signer,
+        )
+        .map_err(|_| PinocchioError::InvalidQuote)?;
+        let quote = DepositQuote::try_from(message)?;
+        if quote.program_id != crate::ID || quote.depositor != *self.accounts.depositor.key() {
+            return Err(PinocchioError::InvalidQuote.into());
+        }
+
+        if Clock::get()?.slot > quote.expires_at_slot {
+            return Err(PinocchioError::QuoteExpired.into());
+        }
+        if lamports > quote.max_lamports {
+            return Err(PinocchioError::QuoteAmountExceeded.into());
+        }
+
+        let deviation = quote.lst_per_sol.abs_diff(pool_lst_per_sol) as u128 * 10_000;
+        if deviation > pool_lst_per_sol as u128 * config.quote_max_deviation_bps as u128 {
+            return Err(PinocchioError::QuoteOutOfBounds.into());
+        }
+
+        self.use_quote(&quote)?;
+
+        u64::try_from(lamports as u128 * quote.lst_per_sol as u128 / LAMPORTS_PER_SOL as u128)
+            .map_err(|_| ProgramError::ArithmeticOverflow)
+    }
+
+    /// Creates the used quote PDA for the quote's nonce. Fails if it already
+    /// exists, i.e. a deposit already used the quote.
+    fn use_quote(&self, quote: &DepositQuote) -> Result<(), ProgramError> {
+        let Some(used_quote_pda) = self.accounts.used_quote_pda else {
+            return Err(PinocchioError::InvalidUsedQuotePda.into());
+        };
+
+        let nonce = quote.nonce.to_le_bytes();
+        let (expected_used_quote_pda, used_quote_bump) = find_program_address(
+            &[b"used_quote", self.accounts.depositor.key(), &nonce],
+            &crate::ID,
+        );
+        if expected_used_quote_pda != *used_quote_pda.key() {
+            return Err(PinocchioError::InvalidUsedQuotePda.into());
+        }
+
+        if !used_quote_pda.data_is_empty() {
+            return Err(PinocchioError::QuoteAlreadyUsed.into());
+        }
+
+        let used_quote_bump_binding = [used_quote_bump];
+        let used_quote_seeds = &[
+            Seed::from(b"used_quote"),
+            Seed::from(self.accounts.depositor.key()),
+            Seed::from(&nonce),
+            Seed::from(&used_quote_bump_binding),
+        ];
+        ProgramAccount::init::<UsedQuote>(
+            self.accounts.depositor,
+            used_quote_pda,
+            used_quote_seeds,
+            UsedQuote::LEN,
+        )?;
+
+        let mut used_quote_data = used_quote_pda.try_borrow_mut_data()?;
+        let used_quote = UsedQuote::load_mut(&mut used_quote_data)?;
+        used_quote.depositor = *self.accounts.depositor.key();
+        used_quote.expires_at_slot = quote.expires_at_slot;
+
+        Ok(())
+    }
+
+    /// Creates the depositor's user stats PDA with the current epoch as their
+    /// first deposit, if it was passed and does not exist yet.
+    fn record_first_deposit(&self) -> Result<(), ProgramError> {
+        let Some(user_stats_pda) = self.accounts.user_stats_pda else {
+            return Ok(());
+        };
+
+        let user_stats_bump = check_user_stats(user_stats_pda, self.accounts.depositor)?;
+        if !user_stats_pda.data_is_empty() {
+            return Ok(());
+        }
+
+        let user_stats_bump_binding = [user_stats_bump];
+        let user_stats_seeds = &[
+            Seed::from(b"user_stats"),
+            Seed::from(self.accounts.depositor.key()),
+            Seed::from(&user_stats_bump_binding),
+        ];
+        ProgramAccount::init::<UserStats>(
+            self.accounts.depositor,
+            user_stats_pda,
+            user_stats_seeds,
+            UserStats::LEN,
+        )?;
+
+        let mut user_stats_data = user_stats_pda.try_borrow_mut_data()?;
+        let user_stats = UserStats::load_mut(&mut user_stats_data)?;
+        user_stats.user = *self.accounts.depositor.key();
+        user_stats.first_deposit_epoch = Clock::get()?.epoch;
+
+        Ok(())
+    }
+
+    /// The treasury ATA, if it was passed, checked against the config PDA.
+    fn treasury_ata(&self, config: &Config) -> Result<Option<&'a AccountInfo>, ProgramError> {
+        let Some(treasury_ata) = self.accounts.treasury_ata else {
+            return Ok(None);
+        };
+
+        let expected_treasury_ata = find_program_address(
+            &[
+                self.accounts.config_pda.key(),
                 self.accounts.token_program.key(),
-                self.accounts.lst_mint.key(),
+                &config.lst_mint,
             ],
-            &pinocchio_associated_token_account::ID,
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
         )
         .0;
-        if expected_ata != *self.accounts.depositor_ata.key() {
-            return Err(PinocchioError::InvalidDepositorAta.into());
+        if expected_treasury_ata != *treasury_ata.key() {
+            return Err(PinocchioError::InvalidTreasuryAta.into());
         }
 
-        let mint = Mint::from_account_info(self.accounts.lst_mint)?;
-        let total_lst_supply = mint.supply();
+        Ok(Some(treasury_ata))
+    }
+
+    /// Adds a referred deposit of `lamports`, minting `lst_minted`, to the
+    /// referrer's stats and returns the referral fee taken from the LST, the
+    /// referrer, and the referrer ATA the fee is paid into, if it was passed.
+    /// A fee paid into the ATA counts as claimed. Zero and `None` when no
+    /// referrer PDA was passed.
+    fn record_referral(
+        &self,
+        config: &Config,
+        lamports: u64,
+        lst_minted: u64,
+    ) -> Result<(u64, Option<Pubkey>, Option<&'a AccountInfo>), ProgramError> {
+        let Some(referrer_pda) = self.accounts.referrer_pda else {
+            if self.accounts.referrer_ata.is_some() {
+                return Err(PinocchioError::InvalidReferrerPda.into());
+            }
+            return Ok((0, None, None));
+        };
+
+        let mut referrer_data = referrer_pda.try_borrow_mut_data()?;
+        let referrer = ReferrerRecord::load_mut(&mut referrer_data)?;
+
+        let (expected_referrer_pda, _) =
+            find_program_address(&[b"referrer", &referrer.referrer], &crate::ID);
+        if expected_referrer_pda != *referrer_pda.key() {
+            return Err(PinocchioError::InvalidReferrerPda.into());
+        }
+
+        let fee = (lst_minted as u128 * config.referral_fee_bps as u128 / 10_000) as u64;
 
-        let total_sol_in_pool = self
-            .accounts
-            .stake_account_main
-            .lamports()
-            .checked_add(self.accounts.stake_account_reserve.lamports())
+        referrer.referred_lamports = referrer
+            .referred_lamports
+            .checked_add(lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        referrer.referred_deposits += 1;
+        referrer.fees_earned = referrer
+            .fees_earned
+            .checked_add(fee)
             .ok_or(ProgramError::ArithmeticOverflow)?;
 
-        let lst_to_mint = if total_lst_supply == 0 || total_sol_in_pool == 0 {
-            self.data.amount_in_lamports
-        } else {
-            (self.data.amount_in_lamports as u128)
-                .checked_mul(total_lst_supply as u128)
-                .ok_or(ProgramError::ArithmeticOverflow)?
-                .checked_div(total_sol_in_pool as u128)
-                .ok_or(ProgramError::ArithmeticOverflow)? as u64
+        if let Some(referrer_ata) = self.accounts.referrer_ata {
+            let expected_referrer_ata = find_program_address(
+                &[
+                    &referrer.referrer,
+                    self.accounts.token_program.key(),
+                    &config.lst_mint,
+                ],
+                &ASSOCIATED_TOKEN_PROGRAM_ID,
+            )
+            .0;
+            if expected_referrer_ata != *referrer_ata.key() {
+                return Err(PinocchioError::InvalidReferrerAta.into());
+            }
+
+            referrer.fees_claimed += fee;
+        }
+
+        Ok((fee, Some(referrer.referrer), self.accounts.referrer_ata))
+    }
+
+    /// Moves `lamports` from the depositor into their queued deposit PDA,
+    /// creating it if needed.
+    fn queue_deposit(&self, lamports: u64) -> Result<(), ProgramError> {
+        let Some(queued_deposit_pda) = self.accounts.queued_deposit_pda else {
+            return Err(PinocchioError::DepositCapExceeded.into());
         };
 
-        drop(mint);
+        let (expected_queued_deposit_pda, queued_deposit_bump) = find_program_address(
+            &[b"queued_deposit", self.accounts.depositor.key()],
+            &crate::ID,
+        );
+        if expected_queued_deposit_pda != *queued_deposit_pda.key() {
+            return Err(PinocchioError::InvalidQueuedDepositPda.into());
+        }
+
+        if queued_deposit_pda.data_is_empty() {
+            let queued_deposit_bump_binding = [queued_deposit_bump];
+            let queued_deposit_seeds = &[
+                Seed::from(b"queued_deposit"),
+                Seed::from(self.accounts.depositor.key()),
+                Seed::from(&queued_deposit_bump_binding),
+            ];
+            ProgramAccount::init::<QueuedDeposit>(
+                self.accounts.depositor,
+                queued_deposit_pda,
+                queued_deposit_seeds,
+                QueuedDeposit::LEN,
+            )?;
+        } else if !queued_deposit_pda.is_owned_by(&crate::ID) {
+            return Err(PinocchioError::InvalidQueuedDepositPda.into());
+        }
 
         Transfer {
             from: self.accounts.depositor,
-            to: self.accounts.stake_account_reserve,
-            lamports: self.data.amount_in_lamports,
+            to: queued_deposit_pda,
+            lamports,
         }
         .invoke()?;
 
-        MintTo {
-            mint: self.accounts.lst_mint,
-            account: self.accounts.depositor_ata,
-            mint_authority: self.accounts.config_pda,
-            amount: lst_to_mint,
+        let mut queued_deposit_data = queued_deposit_pda.try_borrow_mut_data()?;
+        let queued_deposit = QueuedDeposit::load_mut(&mut queued_deposit_data)?;
+        queued_deposit.depositor = *self.accounts.depositor.key();
+        queued_deposit.lamports = queued_deposit
+            .lamports
+            .checked_add(lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        queued_deposit.epoch = Clock::get()?.epoch;
+
+        Ok(())
+    }
+
+    /// Creates the deposit key PDA for the idempotency key, if any. Fails
+    /// if it already exists, i.e. the deposit was already made.
+    fn record_idempotency_key(&self) -> Result<(), ProgramError> {
+        let (idempotency_key, deposit_key_pda) =
+            match (&self.data.idempotency_key, self.accounts.deposit_key_pda) {
+                (None, None) => return Ok(()),
+                (Some(idempotency_key), Some(deposit_key_pda)) => {
+                    (idempotency_key, deposit_key_pda)
+                }
+                _ => return Err(PinocchioError::InvalidDepositKeyPda.into()),
+            };
+
+        let (expected_deposit_key_pda, deposit_key_bump) = find_program_address(
+            &[
+                b"deposit_key",
+                self.accounts.depositor.key(),
+                idempotency_key,
+            ],
+            &crate::ID,
+        );
+        if expected_deposit_key_pda != *deposit_key_pda.key() {
+            return Err(PinocchioError::InvalidDepositKeyPda.into());
+        }
+
+        if !deposit_key_pda.data_is_empty() {
+            return Err(PinocchioError::DuplicateDeposit.into());
         }
-        .invoke_signed(&[Signer::from(config_seeds)])?;
+
+        let deposit_key_bump_binding = [deposit_key_bump];
+        let deposit_key_seeds = &[
+            Seed::from(b"deposit_key"),
+            Seed::from(self.accounts.depositor.key()),
+            Seed::from(idempotency_key),
+            Seed::from(&deposit_key_bump_binding),
+        ];
+        ProgramAccount::init::<DepositKey>(
+            self.accounts.depositor,
+            deposit_key_pda,
+            deposit_key_seeds,
+            DepositKey::LEN,
+        )?;
+
+        let mut deposit_key_data = deposit_key_pda.try_borrow_mut_data()?;
+        DepositKey::load_mut(&mut deposit_key_data)?.set_inner(
+            *self.accounts.depositor.key(),
+            Clock::get()?.slot,
+            self.data.memo_hash.unwrap_or_default(),
+        );
+
+        Ok(())
+    }
+
+    /// Verifies the top-level instruction belongs to a program on the
+    /// trusted callers list, i.e. that this deposit is a CPI from it.
+    fn check_trusted_caller(&self) -> Result<(), ProgramError> {
+        let (Some(instructions_sysvar), Some(trusted_callers_pda)) = (
+            self.accounts.instructions_sysvar,
+            self.accounts.trusted_callers_pda,
+        ) else {
+            return Err(PinocchioError::UntrustedCaller.into());
+        };
+
+        if !trusted_callers_pda.is_owned_by(&crate::ID) {
+            return Err(PinocchioError::InvalidTrustedCallersPda.into());
+        }
+
+        let instructions = Instructions::try_from(instructions_sysvar)?;
+        let top_level =
+            instructions.load_instruction_at(instructions.load_current_index() as usize)?;
+        let caller = top_level.get_program_id();
+
+        // Called directly rather than through CPI.
+        if caller == &crate::ID {
+            return Err(PinocchioError::UntrustedCaller.into());
+        }
+
+        let trusted_callers_data = trusted_callers_pda.try_borrow_data()?;
+        if !TrustedCallers::load(&trusted_callers_data)?.contains(caller) {
+            return Err(PinocchioError::UntrustedCaller.into());
+        }
+
         Ok(())
     }
 }
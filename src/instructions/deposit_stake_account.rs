@@ -0,0 +1,293 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+use pinocchio_token::{instructions::MintTo, state::Mint};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit, EVENT_DEPOSIT},
+    ids::{
+        assert_clock_sysvar, assert_stake_history_sysvar, assert_stake_program,
+        assert_token_program, ASSOCIATED_TOKEN_PROGRAM_ID,
+    },
+    instructions::helpers::{
+        accrue_rewards, check_config_pda, check_deposit_authority, check_pool_cap, epoch_rate,
+        expect_lst_supply_change, pool_lamports, record_inflow, record_managed_change,
+        record_pool_stake, record_principal, stake_account_breakdown, stake_authorities,
+        AccountCheck, ProgramAccount, SignerAccount, StakeAccountAuthorize, StakeAccountMerge,
+        SupplyChange, STAKE_AUTHORIZE_STAKER, STAKE_AUTHORIZE_WITHDRAWER,
+    },
+    math::lamports_to_lst,
+    state::{Config, ManagedChange},
+};
+
+pub struct DepositStakeAccountAccounts<'a> {
+    pub depositor: &'a AccountInfo,
+    pub depositor_ata: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub stake_account: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+    pub clock_sysvar: &'a AccountInfo,
+    pub history_sysvar: &'a AccountInfo,
+    pub stake_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    /// Only needed in a permissioned pool.
+    pub deposit_authority: Option<&'a AccountInfo>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for DepositStakeAccountAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [depositor, depositor_ata, config_pda, stake_account, stake_account_main, stake_account_reserve, lst_mint, pool_state_pda, clock_sysvar, history_sysvar, stake_program, token_program, optional_accounts @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let deposit_authority = match optional_accounts {
+            [] => None,
+            [deposit_authority] => Some(deposit_authority),
+            _ => return Err(ProgramError::NotEnoughAccountKeys),
+        };
+
+        SignerAccount::check(depositor)?;
+
+        assert_clock_sysvar(clock_sysvar)?;
+        assert_stake_history_sysvar(history_sysvar)?;
+        assert_stake_program(stake_program)?;
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            depositor,
+            depositor_ata,
+            config_pda,
+            stake_account,
+            stake_account_main,
+            stake_account_reserve,
+            lst_mint,
+            pool_state_pda,
+            clock_sysvar,
+            history_sysvar,
+            stake_program,
+            token_program,
+            deposit_authority,
+        })
+    }
+}
+
+/// Deposits a stake account the depositor already holds instead of SOL.
+///
+/// The stake account must be fully active on the config's validator, with
+/// the depositor as both staker and withdrawer; anything else fails with
+/// `StakeNotDepositable`. Both authorities move to the config PDA, the
+/// account is merged into main, and LST for every lamport it held is minted
+/// to the depositor's ATA at the current exchange rate, so the stake keeps
+/// earning without an epoch out of the pool. The lamports count against the
+/// per-epoch deposit cap, and anything over it fails with
+/// `DepositCapExceeded`. Pushing the pool past its cap fails with
+/// `PoolCapExceeded`. In a permissioned pool the deposit authority must
+/// co-sign, as for `Deposit`. No deposit fee, referral or bonus applies.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Depositor
+/// 1. `[WRITE]` Depositor ATA
+/// 2. `[]` Config PDA
+/// 3. `[WRITE]` Stake account to deposit
+/// 4. `[WRITE]` Stake account main
+/// 5. `[]` Stake account reserve
+/// 6. `[WRITE]` LST mint
+/// 7. `[WRITE]` Pool state PDA
+/// 8. `[]` Clock sysvar
+/// 9. `[]` Stake history sysvar
+/// 10. `[]` Stake program
+/// 11. `[]` Token program
+/// 12. `[SIGNER]` Deposit authority (only in a permissioned pool, unless the
+///     authority is the depositor)
+pub struct DepositStakeAccount<'a> {
+    pub accounts: DepositStakeAccountAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for DepositStakeAccount<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            accounts: DepositStakeAccountAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> DepositStakeAccount<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &90;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let bump = check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        config.status()?.check_deposits()?;
+
+        check_deposit_authority(
+            &config.deposit_authority,
+            self.accounts
+                .deposit_authority
+                .unwrap_or(self.accounts.depositor),
+        )?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        let expected_ata = find_program_address(
+            &[
+                self.accounts.depositor.key(),
+                self.accounts.token_program.key(),
+                self.accounts.lst_mint.key(),
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .0;
+        if expected_ata != *self.accounts.depositor_ata.key() {
+            return Err(PinocchioError::InvalidDepositorAta.into());
+        }
+
+        let validator_vote_pubkey = config.validator_vote_pubkey;
+        let validator_stake_lamports = config.validator_stake_lamports;
+        let cached_rate = epoch_rate(config)?;
+        let deposit_cap_lamports = config.deposit_cap_lamports;
+        let max_pool_lamports = config.max_pool_lamports;
+        drop(config_data);
+
+        let stake_account = self.accounts.stake_account;
+        let depositor = *self.accounts.depositor.key();
+        if stake_account.key() == self.accounts.stake_account_main.key()
+            || stake_account.key() == self.accounts.stake_account_reserve.key()
+            || stake_authorities(stake_account)? != Some((depositor, depositor))
+        {
+            return Err(PinocchioError::StakeNotDepositable.into());
+        }
+
+        let breakdown = stake_account_breakdown(stake_account, Clock::get()?.epoch)?;
+        if breakdown.active == 0
+            || breakdown.voter != Some(validator_vote_pubkey)
+            || breakdown.activating > 0
+            || breakdown.deactivating > 0
+        {
+            return Err(PinocchioError::StakeNotDepositable.into());
+        }
+
+        record_inflow(
+            self.accounts.pool_state_pda,
+            deposit_cap_lamports,
+            stake_account.lamports(),
+            false,
+        )?;
+
+        let live_lst_supply = Mint::from_account_info(self.accounts.lst_mint)?.supply();
+        let lamports_before = pool_lamports(
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+        check_pool_cap(max_pool_lamports, lamports_before, stake_account.lamports())?;
+        let managed = accrue_rewards(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+
+        for stake_authorize in [STAKE_AUTHORIZE_STAKER, STAKE_AUTHORIZE_WITHDRAWER] {
+            ProgramAccount::authorize_stake_account(
+                stake_account,
+                self.accounts.clock_sysvar,
+                self.accounts.depositor,
+                self.accounts.config_pda.key(),
+                stake_authorize,
+                &[],
+            )?;
+        }
+
+        let bump_binding = [bump];
+        let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
+        ProgramAccount::merge_stake_account(
+            self.accounts.stake_account_main,
+            stake_account,
+            self.accounts.clock_sysvar,
+            self.accounts.history_sysvar,
+            self.accounts.config_pda,
+            config_seeds,
+        )?;
+
+        let deposited = pool_lamports(
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?
+        .checked_sub(lamports_before)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+        record_pool_stake(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+        record_managed_change(
+            self.accounts.pool_state_pda,
+            ManagedChange::Deposited(deposited),
+        )?;
+        let (total_sol_in_pool, total_lst_supply) =
+            cached_rate.unwrap_or((managed, live_lst_supply));
+        let lst_to_mint = lamports_to_lst(deposited, total_lst_supply, total_sol_in_pool)?;
+
+        expect_lst_supply_change(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            SupplyChange::Minted(lst_to_mint),
+            || {
+                MintTo {
+                    mint: self.accounts.lst_mint,
+                    account: self.accounts.depositor_ata,
+                    mint_authority: self.accounts.config_pda,
+                    amount: lst_to_mint,
+                }
+                .invoke_signed(&[Signer::from(config_seeds)])
+            },
+        )?;
+
+        record_principal(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            deposited,
+            lst_to_mint,
+        )?;
+
+        emit(
+            self.accounts.pool_state_pda,
+            EVENT_DEPOSIT,
+            &[
+                self.accounts.depositor.key(),
+                &deposited.to_le_bytes(),
+                &lst_to_mint.to_le_bytes(),
+            ],
+        )
+    }
+}
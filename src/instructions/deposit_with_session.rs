@@ -0,0 +1,293 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+use pinocchio_token::{instructions::MintTo, state::Mint};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit, EVENT_DEPOSIT},
+    ids::{assert_token_program, ASSOCIATED_TOKEN_PROGRAM_ID},
+    instructions::helpers::{
+        check_config_pda, check_deposit_authority, check_pool_cap, epoch_rate,
+        expect_lst_supply_change, managed_lamports, pool_lamports, record_inflow,
+        record_managed_change, record_principal, AccountCheck, SignerAccount, SupplyChange,
+        LAMPORTS_PER_SOL,
+    },
+    math::lamports_to_lst,
+    state::{Config, DepositSession, ManagedChange},
+};
+
+pub struct DepositWithSessionAccounts<'a> {
+    pub session_key: &'a AccountInfo,
+    pub deposit_session_pda: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub user_ata: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+    /// Only needed in a permissioned pool.
+    pub deposit_authority: Option<&'a AccountInfo>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for DepositWithSessionAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [session_key, deposit_session_pda, config_pda, user_ata, lst_mint, stake_account_main, stake_account_reserve, token_program, pool_state_pda, optional_accounts @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let deposit_authority = match optional_accounts {
+            [] => None,
+            [deposit_authority] => Some(deposit_authority),
+            _ => return Err(ProgramError::NotEnoughAccountKeys),
+        };
+
+        SignerAccount::check(session_key)?;
+
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            session_key,
+            deposit_session_pda,
+            config_pda,
+            user_ata,
+            lst_mint,
+            stake_account_main,
+            stake_account_reserve,
+            token_program,
+            pool_state_pda,
+            deposit_authority,
+        })
+    }
+}
+
+pub struct DepositWithSessionInstructionData {
+    pub amount_in_lamports: u64,
+}
+
+impl TryFrom<&[u8]> for DepositWithSessionInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let amount_in_lamports = u64::from_le_bytes(data.try_into().unwrap());
+
+        if amount_in_lamports < LAMPORTS_PER_SOL {
+            return Err(PinocchioError::DepositBelowMinimum.into());
+        }
+
+        Ok(Self { amount_in_lamports })
+    }
+}
+
+/// Deposits SOL escrowed in a deposit session PDA on the user's behalf,
+/// signed by the session key. LST is minted to the user's ATA at the same
+/// rate as `Deposit`, and the amount is taken from the session's budget.
+/// Fails with `DepositCapExceeded` if it does not fit under this epoch's
+/// deposit cap and with `PoolCapExceeded` if it would push the pool past its
+/// cap. In a permissioned pool the deposit authority must co-sign, as for
+/// `Deposit`, so escrow funded before the pool was permissioned cannot mint
+/// without it.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Session key
+/// 1. `[WRITE]` Deposit session PDA
+/// 2. `[]` Config PDA
+/// 3. `[WRITE]` User ATA
+/// 4. `[WRITE]` LST mint
+/// 5. `[]` Stake account main
+/// 6. `[WRITE]` Stake account reserve
+/// 7. `[]` Token program
+/// 8. `[WRITE]` Pool state PDA
+/// 9. `[SIGNER]` Deposit authority (only in a permissioned pool, unless the
+///    authority is the session key)
+pub struct DepositWithSession<'a> {
+    pub accounts: DepositWithSessionAccounts<'a>,
+    pub data: DepositWithSessionInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for DepositWithSession<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: DepositWithSessionAccounts::try_from(accounts)?,
+            data: DepositWithSessionInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> DepositWithSession<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &33;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let bump = check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        config.status()?.check_deposits()?;
+
+        check_deposit_authority(
+            &config.deposit_authority,
+            self.accounts
+                .deposit_authority
+                .unwrap_or(self.accounts.session_key),
+        )?;
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        let deposit_cap_lamports = config.deposit_cap_lamports;
+        let max_pool_lamports = config.max_pool_lamports;
+        let validator_stake_lamports = config.validator_stake_lamports;
+        let cached_rate = epoch_rate(config)?;
+        drop(config_data);
+
+        if !self.accounts.deposit_session_pda.is_owned_by(&crate::ID) {
+            return Err(PinocchioError::InvalidDepositSessionPda.into());
+        }
+
+        let mut deposit_session_data = self.accounts.deposit_session_pda.try_borrow_mut_data()?;
+        let deposit_session = DepositSession::load_mut(&mut deposit_session_data)?;
+        let user = deposit_session.user;
+
+        let expected_deposit_session_pda = find_program_address(
+            &[b"deposit_session", &user, self.accounts.session_key.key()],
+            &crate::ID,
+        )
+        .0;
+        if expected_deposit_session_pda != *self.accounts.deposit_session_pda.key() {
+            return Err(PinocchioError::InvalidDepositSessionPda.into());
+        }
+
+        if Clock::get()?.unix_timestamp >= deposit_session.expires_at {
+            return Err(PinocchioError::DepositSessionExpired.into());
+        }
+
+        deposit_session.budget_lamports = deposit_session
+            .budget_lamports
+            .checked_sub(self.data.amount_in_lamports)
+            .ok_or(PinocchioError::DepositSessionBudgetExceeded)?;
+        drop(deposit_session_data);
+
+        let expected_ata = find_program_address(
+            &[
+                &user,
+                self.accounts.token_program.key(),
+                self.accounts.lst_mint.key(),
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .0;
+        if expected_ata != *self.accounts.user_ata.key() {
+            return Err(PinocchioError::InvalidDepositorAta.into());
+        }
+
+        let live_pool_lamports = pool_lamports(
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+        check_pool_cap(
+            max_pool_lamports,
+            live_pool_lamports,
+            self.data.amount_in_lamports,
+        )?;
+        let managed = managed_lamports(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+        let (total_sol_in_pool, total_lst_supply) = cached_rate.unwrap_or((
+            managed,
+            Mint::from_account_info(self.accounts.lst_mint)?.supply(),
+        ));
+        let lst_to_mint = lamports_to_lst(
+            self.data.amount_in_lamports,
+            total_lst_supply,
+            total_sol_in_pool,
+        )?;
+
+        record_inflow(
+            self.accounts.pool_state_pda,
+            deposit_cap_lamports,
+            self.data.amount_in_lamports,
+            false,
+        )?;
+
+        // The session PDA is program-owned, so its escrow is moved directly.
+        *self
+            .accounts
+            .deposit_session_pda
+            .try_borrow_mut_lamports()? -= self.data.amount_in_lamports;
+        *self
+            .accounts
+            .stake_account_reserve
+            .try_borrow_mut_lamports()? += self.data.amount_in_lamports;
+        record_managed_change(
+            self.accounts.pool_state_pda,
+            ManagedChange::Deposited(self.data.amount_in_lamports),
+        )?;
+
+        let bump_binding = [bump];
+        let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
+
+        expect_lst_supply_change(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            SupplyChange::Minted(lst_to_mint),
+            || {
+                MintTo {
+                    mint: self.accounts.lst_mint,
+                    account: self.accounts.user_ata,
+                    mint_authority: self.accounts.config_pda,
+                    amount: lst_to_mint,
+                }
+                .invoke_signed(&[Signer::from(config_seeds)])
+            },
+        )?;
+
+        record_principal(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            self.data.amount_in_lamports,
+            lst_to_mint,
+        )?;
+
+        emit(
+            self.accounts.pool_state_pda,
+            EVENT_DEPOSIT,
+            &[
+                &user,
+                &self.data.amount_in_lamports.to_le_bytes(),
+                &lst_to_mint.to_le_bytes(),
+            ],
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::find_program_address,
+};
+use pinocchio_token::instructions::Transfer;
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    ids::{assert_token_program, ASSOCIATED_TOKEN_PROGRAM_ID},
+    instructions::helpers::{check_config_pda, AccountCheck, SignerAccount},
+    state::Config,
+};
+
+/// Most recipients one call may pay. Each transfer is a token program CPI,
+/// so this keeps a call well inside the default compute budget.
+pub const MAX_DISTRIBUTE_RECIPIENTS: usize = 16;
+
+pub struct DistributeTreasuryAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub treasury_ata: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+    pub recipients: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for DistributeTreasuryAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, treasury_ata, token_program, pool_state_pda, recipients @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            treasury_ata,
+            token_program,
+            pool_state_pda,
+            recipients,
+        })
+    }
+}
+
+pub struct DistributeTreasuryInstructionData<'a> {
+    /// One little-endian `u64` LST amount per recipient.
+    pub amounts: &'a [u8],
+}
+
+impl<'a> TryFrom<&'a [u8]> for DistributeTreasuryInstructionData<'a> {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.is_empty() || !data.len().is_multiple_of(8) {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { amounts: data })
+    }
+}
+
+/// Pays out LST from the treasury, the config PDA's LST token account, to
+/// each recipient. Lets the admin run incentive programs without an
+/// off-chain script holding the treasury. Larger payouts are split across
+/// calls of at most `MAX_DISTRIBUTE_RECIPIENTS` recipients.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[]` Config PDA
+/// 2. `[WRITE]` Treasury ATA (config PDA's LST token account)
+/// 3. `[]` Token program
+/// 4. `[WRITE]` Pool state PDA
+/// 5. `[WRITE]` Recipient LST token account for each amount, in order
+pub struct DistributeTreasury<'a> {
+    pub accounts: DistributeTreasuryAccounts<'a>,
+    pub data: DistributeTreasuryInstructionData<'a>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for DistributeTreasury<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = DistributeTreasuryAccounts::try_from(accounts)?;
+        let data = DistributeTreasuryInstructionData::try_from(data)?;
+
+        if data.amounts.len() / 8 != accounts.recipients.len() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if accounts.recipients.len() > MAX_DISTRIBUTE_RECIPIENTS {
+            return Err(PinocchioError::BatchTooLarge.into());
+        }
+
+        Ok(Self { accounts, data })
+    }
+}
+
+impl<'a> DistributeTreasury<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &29;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let bump = check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let expected_treasury_ata = find_program_address(
+            &[
+                self.accounts.config_pda.key(),
+                self.accounts.token_program.key(),
+                &config.lst_mint,
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .0;
+        if expected_treasury_ata != *self.accounts.treasury_ata.key() {
+            return Err(PinocchioError::InvalidTreasuryAta.into());
+        }
+
+        drop(config_data);
+
+        let bump_binding = [bump];
+        let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
+
+        // The token program rejects recipients of another mint.
+        for (recipient, amount) in self
+            .accounts
+            .recipients
+            .iter()
+            .zip(self.data.amounts.chunks_exact(8))
+        {
+            Transfer {
+                from: self.accounts.treasury_ata,
+                to: recipient,
+                authority: self.accounts.config_pda,
+                amount: u64::from_le_bytes(amount.try_into().unwrap()),
+            }
+            .invoke_signed(&[Signer::from(config_seeds)])?;
+        }
+
+        Ok(())
+    }
+}
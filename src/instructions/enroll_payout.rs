@@ -0,0 +1,197 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+use pinocchio_token::{instructions::Transfer, state::TokenAccount};
+
+use crate::{
+    errors::PinocchioError,
+    ids::{assert_system_program, assert_token_program},
+    instructions::helpers::{
+        check_config_pda, check_payout_position, check_reward_payout, AccountCheck, ProgramAccount,
+        ProgramAccountInit, SignerAccount,
+    },
+    state::{Config, PayoutPosition, RewardPayout},
+};
+
+pub struct EnrollPayoutAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub owner_ata: &'a AccountInfo,
+    pub position_pda: &'a AccountInfo,
+    pub reward_payout_pda: &'a AccountInfo,
+    pub payout_vault: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for EnrollPayoutAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [owner, owner_ata, position_pda, reward_payout_pda, payout_vault, lst_mint, config_pda, token_program, system_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(owner)?;
+
+        assert_system_program(system_program)?;
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            owner,
+            owner_ata,
+            position_pda,
+            reward_payout_pda,
+            payout_vault,
+            lst_mint,
+            config_pda,
+            token_program,
+            system_program,
+        })
+    }
+}
+
+pub struct EnrollPayoutInstructionData {
+    pub lst_amount: u64,
+}
+
+impl TryFrom<&[u8]> for EnrollPayoutInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            lst_amount: u64::from_le_bytes(data.try_into().unwrap()),
+        })
+    }
+}
+
+/// Moves `lst_amount` of the owner's LST into the payout vault for shares of
+/// the SOL payout pool, recorded in their payout position
+/// (`b"payout_position"`, owner), which the first call creates. From the
+/// next `CrankRewardPayout` on, `reward_payout_bps` of the rewards earned on
+/// the LST is paid to the position as SOL, claimed with `ClaimPayout`; the
+/// rest compounds into the exchange rate as usual.
+///
+/// Fails with `PayoutNotCranked` until this epoch's `CrankRewardPayout` has
+/// run, so an enrollment cannot take a share of rewards earned before it.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Owner
+/// 1. `[WRITE]` Owner LST ATA
+/// 2. `[WRITE]` Payout position PDA
+/// 3. `[WRITE]` Reward payout PDA
+/// 4. `[WRITE]` Payout vault
+/// 5. `[]` LST mint
+/// 6. `[]` Config PDA
+/// 7. `[]` Token program
+/// 8. `[]` System program
+pub struct EnrollPayout<'a> {
+    pub accounts: EnrollPayoutAccounts<'a>,
+    pub data: EnrollPayoutInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for EnrollPayout<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: EnrollPayoutAccounts::try_from(accounts)?,
+            data: EnrollPayoutInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> EnrollPayout<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &74;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        if Config::load(&config_data)?.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+        drop(config_data);
+
+        check_reward_payout(
+            self.accounts.reward_payout_pda,
+            self.accounts.payout_vault,
+            self.accounts.lst_mint,
+            self.accounts.token_program,
+        )?;
+
+        if self.accounts.position_pda.data_is_empty() {
+            let (expected_position_pda, position_bump) =
+                find_program_address(&[b"payout_position", self.accounts.owner.key()], &crate::ID);
+            if expected_position_pda != *self.accounts.position_pda.key() {
+                return Err(PinocchioError::InvalidPayoutPositionPda.into());
+            }
+
+            let position_bump_binding = [position_bump];
+            let position_seeds = &[
+                Seed::from(b"payout_position"),
+                Seed::from(self.accounts.owner.key()),
+                Seed::from(&position_bump_binding),
+            ];
+            ProgramAccount::init::<PayoutPosition>(
+                self.accounts.owner,
+                self.accounts.position_pda,
+                position_seeds,
+                PayoutPosition::LEN,
+            )?;
+
+            let mut position_data = self.accounts.position_pda.try_borrow_mut_data()?;
+            PayoutPosition::load_mut(&mut position_data)?.owner = *self.accounts.owner.key();
+        } else {
+            check_payout_position(self.accounts.position_pda, self.accounts.owner)?;
+        }
+
+        let vault_balance = TokenAccount::from_account_info(self.accounts.payout_vault)?.amount();
+
+        let mut reward_payout_data = self.accounts.reward_payout_pda.try_borrow_mut_data()?;
+        let reward_payout = RewardPayout::load_mut(&mut reward_payout_data)?;
+        reward_payout.check_cranked(Clock::get()?.epoch)?;
+
+        let new_shares = reward_payout.shares_for(self.data.lst_amount, vault_balance);
+        if new_shares == 0 {
+            return Err(PinocchioError::ZeroLiquidity.into());
+        }
+
+        Transfer {
+            from: self.accounts.owner_ata,
+            to: self.accounts.payout_vault,
+            authority: self.accounts.owner,
+            amount: self.data.lst_amount,
+        }
+        .invoke()?;
+
+        let rewards_per_share = reward_payout.rewards_per_share;
+        let mut position_data = self.accounts.position_pda.try_borrow_mut_data()?;
+        let position = PayoutPosition::load_mut(&mut position_data)?;
+        position.settle(rewards_per_share)?;
+        let shares = position
+            .shares
+            .checked_add(new_shares)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        position.set_shares(shares, rewards_per_share);
+
+        reward_payout.total_shares = reward_payout
+            .total_shares
+            .checked_add(new_shares)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit, EVENT_SPLIT_HANDOFF, HANDOFF_EXECUTED},
+    instructions::helpers::{
+        check_split_handoff, AccountCheck, AccountClose, ProgramAccount, SignerAccount,
+    },
+    state::{SplitHandoff, SplitRecord},
+};
+
+pub struct ExecuteSplitHandoffAccounts<'a> {
+    pub new_owner: &'a AccountInfo,
+    pub proposer: &'a AccountInfo,
+    pub split_record: &'a AccountInfo,
+    pub handoff_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ExecuteSplitHandoffAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [new_owner, proposer, split_record, handoff_pda, pool_state_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(new_owner)?;
+
+        Ok(Self {
+            new_owner,
+            proposer,
+            split_record,
+            handoff_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+/// Applies a split handoff once its timelock has passed: the split record's
+/// owner becomes the new owner, who can then `Withdraw` the split account or
+/// crank its remaining tranches. The split account keeps its address, derived
+/// from the first owner. A rent payer that was the previous owner becomes the
+/// new owner too, so `Withdraw` pays everything to them. The handoff PDA is
+/// closed and its rent refunded to the proposer.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` New owner
+/// 1. `[WRITE]` Proposer (the handoff's rent payer)
+/// 2. `[WRITE]` Split record PDA
+/// 3. `[WRITE]` Split handoff PDA
+/// 4. `[WRITE]` Pool state PDA
+pub struct ExecuteSplitHandoff<'a> {
+    pub accounts: ExecuteSplitHandoffAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ExecuteSplitHandoff<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: ExecuteSplitHandoffAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> ExecuteSplitHandoff<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &66;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_split_handoff(self.accounts.handoff_pda, self.accounts.split_record)?;
+        ProgramAccount::check(self.accounts.split_record)?;
+
+        let handoff_data = self.accounts.handoff_pda.try_borrow_data()?;
+        let handoff = SplitHandoff::load(&handoff_data)?;
+        if handoff.new_owner != *self.accounts.new_owner.key() {
+            return Err(PinocchioError::InvalidAddress.into());
+        }
+        if handoff.proposer != *self.accounts.proposer.key() {
+            return Err(PinocchioError::InvalidRentPayer.into());
+        }
+        if Clock::get()?.unix_timestamp < handoff.executable_at {
+            return Err(PinocchioError::HandoffTimelocked.into());
+        }
+        let split_account = handoff.split_account;
+        let executable_at = handoff.executable_at;
+        drop(handoff_data);
+
+        let mut split_record_data = self.accounts.split_record.try_borrow_mut_data()?;
+        let split_record = SplitRecord::load_mut(&mut split_record_data)?;
+        if split_record.rent_payer == split_record.owner {
+            split_record.rent_payer = *self.accounts.new_owner.key();
+        }
+        split_record.owner = *self.accounts.new_owner.key();
+        drop(split_record_data);
+
+        ProgramAccount::close(self.accounts.handoff_pda, self.accounts.proposer)?;
+
+        emit(
+            self.accounts.pool_state_pda,
+            EVENT_SPLIT_HANDOFF,
+            &[
+                &split_account,
+                self.accounts.new_owner.key(),
+                &[HANDOFF_EXECUTED],
+                &executable_at.to_le_bytes(),
+            ],
+        )
+    }
+}
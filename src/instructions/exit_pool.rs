@@ -0,0 +1,391 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+use pinocchio_system::instructions::Transfer;
+use pinocchio_token::{
+    instructions::Burn,
+    state::{Mint, TokenAccount},
+};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit, EVENT_STATUS},
+    ids::{
+        assert_clock_sysvar, assert_stake_history_sysvar, assert_stake_program,
+        assert_system_program, assert_token_program, ASSOCIATED_TOKEN_PROGRAM_ID,
+        SYSTEM_PROGRAM_ID,
+    },
+    instructions::helpers::{
+        accrue_rewards, check_config_pda, expect_lst_supply_change, record_managed_change,
+        record_pool_stake, stake_account_space, AccountCheck, ProgramAccount, ProgramAccountInit,
+        SignerAccount, StakeAccountCreate, StakeAccountDeactivate, StakeAccountSplit,
+        StakeAccountWithdraw, SupplyChange,
+    },
+    state::{Config, ManagedChange, PoolStatus, SplitRecord},
+};
+
+pub struct ExitPoolAccounts<'a> {
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub withdrawer: &'a AccountInfo,
+    pub new_stake_account: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub withdrawer_ata: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub clock_sysvar: &'a AccountInfo,
+    pub history_sysvar: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub stake_program: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub split_record: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+    /// Funds the new split account and split record. The withdrawer unless
+    /// a separate payer is passed.
+    pub payer: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ExitPoolAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [stake_account_main, stake_account_reserve, withdrawer, new_stake_account, config_pda, withdrawer_ata, lst_mint, clock_sysvar, history_sysvar, token_program, stake_program, system_program, split_record, pool_state_pda, optional_accounts @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let payer = match optional_accounts {
+            [] => withdrawer,
+            [payer] => {
+                SignerAccount::check(payer)?;
+                payer
+            }
+            _ => return Err(ProgramError::NotEnoughAccountKeys),
+        };
+
+        SignerAccount::check(withdrawer)?;
+
+        assert_system_program(system_program)?;
+        assert_stake_program(stake_program)?;
+        assert_clock_sysvar(clock_sysvar)?;
+        assert_stake_history_sysvar(history_sysvar)?;
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            stake_account_main,
+            stake_account_reserve,
+            withdrawer,
+            new_stake_account,
+            config_pda,
+            withdrawer_ata,
+            lst_mint,
+            clock_sysvar,
+            history_sysvar,
+            token_program,
+            stake_program,
+            system_program,
+            split_record,
+            pool_state_pda,
+            payer,
+        })
+    }
+}
+
+pub struct ExitPoolInstructionData {
+    pub nonce: u64,
+}
+
+impl TryFrom<&[u8]> for ExitPoolInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let nonce = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+        Ok(Self { nonce })
+    }
+}
+
+/// Lets the holder of the entire LST supply redeem the whole pool.
+///
+/// Undelegated reserve lamports are paid out directly. The main stake
+/// account is split in full into the holder's split PDA and deactivated,
+/// bypassing minimum-delegation constraints on the remainder, and the whole
+/// supply is burned. The split is claimed with `Withdraw` once cooled down.
+/// Delegated reserve stake must be merged into main first, and stake on the
+/// listed validators split out, or it fails with `ValidatorStakeOutstanding`.
+/// An outgoing reserve must be merged by `CrankRedelegateMain` first, or it
+/// fails with `ReserveRotationPending`. Moves the pool to `Closed`.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE]` Stake account main
+/// 1. `[WRITE]` Stake account reserve
+/// 2. `[WRITE, SIGNER]` Withdrawer
+/// 3. `[WRITE]` New stake account (split PDA)
+/// 4. `[WRITE]` Config PDA
+/// 5. `[WRITE]` Withdrawer ATA
+/// 6. `[WRITE]` LST mint
+/// 7. `[]` Clock sysvar
+/// 8. `[]` History sysvar
+/// 9. `[]` Token program
+/// 10. `[]` Stake program
+/// 11. `[]` System program
+/// 12. `[WRITE]` Split record PDA
+/// 13. `[WRITE]` Pool state PDA
+/// 14. `[WRITE, SIGNER]` Payer (optional, defaults to the withdrawer)
+pub struct ExitPool<'a> {
+    pub accounts: ExitPoolAccounts<'a>,
+    pub data: ExitPoolInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for ExitPool<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: ExitPoolAccounts::try_from(accounts)?,
+            data: ExitPoolInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> ExitPool<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &8;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let bump = check_config_pda(self.accounts.config_pda)?;
+
+        let data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        let expected_ata = find_program_address(
+            &[
+                self.accounts.withdrawer.key(),
+                self.accounts.token_program.key(),
+                self.accounts.lst_mint.key(),
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .0;
+        if expected_ata != *self.accounts.withdrawer_ata.key() {
+            return Err(PinocchioError::InvalidWithdrawerAta.into());
+        }
+
+        let total_supply = Mint::from_account_info(self.accounts.lst_mint)?.supply();
+        let withdrawer_ata_amount =
+            TokenAccount::from_account_info(self.accounts.withdrawer_ata)?.amount();
+        if total_supply == 0 || withdrawer_ata_amount != total_supply {
+            return Err(PinocchioError::NotLastHolder.into());
+        }
+
+        config.status()?.check_unstakes()?;
+
+        // Stake on the listed validators is not in main and would be left
+        // behind.
+        if config.validator_stake_lamports != 0 {
+            return Err(PinocchioError::ValidatorStakeOutstanding.into());
+        }
+        if config.outgoing_stake_lamports != 0 {
+            return Err(PinocchioError::ReserveRotationPending.into());
+        }
+
+        let bump_binding = [bump];
+        let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
+
+        let nonce_bytes = self.data.nonce.to_le_bytes();
+        let (expected_new_stake_account, new_stake_account_bump) = find_program_address(
+            &[
+                b"split_account",
+                self.accounts.withdrawer.key(),
+                &nonce_bytes,
+            ],
+            &crate::ID,
+        );
+
+        if expected_new_stake_account != *self.accounts.new_stake_account.key() {
+            return Err(PinocchioError::InvalidSplitAccountPda.into());
+        }
+
+        let (expected_split_record, split_record_bump) = find_program_address(
+            &[b"split_record", self.accounts.new_stake_account.key()],
+            &crate::ID,
+        );
+
+        if expected_split_record != *self.accounts.split_record.key() {
+            return Err(PinocchioError::InvalidSplitRecordPda.into());
+        }
+
+        // Everything the pool manages leaves with the holder.
+        let managed = accrue_rewards(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            0,
+        )?;
+
+        self.drain_reserve()?;
+
+        let new_stake_account_bump_binding = [new_stake_account_bump];
+        let new_stake_seeds = &[
+            Seed::from(b"split_account"),
+            Seed::from(self.accounts.withdrawer.key()),
+            Seed::from(&nonce_bytes),
+            Seed::from(&new_stake_account_bump_binding),
+        ];
+
+        ProgramAccount::stake_account_create(
+            self.accounts.payer,
+            self.accounts.new_stake_account,
+            new_stake_seeds,
+            stake_account_space(self.accounts.stake_account_main),
+        )?;
+
+        // Splitting the full balance deinitializes main, so no minimum has to
+        // remain behind.
+        let lamports_to_split = self.accounts.stake_account_main.lamports();
+
+        ProgramAccount::split_stake_account(
+            self.accounts.stake_account_main,
+            self.accounts.new_stake_account,
+            &lamports_to_split,
+            self.accounts.config_pda,
+            config_seeds,
+        )?;
+        record_pool_stake(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            0,
+        )?;
+
+        ProgramAccount::deactivate_stake_account(
+            self.accounts.new_stake_account,
+            self.accounts.clock_sysvar,
+            self.accounts.config_pda,
+            config_seeds,
+        )?;
+
+        expect_lst_supply_change(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            SupplyChange::Burned(total_supply),
+            || {
+                Burn {
+                    account: self.accounts.withdrawer_ata,
+                    mint: self.accounts.lst_mint,
+                    authority: self.accounts.withdrawer,
+                    amount: total_supply,
+                }
+                .invoke()
+            },
+        )?;
+        record_managed_change(
+            self.accounts.pool_state_pda,
+            ManagedChange::Withdrawn(managed),
+        )?;
+
+        let split_record_bump_binding = [split_record_bump];
+        let split_record_seeds = &[
+            Seed::from(b"split_record"),
+            Seed::from(self.accounts.new_stake_account.key()),
+            Seed::from(&split_record_bump_binding),
+        ];
+
+        ProgramAccount::init::<SplitRecord>(
+            self.accounts.payer,
+            self.accounts.split_record,
+            split_record_seeds,
+            SplitRecord::LEN,
+        )?;
+
+        let mut split_record_data = self.accounts.split_record.try_borrow_mut_data()?;
+        let split_record = SplitRecord::load_mut(&mut split_record_data)?;
+        split_record.set_inner(
+            *self.accounts.withdrawer.key(),
+            *self.accounts.new_stake_account.key(),
+            lamports_to_split,
+            lamports_to_split,
+            total_supply,
+            0,
+            0,
+        );
+        split_record.rent_payer = *self.accounts.payer.key();
+        split_record.created_epoch = Clock::get()?.epoch;
+
+        drop(data);
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let previous = Config::load_mut(&mut config_data)?.transition(PoolStatus::Closed)?;
+        emit(
+            self.accounts.pool_state_pda,
+            EVENT_STATUS,
+            &[&[previous as u8], &[PoolStatus::Closed as u8]],
+        )?;
+
+        Ok(())
+    }
+
+    /// Pays out lamports sitting in the reserve that were never delegated.
+    fn drain_reserve(&self) -> Result<(), ProgramError> {
+        let reserve = self.accounts.stake_account_reserve;
+
+        if reserve.lamports() == 0 {
+            return Ok(());
+        }
+
+        let reserve_bump =
+            Config::load(&self.accounts.config_pda.try_borrow_data()?)?.stake_reserve_bump;
+        let reserve_bump_binding = [reserve_bump];
+        let reserve_seeds = &[
+            Seed::from(b"stake_reserve"),
+            Seed::from(&reserve_bump_binding),
+        ];
+
+        // Deposits made after a merge land in a system-owned reserve.
+        if reserve.is_owned_by(&SYSTEM_PROGRAM_ID) {
+            return Transfer {
+                from: reserve,
+                to: self.accounts.withdrawer,
+                lamports: reserve.lamports(),
+            }
+            .invoke_signed(&[Signer::from(reserve_seeds)]);
+        }
+
+        let reserve_data = reserve.try_borrow_data()?;
+        let stake_state = u32::from_le_bytes(reserve_data[0..4].try_into().unwrap());
+        drop(reserve_data);
+
+        if stake_state != 0 {
+            return Err(PinocchioError::ReserveStakeActive.into());
+        }
+
+        // An uninitialized stake account is its own withdraw authority.
+        ProgramAccount::withdraw_stake_account(
+            reserve,
+            self.accounts.withdrawer,
+            self.accounts.clock_sysvar,
+            self.accounts.history_sysvar,
+            reserve,
+            reserve_seeds,
+        )
+    }
+}
@@ -0,0 +1,416 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::find_program_address,
+};
+use pinocchio_token::{
+    instructions::{Burn, Transfer},
+    state::Mint,
+};
+
+use crate::{
+    errors::PinocchioError,
+    ids::{
+        assert_clock_sysvar, assert_stake_history_sysvar, assert_stake_program,
+        assert_token_program,
+    },
+    instructions::helpers::{
+        check_config_pda, check_unstake_order, close_unstake_order, epoch_rate,
+        expect_lst_supply_change, managed_lamports, record_managed_change, reserve_spare_lamports,
+        AccountCheck, ProgramAccount, SignerAccount, StakeAccountWithdraw, SupplyChange,
+        LAMPORTS_PER_SOL,
+    },
+    math::{lst_to_lamports, unstake_lamports_out},
+    state::{Config, LiquidityPool, ManagedChange, UnstakeOrder},
+};
+
+/// Where `FillUnstakeOrder` takes the SOL from, chosen by the keeper through
+/// the accounts it passes.
+pub enum FillRoute<'a> {
+    /// Swap the LST into the liquidity pool at the `LiquidUnstake` price.
+    Buffer {
+        liquidity_pool_pda: &'a AccountInfo,
+        lst_vault: &'a AccountInfo,
+        pool_state_pda: &'a AccountInfo,
+    },
+    /// Burn the LST and pay its value at the exchange rate from the
+    /// reserve's undelegated lamports, without a fee.
+    Reserve {
+        clock_sysvar: &'a AccountInfo,
+        history_sysvar: &'a AccountInfo,
+        stake_program: &'a AccountInfo,
+        pool_state_pda: &'a AccountInfo,
+    },
+}
+
+impl<'a> FillRoute<'a> {
+    /// The pool state PDA both routes pass, for the managed lamports the
+    /// order is priced against.
+    pub fn pool_state_pda(&self) -> &'a AccountInfo {
+        match *self {
+            FillRoute::Buffer { pool_state_pda, .. } => pool_state_pda,
+            FillRoute::Reserve { pool_state_pda, .. } => pool_state_pda,
+        }
+    }
+}
+
+pub struct FillUnstakeOrderAccounts<'a> {
+    pub keeper: &'a AccountInfo,
+    pub owner: &'a AccountInfo,
+    pub order_pda: &'a AccountInfo,
+    pub order_vault: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub route: FillRoute<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for FillUnstakeOrderAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [keeper, owner, order_pda, order_vault, config_pda, lst_mint, stake_account_main, stake_account_reserve, token_program, route_accounts @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let route = match route_accounts {
+            [liquidity_pool_pda, lst_vault, pool_state_pda] => FillRoute::Buffer {
+                liquidity_pool_pda,
+                lst_vault,
+                pool_state_pda,
+            },
+            [clock_sysvar, history_sysvar, stake_program, pool_state_pda] => {
+                assert_stake_program(stake_program)?;
+                assert_clock_sysvar(clock_sysvar)?;
+                assert_stake_history_sysvar(history_sysvar)?;
+
+                FillRoute::Reserve {
+                    clock_sysvar,
+                    history_sysvar,
+                    stake_program,
+                    pool_state_pda,
+                }
+            }
+            _ => return Err(ProgramError::NotEnoughAccountKeys),
+        };
+
+        SignerAccount::check(keeper)?;
+
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            keeper,
+            owner,
+            order_pda,
+            order_vault,
+            config_pda,
+            lst_mint,
+            stake_account_main,
+            stake_account_reserve,
+            token_program,
+            route,
+        })
+    }
+}
+
+pub struct FillUnstakeOrderInstructionData {
+    pub nonce: u64,
+}
+
+impl TryFrom<&[u8]> for FillUnstakeOrderInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            nonce: u64::from_le_bytes(data.try_into().unwrap()),
+        })
+    }
+}
+
+/// Fills an unstake order once it fetches the owner's minimum rate.
+/// Permissionless.
+///
+/// The whole order is unstaked in one go, either through the liquidity
+/// buffer (accounts 9–11), paying the `LiquidUnstake` price after its fee, or
+/// from the reserve (accounts 9–12), paying the LST's value at the exchange
+/// rate like `BurnFromWrapper`. The SOL goes to the owner, the tip to the
+/// keeper, and the order and its vault are closed with their rent returned
+/// to the owner. Fails with `OrderRateNotMet` while the route pays less than
+/// the order's rate, so keepers retry later or through the other route.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Keeper
+/// 1. `[WRITE]` Owner
+/// 2. `[WRITE]` Unstake order PDA
+/// 3. `[WRITE]` Order vault
+/// 4. `[]` Config PDA
+/// 5. `[WRITE]` LST mint
+/// 6. `[]` Stake account main
+/// 7. `[WRITE]` Stake account reserve
+/// 8. `[]` Token program
+/// 9. `[WRITE]` Liquidity pool PDA (buffer route)
+/// 10. `[WRITE]` LST vault (buffer route)
+/// 11. `[]` Pool state PDA (buffer route)
+/// 9. `[]` Clock sysvar (reserve route)
+/// 10. `[]` History sysvar (reserve route)
+/// 11. `[]` Stake program (reserve route)
+/// 12. `[WRITE]` Pool state PDA (reserve route)
+pub struct FillUnstakeOrder<'a> {
+    pub accounts: FillUnstakeOrderAccounts<'a>,
+    pub data: FillUnstakeOrderInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for FillUnstakeOrder<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: FillUnstakeOrderAccounts::try_from(accounts)?,
+            data: FillUnstakeOrderInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> FillUnstakeOrder<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &58;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        config.status()?.check_unstakes()?;
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+        let validator_stake_lamports = config.validator_stake_lamports;
+        let cached_rate = epoch_rate(config)?;
+        drop(config_data);
+
+        let order_bump = check_unstake_order(
+            self.accounts.order_pda,
+            self.accounts.order_vault,
+            self.accounts.owner,
+            self.data.nonce,
+        )?;
+
+        let order_data = self.accounts.order_pda.try_borrow_data()?;
+        let order = UnstakeOrder::load(&order_data)?;
+        let lst_amount = order.lst_amount;
+        let tip = order.tip_lamports;
+        let min_lamports_out = u64::try_from(
+            (lst_amount as u128 * order.min_lamports_per_lst as u128)
+                .div_ceil(LAMPORTS_PER_SOL as u128),
+        )
+        .map_err(|_| PinocchioError::OrderRateNotMet)?;
+        drop(order_data);
+
+        let (total_lamports, lst_supply) = cached_rate.unwrap_or((
+            managed_lamports(
+                self.accounts.route.pool_state_pda(),
+                self.accounts.stake_account_main,
+                self.accounts.stake_account_reserve,
+                validator_stake_lamports,
+            )?,
+            Mint::from_account_info(self.accounts.lst_mint)?.supply(),
+        ));
+        let lamports_value = lst_to_lamports(lst_amount, lst_supply, total_lamports)?;
+
+        let nonce_bytes = self.data.nonce.to_le_bytes();
+        let order_bump_binding = [order_bump];
+        let order_seeds = &[
+            Seed::from(b"unstake_order"),
+            Seed::from(self.accounts.owner.key()),
+            Seed::from(&nonce_bytes),
+            Seed::from(&order_bump_binding),
+        ];
+
+        match self.accounts.route {
+            FillRoute::Buffer {
+                liquidity_pool_pda,
+                lst_vault,
+                ..
+            } => self.fill_from_buffer(
+                liquidity_pool_pda,
+                lst_vault,
+                lst_amount,
+                lamports_value,
+                min_lamports_out,
+                order_seeds,
+            )?,
+            FillRoute::Reserve {
+                clock_sysvar,
+                history_sysvar,
+                pool_state_pda,
+                ..
+            } => self.fill_from_reserve(
+                clock_sysvar,
+                history_sysvar,
+                pool_state_pda,
+                lst_amount,
+                lamports_value,
+                min_lamports_out,
+                order_seeds,
+            )?,
+        }
+
+        *self.accounts.order_pda.try_borrow_mut_lamports()? -= tip;
+        *self.accounts.keeper.try_borrow_mut_lamports()? += tip;
+
+        close_unstake_order(
+            self.accounts.order_pda,
+            self.accounts.order_vault,
+            self.accounts.owner,
+            order_seeds,
+        )
+    }
+
+    /// Swaps the order's LST into the liquidity pool's vault and pays the
+    /// SOL from the pool, as `LiquidUnstake` would.
+    fn fill_from_buffer(
+        &self,
+        liquidity_pool_pda: &AccountInfo,
+        lst_vault: &AccountInfo,
+        lst_amount: u64,
+        lamports_value: u64,
+        min_lamports_out: u64,
+        order_seeds: &[Seed],
+    ) -> Result<(), ProgramError> {
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+        let unstake_fee_min_bps = config.unstake_fee_min_bps;
+        let unstake_fee_max_bps = config.unstake_fee_max_bps;
+        let liquidity_target_lamports = config.liquidity_target_lamports;
+        drop(config_data);
+
+        let (expected_liquidity_pool_pda, _) =
+            find_program_address(&[b"liquidity_pool"], &crate::ID);
+        if expected_liquidity_pool_pda != *liquidity_pool_pda.key() {
+            return Err(PinocchioError::InvalidLiquidityPoolPda.into());
+        }
+
+        let mut liquidity_pool_data = liquidity_pool_pda.try_borrow_mut_data()?;
+        let liquidity_pool = LiquidityPool::load_mut(&mut liquidity_pool_data)?;
+
+        if liquidity_pool.lst_vault != *lst_vault.key() {
+            return Err(PinocchioError::InvalidLstVault.into());
+        }
+
+        let sol_reserves = liquidity_pool.sol_reserves;
+        let lamports_out = unstake_lamports_out(
+            lamports_value,
+            sol_reserves,
+            unstake_fee_min_bps,
+            unstake_fee_max_bps,
+            liquidity_target_lamports,
+        )?;
+
+        if lamports_out == 0 {
+            return Err(PinocchioError::ZeroLiquidity.into());
+        }
+
+        if lamports_out < min_lamports_out {
+            return Err(PinocchioError::OrderRateNotMet.into());
+        }
+
+        liquidity_pool.sol_reserves = sol_reserves - lamports_out;
+        drop(liquidity_pool_data);
+
+        Transfer {
+            from: self.accounts.order_vault,
+            to: lst_vault,
+            authority: self.accounts.order_pda,
+            amount: lst_amount,
+        }
+        .invoke_signed(&[Signer::from(order_seeds)])?;
+
+        *liquidity_pool_pda.try_borrow_mut_lamports()? -= lamports_out;
+        *self.accounts.owner.try_borrow_mut_lamports()? += lamports_out;
+
+        Ok(())
+    }
+
+    /// Burns the order's LST and pays its value from the reserve's
+    /// undelegated lamports, as `BurnFromWrapper` would.
+    #[allow(clippy::too_many_arguments)]
+    fn fill_from_reserve(
+        &self,
+        clock_sysvar: &AccountInfo,
+        history_sysvar: &AccountInfo,
+        pool_state_pda: &AccountInfo,
+        lst_amount: u64,
+        lamports_value: u64,
+        min_lamports_out: u64,
+        order_seeds: &[Seed],
+    ) -> Result<(), ProgramError> {
+        if lamports_value == 0 {
+            return Err(PinocchioError::ZeroLiquidity.into());
+        }
+
+        if lamports_value < min_lamports_out {
+            return Err(PinocchioError::OrderRateNotMet.into());
+        }
+
+        let reserve = self.accounts.stake_account_reserve;
+        let reserve_spare = reserve_spare_lamports(reserve, PinocchioError::InsufficientLiquidity)?;
+        if lamports_value > reserve_spare {
+            return Err(PinocchioError::InsufficientLiquidity.into());
+        }
+
+        expect_lst_supply_change(
+            pool_state_pda,
+            self.accounts.lst_mint,
+            SupplyChange::Burned(lst_amount),
+            || {
+                Burn {
+                    account: self.accounts.order_vault,
+                    mint: self.accounts.lst_mint,
+                    authority: self.accounts.order_pda,
+                    amount: lst_amount,
+                }
+                .invoke_signed(&[Signer::from(order_seeds)])
+            },
+        )?;
+
+        let stake_reserve_bump =
+            Config::load(&self.accounts.config_pda.try_borrow_data()?)?.stake_reserve_bump;
+        let stake_reserve_bump_binding = [stake_reserve_bump];
+        let stake_reserve_seeds = &[
+            Seed::from(b"stake_reserve"),
+            Seed::from(&stake_reserve_bump_binding),
+        ];
+
+        // An uninitialized stake account is its own withdraw authority.
+        ProgramAccount::withdraw_stake_account_lamports(
+            reserve,
+            self.accounts.owner,
+            clock_sysvar,
+            history_sysvar,
+            reserve,
+            lamports_value,
+            stake_reserve_seeds,
+        )?;
+
+        record_managed_change(pool_state_pda, ManagedChange::Withdrawn(lamports_value))
+    }
+}
@@ -0,0 +1,144 @@
+use pinocchio::{
+    account_info::AccountInfo, cpi::set_return_data, program_error::ProgramError,
+    pubkey::find_program_address,
+};
+
+use crate::{
+    errors::PinocchioError,
+    state::{RateHistory, RateSnapshot},
+};
+
+/// Fixed-point scale used for exchange rates and growth factors.
+pub const RATE_SCALE: u128 = 1_000_000_000;
+
+/// 432,000 slots per epoch at 400ms per slot is two days per epoch.
+pub const EPOCHS_PER_YEAR: u64 = 182;
+
+/// Trailing windows (in epochs) reported by `GetApy`.
+pub const APY_WINDOWS: [u64; 3] = [1, 7, 30];
+
+pub struct GetApyAccounts<'a> {
+    pub rate_history_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GetApyAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [rate_history_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self { rate_history_pda })
+    }
+}
+
+/// Returns trailing 1/7/30-epoch APY computed from the rate history.
+///
+/// Return data is one 16-byte entry per window in `APY_WINDOWS` order:
+/// `epochs_covered: u64` (0 when history is too short) followed by
+/// `apy_bps: i64`.
+///
+/// Accounts expected:
+///
+/// 0. `[]` Rate history PDA
+pub struct GetApy<'a> {
+    pub accounts: GetApyAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GetApy<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: GetApyAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> GetApy<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &7;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let (expected_rate_history_pda, _) = find_program_address(&[b"rate_history"], &crate::ID);
+        if expected_rate_history_pda != *self.accounts.rate_history_pda.key() {
+            return Err(PinocchioError::InvalidRateHistoryPda.into());
+        }
+
+        let data = self.accounts.rate_history_pda.try_borrow_data()?;
+        let rate_history = RateHistory::load(&data)?;
+
+        let mut return_data = [0u8; APY_WINDOWS.len() * 16];
+
+        if let Some(latest) = rate_history.latest() {
+            for (window_index, window) in APY_WINDOWS.iter().enumerate() {
+                let Some(previous) = rate_history.at_least_epochs_before(latest.epoch, *window)
+                else {
+                    continue;
+                };
+
+                let (Some(rate_then), Some(rate_now)) = (rate(&previous), rate(&latest)) else {
+                    continue;
+                };
+
+                let epochs_covered = latest.epoch - previous.epoch;
+                let apy_bps = annualized_bps(rate_then, rate_now, epochs_covered)
+                    .ok_or(ProgramError::ArithmeticOverflow)?;
+
+                let offset = window_index * 16;
+                return_data[offset..offset + 8].copy_from_slice(&epochs_covered.to_le_bytes());
+                return_data[offset + 8..offset + 16].copy_from_slice(&apy_bps.to_le_bytes());
+            }
+        }
+
+        set_return_data(&return_data);
+
+        Ok(())
+    }
+}
+
+/// Lamports per LST scaled by `RATE_SCALE`, or `None` for an empty pool.
+fn rate(snapshot: &RateSnapshot) -> Option<u128> {
+    let lst_supply = snapshot.lst_supply;
+    if lst_supply == 0 {
+        return None;
+    }
+
+    (snapshot.total_lamports as u128)
+        .checked_mul(RATE_SCALE)?
+        .checked_div(lst_supply as u128)
+}
+
+fn pow_scaled(mut base: u128, mut exponent: u64) -> Option<u128> {
+    let mut result = RATE_SCALE;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result.checked_mul(base)? / RATE_SCALE;
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            base = base.checked_mul(base)? / RATE_SCALE;
+        }
+    }
+    Some(result)
+}
+
+/// Compounds the growth observed over `epochs` epochs to a yearly rate in basis points.
+fn annualized_bps(rate_then: u128, rate_now: u128, epochs: u64) -> Option<i64> {
+    let growth = rate_now.checked_mul(RATE_SCALE)?.checked_div(rate_then)?;
+
+    let periods = EPOCHS_PER_YEAR / epochs;
+    let remainder = (EPOCHS_PER_YEAR % epochs) as u128;
+
+    // The fractional period left over is applied linearly.
+    let partial = if growth >= RATE_SCALE {
+        RATE_SCALE + (growth - RATE_SCALE) * remainder / epochs as u128
+    } else {
+        RATE_SCALE - (RATE_SCALE - growth) * remainder / epochs as u128
+    };
+
+    let annual = pow_scaled(growth, periods)?.checked_mul(partial)? / RATE_SCALE;
+
+    let bps = (annual as i128 - RATE_SCALE as i128) * 10_000 / RATE_SCALE as i128;
+    i64::try_from(bps).ok()
+}
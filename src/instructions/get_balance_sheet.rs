@@ -0,0 +1,196 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::set_return_data,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+use pinocchio_token::state::Mint;
+
+use crate::{
+    errors::PinocchioError,
+    instructions::helpers::{check_config_pda, managed_lamports, stake_account_breakdown},
+    math::lst_to_lamports,
+    state::{Config, LiquidityPool},
+};
+
+/// Size of the balance sheet returned by `GetBalanceSheet`.
+pub const BALANCE_SHEET_LEN: usize = 8 * 8 + 1;
+
+pub struct GetBalanceSheetAccounts<'a> {
+    pub config_pda: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub liquidity_pool_pda: &'a AccountInfo,
+    pub fee_vault_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GetBalanceSheetAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [config_pda, stake_account_main, stake_account_reserve, lst_mint, liquidity_pool_pda, fee_vault_pda, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            lst_mint,
+            liquidity_pool_pda,
+            fee_vault_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+/// Returns a balance-sheet summary of the pool so auditors can check
+/// solvency with one simulated call.
+///
+/// The return data is eight little-endian `u64`s:
+///
+/// - Assets backing LST, the lamports in main and the reserve excluding rent:
+///   `active`, `activating`, `deactivating` and `undelegated`.
+/// - `buffer_lamports`: the liquidity pool's SOL buffer plus the SOL cooling
+///   down in its buffer stake account. It belongs to liquidity providers and
+///   does not back LST.
+/// - Liabilities: `lst_supply` and `liabilities_lamports`, the supply valued
+///   at the current exchange rate, against the pool's managed lamports.
+/// - Equity: `fees_accrued_lamports`, the SOL in the fee vault.
+///
+/// A last byte gives the LST mint's decimals. Every line is in raw base
+/// units: `lst_supply` in the LST's decimals, the rest in lamports.
+///
+/// The pool is solvent when the four asset lines cover
+/// `liabilities_lamports`. Rounding only ever leaves the liabilities lower.
+///
+/// Accounts expected:
+///
+/// 0. `[]` Config PDA
+/// 1. `[]` Stake account main
+/// 2. `[]` Stake account reserve
+/// 3. `[]` LST mint
+/// 4. `[]` Liquidity pool PDA (may be uninitialized)
+/// 5. `[]` Fee vault PDA (may be empty)
+/// 6. `[]` Pool state PDA
+pub struct GetBalanceSheet<'a> {
+    pub accounts: GetBalanceSheetAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GetBalanceSheet<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            accounts: GetBalanceSheetAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> GetBalanceSheet<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &42;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+        let validator_stake_lamports = config.validator_stake_lamports;
+        drop(config_data);
+
+        let (expected_liquidity_pool_pda, _) =
+            find_program_address(&[b"liquidity_pool"], &crate::ID);
+        if expected_liquidity_pool_pda != *self.accounts.liquidity_pool_pda.key() {
+            return Err(PinocchioError::InvalidLiquidityPoolPda.into());
+        }
+
+        let (expected_fee_vault_pda, _) = find_program_address(&[b"fee_vault"], &crate::ID);
+        if expected_fee_vault_pda != *self.accounts.fee_vault_pda.key() {
+            return Err(PinocchioError::InvalidFeeVaultPda.into());
+        }
+
+        let epoch = Clock::get()?.epoch;
+        let (mut active, mut activating, mut deactivating, mut undelegated) =
+            (0u64, 0u64, 0u64, 0u64);
+
+        for stake_account in [
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+        ] {
+            if stake_account.lamports() == 0 {
+                continue;
+            }
+
+            let breakdown = stake_account_breakdown(stake_account, epoch)?;
+            active = active.saturating_add(breakdown.active);
+            activating = activating.saturating_add(breakdown.activating);
+            deactivating = deactivating.saturating_add(breakdown.deactivating);
+            undelegated = undelegated.saturating_add(breakdown.undelegated);
+        }
+
+        let liquidity_pool_data = self.accounts.liquidity_pool_pda.try_borrow_data()?;
+        let buffer_lamports = if liquidity_pool_data.is_empty() {
+            0
+        } else {
+            let liquidity_pool = LiquidityPool::load(&liquidity_pool_data)?;
+            liquidity_pool
+                .sol_reserves
+                .saturating_add(liquidity_pool.pending_unstake_lamports)
+        };
+        drop(liquidity_pool_data);
+
+        let mint = Mint::from_account_info(self.accounts.lst_mint)?;
+        let lst_supply = mint.supply();
+        let lst_decimals = mint.decimals();
+        drop(mint);
+        let liabilities_lamports = lst_to_lamports(
+            lst_supply,
+            lst_supply,
+            managed_lamports(
+                self.accounts.pool_state_pda,
+                self.accounts.stake_account_main,
+                self.accounts.stake_account_reserve,
+                validator_stake_lamports,
+            )?,
+        )?;
+
+        let mut return_data = [0u8; BALANCE_SHEET_LEN];
+        for (index, value) in [
+            active,
+            activating,
+            deactivating,
+            undelegated,
+            buffer_lamports,
+            lst_supply,
+            liabilities_lamports,
+            self.accounts.fee_vault_pda.lamports(),
+        ]
+        .iter()
+        .enumerate()
+        {
+            return_data[index * 8..index * 8 + 8].copy_from_slice(&value.to_le_bytes());
+        }
+        return_data[BALANCE_SHEET_LEN - 1] = lst_decimals;
+
+        set_return_data(&return_data);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use pinocchio::{cpi::set_return_data, program_error::ProgramError};
+
+use crate::build_info::FIELDS;
+
+/// Returns what the program was built from, see `build_info`: the crate
+/// version, git commit, enabled features and toolchain. Nothing is read or
+/// written.
+///
+/// The return data is each field in that order as a length byte followed by
+/// its UTF-8 bytes. A field longer than 255 bytes is cut to 255, which keeps
+/// the return data within the 1024-byte limit.
+///
+/// Accounts expected: none.
+pub struct GetBuildInfo;
+
+impl GetBuildInfo {
+    pub const DISCRIMINATOR: &'static u8 = &63;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let mut return_data = [0u8; FIELDS.len() * 256];
+        let mut len = 0;
+
+        for field in FIELDS {
+            let bytes = &field.as_bytes()[..field.len().min(u8::MAX as usize)];
+            return_data[len] = bytes.len() as u8;
+            return_data[len + 1..len + 1 + bytes.len()].copy_from_slice(bytes);
+            len += 1 + bytes.len();
+        }
+
+        set_return_data(&return_data[..len]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::set_return_data,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    errors::PinocchioError,
+    instructions::helpers::check_config_pda,
+    instructions::{
+        adopt_reserve::AdoptReserve,
+        crank_initialize_reserve::CrankInitializeReserve,
+        crank_management_fee::CrankManagementFee,
+        crank_merge_reserve::CrankMergeReserve,
+        crank_record_rate::CrankRecordRate,
+        crank_update_rate::CrankUpdateRate,
+        helpers::{stake_is_activating, stake_lamports_excluding_rent},
+    },
+    state::{
+        Config, EpochTasks, RateHistory, EPOCH_TASK_FEE_ACCRUAL, EPOCH_TASK_INITIALIZE_RESERVE,
+        EPOCH_TASK_MERGE_RESERVE, EPOCH_TASK_ORACLE_PUSH, EPOCH_TASK_RATE_UPDATE,
+    },
+};
+
+/// Most cranks `GetPendingCranks` can report at once.
+pub const MAX_PENDING_CRANKS: usize = 3;
+
+pub struct GetPendingCranksAccounts<'a> {
+    pub config_pda: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub epoch_tasks_pda: &'a AccountInfo,
+    pub rate_history_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GetPendingCranksAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [config_pda, stake_account_main, stake_account_reserve, epoch_tasks_pda, rate_history_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            epoch_tasks_pda,
+            rate_history_pda,
+        })
+    }
+}
+
+/// Returns the pool-wide cranks that would succeed right now, so a generic
+/// keeper can operate the pool by running whatever this view lists instead of
+/// encoding each crank's preconditions. Nothing is written.
+///
+/// The return data is a count byte followed by that many crank
+/// `DISCRIMINATOR`s, in the order they should run:
+///
+/// - `AdoptReserve` when the reserve address holds lamports but no stake
+///   account, as after a merge.
+/// - `CrankInitializeReserve` when the reserve is undelegated, holds lamports
+///   above its rent, and delegating it stays under the validator stake cap.
+/// - `CrankMergeReserve` when the reserve is delegated and may merge into
+///   main this epoch.
+/// - `CrankManagementFee` when this epoch's fee is not charged yet.
+/// - `CrankUpdateRate` when this epoch's rate is not cached yet.
+/// - `CrankRecordRate` when this epoch's rate is not recorded yet.
+///
+/// Cranks whose epoch task has unmet prerequisites are left out, as are
+/// `CrankManagementFee` and `CrankUpdateRate` until `CrankValidatorStake` has
+/// recorded every staked validator this epoch. Per-user
+/// cranks (`CrankSplitTranche`, `CrankDca`) and `CrankRefillBuffer` depend on
+/// accounts outside the pool and are not reported.
+///
+/// Accounts expected:
+///
+/// 0. `[]` Config PDA
+/// 1. `[]` Stake account main
+/// 2. `[]` Stake account reserve
+/// 3. `[]` Epoch tasks PDA
+/// 4. `[]` Rate history PDA (may be uninitialized)
+pub struct GetPendingCranks<'a> {
+    pub accounts: GetPendingCranksAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GetPendingCranks<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = GetPendingCranksAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> GetPendingCranks<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &50;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        let (expected_epoch_tasks_pda, _) = find_program_address(&[b"epoch_tasks"], &crate::ID);
+        if expected_epoch_tasks_pda != *self.accounts.epoch_tasks_pda.key()
+            || !self.accounts.epoch_tasks_pda.is_owned_by(&crate::ID)
+        {
+            return Err(PinocchioError::InvalidEpochTasksPda.into());
+        }
+
+        let (expected_rate_history_pda, _) = find_program_address(&[b"rate_history"], &crate::ID);
+        if expected_rate_history_pda != *self.accounts.rate_history_pda.key() {
+            return Err(PinocchioError::InvalidRateHistoryPda.into());
+        }
+
+        let epoch = Clock::get()?.epoch;
+        let epoch_tasks_data = self.accounts.epoch_tasks_pda.try_borrow_data()?;
+        let epoch_tasks = EpochTasks::load(&epoch_tasks_data)?;
+
+        let mut pending = [0u8; 1 + MAX_PENDING_CRANKS];
+        let mut count = 0;
+        let mut push = |task: u8, discriminator: u8| {
+            if epoch_tasks.missing_prerequisites(epoch, task) == 0 {
+                count += 1;
+                pending[count] = discriminator;
+            }
+        };
+
+        let reserve = self.accounts.stake_account_reserve;
+        let reserve_data = reserve.try_borrow_data()?;
+        let reserve_state = if reserve_data.len() < 4 {
+            None
+        } else {
+            Some(u32::from_le_bytes(reserve_data[0..4].try_into().unwrap()))
+        };
+        drop(reserve_data);
+
+        match reserve_state {
+            Some(0) => {
+                let reserve_stake = stake_lamports_excluding_rent(reserve)?;
+                let validator_stake =
+                    stake_lamports_excluding_rent(self.accounts.stake_account_main)?
+                        .checked_add(reserve_stake)
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                let total_lamports = validator_stake
+                    .checked_add(config.validator_stake_lamports)
+                    .ok_or(ProgramError::ArithmeticOverflow)?;
+                if reserve_stake > 0
+                    && validator_stake <= config.validator_stake_cap(total_lamports)
+                {
+                    push(
+                        EPOCH_TASK_INITIALIZE_RESERVE,
+                        *CrankInitializeReserve::DISCRIMINATOR,
+                    );
+                }
+            }
+            Some(2)
+                if !stake_is_activating(reserve, epoch)?
+                    || stake_is_activating(self.accounts.stake_account_main, epoch)? =>
+            {
+                push(EPOCH_TASK_MERGE_RESERVE, *CrankMergeReserve::DISCRIMINATOR);
+            }
+            None if reserve.lamports() > 0 => push(0, *AdoptReserve::DISCRIMINATOR),
+            _ => {}
+        }
+
+        let validator_stake_current = config.validator_stake_current(epoch);
+
+        if config.last_fee_epoch < epoch && validator_stake_current {
+            push(EPOCH_TASK_FEE_ACCRUAL, *CrankManagementFee::DISCRIMINATOR);
+        }
+
+        if epoch_tasks.completed(epoch) & EPOCH_TASK_RATE_UPDATE == 0 && validator_stake_current {
+            push(EPOCH_TASK_RATE_UPDATE, *CrankUpdateRate::DISCRIMINATOR);
+        }
+
+        let rate_history_data = self.accounts.rate_history_pda.try_borrow_data()?;
+        let rate_recorded = !rate_history_data.is_empty()
+            && RateHistory::load(&rate_history_data)?
+                .latest()
+                .is_some_and(|latest| latest.epoch >= epoch);
+        drop(rate_history_data);
+
+        if !rate_recorded {
+            push(EPOCH_TASK_ORACLE_PUSH, *CrankRecordRate::DISCRIMINATOR);
+        }
+
+        pending[0] = count as u8;
+        set_return_data(&pending[..1 + count]);
+
+        Ok(())
+    }
+}
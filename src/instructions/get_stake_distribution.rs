@@ -0,0 +1,259 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::set_return_data,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+    sysvars::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    errors::PinocchioError,
+    instructions::helpers::{
+        check_config_pda, check_validator_stake, pool_lamports, stake_account_breakdown,
+    },
+    state::{Config, ValidatorList},
+};
+
+/// Size of the header at the start of every page.
+pub const DISTRIBUTION_HEADER_LEN: usize = 16;
+/// Size of one validator row.
+pub const DISTRIBUTION_ROW_LEN: usize = 32 + 8 * 4;
+/// Rows per page, keeping each page within the 1024-byte return data limit.
+pub const DISTRIBUTION_ROWS_PER_PAGE: usize = 15;
+
+/// The config validator plus every listed validator.
+const MAX_ROWS: usize = ValidatorList::CAPACITY + 1;
+
+#[derive(Clone, Copy, Default)]
+struct DistributionRow {
+    vote_account: Pubkey,
+    active: u64,
+    activating: u64,
+    deactivating: u64,
+    target: u64,
+}
+
+pub struct GetStakeDistributionAccounts<'a> {
+    pub config_pda: &'a AccountInfo,
+    pub validator_list_pda: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub buffer_stake_pda: &'a AccountInfo,
+    pub validator_stake_pdas: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GetStakeDistributionAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [config_pda, validator_list_pda, stake_account_main, stake_account_reserve, buffer_stake_pda, validator_stake_pdas @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            config_pda,
+            validator_list_pda,
+            stake_account_main,
+            stake_account_reserve,
+            buffer_stake_pda,
+            validator_stake_pdas,
+        })
+    }
+}
+
+pub struct GetStakeDistributionInstructionData {
+    pub page: u8,
+}
+
+impl TryFrom<&[u8]> for GetStakeDistributionInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 1 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { page: data[0] })
+    }
+}
+
+/// Returns one page of the per-validator stake breakdown, covering the
+/// config's validator and every validator in the validator list.
+///
+/// Each page starts with a 16-byte header: `row_count: u32`,
+/// `page_count: u32` and `undelegated_lamports: u64`, the pool lamports not
+/// delegated to any validator. Up to `DISTRIBUTION_ROWS_PER_PAGE` 64-byte
+/// rows follow: `vote_account: [u8; 32]`, then `active`, `activating`,
+/// `deactivating` and `target` lamports as `u64`. Targets come from the
+/// config's delegation strategy over the validator list; with an empty list
+/// the config's validator is targeted for the whole pool. Stake in a
+/// validator's stake PDA is only broken down when the PDA is passed; it is
+/// left out of the rows otherwise.
+///
+/// Accounts expected:
+///
+/// 0. `[]` Config PDA
+/// 1. `[]` Validator list PDA (may be uninitialized)
+/// 2. `[]` Stake account main
+/// 3. `[]` Stake account reserve
+/// 4. `[]` Buffer stake PDA (may be uninitialized)
+/// 5. `[]` Validator stake PDAs, any number (optional)
+pub struct GetStakeDistribution<'a> {
+    pub accounts: GetStakeDistributionAccounts<'a>,
+    pub data: GetStakeDistributionInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for GetStakeDistribution<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: GetStakeDistributionAccounts::try_from(accounts)?,
+            data: GetStakeDistributionInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> GetStakeDistribution<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &25;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        let (expected_validator_list_pda, _) =
+            find_program_address(&[b"validator_list"], &crate::ID);
+        if expected_validator_list_pda != *self.accounts.validator_list_pda.key() {
+            return Err(PinocchioError::InvalidValidatorListPda.into());
+        }
+
+        let (expected_buffer_stake_pda, _) = find_program_address(&[b"buffer_stake"], &crate::ID);
+        if expected_buffer_stake_pda != *self.accounts.buffer_stake_pda.key() {
+            return Err(PinocchioError::InvalidBufferStakePda.into());
+        }
+
+        let mut rows = [DistributionRow::default(); MAX_ROWS];
+        rows[0].vote_account = config.validator_vote_pubkey;
+        let mut row_count = 1;
+
+        let total_lamports = pool_lamports(
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            config.validator_stake_lamports,
+        )?;
+
+        let validator_list_data = self.accounts.validator_list_pda.try_borrow_data()?;
+        if validator_list_data.is_empty() {
+            rows[0].target = total_lamports;
+        } else {
+            let entries = ValidatorList::load(&validator_list_data)?.entries();
+            let mut targets = [0u64; ValidatorList::CAPACITY];
+            config
+                .delegation_strategy()?
+                .targets(entries, total_lamports, &mut targets);
+
+            for (entry, target) in entries.iter().zip(targets) {
+                if entry.vote_account == rows[0].vote_account {
+                    rows[0].target = target;
+                    continue;
+                }
+                rows[row_count].vote_account = entry.vote_account;
+                rows[row_count].target = target;
+                row_count += 1;
+            }
+        }
+        drop(validator_list_data);
+        drop(config_data);
+
+        let epoch = Clock::get()?.epoch;
+        let mut undelegated_lamports = 0u64;
+
+        let stake_accounts = [
+            (self.accounts.stake_account_main, false),
+            (self.accounts.stake_account_reserve, false),
+            (self.accounts.buffer_stake_pda, false),
+        ]
+        .into_iter()
+        .chain(
+            self.accounts
+                .validator_stake_pdas
+                .iter()
+                .map(|account| (account, true)),
+        );
+
+        for (stake_account, is_validator_stake) in stake_accounts {
+            if stake_account.lamports() == 0 {
+                continue;
+            }
+
+            let breakdown = stake_account_breakdown(stake_account, epoch)?;
+            if is_validator_stake {
+                let voter = breakdown
+                    .voter
+                    .ok_or(PinocchioError::InvalidValidatorStakePda)?;
+                check_validator_stake(stake_account, &voter)?;
+            }
+            undelegated_lamports = undelegated_lamports.saturating_add(breakdown.undelegated);
+
+            let row = breakdown.voter.and_then(|voter| {
+                rows[..row_count]
+                    .iter_mut()
+                    .find(|row| row.vote_account == voter)
+            });
+            match row {
+                Some(row) => {
+                    row.active = row.active.saturating_add(breakdown.active);
+                    row.activating = row.activating.saturating_add(breakdown.activating);
+                    row.deactivating = row.deactivating.saturating_add(breakdown.deactivating);
+                }
+                // Stake on a validator outside the set is reported as undelegated.
+                None => {
+                    undelegated_lamports = undelegated_lamports
+                        .saturating_add(breakdown.active)
+                        .saturating_add(breakdown.activating)
+                        .saturating_add(breakdown.deactivating);
+                }
+            }
+        }
+
+        let page_count = row_count.div_ceil(DISTRIBUTION_ROWS_PER_PAGE);
+        let first_row = self.data.page as usize * DISTRIBUTION_ROWS_PER_PAGE;
+        if first_row >= row_count {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let page_rows = &rows[first_row..row_count.min(first_row + DISTRIBUTION_ROWS_PER_PAGE)];
+
+        let mut return_data =
+            [0u8; DISTRIBUTION_HEADER_LEN + DISTRIBUTION_ROW_LEN * DISTRIBUTION_ROWS_PER_PAGE];
+        return_data[0..4].copy_from_slice(&(row_count as u32).to_le_bytes());
+        return_data[4..8].copy_from_slice(&(page_count as u32).to_le_bytes());
+        return_data[8..16].copy_from_slice(&undelegated_lamports.to_le_bytes());
+
+        for (index, row) in page_rows.iter().enumerate() {
+            let offset = DISTRIBUTION_HEADER_LEN + index * DISTRIBUTION_ROW_LEN;
+            return_data[offset..offset + 32].copy_from_slice(&row.vote_account);
+            return_data[offset + 32..offset + 40].copy_from_slice(&row.active.to_le_bytes());
+            return_data[offset + 40..offset + 48].copy_from_slice(&row.activating.to_le_bytes());
+            return_data[offset + 48..offset + 56].copy_from_slice(&row.deactivating.to_le_bytes());
+            return_data[offset + 56..offset + 64].copy_from_slice(&row.target.to_le_bytes());
+        }
+
+        set_return_data(
+            &return_data[..DISTRIBUTION_HEADER_LEN + DISTRIBUTION_ROW_LEN * page_rows.len()],
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use pinocchio::{
+    account_info::AccountInfo, cpi::set_return_data, program_error::ProgramError,
+    pubkey::find_program_address,
+};
+use pinocchio_token::state::Mint;
+
+use crate::{
+    errors::PinocchioError,
+    instructions::helpers::{check_config_pda, managed_lamports},
+    state::{Config, PoolState},
+};
+
+/// Size of the report returned by `GetYieldReport`.
+pub const YIELD_REPORT_LEN: usize = 4 * 8 + 1;
+
+pub struct GetYieldReportAccounts<'a> {
+    pub config_pda: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GetYieldReportAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [config_pda, stake_account_main, stake_account_reserve, lst_mint, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            lst_mint,
+            pool_state_pda,
+        })
+    }
+}
+
+/// Splits the SOL backing LST into depositors' principal and the yield earned
+/// on it, so operators can report protocol yield without replaying history.
+///
+/// The return data is four little-endian `u64`s: `principal_lamports`,
+/// `yield_lamports`, `pool_lamports` and `lst_supply`, followed by a byte
+/// giving the LST mint's decimals. `principal_lamports` plus `yield_lamports`
+/// equals `pool_lamports` unless the pool is below its principal after a
+/// slash, in which case `yield_lamports` is zero.
+///
+/// Accounts expected:
+///
+/// 0. `[]` Config PDA
+/// 1. `[]` Stake account main
+/// 2. `[]` Stake account reserve
+/// 3. `[]` LST mint
+/// 4. `[]` Pool state PDA
+pub struct GetYieldReport<'a> {
+    pub accounts: GetYieldReportAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GetYieldReport<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            accounts: GetYieldReportAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> GetYieldReport<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &69;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+        let validator_stake_lamports = config.validator_stake_lamports;
+        drop(config_data);
+
+        let (expected_pool_state_pda, _) = find_program_address(&[b"pool_state"], &crate::ID);
+        if expected_pool_state_pda != *self.accounts.pool_state_pda.key()
+            || !self.accounts.pool_state_pda.is_owned_by(&crate::ID)
+        {
+            return Err(PinocchioError::InvalidPoolStatePda.into());
+        }
+
+        let mint = Mint::from_account_info(self.accounts.lst_mint)?;
+        let lst_supply = mint.supply();
+        let lst_decimals = mint.decimals();
+        drop(mint);
+
+        let pool_lamports = managed_lamports(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+
+        let pool_state_data = self.accounts.pool_state_pda.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+        let principal_lamports = pool_state.principal_at(lst_supply);
+        let yield_lamports = pool_state.yield_lamports(pool_lamports, lst_supply);
+        drop(pool_state_data);
+
+        let mut return_data = [0u8; YIELD_REPORT_LEN];
+        for (index, value) in [
+            principal_lamports,
+            yield_lamports,
+            pool_lamports,
+            lst_supply,
+        ]
+        .iter()
+        .enumerate()
+        {
+            return_data[index * 8..index * 8 + 8].copy_from_slice(&value.to_le_bytes());
+        }
+        return_data[YIELD_REPORT_LEN - 1] = lst_decimals;
+
+        set_return_data(&return_data);
+
+        Ok(())
+    }
+}
@@ -1,36 +1,518 @@
 use crate::errors::PinocchioError;
-use pinocchio::cpi::invoke_signed;
+use crate::ids::{
+    assert_vote_account, ASSOCIATED_TOKEN_PROGRAM_ID, STAKE_PROGRAM_ID, SYSTEM_PROGRAM_ID,
+    TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
+use crate::math::holding_rebate_fee_bps;
+use crate::stake_state::StakeState;
+use crate::state::{
+    AccountHeader, Changelog, Config, EpochTasks, ManagedChange, ParamChange, PoolState,
+    SplitHandoff, UnstakeOrder, UserStats, ValidatorList, VoteEscrow, WRAPPER_AUTHORITY_SEED,
+};
+use pinocchio::cpi::{get_return_data, invoke, invoke_signed};
 use pinocchio::instruction::{AccountMeta, Instruction, Seed, Signer};
-use pinocchio::pubkey::find_program_address;
-use pinocchio::sysvars::Sysvar;
+use pinocchio::pubkey::{create_program_address, find_program_address, Pubkey};
+use pinocchio::sysvars::{clock::Clock, Sysvar};
 use pinocchio::{
     account_info::AccountInfo, program_error::ProgramError, sysvars::rent::Rent, ProgramResult,
 };
 use pinocchio_associated_token_account::instructions::Create;
-use pinocchio_system::instructions::CreateAccount;
-use pinocchio_token::instructions::{InitializeAccount3, InitializeMint2};
-
-pub const TOKEN_2022_PROGRAM_ID: [u8; 32] = [
-    0x06, 0xdd, 0xf6, 0xe1, 0xee, 0x75, 0x8f, 0xde, 0x18, 0x42, 0x5d, 0xbc, 0xe4, 0x6c, 0xcd, 0xda,
-    0xb6, 0x1a, 0xfc, 0x4d, 0x83, 0xb9, 0x0d, 0x27, 0xfe, 0xbd, 0xf9, 0x28, 0xd8, 0xa1, 0x8b, 0xfc,
-];
+use pinocchio_system::instructions::{Allocate, Assign, CreateAccount, Transfer};
+use pinocchio_token::instructions::{CloseAccount, InitializeAccount3, InitializeMint2};
 
 const TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET: usize = 165;
 pub const TOKEN_2022_MINT_DISCRIMINATOR: u8 = 0x01;
 pub const TOKEN_2022_TOKEN_ACCOUNT_DISCRIMINATOR: u8 = 0x02;
 
-pub const STAKE_PROGRAM_ID: [u8; 32] = [
-    6, 161, 216, 23, 145, 55, 84, 42, 152, 52, 55, 189, 254, 42, 122, 178, 85, 127, 83, 92, 138,
-    120, 114, 43, 104, 164, 157, 192, 0, 0, 0, 0,
-];
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+/// Decimals of lamport amounts.
+pub const SOL_DECIMALS: u8 = 9;
+/// Decimals `Initialize` creates the LST mint with. Views read the mint's
+/// own decimals instead, so they stay right for pools on other mints.
+pub const LST_DECIMALS: u8 = 9;
+/// Size of the stake program's `StakeStateV2`. Only used where no existing
+/// stake account can be read; see `stake_account_space`.
+pub const DEFAULT_STAKE_ACCOUNT_SPACE: usize = 200;
+
+const VOTE_NODE_PUBKEY_OFFSET: usize = 4;
+
+/// `StakeAuthorize` variants of the stake program's `Authorize` instruction.
+pub const STAKE_AUTHORIZE_STAKER: u32 = 0;
+pub const STAKE_AUTHORIZE_WITHDRAWER: u32 = 1;
+
+/// Returns the rent-exempt reserve of a stake account. Initialized and
+/// delegated accounts carry it in their `Meta`; uninitialized accounts hold
+/// the rent minimum for their data length.
+pub fn stake_rent_exempt_reserve(account: &AccountInfo) -> Result<u64, ProgramError> {
+    let data = account.try_borrow_data()?;
+    let state = StakeState::parse(&data)?;
+
+    Ok(state.rent_exempt_reserve(uninitialized_rent_minimum(&state, data.len())?))
+}
 
-pub const VOTE_PROGRAM_ID: [u8; 32] = [
-    7, 97, 72, 29, 53, 116, 116, 187, 124, 77, 118, 36, 235, 211, 189, 179, 216, 53, 94, 115, 209,
-    16, 67, 252, 13, 163, 83, 128, 0, 0, 0, 0,
-];
+/// Rent minimum an uninitialized stake account of `data_len` bytes keeps.
+/// Other states carry their reserve, so the rent sysvar is only read here.
+fn uninitialized_rent_minimum(state: &StakeState, data_len: usize) -> Result<u64, ProgramError> {
+    match state {
+        StakeState::Uninitialized => Ok(Rent::get()?.minimum_balance(data_len)),
+        _ => Ok(0),
+    }
+}
 
-pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
-pub const STAKE_ACCOUNT_SPACE: usize = 200;
+/// Returns the (staker, withdrawer) authorities of a stake account, or `None`
+/// if it is not an initialized or delegated stake account. Fails with
+/// `InvalidAccountData` when the data is too short for its state.
+pub fn stake_authorities(account: &AccountInfo) -> Result<Option<(Pubkey, Pubkey)>, ProgramError> {
+    if !account.is_owned_by(&STAKE_PROGRAM_ID) {
+        return Ok(None);
+    }
+
+    let data = account.try_borrow_data()?;
+
+    Ok(StakeState::parse(&data)?
+        .meta()
+        .map(|meta| (meta.staker, meta.withdrawer)))
+}
+
+/// Returns whether a delegated stake account was activated in `epoch` or
+/// later, i.e. is still warming up. Accounts that are not delegated are not
+/// activating.
+pub fn stake_is_activating(account: &AccountInfo, epoch: u64) -> Result<bool, ProgramError> {
+    #[cfg(feature = "test-hooks")]
+    if let Some(activating) = crate::test_hooks::forced_activating(account)? {
+        return Ok(activating);
+    }
+
+    let data = account.try_borrow_data()?;
+
+    Ok(StakeState::parse(&data).is_ok_and(|state| state.is_activating(epoch)))
+}
+
+/// Lamports the undelegated reserve can pay out, its balance beyond the rent
+/// minimum. Fails with `ReserveDelegated` once the reserve holds initialized
+/// or delegated stake, and with `missing` while it has no stake state, before
+/// `AdoptReserve` or after a merge drained it.
+pub fn reserve_spare_lamports(
+    reserve: &AccountInfo,
+    missing: PinocchioError,
+) -> Result<u64, ProgramError> {
+    let data = reserve.try_borrow_data()?;
+    match StakeState::parse(&data)? {
+        StakeState::Closed => Err(missing.into()),
+        StakeState::Uninitialized => Ok(reserve
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(data.len()))),
+        _ => Err(PinocchioError::ReserveDelegated.into()),
+    }
+}
+
+/// Checks that `outgoing` is the outgoing reserve PDA (`b"stake_outgoing"`),
+/// which holds a delegated reserve's stake while it cools down after
+/// `UpdateConfig` changed the validator, returning its bump.
+pub fn check_outgoing_reserve(outgoing: &AccountInfo) -> Result<u8, ProgramError> {
+    let (expected_outgoing, outgoing_bump) = find_program_address(&[b"stake_outgoing"], &crate::ID);
+    if expected_outgoing != *outgoing.key() {
+        return Err(PinocchioError::InvalidOutgoingReservePda.into());
+    }
+
+    Ok(outgoing_bump)
+}
+
+/// Checks that `user_stats_pda` is `user`'s user stats PDA
+/// (`b"user_stats"`, user), returning its bump.
+pub fn check_user_stats(
+    user_stats_pda: &AccountInfo,
+    user: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    let (expected_user_stats, user_stats_bump) =
+        find_program_address(&[b"user_stats", user.key()], &crate::ID);
+    if expected_user_stats != *user_stats_pda.key() {
+        return Err(PinocchioError::InvalidUserStatsPda.into());
+    }
+
+    Ok(user_stats_bump)
+}
+
+/// The config's withdrawal fee for `user` in `epoch`, less the holding
+/// rebate for the epochs since the first deposit recorded in their user
+/// stats PDA. The full fee when no user stats PDA is passed.
+pub fn rebated_withdraw_fee_bps(
+    config: &Config,
+    user_stats_pda: Option<&AccountInfo>,
+    user: &AccountInfo,
+    epoch: u64,
+) -> Result<u16, ProgramError> {
+    let Some(user_stats_pda) = user_stats_pda else {
+        return Ok(config.withdraw_fee_bps);
+    };
+
+    check_user_stats(user_stats_pda, user)?;
+    if !user_stats_pda.is_owned_by(&crate::ID) {
+        return Err(PinocchioError::InvalidUserStatsPda.into());
+    }
+    let first_deposit_epoch =
+        UserStats::load(&user_stats_pda.try_borrow_data()?)?.first_deposit_epoch;
+
+    Ok(holding_rebate_fee_bps(
+        config.withdraw_fee_bps,
+        config.holding_rebate_bps,
+        config.holding_rebate_epochs,
+        epoch.saturating_sub(first_deposit_epoch),
+    ))
+}
+
+/// A stake account's lamports (excluding rent) by delegation status.
+#[derive(Default)]
+pub struct StakeBreakdown {
+    /// Vote account the stake is delegated to, if any.
+    pub voter: Option<Pubkey>,
+    pub active: u64,
+    pub activating: u64,
+    pub deactivating: u64,
+    /// Lamports not delegated to any validator, including fully deactivated
+    /// stake and lamports above the delegated amount.
+    pub undelegated: u64,
+}
+
+/// Splits a stake account's lamports by delegation status in `epoch`. Stake
+/// delegated or deactivated in `epoch` or later is counted as still warming
+/// up or cooling down; the cluster's warmup and cooldown rate limits are not
+/// modeled.
+pub fn stake_account_breakdown(
+    account: &AccountInfo,
+    epoch: u64,
+) -> Result<StakeBreakdown, ProgramError> {
+    let lamports = stake_lamports_excluding_rent(account)?;
+    let activating = stake_is_activating(account, epoch)?;
+
+    let data = account.try_borrow_data()?;
+
+    let Some(delegation) = StakeState::parse(&data)
+        .ok()
+        .and_then(|state| state.delegation().copied())
+    else {
+        return Ok(StakeBreakdown {
+            undelegated: lamports,
+            ..StakeBreakdown::default()
+        });
+    };
+
+    let delegated = delegation.stake.min(lamports);
+    let mut breakdown = StakeBreakdown {
+        voter: Some(delegation.voter),
+        undelegated: lamports - delegated,
+        ..StakeBreakdown::default()
+    };
+
+    if delegation.deactivation_epoch != u64::MAX {
+        if delegation.deactivation_epoch >= epoch {
+            breakdown.deactivating = delegated;
+        } else {
+            breakdown.undelegated += delegated;
+        }
+    } else if activating {
+        breakdown.activating = delegated;
+    } else {
+        breakdown.active = delegated;
+    }
+
+    Ok(breakdown)
+}
+
+/// Returns the validator identity (`node_pubkey`) of a vote account, which
+/// follows the version tag in every `VoteStateVersions` layout.
+pub fn vote_account_node_pubkey(account: &AccountInfo) -> Result<Pubkey, ProgramError> {
+    assert_vote_account(account)?;
+
+    let data = account.try_borrow_data()?;
+
+    if data.len() < VOTE_NODE_PUBKEY_OFFSET + 32 {
+        return Err(PinocchioError::InvalidValidatorVoteAccount.into());
+    }
+
+    Ok(data[VOTE_NODE_PUBKEY_OFFSET..VOTE_NODE_PUBKEY_OFFSET + 32]
+        .try_into()
+        .unwrap())
+}
+
+/// Vote account figures read by `vote_account_summary`.
+pub struct VoteSummary {
+    pub commission: u8,
+    pub last_vote_slot: u64,
+    pub root_slot: u64,
+    pub last_epoch: u64,
+    pub last_epoch_credits: u64,
+    pub window_epochs: u8,
+    pub window_credits: u64,
+}
+
+const VOTE_COMMISSION_OFFSET: usize = 68;
+const VOTE_PRIOR_VOTERS_LEN: usize = 32 * (32 + 8 + 8) + 8 + 1;
+
+/// Bounds-checked little-endian read cursor over vote account data.
+struct VoteReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl VoteReader<'_> {
+    fn skip(&mut self, len: usize) -> Result<(), ProgramError> {
+        self.offset = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or(PinocchioError::InvalidValidatorVoteAccount)?;
+        Ok(())
+    }
+
+    fn u8(&mut self) -> Result<u8, ProgramError> {
+        let value = *self
+            .data
+            .get(self.offset)
+            .ok_or(PinocchioError::InvalidValidatorVoteAccount)?;
+        self.offset += 1;
+        Ok(value)
+    }
+
+    fn u64(&mut self) -> Result<u64, ProgramError> {
+        let bytes = self
+            .data
+            .get(self.offset..self.offset + 8)
+            .ok_or(PinocchioError::InvalidValidatorVoteAccount)?;
+        self.offset += 8;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn len(&mut self, entry_len: usize) -> Result<usize, ProgramError> {
+        let len = self.u64()? as usize;
+        if len.saturating_mul(entry_len) > self.data.len() - self.offset {
+            return Err(PinocchioError::InvalidValidatorVoteAccount.into());
+        }
+        Ok(len)
+    }
+}
+
+/// Reads commission, vote and credit figures from a vote account, summing
+/// credits over its last `window` epochs. Handles the `V1_14_11` (tag 1) and
+/// current (tag 2) layouts, which differ only in the size of a vote entry.
+pub fn vote_account_summary(
+    account: &AccountInfo,
+    window: usize,
+) -> Result<VoteSummary, ProgramError> {
+    assert_vote_account(account)?;
+
+    let data = account.try_borrow_data()?;
+    let mut reader = VoteReader {
+        data: &data,
+        offset: 0,
+    };
+
+    // Current votes carry a latency byte ahead of the (slot, confirmations)
+    // lockout.
+    let tag = data
+        .get(0..4)
+        .ok_or(PinocchioError::InvalidValidatorVoteAccount)?;
+    let vote_len = match u32::from_le_bytes(tag.try_into().unwrap()) {
+        1 => 12,
+        2 => 13,
+        _ => return Err(PinocchioError::InvalidValidatorVoteAccount.into()),
+    };
+    let vote_slot_offset = vote_len - 12;
+
+    reader.offset = VOTE_COMMISSION_OFFSET;
+    let commission = reader.u8()?;
+
+    let votes = reader.len(vote_len)?;
+    let mut last_vote_slot = 0;
+    if votes > 0 {
+        reader.skip((votes - 1) * vote_len + vote_slot_offset)?;
+        last_vote_slot = reader.u64()?;
+        reader.skip(4)?;
+    }
+
+    let root_slot = match reader.u8()? {
+        0 => 0,
+        1 => reader.u64()?,
+        _ => return Err(PinocchioError::InvalidValidatorVoteAccount.into()),
+    };
+
+    let authorized_voters = reader.len(40)?;
+    reader.skip(authorized_voters * 40)?;
+    reader.skip(VOTE_PRIOR_VOTERS_LEN)?;
+
+    let epoch_credits = reader.len(24)?;
+    let window_epochs = epoch_credits.min(window);
+    reader.skip((epoch_credits - window_epochs) * 24)?;
+
+    let mut summary = VoteSummary {
+        commission,
+        last_vote_slot,
+        root_slot,
+        last_epoch: 0,
+        last_epoch_credits: 0,
+        window_epochs: window_epochs as u8,
+        window_credits: 0,
+    };
+    for _ in 0..window_epochs {
+        let epoch = reader.u64()?;
+        let credits = reader.u64()?;
+        let prev_credits = reader.u64()?;
+        let earned = credits.saturating_sub(prev_credits);
+
+        summary.last_epoch = epoch;
+        summary.last_epoch_credits = earned;
+        summary.window_credits = summary.window_credits.saturating_add(earned);
+    }
+
+    Ok(summary)
+}
+
+/// Data length for a new stake account that will receive stake from
+/// `source`. The stake program requires split peers to share a layout, so the
+/// source's length is followed if the stake program ever grows its state.
+pub fn stake_account_space(source: &AccountInfo) -> usize {
+    match source.data_len() {
+        0 => DEFAULT_STAKE_ACCOUNT_SPACE,
+        space => space,
+    }
+}
+
+/// Lamports of a stake account that count towards the pool: everything above
+/// the rent-exempt reserve its parsed `Meta` records. Activating, active and
+/// deactivating stake all count, as do undelegated lamports.
+pub fn stake_lamports_excluding_rent(account: &AccountInfo) -> Result<u64, ProgramError> {
+    if account.lamports() == 0 {
+        return Ok(0);
+    }
+
+    let data = account.try_borrow_data()?;
+    let state = StakeState::parse(&data)?;
+    let rent_exempt_reserve =
+        state.rent_exempt_reserve(uninitialized_rent_minimum(&state, data.len())?);
+
+    Ok(account.lamports().saturating_sub(rent_exempt_reserve))
+}
+
+/// Total lamports backing the LST supply across the main and reserve stake
+/// accounts, excluding rent-exempt reserves, plus the config's
+/// `validator_stake_lamports` held in validator stake accounts.
+pub fn pool_lamports(
+    stake_account_main: &AccountInfo,
+    stake_account_reserve: &AccountInfo,
+    validator_stake_lamports: u64,
+) -> Result<u64, ProgramError> {
+    stake_lamports_excluding_rent(stake_account_main)?
+        .checked_add(stake_lamports_excluding_rent(stake_account_reserve)?)
+        .and_then(|lamports| lamports.checked_add(validator_stake_lamports))
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+/// Lamports delegated in a stake account: its delegation's stake, or zero
+/// when it is not delegated. Epoch rewards are added to the delegation as
+/// well as to the account's lamports; lamports sent to the account are not.
+pub fn staked_lamports(account: &AccountInfo) -> Result<u64, ProgramError> {
+    let data = account.try_borrow_data()?;
+    Ok(StakeState::parse(&data)?
+        .delegation()
+        .map_or(0, |delegation| delegation.stake))
+}
+
+/// Stake the pool earns rewards on: main's and the reserve's delegations
+/// plus the config's `validator_stake_lamports`.
+pub fn pool_stake(
+    stake_account_main: &AccountInfo,
+    stake_account_reserve: &AccountInfo,
+    validator_stake_lamports: u64,
+) -> Result<u64, ProgramError> {
+    staked_lamports(stake_account_main)?
+        .checked_add(staked_lamports(stake_account_reserve)?)
+        .and_then(|lamports| lamports.checked_add(validator_stake_lamports))
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+/// The pool state's `total_managed_lamports` with the rewards the pool's
+/// stake earned since it was last recorded: the lamports backing the LST
+/// supply, which every mint and burn prices against.
+pub fn managed_lamports(
+    pool_state_pda: &AccountInfo,
+    stake_account_main: &AccountInfo,
+    stake_account_reserve: &AccountInfo,
+    validator_stake_lamports: u64,
+) -> Result<u64, ProgramError> {
+    check_pool_state_pda(pool_state_pda)?;
+    let stake_lamports = pool_stake(
+        stake_account_main,
+        stake_account_reserve,
+        validator_stake_lamports,
+    )?;
+
+    PoolState::load(&pool_state_pda.try_borrow_data()?)?.managed_lamports(stake_lamports)
+}
+
+/// `managed_lamports`, crediting the rewards to the pool state. Instructions
+/// that move the pool's stake call it before the move and
+/// `record_pool_stake` after, so rewards are credited and the move is not.
+pub fn accrue_rewards(
+    pool_state_pda: &AccountInfo,
+    stake_account_main: &AccountInfo,
+    stake_account_reserve: &AccountInfo,
+    validator_stake_lamports: u64,
+) -> Result<u64, ProgramError> {
+    check_pool_state_pda(pool_state_pda)?;
+    let stake_lamports = pool_stake(
+        stake_account_main,
+        stake_account_reserve,
+        validator_stake_lamports,
+    )?;
+
+    PoolState::load_mut(&mut pool_state_pda.try_borrow_mut_data()?)?.accrue_rewards(stake_lamports)
+}
+
+/// Records the pool's stake after an instruction moved it, so the move is
+/// not credited as rewards.
+pub fn record_pool_stake(
+    pool_state_pda: &AccountInfo,
+    stake_account_main: &AccountInfo,
+    stake_account_reserve: &AccountInfo,
+    validator_stake_lamports: u64,
+) -> ProgramResult {
+    check_pool_state_pda(pool_state_pda)?;
+    let stake_lamports = pool_stake(
+        stake_account_main,
+        stake_account_reserve,
+        validator_stake_lamports,
+    )?;
+
+    let mut pool_state_data = pool_state_pda.try_borrow_mut_data()?;
+    PoolState::load_mut(&mut pool_state_data)?.recorded_stake_lamports = stake_lamports;
+    Ok(())
+}
+
+/// Moves the pool state's `total_managed_lamports` by lamports an
+/// instruction deposited into or paid out of the pool.
+pub fn record_managed_change(pool_state_pda: &AccountInfo, change: ManagedChange) -> ProgramResult {
+    check_pool_state_pda(pool_state_pda)?;
+
+    let mut pool_state_data = pool_state_pda.try_borrow_mut_data()?;
+    PoolState::load_mut(&mut pool_state_data)?.record_managed_change(change)
+}
+
+/// Instruction data of instructions that take none. Any byte after the
+/// discriminator is rejected, as every other parser rejects trailing bytes, so
+/// a field added in a later version never reaches an older program unread.
+pub struct EmptyInstructionData;
+
+impl TryFrom<&[u8]> for EmptyInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if !data.is_empty() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self)
+    }
+}
 
 pub trait AccountCheck {
     fn check(account: &AccountInfo) -> Result<(), ProgramError>;
@@ -51,7 +533,7 @@ pub struct SystemAccount;
 
 impl AccountCheck for SystemAccount {
     fn check(account: &AccountInfo) -> Result<(), ProgramError> {
-        if !account.is_owned_by(&pinocchio_system::ID) {
+        if !account.is_owned_by(&SYSTEM_PROGRAM_ID) {
             return Err(PinocchioError::InvalidOwner.into());
         }
 
@@ -63,7 +545,7 @@ pub struct MintAccount;
 
 impl AccountCheck for MintAccount {
     fn check(account: &AccountInfo) -> Result<(), ProgramError> {
-        if !account.is_owned_by(&pinocchio_token::ID) {
+        if !account.is_owned_by(&TOKEN_PROGRAM_ID) {
             return Err(PinocchioError::InvalidOwner.into());
         }
 
@@ -107,7 +589,7 @@ impl MintInit for MintAccount {
             to: account,
             lamports,
             space: pinocchio_token::state::Mint::LEN as u64,
-            owner: &pinocchio_token::ID,
+            owner: &TOKEN_PROGRAM_ID,
         }
         .invoke()?;
 
@@ -138,7 +620,7 @@ pub struct TokenAccount;
 
 impl AccountCheck for TokenAccount {
     fn check(account: &AccountInfo) -> Result<(), ProgramError> {
-        if !account.is_owned_by(&pinocchio_token::ID) {
+        if !account.is_owned_by(&TOKEN_PROGRAM_ID) {
             return Err(PinocchioError::InvalidOwner.into());
         }
 
@@ -182,7 +664,7 @@ impl AccountInit for TokenAccount {
             to: account,
             lamports,
             space: pinocchio_token::state::TokenAccount::LEN as u64,
-            owner: &pinocchio_token::ID,
+            owner: &TOKEN_PROGRAM_ID,
         }
         .invoke()?;
 
@@ -340,7 +822,7 @@ pub struct MintInterface;
 impl AccountCheck for MintInterface {
     fn check(account: &AccountInfo) -> Result<(), ProgramError> {
         if !account.is_owned_by(&TOKEN_2022_PROGRAM_ID) {
-            if !account.is_owned_by(&pinocchio_token::ID) {
+            if !account.is_owned_by(&TOKEN_PROGRAM_ID) {
                 return Err(PinocchioError::InvalidOwner.into());
             } else {
                 if account.data_len().ne(&pinocchio_token::state::Mint::LEN) {
@@ -370,7 +852,7 @@ pub struct TokenAccountInterface;
 impl AccountCheck for TokenAccountInterface {
     fn check(account: &AccountInfo) -> Result<(), ProgramError> {
         if !account.is_owned_by(&TOKEN_2022_PROGRAM_ID) {
-            if !account.is_owned_by(&pinocchio_token::ID) {
+            if !account.is_owned_by(&TOKEN_PROGRAM_ID) {
                 return Err(PinocchioError::InvalidOwner.into());
             } else {
                 if account
@@ -421,7 +903,7 @@ impl AssociatedTokenAccountCheck for AssociatedTokenAccount {
 
         if find_program_address(
             &[authority.key(), token_program.key(), mint.key()],
-            &pinocchio_associated_token_account::ID,
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
         )
         .0
         .ne(account.key())
@@ -500,7 +982,8 @@ impl AccountCheck for ProgramAccount {
 }
 
 pub trait ProgramAccountInit {
-    fn init<'a, T: Sized>(
+    /// Creates the PDA `account` for state `T` and writes `T`'s header.
+    fn init<'a, T: AccountHeader>(
         payer: &AccountInfo,
         account: &AccountInfo,
         seeds: &[Seed<'a>],
@@ -509,7 +992,7 @@ pub trait ProgramAccountInit {
 }
 
 impl ProgramAccountInit for ProgramAccount {
-    fn init<'a, T: Sized>(
+    fn init<'a, T: AccountHeader>(
         payer: &AccountInfo,
         account: &AccountInfo,
         seeds: &[Seed<'a>],
@@ -517,16 +1000,8 @@ impl ProgramAccountInit for ProgramAccount {
     ) -> ProgramResult {
         let lamports = Rent::get()?.minimum_balance(space);
 
-        let signer = [Signer::from(seeds)];
-
-        CreateAccount {
-            from: payer,
-            to: account,
-            lamports,
-            space: space as u64,
-            owner: &crate::ID,
-        }
-        .invoke_signed(&signer)?;
+        create_pda_account(payer, account, seeds, lamports, space, &crate::ID)?;
+        T::write_header(&mut account.try_borrow_mut_data()?);
 
         Ok(())
     }
@@ -549,11 +1024,58 @@ impl AccountClose for ProgramAccount {
     }
 }
 
+/// Creates the PDA `account` owned by `owner`, funded with `lamports` from
+/// `payer`. Anyone can send lamports to a PDA address before the program
+/// creates it, which makes `CreateAccount` fail. A pre-funded account is
+/// adopted instead: the payer still transfers `lamports`, and the account is
+/// allocated and assigned in place, so whatever was sent ahead stays in it.
+pub fn create_pda_account(
+    payer: &AccountInfo,
+    account: &AccountInfo,
+    seeds: &[Seed],
+    lamports: u64,
+    space: usize,
+    owner: &Pubkey,
+) -> ProgramResult {
+    let signer = [Signer::from(seeds)];
+
+    if account.lamports() == 0 {
+        return CreateAccount {
+            from: payer,
+            to: account,
+            lamports,
+            space: space as u64,
+            owner,
+        }
+        .invoke_signed(&signer);
+    }
+
+    if !account.is_owned_by(&SYSTEM_PROGRAM_ID) || !account.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    Transfer {
+        from: payer,
+        to: account,
+        lamports,
+    }
+    .invoke()?;
+
+    Allocate {
+        account,
+        space: space as u64,
+    }
+    .invoke_signed(&signer)?;
+
+    Assign { account, owner }.invoke_signed(&signer)
+}
+
 pub trait StakeAccountCreate {
     fn stake_account_create(
         payer: &AccountInfo,
         account: &AccountInfo,
         seeds: &[Seed],
+        space: usize,
     ) -> ProgramResult;
 }
 
@@ -562,21 +1084,18 @@ impl StakeAccountCreate for ProgramAccount {
         payer: &AccountInfo,
         account: &AccountInfo,
         seeds: &[Seed],
+        space: usize,
     ) -> ProgramResult {
-        let lamports = Rent::get()?.minimum_balance(STAKE_ACCOUNT_SPACE);
-
-        let signer = [Signer::from(seeds)];
-
-        CreateAccount {
-            from: payer,
-            to: account,
-            lamports: lamports + LAMPORTS_PER_SOL,
-            space: STAKE_ACCOUNT_SPACE as u64,
-            owner: &STAKE_PROGRAM_ID,
-        }
-        .invoke_signed(&signer)?;
+        let lamports = Rent::get()?.minimum_balance(space);
 
-        Ok(())
+        create_pda_account(
+            payer,
+            account,
+            seeds,
+            lamports + LAMPORTS_PER_SOL,
+            space,
+            &STAKE_PROGRAM_ID,
+        )
     }
 }
 
@@ -800,6 +1319,55 @@ impl StakeAccountDeactivate for ProgramAccount {
     }
 }
 
+pub trait StakeAccountAuthorize {
+    /// Moves `account`'s staker or withdrawer authority, per
+    /// `stake_authorize`, from `authority` to `new_authority`. Empty `seeds`
+    /// mean `authority` signed the outer transaction.
+    fn authorize_stake_account(
+        account: &AccountInfo,
+        clock_sysvar: &AccountInfo,
+        authority: &AccountInfo,
+        new_authority: &Pubkey,
+        stake_authorize: u32,
+        seeds: &[Seed],
+    ) -> ProgramResult;
+}
+
+impl StakeAccountAuthorize for ProgramAccount {
+    fn authorize_stake_account(
+        account: &AccountInfo,
+        clock_sysvar: &AccountInfo,
+        authority: &AccountInfo,
+        new_authority: &Pubkey,
+        stake_authorize: u32,
+        seeds: &[Seed],
+    ) -> ProgramResult {
+        let mut authorize_data = Vec::from(1u32.to_le_bytes());
+        authorize_data.extend_from_slice(new_authority);
+        authorize_data.extend_from_slice(&stake_authorize.to_le_bytes());
+
+        let authorize_ix = Instruction {
+            program_id: &STAKE_PROGRAM_ID,
+            data: &authorize_data,
+            accounts: &[
+                account.into(),
+                clock_sysvar.into(),
+                AccountMeta::readonly_signer(authority.key()),
+            ],
+        };
+
+        if seeds.is_empty() {
+            return invoke(&authorize_ix, &[account, clock_sysvar, authority]);
+        }
+
+        invoke_signed(
+            &authorize_ix,
+            &[account, clock_sysvar, authority],
+            &[Signer::from(seeds)],
+        )
+    }
+}
+
 pub trait StakeAccountWithdraw {
     fn withdraw_stake_account(
         account_to_withdraw_from: &AccountInfo,
@@ -809,6 +1377,16 @@ pub trait StakeAccountWithdraw {
         withdraw_authority: &AccountInfo,
         seeds: &[Seed],
     ) -> ProgramResult;
+
+    fn withdraw_stake_account_lamports(
+        account_to_withdraw_from: &AccountInfo,
+        withdrawer: &AccountInfo,
+        clock_sysvar: &AccountInfo,
+        history_sysvar: &AccountInfo,
+        withdraw_authority: &AccountInfo,
+        lamports: u64,
+        seeds: &[Seed],
+    ) -> ProgramResult;
 }
 
 impl StakeAccountWithdraw for ProgramAccount {
@@ -819,11 +1397,29 @@ impl StakeAccountWithdraw for ProgramAccount {
         history_sysvar: &AccountInfo,
         withdraw_authority: &AccountInfo,
         seeds: &[Seed],
+    ) -> ProgramResult {
+        Self::withdraw_stake_account_lamports(
+            account_to_withdraw_from,
+            withdrawer,
+            clock_sysvar,
+            history_sysvar,
+            withdraw_authority,
+            account_to_withdraw_from.lamports(),
+            seeds,
+        )
+    }
+
+    fn withdraw_stake_account_lamports(
+        account_to_withdraw_from: &AccountInfo,
+        withdrawer: &AccountInfo,
+        clock_sysvar: &AccountInfo,
+        history_sysvar: &AccountInfo,
+        withdraw_authority: &AccountInfo,
+        lamports: u64,
+        seeds: &[Seed],
     ) -> ProgramResult {
         let mut withdraw_instruction_data = Vec::from(4u32.to_le_bytes());
-        let lamports_on_account_to_withdraw_from = account_to_withdraw_from.lamports();
-        withdraw_instruction_data
-            .extend_from_slice(&lamports_on_account_to_withdraw_from.to_le_bytes());
+        withdraw_instruction_data.extend_from_slice(&lamports.to_le_bytes());
 
         let withdraw_ix = Instruction {
             program_id: &STAKE_PROGRAM_ID,
@@ -852,3 +1448,633 @@ impl StakeAccountWithdraw for ProgramAccount {
         Ok(())
     }
 }
+
+/// Marks `task` complete for the current epoch in the epoch tasks PDA. Fails
+/// with `PrerequisiteCrankMissing` while a step that must precede `task` is
+/// still pending this epoch.
+pub fn mark_epoch_task(epoch_tasks_pda: &AccountInfo, task: u8) -> ProgramResult {
+    let (expected_epoch_tasks_pda, _) = find_program_address(&[b"epoch_tasks"], &crate::ID);
+    if expected_epoch_tasks_pda != *epoch_tasks_pda.key()
+        || !epoch_tasks_pda.is_owned_by(&crate::ID)
+    {
+        return Err(PinocchioError::InvalidEpochTasksPda.into());
+    }
+
+    let epoch = Clock::get()?.epoch;
+    let mut epoch_tasks_data = epoch_tasks_pda.try_borrow_mut_data()?;
+    let epoch_tasks = EpochTasks::load_mut(&mut epoch_tasks_data)?;
+
+    if epoch_tasks.missing_prerequisites(epoch, task) != 0 {
+        return Err(PinocchioError::PrerequisiteCrankMissing.into());
+    }
+
+    epoch_tasks.mark(epoch, task);
+
+    Ok(())
+}
+
+/// Pays `crank_tip_lamports` from the fee vault PDA to `cranker`, the
+/// signer who sent a successful crank. The tip is skipped rather than failed
+/// while the vault cannot pay it and stay rent exempt, so a crank never
+/// depends on the vault's balance.
+pub fn pay_crank_tip(
+    crank_tip_lamports: u64,
+    cranker: &AccountInfo,
+    fee_vault_pda: &AccountInfo,
+) -> ProgramResult {
+    if !cranker.is_signer() {
+        return Err(PinocchioError::NotSigner.into());
+    }
+
+    let (expected_fee_vault_pda, fee_vault_bump) =
+        find_program_address(&[b"fee_vault"], &crate::ID);
+    if expected_fee_vault_pda != *fee_vault_pda.key() {
+        return Err(PinocchioError::InvalidFeeVaultPda.into());
+    }
+
+    let spare = fee_vault_pda
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    if crank_tip_lamports == 0 || crank_tip_lamports > spare {
+        return Ok(());
+    }
+
+    let fee_vault_bump_binding = [fee_vault_bump];
+    let fee_vault_seeds = &[
+        Seed::from(b"fee_vault"),
+        Seed::from(&fee_vault_bump_binding),
+    ];
+    Transfer {
+        from: fee_vault_pda,
+        to: cranker,
+        lamports: crank_tip_lamports,
+    }
+    .invoke_signed(&[Signer::from(fee_vault_seeds)])
+}
+
+/// Returns the message `signer` signed in an ed25519 program instruction
+/// with `data`, so a program can trust a message only after the runtime
+/// verified its signature. Only single-signature instructions that keep the
+/// key, signature and message in their own data are accepted.
+pub fn ed25519_signed_message<'a>(
+    data: &'a [u8],
+    signer: &[u8; 32],
+) -> Result<&'a [u8], ProgramError> {
+    const OFFSETS_START: usize = 2;
+    const OFFSETS_LEN: usize = 14;
+    const THIS_INSTRUCTION: u16 = u16::MAX;
+
+    let read_u16 = |offset: usize| -> Result<u16, ProgramError> {
+        data.get(offset..offset + 2)
+            .map(|bytes| u16::from_le_bytes(bytes.try_into().unwrap()))
+            .ok_or(ProgramError::InvalidInstructionData)
+    };
+
+    if data.first() != Some(&1) || data.len() < OFFSETS_START + OFFSETS_LEN {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let public_key_offset = read_u16(OFFSETS_START + 4)? as usize;
+    let message_offset = read_u16(OFFSETS_START + 8)? as usize;
+    let message_len = read_u16(OFFSETS_START + 10)? as usize;
+    for instruction_index_offset in [2, 6, 12] {
+        if read_u16(OFFSETS_START + instruction_index_offset)? != THIS_INSTRUCTION {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+    }
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    if public_key != signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    data.get(message_offset..message_offset + message_len)
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+/// Fails with `InstructionDisabled` when the admin has switched off
+/// `discriminator`, reading the bitmask from `config_pda`, which must be the
+/// config PDA.
+pub fn check_instruction_enabled(discriminator: u8, config_pda: &AccountInfo) -> ProgramResult {
+    check_config_pda(config_pda)?;
+
+    let config_data = config_pda.try_borrow_data()?;
+    if Config::load(&config_data)?.is_instruction_disabled(discriminator) {
+        return Err(PinocchioError::InstructionDisabled.into());
+    }
+
+    Ok(())
+}
+
+/// Appends an admin parameter change to the changelog PDA. `parameter` is the
+/// setter's `DISCRIMINATOR`, and the old and new values are the fields of its
+/// instruction data, concatenated and zero-padded to 32 bytes.
+pub fn record_param_change(
+    changelog_pda: &AccountInfo,
+    authority: &AccountInfo,
+    parameter: u8,
+    old_value: &[&[u8]],
+    new_value: &[&[u8]],
+) -> ProgramResult {
+    let (expected_changelog_pda, _) = find_program_address(&[b"changelog"], &crate::ID);
+    if expected_changelog_pda != *changelog_pda.key() || !changelog_pda.is_owned_by(&crate::ID) {
+        return Err(PinocchioError::InvalidChangelogPda.into());
+    }
+
+    let change = ParamChange {
+        parameter,
+        old_value: param_value(old_value)?,
+        new_value: param_value(new_value)?,
+        slot: Clock::get()?.slot,
+        authority: *authority.key(),
+    };
+
+    let mut changelog_data = changelog_pda.try_borrow_mut_data()?;
+    Changelog::load_mut(&mut changelog_data)?.push(change);
+
+    Ok(())
+}
+
+fn param_value(fields: &[&[u8]]) -> Result<[u8; 32], ProgramError> {
+    let mut value = [0u8; 32];
+    let mut offset = 0;
+    for field in fields {
+        value
+            .get_mut(offset..offset + field.len())
+            .ok_or(ProgramError::InvalidArgument)?
+            .copy_from_slice(field);
+        offset += field.len();
+    }
+    Ok(value)
+}
+
+/// Counts `lamports` of deposits against this epoch's cap of `cap_lamports`
+/// (zero for no cap) in the pool state PDA and returns how many fit. With
+/// `partial` the lamports that fit are taken and the rest is left to the
+/// caller, otherwise anything over the cap fails with `DepositCapExceeded`.
+pub fn record_inflow(
+    pool_state_pda: &AccountInfo,
+    cap_lamports: u64,
+    lamports: u64,
+    partial: bool,
+) -> Result<u64, ProgramError> {
+    let (expected_pool_state_pda, _) = find_program_address(&[b"pool_state"], &crate::ID);
+    if expected_pool_state_pda != *pool_state_pda.key() || !pool_state_pda.is_owned_by(&crate::ID) {
+        return Err(PinocchioError::InvalidPoolStatePda.into());
+    }
+
+    let epoch = Clock::get()?.epoch;
+    let mut pool_state_data = pool_state_pda.try_borrow_mut_data()?;
+    let pool_state = PoolState::load_mut(&mut pool_state_data)?;
+
+    let room = pool_state.inflow_room(epoch, cap_lamports);
+    if room < lamports && !partial {
+        return Err(PinocchioError::DepositCapExceeded.into());
+    }
+
+    let accepted = lamports.min(room);
+    pool_state.record_inflow(epoch, accepted);
+
+    Ok(accepted)
+}
+
+/// Returns the `(pool_lamports, lst_supply)` rate `CrankUpdateRate` cached
+/// in the config this epoch. Every instruction that mints or burns LST
+/// prices against it, falling back to the managed lamports before the crank has
+/// run, so lamports landing mid-epoch never move the price of one trade and
+/// not another. The live fallback fails with `ValidatorStakeStale` until the
+/// validator stake is recorded this epoch, see `check_validator_stake_current`.
+pub fn epoch_rate(config: &Config) -> Result<Option<(u64, u64)>, ProgramError> {
+    let epoch = Clock::get()?.epoch;
+    let cached_rate = config.cached_rate(epoch);
+    if cached_rate.is_none() {
+        check_validator_stake_current(config, epoch)?;
+    }
+    Ok(cached_rate)
+}
+
+/// Fails with `ValidatorStakeStale` unless the config's
+/// `validator_stake_lamports` is current for `epoch`: `CrankValidatorStake`
+/// must record every staked validator each epoch before the pool's lamports
+/// price LST.
+pub fn check_validator_stake_current(config: &Config, epoch: u64) -> ProgramResult {
+    if !config.validator_stake_current(epoch) {
+        return Err(PinocchioError::ValidatorStakeStale.into());
+    }
+    Ok(())
+}
+
+/// Fails with `PoolCapExceeded` when depositing `lamports` would push the
+/// pool's `pool_lamports` past the config's `max_pool_lamports` (zero for no
+/// cap).
+pub fn check_pool_cap(max_pool_lamports: u64, pool_lamports: u64, lamports: u64) -> ProgramResult {
+    if max_pool_lamports > 0 && pool_lamports.saturating_add(lamports) > max_pool_lamports {
+        return Err(PinocchioError::PoolCapExceeded.into());
+    }
+    Ok(())
+}
+
+/// Fails with `MissingDepositAuthority` unless `signer` is the config's
+/// `deposit_authority` and signed. Anyone passes while the authority is the
+/// zero key.
+pub fn check_deposit_authority(deposit_authority: &Pubkey, signer: &AccountInfo) -> ProgramResult {
+    let authorized = *deposit_authority == [0u8; 32]
+        || (signer.key() == deposit_authority && signer.is_signer());
+    if !authorized {
+        return Err(PinocchioError::MissingDepositAuthority.into());
+    }
+    Ok(())
+}
+
+/// Adds `lamports` deposited for `lst_minted` LST to the principal in the pool
+/// state PDA. Called right after the mint, so `lst_mint`'s supply already
+/// includes `lst_minted`.
+pub fn record_principal(
+    pool_state_pda: &AccountInfo,
+    lst_mint: &AccountInfo,
+    lamports: u64,
+    lst_minted: u64,
+) -> ProgramResult {
+    check_pool_state_pda(pool_state_pda)?;
+
+    let lst_supply = pinocchio_token::state::Mint::from_account_info(lst_mint)?.supply();
+    let lst_supply_before = lst_supply
+        .checked_sub(lst_minted)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let mut pool_state_data = pool_state_pda.try_borrow_mut_data()?;
+    PoolState::load_mut(&mut pool_state_data)?.record_principal(
+        lst_supply_before,
+        lamports,
+        lst_minted,
+    )
+}
+
+/// Checks that `pool_state_pda` is the program's pool state PDA.
+pub fn check_pool_state_pda(pool_state_pda: &AccountInfo) -> ProgramResult {
+    let (expected_pool_state_pda, _) = find_program_address(&[b"pool_state"], &crate::ID);
+    if expected_pool_state_pda != *pool_state_pda.key() || !pool_state_pda.is_owned_by(&crate::ID) {
+        return Err(PinocchioError::InvalidPoolStatePda.into());
+    }
+
+    Ok(())
+}
+
+/// Queries the stake program for the cluster's minimum delegation.
+pub fn minimum_delegation() -> Result<u64, ProgramError> {
+    let get_minimum_delegation_ix = Instruction {
+        program_id: &STAKE_PROGRAM_ID,
+        data: &13u32.to_le_bytes(),
+        accounts: &[],
+    };
+
+    invoke::<0>(&get_minimum_delegation_ix, &[])?;
+
+    let return_data = get_return_data().ok_or(ProgramError::InvalidAccountData)?;
+    if return_data.program_id() != &STAKE_PROGRAM_ID || return_data.as_slice().len() != 8 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(u64::from_le_bytes(
+        return_data.as_slice().try_into().unwrap(),
+    ))
+}
+
+/// How a `MintTo` or `Burn` CPI is expected to move a mint's supply.
+#[derive(Clone, Copy)]
+pub enum SupplyChange {
+    Minted(u64),
+    Burned(u64),
+}
+
+/// Runs `cpi`, a `MintTo` or `Burn` on `mint`, then reloads the mint and
+/// checks the supply moved by exactly `change`. A token program that mints or
+/// burns anything else fails the transaction with `SupplyMismatch` instead
+/// of silently drifting the exchange rate.
+pub fn expect_supply_change(
+    mint: &AccountInfo,
+    change: SupplyChange,
+    cpi: impl FnOnce() -> ProgramResult,
+) -> ProgramResult {
+    let before = pinocchio_token::state::Mint::from_account_info(mint)?.supply();
+    cpi()?;
+    let after = pinocchio_token::state::Mint::from_account_info(mint)?.supply();
+
+    let expected = match change {
+        SupplyChange::Minted(amount) => before.checked_add(amount),
+        SupplyChange::Burned(amount) => before.checked_sub(amount),
+    };
+    if expected != Some(after) {
+        return Err(PinocchioError::SupplyMismatch.into());
+    }
+
+    Ok(())
+}
+
+/// `expect_supply_change` on the LST mint that also moves the pool state's
+/// `lst_issued` by `change` and checks the supply does not exceed it, so the
+/// supply cannot drift from what the program itself minted and burned.
+pub fn expect_lst_supply_change(
+    pool_state_pda: &AccountInfo,
+    lst_mint: &AccountInfo,
+    change: SupplyChange,
+    cpi: impl FnOnce() -> ProgramResult,
+) -> ProgramResult {
+    check_pool_state_pda(pool_state_pda)?;
+    expect_supply_change(lst_mint, change, cpi)?;
+
+    let lst_supply = pinocchio_token::state::Mint::from_account_info(lst_mint)?.supply();
+    let mut pool_state_data = pool_state_pda.try_borrow_mut_data()?;
+    let pool_state = PoolState::load_mut(&mut pool_state_data)?;
+    let lst_issued = match change {
+        SupplyChange::Minted(amount) => pool_state.lst_issued.checked_add(amount),
+        SupplyChange::Burned(amount) => pool_state.lst_issued.checked_sub(amount),
+    }
+    .ok_or(PinocchioError::SupplyMismatch)?;
+
+    pool_state.record_lst_issued(lst_issued, lst_supply)
+}
+
+/// Checks that `order_pda` is `owner`'s unstake order with `nonce` and
+/// `order_vault` its LST ATA, returning the order's bump.
+pub fn check_unstake_order(
+    order_pda: &AccountInfo,
+    order_vault: &AccountInfo,
+    owner: &AccountInfo,
+    nonce: u64,
+) -> Result<u8, ProgramError> {
+    if !order_pda.is_owned_by(&crate::ID) {
+        return Err(PinocchioError::InvalidUnstakeOrderPda.into());
+    }
+
+    let order_data = order_pda.try_borrow_data()?;
+    let order = UnstakeOrder::load(&order_data)?;
+
+    let (expected_order_pda, order_bump) = find_program_address(
+        &[b"unstake_order", &order.owner, &nonce.to_le_bytes()],
+        &crate::ID,
+    );
+    if expected_order_pda != *order_pda.key() || order.owner != *owner.key() {
+        return Err(PinocchioError::InvalidUnstakeOrderPda.into());
+    }
+
+    if order.vault != *order_vault.key() {
+        return Err(PinocchioError::InvalidAddress.into());
+    }
+
+    Ok(order_bump)
+}
+
+/// Closes an unstake order whose vault is empty, returning the rent of the
+/// vault and of the order PDA, with whatever tip is left in it, to `owner`.
+pub fn close_unstake_order(
+    order_pda: &AccountInfo,
+    order_vault: &AccountInfo,
+    owner: &AccountInfo,
+    order_seeds: &[Seed],
+) -> ProgramResult {
+    CloseAccount {
+        account: order_vault,
+        destination: owner,
+        authority: order_pda,
+    }
+    .invoke_signed(&[Signer::from(order_seeds)])?;
+
+    ProgramAccount::close(order_pda, owner)
+}
+
+/// Checks that `escrow_pda` is `owner`'s vote escrow and `escrow_vault` its
+/// LST ATA, returning the escrow's bump.
+pub fn check_vote_escrow(
+    escrow_pda: &AccountInfo,
+    escrow_vault: &AccountInfo,
+    owner: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    if !escrow_pda.is_owned_by(&crate::ID) {
+        return Err(PinocchioError::InvalidVoteEscrowPda.into());
+    }
+
+    let escrow_data = escrow_pda.try_borrow_data()?;
+    let escrow = VoteEscrow::load(&escrow_data)?;
+
+    let (expected_escrow_pda, escrow_bump) =
+        find_program_address(&[b"vote_escrow", &escrow.owner], &crate::ID);
+    if expected_escrow_pda != *escrow_pda.key() || escrow.owner != *owner.key() {
+        return Err(PinocchioError::InvalidVoteEscrowPda.into());
+    }
+
+    if escrow.vault != *escrow_vault.key() {
+        return Err(PinocchioError::InvalidAddress.into());
+    }
+
+    Ok(escrow_bump)
+}
+
+/// Checks that `reward_payout_pda` is the SOL payout pool and `payout_vault`
+/// its LST ATA, returning the pool's bump.
+pub fn check_reward_payout(
+    reward_payout_pda: &AccountInfo,
+    payout_vault: &AccountInfo,
+    lst_mint: &AccountInfo,
+    token_program: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    let (expected_reward_payout_pda, reward_payout_bump) =
+        find_program_address(&[b"reward_payout"], &crate::ID);
+    if expected_reward_payout_pda != *reward_payout_pda.key()
+        || !reward_payout_pda.is_owned_by(&crate::ID)
+    {
+        return Err(PinocchioError::InvalidRewardPayoutPda.into());
+    }
+
+    let expected_payout_vault = find_program_address(
+        &[reward_payout_pda.key(), token_program.key(), lst_mint.key()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0;
+    if expected_payout_vault != *payout_vault.key() {
+        return Err(PinocchioError::InvalidAddress.into());
+    }
+
+    Ok(reward_payout_bump)
+}
+
+/// Checks that `position_pda` is `owner`'s existing payout position.
+pub fn check_payout_position(position_pda: &AccountInfo, owner: &AccountInfo) -> ProgramResult {
+    if !position_pda.is_owned_by(&crate::ID)
+        || find_program_address(&[b"payout_position", owner.key()], &crate::ID).0
+            != *position_pda.key()
+    {
+        return Err(PinocchioError::InvalidPayoutPositionPda.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that `config_pda` is the config PDA, returning its bump. The bump
+/// stored in the config is checked with `create_program_address`, which
+/// costs far less than searching for it with `find_program_address`.
+pub fn check_config_pda(config_pda: &AccountInfo) -> Result<u8, ProgramError> {
+    if !config_pda.is_owned_by(&crate::ID) {
+        return Err(PinocchioError::InvalidConfigPda.into());
+    }
+
+    let config_bump = Config::load(&config_pda.try_borrow_data()?)
+        .map_err(|_| PinocchioError::InvalidConfigPda)?
+        .config_bump;
+    if create_program_address(&[b"config", &[config_bump]], &crate::ID)? != *config_pda.key() {
+        return Err(PinocchioError::InvalidConfigPda.into());
+    }
+
+    Ok(config_bump)
+}
+
+/// Checks that `validator_list_pda` is the validator list PDA.
+pub fn check_validator_list(validator_list_pda: &AccountInfo) -> ProgramResult {
+    if find_program_address(&[b"validator_list"], &crate::ID).0 != *validator_list_pda.key()
+        || !validator_list_pda.is_owned_by(&crate::ID)
+    {
+        return Err(PinocchioError::InvalidValidatorListPda.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that `validator_stake_pda` is the stake PDA of `vote_account`,
+/// returning its bump.
+pub fn check_validator_stake(
+    validator_stake_pda: &AccountInfo,
+    vote_account: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let (expected_validator_stake_pda, validator_stake_bump) =
+        find_program_address(&[b"validator_stake", vote_account], &crate::ID);
+    if expected_validator_stake_pda != *validator_stake_pda.key() {
+        return Err(PinocchioError::InvalidValidatorStakePda.into());
+    }
+
+    Ok(validator_stake_bump)
+}
+
+/// Records the lamports delegated in `validator_stake_pda`, see
+/// `staked_lamports`, as the stake of `vote_account`, which must be in the
+/// validator list, updating the config's `validator_stake_lamports` to match. Once every staked
+/// validator is recorded this epoch, the config's `validator_stake_epoch` is
+/// stamped with it.
+pub fn record_validator_stake(
+    config_pda: &AccountInfo,
+    validator_list_pda: &AccountInfo,
+    validator_stake_pda: &AccountInfo,
+    vote_account: &Pubkey,
+) -> ProgramResult {
+    check_validator_list(validator_list_pda)?;
+    check_validator_stake(validator_stake_pda, vote_account)?;
+    let stake_lamports = staked_lamports(validator_stake_pda)?;
+
+    let epoch = Clock::get()?.epoch;
+    let mut validator_list_data = validator_list_pda.try_borrow_mut_data()?;
+    let validator_list = ValidatorList::load_mut(&mut validator_list_data)?;
+    let entry = validator_list
+        .find_mut(vote_account)
+        .ok_or(PinocchioError::ValidatorNotInList)?;
+
+    let mut config_data = config_pda.try_borrow_mut_data()?;
+    let config = Config::load_mut(&mut config_data)?;
+    config.record_validator_stake(entry, stake_lamports, epoch)?;
+    config.stamp_validator_stake(validator_list, epoch);
+    Ok(())
+}
+
+/// Checks that `transient_pda` is the transient stake PDA of `vote_account`,
+/// returning its bump.
+pub fn check_validator_transient(
+    transient_pda: &AccountInfo,
+    vote_account: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let (expected_transient_pda, transient_bump) =
+        find_program_address(&[b"validator_transient", vote_account], &crate::ID);
+    if expected_transient_pda != *transient_pda.key() {
+        return Err(PinocchioError::InvalidValidatorStakePda.into());
+    }
+
+    Ok(transient_bump)
+}
+
+/// Records the lamports delegated in `transient_pda` as the transient
+/// stake of `vote_account`, which must be in the validator list, updating
+/// the config's `validator_stake_lamports` and `validator_stake_epoch` as
+/// `record_validator_stake` does.
+pub fn record_transient_stake(
+    config_pda: &AccountInfo,
+    validator_list_pda: &AccountInfo,
+    transient_pda: &AccountInfo,
+    vote_account: &Pubkey,
+) -> ProgramResult {
+    check_validator_list(validator_list_pda)?;
+    check_validator_transient(transient_pda, vote_account)?;
+    let transient_lamports = staked_lamports(transient_pda)?;
+
+    let epoch = Clock::get()?.epoch;
+    let mut validator_list_data = validator_list_pda.try_borrow_mut_data()?;
+    let validator_list = ValidatorList::load_mut(&mut validator_list_data)?;
+    let entry = validator_list
+        .find_mut(vote_account)
+        .ok_or(PinocchioError::ValidatorNotInList)?;
+
+    let mut config_data = config_pda.try_borrow_mut_data()?;
+    let config = Config::load_mut(&mut config_data)?;
+    config.record_transient_stake(entry, transient_lamports, epoch)?;
+    config.stamp_validator_stake(validator_list, epoch);
+    Ok(())
+}
+
+/// Checks that `handoff_pda` is a pending split handoff and `split_record`
+/// the record of the split account it hands off.
+pub fn check_split_handoff(handoff_pda: &AccountInfo, split_record: &AccountInfo) -> ProgramResult {
+    if !handoff_pda.is_owned_by(&crate::ID) {
+        return Err(PinocchioError::InvalidSplitHandoffPda.into());
+    }
+
+    let handoff_data = handoff_pda.try_borrow_data()?;
+    let split_account = SplitHandoff::load(&handoff_data)?.split_account;
+
+    if find_program_address(&[b"split_handoff", &split_account], &crate::ID).0 != *handoff_pda.key()
+    {
+        return Err(PinocchioError::InvalidSplitHandoffPda.into());
+    }
+
+    if find_program_address(&[b"split_record", &split_account], &crate::ID).0 != *split_record.key()
+    {
+        return Err(PinocchioError::InvalidSplitRecordPda.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that `wrapper_authority` is the `WRAPPER_AUTHORITY_SEED` PDA of the
+/// approved `wrapper_program` and `wrapper_vault` its LST ATA.
+pub fn check_wrapper(
+    wrapper_program: &Pubkey,
+    wrapper_authority: &AccountInfo,
+    wrapper_vault: &AccountInfo,
+    lst_mint: &AccountInfo,
+    token_program: &AccountInfo,
+) -> ProgramResult {
+    if *wrapper_program == [0u8; 32]
+        || find_program_address(&[WRAPPER_AUTHORITY_SEED], wrapper_program).0
+            != *wrapper_authority.key()
+    {
+        return Err(PinocchioError::WrapperNotApproved.into());
+    }
+
+    let expected_vault = find_program_address(
+        &[wrapper_authority.key(), token_program.key(), lst_mint.key()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0;
+    if expected_vault != *wrapper_vault.key() {
+        return Err(PinocchioError::InvalidWrapperVault.into());
+    }
+
+    Ok(())
+}
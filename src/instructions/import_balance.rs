@@ -0,0 +1,256 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::find_program_address,
+};
+use pinocchio_token::{instructions::Transfer, state::TokenAccount};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    ids::{
+        assert_associated_token_program, assert_system_program, assert_token_program,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+    },
+    instructions::helpers::{
+        check_config_pda, AccountCheck, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+        AssociatedTokenAccountInit, ProgramAccount, ProgramAccountInit, SignerAccount,
+    },
+    state::{Config, ImportedBalance, Migration},
+};
+
+pub struct ImportBalanceAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub migration_pda: &'a AccountInfo,
+    pub migration_vault: &'a AccountInfo,
+    pub depositor: &'a AccountInfo,
+    pub depositor_ata: &'a AccountInfo,
+    pub imported_balance_pda: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub associated_token_program: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ImportBalanceAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, migration_pda, migration_vault, depositor, depositor_ata, imported_balance_pda, lst_mint, token_program, associated_token_program, system_program, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        assert_system_program(system_program)?;
+        assert_token_program(token_program)?;
+        assert_associated_token_program(associated_token_program)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            migration_pda,
+            migration_vault,
+            depositor,
+            depositor_ata,
+            imported_balance_pda,
+            lst_mint,
+            token_program,
+            associated_token_program,
+            system_program,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct ImportBalanceInstructionData {
+    pub pool_tokens: u64,
+}
+
+impl TryFrom<&[u8]> for ImportBalanceInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            pool_tokens: u64::from_le_bytes(data.try_into().unwrap()),
+        })
+    }
+}
+
+/// Pays a depositor's exported balance of `pool_tokens` source pool tokens
+/// from the migration vault, as the same share of the LST minted for the
+/// imported stake. Creates the depositor's LST ATA if needed and their
+/// imported balance PDA (`b"imported_balance"`, depositor), so each
+/// depositor is imported once; the admin pays the rent of both. The first
+/// call seals the migration against further stake imports, and the balance
+/// that brings the total to the source supply also takes the rounding dust
+/// left in the vault. Balances beyond the source supply fail with
+/// `MigrationExceedsSource`.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Admin
+/// 1. `[]` Config PDA
+/// 2. `[WRITE]` Migration PDA
+/// 3. `[WRITE]` Migration vault
+/// 4. `[]` Depositor
+/// 5. `[WRITE]` Depositor LST ATA
+/// 6. `[WRITE]` Imported balance PDA
+/// 7. `[]` LST mint
+/// 8. `[]` Token program
+/// 9. `[]` Associated token program
+/// 10. `[]` System program
+/// 11. `[WRITE]` Pool state PDA
+pub struct ImportBalance<'a> {
+    pub accounts: ImportBalanceAccounts<'a>,
+    pub data: ImportBalanceInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for ImportBalance<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: ImportBalanceAccounts::try_from(accounts)?,
+            data: ImportBalanceInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> ImportBalance<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &72;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+        drop(config_data);
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        if self.data.pool_tokens == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let (expected_migration_pda, migration_bump) =
+            find_program_address(&[b"migration"], &crate::ID);
+        if expected_migration_pda != *self.accounts.migration_pda.key()
+            || !self.accounts.migration_pda.is_owned_by(&crate::ID)
+        {
+            return Err(PinocchioError::InvalidMigrationPda.into());
+        }
+
+        let expected_migration_vault = find_program_address(
+            &[
+                self.accounts.migration_pda.key(),
+                self.accounts.token_program.key(),
+                self.accounts.lst_mint.key(),
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .0;
+        if expected_migration_vault != *self.accounts.migration_vault.key() {
+            return Err(PinocchioError::InvalidAddress.into());
+        }
+
+        let (expected_imported_balance_pda, imported_balance_bump) = find_program_address(
+            &[b"imported_balance", self.accounts.depositor.key()],
+            &crate::ID,
+        );
+        if expected_imported_balance_pda != *self.accounts.imported_balance_pda.key() {
+            return Err(PinocchioError::InvalidAddress.into());
+        }
+
+        let vault_balance =
+            TokenAccount::from_account_info(self.accounts.migration_vault)?.amount();
+
+        let mut migration_data = self.accounts.migration_pda.try_borrow_mut_data()?;
+        let migration = Migration::load_mut(&mut migration_data)?;
+
+        let imported_pool_tokens = migration
+            .imported_pool_tokens
+            .checked_add(self.data.pool_tokens)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        if imported_pool_tokens > migration.source_pool_tokens {
+            return Err(PinocchioError::MigrationExceedsSource.into());
+        }
+
+        let lst_amount = migration.lst_for(self.data.pool_tokens, vault_balance)?;
+        migration.imported_pool_tokens = imported_pool_tokens;
+        migration.sealed = 1;
+        drop(migration_data);
+
+        let imported_balance_bump_binding = [imported_balance_bump];
+        let imported_balance_seeds = &[
+            Seed::from(b"imported_balance"),
+            Seed::from(self.accounts.depositor.key()),
+            Seed::from(&imported_balance_bump_binding),
+        ];
+        ProgramAccount::init::<ImportedBalance>(
+            self.accounts.admin,
+            self.accounts.imported_balance_pda,
+            imported_balance_seeds,
+            ImportedBalance::LEN,
+        )?;
+
+        let mut imported_balance_data = self.accounts.imported_balance_pda.try_borrow_mut_data()?;
+        let imported_balance = ImportedBalance::load_mut(&mut imported_balance_data)?;
+        imported_balance.pool_tokens = self.data.pool_tokens;
+        imported_balance.lst_amount = lst_amount;
+        drop(imported_balance_data);
+
+        if self.accounts.depositor_ata.data_is_empty() {
+            AssociatedTokenAccount::init(
+                self.accounts.depositor_ata,
+                self.accounts.lst_mint,
+                self.accounts.admin,
+                self.accounts.depositor,
+                self.accounts.system_program,
+                self.accounts.token_program,
+            )?;
+        } else {
+            AssociatedTokenAccount::check(
+                self.accounts.depositor_ata,
+                self.accounts.depositor,
+                self.accounts.lst_mint,
+                self.accounts.token_program,
+            )?;
+        }
+
+        let migration_bump_binding = [migration_bump];
+        let migration_seeds = &[
+            Seed::from(b"migration"),
+            Seed::from(&migration_bump_binding),
+        ];
+
+        Transfer {
+            from: self.accounts.migration_vault,
+            to: self.accounts.depositor_ata,
+            authority: self.accounts.migration_pda,
+            amount: lst_amount,
+        }
+        .invoke_signed(&[Signer::from(migration_seeds)])
+    }
+}
@@ -0,0 +1,295 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+use pinocchio_token::{instructions::MintTo, state::Mint};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    ids::{
+        assert_clock_sysvar, assert_stake_history_sysvar, assert_stake_program,
+        assert_token_program, ASSOCIATED_TOKEN_PROGRAM_ID,
+    },
+    instructions::helpers::{
+        accrue_rewards, check_config_pda, check_pool_cap, epoch_rate, expect_lst_supply_change,
+        pool_lamports, record_managed_change, record_pool_stake, record_principal,
+        stake_account_breakdown, stake_authorities, AccountCheck, ProgramAccount, SignerAccount,
+        StakeAccountMerge, StakeAccountWithdraw, SupplyChange,
+    },
+    math::lamports_to_lst,
+    state::{Config, ManagedChange, Migration},
+};
+
+pub struct ImportStakeAccountAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub migration_pda: &'a AccountInfo,
+    pub migration_vault: &'a AccountInfo,
+    pub stake_account: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+    pub clock_sysvar: &'a AccountInfo,
+    pub history_sysvar: &'a AccountInfo,
+    pub stake_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ImportStakeAccountAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, migration_pda, migration_vault, stake_account, stake_account_main, stake_account_reserve, lst_mint, pool_state_pda, clock_sysvar, history_sysvar, stake_program, token_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        assert_stake_program(stake_program)?;
+        assert_clock_sysvar(clock_sysvar)?;
+        assert_stake_history_sysvar(history_sysvar)?;
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            migration_pda,
+            migration_vault,
+            stake_account,
+            stake_account_main,
+            stake_account_reserve,
+            lst_mint,
+            pool_state_pda,
+            clock_sysvar,
+            history_sysvar,
+            stake_program,
+            token_program,
+        })
+    }
+}
+
+/// Adopts a stake account exported from the source pool and mints LST for
+/// it into the migration vault, at the current exchange rate so existing
+/// holders are not diluted. The source pool's `WithdrawStake` can hand the
+/// account straight to the config PDA; otherwise its authorities must first
+/// be moved there, as both the staker and the withdrawer must be the config
+/// PDA.
+///
+/// Stake fully active on the config's validator is merged into main.
+/// Inactive stake is withdrawn into the reserve, which delegates it with the
+/// next `CrankInitializeReserve`. Stake on any other validator must be
+/// deactivated first, and stake that is still warming up or cooling down
+/// fails with `StakeNotImportable`. The deposit cap does not apply, but the
+/// pool cap does (`PoolCapExceeded`). Fails with `MigrationSealed` once
+/// balances are being imported.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[]` Config PDA
+/// 2. `[WRITE]` Migration PDA
+/// 3. `[WRITE]` Migration vault
+/// 4. `[WRITE]` Stake account to import
+/// 5. `[WRITE]` Stake account main
+/// 6. `[WRITE]` Stake account reserve
+/// 7. `[WRITE]` LST mint
+/// 8. `[WRITE]` Pool state PDA
+/// 9. `[]` Clock sysvar
+/// 10. `[]` Stake history sysvar
+/// 11. `[]` Stake program
+/// 12. `[]` Token program
+pub struct ImportStakeAccount<'a> {
+    pub accounts: ImportStakeAccountAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ImportStakeAccount<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            accounts: ImportStakeAccountAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> ImportStakeAccount<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &71;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let bump = check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+        let validator_vote_pubkey = config.validator_vote_pubkey;
+        let validator_stake_lamports = config.validator_stake_lamports;
+        let max_pool_lamports = config.max_pool_lamports;
+        let cached_rate = epoch_rate(config)?;
+        drop(config_data);
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let (expected_migration_pda, _) = find_program_address(&[b"migration"], &crate::ID);
+        if expected_migration_pda != *self.accounts.migration_pda.key()
+            || !self.accounts.migration_pda.is_owned_by(&crate::ID)
+        {
+            return Err(PinocchioError::InvalidMigrationPda.into());
+        }
+
+        if Migration::load(&self.accounts.migration_pda.try_borrow_data()?)?.sealed != 0 {
+            return Err(PinocchioError::MigrationSealed.into());
+        }
+
+        let expected_migration_vault = find_program_address(
+            &[
+                self.accounts.migration_pda.key(),
+                self.accounts.token_program.key(),
+                self.accounts.lst_mint.key(),
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .0;
+        if expected_migration_vault != *self.accounts.migration_vault.key() {
+            return Err(PinocchioError::InvalidAddress.into());
+        }
+
+        let stake_account = self.accounts.stake_account;
+        let config_pda = *self.accounts.config_pda.key();
+        if stake_account.key() == self.accounts.stake_account_main.key()
+            || stake_account.key() == self.accounts.stake_account_reserve.key()
+            || stake_authorities(stake_account)? != Some((config_pda, config_pda))
+        {
+            return Err(PinocchioError::StakeNotImportable.into());
+        }
+
+        let breakdown = stake_account_breakdown(stake_account, Clock::get()?.epoch)?;
+        let merge = breakdown.active > 0
+            && breakdown.voter == Some(validator_vote_pubkey)
+            && breakdown.activating == 0
+            && breakdown.deactivating == 0;
+        let withdraw =
+            breakdown.active == 0 && breakdown.activating == 0 && breakdown.deactivating == 0;
+        if !merge && !withdraw {
+            return Err(PinocchioError::StakeNotImportable.into());
+        }
+
+        let live_lst_supply = Mint::from_account_info(self.accounts.lst_mint)?.supply();
+        let lamports_before = pool_lamports(
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+        let managed = accrue_rewards(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+
+        let bump_binding = [bump];
+        let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
+
+        if merge {
+            ProgramAccount::merge_stake_account(
+                self.accounts.stake_account_main,
+                stake_account,
+                self.accounts.clock_sysvar,
+                self.accounts.history_sysvar,
+                self.accounts.config_pda,
+                config_seeds,
+            )?;
+        } else {
+            ProgramAccount::withdraw_stake_account(
+                stake_account,
+                self.accounts.stake_account_reserve,
+                self.accounts.clock_sysvar,
+                self.accounts.history_sysvar,
+                self.accounts.config_pda,
+                config_seeds,
+            )?;
+        }
+
+        let imported_lamports = pool_lamports(
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?
+        .checked_sub(lamports_before)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+        check_pool_cap(max_pool_lamports, lamports_before, imported_lamports)?;
+        record_pool_stake(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+        record_managed_change(
+            self.accounts.pool_state_pda,
+            ManagedChange::Deposited(imported_lamports),
+        )?;
+        let (total_sol_in_pool, total_lst_supply) =
+            cached_rate.unwrap_or((managed, live_lst_supply));
+        let lst_to_mint = lamports_to_lst(imported_lamports, total_lst_supply, total_sol_in_pool)?;
+
+        expect_lst_supply_change(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            SupplyChange::Minted(lst_to_mint),
+            || {
+                MintTo {
+                    mint: self.accounts.lst_mint,
+                    account: self.accounts.migration_vault,
+                    mint_authority: self.accounts.config_pda,
+                    amount: lst_to_mint,
+                }
+                .invoke_signed(&[Signer::from(config_seeds)])
+            },
+        )?;
+
+        record_principal(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            imported_lamports,
+            lst_to_mint,
+        )?;
+
+        let mut migration_data = self.accounts.migration_pda.try_borrow_mut_data()?;
+        let migration = Migration::load_mut(&mut migration_data)?;
+        migration.imported_lamports = migration
+            .imported_lamports
+            .checked_add(imported_lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        migration.minted_lst = migration
+            .minted_lst
+            .checked_add(lst_to_mint)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+}
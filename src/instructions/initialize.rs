@@ -3,19 +3,45 @@ use pinocchio::{
     instruction::{Seed, Signer},
     msg,
     program_error::ProgramError,
-    pubkey::find_program_address,
+    pubkey::{find_program_address, Pubkey},
+    sysvars::{clock::Clock, Sysvar},
 };
 use pinocchio_token::instructions::MintTo;
 
 use crate::{
+    delegation_strategy::DelegationStrategyKind,
     errors::PinocchioError,
+    ids::{
+        assert_associated_token_program, assert_clock_sysvar, assert_rent_sysvar,
+        assert_stake_history_sysvar, assert_stake_program, assert_system_program,
+        assert_token_program, assert_vote_account,
+    },
     instructions::helpers::{
-        AccountCheck, AssociatedTokenAccount, AssociatedTokenAccountInit, MintAccount, MintInit,
-        ProgramAccount, ProgramAccountInit, SignerAccount, StakeAccountCreate,
-        StakeAccountDelegate, StakeAccountInitialize, SystemAccount, STAKE_PROGRAM_ID,
-        VOTE_PROGRAM_ID,
+        expect_lst_supply_change, pool_lamports, record_managed_change, record_pool_stake,
+        record_principal, stake_account_space, AccountCheck, AssociatedTokenAccount,
+        AssociatedTokenAccountInit, MintAccount, MintInit, ProgramAccount, ProgramAccountInit,
+        SignerAccount, StakeAccountCreate, StakeAccountDelegate, StakeAccountInitialize,
+        SupplyChange, SystemAccount, DEFAULT_STAKE_ACCOUNT_SPACE, LST_DECIMALS,
+    },
+    instructions::liquid_unstake::{
+        DEFAULT_LIQUIDITY_TARGET_LAMPORTS, DEFAULT_UNSTAKE_FEE_MAX_BPS, DEFAULT_UNSTAKE_FEE_MIN_BPS,
     },
-    state::Config,
+    instructions::set_split_minimum::DEFAULT_MIN_SPLIT_STAKE_LAMPORTS,
+    instructions::set_validator_stake_cap::{
+        DEFAULT_MAX_VALIDATOR_STAKE_BPS, DEFAULT_MAX_VALIDATOR_STAKE_LAMPORTS,
+    },
+    state::{Changelog, Config, EpochTasks, ManagedChange, PoolState, EPOCH_TASK_FEE_ACCRUAL},
+};
+
+/// The only key allowed to call `Initialize`, set at build time with the
+/// `POOL_ADMIN` environment variable (base58). The config PDA is a global
+/// singleton, so otherwise whoever initializes first after deployment owns
+/// the pool. `build.rs` refuses SBF builds without it unless the
+/// `open-initialize` feature is on, so only test and host builds accept any
+/// initializer.
+pub const EXPECTED_ADMIN: Option<Pubkey> = match option_env!("POOL_ADMIN") {
+    Some(admin) => Some(bs58::decode(admin.as_bytes()).into_array_const_unwrap()),
+    None => None,
 };
 
 pub struct InitializeAccounts<'a> {
@@ -34,13 +60,16 @@ pub struct InitializeAccounts<'a> {
     pub rent_sysvar: &'a AccountInfo,
     pub clock_sysvar: &'a AccountInfo,
     pub history_sysvar: &'a AccountInfo,
+    pub epoch_tasks_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+    pub changelog_pda: &'a AccountInfo,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for InitializeAccounts<'a> {
     type Error = ProgramError;
 
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [initializer, initializer_ata, config_pda, stake_account_main, stake_account_reserve, lst_mint, validator_vote_account, unused_account, system_program, stake_program, token_program, associated_token_program, rent_sysvar, clock_sysvar, history_sysvar] =
+        let [initializer, initializer_ata, config_pda, stake_account_main, stake_account_reserve, lst_mint, validator_vote_account, unused_account, system_program, stake_program, token_program, associated_token_program, rent_sysvar, clock_sysvar, history_sysvar, epoch_tasks_pda, pool_state_pda, changelog_pda] =
             accounts
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
@@ -49,13 +78,8 @@ impl<'a> TryFrom<&'a [AccountInfo]> for InitializeAccounts<'a> {
         SignerAccount::check(initializer)?;
         SignerAccount::check(lst_mint)?;
 
-        if system_program.key() != &pinocchio_system::ID {
-            return Err(PinocchioError::InvalidSystemProgram.into());
-        }
-
-        if token_program.key() != &pinocchio_token::ID {
-            return Err(PinocchioError::InvalidTokenProgram.into());
-        }
+        assert_system_program(system_program)?;
+        assert_token_program(token_program)?;
 
         SystemAccount::check(config_pda)?;
 
@@ -75,20 +99,33 @@ impl<'a> TryFrom<&'a [AccountInfo]> for InitializeAccounts<'a> {
             return Err(ProgramError::AccountAlreadyInitialized);
         }
 
-        MintAccount::check(lst_mint)?;
+        SystemAccount::check(epoch_tasks_pda)?;
 
-        if !validator_vote_account.is_owned_by(&VOTE_PROGRAM_ID) {
-            return Err(PinocchioError::InvalidValidatorVoteAccount.into());
+        if !epoch_tasks_pda.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
         }
 
-        if stake_program.key() != &STAKE_PROGRAM_ID {
-            return Err(PinocchioError::InvalidStakeProgram.into());
+        SystemAccount::check(pool_state_pda)?;
+
+        if !pool_state_pda.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
         }
 
-        if associated_token_program.key() != &pinocchio_associated_token_account::ID {
-            return Err(PinocchioError::InvalidAssociatedTokenProgram.into());
+        SystemAccount::check(changelog_pda)?;
+
+        if !changelog_pda.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
         }
 
+        MintAccount::check(lst_mint)?;
+
+        assert_vote_account(validator_vote_account)?;
+        assert_stake_program(stake_program)?;
+        assert_rent_sysvar(rent_sysvar)?;
+        assert_clock_sysvar(clock_sysvar)?;
+        assert_stake_history_sysvar(history_sysvar)?;
+        assert_associated_token_program(associated_token_program)?;
+
         Ok(Self {
             initializer,
             initializer_ata,
@@ -105,10 +142,19 @@ impl<'a> TryFrom<&'a [AccountInfo]> for InitializeAccounts<'a> {
             rent_sysvar,
             clock_sysvar,
             history_sysvar,
+            epoch_tasks_pda,
+            pool_state_pda,
+            changelog_pda,
         })
     }
 }
-/// Sets up liquid staking pool and mints initial LST.
+/// Sets up liquid staking pool and mints initial LST. Also creates the epoch
+/// tasks PDA the cranks mark their progress in, the pool state PDA that
+/// holds the fields user instructions write, and the changelog PDA admin
+/// setters record their changes in.
+///
+/// The pool has no yield in the epoch it is created, so that epoch's
+/// management fee counts as charged and its fee accrual step as done.
 ///
 /// Accounts expected:
 ///
@@ -127,6 +173,9 @@ impl<'a> TryFrom<&'a [AccountInfo]> for InitializeAccounts<'a> {
 /// 12. `[]` Rent sysvar
 /// 13. `[]` Clock sysvar
 /// 14. `[]` History sysvar
+/// 15. `[WRITE]` Epoch tasks PDA
+/// 16. `[WRITE]` Pool state PDA
+/// 17. `[WRITE]` Changelog PDA
 pub struct Initialize<'a> {
     pub accounts: InitializeAccounts<'a>,
 }
@@ -144,6 +193,12 @@ impl<'a> Initialize<'a> {
     pub const DISCRIMINATOR: &'static u8 = &0;
 
     pub fn process(&mut self) -> Result<(), ProgramError> {
+        if let Some(expected_admin) = EXPECTED_ADMIN {
+            if *self.accounts.initializer.key() != expected_admin {
+                return Err(PinocchioError::UnexpectedInitializer.into());
+            }
+        }
+
         let (expected_config_pda, bump) = find_program_address(&[b"config"], &crate::ID);
         if expected_config_pda != *self.accounts.config_pda.key() {
             return Err(PinocchioError::InvalidConfigPda.into());
@@ -166,6 +221,73 @@ impl<'a> Initialize<'a> {
             *self.accounts.stake_account_reserve.key(),
             *self.accounts.validator_vote_account.key(),
         );
+        config.set_unstake_fee_params(
+            DEFAULT_UNSTAKE_FEE_MIN_BPS,
+            DEFAULT_UNSTAKE_FEE_MAX_BPS,
+            DEFAULT_LIQUIDITY_TARGET_LAMPORTS,
+        );
+        config.set_validator_stake_cap(
+            DEFAULT_MAX_VALIDATOR_STAKE_BPS,
+            DEFAULT_MAX_VALIDATOR_STAKE_LAMPORTS,
+        );
+        config.set_delegation_strategy(DelegationStrategyKind::EvenSplit);
+        config.set_min_split_stake(DEFAULT_MIN_SPLIT_STAKE_LAMPORTS);
+
+        let epoch = Clock::get()?.epoch;
+        config.last_fee_epoch = epoch;
+
+        let (expected_epoch_tasks_pda, epoch_tasks_bump) =
+            find_program_address(&[b"epoch_tasks"], &crate::ID);
+        if expected_epoch_tasks_pda != *self.accounts.epoch_tasks_pda.key() {
+            return Err(PinocchioError::InvalidEpochTasksPda.into());
+        }
+        let epoch_tasks_bump_binding = [epoch_tasks_bump];
+        let epoch_tasks_seeds = &[
+            Seed::from(b"epoch_tasks"),
+            Seed::from(&epoch_tasks_bump_binding),
+        ];
+        ProgramAccount::init::<EpochTasks>(
+            self.accounts.initializer,
+            self.accounts.epoch_tasks_pda,
+            epoch_tasks_seeds,
+            EpochTasks::LEN,
+        )?;
+        EpochTasks::load_mut(&mut self.accounts.epoch_tasks_pda.try_borrow_mut_data()?)?
+            .mark(epoch, EPOCH_TASK_FEE_ACCRUAL);
+
+        let (expected_pool_state_pda, pool_state_bump) =
+            find_program_address(&[b"pool_state"], &crate::ID);
+        if expected_pool_state_pda != *self.accounts.pool_state_pda.key() {
+            return Err(PinocchioError::InvalidPoolStatePda.into());
+        }
+        let pool_state_bump_binding = [pool_state_bump];
+        let pool_state_seeds = &[
+            Seed::from(b"pool_state"),
+            Seed::from(&pool_state_bump_binding),
+        ];
+        ProgramAccount::init::<PoolState>(
+            self.accounts.initializer,
+            self.accounts.pool_state_pda,
+            pool_state_seeds,
+            PoolState::LEN,
+        )?;
+
+        let (expected_changelog_pda, changelog_bump) =
+            find_program_address(&[b"changelog"], &crate::ID);
+        if expected_changelog_pda != *self.accounts.changelog_pda.key() {
+            return Err(PinocchioError::InvalidChangelogPda.into());
+        }
+        let changelog_bump_binding = [changelog_bump];
+        let changelog_seeds = &[
+            Seed::from(b"changelog"),
+            Seed::from(&changelog_bump_binding),
+        ];
+        ProgramAccount::init::<Changelog>(
+            self.accounts.initializer,
+            self.accounts.changelog_pda,
+            changelog_seeds,
+            Changelog::LEN,
+        )?;
 
         //make and fund stake account main
         let (expected_stake_account_main, stake_main_bump) =
@@ -185,6 +307,7 @@ impl<'a> Initialize<'a> {
             self.accounts.initializer,
             self.accounts.stake_account_main,
             stake_main_seeds,
+            DEFAULT_STAKE_ACCOUNT_SPACE,
         )?;
 
         ProgramAccount::initialize_stake_account_no_lockup(
@@ -225,13 +348,19 @@ impl<'a> Initialize<'a> {
             self.accounts.initializer,
             self.accounts.stake_account_reserve,
             stake_reserve_seeds,
+            stake_account_space(self.accounts.stake_account_main),
         )?;
+
+        let mut data = self.accounts.config_pda.try_borrow_mut_data()?;
+        Config::load_mut(data.as_mut())?.set_bumps(bump, stake_main_bump, stake_reserve_bump);
+        drop(data);
+
         let signer = [Signer::from(config_seeds)];
 
         MintAccount::init_if_needed(
             self.accounts.lst_mint,
             self.accounts.initializer,
-            9,
+            LST_DECIMALS,
             self.accounts.config_pda.key(),
             None,
         )?;
@@ -245,13 +374,43 @@ impl<'a> Initialize<'a> {
             self.accounts.token_program,
         )?;
 
-        MintTo {
-            mint: self.accounts.lst_mint,
-            account: self.accounts.initializer_ata,
-            mint_authority: self.accounts.config_pda,
-            amount: 1 * 10u64.pow(9),
-        }
-        .invoke_signed(&signer)?;
+        expect_lst_supply_change(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            SupplyChange::Minted(1 * 10u64.pow(9)),
+            || {
+                MintTo {
+                    mint: self.accounts.lst_mint,
+                    account: self.accounts.initializer_ata,
+                    mint_authority: self.accounts.config_pda,
+                    amount: 1 * 10u64.pow(9),
+                }
+                .invoke_signed(&signer)
+            },
+        )?;
+
+        // The initializer's LST is backed by what funded main and the reserve.
+        let initial_lamports = pool_lamports(
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            0,
+        )?;
+        record_principal(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            initial_lamports,
+            1 * 10u64.pow(9),
+        )?;
+        record_managed_change(
+            self.accounts.pool_state_pda,
+            ManagedChange::Deposited(initial_lamports),
+        )?;
+        record_pool_stake(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            0,
+        )?;
 
         Ok(())
     }
@@ -0,0 +1,186 @@
+use pinocchio::{
+    account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+    pubkey::find_program_address,
+};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    ids::{
+        assert_associated_token_program, assert_system_program, assert_token_program,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+    },
+    instructions::helpers::{
+        check_config_pda, AccountCheck, AssociatedTokenAccount, AssociatedTokenAccountInit,
+        MintAccount, MintInit, ProgramAccount, ProgramAccountInit, SignerAccount, SystemAccount,
+    },
+    state::{Config, LiquidityPool},
+};
+
+pub struct InitializeLiquidityPoolAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub liquidity_pool_pda: &'a AccountInfo,
+    pub lp_mint: &'a AccountInfo,
+    pub lst_vault: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub associated_token_program: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for InitializeLiquidityPoolAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, liquidity_pool_pda, lp_mint, lst_vault, lst_mint, token_program, associated_token_program, system_program, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+        SignerAccount::check(lp_mint)?;
+
+        assert_system_program(system_program)?;
+        assert_token_program(token_program)?;
+        assert_associated_token_program(associated_token_program)?;
+
+        SystemAccount::check(liquidity_pool_pda)?;
+
+        if !liquidity_pool_pda.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        SystemAccount::check(lp_mint)?;
+
+        if !lp_mint.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        Ok(Self {
+            admin,
+            config_pda,
+            liquidity_pool_pda,
+            lp_mint,
+            lst_vault,
+            lst_mint,
+            token_program,
+            associated_token_program,
+            system_program,
+            pool_state_pda,
+        })
+    }
+}
+
+/// Creates the instant-unstake liquidity pool: the pool PDA, its LP mint
+/// (authority: pool PDA) and its LST vault (the pool PDA's LST ATA).
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Admin
+/// 1. `[]` Config PDA
+/// 2. `[WRITE]` Liquidity pool PDA
+/// 3. `[WRITE, SIGNER]` LP mint
+/// 4. `[WRITE]` LST vault
+/// 5. `[]` LST mint
+/// 6. `[]` Token program
+/// 7. `[]` Associated token program
+/// 8. `[]` System program
+/// 9. `[WRITE]` Pool state PDA
+pub struct InitializeLiquidityPool<'a> {
+    pub accounts: InitializeLiquidityPoolAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for InitializeLiquidityPool<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: InitializeLiquidityPoolAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> InitializeLiquidityPool<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &11;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        drop(config_data);
+
+        let (expected_liquidity_pool_pda, liquidity_pool_bump) =
+            find_program_address(&[b"liquidity_pool"], &crate::ID);
+        if expected_liquidity_pool_pda != *self.accounts.liquidity_pool_pda.key() {
+            return Err(PinocchioError::InvalidLiquidityPoolPda.into());
+        }
+
+        let expected_lst_vault = find_program_address(
+            &[
+                self.accounts.liquidity_pool_pda.key(),
+                self.accounts.token_program.key(),
+                self.accounts.lst_mint.key(),
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .0;
+        if expected_lst_vault != *self.accounts.lst_vault.key() {
+            return Err(PinocchioError::InvalidLstVault.into());
+        }
+
+        let liquidity_pool_bump_binding = [liquidity_pool_bump];
+        let liquidity_pool_seeds = &[
+            Seed::from(b"liquidity_pool"),
+            Seed::from(&liquidity_pool_bump_binding),
+        ];
+
+        ProgramAccount::init::<LiquidityPool>(
+            self.accounts.admin,
+            self.accounts.liquidity_pool_pda,
+            liquidity_pool_seeds,
+            LiquidityPool::LEN,
+        )?;
+
+        MintAccount::init(
+            self.accounts.lp_mint,
+            self.accounts.admin,
+            9,
+            self.accounts.liquidity_pool_pda.key(),
+            None,
+        )?;
+
+        AssociatedTokenAccount::init(
+            self.accounts.lst_vault,
+            self.accounts.lst_mint,
+            self.accounts.admin,
+            self.accounts.liquidity_pool_pda,
+            self.accounts.system_program,
+            self.accounts.token_program,
+        )?;
+
+        let mut liquidity_pool_data = self.accounts.liquidity_pool_pda.try_borrow_mut_data()?;
+        LiquidityPool::load_mut(&mut liquidity_pool_data)?
+            .set_inner(*self.accounts.lp_mint.key(), *self.accounts.lst_vault.key());
+
+        Ok(())
+    }
+}
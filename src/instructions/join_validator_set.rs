@@ -0,0 +1,166 @@
+use pinocchio::{
+    account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+    pubkey::find_program_address,
+};
+use pinocchio_system::instructions::Transfer;
+
+use crate::{
+    errors::PinocchioError,
+    ids::assert_system_program,
+    instructions::helpers::{
+        vote_account_node_pubkey, AccountCheck, ProgramAccount, ProgramAccountInit, SignerAccount,
+        LAMPORTS_PER_SOL,
+    },
+    state::{ValidatorBond, VALIDATOR_BOND_EXITING},
+};
+
+/// Smallest bond a validator must hold before it can be activated.
+pub const MIN_VALIDATOR_BOND_LAMPORTS: u64 = 10 * LAMPORTS_PER_SOL;
+
+pub struct JoinValidatorSetAccounts<'a> {
+    pub operator: &'a AccountInfo,
+    pub validator_vote_account: &'a AccountInfo,
+    pub validator_bond_pda: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for JoinValidatorSetAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [operator, validator_vote_account, validator_bond_pda, system_program] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(operator)?;
+
+        assert_system_program(system_program)?;
+
+        Ok(Self {
+            operator,
+            validator_vote_account,
+            validator_bond_pda,
+            system_program,
+        })
+    }
+}
+
+pub struct JoinValidatorSetInstructionData {
+    pub bond_lamports: u64,
+}
+
+impl TryFrom<&[u8]> for JoinValidatorSetInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let bond_lamports = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+        if bond_lamports == 0 {
+            return Err(PinocchioError::BondBelowMinimum.into());
+        }
+
+        Ok(Self { bond_lamports })
+    }
+}
+
+/// Posts or tops up a validator's refundable bond, applying to join the
+/// validator set. The signer must be the vote account's validator identity.
+/// The bond PDA is created on first use in pending status, and the bond must
+/// reach `MIN_VALIDATOR_BOND_LAMPORTS` before the admin can activate it.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Validator identity (operator)
+/// 1. `[]` Validator vote account
+/// 2. `[WRITE]` Validator bond PDA
+/// 3. `[]` System program
+pub struct JoinValidatorSet<'a> {
+    pub accounts: JoinValidatorSetAccounts<'a>,
+    pub data: JoinValidatorSetInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for JoinValidatorSet<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: JoinValidatorSetAccounts::try_from(accounts)?,
+            data: JoinValidatorSetInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> JoinValidatorSet<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &18;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        if vote_account_node_pubkey(self.accounts.validator_vote_account)?
+            != *self.accounts.operator.key()
+        {
+            return Err(PinocchioError::NotValidatorOperator.into());
+        }
+
+        let (expected_validator_bond_pda, validator_bond_bump) = find_program_address(
+            &[
+                b"validator_bond",
+                self.accounts.validator_vote_account.key(),
+            ],
+            &crate::ID,
+        );
+        if expected_validator_bond_pda != *self.accounts.validator_bond_pda.key() {
+            return Err(PinocchioError::InvalidValidatorBondPda.into());
+        }
+
+        if self.accounts.validator_bond_pda.data_is_empty() {
+            let validator_bond_bump_binding = [validator_bond_bump];
+            let validator_bond_seeds = &[
+                Seed::from(b"validator_bond"),
+                Seed::from(self.accounts.validator_vote_account.key()),
+                Seed::from(&validator_bond_bump_binding),
+            ];
+            ProgramAccount::init::<ValidatorBond>(
+                self.accounts.operator,
+                self.accounts.validator_bond_pda,
+                validator_bond_seeds,
+                ValidatorBond::LEN,
+            )?;
+
+            let mut validator_bond_data = self.accounts.validator_bond_pda.try_borrow_mut_data()?;
+            ValidatorBond::load_mut(&mut validator_bond_data)?.set_inner(
+                *self.accounts.validator_vote_account.key(),
+                *self.accounts.operator.key(),
+            );
+        }
+
+        let mut validator_bond_data = self.accounts.validator_bond_pda.try_borrow_mut_data()?;
+        let validator_bond = ValidatorBond::load_mut(&mut validator_bond_data)?;
+
+        // The identity may have rotated since the bond was posted.
+        validator_bond.operator = *self.accounts.operator.key();
+
+        if validator_bond.status == VALIDATOR_BOND_EXITING {
+            return Err(PinocchioError::InvalidValidatorStatus.into());
+        }
+
+        validator_bond.bond_lamports = validator_bond
+            .bond_lamports
+            .checked_add(self.data.bond_lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        drop(validator_bond_data);
+
+        Transfer {
+            from: self.accounts.operator,
+            to: self.accounts.validator_bond_pda,
+            lamports: self.data.bond_lamports,
+        }
+        .invoke()?;
+
+        Ok(())
+    }
+}
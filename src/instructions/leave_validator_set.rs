@@ -0,0 +1,125 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    errors::PinocchioError,
+    instructions::helpers::{AccountCheck, AccountClose, ProgramAccount, SignerAccount},
+    state::{
+        ValidatorBond, ValidatorList, VALIDATOR_BOND_ACTIVE, VALIDATOR_BOND_EXITING,
+        VALIDATOR_BOND_PENDING,
+    },
+};
+
+/// Epochs an active validator's bond stays slashable after it starts leaving.
+pub const VALIDATOR_BOND_UNLOCK_EPOCHS: u64 = 2;
+
+pub struct LeaveValidatorSetAccounts<'a> {
+    pub operator: &'a AccountInfo,
+    pub validator_bond_pda: &'a AccountInfo,
+    pub validator_list_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for LeaveValidatorSetAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [operator, validator_bond_pda, validator_list_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(operator)?;
+
+        Ok(Self {
+            operator,
+            validator_bond_pda,
+            validator_list_pda,
+        })
+    }
+}
+
+/// Leaves the validator set and refunds the bond to the operator.
+///
+/// A pending validator's bond is refunded immediately. An active validator is
+/// removed from the validator set and its bond stays locked, and slashable,
+/// for `VALIDATOR_BOND_UNLOCK_EPOCHS` epochs; calling again once unlocked
+/// refunds it. Refunding closes the bond PDA.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Validator identity (operator)
+/// 1. `[WRITE]` Validator bond PDA
+/// 2. `[WRITE]` Validator list PDA
+pub struct LeaveValidatorSet<'a> {
+    pub accounts: LeaveValidatorSetAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for LeaveValidatorSet<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: LeaveValidatorSetAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> LeaveValidatorSet<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &21;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        if !self.accounts.validator_bond_pda.is_owned_by(&crate::ID) {
+            return Err(PinocchioError::InvalidValidatorBondPda.into());
+        }
+
+        let mut validator_bond_data = self.accounts.validator_bond_pda.try_borrow_mut_data()?;
+        let validator_bond = ValidatorBond::load_mut(&mut validator_bond_data)?;
+
+        let (expected_validator_bond_pda, _) = find_program_address(
+            &[b"validator_bond", &validator_bond.vote_account],
+            &crate::ID,
+        );
+        if expected_validator_bond_pda != *self.accounts.validator_bond_pda.key() {
+            return Err(PinocchioError::InvalidValidatorBondPda.into());
+        }
+
+        if validator_bond.operator != *self.accounts.operator.key() {
+            return Err(PinocchioError::NotValidatorOperator.into());
+        }
+
+        let epoch = Clock::get()?.epoch;
+
+        match validator_bond.status {
+            VALIDATOR_BOND_ACTIVE => {
+                let (expected_validator_list_pda, _) =
+                    find_program_address(&[b"validator_list"], &crate::ID);
+                if expected_validator_list_pda != *self.accounts.validator_list_pda.key() {
+                    return Err(PinocchioError::InvalidValidatorListPda.into());
+                }
+
+                let mut validator_list_data =
+                    self.accounts.validator_list_pda.try_borrow_mut_data()?;
+                ValidatorList::load_mut(&mut validator_list_data)?
+                    .remove(&validator_bond.vote_account)?;
+
+                validator_bond.status = VALIDATOR_BOND_EXITING;
+                validator_bond.unlock_epoch = epoch
+                    .checked_add(VALIDATOR_BOND_UNLOCK_EPOCHS)
+                    .ok_or(ProgramError::ArithmeticOverflow)?;
+
+                Ok(())
+            }
+            VALIDATOR_BOND_EXITING if epoch < validator_bond.unlock_epoch => {
+                Err(PinocchioError::ValidatorBondLocked.into())
+            }
+            VALIDATOR_BOND_PENDING | VALIDATOR_BOND_EXITING => {
+                drop(validator_bond_data);
+                ProgramAccount::close(self.accounts.validator_bond_pda, self.accounts.operator)
+            }
+            _ => Err(PinocchioError::InvalidValidatorStatus.into()),
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::find_program_address,
+};
+use pinocchio_token::{instructions::Transfer, state::Mint};
+
+use crate::{
+    errors::PinocchioError,
+    ids::assert_token_program,
+    instructions::helpers::{
+        check_config_pda, epoch_rate, managed_lamports, AccountCheck, SignerAccount,
+        LAMPORTS_PER_SOL,
+    },
+    math::{lst_to_lamports, unstake_lamports_out},
+    state::{Config, LiquidityPool},
+};
+
+/// Instant-unstake fee, in basis points, when the buffer stays at or above
+/// its target.
+pub const DEFAULT_UNSTAKE_FEE_MIN_BPS: u16 = 30;
+
+/// Instant-unstake fee, in basis points, when the buffer is drained.
+pub const DEFAULT_UNSTAKE_FEE_MAX_BPS: u16 = 300;
+
+/// Buffer size at which the minimum fee applies.
+pub const DEFAULT_LIQUIDITY_TARGET_LAMPORTS: u64 = 100 * LAMPORTS_PER_SOL;
+
+pub struct LiquidUnstakeAccounts<'a> {
+    pub unstaker: &'a AccountInfo,
+    pub unstaker_ata: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub liquidity_pool_pda: &'a AccountInfo,
+    pub lst_vault: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for LiquidUnstakeAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [unstaker, unstaker_ata, config_pda, liquidity_pool_pda, lst_vault, stake_account_main, stake_account_reserve, lst_mint, token_program, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(unstaker)?;
+
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            unstaker,
+            unstaker_ata,
+            config_pda,
+            liquidity_pool_pda,
+            lst_vault,
+            stake_account_main,
+            stake_account_reserve,
+            lst_mint,
+            token_program,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct LiquidUnstakeInstructionData {
+    pub lst_amount: u64,
+    pub min_lamports_out: u64,
+}
+
+impl TryFrom<&[u8]> for LiquidUnstakeInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 16 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let lst_amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let min_lamports_out = u64::from_le_bytes(data[8..16].try_into().unwrap());
+
+        if lst_amount == 0 {
+            return Err(PinocchioError::ZeroLiquidity.into());
+        }
+
+        Ok(Self {
+            lst_amount,
+            min_lamports_out,
+        })
+    }
+}
+
+/// Swaps LST for SOL from the liquidity pool immediately, skipping the stake
+/// cooldown. The LST is valued at the current exchange rate and a fee is kept
+/// by the pool for its liquidity providers.
+///
+/// The fee follows the buffer's utilization: `unstake_fee_min_bps` while the
+/// SOL left after the unstake is at least `liquidity_target_lamports`, rising
+/// linearly to `unstake_fee_max_bps` as the buffer drains.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Unstaker
+/// 1. `[WRITE]` Unstaker LST ATA
+/// 2. `[]` Config PDA
+/// 3. `[WRITE]` Liquidity pool PDA
+/// 4. `[WRITE]` LST vault
+/// 5. `[]` Stake account main
+/// 6. `[]` Stake account reserve
+/// 7. `[]` LST mint
+/// 8. `[]` Token program
+/// 9. `[]` Pool state PDA
+pub struct LiquidUnstake<'a> {
+    pub accounts: LiquidUnstakeAccounts<'a>,
+    pub data: LiquidUnstakeInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for LiquidUnstake<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: LiquidUnstakeAccounts::try_from(accounts)?,
+            data: LiquidUnstakeInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> LiquidUnstake<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &14;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        config.status()?.check_unstakes()?;
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        let unstake_fee_min_bps = config.unstake_fee_min_bps;
+        let unstake_fee_max_bps = config.unstake_fee_max_bps;
+        let liquidity_target_lamports = config.liquidity_target_lamports;
+
+        let validator_stake_lamports = config.validator_stake_lamports;
+        let cached_rate = epoch_rate(config)?;
+        drop(config_data);
+
+        let (expected_liquidity_pool_pda, _) =
+            find_program_address(&[b"liquidity_pool"], &crate::ID);
+        if expected_liquidity_pool_pda != *self.accounts.liquidity_pool_pda.key() {
+            return Err(PinocchioError::InvalidLiquidityPoolPda.into());
+        }
+
+        let mut liquidity_pool_data = self.accounts.liquidity_pool_pda.try_borrow_mut_data()?;
+        let liquidity_pool = LiquidityPool::load_mut(&mut liquidity_pool_data)?;
+
+        if liquidity_pool.lst_vault != *self.accounts.lst_vault.key() {
+            return Err(PinocchioError::InvalidLstVault.into());
+        }
+
+        let (total_lamports, lst_supply) = cached_rate.unwrap_or((
+            managed_lamports(
+                self.accounts.pool_state_pda,
+                self.accounts.stake_account_main,
+                self.accounts.stake_account_reserve,
+                validator_stake_lamports,
+            )?,
+            Mint::from_account_info(self.accounts.lst_mint)?.supply(),
+        ));
+        let lamports_value = lst_to_lamports(self.data.lst_amount, lst_supply, total_lamports)?;
+
+        let sol_reserves = liquidity_pool.sol_reserves;
+        let lamports_out = unstake_lamports_out(
+            lamports_value,
+            sol_reserves,
+            unstake_fee_min_bps,
+            unstake_fee_max_bps,
+            liquidity_target_lamports,
+        )?;
+
+        if lamports_out == 0 {
+            return Err(PinocchioError::ZeroLiquidity.into());
+        }
+
+        if lamports_out < self.data.min_lamports_out {
+            return Err(PinocchioError::SlippageExceeded.into());
+        }
+
+        liquidity_pool.sol_reserves = sol_reserves - lamports_out;
+
+        drop(liquidity_pool_data);
+
+        Transfer {
+            from: self.accounts.unstaker_ata,
+            to: self.accounts.lst_vault,
+            authority: self.accounts.unstaker,
+            amount: self.data.lst_amount,
+        }
+        .invoke()?;
+
+        *self.accounts.liquidity_pool_pda.try_borrow_mut_lamports()? -= lamports_out;
+        *self.accounts.unstaker.try_borrow_mut_lamports()? += lamports_out;
+
+        Ok(())
+    }
+}
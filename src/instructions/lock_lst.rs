@@ -0,0 +1,233 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+use pinocchio_token::instructions::Transfer;
+
+use crate::{
+    errors::PinocchioError,
+    ids::{
+        assert_associated_token_program, assert_system_program, assert_token_program,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+    },
+    instructions::helpers::{
+        check_config_pda, check_vote_escrow, AccountCheck, AssociatedTokenAccount,
+        AssociatedTokenAccountInit, ProgramAccount, ProgramAccountInit, SignerAccount,
+    },
+    math::lst_to_ve_weight,
+    state::{Config, VoteEscrow, VE_MAX_LOCK_SECONDS, VE_MIN_LOCK_SECONDS},
+};
+
+pub struct LockLstAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub owner_ata: &'a AccountInfo,
+    pub escrow_pda: &'a AccountInfo,
+    pub escrow_vault: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub associated_token_program: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for LockLstAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [owner, owner_ata, escrow_pda, escrow_vault, lst_mint, config_pda, token_program, associated_token_program, system_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(owner)?;
+
+        assert_system_program(system_program)?;
+        assert_token_program(token_program)?;
+        assert_associated_token_program(associated_token_program)?;
+
+        Ok(Self {
+            owner,
+            owner_ata,
+            escrow_pda,
+            escrow_vault,
+            lst_mint,
+            config_pda,
+            token_program,
+            associated_token_program,
+            system_program,
+        })
+    }
+}
+
+pub struct LockLstInstructionData {
+    pub lst_amount: u64,
+    pub unlock_ts: i64,
+}
+
+impl TryFrom<&[u8]> for LockLstInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 16 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            lst_amount: u64::from_le_bytes(data[0..8].try_into().unwrap()),
+            unlock_ts: i64::from_le_bytes(data[8..16].try_into().unwrap()),
+        })
+    }
+}
+
+/// Locks `lst_amount` of the owner's LST in their vote escrow PDA
+/// (`b"vote_escrow"`, owner) until `unlock_ts`, in exchange for voting weight
+/// recorded in the PDA. The LST is held in the escrow PDA's LST ATA. The
+/// first call creates the escrow; later calls add LST, push `unlock_ts` out,
+/// or both, but never bring it forward. `lst_amount` may be zero to only
+/// extend an existing lock.
+///
+/// The lock must end between `VE_MIN_LOCK_SECONDS` and `VE_MAX_LOCK_SECONDS`
+/// from now. The weight is the whole locked amount scaled by the time left
+/// until `unlock_ts` (see `lst_to_ve_weight`) and stays fixed until the next
+/// `LockLst`. The escrow is keyed by owner and has no transfer instruction, so
+/// the weight cannot change hands.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Owner
+/// 1. `[WRITE]` Owner LST ATA
+/// 2. `[WRITE]` Vote escrow PDA
+/// 3. `[WRITE]` Escrow vault (escrow PDA's LST ATA)
+/// 4. `[]` LST mint
+/// 5. `[]` Config PDA
+/// 6. `[]` Token program
+/// 7. `[]` Associated token program
+/// 8. `[]` System program
+pub struct LockLst<'a> {
+    pub accounts: LockLstAccounts<'a>,
+    pub data: LockLstInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for LockLst<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: LockLstAccounts::try_from(accounts)?,
+            data: LockLstInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> LockLst<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &61;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        if Config::load(&config_data)?.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+        drop(config_data);
+
+        let (expected_escrow_pda, escrow_bump) =
+            find_program_address(&[b"vote_escrow", self.accounts.owner.key()], &crate::ID);
+        if expected_escrow_pda != *self.accounts.escrow_pda.key() {
+            return Err(PinocchioError::InvalidVoteEscrowPda.into());
+        }
+
+        let expected_escrow_vault = find_program_address(
+            &[
+                self.accounts.escrow_pda.key(),
+                self.accounts.token_program.key(),
+                self.accounts.lst_mint.key(),
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .0;
+        if expected_escrow_vault != *self.accounts.escrow_vault.key() {
+            return Err(PinocchioError::InvalidAddress.into());
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let lock_seconds = self.data.unlock_ts.saturating_sub(now);
+        if !(VE_MIN_LOCK_SECONDS..=VE_MAX_LOCK_SECONDS).contains(&lock_seconds) {
+            return Err(PinocchioError::InvalidLockDuration.into());
+        }
+
+        let locked_amount = if self.accounts.escrow_pda.data_is_empty() {
+            if self.data.lst_amount == 0 {
+                return Err(PinocchioError::ZeroLiquidity.into());
+            }
+
+            let escrow_bump_binding = [escrow_bump];
+            let escrow_seeds = &[
+                Seed::from(b"vote_escrow"),
+                Seed::from(self.accounts.owner.key()),
+                Seed::from(&escrow_bump_binding),
+            ];
+
+            ProgramAccount::init::<VoteEscrow>(
+                self.accounts.owner,
+                self.accounts.escrow_pda,
+                escrow_seeds,
+                VoteEscrow::LEN,
+            )?;
+
+            AssociatedTokenAccount::init(
+                self.accounts.escrow_vault,
+                self.accounts.lst_mint,
+                self.accounts.owner,
+                self.accounts.escrow_pda,
+                self.accounts.system_program,
+                self.accounts.token_program,
+            )?;
+
+            self.data.lst_amount
+        } else {
+            check_vote_escrow(
+                self.accounts.escrow_pda,
+                self.accounts.escrow_vault,
+                self.accounts.owner,
+            )?;
+
+            let escrow_data = self.accounts.escrow_pda.try_borrow_data()?;
+            let escrow = VoteEscrow::load(&escrow_data)?;
+            if self.data.unlock_ts < escrow.unlock_ts {
+                return Err(PinocchioError::InvalidLockDuration.into());
+            }
+
+            escrow
+                .locked_amount
+                .checked_add(self.data.lst_amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?
+        };
+
+        if self.data.lst_amount > 0 {
+            Transfer {
+                from: self.accounts.owner_ata,
+                to: self.accounts.escrow_vault,
+                authority: self.accounts.owner,
+                amount: self.data.lst_amount,
+            }
+            .invoke()?;
+        }
+
+        let mut escrow_data = self.accounts.escrow_pda.try_borrow_mut_data()?;
+        VoteEscrow::load_mut(&mut escrow_data)?.set_inner(
+            *self.accounts.owner.key(),
+            *self.accounts.escrow_vault.key(),
+            locked_amount,
+            self.data.unlock_ts,
+            lst_to_ve_weight(locked_amount, lock_seconds),
+            now,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,249 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+};
+use pinocchio_system::instructions::Transfer;
+use pinocchio_token::{instructions::MintTo, state::Mint};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit, EVENT_DEPOSIT},
+    ids::{assert_system_program, assert_token_program},
+    instructions::helpers::{
+        check_config_pda, check_deposit_authority, check_pool_cap, check_wrapper, epoch_rate,
+        expect_lst_supply_change, managed_lamports, pool_lamports, record_inflow,
+        record_managed_change, record_principal, AccountCheck, SignerAccount, SupplyChange,
+        LAMPORTS_PER_SOL,
+    },
+    math::lamports_to_lst,
+    state::{Config, ManagedChange},
+};
+
+pub struct MintToWrapperAccounts<'a> {
+    pub wrapper_authority: &'a AccountInfo,
+    pub wrapper_vault: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+    /// Only needed in a permissioned pool.
+    pub deposit_authority: Option<&'a AccountInfo>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for MintToWrapperAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [wrapper_authority, wrapper_vault, config_pda, lst_mint, stake_account_main, stake_account_reserve, system_program, token_program, pool_state_pda, optional_accounts @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let deposit_authority = match optional_accounts {
+            [] => None,
+            [deposit_authority] => Some(deposit_authority),
+            _ => return Err(ProgramError::NotEnoughAccountKeys),
+        };
+
+        SignerAccount::check(wrapper_authority)?;
+
+        assert_system_program(system_program)?;
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            wrapper_authority,
+            wrapper_vault,
+            config_pda,
+            lst_mint,
+            stake_account_main,
+            stake_account_reserve,
+            system_program,
+            token_program,
+            pool_state_pda,
+            deposit_authority,
+        })
+    }
+}
+
+pub struct MintToWrapperInstructionData {
+    pub lamports: u64,
+}
+
+impl TryFrom<&[u8]> for MintToWrapperInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let lamports = u64::from_le_bytes(data.try_into().unwrap());
+        if lamports < LAMPORTS_PER_SOL {
+            return Err(PinocchioError::DepositBelowMinimum.into());
+        }
+
+        Ok(Self { lamports })
+    }
+}
+
+/// Deposits SOL for the approved wrapper program and mints the LST into its
+/// vault, so a restaking or yield-aggregator protocol can back its own token
+/// 1:1 with LST. Only callable by CPI from the wrapper program, signing with
+/// its `WRAPPER_AUTHORITY_SEED` PDA.
+///
+/// The deposit is priced like `Deposit` and counts against the per-epoch
+/// deposit cap and the pool cap, always rejecting the excess. In a
+/// permissioned pool the deposit authority must co-sign, as for `Deposit`;
+/// the wrapper passes it through from its own caller. The
+/// user-facing extras (idempotency keys, queued deposits, the early-supporter
+/// bonus) are left to the wrapper.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Wrapper authority PDA (pays the lamports)
+/// 1. `[WRITE]` Wrapper vault (the authority's LST ATA)
+/// 2. `[]` Config PDA
+/// 3. `[WRITE]` LST mint
+/// 4. `[]` Stake account main
+/// 5. `[WRITE]` Stake account reserve
+/// 6. `[]` System program
+/// 7. `[]` Token program
+/// 8. `[WRITE]` Pool state PDA
+/// 9. `[SIGNER]` Deposit authority (only in a permissioned pool)
+pub struct MintToWrapper<'a> {
+    pub accounts: MintToWrapperAccounts<'a>,
+    pub data: MintToWrapperInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for MintToWrapper<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: MintToWrapperAccounts::try_from(accounts)?,
+            data: MintToWrapperInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> MintToWrapper<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &48;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let bump = check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        config.status()?.check_deposits()?;
+
+        check_deposit_authority(
+            &config.deposit_authority,
+            self.accounts
+                .deposit_authority
+                .unwrap_or(self.accounts.wrapper_authority),
+        )?;
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        check_wrapper(
+            &config.wrapper_program,
+            self.accounts.wrapper_authority,
+            self.accounts.wrapper_vault,
+            self.accounts.lst_mint,
+            self.accounts.token_program,
+        )?;
+
+        record_inflow(
+            self.accounts.pool_state_pda,
+            config.deposit_cap_lamports,
+            self.data.lamports,
+            false,
+        )?;
+
+        let live_pool_lamports = pool_lamports(
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            config.validator_stake_lamports,
+        )?;
+        check_pool_cap(
+            config.max_pool_lamports,
+            live_pool_lamports,
+            self.data.lamports,
+        )?;
+
+        let managed = managed_lamports(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            config.validator_stake_lamports,
+        )?;
+
+        let (total_sol_in_pool, total_lst_supply) = epoch_rate(config)?.unwrap_or((
+            managed,
+            Mint::from_account_info(self.accounts.lst_mint)?.supply(),
+        ));
+        let lst_to_mint = lamports_to_lst(self.data.lamports, total_lst_supply, total_sol_in_pool)?;
+
+        Transfer {
+            from: self.accounts.wrapper_authority,
+            to: self.accounts.stake_account_reserve,
+            lamports: self.data.lamports,
+        }
+        .invoke()?;
+        record_managed_change(
+            self.accounts.pool_state_pda,
+            ManagedChange::Deposited(self.data.lamports),
+        )?;
+
+        let bump_binding = [bump];
+        let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
+
+        expect_lst_supply_change(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            SupplyChange::Minted(lst_to_mint),
+            || {
+                MintTo {
+                    mint: self.accounts.lst_mint,
+                    account: self.accounts.wrapper_vault,
+                    mint_authority: self.accounts.config_pda,
+                    amount: lst_to_mint,
+                }
+                .invoke_signed(&[Signer::from(config_seeds)])
+            },
+        )?;
+
+        record_principal(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            self.data.lamports,
+            lst_to_mint,
+        )?;
+
+        emit(
+            self.accounts.pool_state_pda,
+            EVENT_DEPOSIT,
+            &[
+                self.accounts.wrapper_authority.key(),
+                &self.data.lamports.to_le_bytes(),
+                &lst_to_mint.to_le_bytes(),
+            ],
+        )
+    }
+}
@@ -1,7 +1,101 @@
+pub mod accept_admin;
+pub mod add_liquidity;
+pub mod add_validator;
+pub mod adopt_reserve;
+pub mod burn_and_donate;
+pub mod burn_from_wrapper;
+pub mod buyback;
+pub mod cancel_split_handoff;
+pub mod cancel_unstake_order;
+pub mod claim_payout;
+pub mod claim_queued_deposit;
+pub mod claim_referral_fees;
+pub mod claim_vested;
+pub mod close_dca_schedule;
+pub mod close_deposit_key;
+pub mod close_deposit_session;
+pub mod close_used_quote;
+pub mod crank_dca;
 pub mod crank_initialize_reserve;
+pub mod crank_management_fee;
 pub mod crank_merge_reserve;
+pub mod crank_rebalance;
+pub mod crank_record_rate;
+pub mod crank_redelegate_main;
+pub mod crank_refill_buffer;
+pub mod crank_reward_payout;
 pub mod crank_split;
+pub mod crank_split_next;
+pub mod crank_split_single;
+pub mod crank_split_tranche;
+pub mod crank_update_rate;
+pub mod crank_validator_health;
+pub mod crank_validator_stake;
+pub mod create_dca_schedule;
+pub mod create_deposit_session;
+pub mod create_unstake_order;
+pub mod create_vesting;
 pub mod deposit;
+pub mod deposit_stake_account;
+pub mod deposit_with_session;
+pub mod distribute_treasury;
+pub mod enroll_payout;
+pub mod execute_split_handoff;
+pub mod exit_pool;
+pub mod fill_unstake_order;
+pub mod get_apy;
+pub mod get_balance_sheet;
+pub mod get_build_info;
+pub mod get_pending_cranks;
+pub mod get_stake_distribution;
+pub mod get_yield_report;
 pub mod helpers;
+pub mod import_balance;
+pub mod import_stake_account;
 pub mod initialize;
+pub mod initialize_liquidity_pool;
+pub mod instant_withdraw;
+pub mod join_validator_set;
+pub mod leave_validator_set;
+pub mod liquid_unstake;
+pub mod lock_lst;
+pub mod mint_to_wrapper;
+pub mod open_migration;
+pub mod propose_admin;
+pub mod propose_split_handoff;
+pub mod register_referrer;
+pub mod remove_liquidity;
+pub mod remove_validator;
+pub mod set_bonus_schedule;
+pub mod set_buyback_policy;
+pub mod set_crank_tip;
+pub mod set_delegation_strategy;
+pub mod set_deposit_authority;
+pub mod set_deposit_cap;
+pub mod set_deposit_fee;
+pub mod set_holding_rebate;
+pub mod set_instruction_enabled;
+pub mod set_main_headroom;
+pub mod set_management_fee;
+pub mod set_pool_cap;
+pub mod set_pool_status;
+pub mod set_quote_signer;
+pub mod set_referral_fee;
+pub mod set_reward_payout;
+pub mod set_split_minimum;
+pub mod set_trusted_caller;
+pub mod set_unstake_fee_params;
+pub mod set_validator_metrics;
+pub mod set_validator_stake_cap;
+pub mod set_validator_status;
+pub mod set_withdraw_fee;
+pub mod set_wrapper_program;
+pub mod simulate_params;
+pub mod slash_validator_bond;
+pub mod swap_buffer;
+pub mod unlock_lst;
+pub mod update_config;
+pub mod verify_authorities;
 pub mod withdraw;
+pub mod withdraw_payout;
+pub mod withdraw_stake;
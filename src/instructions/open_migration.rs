@@ -0,0 +1,171 @@
+use pinocchio::{
+    account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+    pubkey::find_program_address,
+};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    ids::{assert_associated_token_program, assert_system_program, assert_token_program},
+    instructions::helpers::{
+        check_config_pda, AccountCheck, AssociatedTokenAccount, AssociatedTokenAccountInit,
+        ProgramAccount, ProgramAccountInit, SignerAccount,
+    },
+    state::{Config, Migration},
+};
+
+pub struct OpenMigrationAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub migration_pda: &'a AccountInfo,
+    pub migration_vault: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub associated_token_program: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for OpenMigrationAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, migration_pda, migration_vault, lst_mint, token_program, associated_token_program, system_program, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        assert_system_program(system_program)?;
+        assert_token_program(token_program)?;
+        assert_associated_token_program(associated_token_program)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            migration_pda,
+            migration_vault,
+            lst_mint,
+            token_program,
+            associated_token_program,
+            system_program,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct OpenMigrationInstructionData {
+    pub source_pool_tokens: u64,
+}
+
+impl TryFrom<&[u8]> for OpenMigrationInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            source_pool_tokens: u64::from_le_bytes(data.try_into().unwrap()),
+        })
+    }
+}
+
+/// Starts importing an SPL stake pool whose pool token supply is
+/// `source_pool_tokens`. Creates the migration PDA (`b"migration"`) and its
+/// LST ATA, the migration vault. `ImportStakeAccount` then adopts the source
+/// pool's stake and mints LST for it into the vault, and `ImportBalance` pays
+/// each exported balance its share of the vault. A pool is migrated once, so
+/// the PDA is never closed.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Admin
+/// 1. `[]` Config PDA
+/// 2. `[WRITE]` Migration PDA
+/// 3. `[WRITE]` Migration vault (migration PDA's LST ATA)
+/// 4. `[]` LST mint
+/// 5. `[]` Token program
+/// 6. `[]` Associated token program
+/// 7. `[]` System program
+/// 8. `[WRITE]` Pool state PDA
+pub struct OpenMigration<'a> {
+    pub accounts: OpenMigrationAccounts<'a>,
+    pub data: OpenMigrationInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for OpenMigration<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: OpenMigrationAccounts::try_from(accounts)?,
+            data: OpenMigrationInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> OpenMigration<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &70;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+        drop(config_data);
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        if self.data.source_pool_tokens == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let (expected_migration_pda, migration_bump) =
+            find_program_address(&[b"migration"], &crate::ID);
+        if expected_migration_pda != *self.accounts.migration_pda.key() {
+            return Err(PinocchioError::InvalidMigrationPda.into());
+        }
+
+        let migration_bump_binding = [migration_bump];
+        let migration_seeds = &[
+            Seed::from(b"migration"),
+            Seed::from(&migration_bump_binding),
+        ];
+        ProgramAccount::init::<Migration>(
+            self.accounts.admin,
+            self.accounts.migration_pda,
+            migration_seeds,
+            Migration::LEN,
+        )?;
+
+        AssociatedTokenAccount::init(
+            self.accounts.migration_vault,
+            self.accounts.lst_mint,
+            self.accounts.admin,
+            self.accounts.migration_pda,
+            self.accounts.system_program,
+            self.accounts.token_program,
+        )?;
+
+        let mut migration_data = self.accounts.migration_pda.try_borrow_mut_data()?;
+        Migration::load_mut(&mut migration_data)?.source_pool_tokens = self.data.source_pool_tokens;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    instructions::helpers::{check_config_pda, record_param_change, AccountCheck, SignerAccount},
+    state::Config,
+};
+
+pub struct ProposeAdminAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub changelog_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ProposeAdminAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, changelog_pda, pool_state_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            changelog_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct ProposeAdminInstructionData {
+    pub pending_admin: Pubkey,
+}
+
+impl TryFrom<&[u8]> for ProposeAdminInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 32 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            pending_admin: data.try_into().unwrap(),
+        })
+    }
+}
+
+/// Proposes a new admin, who takes over once they sign `AcceptAdmin`. Until
+/// then the current admin keeps every right and can propose again, which
+/// replaces the pending admin. Zero cancels a pending transfer.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Changelog PDA
+/// 3. `[WRITE]` Pool state PDA
+pub struct ProposeAdmin<'a> {
+    pub accounts: ProposeAdminAccounts<'a>,
+    pub data: ProposeAdminInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for ProposeAdmin<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: ProposeAdminAccounts::try_from(accounts)?,
+            data: ProposeAdminInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> ProposeAdmin<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &84;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let old_value = config.pending_admin;
+
+        config.set_pending_admin(self.data.pending_admin);
+
+        record_param_change(
+            self.accounts.changelog_pda,
+            self.accounts.admin,
+            *Self::DISCRIMINATOR,
+            &[&old_value],
+            &[&self.data.pending_admin],
+        )
+    }
+}
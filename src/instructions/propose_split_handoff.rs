@@ -0,0 +1,173 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit, emit_admin_action, EVENT_SPLIT_HANDOFF, HANDOFF_PROPOSED, ROLE_ADMIN},
+    ids::assert_system_program,
+    instructions::helpers::{
+        check_config_pda, AccountCheck, ProgramAccount, ProgramAccountInit, SignerAccount,
+    },
+    state::{Config, SplitHandoff, SplitRecord, SPLIT_HANDOFF_DELAY_SECONDS},
+};
+
+pub struct ProposeSplitHandoffAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub new_owner: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub split_account: &'a AccountInfo,
+    pub split_record: &'a AccountInfo,
+    pub handoff_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ProposeSplitHandoffAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, new_owner, config_pda, split_account, split_record, handoff_pda, pool_state_pda, system_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+        SignerAccount::check(new_owner)?;
+
+        assert_system_program(system_program)?;
+
+        Ok(Self {
+            admin,
+            new_owner,
+            config_pda,
+            split_account,
+            split_record,
+            handoff_pda,
+            pool_state_pda,
+            system_program,
+        })
+    }
+}
+
+/// Proposes reassigning a split record, and with it the right to withdraw
+/// or keep splitting the split account, to `new_owner`, for an owner who
+/// lost their key and proved their claim off-chain. The admin and the new
+/// owner co-sign. The handoff is stored in a PDA (`b"split_handoff"`, split
+/// account) paid by the admin, and `ExecuteSplitHandoff` can apply it after
+/// `SPLIT_HANDOFF_DELAY_SECONDS`. Until then the current owner or the admin
+/// can cancel it with `CancelSplitHandoff`, so a handoff proposed against an
+/// owner who still holds their key is stopped by them.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Admin
+/// 1. `[SIGNER]` New owner
+/// 2. `[]` Config PDA
+/// 3. `[]` Split account
+/// 4. `[]` Split record PDA
+/// 5. `[WRITE]` Split handoff PDA
+/// 6. `[WRITE]` Pool state PDA
+/// 7. `[]` System program
+pub struct ProposeSplitHandoff<'a> {
+    pub accounts: ProposeSplitHandoffAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ProposeSplitHandoff<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: ProposeSplitHandoffAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> ProposeSplitHandoff<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &64;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        if Config::load(&config_data)?.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+        drop(config_data);
+
+        let expected_split_record = find_program_address(
+            &[b"split_record", self.accounts.split_account.key()],
+            &crate::ID,
+        )
+        .0;
+        if expected_split_record != *self.accounts.split_record.key() {
+            return Err(PinocchioError::InvalidSplitRecordPda.into());
+        }
+
+        ProgramAccount::check(self.accounts.split_record)?;
+
+        let split_record_data = self.accounts.split_record.try_borrow_data()?;
+        if SplitRecord::load(&split_record_data)?.owner == *self.accounts.new_owner.key() {
+            return Err(PinocchioError::InvalidAddress.into());
+        }
+        drop(split_record_data);
+
+        let (expected_handoff_pda, handoff_bump) = find_program_address(
+            &[b"split_handoff", self.accounts.split_account.key()],
+            &crate::ID,
+        );
+        if expected_handoff_pda != *self.accounts.handoff_pda.key() {
+            return Err(PinocchioError::InvalidSplitHandoffPda.into());
+        }
+
+        let handoff_bump_binding = [handoff_bump];
+        let handoff_seeds = &[
+            Seed::from(b"split_handoff"),
+            Seed::from(self.accounts.split_account.key()),
+            Seed::from(&handoff_bump_binding),
+        ];
+
+        ProgramAccount::init::<SplitHandoff>(
+            self.accounts.admin,
+            self.accounts.handoff_pda,
+            handoff_seeds,
+            SplitHandoff::LEN,
+        )?;
+
+        let executable_at = Clock::get()?
+            .unix_timestamp
+            .checked_add(SPLIT_HANDOFF_DELAY_SECONDS)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let mut handoff_data = self.accounts.handoff_pda.try_borrow_mut_data()?;
+        SplitHandoff::load_mut(&mut handoff_data)?.set_inner(
+            *self.accounts.split_account.key(),
+            *self.accounts.new_owner.key(),
+            *self.accounts.admin.key(),
+            executable_at,
+        );
+        drop(handoff_data);
+
+        emit(
+            self.accounts.pool_state_pda,
+            EVENT_SPLIT_HANDOFF,
+            &[
+                self.accounts.split_account.key(),
+                self.accounts.new_owner.key(),
+                &[HANDOFF_PROPOSED],
+                &executable_at.to_le_bytes(),
+            ],
+        )
+    }
+}
@@ -0,0 +1,99 @@
+use pinocchio::{
+    account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+    pubkey::find_program_address,
+};
+
+use crate::{
+    errors::PinocchioError,
+    ids::assert_system_program,
+    instructions::helpers::{
+        AccountCheck, ProgramAccount, ProgramAccountInit, SignerAccount, SystemAccount,
+    },
+    state::ReferrerRecord,
+};
+
+pub struct RegisterReferrerAccounts<'a> {
+    pub referrer: &'a AccountInfo,
+    pub referrer_pda: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RegisterReferrerAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [referrer, referrer_pda, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(referrer)?;
+
+        assert_system_program(system_program)?;
+
+        SystemAccount::check(referrer_pda)?;
+
+        if !referrer_pda.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        Ok(Self {
+            referrer,
+            referrer_pda,
+            system_program,
+        })
+    }
+}
+
+/// Creates the referrer's registry entry (`b"referrer"`, referrer), so
+/// deposits can name it and the referrer can claim their fees without the
+/// admin. Anyone may register themselves, paying the rent.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Referrer
+/// 1. `[WRITE]` Referrer PDA
+/// 2. `[]` System program
+pub struct RegisterReferrer<'a> {
+    pub accounts: RegisterReferrerAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RegisterReferrer<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = RegisterReferrerAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> RegisterReferrer<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &53;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let (expected_referrer_pda, referrer_bump) =
+            find_program_address(&[b"referrer", self.accounts.referrer.key()], &crate::ID);
+        if expected_referrer_pda != *self.accounts.referrer_pda.key() {
+            return Err(PinocchioError::InvalidReferrerPda.into());
+        }
+
+        let referrer_bump_binding = [referrer_bump];
+        let referrer_seeds = &[
+            Seed::from(b"referrer"),
+            Seed::from(self.accounts.referrer.key()),
+            Seed::from(&referrer_bump_binding),
+        ];
+
+        ProgramAccount::init::<ReferrerRecord>(
+            self.accounts.referrer,
+            self.accounts.referrer_pda,
+            referrer_seeds,
+            ReferrerRecord::LEN,
+        )?;
+
+        let mut referrer_data = self.accounts.referrer_pda.try_borrow_mut_data()?;
+        ReferrerRecord::load_mut(&mut referrer_data)?.set_inner(*self.accounts.referrer.key());
+
+        Ok(())
+    }
+}
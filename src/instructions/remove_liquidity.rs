@@ -0,0 +1,216 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::find_program_address,
+};
+use pinocchio_token::{
+    instructions::{Burn, Transfer},
+    state::{Mint, TokenAccount},
+};
+
+use crate::{
+    errors::PinocchioError,
+    ids::assert_token_program,
+    instructions::helpers::{
+        check_config_pda, expect_supply_change, AccountCheck, SignerAccount, SupplyChange,
+    },
+    state::{Config, LiquidityPool},
+};
+
+pub struct RemoveLiquidityAccounts<'a> {
+    pub provider: &'a AccountInfo,
+    pub provider_lp_ata: &'a AccountInfo,
+    pub provider_lst_ata: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub liquidity_pool_pda: &'a AccountInfo,
+    pub lp_mint: &'a AccountInfo,
+    pub lst_vault: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RemoveLiquidityAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [provider, provider_lp_ata, provider_lst_ata, config_pda, liquidity_pool_pda, lp_mint, lst_vault, lst_mint, token_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(provider)?;
+
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            provider,
+            provider_lp_ata,
+            provider_lst_ata,
+            config_pda,
+            liquidity_pool_pda,
+            lp_mint,
+            lst_vault,
+            lst_mint,
+            token_program,
+        })
+    }
+}
+
+pub struct RemoveLiquidityInstructionData {
+    pub lp_amount: u64,
+}
+
+impl TryFrom<&[u8]> for RemoveLiquidityInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let lp_amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+        if lp_amount == 0 {
+            return Err(PinocchioError::ZeroLiquidity.into());
+        }
+
+        Ok(Self { lp_amount })
+    }
+}
+
+/// Burns LP tokens and pays out the provider's pro-rata share of the pool's
+/// SOL reserves and LST vault, including any fees accrued since deposit. The
+/// share of SOL cooling down in the buffer stake account is paid in SOL.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Provider
+/// 1. `[WRITE]` Provider LP ATA
+/// 2. `[WRITE]` Provider LST ATA
+/// 3. `[]` Config PDA
+/// 4. `[WRITE]` Liquidity pool PDA
+/// 5. `[WRITE]` LP mint
+/// 6. `[WRITE]` LST vault
+/// 7. `[]` LST mint
+/// 8. `[]` Token program
+pub struct RemoveLiquidity<'a> {
+    pub accounts: RemoveLiquidityAccounts<'a>,
+    pub data: RemoveLiquidityInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for RemoveLiquidity<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: RemoveLiquidityAccounts::try_from(accounts)?,
+            data: RemoveLiquidityInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> RemoveLiquidity<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &13;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        if Config::load(&config_data)?.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+        drop(config_data);
+
+        let (expected_liquidity_pool_pda, liquidity_pool_bump) =
+            find_program_address(&[b"liquidity_pool"], &crate::ID);
+        if expected_liquidity_pool_pda != *self.accounts.liquidity_pool_pda.key() {
+            return Err(PinocchioError::InvalidLiquidityPoolPda.into());
+        }
+
+        let mut liquidity_pool_data = self.accounts.liquidity_pool_pda.try_borrow_mut_data()?;
+        let liquidity_pool = LiquidityPool::load_mut(&mut liquidity_pool_data)?;
+
+        if liquidity_pool.lp_mint != *self.accounts.lp_mint.key() {
+            return Err(PinocchioError::InvalidLpMint.into());
+        }
+
+        if liquidity_pool.lst_vault != *self.accounts.lst_vault.key() {
+            return Err(PinocchioError::InvalidLstVault.into());
+        }
+
+        let provider_lst_ata = TokenAccount::from_account_info(self.accounts.provider_lst_ata)?;
+        if provider_lst_ata.owner() != self.accounts.provider.key()
+            || provider_lst_ata.mint() != self.accounts.lst_mint.key()
+        {
+            return Err(PinocchioError::InvalidProviderAta.into());
+        }
+        drop(provider_lst_ata);
+
+        let lp_supply = Mint::from_account_info(self.accounts.lp_mint)?.supply();
+        if lp_supply == 0 {
+            return Err(PinocchioError::ZeroLiquidity.into());
+        }
+
+        let lst_vault_amount = TokenAccount::from_account_info(self.accounts.lst_vault)?.amount();
+
+        let sol_out = (liquidity_pool.sol_reserves as u128)
+            .checked_add(liquidity_pool.pending_unstake_lamports as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .checked_mul(self.data.lp_amount as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .checked_div(lp_supply as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)? as u64;
+
+        let lst_out = (lst_vault_amount as u128)
+            .checked_mul(self.data.lp_amount as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .checked_div(lp_supply as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)? as u64;
+
+        liquidity_pool.sol_reserves = liquidity_pool
+            .sol_reserves
+            .checked_sub(sol_out)
+            .ok_or(PinocchioError::InsufficientLiquidity)?;
+
+        drop(liquidity_pool_data);
+
+        expect_supply_change(
+            self.accounts.lp_mint,
+            SupplyChange::Burned(self.data.lp_amount),
+            || {
+                Burn {
+                    account: self.accounts.provider_lp_ata,
+                    mint: self.accounts.lp_mint,
+                    authority: self.accounts.provider,
+                    amount: self.data.lp_amount,
+                }
+                .invoke()
+            },
+        )?;
+
+        let liquidity_pool_bump_binding = [liquidity_pool_bump];
+        let liquidity_pool_seeds = &[
+            Seed::from(b"liquidity_pool"),
+            Seed::from(&liquidity_pool_bump_binding),
+        ];
+
+        if lst_out > 0 {
+            Transfer {
+                from: self.accounts.lst_vault,
+                to: self.accounts.provider_lst_ata,
+                authority: self.accounts.liquidity_pool_pda,
+                amount: lst_out,
+            }
+            .invoke_signed(&[Signer::from(liquidity_pool_seeds)])?;
+        }
+
+        if sol_out > 0 {
+            *self.accounts.liquidity_pool_pda.try_borrow_mut_lamports()? -= sol_out;
+            *self.accounts.provider.try_borrow_mut_lamports()? += sol_out;
+        }
+
+        Ok(())
+    }
+}
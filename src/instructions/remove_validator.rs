@@ -0,0 +1,264 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    ids::{
+        assert_clock_sysvar, assert_stake_history_sysvar, assert_stake_program, STAKE_PROGRAM_ID,
+    },
+    instructions::helpers::{
+        accrue_rewards, check_config_pda, check_validator_list, check_validator_stake,
+        record_managed_change, record_pool_stake, record_validator_stake, stake_account_breakdown,
+        stake_authorities, AccountCheck, ProgramAccount, SignerAccount, StakeAccountDeactivate,
+        StakeAccountWithdraw,
+    },
+    state::{Config, ManagedChange, ValidatorList},
+};
+
+pub struct RemoveValidatorAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub validator_list_pda: &'a AccountInfo,
+    pub validator_stake_pda: &'a AccountInfo,
+    pub validator_vote_account: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub clock_sysvar: &'a AccountInfo,
+    pub history_sysvar: &'a AccountInfo,
+    pub stake_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RemoveValidatorAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, validator_list_pda, validator_stake_pda, validator_vote_account, stake_account_reserve, clock_sysvar, history_sysvar, stake_program, pool_state_pda, stake_account_main] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        assert_clock_sysvar(clock_sysvar)?;
+        assert_stake_history_sysvar(history_sysvar)?;
+        assert_stake_program(stake_program)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            validator_list_pda,
+            validator_stake_pda,
+            validator_vote_account,
+            stake_account_reserve,
+            clock_sysvar,
+            history_sysvar,
+            stake_program,
+            pool_state_pda,
+            stake_account_main,
+        })
+    }
+}
+
+/// Removes a vote account from the validator list, draining its validator
+/// stake PDA back into the reserve first.
+///
+/// While the PDA holds active or activating stake, deactivates it, records
+/// its stake and leaves the validator listed, so its stake stays in the
+/// exchange rate while cooling down. Calling again before the stake has
+/// cooled down fails with `ValidatorStakeCoolingDown`. Once nothing is
+/// delegated, withdraws the PDA's lamports, its rent included, into the
+/// reserve, crediting what was not recorded as stake to the managed
+/// lamports, and removes the validator. A
+/// validator's bond is left as it is.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Validator list PDA
+/// 3. `[WRITE]` Validator stake PDA
+/// 4. `[]` Validator vote account
+/// 5. `[WRITE]` Stake account reserve
+/// 6. `[]` Clock sysvar
+/// 7. `[]` Stake history sysvar
+/// 8. `[]` Stake program
+/// 9. `[WRITE]` Pool state PDA
+/// 10. `[]` Stake account main
+pub struct RemoveValidator<'a> {
+    pub accounts: RemoveValidatorAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RemoveValidator<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            accounts: RemoveValidatorAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> RemoveValidator<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &80;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let bump = check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+        drop(config_data);
+
+        let vote_account = self.accounts.validator_vote_account.key();
+        check_validator_list(self.accounts.validator_list_pda)?;
+        let validator_stake_bump =
+            check_validator_stake(self.accounts.validator_stake_pda, vote_account)?;
+
+        let validator_list_data = self.accounts.validator_list_pda.try_borrow_data()?;
+        if !ValidatorList::load(&validator_list_data)?.contains(vote_account) {
+            return Err(PinocchioError::ValidatorNotInList.into());
+        }
+        drop(validator_list_data);
+
+        let bump_binding = [bump];
+        let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
+
+        let staked = self
+            .accounts
+            .validator_stake_pda
+            .is_owned_by(&STAKE_PROGRAM_ID);
+        if staked {
+            let breakdown =
+                stake_account_breakdown(self.accounts.validator_stake_pda, Clock::get()?.epoch)?;
+
+            if breakdown.active > 0 || breakdown.activating > 0 {
+                ProgramAccount::deactivate_stake_account(
+                    self.accounts.validator_stake_pda,
+                    self.accounts.clock_sysvar,
+                    self.accounts.config_pda,
+                    config_seeds,
+                )?;
+
+                record_validator_stake(
+                    self.accounts.config_pda,
+                    self.accounts.validator_list_pda,
+                    self.accounts.validator_stake_pda,
+                    vote_account,
+                )?;
+                return self.accrue_rewards().map(|_| ());
+            }
+
+            if breakdown.deactivating > 0 {
+                return Err(PinocchioError::ValidatorStakeCoolingDown.into());
+            }
+        }
+
+        self.accrue_rewards()?;
+
+        if staked {
+            // The recorded stake is already managed; the rest, rent included,
+            // is credited as it joins the reserve.
+            let validator_list_data = self.accounts.validator_list_pda.try_borrow_data()?;
+            let recorded = ValidatorList::load(&validator_list_data)?
+                .entries()
+                .iter()
+                .find(|entry| entry.vote_account == *vote_account)
+                .map_or(0, |entry| entry.stake_lamports);
+            drop(validator_list_data);
+            let surplus = self
+                .accounts
+                .validator_stake_pda
+                .lamports()
+                .saturating_sub(recorded);
+
+            self.drain(validator_stake_bump, config_seeds)?;
+            record_managed_change(
+                self.accounts.pool_state_pda,
+                ManagedChange::Deposited(surplus),
+            )?;
+        }
+
+        let mut validator_list_data = self.accounts.validator_list_pda.try_borrow_mut_data()?;
+        let validator_list = ValidatorList::load_mut(&mut validator_list_data)?;
+        let entry = validator_list
+            .find_mut(vote_account)
+            .ok_or(PinocchioError::ValidatorNotInList)?;
+
+        let epoch = Clock::get()?.epoch;
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+        config.record_validator_stake(entry, 0, epoch)?;
+        validator_list.remove(vote_account)?;
+        config.stamp_validator_stake(validator_list, epoch);
+        let validator_stake_lamports = config.validator_stake_lamports;
+        drop(config_data);
+
+        record_pool_stake(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )
+    }
+
+    /// Credits the rewards the pool's stake earned at the config's recorded
+    /// validator stake, see `accrue_rewards`.
+    fn accrue_rewards(&self) -> Result<u64, ProgramError> {
+        accrue_rewards(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            Config::load(&self.accounts.config_pda.try_borrow_data()?)?.validator_stake_lamports,
+        )
+    }
+
+    /// Withdraws every lamport of the undelegated validator stake PDA into
+    /// the reserve, closing it.
+    fn drain(&self, validator_stake_bump: u8, config_seeds: &[Seed]) -> Result<(), ProgramError> {
+        let validator_stake_bump_binding = [validator_stake_bump];
+        let validator_stake_seeds = &[
+            Seed::from(b"validator_stake"),
+            Seed::from(self.accounts.validator_vote_account.key()),
+            Seed::from(&validator_stake_bump_binding),
+        ];
+
+        // An uninitialized stake account is its own withdraw authority.
+        let (withdraw_authority, seeds): (&AccountInfo, &[Seed]) =
+            match stake_authorities(self.accounts.validator_stake_pda)? {
+                Some(_) => (self.accounts.config_pda, config_seeds),
+                None => (self.accounts.validator_stake_pda, validator_stake_seeds),
+            };
+
+        ProgramAccount::withdraw_stake_account(
+            self.accounts.validator_stake_pda,
+            self.accounts.stake_account_reserve,
+            self.accounts.clock_sysvar,
+            self.accounts.history_sysvar,
+            withdraw_authority,
+            seeds,
+        )
+    }
+}
@@ -0,0 +1,122 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    instructions::helpers::{check_config_pda, record_param_change, AccountCheck, SignerAccount},
+    state::Config,
+};
+
+pub struct SetBonusScheduleAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub changelog_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetBonusScheduleAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, changelog_pda, pool_state_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            changelog_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct SetBonusScheduleInstructionData {
+    pub bonus_bps: u16,
+    pub bonus_cutoff_epoch: u64,
+}
+
+impl TryFrom<&[u8]> for SetBonusScheduleInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 10 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let bonus_bps = u16::from_le_bytes(data[0..2].try_into().unwrap());
+        if bonus_bps > 10_000 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            bonus_bps,
+            bonus_cutoff_epoch: u64::from_le_bytes(data[2..10].try_into().unwrap()),
+        })
+    }
+}
+
+/// Sets the early-supporter bonus: `Deposit`s before `bonus_cutoff_epoch`
+/// receive an extra `bonus_bps` of the LST they mint from the treasury.
+/// Setting a cutoff in the past or zero bps ends the program.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Changelog PDA
+/// 3. `[WRITE]` Pool state PDA
+pub struct SetBonusSchedule<'a> {
+    pub accounts: SetBonusScheduleAccounts<'a>,
+    pub data: SetBonusScheduleInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetBonusSchedule<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetBonusScheduleAccounts::try_from(accounts)?,
+            data: SetBonusScheduleInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetBonusSchedule<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &45;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let old_value = (config.bonus_bps, config.bonus_cutoff_epoch);
+
+        config.bonus_bps = self.data.bonus_bps;
+        config.bonus_cutoff_epoch = self.data.bonus_cutoff_epoch;
+
+        record_param_change(
+            self.accounts.changelog_pda,
+            self.accounts.admin,
+            *Self::DISCRIMINATOR,
+            &[&old_value.0.to_le_bytes(), &old_value.1.to_le_bytes()],
+            &[
+                &self.data.bonus_bps.to_le_bytes(),
+                &self.data.bonus_cutoff_epoch.to_le_bytes(),
+            ],
+        )
+    }
+}
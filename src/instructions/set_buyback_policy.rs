@@ -0,0 +1,114 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    instructions::helpers::{check_config_pda, record_param_change, AccountCheck, SignerAccount},
+    state::{Config, BUYBACK_BURN, BUYBACK_TO_TREASURY},
+};
+
+pub struct SetBuybackPolicyAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub changelog_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetBuybackPolicyAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, changelog_pda, pool_state_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            changelog_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct SetBuybackPolicyInstructionData {
+    pub buyback_policy: u8,
+}
+
+impl TryFrom<&[u8]> for SetBuybackPolicyInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 1 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if data[0] != BUYBACK_TO_TREASURY && data[0] != BUYBACK_BURN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            buyback_policy: data[0],
+        })
+    }
+}
+
+/// Chooses whether `Buyback` locks the LST it buys in the treasury or burns
+/// it.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Changelog PDA
+/// 3. `[WRITE]` Pool state PDA
+pub struct SetBuybackPolicy<'a> {
+    pub accounts: SetBuybackPolicyAccounts<'a>,
+    pub data: SetBuybackPolicyInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetBuybackPolicy<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetBuybackPolicyAccounts::try_from(accounts)?,
+            data: SetBuybackPolicyInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetBuybackPolicy<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &40;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let old_value = config.buyback_policy;
+
+        config.buyback_policy = self.data.buyback_policy;
+
+        record_param_change(
+            self.accounts.changelog_pda,
+            self.accounts.admin,
+            *Self::DISCRIMINATOR,
+            &[&[old_value]],
+            &[&[self.data.buyback_policy]],
+        )
+    }
+}
@@ -0,0 +1,111 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    instructions::helpers::{check_config_pda, record_param_change, AccountCheck, SignerAccount},
+    state::Config,
+};
+
+pub struct SetCrankTipAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub changelog_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetCrankTipAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, changelog_pda, pool_state_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            changelog_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct SetCrankTipInstructionData {
+    pub crank_tip_lamports: u64,
+}
+
+impl TryFrom<&[u8]> for SetCrankTipInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            crank_tip_lamports: u64::from_le_bytes(data.try_into().unwrap()),
+        })
+    }
+}
+
+/// Sets the tip the fee vault pays to the cranker of a successful
+/// `CrankInitializeReserve` or `CrankMergeReserve`, so keepers are not
+/// paying transaction fees out of pocket. Zero, the default, pays nothing.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Changelog PDA
+/// 3. `[WRITE]` Pool state PDA
+pub struct SetCrankTip<'a> {
+    pub accounts: SetCrankTipAccounts<'a>,
+    pub data: SetCrankTipInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetCrankTip<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetCrankTipAccounts::try_from(accounts)?,
+            data: SetCrankTipInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetCrankTip<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &94;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let old_value = config.crank_tip_lamports;
+
+        config.set_crank_tip(self.data.crank_tip_lamports);
+
+        record_param_change(
+            self.accounts.changelog_pda,
+            self.accounts.admin,
+            *Self::DISCRIMINATOR,
+            &[&old_value.to_le_bytes()],
+            &[&self.data.crank_tip_lamports.to_le_bytes()],
+        )
+    }
+}
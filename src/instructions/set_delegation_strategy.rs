@@ -0,0 +1,111 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    delegation_strategy::DelegationStrategyKind,
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    instructions::helpers::{check_config_pda, record_param_change, AccountCheck, SignerAccount},
+    state::Config,
+};
+
+pub struct SetDelegationStrategyAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub changelog_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetDelegationStrategyAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, changelog_pda, pool_state_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            changelog_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct SetDelegationStrategyInstructionData {
+    pub delegation_strategy: DelegationStrategyKind,
+}
+
+impl TryFrom<&[u8]> for SetDelegationStrategyInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 1 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            delegation_strategy: DelegationStrategyKind::try_from(data[0])?,
+        })
+    }
+}
+
+/// Selects the strategy used to compute validator stake targets: `0` even
+/// split, `1` weight-based, `2` score-based.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Changelog PDA
+/// 3. `[WRITE]` Pool state PDA
+pub struct SetDelegationStrategy<'a> {
+    pub accounts: SetDelegationStrategyAccounts<'a>,
+    pub data: SetDelegationStrategyInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetDelegationStrategy<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetDelegationStrategyAccounts::try_from(accounts)?,
+            data: SetDelegationStrategyInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetDelegationStrategy<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &23;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let old_value = config.delegation_strategy;
+
+        config.set_delegation_strategy(self.data.delegation_strategy);
+
+        record_param_change(
+            self.accounts.changelog_pda,
+            self.accounts.admin,
+            *Self::DISCRIMINATOR,
+            &[&[old_value]],
+            &[&[self.data.delegation_strategy as u8]],
+        )
+    }
+}
@@ -0,0 +1,122 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    instructions::helpers::{check_config_pda, record_param_change, AccountCheck, SignerAccount},
+    state::{Config, DEPOSIT_CAP_QUEUE, DEPOSIT_CAP_REJECT},
+};
+
+pub struct SetDepositCapAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub changelog_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetDepositCapAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, changelog_pda, pool_state_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            changelog_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct SetDepositCapInstructionData {
+    pub deposit_cap_lamports: u64,
+    pub deposit_cap_mode: u8,
+}
+
+impl TryFrom<&[u8]> for SetDepositCapInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 9 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if data[8] != DEPOSIT_CAP_REJECT && data[8] != DEPOSIT_CAP_QUEUE {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            deposit_cap_lamports: u64::from_le_bytes(data[0..8].try_into().unwrap()),
+            deposit_cap_mode: data[8],
+        })
+    }
+}
+
+/// Caps how many lamports deposits may add to the reserve per epoch, so the
+/// validator is not hit by one large activation. Zero removes the cap. The
+/// mode decides whether a `Deposit` over the cap fails or queues the excess
+/// for `ClaimQueuedDeposit`.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Changelog PDA
+/// 3. `[WRITE]` Pool state PDA
+pub struct SetDepositCap<'a> {
+    pub accounts: SetDepositCapAccounts<'a>,
+    pub data: SetDepositCapInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetDepositCap<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetDepositCapAccounts::try_from(accounts)?,
+            data: SetDepositCapInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetDepositCap<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &43;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let old_value = (config.deposit_cap_lamports, config.deposit_cap_mode);
+
+        config.deposit_cap_lamports = self.data.deposit_cap_lamports;
+        config.deposit_cap_mode = self.data.deposit_cap_mode;
+
+        record_param_change(
+            self.accounts.changelog_pda,
+            self.accounts.admin,
+            *Self::DISCRIMINATOR,
+            &[&old_value.0.to_le_bytes(), &[old_value.1]],
+            &[
+                &self.data.deposit_cap_lamports.to_le_bytes(),
+                &[self.data.deposit_cap_mode],
+            ],
+        )
+    }
+}
@@ -0,0 +1,114 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    instructions::helpers::{check_config_pda, record_param_change, AccountCheck, SignerAccount},
+    state::Config,
+};
+
+pub struct SetDepositFeeAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub changelog_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetDepositFeeAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, changelog_pda, pool_state_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            changelog_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct SetDepositFeeInstructionData {
+    pub deposit_fee_bps: u16,
+}
+
+impl TryFrom<&[u8]> for SetDepositFeeInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 2 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let deposit_fee_bps = u16::from_le_bytes(data.try_into().unwrap());
+        if deposit_fee_bps > 10_000 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { deposit_fee_bps })
+    }
+}
+
+/// Sets the deposit fee: every `Deposit` mints `deposit_fee_bps` of its LST
+/// into the treasury ATA instead of to the depositor. The fee is taken after
+/// any referral fee. Zero, the default, charges nothing.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Changelog PDA
+/// 3. `[WRITE]` Pool state PDA
+pub struct SetDepositFee<'a> {
+    pub accounts: SetDepositFeeAccounts<'a>,
+    pub data: SetDepositFeeInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetDepositFee<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetDepositFeeAccounts::try_from(accounts)?,
+            data: SetDepositFeeInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetDepositFee<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &86;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let old_value = config.deposit_fee_bps;
+
+        config.set_deposit_fee(self.data.deposit_fee_bps);
+
+        record_param_change(
+            self.accounts.changelog_pda,
+            self.accounts.admin,
+            *Self::DISCRIMINATOR,
+            &[&old_value.to_le_bytes()],
+            &[&self.data.deposit_fee_bps.to_le_bytes()],
+        )
+    }
+}
@@ -0,0 +1,128 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    instructions::helpers::{check_config_pda, record_param_change, AccountCheck, SignerAccount},
+    state::Config,
+};
+
+pub struct SetHoldingRebateAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub changelog_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetHoldingRebateAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, changelog_pda, pool_state_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            changelog_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct SetHoldingRebateInstructionData {
+    pub holding_rebate_bps: u16,
+    pub holding_rebate_epochs: u64,
+}
+
+impl TryFrom<&[u8]> for SetHoldingRebateInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 10 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let holding_rebate_bps = u16::from_le_bytes(data[0..2].try_into().unwrap());
+        if holding_rebate_bps > 10_000 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let holding_rebate_epochs = u64::from_le_bytes(data[2..10].try_into().unwrap());
+
+        Ok(Self {
+            holding_rebate_bps,
+            holding_rebate_epochs,
+        })
+    }
+}
+
+/// Sets the withdrawal fee's holding rebate: a withdrawer who passes their
+/// user stats PDA to `CrankSplit` or `CrankSplitTranche` has
+/// `holding_rebate_bps` of the withdrawal fee waived once
+/// `holding_rebate_epochs` have passed since their first deposit, and a
+/// linear share of it before. Zero epochs, the default, disables the rebate.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Changelog PDA
+/// 3. `[WRITE]` Pool state PDA
+pub struct SetHoldingRebate<'a> {
+    pub accounts: SetHoldingRebateAccounts<'a>,
+    pub data: SetHoldingRebateInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetHoldingRebate<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetHoldingRebateAccounts::try_from(accounts)?,
+            data: SetHoldingRebateInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetHoldingRebate<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &99;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let old_bps = config.holding_rebate_bps;
+        let old_epochs = config.holding_rebate_epochs;
+
+        config.set_holding_rebate(
+            self.data.holding_rebate_bps,
+            self.data.holding_rebate_epochs,
+        );
+
+        record_param_change(
+            self.accounts.changelog_pda,
+            self.accounts.admin,
+            *Self::DISCRIMINATOR,
+            &[&old_bps.to_le_bytes(), &old_epochs.to_le_bytes()],
+            &[
+                &self.data.holding_rebate_bps.to_le_bytes(),
+                &self.data.holding_rebate_epochs.to_le_bytes(),
+            ],
+        )
+    }
+}
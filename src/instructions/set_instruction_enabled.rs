@@ -0,0 +1,128 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    instructions::helpers::{check_config_pda, record_param_change, AccountCheck, SignerAccount},
+    state::Config,
+};
+
+pub struct SetInstructionEnabledAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub changelog_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetInstructionEnabledAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, changelog_pda, pool_state_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            changelog_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct SetInstructionEnabledInstructionData {
+    pub discriminator: u8,
+    pub enabled: bool,
+}
+
+impl TryFrom<&[u8]> for SetInstructionEnabledInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        let [discriminator, enabled] = data else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        let enabled = match enabled {
+            0 => false,
+            1 => true,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        // The switch itself stays on, so the admin cannot lock it.
+        if *discriminator == *SetInstructionEnabled::DISCRIMINATOR && !enabled {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            discriminator: *discriminator,
+            enabled,
+        })
+    }
+}
+
+/// Switches one instruction off or back on without pausing the whole pool,
+/// so an issue found in a single path can be contained while the rest keeps
+/// running. A disabled instruction fails with `InstructionDisabled` before it
+/// runs, whichever discriminator form it was called with.
+///
+/// Data: the instruction's single-byte discriminator and `enabled` (0 or 1).
+/// Only instructions that take the config PDA can be switched off.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Changelog PDA
+/// 3. `[WRITE]` Pool state PDA
+pub struct SetInstructionEnabled<'a> {
+    pub accounts: SetInstructionEnabledAccounts<'a>,
+    pub data: SetInstructionEnabledInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetInstructionEnabled<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetInstructionEnabledAccounts::try_from(accounts)?,
+            data: SetInstructionEnabledInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetInstructionEnabled<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &52;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let was_enabled = !config.is_instruction_disabled(self.data.discriminator);
+
+        config.set_instruction_disabled(self.data.discriminator, !self.data.enabled);
+
+        record_param_change(
+            self.accounts.changelog_pda,
+            self.accounts.admin,
+            *Self::DISCRIMINATOR,
+            &[&[self.data.discriminator], &[was_enabled as u8]],
+            &[&[self.data.discriminator], &[self.data.enabled as u8]],
+        )
+    }
+}
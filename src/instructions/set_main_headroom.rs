@@ -0,0 +1,112 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    instructions::helpers::{check_config_pda, record_param_change, AccountCheck, SignerAccount},
+    state::Config,
+};
+
+pub struct SetMainHeadroomAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub changelog_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetMainHeadroomAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, changelog_pda, pool_state_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            changelog_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct SetMainHeadroomInstructionData {
+    pub main_headroom_lamports: u64,
+}
+
+impl TryFrom<&[u8]> for SetMainHeadroomInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            main_headroom_lamports: u64::from_le_bytes(data.try_into().unwrap()),
+        })
+    }
+}
+
+/// Sets the headroom main must keep beyond its rent and the minimum
+/// delegation after a split. Splits that would eat into it fail with
+/// `MainBelowMinimum`, which leaves the rest of the exit to `LiquidUnstake`.
+/// Zero, the default, only keeps what the stake program requires.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Changelog PDA
+/// 3. `[WRITE]` Pool state PDA
+pub struct SetMainHeadroom<'a> {
+    pub accounts: SetMainHeadroomAccounts<'a>,
+    pub data: SetMainHeadroomInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetMainHeadroom<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetMainHeadroomAccounts::try_from(accounts)?,
+            data: SetMainHeadroomInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetMainHeadroom<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &67;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let old_value = config.main_headroom_lamports;
+
+        config.set_main_headroom(self.data.main_headroom_lamports);
+
+        record_param_change(
+            self.accounts.changelog_pda,
+            self.accounts.admin,
+            *Self::DISCRIMINATOR,
+            &[&old_value.to_le_bytes()],
+            &[&self.data.main_headroom_lamports.to_le_bytes()],
+        )
+    }
+}
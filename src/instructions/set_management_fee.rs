@@ -0,0 +1,114 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    instructions::helpers::{check_config_pda, record_param_change, AccountCheck, SignerAccount},
+    state::Config,
+};
+
+pub struct SetManagementFeeAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub changelog_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetManagementFeeAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, changelog_pda, pool_state_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            changelog_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct SetManagementFeeInstructionData {
+    pub management_fee_bps: u16,
+}
+
+impl TryFrom<&[u8]> for SetManagementFeeInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 2 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let management_fee_bps = u16::from_le_bytes(data.try_into().unwrap());
+        if management_fee_bps >= 10_000 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { management_fee_bps })
+    }
+}
+
+/// Sets the management fee: each epoch `CrankManagementFee` mints LST worth
+/// `management_fee_bps` of the pool's lamports to the treasury ATA, never
+/// more than the pool's yield. Zero, the default, charges nothing.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Changelog PDA
+/// 3. `[WRITE]` Pool state PDA
+pub struct SetManagementFee<'a> {
+    pub accounts: SetManagementFeeAccounts<'a>,
+    pub data: SetManagementFeeInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetManagementFee<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetManagementFeeAccounts::try_from(accounts)?,
+            data: SetManagementFeeInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetManagementFee<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &88;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let old_value = config.management_fee_bps;
+
+        config.set_management_fee(self.data.management_fee_bps);
+
+        record_param_change(
+            self.accounts.changelog_pda,
+            self.accounts.admin,
+            *Self::DISCRIMINATOR,
+            &[&old_value.to_le_bytes()],
+            &[&self.data.management_fee_bps.to_le_bytes()],
+        )
+    }
+}
@@ -0,0 +1,121 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit, emit_admin_action, EVENT_STATUS, ROLE_ADMIN},
+    instructions::helpers::{check_config_pda, record_param_change, AccountCheck, SignerAccount},
+    state::{Config, PoolStatus},
+};
+
+pub struct SetPoolStatusAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+    pub changelog_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetPoolStatusAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, pool_state_pda, changelog_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            pool_state_pda,
+            changelog_pda,
+        })
+    }
+}
+
+pub struct SetPoolStatusInstructionData {
+    pub status: PoolStatus,
+}
+
+impl TryFrom<&[u8]> for SetPoolStatusInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 1 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // Bootstrapping and Closed are only entered by the program itself.
+        let status = match PoolStatus::try_from(data[0])? {
+            status @ (PoolStatus::Active | PoolStatus::Paused | PoolStatus::Deactivating) => status,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        Ok(Self { status })
+    }
+}
+
+/// Pauses or resumes the pool, or starts winding it down.
+///
+/// The admin can move the pool to `Active`, `Paused` or `Deactivating`,
+/// subject to `PoolStatus::can_transition_to`. `Deactivating` is one-way.
+/// Logs an `EVENT_STATUS` for the transition.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Pool state PDA
+/// 3. `[WRITE]` Changelog PDA
+pub struct SetPoolStatus<'a> {
+    pub accounts: SetPoolStatusAccounts<'a>,
+    pub data: SetPoolStatusInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetPoolStatus<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetPoolStatusAccounts::try_from(accounts)?,
+            data: SetPoolStatusInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetPoolStatus<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &38;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let previous = config.transition(self.data.status)?;
+
+        record_param_change(
+            self.accounts.changelog_pda,
+            self.accounts.admin,
+            *Self::DISCRIMINATOR,
+            &[&[previous as u8]],
+            &[&[self.data.status as u8]],
+        )?;
+
+        emit(
+            self.accounts.pool_state_pda,
+            EVENT_STATUS,
+            &[&[previous as u8], &[self.data.status as u8]],
+        )
+    }
+}
@@ -0,0 +1,121 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    instructions::helpers::{check_config_pda, record_param_change, AccountCheck, SignerAccount},
+    state::Config,
+};
+
+pub struct SetQuoteSignerAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub changelog_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetQuoteSignerAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, changelog_pda, pool_state_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            changelog_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct SetQuoteSignerInstructionData {
+    pub quote_signer: [u8; 32],
+    pub quote_max_deviation_bps: u16,
+}
+
+impl TryFrom<&[u8]> for SetQuoteSignerInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 34 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let quote_max_deviation_bps = u16::from_le_bytes(data[32..34].try_into().unwrap());
+        if quote_max_deviation_bps > 10_000 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            quote_signer: data[0..32].try_into().unwrap(),
+            quote_max_deviation_bps,
+        })
+    }
+}
+
+/// Sets the key whose signed rate quotes `Deposit` honors, for onramps that
+/// need a fixed price, and how far in bps a quoted rate may be from the
+/// pool's own. The zero key turns quotes off.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Changelog PDA
+/// 3. `[WRITE]` Pool state PDA
+pub struct SetQuoteSigner<'a> {
+    pub accounts: SetQuoteSignerAccounts<'a>,
+    pub data: SetQuoteSignerInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetQuoteSigner<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetQuoteSignerAccounts::try_from(accounts)?,
+            data: SetQuoteSignerInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetQuoteSigner<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &56;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let old_signer = config.quote_signer;
+
+        config.quote_signer = self.data.quote_signer;
+        config.quote_max_deviation_bps = self.data.quote_max_deviation_bps;
+
+        // The 32-byte key leaves no room for the bound, so only the signer
+        // is recorded.
+        record_param_change(
+            self.accounts.changelog_pda,
+            self.accounts.admin,
+            *Self::DISCRIMINATOR,
+            &[&old_signer],
+            &[&self.data.quote_signer],
+        )
+    }
+}
@@ -0,0 +1,199 @@
+use pinocchio::{
+    account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+    pubkey::find_program_address,
+};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    ids::{
+        assert_associated_token_program, assert_system_program, assert_token_program,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+    },
+    instructions::helpers::{
+        check_config_pda, record_param_change, AccountCheck, AssociatedTokenAccount,
+        AssociatedTokenAccountInit, ProgramAccount, ProgramAccountInit, SignerAccount,
+    },
+    state::{Config, RewardPayout},
+};
+
+pub struct SetRewardPayoutAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub changelog_pda: &'a AccountInfo,
+    pub reward_payout_pda: &'a AccountInfo,
+    pub payout_vault: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub associated_token_program: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetRewardPayoutAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, changelog_pda, reward_payout_pda, payout_vault, lst_mint, token_program, associated_token_program, system_program, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        assert_system_program(system_program)?;
+        assert_token_program(token_program)?;
+        assert_associated_token_program(associated_token_program)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            changelog_pda,
+            reward_payout_pda,
+            payout_vault,
+            lst_mint,
+            token_program,
+            associated_token_program,
+            system_program,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct SetRewardPayoutInstructionData {
+    pub reward_payout_bps: u16,
+}
+
+impl TryFrom<&[u8]> for SetRewardPayoutInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 2 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let reward_payout_bps = u16::from_le_bytes(data.try_into().unwrap());
+        if reward_payout_bps > 10_000 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { reward_payout_bps })
+    }
+}
+
+/// Sets the share of the rewards earned on enrolled LST that
+/// `CrankRewardPayout` pays out as SOL instead of compounding into the
+/// exchange rate, in bps of at most 10 000. Zero, the default, compounds
+/// everything. The first call creates the SOL payout pool
+/// (`b"reward_payout"`) and its vault, the pool's LST ATA, which holders
+/// enroll into with `EnrollPayout`. LST that is not enrolled is unaffected
+/// whatever the split.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Admin
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Changelog PDA
+/// 3. `[WRITE]` Reward payout PDA
+/// 4. `[WRITE]` Payout vault (reward payout PDA's LST ATA)
+/// 5. `[]` LST mint
+/// 6. `[]` Token program
+/// 7. `[]` Associated token program
+/// 8. `[]` System program
+/// 9. `[WRITE]` Pool state PDA
+pub struct SetRewardPayout<'a> {
+    pub accounts: SetRewardPayoutAccounts<'a>,
+    pub data: SetRewardPayoutInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetRewardPayout<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetRewardPayoutAccounts::try_from(accounts)?,
+            data: SetRewardPayoutInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetRewardPayout<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &73;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let old_value = config.reward_payout_bps;
+
+        config.set_reward_payout(self.data.reward_payout_bps);
+        drop(config_data);
+
+        let (expected_reward_payout_pda, reward_payout_bump) =
+            find_program_address(&[b"reward_payout"], &crate::ID);
+        if expected_reward_payout_pda != *self.accounts.reward_payout_pda.key() {
+            return Err(PinocchioError::InvalidRewardPayoutPda.into());
+        }
+
+        let expected_payout_vault = find_program_address(
+            &[
+                self.accounts.reward_payout_pda.key(),
+                self.accounts.token_program.key(),
+                self.accounts.lst_mint.key(),
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .0;
+        if expected_payout_vault != *self.accounts.payout_vault.key() {
+            return Err(PinocchioError::InvalidAddress.into());
+        }
+
+        if self.accounts.reward_payout_pda.data_is_empty() {
+            let reward_payout_bump_binding = [reward_payout_bump];
+            let reward_payout_seeds = &[
+                Seed::from(b"reward_payout"),
+                Seed::from(&reward_payout_bump_binding),
+            ];
+            ProgramAccount::init::<RewardPayout>(
+                self.accounts.admin,
+                self.accounts.reward_payout_pda,
+                reward_payout_seeds,
+                RewardPayout::LEN,
+            )?;
+
+            AssociatedTokenAccount::init(
+                self.accounts.payout_vault,
+                self.accounts.lst_mint,
+                self.accounts.admin,
+                self.accounts.reward_payout_pda,
+                self.accounts.system_program,
+                self.accounts.token_program,
+            )?;
+        }
+
+        record_param_change(
+            self.accounts.changelog_pda,
+            self.accounts.admin,
+            *Self::DISCRIMINATOR,
+            &[&old_value.to_le_bytes()],
+            &[&self.data.reward_payout_bps.to_le_bytes()],
+        )
+    }
+}
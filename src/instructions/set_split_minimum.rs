@@ -0,0 +1,116 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    instructions::helpers::{
+        check_config_pda, record_param_change, AccountCheck, SignerAccount, LAMPORTS_PER_SOL,
+    },
+    state::Config,
+};
+
+/// Stake portion of the split minimum until the admin sets one.
+pub const DEFAULT_MIN_SPLIT_STAKE_LAMPORTS: u64 = LAMPORTS_PER_SOL;
+
+pub struct SetSplitMinimumAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub changelog_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetSplitMinimumAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, changelog_pda, pool_state_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            changelog_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct SetSplitMinimumInstructionData {
+    pub min_split_stake_lamports: u64,
+}
+
+impl TryFrom<&[u8]> for SetSplitMinimumInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            min_split_stake_lamports: u64::from_le_bytes(data.try_into().unwrap()),
+        })
+    }
+}
+
+/// Sets the stake portion of the split minimum, the smallest stake a split
+/// account may hold on top of its rent. The cluster's minimum delegation is
+/// used instead whenever it is higher, so zero follows the cluster exactly.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Changelog PDA
+/// 3. `[WRITE]` Pool state PDA
+pub struct SetSplitMinimum<'a> {
+    pub accounts: SetSplitMinimumAccounts<'a>,
+    pub data: SetSplitMinimumInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetSplitMinimum<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetSplitMinimumAccounts::try_from(accounts)?,
+            data: SetSplitMinimumInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetSplitMinimum<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &26;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let old_value = config.min_split_stake_lamports;
+
+        config.set_min_split_stake(self.data.min_split_stake_lamports);
+
+        record_param_change(
+            self.accounts.changelog_pda,
+            self.accounts.admin,
+            *Self::DISCRIMINATOR,
+            &[&old_value.to_le_bytes()],
+            &[&self.data.min_split_stake_lamports.to_le_bytes()],
+        )
+    }
+}
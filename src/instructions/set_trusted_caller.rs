@@ -0,0 +1,148 @@
+use pinocchio::{
+    account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+    pubkey::find_program_address,
+};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    ids::assert_system_program,
+    instructions::helpers::{
+        check_config_pda, AccountCheck, ProgramAccount, ProgramAccountInit, SignerAccount,
+    },
+    state::{Config, TrustedCallers},
+};
+
+pub struct SetTrustedCallerAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub trusted_callers_pda: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetTrustedCallerAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, trusted_callers_pda, system_program, pool_state_pda] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        assert_system_program(system_program)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            trusted_callers_pda,
+            system_program,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct SetTrustedCallerInstructionData {
+    pub program: [u8; 32],
+    pub approved: bool,
+}
+
+impl TryFrom<&[u8]> for SetTrustedCallerInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 33 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let program = data[0..32].try_into().unwrap();
+        let approved = match data[32] {
+            0 => false,
+            1 => true,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        Ok(Self { program, approved })
+    }
+}
+
+/// Adds or removes a program from the list of callers allowed to use the
+/// skip-checks path of `Deposit`. Creates the trusted callers PDA on first use.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Admin
+/// 1. `[]` Config PDA
+/// 2. `[WRITE]` Trusted callers PDA
+/// 3. `[]` System program
+/// 4. `[WRITE]` Pool state PDA
+pub struct SetTrustedCaller<'a> {
+    pub accounts: SetTrustedCallerAccounts<'a>,
+    pub data: SetTrustedCallerInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetTrustedCaller<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetTrustedCallerAccounts::try_from(accounts)?,
+            data: SetTrustedCallerInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetTrustedCaller<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &10;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        if Config::load(&config_data)?.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+        drop(config_data);
+
+        let (expected_trusted_callers_pda, trusted_callers_bump) =
+            find_program_address(&[b"trusted_callers"], &crate::ID);
+        if expected_trusted_callers_pda != *self.accounts.trusted_callers_pda.key() {
+            return Err(PinocchioError::InvalidTrustedCallersPda.into());
+        }
+
+        if self.accounts.trusted_callers_pda.data_is_empty() {
+            let trusted_callers_bump_binding = [trusted_callers_bump];
+            let trusted_callers_seeds = &[
+                Seed::from(b"trusted_callers"),
+                Seed::from(&trusted_callers_bump_binding),
+            ];
+            ProgramAccount::init::<TrustedCallers>(
+                self.accounts.admin,
+                self.accounts.trusted_callers_pda,
+                trusted_callers_seeds,
+                TrustedCallers::LEN,
+            )?;
+        }
+
+        let mut trusted_callers_data = self.accounts.trusted_callers_pda.try_borrow_mut_data()?;
+        let trusted_callers = TrustedCallers::load_mut(&mut trusted_callers_data)?;
+
+        if self.data.approved {
+            if !trusted_callers.add(self.data.program) {
+                return Err(PinocchioError::TrustedCallersFull.into());
+            }
+        } else {
+            trusted_callers.remove(&self.data.program);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    instructions::helpers::{check_config_pda, record_param_change, AccountCheck, SignerAccount},
+    state::Config,
+};
+
+pub struct SetUnstakeFeeParamsAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub changelog_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetUnstakeFeeParamsAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, changelog_pda, pool_state_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            changelog_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct SetUnstakeFeeParamsInstructionData {
+    pub unstake_fee_min_bps: u16,
+    pub unstake_fee_max_bps: u16,
+    pub liquidity_target_lamports: u64,
+}
+
+impl TryFrom<&[u8]> for SetUnstakeFeeParamsInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 12 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let unstake_fee_min_bps = u16::from_le_bytes(data[0..2].try_into().unwrap());
+        let unstake_fee_max_bps = u16::from_le_bytes(data[2..4].try_into().unwrap());
+        let liquidity_target_lamports = u64::from_le_bytes(data[4..12].try_into().unwrap());
+
+        if unstake_fee_min_bps > unstake_fee_max_bps
+            || unstake_fee_max_bps > 10_000
+            || liquidity_target_lamports == 0
+        {
+            return Err(PinocchioError::InvalidFeeParams.into());
+        }
+
+        Ok(Self {
+            unstake_fee_min_bps,
+            unstake_fee_max_bps,
+            liquidity_target_lamports,
+        })
+    }
+}
+
+/// Updates the instant-unstake fee curve used by `LiquidUnstake`.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Changelog PDA
+/// 3. `[WRITE]` Pool state PDA
+pub struct SetUnstakeFeeParams<'a> {
+    pub accounts: SetUnstakeFeeParamsAccounts<'a>,
+    pub data: SetUnstakeFeeParamsInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetUnstakeFeeParams<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetUnstakeFeeParamsAccounts::try_from(accounts)?,
+            data: SetUnstakeFeeParamsInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetUnstakeFeeParams<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &15;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let old_value = (
+            config.unstake_fee_min_bps,
+            config.unstake_fee_max_bps,
+            config.liquidity_target_lamports,
+        );
+
+        config.set_unstake_fee_params(
+            self.data.unstake_fee_min_bps,
+            self.data.unstake_fee_max_bps,
+            self.data.liquidity_target_lamports,
+        );
+
+        record_param_change(
+            self.accounts.changelog_pda,
+            self.accounts.admin,
+            *Self::DISCRIMINATOR,
+            &[
+                &old_value.0.to_le_bytes(),
+                &old_value.1.to_le_bytes(),
+                &old_value.2.to_le_bytes(),
+            ],
+            &[
+                &self.data.unstake_fee_min_bps.to_le_bytes(),
+                &self.data.unstake_fee_max_bps.to_le_bytes(),
+                &self.data.liquidity_target_lamports.to_le_bytes(),
+            ],
+        )
+    }
+}
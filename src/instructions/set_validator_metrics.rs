@@ -0,0 +1,123 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::find_program_address,
+};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    instructions::helpers::{check_config_pda, AccountCheck, SignerAccount},
+    state::{Config, ValidatorList},
+};
+
+pub struct SetValidatorMetricsAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub validator_list_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetValidatorMetricsAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, validator_list_pda, pool_state_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            validator_list_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct SetValidatorMetricsInstructionData {
+    pub vote_account: [u8; 32],
+    pub weight: u32,
+    pub score: u32,
+}
+
+impl TryFrom<&[u8]> for SetValidatorMetricsInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 40 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let vote_account = data[0..32].try_into().unwrap();
+        let weight = u32::from_le_bytes(data[32..36].try_into().unwrap());
+        let score = u32::from_le_bytes(data[36..40].try_into().unwrap());
+
+        Ok(Self {
+            vote_account,
+            weight,
+            score,
+        })
+    }
+}
+
+/// Sets the weight and performance score a validator in the validator list
+/// is given by the weight-based and score-based delegation strategies.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[]` Config PDA
+/// 2. `[WRITE]` Validator list PDA
+/// 3. `[WRITE]` Pool state PDA
+pub struct SetValidatorMetrics<'a> {
+    pub accounts: SetValidatorMetricsAccounts<'a>,
+    pub data: SetValidatorMetricsInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetValidatorMetrics<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetValidatorMetricsAccounts::try_from(accounts)?,
+            data: SetValidatorMetricsInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetValidatorMetrics<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &24;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        if Config::load(&config_data)?.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+        drop(config_data);
+
+        let (expected_validator_list_pda, _) =
+            find_program_address(&[b"validator_list"], &crate::ID);
+        if expected_validator_list_pda != *self.accounts.validator_list_pda.key() {
+            return Err(PinocchioError::InvalidValidatorListPda.into());
+        }
+
+        let mut validator_list_data = self.accounts.validator_list_pda.try_borrow_mut_data()?;
+        let entry = ValidatorList::load_mut(&mut validator_list_data)?
+            .find_mut(&self.data.vote_account)
+            .ok_or(PinocchioError::ValidatorNotInList)?;
+
+        entry.weight = self.data.weight;
+        entry.score = self.data.score;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    instructions::helpers::{check_config_pda, record_param_change, AccountCheck, SignerAccount},
+    state::Config,
+};
+
+/// No share limit until the admin sets one.
+pub const DEFAULT_MAX_VALIDATOR_STAKE_BPS: u16 = 10_000;
+/// No absolute limit until the admin sets one.
+pub const DEFAULT_MAX_VALIDATOR_STAKE_LAMPORTS: u64 = 0;
+
+pub struct SetValidatorStakeCapAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub changelog_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetValidatorStakeCapAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, changelog_pda, pool_state_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            changelog_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct SetValidatorStakeCapInstructionData {
+    pub max_validator_stake_bps: u16,
+    pub max_validator_stake_lamports: u64,
+}
+
+impl TryFrom<&[u8]> for SetValidatorStakeCapInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 10 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let max_validator_stake_bps = u16::from_le_bytes(data[0..2].try_into().unwrap());
+        let max_validator_stake_lamports = u64::from_le_bytes(data[2..10].try_into().unwrap());
+
+        if max_validator_stake_bps == 0 || max_validator_stake_bps > 10_000 {
+            return Err(PinocchioError::InvalidValidatorStakeCap.into());
+        }
+
+        Ok(Self {
+            max_validator_stake_bps,
+            max_validator_stake_lamports,
+        })
+    }
+}
+
+/// Sets the per-validator stake cap enforced when stake is delegated: a share
+/// of the pool's lamports in bps and an optional absolute limit in lamports
+/// (zero for none). The lower of the two applies.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Changelog PDA
+/// 3. `[WRITE]` Pool state PDA
+pub struct SetValidatorStakeCap<'a> {
+    pub accounts: SetValidatorStakeCapAccounts<'a>,
+    pub data: SetValidatorStakeCapInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetValidatorStakeCap<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetValidatorStakeCapAccounts::try_from(accounts)?,
+            data: SetValidatorStakeCapInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetValidatorStakeCap<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &22;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let old_value = (
+            config.max_validator_stake_bps,
+            config.max_validator_stake_lamports,
+        );
+
+        config.set_validator_stake_cap(
+            self.data.max_validator_stake_bps,
+            self.data.max_validator_stake_lamports,
+        );
+
+        record_param_change(
+            self.accounts.changelog_pda,
+            self.accounts.admin,
+            *Self::DISCRIMINATOR,
+            &[&old_value.0.to_le_bytes(), &old_value.1.to_le_bytes()],
+            &[
+                &self.data.max_validator_stake_bps.to_le_bytes(),
+                &self.data.max_validator_stake_lamports.to_le_bytes(),
+            ],
+        )
+    }
+}
@@ -0,0 +1,183 @@
+use pinocchio::{
+    account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+    pubkey::find_program_address,
+};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    ids::assert_system_program,
+    instructions::helpers::check_config_pda,
+    instructions::{
+        helpers::{AccountCheck, ProgramAccount, ProgramAccountInit, SignerAccount},
+        join_validator_set::MIN_VALIDATOR_BOND_LAMPORTS,
+    },
+    state::{Config, ValidatorBond, ValidatorList, VALIDATOR_BOND_ACTIVE, VALIDATOR_BOND_PENDING},
+};
+
+pub struct SetValidatorStatusAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub validator_bond_pda: &'a AccountInfo,
+    pub validator_list_pda: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetValidatorStatusAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, validator_bond_pda, validator_list_pda, system_program, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        assert_system_program(system_program)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            validator_bond_pda,
+            validator_list_pda,
+            system_program,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct SetValidatorStatusInstructionData {
+    pub active: bool,
+}
+
+impl TryFrom<&[u8]> for SetValidatorStatusInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 1 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let active = match data[0] {
+            0 => false,
+            1 => true,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        Ok(Self { active })
+    }
+}
+
+/// Activates a pending validator into the validator set, or deactivates an
+/// active one back to pending. Activation requires a bond of at least
+/// `MIN_VALIDATOR_BOND_LAMPORTS`. Creates the validator list PDA on first use.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Admin
+/// 1. `[]` Config PDA
+/// 2. `[WRITE]` Validator bond PDA
+/// 3. `[WRITE]` Validator list PDA
+/// 4. `[]` System program
+/// 5. `[WRITE]` Pool state PDA
+pub struct SetValidatorStatus<'a> {
+    pub accounts: SetValidatorStatusAccounts<'a>,
+    pub data: SetValidatorStatusInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetValidatorStatus<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetValidatorStatusAccounts::try_from(accounts)?,
+            data: SetValidatorStatusInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetValidatorStatus<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &19;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        if Config::load(&config_data)?.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+        drop(config_data);
+
+        if !self.accounts.validator_bond_pda.is_owned_by(&crate::ID) {
+            return Err(PinocchioError::InvalidValidatorBondPda.into());
+        }
+
+        let mut validator_bond_data = self.accounts.validator_bond_pda.try_borrow_mut_data()?;
+        let validator_bond = ValidatorBond::load_mut(&mut validator_bond_data)?;
+
+        let (expected_validator_bond_pda, _) = find_program_address(
+            &[b"validator_bond", &validator_bond.vote_account],
+            &crate::ID,
+        );
+        if expected_validator_bond_pda != *self.accounts.validator_bond_pda.key() {
+            return Err(PinocchioError::InvalidValidatorBondPda.into());
+        }
+
+        let (expected_validator_list_pda, validator_list_bump) =
+            find_program_address(&[b"validator_list"], &crate::ID);
+        if expected_validator_list_pda != *self.accounts.validator_list_pda.key() {
+            return Err(PinocchioError::InvalidValidatorListPda.into());
+        }
+
+        if self.accounts.validator_list_pda.data_is_empty() {
+            let validator_list_bump_binding = [validator_list_bump];
+            let validator_list_seeds = &[
+                Seed::from(b"validator_list"),
+                Seed::from(&validator_list_bump_binding),
+            ];
+            ProgramAccount::init::<ValidatorList>(
+                self.accounts.admin,
+                self.accounts.validator_list_pda,
+                validator_list_seeds,
+                ValidatorList::LEN,
+            )?;
+        }
+
+        let mut validator_list_data = self.accounts.validator_list_pda.try_borrow_mut_data()?;
+        let validator_list = ValidatorList::load_mut(&mut validator_list_data)?;
+
+        if self.data.active {
+            if validator_bond.status != VALIDATOR_BOND_PENDING {
+                return Err(PinocchioError::InvalidValidatorStatus.into());
+            }
+
+            if validator_bond.bond_lamports < MIN_VALIDATOR_BOND_LAMPORTS {
+                return Err(PinocchioError::BondBelowMinimum.into());
+            }
+
+            if !validator_list.add(validator_bond.vote_account) {
+                return Err(PinocchioError::ValidatorListFull.into());
+            }
+
+            validator_bond.status = VALIDATOR_BOND_ACTIVE;
+        } else {
+            if validator_bond.status != VALIDATOR_BOND_ACTIVE {
+                return Err(PinocchioError::InvalidValidatorStatus.into());
+            }
+
+            validator_list.remove(&validator_bond.vote_account)?;
+            validator_bond.status = VALIDATOR_BOND_PENDING;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    instructions::helpers::{check_config_pda, record_param_change, AccountCheck, SignerAccount},
+    state::Config,
+};
+
+pub struct SetWithdrawFeeAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub changelog_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetWithdrawFeeAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, changelog_pda, pool_state_pda] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            changelog_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct SetWithdrawFeeInstructionData {
+    pub withdraw_fee_bps: u16,
+}
+
+impl TryFrom<&[u8]> for SetWithdrawFeeInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 2 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let withdraw_fee_bps = u16::from_le_bytes(data.try_into().unwrap());
+        if withdraw_fee_bps > 10_000 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { withdraw_fee_bps })
+    }
+}
+
+/// Sets the withdrawal fee: `CrankSplit` and `CrankSplitTranche` burn
+/// `withdraw_fee_bps` of a split's pro-rata LST on top of it, which raises
+/// the exchange rate for the remaining holders. Zero, the default, charges
+/// nothing.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Changelog PDA
+/// 3. `[WRITE]` Pool state PDA
+pub struct SetWithdrawFee<'a> {
+    pub accounts: SetWithdrawFeeAccounts<'a>,
+    pub data: SetWithdrawFeeInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetWithdrawFee<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SetWithdrawFeeAccounts::try_from(accounts)?,
+            data: SetWithdrawFeeInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SetWithdrawFee<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &87;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        let old_value = config.withdraw_fee_bps;
+
+        config.set_withdraw_fee(self.data.withdraw_fee_bps);
+
+        record_param_change(
+            self.accounts.changelog_pda,
+            self.accounts.admin,
+            *Self::DISCRIMINATOR,
+            &[&old_value.to_le_bytes()],
+            &[&self.data.withdraw_fee_bps.to_le_bytes()],
+        )
+    }
+}
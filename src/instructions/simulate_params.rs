@@ -0,0 +1,287 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::set_return_data,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
+};
+use pinocchio_token::state::Mint;
+
+use crate::{
+    errors::PinocchioError,
+    instructions::helpers::check_config_pda,
+    instructions::{
+        helpers::{epoch_rate, managed_lamports},
+        set_unstake_fee_params::SetUnstakeFeeParamsInstructionData,
+    },
+    math::{lamports_to_lst, lst_to_lamports, unstake_fee_bps, unstake_lamports_out},
+    state::{Config, LiquidityPool, PoolState},
+};
+
+/// Size of one scenario's projection returned by `SimulateParams`.
+pub const PROJECTION_LEN: usize = 5 * 8;
+
+pub struct SimulateParamsAccounts<'a> {
+    pub config_pda: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub liquidity_pool_pda: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SimulateParamsAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [config_pda, stake_account_main, stake_account_reserve, lst_mint, liquidity_pool_pda, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            lst_mint,
+            liquidity_pool_pda,
+            pool_state_pda,
+        })
+    }
+}
+
+/// Fee and cap parameters to project, in the layouts of `SetUnstakeFeeParams`
+/// and `SetDepositCap`, plus the amounts to quote.
+pub struct SimulateParamsInstructionData {
+    pub fee_params: SetUnstakeFeeParamsInstructionData,
+    pub deposit_cap_lamports: u64,
+    pub deposit_lamports: u64,
+    pub unstake_lst: u64,
+}
+
+impl TryFrom<&[u8]> for SimulateParamsInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 36 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            fee_params: SetUnstakeFeeParamsInstructionData::try_from(&data[0..12])?,
+            deposit_cap_lamports: u64::from_le_bytes(data[12..20].try_into().unwrap()),
+            deposit_lamports: u64::from_le_bytes(data[20..28].try_into().unwrap()),
+            unstake_lst: u64::from_le_bytes(data[28..36].try_into().unwrap()),
+        })
+    }
+}
+
+/// Pool values both scenarios are priced against.
+struct PoolSnapshot {
+    lst_supply: u64,
+    pool_lamports: u64,
+    sol_reserves: u64,
+}
+
+/// Projects quotes under hypothetical fee and cap parameters, so governance
+/// can weigh a change with the program's own math before making it. Nothing
+/// is written.
+///
+/// Data: `unstake_fee_min_bps` (u16), `unstake_fee_max_bps` (u16),
+/// `liquidity_target_lamports` (u64), `deposit_cap_lamports` (u64), then the
+/// quote amounts `deposit_lamports` (u64) and `unstake_lst` (u64). The
+/// parameters are validated as `SetUnstakeFeeParams` would.
+///
+/// The return data is two projections, first under the current config and
+/// then under the proposed parameters, each five little-endian `u64`s:
+///
+/// - `deposit_accepted_lamports`: how much of `deposit_lamports` fits under
+///   what is left of this epoch's deposit cap.
+/// - `deposit_lst_out`: LST minted for the accepted lamports.
+/// - `unstake_fee_bps`: the `LiquidUnstake` fee for `unstake_lst`.
+/// - `unstake_lamports_out`: SOL paid out, zero if the buffer is too small.
+/// - `unstake_fee_lamports`: the fee kept by liquidity providers.
+///
+/// A last byte gives the LST mint's decimals, the decimals of `unstake_lst`
+/// and `deposit_lst_out`. The other amounts are lamports.
+///
+/// Accounts expected:
+///
+/// 0. `[]` Config PDA
+/// 1. `[]` Stake account main
+/// 2. `[]` Stake account reserve
+/// 3. `[]` LST mint
+/// 4. `[]` Liquidity pool PDA (may be uninitialized)
+/// 5. `[]` Pool state PDA
+pub struct SimulateParams<'a> {
+    pub accounts: SimulateParamsAccounts<'a>,
+    pub data: SimulateParamsInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SimulateParams<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SimulateParamsAccounts::try_from(accounts)?,
+            data: SimulateParamsInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SimulateParams<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &46;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        let current = SetUnstakeFeeParamsInstructionData {
+            unstake_fee_min_bps: config.unstake_fee_min_bps,
+            unstake_fee_max_bps: config.unstake_fee_max_bps,
+            liquidity_target_lamports: config.liquidity_target_lamports,
+        };
+        let current_deposit_cap_lamports = config.deposit_cap_lamports;
+        let validator_stake_lamports = config.validator_stake_lamports;
+        let cached_rate = epoch_rate(config)?;
+        drop(config_data);
+
+        let (expected_liquidity_pool_pda, _) =
+            find_program_address(&[b"liquidity_pool"], &crate::ID);
+        if expected_liquidity_pool_pda != *self.accounts.liquidity_pool_pda.key() {
+            return Err(PinocchioError::InvalidLiquidityPoolPda.into());
+        }
+
+        let (expected_pool_state_pda, _) = find_program_address(&[b"pool_state"], &crate::ID);
+        if expected_pool_state_pda != *self.accounts.pool_state_pda.key()
+            || !self.accounts.pool_state_pda.is_owned_by(&crate::ID)
+        {
+            return Err(PinocchioError::InvalidPoolStatePda.into());
+        }
+
+        let liquidity_pool_data = self.accounts.liquidity_pool_pda.try_borrow_data()?;
+        let sol_reserves = if liquidity_pool_data.is_empty() {
+            0
+        } else {
+            LiquidityPool::load(&liquidity_pool_data)?.sol_reserves
+        };
+        drop(liquidity_pool_data);
+
+        let epoch = Clock::get()?.epoch;
+        let pool_state_data = self.accounts.pool_state_pda.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+        let current_deposit_room = pool_state.inflow_room(epoch, current_deposit_cap_lamports);
+        let proposed_deposit_room = pool_state.inflow_room(epoch, self.data.deposit_cap_lamports);
+        drop(pool_state_data);
+
+        let mint = Mint::from_account_info(self.accounts.lst_mint)?;
+        let lst_decimals = mint.decimals();
+        let (total_sol_in_pool, total_lst_supply) = match cached_rate {
+            Some(rate) => rate,
+            None => (
+                managed_lamports(
+                    self.accounts.pool_state_pda,
+                    self.accounts.stake_account_main,
+                    self.accounts.stake_account_reserve,
+                    validator_stake_lamports,
+                )?,
+                mint.supply(),
+            ),
+        };
+        let snapshot = PoolSnapshot {
+            lst_supply: total_lst_supply,
+            pool_lamports: total_sol_in_pool,
+            sol_reserves,
+        };
+        drop(mint);
+
+        let mut return_data = [0u8; 2 * PROJECTION_LEN + 1];
+        return_data[..PROJECTION_LEN].copy_from_slice(&self.project(
+            &snapshot,
+            &current,
+            current_deposit_room,
+        )?);
+        return_data[PROJECTION_LEN..2 * PROJECTION_LEN].copy_from_slice(&self.project(
+            &snapshot,
+            &self.data.fee_params,
+            proposed_deposit_room,
+        )?);
+        return_data[2 * PROJECTION_LEN] = lst_decimals;
+
+        set_return_data(&return_data);
+
+        Ok(())
+    }
+
+    /// Quotes the requested deposit and unstake under one set of fee
+    /// parameters, with `deposit_room` lamports left under the deposit cap.
+    fn project(
+        &self,
+        snapshot: &PoolSnapshot,
+        fee_params: &SetUnstakeFeeParamsInstructionData,
+        deposit_room: u64,
+    ) -> Result<[u8; PROJECTION_LEN], ProgramError> {
+        let deposit_accepted_lamports = self.data.deposit_lamports.min(deposit_room);
+        let deposit_lst_out = lamports_to_lst(
+            deposit_accepted_lamports,
+            snapshot.lst_supply,
+            snapshot.pool_lamports,
+        )?;
+
+        let lamports_value = lst_to_lamports(
+            self.data.unstake_lst,
+            snapshot.lst_supply,
+            snapshot.pool_lamports,
+        )?;
+        let fee_bps = unstake_fee_bps(
+            fee_params.unstake_fee_min_bps,
+            fee_params.unstake_fee_max_bps,
+            fee_params.liquidity_target_lamports,
+            snapshot.sol_reserves.saturating_sub(lamports_value),
+        );
+        let lamports_out = unstake_lamports_out(
+            lamports_value,
+            snapshot.sol_reserves,
+            fee_params.unstake_fee_min_bps,
+            fee_params.unstake_fee_max_bps,
+            fee_params.liquidity_target_lamports,
+        )
+        .unwrap_or(0);
+        let fee_lamports = if lamports_out == 0 {
+            0
+        } else {
+            lamports_value - lamports_out
+        };
+
+        let mut projection = [0u8; PROJECTION_LEN];
+        for (index, value) in [
+            deposit_accepted_lamports,
+            deposit_lst_out,
+            fee_bps as u64,
+            lamports_out,
+            fee_lamports,
+        ]
+        .iter()
+        .enumerate()
+        {
+            projection[index * 8..index * 8 + 8].copy_from_slice(&value.to_le_bytes());
+        }
+
+        Ok(projection)
+    }
+}
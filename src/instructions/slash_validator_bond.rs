@@ -0,0 +1,170 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::find_program_address,
+};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    instructions::helpers::{check_config_pda, record_managed_change, AccountCheck, SignerAccount},
+    state::{
+        Config, ManagedChange, ValidatorBond, ValidatorList, VALIDATOR_BOND_ACTIVE,
+        VALIDATOR_BOND_PENDING,
+    },
+};
+
+pub struct SlashValidatorBondAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub validator_bond_pda: &'a AccountInfo,
+    pub validator_list_pda: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SlashValidatorBondAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, validator_bond_pda, validator_list_pda, stake_account_reserve, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            validator_bond_pda,
+            validator_list_pda,
+            stake_account_reserve,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct SlashValidatorBondInstructionData {
+    pub lamports: u64,
+}
+
+impl TryFrom<&[u8]> for SlashValidatorBondInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let lamports = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+        Ok(Self { lamports })
+    }
+}
+
+/// Slashes a misbehaving validator's bond into the reserve stake account,
+/// where it accrues to LST holders through the exchange rate. An active
+/// validator is also removed from the validator set. An exiting validator
+/// remains exiting, so what is left of its bond unlocks on schedule.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Admin
+/// 1. `[]` Config PDA
+/// 2. `[WRITE]` Validator bond PDA
+/// 3. `[WRITE]` Validator list PDA
+/// 4. `[WRITE]` Stake account reserve
+/// 5. `[WRITE]` Pool state PDA
+pub struct SlashValidatorBond<'a> {
+    pub accounts: SlashValidatorBondAccounts<'a>,
+    pub data: SlashValidatorBondInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SlashValidatorBond<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SlashValidatorBondAccounts::try_from(accounts)?,
+            data: SlashValidatorBondInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SlashValidatorBond<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &20;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        drop(config_data);
+
+        if !self.accounts.validator_bond_pda.is_owned_by(&crate::ID) {
+            return Err(PinocchioError::InvalidValidatorBondPda.into());
+        }
+
+        let mut validator_bond_data = self.accounts.validator_bond_pda.try_borrow_mut_data()?;
+        let validator_bond = ValidatorBond::load_mut(&mut validator_bond_data)?;
+
+        let (expected_validator_bond_pda, _) = find_program_address(
+            &[b"validator_bond", &validator_bond.vote_account],
+            &crate::ID,
+        );
+        if expected_validator_bond_pda != *self.accounts.validator_bond_pda.key() {
+            return Err(PinocchioError::InvalidValidatorBondPda.into());
+        }
+
+        if validator_bond.status == VALIDATOR_BOND_PENDING {
+            return Err(PinocchioError::InvalidValidatorStatus.into());
+        }
+
+        validator_bond.bond_lamports = validator_bond
+            .bond_lamports
+            .checked_sub(self.data.lamports)
+            .ok_or(PinocchioError::SlashExceedsBond)?;
+
+        if validator_bond.status == VALIDATOR_BOND_ACTIVE {
+            let (expected_validator_list_pda, _) =
+                find_program_address(&[b"validator_list"], &crate::ID);
+            if expected_validator_list_pda != *self.accounts.validator_list_pda.key() {
+                return Err(PinocchioError::InvalidValidatorListPda.into());
+            }
+
+            let mut validator_list_data = self.accounts.validator_list_pda.try_borrow_mut_data()?;
+            ValidatorList::load_mut(&mut validator_list_data)?
+                .remove(&validator_bond.vote_account)?;
+
+            validator_bond.status = VALIDATOR_BOND_PENDING;
+        }
+
+        drop(validator_bond_data);
+
+        *self.accounts.validator_bond_pda.try_borrow_mut_lamports()? -= self.data.lamports;
+        *self
+            .accounts
+            .stake_account_reserve
+            .try_borrow_mut_lamports()? += self.data.lamports;
+
+        record_managed_change(
+            self.accounts.pool_state_pda,
+            ManagedChange::Deposited(self.data.lamports),
+        )
+    }
+}
@@ -0,0 +1,317 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::find_program_address,
+};
+use pinocchio_system::instructions::Transfer;
+use pinocchio_token::{
+    instructions::Transfer as TokenTransfer,
+    state::{Mint, TokenAccount},
+};
+
+use crate::{
+    errors::PinocchioError,
+    ids::{assert_system_program, assert_token_program},
+    instructions::helpers::{
+        check_config_pda, epoch_rate, managed_lamports, AccountCheck, SignerAccount,
+    },
+    math::lst_to_lamports,
+    state::{Config, LiquidityPool},
+};
+
+/// Which side of the buffer a `SwapBuffer` trader pays into.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SwapDirection {
+    /// Trader pays SOL into a buffer below target and receives vault LST.
+    SolForLst,
+    /// Trader pays LST into the vault and receives SOL from a buffer above
+    /// target.
+    LstForSol,
+}
+
+impl TryFrom<u8> for SwapDirection {
+    type Error = ProgramError;
+
+    fn try_from(direction: u8) -> Result<Self, Self::Error> {
+        match direction {
+            0 => Ok(Self::SolForLst),
+            1 => Ok(Self::LstForSol),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+pub struct SwapBufferAccounts<'a> {
+    pub trader: &'a AccountInfo,
+    pub trader_ata: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub liquidity_pool_pda: &'a AccountInfo,
+    pub lst_vault: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SwapBufferAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [trader, trader_ata, config_pda, liquidity_pool_pda, lst_vault, stake_account_main, stake_account_reserve, lst_mint, token_program, system_program, pool_state_pda] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(trader)?;
+
+        assert_system_program(system_program)?;
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            trader,
+            trader_ata,
+            config_pda,
+            liquidity_pool_pda,
+            lst_vault,
+            stake_account_main,
+            stake_account_reserve,
+            lst_mint,
+            token_program,
+            system_program,
+            pool_state_pda,
+        })
+    }
+}
+
+pub struct SwapBufferInstructionData {
+    pub direction: SwapDirection,
+    /// Lamports paid for `SolForLst`, LST paid for `LstForSol`.
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+}
+
+impl TryFrom<&[u8]> for SwapBufferInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 17 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let direction = SwapDirection::try_from(data[0])?;
+        let amount_in = u64::from_le_bytes(data[1..9].try_into().unwrap());
+        let min_amount_out = u64::from_le_bytes(data[9..17].try_into().unwrap());
+
+        if amount_in == 0 {
+            return Err(PinocchioError::ZeroLiquidity.into());
+        }
+
+        Ok(Self {
+            direction,
+            amount_in,
+            min_amount_out,
+        })
+    }
+}
+
+/// Swaps between the liquidity buffer's SOL and its vault LST at the fair
+/// exchange rate, only ever moving the buffer towards
+/// `liquidity_target_lamports`.
+///
+/// Below target, anyone may pay SOL into the buffer for vault LST, up to the
+/// deficit. Above target, anyone may pay LST into the vault for buffer SOL,
+/// up to the excess. The trade is value-neutral for the pool, and keeps the
+/// instant-unstake fee near its minimum without external arbitrage. Outputs
+/// round down in the pool's favor.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Trader
+/// 1. `[WRITE]` Trader LST ATA
+/// 2. `[]` Config PDA
+/// 3. `[WRITE]` Liquidity pool PDA
+/// 4. `[WRITE]` LST vault
+/// 5. `[]` Stake account main
+/// 6. `[]` Stake account reserve
+/// 7. `[]` LST mint
+/// 8. `[]` Token program
+/// 9. `[]` System program
+/// 10. `[]` Pool state PDA
+pub struct SwapBuffer<'a> {
+    pub accounts: SwapBufferAccounts<'a>,
+    pub data: SwapBufferInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SwapBuffer<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: SwapBufferAccounts::try_from(accounts)?,
+            data: SwapBufferInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> SwapBuffer<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &17;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        let liquidity_target_lamports = config.liquidity_target_lamports;
+
+        let validator_stake_lamports = config.validator_stake_lamports;
+        let cached_rate = epoch_rate(config)?;
+        drop(config_data);
+
+        let (expected_liquidity_pool_pda, liquidity_pool_bump) =
+            find_program_address(&[b"liquidity_pool"], &crate::ID);
+        if expected_liquidity_pool_pda != *self.accounts.liquidity_pool_pda.key() {
+            return Err(PinocchioError::InvalidLiquidityPoolPda.into());
+        }
+
+        let mut liquidity_pool_data = self.accounts.liquidity_pool_pda.try_borrow_mut_data()?;
+        let liquidity_pool = LiquidityPool::load_mut(&mut liquidity_pool_data)?;
+
+        if liquidity_pool.lst_vault != *self.accounts.lst_vault.key() {
+            return Err(PinocchioError::InvalidLstVault.into());
+        }
+
+        let trader_ata = TokenAccount::from_account_info(self.accounts.trader_ata)?;
+        if trader_ata.owner() != self.accounts.trader.key()
+            || trader_ata.mint() != self.accounts.lst_mint.key()
+        {
+            return Err(PinocchioError::InvalidProviderAta.into());
+        }
+        drop(trader_ata);
+
+        let (total_lamports, lst_supply) = cached_rate.unwrap_or((
+            managed_lamports(
+                self.accounts.pool_state_pda,
+                self.accounts.stake_account_main,
+                self.accounts.stake_account_reserve,
+                validator_stake_lamports,
+            )?,
+            Mint::from_account_info(self.accounts.lst_mint)?.supply(),
+        ));
+
+        let liquidity_pool_bump_binding = [liquidity_pool_bump];
+        let liquidity_pool_seeds = &[
+            Seed::from(b"liquidity_pool"),
+            Seed::from(&liquidity_pool_bump_binding),
+        ];
+
+        match self.data.direction {
+            SwapDirection::SolForLst => {
+                let deficit = liquidity_target_lamports.saturating_sub(
+                    liquidity_pool
+                        .sol_reserves
+                        .saturating_add(liquidity_pool.pending_unstake_lamports),
+                );
+                if self.data.amount_in > deficit {
+                    return Err(PinocchioError::SwapExceedsImbalance.into());
+                }
+
+                if total_lamports == 0 {
+                    return Err(PinocchioError::ZeroLiquidity.into());
+                }
+
+                let lst_out = ((self.data.amount_in as u128)
+                    .checked_mul(lst_supply as u128)
+                    .ok_or(ProgramError::ArithmeticOverflow)?
+                    / total_lamports as u128) as u64;
+
+                if lst_out == 0 {
+                    return Err(PinocchioError::ZeroLiquidity.into());
+                }
+
+                if lst_out < self.data.min_amount_out {
+                    return Err(PinocchioError::SlippageExceeded.into());
+                }
+
+                if lst_out > TokenAccount::from_account_info(self.accounts.lst_vault)?.amount() {
+                    return Err(PinocchioError::SwapExceedsImbalance.into());
+                }
+
+                liquidity_pool.sol_reserves = liquidity_pool
+                    .sol_reserves
+                    .checked_add(self.data.amount_in)
+                    .ok_or(ProgramError::ArithmeticOverflow)?;
+
+                drop(liquidity_pool_data);
+
+                Transfer {
+                    from: self.accounts.trader,
+                    to: self.accounts.liquidity_pool_pda,
+                    lamports: self.data.amount_in,
+                }
+                .invoke()?;
+
+                TokenTransfer {
+                    from: self.accounts.lst_vault,
+                    to: self.accounts.trader_ata,
+                    authority: self.accounts.liquidity_pool_pda,
+                    amount: lst_out,
+                }
+                .invoke_signed(&[Signer::from(liquidity_pool_seeds)])?;
+            }
+            SwapDirection::LstForSol => {
+                let excess = liquidity_pool
+                    .sol_reserves
+                    .saturating_sub(liquidity_target_lamports);
+
+                let lamports_out =
+                    lst_to_lamports(self.data.amount_in, lst_supply, total_lamports)?;
+
+                if lamports_out > excess {
+                    return Err(PinocchioError::SwapExceedsImbalance.into());
+                }
+
+                if lamports_out == 0 {
+                    return Err(PinocchioError::ZeroLiquidity.into());
+                }
+
+                if lamports_out < self.data.min_amount_out {
+                    return Err(PinocchioError::SlippageExceeded.into());
+                }
+
+                liquidity_pool.sol_reserves -= lamports_out;
+
+                drop(liquidity_pool_data);
+
+                TokenTransfer {
+                    from: self.accounts.trader_ata,
+                    to: self.accounts.lst_vault,
+                    authority: self.accounts.trader,
+                    amount: self.data.amount_in,
+                }
+                .invoke()?;
+
+                *self.accounts.liquidity_pool_pda.try_borrow_mut_lamports()? -= lamports_out;
+                *self.accounts.trader.try_borrow_mut_lamports()? += lamports_out;
+            }
+        }
+
+        Ok(())
+    }
+}
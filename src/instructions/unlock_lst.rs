@@ -0,0 +1,115 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+};
+use pinocchio_token::instructions::{CloseAccount, Transfer};
+
+use crate::{
+    errors::PinocchioError,
+    ids::assert_token_program,
+    instructions::helpers::{
+        check_vote_escrow, AccountCheck, AccountClose, ProgramAccount, SignerAccount,
+    },
+    state::VoteEscrow,
+};
+
+pub struct UnlockLstAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub owner_ata: &'a AccountInfo,
+    pub escrow_pda: &'a AccountInfo,
+    pub escrow_vault: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for UnlockLstAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [owner, owner_ata, escrow_pda, escrow_vault, token_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(owner)?;
+
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            owner,
+            owner_ata,
+            escrow_pda,
+            escrow_vault,
+            token_program,
+        })
+    }
+}
+
+/// Returns all LST in the owner's vote escrow to their ATA once `unlock_ts`
+/// has passed, dropping its voting weight, and closes the escrow and its
+/// vault, refunding their rent to the owner.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Owner
+/// 1. `[WRITE]` Owner LST ATA
+/// 2. `[WRITE]` Vote escrow PDA
+/// 3. `[WRITE]` Escrow vault
+/// 4. `[]` Token program
+pub struct UnlockLst<'a> {
+    pub accounts: UnlockLstAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for UnlockLst<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: UnlockLstAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> UnlockLst<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &62;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let escrow_bump = check_vote_escrow(
+            self.accounts.escrow_pda,
+            self.accounts.escrow_vault,
+            self.accounts.owner,
+        )?;
+
+        let escrow_data = self.accounts.escrow_pda.try_borrow_data()?;
+        let escrow = VoteEscrow::load(&escrow_data)?;
+        if Clock::get()?.unix_timestamp < escrow.unlock_ts {
+            return Err(PinocchioError::LockNotExpired.into());
+        }
+        let locked_amount = escrow.locked_amount;
+        drop(escrow_data);
+
+        let escrow_bump_binding = [escrow_bump];
+        let escrow_seeds = &[
+            Seed::from(b"vote_escrow"),
+            Seed::from(self.accounts.owner.key()),
+            Seed::from(&escrow_bump_binding),
+        ];
+
+        Transfer {
+            from: self.accounts.escrow_vault,
+            to: self.accounts.owner_ata,
+            authority: self.accounts.escrow_pda,
+            amount: locked_amount,
+        }
+        .invoke_signed(&[Signer::from(escrow_seeds)])?;
+
+        CloseAccount {
+            account: self.accounts.escrow_vault,
+            destination: self.accounts.owner,
+            authority: self.accounts.escrow_pda,
+        }
+        .invoke_signed(&[Signer::from(escrow_seeds)])?;
+
+        ProgramAccount::close(self.accounts.escrow_pda, self.accounts.owner)
+    }
+}
@@ -0,0 +1,261 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit_admin_action, ROLE_ADMIN},
+    ids::{
+        assert_clock_sysvar, assert_stake_program, assert_system_program, assert_vote_account,
+        STAKE_PROGRAM_ID,
+    },
+    instructions::helpers::{
+        accrue_rewards, check_config_pda, check_outgoing_reserve, check_validator_list,
+        create_pda_account, record_param_change, record_pool_stake, reserve_spare_lamports,
+        stake_account_breakdown, stake_account_space, staked_lamports, AccountCheck,
+        ProgramAccount, SignerAccount, StakeAccountDeactivate, StakeAccountSplit,
+    },
+    state::{Config, ValidatorList},
+};
+
+pub struct UpdateConfigAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub changelog_pda: &'a AccountInfo,
+    pub validator_vote_account: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub validator_list_pda: &'a AccountInfo,
+    pub clock_sysvar: &'a AccountInfo,
+    pub stake_program: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+    pub stake_account_outgoing: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for UpdateConfigAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config_pda, changelog_pda, validator_vote_account, stake_account_main, stake_account_reserve, validator_list_pda, clock_sysvar, stake_program, pool_state_pda, stake_account_outgoing, system_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(admin)?;
+
+        assert_vote_account(validator_vote_account)?;
+        assert_clock_sysvar(clock_sysvar)?;
+        assert_stake_program(stake_program)?;
+        assert_system_program(system_program)?;
+
+        Ok(Self {
+            admin,
+            config_pda,
+            changelog_pda,
+            validator_vote_account,
+            stake_account_main,
+            stake_account_reserve,
+            validator_list_pda,
+            clock_sysvar,
+            stake_program,
+            pool_state_pda,
+            stake_account_outgoing,
+            system_program,
+        })
+    }
+}
+
+/// Moves the pool to a new validator: replaces the config's
+/// `validator_vote_pubkey` with the passed vote account and deactivates main,
+/// which `CrankRedelegateMain` delegates to the new validator once it has
+/// cooled down. Deposits delegated by `CrankInitializeReserve` go to the new
+/// validator right away.
+///
+/// A reserve already delegated to the old validator could no longer be merged
+/// into main, so it is rotated out: its whole stake is split into the outgoing
+/// reserve (`b"stake_outgoing"`, rent paid by the admin) and deactivated
+/// there, and `CrankRedelegateMain` merges it into main once both cooled
+/// down. The drained reserve keeps taking deposits, and `AdoptReserve` and
+/// `CrankInitializeReserve` delegate them to the new validator in the
+/// meantime. A second rotation before that merge fails with
+/// `ReserveRotationPending`.
+///
+/// A validator in the validator list is rejected with
+/// `ValidatorAlreadyListed`. The validator list PDA may be any account
+/// while no list exists.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Admin
+/// 1. `[WRITE]` Config PDA
+/// 2. `[WRITE]` Changelog PDA
+/// 3. `[]` New validator vote account
+/// 4. `[WRITE]` Stake account main
+/// 5. `[WRITE]` Stake account reserve
+/// 6. `[]` Validator list PDA
+/// 7. `[]` Clock sysvar
+/// 8. `[]` Stake program
+/// 9. `[WRITE]` Pool state PDA
+/// 10. `[WRITE]` Stake account outgoing
+/// 11. `[]` System program
+pub struct UpdateConfig<'a> {
+    pub accounts: UpdateConfigAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for UpdateConfig<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            accounts: UpdateConfigAccounts::try_from(accounts)?,
+        })
+    }
+}
+
+impl<'a> UpdateConfig<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &82;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let bump = check_config_pda(self.accounts.config_pda)?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.admin != *self.accounts.admin.key() {
+            return Err(PinocchioError::NotAdmin.into());
+        }
+        emit_admin_action(
+            self.accounts.pool_state_pda,
+            self.accounts.admin,
+            ROLE_ADMIN,
+            *Self::DISCRIMINATOR,
+        )?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        let validator_vote_pubkey: Pubkey = *self.accounts.validator_vote_account.key();
+        if config.validator_vote_pubkey == validator_vote_pubkey {
+            return Err(PinocchioError::InvalidValidatorVoteKey.into());
+        }
+
+        let outgoing_bump = check_outgoing_reserve(self.accounts.stake_account_outgoing)?;
+        let rotating = staked_lamports(self.accounts.stake_account_reserve)? > 0;
+        if !rotating {
+            reserve_spare_lamports(
+                self.accounts.stake_account_reserve,
+                PinocchioError::ReserveNotAdopted,
+            )?;
+        } else if config.outgoing_stake_lamports != 0 {
+            return Err(PinocchioError::ReserveRotationPending.into());
+        }
+
+        if !self.accounts.validator_list_pda.data_is_empty() {
+            check_validator_list(self.accounts.validator_list_pda)?;
+            let validator_list_data = self.accounts.validator_list_pda.try_borrow_data()?;
+            if ValidatorList::load(&validator_list_data)?.contains(&validator_vote_pubkey) {
+                return Err(PinocchioError::ValidatorAlreadyListed.into());
+            }
+        }
+
+        let old_value = config.validator_vote_pubkey;
+        config.set_validator_vote_pubkey(validator_vote_pubkey);
+        let validator_stake_lamports = config.validator_stake_lamports;
+        drop(config_data);
+
+        let bump_binding = [bump];
+        let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
+        if rotating {
+            self.rotate_reserve(outgoing_bump, validator_stake_lamports, config_seeds)?;
+        }
+
+        let breakdown =
+            stake_account_breakdown(self.accounts.stake_account_main, Clock::get()?.epoch)?;
+        if breakdown.active > 0 || breakdown.activating > 0 {
+            ProgramAccount::deactivate_stake_account(
+                self.accounts.stake_account_main,
+                self.accounts.clock_sysvar,
+                self.accounts.config_pda,
+                config_seeds,
+            )?;
+        }
+
+        record_param_change(
+            self.accounts.changelog_pda,
+            self.accounts.admin,
+            *Self::DISCRIMINATOR,
+            &[&old_value],
+            &[&validator_vote_pubkey],
+        )
+    }
+
+    /// Splits the delegated reserve's whole balance into the outgoing reserve
+    /// and deactivates it, leaving the reserve drained. The outgoing stake
+    /// leaves the pool's recorded stake, and the config keeps it in
+    /// `outgoing_stake_lamports` until `CrankRedelegateMain` merges it back.
+    fn rotate_reserve(
+        &self,
+        outgoing_bump: u8,
+        validator_stake_lamports: u64,
+        config_seeds: &[Seed],
+    ) -> Result<(), ProgramError> {
+        accrue_rewards(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+
+        let space = stake_account_space(self.accounts.stake_account_reserve);
+        let outgoing_bump_binding = [outgoing_bump];
+        let outgoing_seeds = &[
+            Seed::from(b"stake_outgoing"),
+            Seed::from(&outgoing_bump_binding),
+        ];
+        create_pda_account(
+            self.accounts.admin,
+            self.accounts.stake_account_outgoing,
+            outgoing_seeds,
+            Rent::get()?.minimum_balance(space),
+            space,
+            &STAKE_PROGRAM_ID,
+        )?;
+
+        ProgramAccount::split_stake_account(
+            self.accounts.stake_account_reserve,
+            self.accounts.stake_account_outgoing,
+            &self.accounts.stake_account_reserve.lamports(),
+            self.accounts.config_pda,
+            config_seeds,
+        )?;
+        ProgramAccount::deactivate_stake_account(
+            self.accounts.stake_account_outgoing,
+            self.accounts.clock_sysvar,
+            self.accounts.config_pda,
+            config_seeds,
+        )?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        Config::load_mut(&mut config_data)?.outgoing_stake_lamports =
+            staked_lamports(self.accounts.stake_account_outgoing)?;
+        drop(config_data);
+
+        record_pool_stake(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )
+    }
+}
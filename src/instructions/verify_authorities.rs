@@ -0,0 +1,155 @@
+use pinocchio::{
+    account_info::AccountInfo, msg, program_error::ProgramError, pubkey::find_program_address,
+};
+
+use crate::{
+    errors::PinocchioError,
+    instructions::helpers::{check_config_pda, stake_authorities, AccountCheck, ProgramAccount},
+    state::{
+        Config, SplitRecord, AUTHORITY_DRIFT_BUFFER, AUTHORITY_DRIFT_MAIN, AUTHORITY_DRIFT_RESERVE,
+        AUTHORITY_DRIFT_SPLIT,
+    },
+};
+
+/// Most pending splits one call may audit, passed as (split account, split
+/// record) pairs.
+pub const MAX_VERIFY_SPLITS: usize = 16;
+
+pub struct VerifyAuthoritiesAccounts<'a> {
+    pub config_pda: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub buffer_stake_account: &'a AccountInfo,
+    pub splits: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for VerifyAuthoritiesAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [config_pda, stake_account_main, stake_account_reserve, buffer_stake_account, splits @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if splits.len() % 2 != 0 {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        Ok(Self {
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            buffer_stake_account,
+            splits,
+        })
+    }
+}
+
+/// Checks that every program-controlled stake account still has the config
+/// PDA as both staker and withdrawer.
+///
+/// Covers main, the reserve, the liquidity pool's buffer stake account and
+/// any pending split accounts passed after them. Accounts that are not
+/// initialized stake accounts are skipped. Each mismatch is logged, and the
+/// config's `authority_drift` bits are overwritten with this call's result,
+/// so monitors can read the latest audit from the config alone. Split drift
+/// is only as complete as the splits passed in.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE]` Config PDA
+/// 1. `[]` Stake account main
+/// 2. `[]` Stake account reserve
+/// 3. `[]` Buffer stake account PDA
+/// 4. `[]` Split account, then its `[]` split record PDA, repeated for up to
+///    `MAX_VERIFY_SPLITS` splits
+pub struct VerifyAuthorities<'a> {
+    pub accounts: VerifyAuthoritiesAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for VerifyAuthorities<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        let accounts = VerifyAuthoritiesAccounts::try_from(accounts)?;
+
+        if accounts.splits.len() / 2 > MAX_VERIFY_SPLITS {
+            return Err(PinocchioError::BatchTooLarge.into());
+        }
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> VerifyAuthorities<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &39;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let mut config_data = self.accounts.config_pda.try_borrow_mut_data()?;
+        let config = Config::load_mut(&mut config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        let (expected_buffer_stake_account, _) =
+            find_program_address(&[b"buffer_stake"], &crate::ID);
+        if expected_buffer_stake_account != *self.accounts.buffer_stake_account.key() {
+            return Err(PinocchioError::InvalidBufferStakePda.into());
+        }
+
+        let mut drift = 0;
+
+        if !self.has_expected_authorities(self.accounts.stake_account_main)? {
+            msg!("Authority mismatch: stake account main");
+            drift |= AUTHORITY_DRIFT_MAIN;
+        }
+
+        if !self.has_expected_authorities(self.accounts.stake_account_reserve)? {
+            msg!("Authority mismatch: stake account reserve");
+            drift |= AUTHORITY_DRIFT_RESERVE;
+        }
+
+        if !self.has_expected_authorities(self.accounts.buffer_stake_account)? {
+            msg!("Authority mismatch: buffer stake account");
+            drift |= AUTHORITY_DRIFT_BUFFER;
+        }
+
+        for pair in self.accounts.splits.chunks_exact(2) {
+            let (split_account, split_record) = (&pair[0], &pair[1]);
+
+            let (expected_split_record, _) =
+                find_program_address(&[b"split_record", split_account.key()], &crate::ID);
+            if expected_split_record != *split_record.key() {
+                return Err(PinocchioError::InvalidSplitRecordPda.into());
+            }
+            ProgramAccount::check(split_record)?;
+            SplitRecord::load(&split_record.try_borrow_data()?)?;
+
+            if !self.has_expected_authorities(split_account)? {
+                msg!("Authority mismatch: split account");
+                drift |= AUTHORITY_DRIFT_SPLIT;
+            }
+        }
+
+        config.authority_drift = drift;
+
+        Ok(())
+    }
+
+    fn has_expected_authorities(&self, stake_account: &AccountInfo) -> Result<bool, ProgramError> {
+        match stake_authorities(stake_account)? {
+            Some((staker, withdrawer)) => Ok(staker == *self.accounts.config_pda.key()
+                && withdrawer == *self.accounts.config_pda.key()),
+            None => Ok(true),
+        }
+    }
+}
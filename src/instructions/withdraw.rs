@@ -1,15 +1,37 @@
 use pinocchio::{
-    account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
     pubkey::find_program_address,
+    sysvars::{rent::Rent, Sysvar},
 };
+use pinocchio_token::{instructions::MintTo, state::Mint};
 
 use crate::{
     errors::PinocchioError,
+    events::{emit, EVENT_DEPOSIT, EVENT_WITHDRAW},
+    ids::{
+        assert_clock_sysvar, assert_stake_history_sysvar, assert_stake_program,
+        assert_token_program, ASSOCIATED_TOKEN_PROGRAM_ID,
+    },
     instructions::helpers::{
-        AccountCheck, ProgramAccount, SignerAccount, StakeAccountWithdraw, STAKE_PROGRAM_ID,
+        check_config_pda, check_pool_cap, epoch_rate, expect_lst_supply_change, managed_lamports,
+        pool_lamports, record_managed_change, record_principal, AccountCheck, AccountClose,
+        ProgramAccount, SignerAccount, StakeAccountWithdraw, SupplyChange, LAMPORTS_PER_SOL,
     },
+    math::lamports_to_lst,
+    state::{Config, ManagedChange, SplitRecord},
 };
 
+/// Accounts `Withdraw` needs to deposit the withdrawn SOL back into the pool.
+pub struct RestakeAccounts<'a> {
+    pub withdrawer_ata: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+
 pub struct WithdrawAccounts<'a> {
     pub account_to_withdraw_from: &'a AccountInfo,
     pub withdrawer: &'a AccountInfo,
@@ -17,23 +39,49 @@ pub struct WithdrawAccounts<'a> {
     pub history_sysvar: &'a AccountInfo,
     pub config_pda: &'a AccountInfo,
     pub stake_program: &'a AccountInfo,
+    pub split_record: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+    /// Only passed when restaking.
+    pub restake: Option<RestakeAccounts<'a>>,
+    /// Only passed when a separate payer funded the split.
+    pub rent_payer: Option<&'a AccountInfo>,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawAccounts<'a> {
     type Error = ProgramError;
 
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [account_to_withdraw_from, withdrawer, clock_sysvar, history_sysvar, config_pda, stake_program] =
+        let [account_to_withdraw_from, withdrawer, clock_sysvar, history_sysvar, config_pda, stake_program, split_record, pool_state_pda, optional_accounts @ ..] =
             accounts
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
+        let (restake, rent_payer) = match optional_accounts {
+            [] => (None, None),
+            [rent_payer] => (None, Some(rent_payer)),
+            [withdrawer_ata, lst_mint, stake_account_main, stake_account_reserve, token_program, rent_payer @ ..]
+                if rent_payer.len() <= 1 =>
+            {
+                assert_token_program(token_program)?;
+
+                let restake = RestakeAccounts {
+                    withdrawer_ata,
+                    lst_mint,
+                    stake_account_main,
+                    stake_account_reserve,
+                    token_program,
+                };
+                (Some(restake), rent_payer.first())
+            }
+            _ => return Err(ProgramError::NotEnoughAccountKeys),
+        };
+
         SignerAccount::check(withdrawer)?;
 
-        if stake_program.key() != &STAKE_PROGRAM_ID {
-            return Err(PinocchioError::InvalidStakeProgram.into());
-        }
+        assert_stake_program(stake_program)?;
+        assert_clock_sysvar(clock_sysvar)?;
+        assert_stake_history_sysvar(history_sysvar)?;
 
         Ok(Self {
             account_to_withdraw_from,
@@ -42,29 +90,46 @@ impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawAccounts<'a> {
             history_sysvar,
             config_pda,
             stake_program,
+            split_record,
+            pool_state_pda,
+            restake,
+            rent_payer,
         })
     }
 }
 
 pub struct WithdrawInstructionData {
+    /// Nonce the split account was derived with. Not checked, see `process`.
     pub nonce: u64,
+    /// Deposit the withdrawn SOL back into the pool instead of paying it out.
+    pub restake: bool,
 }
 
 impl TryFrom<&[u8]> for WithdrawInstructionData {
     type Error = ProgramError;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
-        if data.len() != 8 {
-            return Err(ProgramError::InvalidInstructionData);
-        }
+        let restake = match data.len() {
+            8 => false,
+            9 if data[8] <= 1 => data[8] == 1,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
 
         let nonce = u64::from_le_bytes(data[0..8].try_into().unwrap());
 
-        Ok(Self { nonce })
+        Ok(Self { nonce, restake })
     }
 }
 
-/// Withdraws SOL from deactivated split stake account to user.
+/// Withdraws SOL from deactivated split stake account to user and closes
+/// the split record, unless it still schedules tranches for later epochs.
+/// A record kept open is marked claimed, and withdrawing it again fails with
+/// `SplitAlreadyClaimed`.
+///
+/// With the trailing `restake` flag the SOL is withdrawn straight into the
+/// reserve instead and LST is minted to the withdrawer at the current rate,
+/// as a `Deposit` would, so it is never left unstaked in the wallet. Like a
+/// deposit, the restake fails with `PoolCapExceeded` past the pool cap.
 ///
 /// Accounts expected:
 ///
@@ -74,6 +139,15 @@ impl TryFrom<&[u8]> for WithdrawInstructionData {
 /// 3. `[]` History sysvar
 /// 4. `[WRITE]` Config PDA
 /// 5. `[]` Stake program
+/// 6. `[WRITE]` Split record PDA
+/// 7. `[WRITE]` Pool state PDA
+/// 8. `[WRITE]` Withdrawer ATA (restake only)
+/// 9. `[WRITE]` LST mint (restake only)
+/// 10. `[]` Stake account main (restake only)
+/// 11. `[WRITE]` Stake account reserve (restake only)
+/// 12. `[]` Token program (restake only)
+/// 13. `[WRITE]` Rent payer (only when the split record names a payer other
+///     than the withdrawer; index 8 without the restake accounts)
 pub struct Withdraw<'a> {
     pub accounts: WithdrawAccounts<'a>,
     pub data: WithdrawInstructionData,
@@ -83,10 +157,14 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Withdraw<'a> {
     type Error = ProgramError;
 
     fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
-        Ok(Self {
-            accounts: WithdrawAccounts::try_from(accounts)?,
-            data: WithdrawInstructionData::try_from(data)?,
-        })
+        let accounts = WithdrawAccounts::try_from(accounts)?;
+        let data = WithdrawInstructionData::try_from(data)?;
+
+        if data.restake != accounts.restake.is_some() {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        Ok(Self { accounts, data })
     }
 }
 
@@ -94,38 +172,212 @@ impl<'a> Withdraw<'a> {
     pub const DISCRIMINATOR: &'static u8 = &5;
 
     pub fn process(&self) -> Result<(), ProgramError> {
-        let (expected_config_pda, bump) = find_program_address(&[b"config"], &crate::ID);
-        if *self.accounts.config_pda.key() != expected_config_pda {
-            return Err(PinocchioError::InvalidConfigPda.into());
-        }
+        let bump = check_config_pda(self.accounts.config_pda)?;
 
-        let nonce_bytes = self.data.nonce.to_le_bytes();
-        let expected_split_account = find_program_address(
+        // The split account is not derived from the withdrawer and nonce
+        // here: a split handed off with `ExecuteSplitHandoff` keeps the
+        // address derived from its first owner. Only the program creates
+        // split records, at the PDA of the split account, so a record naming
+        // the withdrawer identifies the split either way.
+        let expected_split_record = find_program_address(
             &[
-                b"split_account",
-                self.accounts.withdrawer.key(),
-                &nonce_bytes,
+                b"split_record",
+                self.accounts.account_to_withdraw_from.key(),
             ],
             &crate::ID,
         )
         .0;
 
-        if *self.accounts.account_to_withdraw_from.key() != expected_split_account {
+        if *self.accounts.split_record.key() != expected_split_record {
+            return Err(PinocchioError::InvalidSplitRecordPda.into());
+        }
+
+        ProgramAccount::check(self.accounts.split_record)?;
+
+        let split_record_data = self.accounts.split_record.try_borrow_data()?;
+        let split_record = SplitRecord::load(&split_record_data)?;
+        if split_record.owner != *self.accounts.withdrawer.key() {
             return Err(PinocchioError::InvalidSplitAccountPda.into());
         }
+        if split_record.claimed != 0 {
+            return Err(PinocchioError::SplitAlreadyClaimed.into());
+        }
+        let lamports_scheduled = split_record.lamports_scheduled;
+        let rent_payer = split_record.rent_payer;
+        drop(split_record_data);
 
         let bump_binding = [bump];
         let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
 
-        ProgramAccount::withdraw_stake_account(
-            self.accounts.account_to_withdraw_from,
-            self.accounts.withdrawer,
-            self.accounts.clock_sysvar,
-            self.accounts.history_sysvar,
-            self.accounts.config_pda,
-            config_seeds,
+        let lamports_withdrawn = self.accounts.account_to_withdraw_from.lamports();
+
+        // A sponsored split returns the payer's rent and funding to the payer.
+        let refund = if rent_payer == *self.accounts.withdrawer.key() {
+            None
+        } else {
+            let payer = self
+                .accounts
+                .rent_payer
+                .ok_or(PinocchioError::InvalidRentPayer)?;
+            if *payer.key() != rent_payer {
+                return Err(PinocchioError::InvalidRentPayer.into());
+            }
+
+            let funding = Rent::get()?
+                .minimum_balance(self.accounts.account_to_withdraw_from.data_len())
+                .checked_add(LAMPORTS_PER_SOL)
+                .ok_or(ProgramError::ArithmeticOverflow)?
+                .min(lamports_withdrawn);
+            Some((payer, funding))
+        };
+        let proceeds = lamports_withdrawn - refund.map_or(0, |(_, funding)| funding);
+
+        let (recipient, lst_to_mint) = match &self.accounts.restake {
+            Some(restake) => (
+                restake.stake_account_reserve,
+                self.restake_quote(restake, proceeds)?,
+            ),
+            None => (self.accounts.withdrawer, 0),
+        };
+
+        match refund {
+            Some((payer, _)) => {
+                ProgramAccount::withdraw_stake_account_lamports(
+                    self.accounts.account_to_withdraw_from,
+                    recipient,
+                    self.accounts.clock_sysvar,
+                    self.accounts.history_sysvar,
+                    self.accounts.config_pda,
+                    proceeds,
+                    config_seeds,
+                )?;
+                ProgramAccount::withdraw_stake_account(
+                    self.accounts.account_to_withdraw_from,
+                    payer,
+                    self.accounts.clock_sysvar,
+                    self.accounts.history_sysvar,
+                    self.accounts.config_pda,
+                    config_seeds,
+                )?;
+            }
+            None => ProgramAccount::withdraw_stake_account(
+                self.accounts.account_to_withdraw_from,
+                recipient,
+                self.accounts.clock_sysvar,
+                self.accounts.history_sysvar,
+                self.accounts.config_pda,
+                config_seeds,
+            )?,
+        }
+
+        if let Some(restake) = &self.accounts.restake {
+            expect_lst_supply_change(
+                self.accounts.pool_state_pda,
+                restake.lst_mint,
+                SupplyChange::Minted(lst_to_mint),
+                || {
+                    MintTo {
+                        mint: restake.lst_mint,
+                        account: restake.withdrawer_ata,
+                        mint_authority: self.accounts.config_pda,
+                        amount: lst_to_mint,
+                    }
+                    .invoke_signed(&[Signer::from(config_seeds)])
+                },
+            )?;
+            record_principal(
+                self.accounts.pool_state_pda,
+                restake.lst_mint,
+                proceeds,
+                lst_to_mint,
+            )?;
+            record_managed_change(
+                self.accounts.pool_state_pda,
+                ManagedChange::Deposited(proceeds),
+            )?;
+        }
+
+        // The record still holds the schedule for later tranches.
+        if lamports_scheduled == 0 {
+            ProgramAccount::close(self.accounts.split_record, self.accounts.withdrawer)?;
+        } else {
+            SplitRecord::load_mut(&mut self.accounts.split_record.try_borrow_mut_data()?)?
+                .claimed = 1;
+        }
+
+        emit(
+            self.accounts.pool_state_pda,
+            EVENT_WITHDRAW,
+            &[
+                self.accounts.withdrawer.key(),
+                self.accounts.account_to_withdraw_from.key(),
+                &lamports_withdrawn.to_le_bytes(),
+            ],
         )?;
 
+        if self.accounts.restake.is_some() {
+            emit(
+                self.accounts.pool_state_pda,
+                EVENT_DEPOSIT,
+                &[
+                    self.accounts.withdrawer.key(),
+                    &proceeds.to_le_bytes(),
+                    &lst_to_mint.to_le_bytes(),
+                ],
+            )?;
+        }
+
         Ok(())
     }
+
+    /// Checks the restake accounts against the config and prices `lamports`
+    /// in LST as `Deposit` would, before they reach the reserve.
+    fn restake_quote(&self, restake: &RestakeAccounts, lamports: u64) -> Result<u64, ProgramError> {
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *restake.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        config.status()?.check_deposits()?;
+
+        if config.stake_account_reserve != *restake.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        if config.lst_mint != *restake.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        let expected_ata = find_program_address(
+            &[
+                self.accounts.withdrawer.key(),
+                restake.token_program.key(),
+                restake.lst_mint.key(),
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .0;
+        if expected_ata != *restake.withdrawer_ata.key() {
+            return Err(PinocchioError::InvalidWithdrawerAta.into());
+        }
+
+        let live_pool_lamports = pool_lamports(
+            restake.stake_account_main,
+            restake.stake_account_reserve,
+            config.validator_stake_lamports,
+        )?;
+        check_pool_cap(config.max_pool_lamports, live_pool_lamports, lamports)?;
+        let managed = managed_lamports(
+            self.accounts.pool_state_pda,
+            restake.stake_account_main,
+            restake.stake_account_reserve,
+            config.validator_stake_lamports,
+        )?;
+
+        let (total_sol_in_pool, total_lst_supply) = epoch_rate(config)?
+            .unwrap_or((managed, Mint::from_account_info(restake.lst_mint)?.supply()));
+        lamports_to_lst(lamports, total_lst_supply, total_sol_in_pool)
+    }
 }
@@ -0,0 +1,166 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+};
+use pinocchio_token::{instructions::Transfer, state::TokenAccount};
+
+use crate::{
+    errors::PinocchioError,
+    ids::assert_token_program,
+    instructions::helpers::{
+        check_config_pda, check_payout_position, check_reward_payout, AccountCheck, SignerAccount,
+    },
+    state::{Config, PayoutPosition, RewardPayout},
+};
+
+pub struct WithdrawPayoutAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub owner_ata: &'a AccountInfo,
+    pub position_pda: &'a AccountInfo,
+    pub reward_payout_pda: &'a AccountInfo,
+    pub payout_vault: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawPayoutAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [owner, owner_ata, position_pda, reward_payout_pda, payout_vault, lst_mint, config_pda, token_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(owner)?;
+
+        assert_token_program(token_program)?;
+
+        Ok(Self {
+            owner,
+            owner_ata,
+            position_pda,
+            reward_payout_pda,
+            payout_vault,
+            lst_mint,
+            config_pda,
+            token_program,
+        })
+    }
+}
+
+pub struct WithdrawPayoutInstructionData {
+    pub shares: u64,
+}
+
+impl TryFrom<&[u8]> for WithdrawPayoutInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            shares: u64::from_le_bytes(data.try_into().unwrap()),
+        })
+    }
+}
+
+/// Redeems `shares` of the owner's payout position for their LST in the
+/// payout vault, which is less than was enrolled by what `CrankRewardPayout`
+/// has burned to pay out SOL. SOL already paid to the position stays
+/// claimable, and the position is kept even with no shares left. Fails with
+/// `InsufficientPayoutShares` if the position holds fewer than `shares`, and
+/// with `PayoutNotCranked` until this epoch's `CrankRewardPayout` has run.
+///
+/// Accounts expected:
+///
+/// 0. `[SIGNER]` Owner
+/// 1. `[WRITE]` Owner LST ATA
+/// 2. `[WRITE]` Payout position PDA
+/// 3. `[WRITE]` Reward payout PDA
+/// 4. `[WRITE]` Payout vault
+/// 5. `[]` LST mint
+/// 6. `[]` Config PDA
+/// 7. `[]` Token program
+pub struct WithdrawPayout<'a> {
+    pub accounts: WithdrawPayoutAccounts<'a>,
+    pub data: WithdrawPayoutInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for WithdrawPayout<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: WithdrawPayoutAccounts::try_from(accounts)?,
+            data: WithdrawPayoutInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> WithdrawPayout<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &75;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        if Config::load(&config_data)?.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+        drop(config_data);
+
+        let reward_payout_bump = check_reward_payout(
+            self.accounts.reward_payout_pda,
+            self.accounts.payout_vault,
+            self.accounts.lst_mint,
+            self.accounts.token_program,
+        )?;
+        check_payout_position(self.accounts.position_pda, self.accounts.owner)?;
+
+        let vault_balance = TokenAccount::from_account_info(self.accounts.payout_vault)?.amount();
+
+        let mut reward_payout_data = self.accounts.reward_payout_pda.try_borrow_mut_data()?;
+        let reward_payout = RewardPayout::load_mut(&mut reward_payout_data)?;
+        reward_payout.check_cranked(Clock::get()?.epoch)?;
+
+        let rewards_per_share = reward_payout.rewards_per_share;
+        let mut position_data = self.accounts.position_pda.try_borrow_mut_data()?;
+        let position = PayoutPosition::load_mut(&mut position_data)?;
+        let shares = position
+            .shares
+            .checked_sub(self.data.shares)
+            .ok_or(PinocchioError::InsufficientPayoutShares)?;
+
+        let lst_amount = reward_payout.lst_for(self.data.shares, vault_balance);
+        if lst_amount == 0 {
+            return Err(PinocchioError::ZeroLiquidity.into());
+        }
+
+        position.settle(rewards_per_share)?;
+        position.set_shares(shares, rewards_per_share);
+        drop(position_data);
+
+        reward_payout.total_shares -= self.data.shares;
+        drop(reward_payout_data);
+
+        let reward_payout_bump_binding = [reward_payout_bump];
+        let reward_payout_seeds = &[
+            Seed::from(b"reward_payout"),
+            Seed::from(&reward_payout_bump_binding),
+        ];
+        Transfer {
+            from: self.accounts.payout_vault,
+            to: self.accounts.owner_ata,
+            authority: self.accounts.reward_payout_pda,
+            amount: lst_amount,
+        }
+        .invoke_signed(&[Signer::from(reward_payout_seeds)])
+    }
+}
@@ -0,0 +1,312 @@
+use pinocchio::{
+    account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+    pubkey::find_program_address,
+};
+use pinocchio_token::{
+    instructions::Burn,
+    state::{Mint, TokenAccount},
+};
+
+use crate::{
+    errors::PinocchioError,
+    events::{emit, EVENT_SPLIT},
+    ids::{
+        assert_clock_sysvar, assert_stake_program, assert_system_program, assert_token_program,
+        ASSOCIATED_TOKEN_PROGRAM_ID, SYSTEM_PROGRAM_ID,
+    },
+    instructions::{
+        crank_split::{main_minimum, split_minimum},
+        helpers::{
+            accrue_rewards, check_config_pda, epoch_rate, expect_lst_supply_change,
+            record_managed_change, record_pool_stake, stake_account_space, AccountCheck,
+            ProgramAccount, SignerAccount, StakeAccountAuthorize, StakeAccountCreate,
+            StakeAccountSplit, SupplyChange, STAKE_AUTHORIZE_STAKER, STAKE_AUTHORIZE_WITHDRAWER,
+        },
+    },
+    math::{lamports_to_lst, lst_to_burn_with_fee},
+    state::{Config, ManagedChange},
+};
+
+pub struct WithdrawStakeAccounts<'a> {
+    pub withdrawer: &'a AccountInfo,
+    pub withdrawer_ata: &'a AccountInfo,
+    pub config_pda: &'a AccountInfo,
+    pub new_stake_account: &'a AccountInfo,
+    pub stake_account_main: &'a AccountInfo,
+    pub stake_account_reserve: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub pool_state_pda: &'a AccountInfo,
+    pub clock_sysvar: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub stake_program: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawStakeAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [withdrawer, withdrawer_ata, config_pda, new_stake_account, stake_account_main, stake_account_reserve, lst_mint, pool_state_pda, clock_sysvar, token_program, stake_program, system_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(withdrawer)?;
+
+        assert_clock_sysvar(clock_sysvar)?;
+        assert_token_program(token_program)?;
+        assert_stake_program(stake_program)?;
+        assert_system_program(system_program)?;
+
+        Ok(Self {
+            withdrawer,
+            withdrawer_ata,
+            config_pda,
+            new_stake_account,
+            stake_account_main,
+            stake_account_reserve,
+            lst_mint,
+            pool_state_pda,
+            clock_sysvar,
+            token_program,
+            stake_program,
+            system_program,
+        })
+    }
+}
+
+pub struct WithdrawStakeInstructionData {
+    pub lamports: u64,
+    /// Distinguishes the withdrawer's split accounts, shared with `CrankSplit`.
+    pub nonce: u64,
+}
+
+impl TryFrom<&[u8]> for WithdrawStakeInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() != 16 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let lamports = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let nonce = u64::from_le_bytes(data[8..16].try_into().unwrap());
+
+        Ok(Self { lamports, nonce })
+    }
+}
+
+/// Exits the pool as a stake account instead of SOL.
+///
+/// Splits `lamports` from main into the withdrawer's split PDA, burns their
+/// pro-rata LST plus the admin's `withdraw_fee_bps` of it, and hands the
+/// split's staker and withdrawer authorities to the withdrawer. The stake
+/// stays active on the pool's validator, so the holder misses no epoch of
+/// rewards and no cooldown is scheduled. The split and main keep the same
+/// minimums as in `CrankSplit`, failing with `SplitBelowMinimum` and
+/// `MainBelowMinimum`, but the split is never served in tranches and no
+/// split record is created. The withdrawer funds the split account with its
+/// rent and 1 SOL, which stay in it as undelegated lamports they control.
+/// Logs an `EVENT_SPLIT` event.
+///
+/// Accounts expected:
+///
+/// 0. `[WRITE, SIGNER]` Withdrawer
+/// 1. `[WRITE]` Withdrawer ATA
+/// 2. `[]` Config PDA
+/// 3. `[WRITE]` New stake account (split PDA)
+/// 4. `[WRITE]` Stake account main
+/// 5. `[]` Stake account reserve
+/// 6. `[WRITE]` LST mint
+/// 7. `[WRITE]` Pool state PDA
+/// 8. `[]` Clock sysvar
+/// 9. `[]` Token program
+/// 10. `[]` Stake program
+/// 11. `[]` System program
+pub struct WithdrawStake<'a> {
+    pub accounts: WithdrawStakeAccounts<'a>,
+    pub data: WithdrawStakeInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for WithdrawStake<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accounts: WithdrawStakeAccounts::try_from(accounts)?,
+            data: WithdrawStakeInstructionData::try_from(data)?,
+        })
+    }
+}
+
+impl<'a> WithdrawStake<'a> {
+    pub const DISCRIMINATOR: &'static u8 = &91;
+
+    pub fn process(&self) -> Result<(), ProgramError> {
+        let bump = check_config_pda(self.accounts.config_pda)?;
+
+        let config_data = self.accounts.config_pda.try_borrow_data()?;
+        let config = Config::load(&config_data)?;
+
+        if config.stake_account_main != *self.accounts.stake_account_main.key() {
+            return Err(PinocchioError::InvalidStakeAccountMain.into());
+        }
+
+        if config.stake_account_reserve != *self.accounts.stake_account_reserve.key() {
+            return Err(PinocchioError::InvalidStakeAccountReserve.into());
+        }
+
+        config.status()?.check_unstakes()?;
+
+        if config.lst_mint != *self.accounts.lst_mint.key() {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        let min_split_stake_lamports = config.min_split_stake_lamports;
+        let main_headroom_lamports = config.main_headroom_lamports;
+        let withdraw_fee_bps = config.withdraw_fee_bps;
+        let validator_stake_lamports = config.validator_stake_lamports;
+        let cached_rate = epoch_rate(config)?;
+        drop(config_data);
+
+        let expected_ata = find_program_address(
+            &[
+                self.accounts.withdrawer.key(),
+                self.accounts.token_program.key(),
+                self.accounts.lst_mint.key(),
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .0;
+        if expected_ata != *self.accounts.withdrawer_ata.key() {
+            return Err(PinocchioError::InvalidWithdrawerAta.into());
+        }
+
+        let nonce_bytes = self.data.nonce.to_le_bytes();
+        let (expected_new_stake_account, new_stake_account_bump) = find_program_address(
+            &[
+                b"split_account",
+                self.accounts.withdrawer.key(),
+                &nonce_bytes,
+            ],
+            &crate::ID,
+        );
+        if expected_new_stake_account != *self.accounts.new_stake_account.key() {
+            return Err(PinocchioError::InvalidSplitAccountPda.into());
+        }
+        if !self
+            .accounts
+            .new_stake_account
+            .is_owned_by(&SYSTEM_PROGRAM_ID)
+        {
+            return Err(PinocchioError::SplitAlreadyPending.into());
+        }
+
+        let space = stake_account_space(self.accounts.stake_account_main);
+        let lamports = self.data.lamports;
+        if lamports < split_minimum(space, min_split_stake_lamports)? {
+            return Err(PinocchioError::SplitBelowMinimum.into());
+        }
+        let main_available =
+            self.accounts
+                .stake_account_main
+                .lamports()
+                .saturating_sub(main_minimum(
+                    self.accounts.stake_account_main,
+                    main_headroom_lamports,
+                )?);
+        if lamports > main_available {
+            return Err(PinocchioError::MainBelowMinimum.into());
+        }
+
+        let managed = accrue_rewards(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+        let (total_lamports_managed, lst_supply) = cached_rate.unwrap_or((
+            managed,
+            Mint::from_account_info(self.accounts.lst_mint)?.supply(),
+        ));
+        let lst_value = lamports_to_lst(lamports, lst_supply, total_lamports_managed)?;
+        let lst_to_burn = lst_to_burn_with_fee(lst_value, withdraw_fee_bps)?;
+
+        if TokenAccount::from_account_info(self.accounts.withdrawer_ata)?.amount() < lst_to_burn {
+            return Err(PinocchioError::InsufficientLstBalance.into());
+        }
+
+        let bump_binding = [bump];
+        let config_seeds = &[Seed::from(b"config"), Seed::from(&bump_binding)];
+        let new_stake_account_bump_binding = [new_stake_account_bump];
+        let new_stake_seeds = &[
+            Seed::from(b"split_account"),
+            Seed::from(self.accounts.withdrawer.key()),
+            Seed::from(&nonce_bytes),
+            Seed::from(&new_stake_account_bump_binding),
+        ];
+
+        ProgramAccount::stake_account_create(
+            self.accounts.withdrawer,
+            self.accounts.new_stake_account,
+            new_stake_seeds,
+            space,
+        )?;
+
+        ProgramAccount::split_stake_account(
+            self.accounts.stake_account_main,
+            self.accounts.new_stake_account,
+            &lamports,
+            self.accounts.config_pda,
+            config_seeds,
+        )?;
+        record_pool_stake(
+            self.accounts.pool_state_pda,
+            self.accounts.stake_account_main,
+            self.accounts.stake_account_reserve,
+            validator_stake_lamports,
+        )?;
+
+        for stake_authorize in [STAKE_AUTHORIZE_STAKER, STAKE_AUTHORIZE_WITHDRAWER] {
+            ProgramAccount::authorize_stake_account(
+                self.accounts.new_stake_account,
+                self.accounts.clock_sysvar,
+                self.accounts.config_pda,
+                self.accounts.withdrawer.key(),
+                stake_authorize,
+                config_seeds,
+            )?;
+        }
+
+        expect_lst_supply_change(
+            self.accounts.pool_state_pda,
+            self.accounts.lst_mint,
+            SupplyChange::Burned(lst_to_burn),
+            || {
+                Burn {
+                    account: self.accounts.withdrawer_ata,
+                    mint: self.accounts.lst_mint,
+                    authority: self.accounts.withdrawer,
+                    amount: lst_to_burn,
+                }
+                .invoke()
+            },
+        )?;
+        record_managed_change(
+            self.accounts.pool_state_pda,
+            ManagedChange::Withdrawn(lamports),
+        )?;
+
+        emit(
+            self.accounts.pool_state_pda,
+            EVENT_SPLIT,
+            &[
+                self.accounts.withdrawer.key(),
+                self.accounts.new_stake_account.key(),
+                &lamports.to_le_bytes(),
+                &lst_to_burn.to_le_bytes(),
+            ],
+        )
+    }
+}
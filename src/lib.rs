@@ -1,43 +1,123 @@
 use pinocchio::{
-    account_info::AccountInfo, entrypoint, msg, program_error::ProgramError, pubkey::Pubkey,
-    ProgramResult,
+    account_info::AccountInfo, msg, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
 };
 
+use crate::instructions::helpers::{check_instruction_enabled, EmptyInstructionData};
 use crate::instructions::{
-    crank_initialize_reserve::CrankInitializeReserve, crank_merge_reserve::CrankMergeReserve,
-    crank_split::CrankSplit, deposit::Deposit, initialize::Initialize, withdraw::Withdraw,
+    accept_admin::AcceptAdmin, add_liquidity::AddLiquidity, add_validator::AddValidator,
+    adopt_reserve::AdoptReserve, burn_and_donate::BurnAndDonate,
+    burn_from_wrapper::BurnFromWrapper, buyback::Buyback, cancel_split_handoff::CancelSplitHandoff,
+    cancel_unstake_order::CancelUnstakeOrder, claim_payout::ClaimPayout,
+    claim_queued_deposit::ClaimQueuedDeposit, claim_referral_fees::ClaimReferralFees,
+    claim_vested::ClaimVested, close_dca_schedule::CloseDcaSchedule,
+    close_deposit_key::CloseDepositKey, close_deposit_session::CloseDepositSession,
+    close_used_quote::CloseUsedQuote, crank_dca::CrankDca,
+    crank_initialize_reserve::CrankInitializeReserve, crank_management_fee::CrankManagementFee,
+    crank_merge_reserve::CrankMergeReserve, crank_rebalance::CrankRebalance,
+    crank_record_rate::CrankRecordRate, crank_redelegate_main::CrankRedelegateMain,
+    crank_refill_buffer::CrankRefillBuffer, crank_reward_payout::CrankRewardPayout,
+    crank_split::CrankSplit, crank_split_next::CrankSplitNext,
+    crank_split_single::CrankSplitSingle, crank_split_tranche::CrankSplitTranche,
+    crank_update_rate::CrankUpdateRate, crank_validator_health::CrankValidatorHealth,
+    crank_validator_stake::CrankValidatorStake, create_dca_schedule::CreateDcaSchedule,
+    create_deposit_session::CreateDepositSession, create_unstake_order::CreateUnstakeOrder,
+    create_vesting::CreateVesting, deposit::Deposit, deposit_stake_account::DepositStakeAccount,
+    deposit_with_session::DepositWithSession, distribute_treasury::DistributeTreasury,
+    enroll_payout::EnrollPayout, execute_split_handoff::ExecuteSplitHandoff, exit_pool::ExitPool,
+    fill_unstake_order::FillUnstakeOrder, get_apy::GetApy, get_balance_sheet::GetBalanceSheet,
+    get_build_info::GetBuildInfo, get_pending_cranks::GetPendingCranks,
+    get_stake_distribution::GetStakeDistribution, get_yield_report::GetYieldReport,
+    import_balance::ImportBalance, import_stake_account::ImportStakeAccount,
+    initialize::Initialize, initialize_liquidity_pool::InitializeLiquidityPool,
+    instant_withdraw::InstantWithdraw, join_validator_set::JoinValidatorSet,
+    leave_validator_set::LeaveValidatorSet, liquid_unstake::LiquidUnstake, lock_lst::LockLst,
+    mint_to_wrapper::MintToWrapper, open_migration::OpenMigration, propose_admin::ProposeAdmin,
+    propose_split_handoff::ProposeSplitHandoff, register_referrer::RegisterReferrer,
+    remove_liquidity::RemoveLiquidity, remove_validator::RemoveValidator,
+    set_bonus_schedule::SetBonusSchedule, set_buyback_policy::SetBuybackPolicy,
+    set_crank_tip::SetCrankTip, set_delegation_strategy::SetDelegationStrategy,
+    set_deposit_authority::SetDepositAuthority, set_deposit_cap::SetDepositCap,
+    set_deposit_fee::SetDepositFee, set_holding_rebate::SetHoldingRebate,
+    set_instruction_enabled::SetInstructionEnabled, set_main_headroom::SetMainHeadroom,
+    set_management_fee::SetManagementFee, set_pool_cap::SetPoolCap, set_pool_status::SetPoolStatus,
+    set_quote_signer::SetQuoteSigner, set_referral_fee::SetReferralFee,
+    set_reward_payout::SetRewardPayout, set_split_minimum::SetSplitMinimum,
+    set_trusted_caller::SetTrustedCaller, set_unstake_fee_params::SetUnstakeFeeParams,
+    set_validator_metrics::SetValidatorMetrics, set_validator_stake_cap::SetValidatorStakeCap,
+    set_validator_status::SetValidatorStatus, set_withdraw_fee::SetWithdrawFee,
+    set_wrapper_program::SetWrapperProgram, simulate_params::SimulateParams,
+    slash_validator_bond::SlashValidatorBond, swap_buffer::SwapBuffer, unlock_lst::UnlockLst,
+    update_config::UpdateConfig, verify_authorities::VerifyAuthorities, withdraw::Withdraw,
+    withdraw_payout::WithdrawPayout, withdraw_stake::WithdrawStake,
 };
 
-entrypoint!(process_instruction);
+#[cfg(not(feature = "no-entrypoint"))]
+pinocchio::entrypoint!(process_instruction);
+
+pub mod build_info;
+
+pub mod delegation_strategy;
+
+pub mod discriminators;
 
 pub mod errors;
 
+pub mod events;
+
+pub mod ids;
+
 pub mod instructions;
 
+pub mod math;
+
+pub mod stake_state;
+
 pub mod state;
 
+#[cfg(feature = "client")]
+pub mod client;
+
+#[cfg(feature = "test-hooks")]
+pub mod test_hooks;
+
 // 22222222222222222222222222222222222222222222
 pub const ID: Pubkey = [
     0x0f, 0x1e, 0x6b, 0x14, 0x21, 0xc0, 0x4a, 0x07, 0x04, 0x31, 0x26, 0x5c, 0x19, 0xc5, 0xbb, 0xee,
     0x19, 0x92, 0xba, 0xe8, 0xaf, 0xd1, 0xcd, 0x07, 0x8e, 0xf8, 0xaf, 0x70, 0x47, 0xdc, 0x11, 0xf7,
 ];
 
-fn process_instruction(
+pub fn process_instruction(
     _program_id: &Pubkey,
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
-    match instruction_data.split_first() {
-        Some((Initialize::DISCRIMINATOR, _data)) => {
+    let split = instruction_data.split_first();
+    #[cfg(feature = "hashed-discriminators")]
+    let split = discriminators::split_hashed(instruction_data).or(split);
+
+    if let Some((&discriminator, _)) = split {
+        if let Some(index) = config_index(discriminator) {
+            let config_pda = accounts
+                .get(index)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            check_instruction_enabled(discriminator, config_pda)?;
+        }
+    }
+
+    match split {
+        Some((Initialize::DISCRIMINATOR, data)) => {
             msg!("Initialize instruction called");
+            EmptyInstructionData::try_from(data)?;
             Initialize::try_from(accounts)?.process()
         }
-        Some((CrankInitializeReserve::DISCRIMINATOR, _data)) => {
+        Some((CrankInitializeReserve::DISCRIMINATOR, data)) => {
             msg!("CrankInitializeReserve instruction called");
+            EmptyInstructionData::try_from(data)?;
             CrankInitializeReserve::try_from(accounts)?.process()
         }
-        Some((CrankMergeReserve::DISCRIMINATOR, _data)) => {
+        Some((CrankMergeReserve::DISCRIMINATOR, data)) => {
             msg!("CrankMergeReserve instruction called");
+            EmptyInstructionData::try_from(data)?;
             CrankMergeReserve::try_from(accounts)?.process()
         }
         Some((Deposit::DISCRIMINATOR, data)) => {
@@ -52,6 +132,505 @@ fn process_instruction(
             msg!("Withdraw instruction called");
             Withdraw::try_from((data, accounts))?.process()
         }
+        Some((CrankRecordRate::DISCRIMINATOR, data)) => {
+            msg!("CrankRecordRate instruction called");
+            EmptyInstructionData::try_from(data)?;
+            CrankRecordRate::try_from(accounts)?.process()
+        }
+        Some((GetApy::DISCRIMINATOR, data)) => {
+            msg!("GetApy instruction called");
+            EmptyInstructionData::try_from(data)?;
+            GetApy::try_from(accounts)?.process()
+        }
+        Some((ExitPool::DISCRIMINATOR, data)) => {
+            msg!("ExitPool instruction called");
+            ExitPool::try_from((data, accounts))?.process()
+        }
+        Some((CrankSplitTranche::DISCRIMINATOR, data)) => {
+            msg!("CrankSplitTranche instruction called");
+            CrankSplitTranche::try_from((data, accounts))?.process()
+        }
+        Some((SetTrustedCaller::DISCRIMINATOR, data)) => {
+            msg!("SetTrustedCaller instruction called");
+            SetTrustedCaller::try_from((data, accounts))?.process()
+        }
+        Some((InitializeLiquidityPool::DISCRIMINATOR, data)) => {
+            msg!("InitializeLiquidityPool instruction called");
+            EmptyInstructionData::try_from(data)?;
+            InitializeLiquidityPool::try_from(accounts)?.process()
+        }
+        Some((AddLiquidity::DISCRIMINATOR, data)) => {
+            msg!("AddLiquidity instruction called");
+            AddLiquidity::try_from((data, accounts))?.process()
+        }
+        Some((RemoveLiquidity::DISCRIMINATOR, data)) => {
+            msg!("RemoveLiquidity instruction called");
+            RemoveLiquidity::try_from((data, accounts))?.process()
+        }
+        Some((LiquidUnstake::DISCRIMINATOR, data)) => {
+            msg!("LiquidUnstake instruction called");
+            LiquidUnstake::try_from((data, accounts))?.process()
+        }
+        Some((SetUnstakeFeeParams::DISCRIMINATOR, data)) => {
+            msg!("SetUnstakeFeeParams instruction called");
+            SetUnstakeFeeParams::try_from((data, accounts))?.process()
+        }
+        Some((CrankRefillBuffer::DISCRIMINATOR, data)) => {
+            msg!("CrankRefillBuffer instruction called");
+            CrankRefillBuffer::try_from((data, accounts))?.process()
+        }
+        Some((SwapBuffer::DISCRIMINATOR, data)) => {
+            msg!("SwapBuffer instruction called");
+            SwapBuffer::try_from((data, accounts))?.process()
+        }
+        Some((JoinValidatorSet::DISCRIMINATOR, data)) => {
+            msg!("JoinValidatorSet instruction called");
+            JoinValidatorSet::try_from((data, accounts))?.process()
+        }
+        Some((SetValidatorStatus::DISCRIMINATOR, data)) => {
+            msg!("SetValidatorStatus instruction called");
+            SetValidatorStatus::try_from((data, accounts))?.process()
+        }
+        Some((SlashValidatorBond::DISCRIMINATOR, data)) => {
+            msg!("SlashValidatorBond instruction called");
+            SlashValidatorBond::try_from((data, accounts))?.process()
+        }
+        Some((LeaveValidatorSet::DISCRIMINATOR, data)) => {
+            msg!("LeaveValidatorSet instruction called");
+            EmptyInstructionData::try_from(data)?;
+            LeaveValidatorSet::try_from(accounts)?.process()
+        }
+        Some((SetValidatorStakeCap::DISCRIMINATOR, data)) => {
+            msg!("SetValidatorStakeCap instruction called");
+            SetValidatorStakeCap::try_from((data, accounts))?.process()
+        }
+        Some((SetDelegationStrategy::DISCRIMINATOR, data)) => {
+            msg!("SetDelegationStrategy instruction called");
+            SetDelegationStrategy::try_from((data, accounts))?.process()
+        }
+        Some((SetValidatorMetrics::DISCRIMINATOR, data)) => {
+            msg!("SetValidatorMetrics instruction called");
+            SetValidatorMetrics::try_from((data, accounts))?.process()
+        }
+        Some((GetStakeDistribution::DISCRIMINATOR, data)) => {
+            msg!("GetStakeDistribution instruction called");
+            GetStakeDistribution::try_from((data, accounts))?.process()
+        }
+        Some((SetSplitMinimum::DISCRIMINATOR, data)) => {
+            msg!("SetSplitMinimum instruction called");
+            SetSplitMinimum::try_from((data, accounts))?.process()
+        }
+        Some((CloseDepositKey::DISCRIMINATOR, data)) => {
+            msg!("CloseDepositKey instruction called");
+            CloseDepositKey::try_from((data, accounts))?.process()
+        }
+        Some((BurnAndDonate::DISCRIMINATOR, data)) => {
+            msg!("BurnAndDonate instruction called");
+            BurnAndDonate::try_from((data, accounts))?.process()
+        }
+        Some((DistributeTreasury::DISCRIMINATOR, data)) => {
+            msg!("DistributeTreasury instruction called");
+            DistributeTreasury::try_from((data, accounts))?.process()
+        }
+        Some((CreateVesting::DISCRIMINATOR, data)) => {
+            msg!("CreateVesting instruction called");
+            CreateVesting::try_from((data, accounts))?.process()
+        }
+        Some((ClaimVested::DISCRIMINATOR, data)) => {
+            msg!("ClaimVested instruction called");
+            ClaimVested::try_from((data, accounts))?.process()
+        }
+        Some((CreateDepositSession::DISCRIMINATOR, data)) => {
+            msg!("CreateDepositSession instruction called");
+            CreateDepositSession::try_from((data, accounts))?.process()
+        }
+        Some((DepositWithSession::DISCRIMINATOR, data)) => {
+            msg!("DepositWithSession instruction called");
+            DepositWithSession::try_from((data, accounts))?.process()
+        }
+        Some((CloseDepositSession::DISCRIMINATOR, data)) => {
+            msg!("CloseDepositSession instruction called");
+            EmptyInstructionData::try_from(data)?;
+            CloseDepositSession::try_from(accounts)?.process()
+        }
+        Some((CreateDcaSchedule::DISCRIMINATOR, data)) => {
+            msg!("CreateDcaSchedule instruction called");
+            CreateDcaSchedule::try_from((data, accounts))?.process()
+        }
+        Some((CrankDca::DISCRIMINATOR, data)) => {
+            msg!("CrankDca instruction called");
+            CrankDca::try_from((data, accounts))?.process()
+        }
+        Some((CloseDcaSchedule::DISCRIMINATOR, data)) => {
+            msg!("CloseDcaSchedule instruction called");
+            EmptyInstructionData::try_from(data)?;
+            CloseDcaSchedule::try_from(accounts)?.process()
+        }
+        Some((SetPoolStatus::DISCRIMINATOR, data)) => {
+            msg!("SetPoolStatus instruction called");
+            SetPoolStatus::try_from((data, accounts))?.process()
+        }
+        Some((VerifyAuthorities::DISCRIMINATOR, data)) => {
+            msg!("VerifyAuthorities instruction called");
+            EmptyInstructionData::try_from(data)?;
+            VerifyAuthorities::try_from(accounts)?.process()
+        }
+        Some((SetBuybackPolicy::DISCRIMINATOR, data)) => {
+            msg!("SetBuybackPolicy instruction called");
+            SetBuybackPolicy::try_from((data, accounts))?.process()
+        }
+        Some((Buyback::DISCRIMINATOR, data)) => {
+            msg!("Buyback instruction called");
+            EmptyInstructionData::try_from(data)?;
+            Buyback::try_from(accounts)?.process()
+        }
+        Some((GetBalanceSheet::DISCRIMINATOR, data)) => {
+            msg!("GetBalanceSheet instruction called");
+            EmptyInstructionData::try_from(data)?;
+            GetBalanceSheet::try_from(accounts)?.process()
+        }
+        Some((SetDepositCap::DISCRIMINATOR, data)) => {
+            msg!("SetDepositCap instruction called");
+            SetDepositCap::try_from((data, accounts))?.process()
+        }
+        Some((ClaimQueuedDeposit::DISCRIMINATOR, data)) => {
+            msg!("ClaimQueuedDeposit instruction called");
+            EmptyInstructionData::try_from(data)?;
+            ClaimQueuedDeposit::try_from(accounts)?.process()
+        }
+        Some((SetBonusSchedule::DISCRIMINATOR, data)) => {
+            msg!("SetBonusSchedule instruction called");
+            SetBonusSchedule::try_from((data, accounts))?.process()
+        }
+        Some((SimulateParams::DISCRIMINATOR, data)) => {
+            msg!("SimulateParams instruction called");
+            SimulateParams::try_from((data, accounts))?.process()
+        }
+        Some((SetWrapperProgram::DISCRIMINATOR, data)) => {
+            msg!("SetWrapperProgram instruction called");
+            SetWrapperProgram::try_from((data, accounts))?.process()
+        }
+        Some((MintToWrapper::DISCRIMINATOR, data)) => {
+            msg!("MintToWrapper instruction called");
+            MintToWrapper::try_from((data, accounts))?.process()
+        }
+        Some((BurnFromWrapper::DISCRIMINATOR, data)) => {
+            msg!("BurnFromWrapper instruction called");
+            BurnFromWrapper::try_from((data, accounts))?.process()
+        }
+        Some((GetPendingCranks::DISCRIMINATOR, data)) => {
+            msg!("GetPendingCranks instruction called");
+            EmptyInstructionData::try_from(data)?;
+            GetPendingCranks::try_from(accounts)?.process()
+        }
+        Some((AdoptReserve::DISCRIMINATOR, data)) => {
+            msg!("AdoptReserve instruction called");
+            EmptyInstructionData::try_from(data)?;
+            AdoptReserve::try_from(accounts)?.process()
+        }
+        Some((SetInstructionEnabled::DISCRIMINATOR, data)) => {
+            msg!("SetInstructionEnabled instruction called");
+            SetInstructionEnabled::try_from((data, accounts))?.process()
+        }
+        Some((RegisterReferrer::DISCRIMINATOR, data)) => {
+            msg!("RegisterReferrer instruction called");
+            EmptyInstructionData::try_from(data)?;
+            RegisterReferrer::try_from(accounts)?.process()
+        }
+        Some((ClaimReferralFees::DISCRIMINATOR, data)) => {
+            msg!("ClaimReferralFees instruction called");
+            EmptyInstructionData::try_from(data)?;
+            ClaimReferralFees::try_from(accounts)?.process()
+        }
+        Some((SetReferralFee::DISCRIMINATOR, data)) => {
+            msg!("SetReferralFee instruction called");
+            SetReferralFee::try_from((data, accounts))?.process()
+        }
+        Some((SetQuoteSigner::DISCRIMINATOR, data)) => {
+            msg!("SetQuoteSigner instruction called");
+            SetQuoteSigner::try_from((data, accounts))?.process()
+        }
+        Some((CreateUnstakeOrder::DISCRIMINATOR, data)) => {
+            msg!("CreateUnstakeOrder instruction called");
+            CreateUnstakeOrder::try_from((data, accounts))?.process()
+        }
+        Some((FillUnstakeOrder::DISCRIMINATOR, data)) => {
+            msg!("FillUnstakeOrder instruction called");
+            FillUnstakeOrder::try_from((data, accounts))?.process()
+        }
+        Some((CancelUnstakeOrder::DISCRIMINATOR, data)) => {
+            msg!("CancelUnstakeOrder instruction called");
+            CancelUnstakeOrder::try_from((data, accounts))?.process()
+        }
+        Some((CrankValidatorHealth::DISCRIMINATOR, data)) => {
+            msg!("CrankValidatorHealth instruction called");
+            EmptyInstructionData::try_from(data)?;
+            CrankValidatorHealth::try_from(accounts)?.process()
+        }
+        Some((LockLst::DISCRIMINATOR, data)) => {
+            msg!("LockLst instruction called");
+            LockLst::try_from((data, accounts))?.process()
+        }
+        Some((UnlockLst::DISCRIMINATOR, data)) => {
+            msg!("UnlockLst instruction called");
+            EmptyInstructionData::try_from(data)?;
+            UnlockLst::try_from(accounts)?.process()
+        }
+        Some((GetBuildInfo::DISCRIMINATOR, data)) => {
+            msg!("GetBuildInfo instruction called");
+            EmptyInstructionData::try_from(data)?;
+            GetBuildInfo.process()
+        }
+        Some((ProposeSplitHandoff::DISCRIMINATOR, data)) => {
+            msg!("ProposeSplitHandoff instruction called");
+            EmptyInstructionData::try_from(data)?;
+            ProposeSplitHandoff::try_from(accounts)?.process()
+        }
+        Some((CancelSplitHandoff::DISCRIMINATOR, data)) => {
+            msg!("CancelSplitHandoff instruction called");
+            EmptyInstructionData::try_from(data)?;
+            CancelSplitHandoff::try_from(accounts)?.process()
+        }
+        Some((ExecuteSplitHandoff::DISCRIMINATOR, data)) => {
+            msg!("ExecuteSplitHandoff instruction called");
+            EmptyInstructionData::try_from(data)?;
+            ExecuteSplitHandoff::try_from(accounts)?.process()
+        }
+        Some((SetMainHeadroom::DISCRIMINATOR, data)) => {
+            msg!("SetMainHeadroom instruction called");
+            SetMainHeadroom::try_from((data, accounts))?.process()
+        }
+        Some((CrankSplitSingle::DISCRIMINATOR, data)) => {
+            msg!("CrankSplitSingle instruction called");
+            CrankSplitSingle::try_from((data, accounts))?.process()
+        }
+        Some((GetYieldReport::DISCRIMINATOR, data)) => {
+            msg!("GetYieldReport instruction called");
+            EmptyInstructionData::try_from(data)?;
+            GetYieldReport::try_from(accounts)?.process()
+        }
+        Some((OpenMigration::DISCRIMINATOR, data)) => {
+            msg!("OpenMigration instruction called");
+            OpenMigration::try_from((data, accounts))?.process()
+        }
+        Some((ImportStakeAccount::DISCRIMINATOR, data)) => {
+            msg!("ImportStakeAccount instruction called");
+            EmptyInstructionData::try_from(data)?;
+            ImportStakeAccount::try_from(accounts)?.process()
+        }
+        Some((ImportBalance::DISCRIMINATOR, data)) => {
+            msg!("ImportBalance instruction called");
+            ImportBalance::try_from((data, accounts))?.process()
+        }
+        Some((SetRewardPayout::DISCRIMINATOR, data)) => {
+            msg!("SetRewardPayout instruction called");
+            SetRewardPayout::try_from((data, accounts))?.process()
+        }
+        Some((EnrollPayout::DISCRIMINATOR, data)) => {
+            msg!("EnrollPayout instruction called");
+            EnrollPayout::try_from((data, accounts))?.process()
+        }
+        Some((WithdrawPayout::DISCRIMINATOR, data)) => {
+            msg!("WithdrawPayout instruction called");
+            WithdrawPayout::try_from((data, accounts))?.process()
+        }
+        Some((ClaimPayout::DISCRIMINATOR, data)) => {
+            msg!("ClaimPayout instruction called");
+            EmptyInstructionData::try_from(data)?;
+            ClaimPayout::try_from(accounts)?.process()
+        }
+        Some((CrankRewardPayout::DISCRIMINATOR, data)) => {
+            msg!("CrankRewardPayout instruction called");
+            EmptyInstructionData::try_from(data)?;
+            CrankRewardPayout::try_from(accounts)?.process()
+        }
+        Some((CrankValidatorStake::DISCRIMINATOR, data)) => {
+            msg!("CrankValidatorStake instruction called");
+            EmptyInstructionData::try_from(data)?;
+            CrankValidatorStake::try_from(accounts)?.process()
+        }
+        Some((AddValidator::DISCRIMINATOR, data)) => {
+            msg!("AddValidator instruction called");
+            EmptyInstructionData::try_from(data)?;
+            AddValidator::try_from(accounts)?.process()
+        }
+        Some((RemoveValidator::DISCRIMINATOR, data)) => {
+            msg!("RemoveValidator instruction called");
+            EmptyInstructionData::try_from(data)?;
+            RemoveValidator::try_from(accounts)?.process()
+        }
+        Some((CrankRebalance::DISCRIMINATOR, data)) => {
+            msg!("CrankRebalance instruction called");
+            EmptyInstructionData::try_from(data)?;
+            CrankRebalance::try_from(accounts)?.process()
+        }
+        Some((UpdateConfig::DISCRIMINATOR, data)) => {
+            msg!("UpdateConfig instruction called");
+            EmptyInstructionData::try_from(data)?;
+            UpdateConfig::try_from(accounts)?.process()
+        }
+        Some((CrankRedelegateMain::DISCRIMINATOR, data)) => {
+            msg!("CrankRedelegateMain instruction called");
+            EmptyInstructionData::try_from(data)?;
+            CrankRedelegateMain::try_from(accounts)?.process()
+        }
+        Some((ProposeAdmin::DISCRIMINATOR, data)) => {
+            msg!("ProposeAdmin instruction called");
+            ProposeAdmin::try_from((data, accounts))?.process()
+        }
+        Some((AcceptAdmin::DISCRIMINATOR, data)) => {
+            msg!("AcceptAdmin instruction called");
+            EmptyInstructionData::try_from(data)?;
+            AcceptAdmin::try_from(accounts)?.process()
+        }
+        Some((SetDepositFee::DISCRIMINATOR, data)) => {
+            msg!("SetDepositFee instruction called");
+            SetDepositFee::try_from((data, accounts))?.process()
+        }
+        Some((SetWithdrawFee::DISCRIMINATOR, data)) => {
+            msg!("SetWithdrawFee instruction called");
+            SetWithdrawFee::try_from((data, accounts))?.process()
+        }
+        Some((SetManagementFee::DISCRIMINATOR, data)) => {
+            msg!("SetManagementFee instruction called");
+            SetManagementFee::try_from((data, accounts))?.process()
+        }
+        Some((CrankManagementFee::DISCRIMINATOR, data)) => {
+            msg!("CrankManagementFee instruction called");
+            EmptyInstructionData::try_from(data)?;
+            CrankManagementFee::try_from(accounts)?.process()
+        }
+        Some((DepositStakeAccount::DISCRIMINATOR, data)) => {
+            msg!("DepositStakeAccount instruction called");
+            EmptyInstructionData::try_from(data)?;
+            DepositStakeAccount::try_from(accounts)?.process()
+        }
+        Some((WithdrawStake::DISCRIMINATOR, data)) => {
+            msg!("WithdrawStake instruction called");
+            WithdrawStake::try_from((data, accounts))?.process()
+        }
+        Some((InstantWithdraw::DISCRIMINATOR, data)) => {
+            msg!("InstantWithdraw instruction called");
+            InstantWithdraw::try_from((data, accounts))?.process()
+        }
+        Some((CrankSplitNext::DISCRIMINATOR, data)) => {
+            msg!("CrankSplitNext instruction called");
+            CrankSplitNext::try_from((data, accounts))?.process()
+        }
+        Some((SetCrankTip::DISCRIMINATOR, data)) => {
+            msg!("SetCrankTip instruction called");
+            SetCrankTip::try_from((data, accounts))?.process()
+        }
+        Some((CrankUpdateRate::DISCRIMINATOR, data)) => {
+            msg!("CrankUpdateRate instruction called");
+            EmptyInstructionData::try_from(data)?;
+            CrankUpdateRate::try_from(accounts)?.process()
+        }
+        Some((SetPoolCap::DISCRIMINATOR, data)) => {
+            msg!("SetPoolCap instruction called");
+            SetPoolCap::try_from((data, accounts))?.process()
+        }
+        Some((SetDepositAuthority::DISCRIMINATOR, data)) => {
+            msg!("SetDepositAuthority instruction called");
+            SetDepositAuthority::try_from((data, accounts))?.process()
+        }
+        Some((CloseUsedQuote::DISCRIMINATOR, data)) => {
+            msg!("CloseUsedQuote instruction called");
+            CloseUsedQuote::try_from((data, accounts))?.process()
+        }
+        Some((SetHoldingRebate::DISCRIMINATOR, data)) => {
+            msg!("SetHoldingRebate instruction called");
+            SetHoldingRebate::try_from((data, accounts))?.process()
+        }
         _ => Err(ProgramError::InvalidInstructionData),
     }
 }
+
+/// Position of the config PDA in the accounts of each instruction the admin
+/// can switch off. Instructions that do not take the config, `Initialize`
+/// (which creates it) and `SetInstructionEnabled` cannot be switched off.
+fn config_index(discriminator: u8) -> Option<usize> {
+    let index = match &discriminator {
+        Buyback::DISCRIMINATOR
+        | ClaimQueuedDeposit::DISCRIMINATOR
+        | CrankInitializeReserve::DISCRIMINATOR
+        | CrankManagementFee::DISCRIMINATOR
+        | CrankMergeReserve::DISCRIMINATOR
+        | CrankRedelegateMain::DISCRIMINATOR
+        | CrankRewardPayout::DISCRIMINATOR
+        | CrankUpdateRate::DISCRIMINATOR
+        | Deposit::DISCRIMINATOR
+        | GetBalanceSheet::DISCRIMINATOR
+        | GetPendingCranks::DISCRIMINATOR
+        | GetStakeDistribution::DISCRIMINATOR
+        | GetYieldReport::DISCRIMINATOR
+        | SimulateParams::DISCRIMINATOR
+        | VerifyAuthorities::DISCRIMINATOR => 0,
+        AcceptAdmin::DISCRIMINATOR
+        | AddValidator::DISCRIMINATOR
+        | AdoptReserve::DISCRIMINATOR
+        | CrankRebalance::DISCRIMINATOR
+        | CrankRecordRate::DISCRIMINATOR
+        | CrankRefillBuffer::DISCRIMINATOR
+        | CrankValidatorStake::DISCRIMINATOR
+        | DistributeTreasury::DISCRIMINATOR
+        | ImportBalance::DISCRIMINATOR
+        | ImportStakeAccount::DISCRIMINATOR
+        | InitializeLiquidityPool::DISCRIMINATOR
+        | OpenMigration::DISCRIMINATOR
+        | ProposeAdmin::DISCRIMINATOR
+        | RemoveValidator::DISCRIMINATOR
+        | SetBonusSchedule::DISCRIMINATOR
+        | SetBuybackPolicy::DISCRIMINATOR
+        | SetCrankTip::DISCRIMINATOR
+        | SetDelegationStrategy::DISCRIMINATOR
+        | SetDepositAuthority::DISCRIMINATOR
+        | SetDepositCap::DISCRIMINATOR
+        | SetDepositFee::DISCRIMINATOR
+        | SetHoldingRebate::DISCRIMINATOR
+        | SetMainHeadroom::DISCRIMINATOR
+        | SetManagementFee::DISCRIMINATOR
+        | SetPoolCap::DISCRIMINATOR
+        | SetPoolStatus::DISCRIMINATOR
+        | SetQuoteSigner::DISCRIMINATOR
+        | SetReferralFee::DISCRIMINATOR
+        | SetRewardPayout::DISCRIMINATOR
+        | SetSplitMinimum::DISCRIMINATOR
+        | SetTrustedCaller::DISCRIMINATOR
+        | SetUnstakeFeeParams::DISCRIMINATOR
+        | SetValidatorMetrics::DISCRIMINATOR
+        | SetValidatorStakeCap::DISCRIMINATOR
+        | SetValidatorStatus::DISCRIMINATOR
+        | SetWithdrawFee::DISCRIMINATOR
+        | SetWrapperProgram::DISCRIMINATOR
+        | SlashValidatorBond::DISCRIMINATOR
+        | UpdateConfig::DISCRIMINATOR => 1,
+        AddLiquidity::DISCRIMINATOR
+        | BurnFromWrapper::DISCRIMINATOR
+        | CancelSplitHandoff::DISCRIMINATOR
+        | CrankDca::DISCRIMINATOR
+        | DepositStakeAccount::DISCRIMINATOR
+        | DepositWithSession::DISCRIMINATOR
+        | InstantWithdraw::DISCRIMINATOR
+        | LiquidUnstake::DISCRIMINATOR
+        | MintToWrapper::DISCRIMINATOR
+        | ProposeSplitHandoff::DISCRIMINATOR
+        | SwapBuffer::DISCRIMINATOR
+        | WithdrawStake::DISCRIMINATOR => 2,
+        BurnAndDonate::DISCRIMINATOR
+        | ClaimReferralFees::DISCRIMINATOR
+        | CrankValidatorHealth::DISCRIMINATOR
+        | RemoveLiquidity::DISCRIMINATOR => 3,
+        CrankSplit::DISCRIMINATOR
+        | CrankSplitTranche::DISCRIMINATOR
+        | ExitPool::DISCRIMINATOR
+        | FillUnstakeOrder::DISCRIMINATOR
+        | Withdraw::DISCRIMINATOR => 4,
+        CreateUnstakeOrder::DISCRIMINATOR | LockLst::DISCRIMINATOR => 5,
+        CreateVesting::DISCRIMINATOR
+        | EnrollPayout::DISCRIMINATOR
+        | WithdrawPayout::DISCRIMINATOR => 6,
+        _ => return None,
+    };
+    Some(index)
+}
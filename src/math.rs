@@ -0,0 +1,239 @@
+//! Exchange rate and fee math shared by the instructions and the `client`
+//! feature.
+//!
+//! Everything here is pure integer arithmetic on plain values, with no
+//! account access or syscalls, so it builds for wasm32 and gives browser
+//! frontends the exact amounts the program computes.
+
+use pinocchio::program_error::ProgramError;
+
+use crate::{errors::PinocchioError, state::VE_MAX_LOCK_SECONDS};
+
+/// Value in lamports of `lst_amount` LST at the current exchange rate.
+pub fn lst_to_lamports(
+    lst_amount: u64,
+    lst_supply: u64,
+    pool_lamports: u64,
+) -> Result<u64, ProgramError> {
+    if lst_supply == 0 {
+        return Ok(0);
+    }
+
+    Ok((lst_amount as u128)
+        .checked_mul(pool_lamports as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        .checked_div(lst_supply as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)? as u64)
+}
+
+/// LST minted for `lamports` at the current exchange rate. An empty pool
+/// mints one LST per lamport.
+pub fn lamports_to_lst(
+    lamports: u64,
+    lst_supply: u64,
+    pool_lamports: u64,
+) -> Result<u64, ProgramError> {
+    if lst_supply == 0 || pool_lamports == 0 {
+        return Ok(lamports);
+    }
+
+    Ok((lamports as u128)
+        .checked_mul(lst_supply as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        .checked_div(pool_lamports as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)? as u64)
+}
+
+/// Fee in basis points for an unstake that leaves `remaining_lamports` in the
+/// buffer. Scales linearly from `max_bps` at an empty buffer to `min_bps` at
+/// `target_lamports` or more.
+pub fn unstake_fee_bps(
+    min_bps: u16,
+    max_bps: u16,
+    target_lamports: u64,
+    remaining_lamports: u64,
+) -> u16 {
+    if target_lamports == 0 || remaining_lamports >= target_lamports {
+        return min_bps;
+    }
+
+    let drained = (target_lamports - remaining_lamports) as u128;
+    let spread = (max_bps - min_bps) as u128;
+
+    min_bps + (spread * drained / target_lamports as u128) as u16
+}
+
+/// Lamports paid out for an unstake worth `lamports_value` from a buffer
+/// holding `sol_reserves`, after the fee.
+pub fn unstake_lamports_out(
+    lamports_value: u64,
+    sol_reserves: u64,
+    min_bps: u16,
+    max_bps: u16,
+    target_lamports: u64,
+) -> Result<u64, ProgramError> {
+    if lamports_value > sol_reserves {
+        return Err(PinocchioError::InsufficientLiquidity.into());
+    }
+
+    let fee_bps = unstake_fee_bps(
+        min_bps,
+        max_bps,
+        target_lamports,
+        sol_reserves - lamports_value,
+    );
+
+    let fee = ((lamports_value as u128) * (fee_bps as u128) / 10_000) as u64;
+    Ok(lamports_value - fee)
+}
+
+/// LST released by `now` from a grant of `total_amount` that vests linearly
+/// from `start_ts` to `end_ts`, with nothing released before `cliff_ts`.
+pub fn vested_amount(
+    total_amount: u64,
+    start_ts: i64,
+    cliff_ts: i64,
+    end_ts: i64,
+    now: i64,
+) -> u64 {
+    if now < cliff_ts {
+        return 0;
+    }
+    if now >= end_ts {
+        return total_amount;
+    }
+
+    let elapsed = (now - start_ts) as u128;
+    let duration = (end_ts - start_ts) as u128;
+
+    (total_amount as u128 * elapsed / duration) as u64
+}
+
+/// Bonus LST paid from the treasury on top of `lst_minted` for a deposit in
+/// `epoch`: `bonus_bps` of it before `cutoff_epoch`, nothing from then on,
+/// and never more than `treasury_balance`.
+pub fn deposit_bonus_lst(
+    lst_minted: u64,
+    bonus_bps: u16,
+    epoch: u64,
+    cutoff_epoch: u64,
+    treasury_balance: u64,
+) -> u64 {
+    if epoch >= cutoff_epoch {
+        return 0;
+    }
+
+    let bonus = (lst_minted as u128 * bonus_bps as u128 / 10_000) as u64;
+    bonus.min(treasury_balance)
+}
+
+/// Deposit fee on `lst_minted`: the `fee_bps` of it that a `Deposit` mints
+/// into the treasury instead of to the depositor. Rounds down.
+pub fn deposit_fee_lst(lst_minted: u64, fee_bps: u16) -> u64 {
+    (lst_minted as u128 * fee_bps as u128 / 10_000) as u64
+}
+
+/// LST a split burns for `lst_value`, the pro-rata LST of the split
+/// lamports, with the withdrawal fee of `fee_bps` on top. Rounds the fee
+/// down.
+pub fn lst_to_burn_with_fee(lst_value: u64, fee_bps: u16) -> Result<u64, ProgramError> {
+    lst_value
+        .checked_add((lst_value as u128 * fee_bps as u128 / 10_000) as u64)
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+/// Withdrawal fee in bps after the holding rebate: `rebate_bps` of
+/// `fee_bps` is waived once `held_epochs` reaches `rebate_epochs`, and a
+/// linear share of it before. Rounds the rebate down. Zero `rebate_epochs`
+/// disables the rebate.
+pub fn holding_rebate_fee_bps(
+    fee_bps: u16,
+    rebate_bps: u16,
+    rebate_epochs: u64,
+    held_epochs: u64,
+) -> u16 {
+    if rebate_epochs == 0 {
+        return fee_bps;
+    }
+
+    let rebate = fee_bps as u128 * rebate_bps as u128 * held_epochs.min(rebate_epochs) as u128
+        / (10_000 * rebate_epochs as u128);
+
+    fee_bps - rebate as u16
+}
+
+/// Withdrawal fee of `fee_bps` on `lamports` paid out instantly, which
+/// stays in the pool for the other holders. Rounds down.
+pub fn withdraw_fee_lamports(lamports: u64, fee_bps: u16) -> u64 {
+    (lamports as u128 * fee_bps as u128 / 10_000) as u64
+}
+
+/// Management fee for one epoch: `fee_bps` of `pool_lamports`, capped at the
+/// pool's `yield_lamports` so depositors' principal is never charged, and the
+/// LST that dilutes the other holders by exactly that value. Returns
+/// `(fee_lamports, fee_lst)`, zero for an empty pool.
+pub fn management_fee(
+    pool_lamports: u64,
+    lst_supply: u64,
+    yield_lamports: u64,
+    fee_bps: u16,
+) -> Result<(u64, u64), ProgramError> {
+    if lst_supply == 0 {
+        return Ok((0, 0));
+    }
+
+    let fee_lamports =
+        ((pool_lamports as u128 * fee_bps as u128 / 10_000) as u64).min(yield_lamports);
+    let fee_lst = lamports_to_lst(
+        fee_lamports,
+        lst_supply,
+        pool_lamports
+            .checked_sub(fee_lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?,
+    )?;
+
+    Ok((fee_lamports, fee_lst))
+}
+
+/// Lamports `CrankRewardPayout` pays out for `vault_lst` LST: `payout_bps`
+/// of what the LST gained in value since the rate of the checkpoint. Zero
+/// without a checkpoint or when the rate did not rise.
+pub fn reward_payout_lamports(
+    vault_lst: u64,
+    checkpoint_lamports: u64,
+    checkpoint_lst_supply: u64,
+    pool_lamports: u64,
+    lst_supply: u64,
+    payout_bps: u16,
+) -> u64 {
+    if checkpoint_lst_supply == 0 || lst_supply == 0 {
+        return 0;
+    }
+
+    let value_now = vault_lst as u128 * pool_lamports as u128 / lst_supply as u128;
+    let value_then =
+        vault_lst as u128 * checkpoint_lamports as u128 / checkpoint_lst_supply as u128;
+    (value_now.saturating_sub(value_then) * payout_bps as u128 / 10_000) as u64
+}
+
+/// Voting weight of `lst_amount` LST locked for `lock_seconds`: the full
+/// amount for a `VE_MAX_LOCK_SECONDS` lock, proportionally less for shorter
+/// ones. Rounds down.
+pub fn lst_to_ve_weight(lst_amount: u64, lock_seconds: i64) -> u64 {
+    let lock_seconds = lock_seconds.clamp(0, VE_MAX_LOCK_SECONDS) as u128;
+    (lst_amount as u128 * lock_seconds / VE_MAX_LOCK_SECONDS as u128) as u64
+}
+
+/// LST that has to be locked for `lock_seconds` to get at least
+/// `voting_weight`, or `None` for a lock without weight or an amount above
+/// `u64::MAX`. Rounds up.
+pub fn ve_weight_to_lst(voting_weight: u64, lock_seconds: i64) -> Option<u64> {
+    let lock_seconds = lock_seconds.min(VE_MAX_LOCK_SECONDS);
+    if lock_seconds <= 0 {
+        return None;
+    }
+
+    let lock_seconds = lock_seconds as u128;
+    let lst = (voting_weight as u128 * VE_MAX_LOCK_SECONDS as u128).div_ceil(lock_seconds);
+    u64::try_from(lst).ok()
+}
@@ -0,0 +1,137 @@
+//! Parsed state of stake program accounts.
+//!
+//! The program reads stake accounts it does not own, so it parses the stake
+//! program's `StakeStateV2` layout by offset instead of depending on the
+//! stake program crate. Everything here works on the raw account data, with
+//! no account access or syscalls; callers supply the lamports and, for
+//! uninitialized accounts, the rent minimum.
+
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::errors::PinocchioError;
+
+pub const STAKE_STATE_UNINITIALIZED: u32 = 0;
+pub const STAKE_STATE_INITIALIZED: u32 = 1;
+pub const STAKE_STATE_STAKE: u32 = 2;
+
+const META_RENT_EXEMPT_RESERVE_OFFSET: usize = 4;
+const META_STAKER_OFFSET: usize = 12;
+const META_WITHDRAWER_OFFSET: usize = 44;
+/// End of `Meta`, after the 48-byte lockup.
+const META_END: usize = 124;
+const DELEGATION_VOTER_OFFSET: usize = 124;
+const DELEGATION_STAKE_OFFSET: usize = 156;
+const DELEGATION_ACTIVATION_EPOCH_OFFSET: usize = 164;
+const DELEGATION_DEACTIVATION_EPOCH_OFFSET: usize = 172;
+/// End of `Stake`, after the delegation's deprecated `warmup_cooldown_rate`
+/// and `credits_observed`.
+const STAKE_END: usize = 196;
+
+/// Authorities and rent reserve of an initialized or delegated account.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct StakeMeta {
+    pub rent_exempt_reserve: u64,
+    pub staker: Pubkey,
+    pub withdrawer: Pubkey,
+}
+
+/// Delegation of a delegated account.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct StakeDelegation {
+    pub voter: Pubkey,
+    /// Delegated lamports. Epoch rewards are added to it as well as to the
+    /// account's lamports.
+    pub stake: u64,
+    pub activation_epoch: u64,
+    /// `u64::MAX` while the stake has not been deactivated.
+    pub deactivation_epoch: u64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StakeState {
+    /// No data, as left by a merge that drained the account.
+    Closed,
+    /// Any state other than initialized or delegated.
+    Uninitialized,
+    Initialized(StakeMeta),
+    Stake(StakeMeta, StakeDelegation),
+}
+
+impl StakeState {
+    /// Parses stake account data. Fails with `InvalidAccountData` when the
+    /// data is too short for the state its tag names.
+    pub fn parse(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.is_empty() {
+            return Ok(Self::Closed);
+        }
+        if data.len() < 4 {
+            return Err(PinocchioError::InvalidAccountData.into());
+        }
+
+        let read_u64 =
+            |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+        let read_pubkey =
+            |offset: usize| -> Pubkey { data[offset..offset + 32].try_into().unwrap() };
+
+        let tag = u32::from_le_bytes(data[0..4].try_into().unwrap());
+        let required_len = match tag {
+            STAKE_STATE_INITIALIZED => META_END,
+            STAKE_STATE_STAKE => STAKE_END,
+            _ => return Ok(Self::Uninitialized),
+        };
+        if data.len() < required_len {
+            return Err(PinocchioError::InvalidAccountData.into());
+        }
+
+        let meta = StakeMeta {
+            rent_exempt_reserve: read_u64(META_RENT_EXEMPT_RESERVE_OFFSET),
+            staker: read_pubkey(META_STAKER_OFFSET),
+            withdrawer: read_pubkey(META_WITHDRAWER_OFFSET),
+        };
+        if tag == STAKE_STATE_INITIALIZED {
+            return Ok(Self::Initialized(meta));
+        }
+
+        Ok(Self::Stake(
+            meta,
+            StakeDelegation {
+                voter: read_pubkey(DELEGATION_VOTER_OFFSET),
+                stake: read_u64(DELEGATION_STAKE_OFFSET),
+                activation_epoch: read_u64(DELEGATION_ACTIVATION_EPOCH_OFFSET),
+                deactivation_epoch: read_u64(DELEGATION_DEACTIVATION_EPOCH_OFFSET),
+            },
+        ))
+    }
+
+    pub fn meta(&self) -> Option<&StakeMeta> {
+        match self {
+            Self::Initialized(meta) | Self::Stake(meta, _) => Some(meta),
+            _ => None,
+        }
+    }
+
+    pub fn delegation(&self) -> Option<&StakeDelegation> {
+        match self {
+            Self::Stake(_, delegation) => Some(delegation),
+            _ => None,
+        }
+    }
+
+    /// Rent-exempt reserve from the `Meta`. An uninitialized account keeps
+    /// `rent_minimum`, the rent minimum for its data length, and a closed
+    /// one nothing.
+    pub fn rent_exempt_reserve(&self, rent_minimum: u64) -> u64 {
+        match self {
+            Self::Closed => 0,
+            Self::Uninitialized => rent_minimum,
+            Self::Initialized(meta) | Self::Stake(meta, _) => meta.rent_exempt_reserve,
+        }
+    }
+
+    /// Whether the stake was activated in `epoch` or later, i.e. is still
+    /// warming up. Accounts that are not delegated are not activating.
+    pub fn is_activating(&self, epoch: u64) -> bool {
+        self.delegation()
+            .is_some_and(|delegation| delegation.activation_epoch >= epoch)
+    }
+}
@@ -1,16 +1,250 @@
 use pinocchio::{msg, program_error::ProgramError, pubkey::Pubkey};
 
+use crate::{delegation_strategy::DelegationStrategyKind, errors::PinocchioError};
+
 #[repr(C, packed)]
 pub struct Config {
+    /// `AccountDiscriminator::Config`, so another program-owned account of
+    /// the same length is not read as the config.
+    pub discriminator: u8,
+    /// Layout version, `Config::VERSION` for accounts this program reads.
+    pub version: u8,
     pub admin: [u8; 32],
     pub lst_mint: [u8; 32],
     pub stake_account_main: [u8; 32],
     pub stake_account_reserve: [u8; 32],
     pub validator_vote_pubkey: [u8; 32],
+    pub unstake_fee_min_bps: u16,
+    pub unstake_fee_max_bps: u16,
+    pub liquidity_target_lamports: u64,
+    /// Largest share of the pool's lamports one validator may hold, in bps.
+    pub max_validator_stake_bps: u16,
+    /// Largest stake one validator may hold, in lamports. Zero for no limit.
+    pub max_validator_stake_lamports: u64,
+    /// `DelegationStrategyKind` used to compute validator stake targets.
+    pub delegation_strategy: u8,
+    /// Stake portion of the split minimum, in lamports. The cluster's minimum
+    /// delegation applies when it is higher.
+    pub min_split_stake_lamports: u64,
+    /// `PoolStatus` of the pool's lifecycle.
+    pub status: u8,
+    /// `AUTHORITY_DRIFT_*` bits set by the last `VerifyAuthorities`.
+    pub authority_drift: u8,
+    /// `BUYBACK_*` policy for LST bought back with protocol fees.
+    pub buyback_policy: u8,
+    /// Most lamports deposits may add to the reserve in one epoch. Zero for
+    /// no cap.
+    pub deposit_cap_lamports: u64,
+    /// `DEPOSIT_CAP_*` handling of `Deposit`s beyond the cap.
+    pub deposit_cap_mode: u8,
+    /// Extra LST paid from the treasury to `Deposit`s before
+    /// `bonus_cutoff_epoch`, in bps of the LST minted.
+    pub bonus_bps: u16,
+    pub bonus_cutoff_epoch: u64,
+    /// Program allowed to call `MintToWrapper` and `BurnFromWrapper` through
+    /// its `WRAPPER_AUTHORITY_SEED` PDA. Zero when no wrapper is approved.
+    pub wrapper_program: [u8; 32],
+    /// Instructions the admin has switched off, one bit per discriminator:
+    /// bit `d % 8` of byte `d / 8`.
+    pub disabled_instructions: [u8; 32],
+    /// Share of the LST minted by a referred `Deposit` that goes to the
+    /// referrer instead of the depositor, in bps.
+    pub referral_fee_bps: u16,
+    /// Key whose signed rate quotes `Deposit` honors. Zero when quotes are
+    /// off.
+    pub quote_signer: [u8; 32],
+    /// Furthest a quoted rate may be from the pool's own rate, in bps.
+    pub quote_max_deviation_bps: u16,
+    /// Lamports main must keep beyond its rent and the minimum delegation
+    /// after a split, see `main_minimum`.
+    pub main_headroom_lamports: u64,
+    /// Share of the rewards on LST enrolled in the payout vault that
+    /// `CrankRewardPayout` pays out as SOL, in bps. Zero compounds
+    /// everything into the rate.
+    pub reward_payout_bps: u16,
+    /// Sum of the validator list's `stake_lamports` and `transient_lamports`:
+    /// the pool's lamports held in validator stake and transient stake
+    /// accounts, counted by `pool_lamports`.
+    pub validator_stake_lamports: u64,
+    /// Admin proposed by `ProposeAdmin`, who becomes the admin by signing
+    /// `AcceptAdmin`. Zero when no transfer is pending.
+    pub pending_admin: [u8; 32],
+    /// Share of the LST minted by a `Deposit` that goes to the treasury ATA
+    /// instead of the depositor, in bps.
+    pub deposit_fee_bps: u16,
+    /// Withdrawal fee: extra LST `CrankSplit` and `CrankSplitTranche` burn
+    /// on top of the split's pro-rata LST, in bps of it.
+    pub withdraw_fee_bps: u16,
+    /// Share of the pool's lamports `CrankManagementFee` mints to the
+    /// treasury as LST each epoch, in bps.
+    pub management_fee_bps: u16,
+    /// Epoch `CrankManagementFee` last ran in, so each epoch is charged once.
+    pub last_fee_epoch: u64,
+    /// Canonical bumps of the config, `stake_main` and `stake_reserve` PDAs,
+    /// stored by `Initialize` so instructions need not search for them.
+    pub config_bump: u8,
+    pub stake_main_bump: u8,
+    pub stake_reserve_bump: u8,
+    /// Lamports the fee vault pays the cranker of a successful
+    /// `CrankInitializeReserve` or `CrankMergeReserve`. Zero for no tip.
+    pub crank_tip_lamports: u64,
+    /// Pool lamports and LST supply `CrankUpdateRate` snapshotted in
+    /// `rate_epoch`. Every instruction that mints or burns LST prices
+    /// against them for the rest of that epoch, see `cached_rate`.
+    pub rate_lamports: u64,
+    pub rate_lst_supply: u64,
+    pub rate_epoch: u64,
+    /// Pool lamports deposits may not push the pool past, for guarded
+    /// launches. Zero for no cap.
+    pub max_pool_lamports: u64,
+    /// Key that must co-sign every instruction minting LST for new deposits,
+    /// for permissioned pools. The zero key lets anyone deposit.
+    pub deposit_authority: [u8; 32],
+    /// Epoch in which every validator holding pool stake last had it
+    /// recorded, making `validator_stake_lamports` current, see
+    /// `validator_stake_current`.
+    pub validator_stake_epoch: u64,
+    /// Delegated stake `UpdateConfig` moved from a delegated reserve into the
+    /// outgoing reserve (`b"stake_outgoing"`) when it last changed the
+    /// validator. `CrankRedelegateMain` credits what that stake grew by as
+    /// rewards when it merges the outgoing reserve into main. Zero when no
+    /// rotation is pending.
+    pub outgoing_stake_lamports: u64,
+    /// Share of the withdrawal fee waived for a withdrawer who has held
+    /// since their first deposit for `holding_rebate_epochs`, in bps.
+    pub holding_rebate_bps: u16,
+    /// Epochs since the first deposit after which the full
+    /// `holding_rebate_bps` applies. The rebate grows linearly until then.
+    /// Zero disables the rebate.
+    pub holding_rebate_epochs: u64,
+}
+
+/// First byte of every state account the program creates. Values are never
+/// reused, so a retired account type cannot be read as a new one.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum AccountDiscriminator {
+    Config = 1,
+    SplitCounter = 2,
+    RateHistory = 3,
+    SplitRecord = 4,
+    TrustedCallers = 5,
+    LiquidityPool = 6,
+    ValidatorBond = 7,
+    ValidatorList = 8,
+    EpochTasks = 9,
+    QueuedDeposit = 10,
+    ReferrerRecord = 11,
+    DepositKey = 12,
+    UsedQuote = 13,
+    PoolState = 14,
+    VestingEscrow = 15,
+    DepositSession = 16,
+    DcaSchedule = 17,
+    ValidatorHealth = 18,
+    UnstakeOrder = 19,
+    VoteEscrow = 20,
+    SplitHandoff = 21,
+    Changelog = 22,
+    Migration = 23,
+    ImportedBalance = 24,
+    RewardPayout = 25,
+    PayoutPosition = 26,
+    UserStats = 27,
+}
+
+/// State accounts the program creates, which start with a two-byte header:
+/// the `AccountDiscriminator`, then the layout version. `ProgramAccount::init`
+/// writes it, and `load` and `load_mut` reject an account without it, so one
+/// program-owned account of the right length is not read as another.
+pub trait AccountHeader {
+    const DISCRIMINATOR: AccountDiscriminator;
+    /// Layout version written on creation and required to read the account.
+    const VERSION: u8;
+
+    #[inline(always)]
+    fn write_header(bytes: &mut [u8]) {
+        bytes[0] = Self::DISCRIMINATOR as u8;
+        bytes[1] = Self::VERSION;
+    }
+
+    #[inline(always)]
+    fn check_header(bytes: &[u8]) -> Result<(), ProgramError> {
+        if bytes.len() < 2 || bytes[0] != Self::DISCRIMINATOR as u8 || bytes[1] != Self::VERSION {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+}
+
+/// Stake accounts whose staker or withdrawer was not the config PDA when
+/// `VerifyAuthorities` last ran.
+pub const AUTHORITY_DRIFT_MAIN: u8 = 1 << 0;
+pub const AUTHORITY_DRIFT_RESERVE: u8 = 1 << 1;
+pub const AUTHORITY_DRIFT_BUFFER: u8 = 1 << 2;
+pub const AUTHORITY_DRIFT_SPLIT: u8 = 1 << 3;
+
+/// What `Buyback` does with the LST it buys with the fee vault's SOL.
+pub const BUYBACK_TO_TREASURY: u8 = 0;
+pub const BUYBACK_BURN: u8 = 1;
+
+/// What `Deposit` does with lamports beyond the per-epoch deposit cap.
+/// `DepositWithSession` and `CrankDca` always reject them.
+pub const DEPOSIT_CAP_REJECT: u8 = 0;
+pub const DEPOSIT_CAP_QUEUE: u8 = 1;
+
+/// Seed of the PDA, under the approved wrapper program, that signs for it.
+pub const WRAPPER_AUTHORITY_SEED: &[u8] = b"lst_wrapper";
+
+impl AccountHeader for Config {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::Config;
+    const VERSION: u8 = 1;
 }
 
 impl Config {
-    pub const LEN: usize = 32 + 32 + 32 + 32 + 32;
+    pub const LEN: usize = 1
+        + 1
+        + 32 * 5
+        + 2
+        + 2
+        + 8
+        + 2
+        + 8
+        + 1
+        + 8
+        + 1
+        + 1
+        + 1
+        + 8
+        + 1
+        + 2
+        + 8
+        + 32
+        + 32
+        + 2
+        + 32
+        + 2
+        + 8
+        + 2
+        + 8
+        + 32
+        + 2
+        + 2
+        + 2
+        + 8
+        + 1
+        + 1
+        + 1
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 32
+        + 8
+        + 8
+        + 2
+        + 8;
 
     #[inline(always)]
     pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
@@ -19,6 +253,7 @@ impl Config {
             msg!("Config invalid length");
             return Err(ProgramError::InvalidAccountData);
         }
+        Self::check_header(bytes)?;
         Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
     }
 
@@ -27,6 +262,7 @@ impl Config {
         if bytes.len() != Config::LEN {
             return Err(ProgramError::InvalidAccountData);
         }
+        Self::check_header(bytes)?;
         Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
     }
 
@@ -44,5 +280,2248 @@ impl Config {
         self.stake_account_main = stake_account_main;
         self.stake_account_reserve = stake_account_reserve;
         self.validator_vote_pubkey = validator_vote_pubkey;
+        self.status = PoolStatus::Bootstrapping as u8;
+        self.authority_drift = 0;
+        self.buyback_policy = BUYBACK_TO_TREASURY;
+        self.deposit_cap_lamports = 0;
+        self.deposit_cap_mode = DEPOSIT_CAP_REJECT;
+        self.bonus_bps = 0;
+        self.bonus_cutoff_epoch = 0;
+        self.wrapper_program = [0u8; 32];
+        self.disabled_instructions = [0u8; 32];
+        self.referral_fee_bps = 0;
+        self.quote_signer = [0u8; 32];
+        self.quote_max_deviation_bps = 0;
+        self.main_headroom_lamports = 0;
+        self.reward_payout_bps = 0;
+        self.validator_stake_lamports = 0;
+        self.pending_admin = [0; 32];
+        self.deposit_fee_bps = 0;
+        self.withdraw_fee_bps = 0;
+        self.management_fee_bps = 0;
+        self.last_fee_epoch = 0;
+        self.crank_tip_lamports = 0;
+        self.rate_lamports = 0;
+        self.rate_lst_supply = 0;
+        self.rate_epoch = 0;
+        self.max_pool_lamports = 0;
+        self.deposit_authority = [0u8; 32];
+        self.validator_stake_epoch = 0;
+        self.holding_rebate_bps = 0;
+        self.holding_rebate_epochs = 0;
+    }
+
+    #[inline(always)]
+    pub fn set_unstake_fee_params(
+        &mut self,
+        unstake_fee_min_bps: u16,
+        unstake_fee_max_bps: u16,
+        liquidity_target_lamports: u64,
+    ) {
+        self.unstake_fee_min_bps = unstake_fee_min_bps;
+        self.unstake_fee_max_bps = unstake_fee_max_bps;
+        self.liquidity_target_lamports = liquidity_target_lamports;
+    }
+
+    #[inline(always)]
+    pub fn set_validator_stake_cap(
+        &mut self,
+        max_validator_stake_bps: u16,
+        max_validator_stake_lamports: u64,
+    ) {
+        self.max_validator_stake_bps = max_validator_stake_bps;
+        self.max_validator_stake_lamports = max_validator_stake_lamports;
+    }
+
+    #[inline(always)]
+    pub fn set_delegation_strategy(&mut self, delegation_strategy: DelegationStrategyKind) {
+        self.delegation_strategy = delegation_strategy as u8;
+    }
+
+    #[inline(always)]
+    pub fn set_min_split_stake(&mut self, min_split_stake_lamports: u64) {
+        self.min_split_stake_lamports = min_split_stake_lamports;
+    }
+
+    #[inline(always)]
+    pub fn set_main_headroom(&mut self, main_headroom_lamports: u64) {
+        self.main_headroom_lamports = main_headroom_lamports;
+    }
+
+    #[inline(always)]
+    pub fn set_bumps(&mut self, config_bump: u8, stake_main_bump: u8, stake_reserve_bump: u8) {
+        self.config_bump = config_bump;
+        self.stake_main_bump = stake_main_bump;
+        self.stake_reserve_bump = stake_reserve_bump;
+    }
+
+    #[inline(always)]
+    pub fn set_deposit_fee(&mut self, deposit_fee_bps: u16) {
+        self.deposit_fee_bps = deposit_fee_bps;
+    }
+
+    #[inline(always)]
+    pub fn set_withdraw_fee(&mut self, withdraw_fee_bps: u16) {
+        self.withdraw_fee_bps = withdraw_fee_bps;
+    }
+
+    #[inline(always)]
+    pub fn set_holding_rebate(&mut self, holding_rebate_bps: u16, holding_rebate_epochs: u64) {
+        self.holding_rebate_bps = holding_rebate_bps;
+        self.holding_rebate_epochs = holding_rebate_epochs;
+    }
+
+    #[inline(always)]
+    pub fn set_management_fee(&mut self, management_fee_bps: u16) {
+        self.management_fee_bps = management_fee_bps;
+    }
+
+    #[inline(always)]
+    pub fn set_crank_tip(&mut self, crank_tip_lamports: u64) {
+        self.crank_tip_lamports = crank_tip_lamports;
+    }
+
+    #[inline(always)]
+    pub fn set_cached_rate(&mut self, rate_lamports: u64, rate_lst_supply: u64, rate_epoch: u64) {
+        self.rate_lamports = rate_lamports;
+        self.rate_lst_supply = rate_lst_supply;
+        self.rate_epoch = rate_epoch;
+    }
+
+    /// The `(pool_lamports, lst_supply)` rate `CrankUpdateRate` cached in
+    /// `epoch`. `None` when the rate was not updated this epoch or the
+    /// snapshot was of an empty pool, and callers price against the live
+    /// balances instead.
+    #[inline(always)]
+    pub fn cached_rate(&self, epoch: u64) -> Option<(u64, u64)> {
+        if self.rate_epoch != epoch || self.rate_lamports == 0 || self.rate_lst_supply == 0 {
+            return None;
+        }
+        Some((self.rate_lamports, self.rate_lst_supply))
+    }
+
+    #[inline(always)]
+    pub fn set_max_pool_lamports(&mut self, max_pool_lamports: u64) {
+        self.max_pool_lamports = max_pool_lamports;
+    }
+
+    #[inline(always)]
+    pub fn set_deposit_authority(&mut self, deposit_authority: Pubkey) {
+        self.deposit_authority = deposit_authority;
+    }
+
+    #[inline(always)]
+    pub fn set_pending_admin(&mut self, pending_admin: Pubkey) {
+        self.pending_admin = pending_admin;
+    }
+
+    /// Makes the pending admin the admin and clears the pending transfer.
+    #[inline(always)]
+    pub fn accept_admin(&mut self) {
+        self.admin = self.pending_admin;
+        self.pending_admin = [0; 32];
+    }
+
+    #[inline(always)]
+    pub fn set_validator_vote_pubkey(&mut self, validator_vote_pubkey: Pubkey) {
+        self.validator_vote_pubkey = validator_vote_pubkey;
+    }
+
+    #[inline(always)]
+    pub fn set_reward_payout(&mut self, reward_payout_bps: u16) {
+        self.reward_payout_bps = reward_payout_bps;
+    }
+
+    /// Records `stake_lamports` as a listed validator's stake in `epoch` in
+    /// place of what was recorded before, keeping `validator_stake_lamports`
+    /// the sum of the list.
+    #[inline(always)]
+    pub fn record_validator_stake(
+        &mut self,
+        entry: &mut ValidatorEntry,
+        stake_lamports: u64,
+        epoch: u64,
+    ) -> Result<(), ProgramError> {
+        self.validator_stake_lamports = self
+            .validator_stake_lamports
+            .saturating_sub(entry.stake_lamports)
+            .checked_add(stake_lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        entry.stake_lamports = stake_lamports;
+        entry.recorded_epoch = epoch;
+        Ok(())
+    }
+
+    /// Sets `entry`'s recorded transient stake to `transient_lamports` in
+    /// `epoch`, keeping `validator_stake_lamports` the sum over the list.
+    #[inline(always)]
+    pub fn record_transient_stake(
+        &mut self,
+        entry: &mut ValidatorEntry,
+        transient_lamports: u64,
+        epoch: u64,
+    ) -> Result<(), ProgramError> {
+        self.validator_stake_lamports = self
+            .validator_stake_lamports
+            .saturating_sub(entry.transient_lamports)
+            .checked_add(transient_lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        entry.transient_lamports = transient_lamports;
+        entry.recorded_epoch = epoch;
+        Ok(())
+    }
+
+    /// Marks `validator_stake_lamports` current for `epoch` once every
+    /// validator in `validator_list` holding stake was recorded in it.
+    #[inline(always)]
+    pub fn stamp_validator_stake(&mut self, validator_list: &ValidatorList, epoch: u64) {
+        if validator_list.recorded_in(epoch) {
+            self.validator_stake_epoch = epoch;
+        }
+    }
+
+    /// Whether `validator_stake_lamports` can be priced against in `epoch`:
+    /// no validator holds stake, or all of it was recorded this epoch.
+    /// Rewards land in validator stake accounts at the epoch boundary, so
+    /// an earlier sum leaves them out of the rate.
+    #[inline(always)]
+    pub fn validator_stake_current(&self, epoch: u64) -> bool {
+        self.validator_stake_lamports == 0 || self.validator_stake_epoch == epoch
+    }
+
+    #[inline(always)]
+    pub fn delegation_strategy(&self) -> Result<DelegationStrategyKind, ProgramError> {
+        DelegationStrategyKind::try_from(self.delegation_strategy)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    #[inline(always)]
+    pub fn status(&self) -> Result<PoolStatus, ProgramError> {
+        PoolStatus::try_from(self.status).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Moves the pool to `next` if the lifecycle allows it and returns the
+    /// status it left.
+    #[inline(always)]
+    pub fn transition(&mut self, next: PoolStatus) -> Result<PoolStatus, ProgramError> {
+        let current = self.status()?;
+        if !current.can_transition_to(next) {
+            return Err(PinocchioError::InvalidPoolStatusTransition.into());
+        }
+        self.status = next as u8;
+        Ok(current)
+    }
+
+    #[inline(always)]
+    pub fn is_instruction_disabled(&self, discriminator: u8) -> bool {
+        self.disabled_instructions[discriminator as usize / 8] & (1 << (discriminator % 8)) != 0
+    }
+
+    #[inline(always)]
+    pub fn set_instruction_disabled(&mut self, discriminator: u8, disabled: bool) {
+        let byte = &mut self.disabled_instructions[discriminator as usize / 8];
+        if disabled {
+            *byte |= 1 << (discriminator % 8);
+        } else {
+            *byte &= !(1 << (discriminator % 8));
+        }
+    }
+
+    /// Most lamports one validator may hold when the pool holds `pool_lamports`,
+    /// the lower of the percentage and absolute caps.
+    #[inline(always)]
+    pub fn validator_stake_cap(&self, pool_lamports: u64) -> u64 {
+        let share_cap =
+            (pool_lamports as u128 * self.max_validator_stake_bps as u128 / 10_000) as u64;
+        match self.max_validator_stake_lamports {
+            0 => share_cap,
+            absolute_cap => share_cap.min(absolute_cap),
+        }
+    }
+}
+
+/// Lifecycle stage selected by the config's `status` byte.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PoolStatus {
+    /// Set by `Initialize`. The pool only takes deposits while main warms up,
+    /// as the exchange rate means little before its stake is active. It
+    /// becomes `Active` on the first reserve merge, the first epoch update
+    /// that proves stake has activated.
+    Bootstrapping = 0,
+    Active = 1,
+    /// Deposits and unstakes are halted. Cooled-down splits can still be
+    /// withdrawn.
+    Paused = 2,
+    /// Winding down. No new deposits, but holders can still unstake and exit.
+    Deactivating = 3,
+    /// The pool's stake was redeemed with `ExitPool`.
+    Closed = 4,
+}
+
+impl TryFrom<u8> for PoolStatus {
+    type Error = ProgramError;
+
+    fn try_from(status: u8) -> Result<Self, Self::Error> {
+        match status {
+            0 => Ok(Self::Bootstrapping),
+            1 => Ok(Self::Active),
+            2 => Ok(Self::Paused),
+            3 => Ok(Self::Deactivating),
+            4 => Ok(Self::Closed),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+impl PoolStatus {
+    pub fn can_transition_to(&self, next: PoolStatus) -> bool {
+        use PoolStatus::*;
+        matches!(
+            (self, next),
+            (Bootstrapping, Active)
+                | (Bootstrapping | Active, Paused)
+                | (Paused, Active)
+                | (Bootstrapping | Active | Paused, Deactivating)
+                | (Active | Deactivating, Closed)
+        )
     }
+
+    /// Fails unless the pool accepts new deposits.
+    pub fn check_deposits(&self) -> Result<(), ProgramError> {
+        match self {
+            Self::Bootstrapping | Self::Active => Ok(()),
+            Self::Paused => Err(PinocchioError::PoolPaused.into()),
+            Self::Deactivating | Self::Closed => Err(PinocchioError::PoolClosed.into()),
+        }
+    }
+
+    /// Fails unless stake may leave the pool through splits, liquid unstakes
+    /// or `ExitPool`.
+    pub fn check_unstakes(&self) -> Result<(), ProgramError> {
+        match self {
+            Self::Active | Self::Deactivating => Ok(()),
+            Self::Bootstrapping => Err(PinocchioError::PoolBootstrapping.into()),
+            Self::Paused => Err(PinocchioError::PoolPaused.into()),
+            Self::Closed => Err(PinocchioError::PoolClosed.into()),
+        }
+    }
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct RateSnapshot {
+    pub epoch: u64,
+    pub total_lamports: u64,
+    pub lst_supply: u64,
+}
+
+impl RateSnapshot {
+    pub const LEN: usize = 8 + 8 + 8;
+}
+
+#[repr(C, packed)]
+pub struct RateHistory {
+    /// `AccountDiscriminator::RateHistory`.
+    pub discriminator: u8,
+    /// Layout version, `RateHistory::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub head: u64,
+    pub len: u64,
+    pub snapshots: [RateSnapshot; RateHistory::CAPACITY],
+}
+
+impl RateHistory {
+    pub const CAPACITY: usize = 64;
+    pub const LEN: usize = 1 + 1 + 8 + 8 + RateSnapshot::LEN * RateHistory::CAPACITY;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != RateHistory::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != RateHistory::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    /// Appends a snapshot, overwriting the oldest one once the buffer is full.
+    #[inline(always)]
+    pub fn push(&mut self, snapshot: RateSnapshot) {
+        let head = self.head as usize % Self::CAPACITY;
+        self.snapshots[head] = snapshot;
+        self.head = ((head + 1) % Self::CAPACITY) as u64;
+        if (self.len as usize) < Self::CAPACITY {
+            self.len += 1;
+        }
+    }
+
+    /// Returns the snapshot `index` positions back from the most recent one.
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> Option<RateSnapshot> {
+        if index >= self.len as usize {
+            return None;
+        }
+        let position = (self.head as usize + Self::CAPACITY - 1 - index) % Self::CAPACITY;
+        Some(self.snapshots[position])
+    }
+
+    #[inline(always)]
+    pub fn latest(&self) -> Option<RateSnapshot> {
+        self.get(0)
+    }
+
+    /// Returns the most recent snapshot taken at least `epochs` epochs before `epoch`.
+    #[inline(always)]
+    pub fn at_least_epochs_before(&self, epoch: u64, epochs: u64) -> Option<RateSnapshot> {
+        (0..self.len as usize)
+            .filter_map(|index| self.get(index))
+            .find(|snapshot| snapshot.epoch.saturating_add(epochs) <= epoch)
+    }
+}
+
+impl AccountHeader for RateHistory {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::RateHistory;
+    const VERSION: u8 = 1;
+}
+
+#[repr(C, packed)]
+pub struct SplitRecord {
+    /// `AccountDiscriminator::SplitRecord`.
+    pub discriminator: u8,
+    /// Layout version, `SplitRecord::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub owner: [u8; 32],
+    pub split_account: [u8; 32],
+    pub lamports_requested: u64,
+    pub lamports_split: u64,
+    pub lst_burned: u64,
+    /// Requested minus split and scheduled lamports. Positive when the split
+    /// was rounded down to keep main above its minimum, negative when rounded
+    /// up to the split minimum.
+    pub dust_lamports: i64,
+    /// Lamports still to be split in later epochs' tranches.
+    pub lamports_scheduled: u64,
+    /// First epoch in which the next tranche may be split.
+    pub next_tranche_epoch: u64,
+    /// Number of tranches split so far, including the first.
+    pub tranches_split: u64,
+    /// Account that funded the split account, refunded its rent and funding
+    /// by `Withdraw`. The owner unless a separate payer sponsored the split.
+    pub rent_payer: [u8; 32],
+    /// Epoch in which the split was made.
+    pub created_epoch: u64,
+    /// Set once `Withdraw` has paid out the split account. Only seen on a
+    /// record kept open for later tranches, since `Withdraw` closes the rest.
+    pub claimed: u8,
+}
+
+impl SplitRecord {
+    pub const LEN: usize = 1 + 1 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 32 + 8 + 1;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != SplitRecord::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != SplitRecord::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    #[inline(always)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_inner(
+        &mut self,
+        owner: Pubkey,
+        split_account: Pubkey,
+        lamports_requested: u64,
+        lamports_split: u64,
+        lst_burned: u64,
+        lamports_scheduled: u64,
+        next_tranche_epoch: u64,
+    ) {
+        self.owner = owner;
+        self.split_account = split_account;
+        self.lamports_requested = lamports_requested;
+        self.lamports_split = lamports_split;
+        self.lst_burned = lst_burned;
+        self.dust_lamports = (lamports_requested as i64)
+            .wrapping_sub(lamports_split as i64)
+            .wrapping_sub(lamports_scheduled as i64);
+        self.lamports_scheduled = lamports_scheduled;
+        self.next_tranche_epoch = next_tranche_epoch;
+        self.tranches_split = 1;
+        self.rent_payer = owner;
+        self.created_epoch = 0;
+        self.claimed = 0;
+    }
+}
+
+impl AccountHeader for SplitRecord {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::SplitRecord;
+    const VERSION: u8 = 1;
+}
+
+/// A withdrawer's next split nonce, at `[b"split_counter", withdrawer]`.
+/// `CrankSplitNext` splits with it and increments it, so clients read the
+/// nonce instead of keeping track of their own.
+#[repr(C, packed)]
+pub struct SplitCounter {
+    /// `AccountDiscriminator::SplitCounter`.
+    pub discriminator: u8,
+    /// Layout version, `SplitCounter::VERSION` for accounts this program
+    /// reads.
+    pub version: u8,
+    pub next_nonce: u64,
+}
+
+impl AccountHeader for SplitCounter {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::SplitCounter;
+    const VERSION: u8 = 1;
+}
+
+impl SplitCounter {
+    pub const LEN: usize = 1 + 1 + 8;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != SplitCounter::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != SplitCounter::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+}
+
+/// Programs approved by the admin to call `Deposit` with checks skipped.
+#[repr(C, packed)]
+pub struct TrustedCallers {
+    /// `AccountDiscriminator::TrustedCallers`.
+    pub discriminator: u8,
+    /// Layout version, `TrustedCallers::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub len: u64,
+    pub programs: [[u8; 32]; TrustedCallers::CAPACITY],
+}
+
+impl TrustedCallers {
+    pub const CAPACITY: usize = 16;
+    pub const LEN: usize = 1 + 1 + 8 + 32 * TrustedCallers::CAPACITY;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != TrustedCallers::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != TrustedCallers::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn contains(&self, program: &Pubkey) -> bool {
+        self.programs[..self.len as usize].contains(program)
+    }
+
+    /// Adds `program` unless already present. Returns false when full.
+    #[inline(always)]
+    pub fn add(&mut self, program: Pubkey) -> bool {
+        if self.contains(&program) {
+            return true;
+        }
+        if self.len as usize >= Self::CAPACITY {
+            return false;
+        }
+        self.programs[self.len as usize] = program;
+        self.len += 1;
+        true
+    }
+
+    #[inline(always)]
+    pub fn remove(&mut self, program: &Pubkey) {
+        let len = self.len as usize;
+        if let Some(index) = self.programs[..len].iter().position(|p| p == program) {
+            self.programs[index] = self.programs[len - 1];
+            self.programs[len - 1] = [0u8; 32];
+            self.len -= 1;
+        }
+    }
+}
+
+impl AccountHeader for TrustedCallers {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::TrustedCallers;
+    const VERSION: u8 = 1;
+}
+
+/// Instant-unstake liquidity pool. Holds a SOL buffer in its own lamports
+/// (tracked by `sol_reserves`, excluding rent) and LST in `lst_vault`, an ATA
+/// owned by this PDA. Liquidity providers hold `lp_mint` shares of both.
+#[repr(C, packed)]
+pub struct LiquidityPool {
+    /// `AccountDiscriminator::LiquidityPool`.
+    pub discriminator: u8,
+    /// Layout version, `LiquidityPool::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub lp_mint: [u8; 32],
+    pub lst_vault: [u8; 32],
+    pub sol_reserves: u64,
+    /// SOL cooling down in the buffer stake account, owed to the pool.
+    pub pending_unstake_lamports: u64,
+    /// Keeper that funded the buffer stake account, refunded on collection.
+    pub buffer_stake_funder: [u8; 32],
+}
+
+impl LiquidityPool {
+    pub const LEN: usize = 1 + 1 + 32 + 32 + 8 + 8 + 32;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != LiquidityPool::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != LiquidityPool::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, lp_mint: Pubkey, lst_vault: Pubkey) {
+        self.lp_mint = lp_mint;
+        self.lst_vault = lst_vault;
+        self.sol_reserves = 0;
+        self.pending_unstake_lamports = 0;
+        self.buffer_stake_funder = [0; 32];
+    }
+}
+
+impl AccountHeader for LiquidityPool {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::LiquidityPool;
+    const VERSION: u8 = 1;
+}
+
+/// Status of a validator's bond in the self-onboarding flow.
+pub const VALIDATOR_BOND_PENDING: u8 = 0;
+pub const VALIDATOR_BOND_ACTIVE: u8 = 1;
+pub const VALIDATOR_BOND_EXITING: u8 = 2;
+
+/// Refundable bond posted by a validator operator to join the validator set.
+/// Holds the bond in its own lamports (tracked by `bond_lamports`, excluding
+/// rent) at PDA `[b"validator_bond", vote_account]`.
+#[repr(C, packed)]
+pub struct ValidatorBond {
+    /// `AccountDiscriminator::ValidatorBond`.
+    pub discriminator: u8,
+    /// Layout version, `ValidatorBond::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub vote_account: [u8; 32],
+    /// Validator identity that posted the bond, refunded when it leaves.
+    pub operator: [u8; 32],
+    pub bond_lamports: u64,
+    pub status: u8,
+    /// First epoch in which an exiting validator may withdraw its bond.
+    pub unlock_epoch: u64,
+}
+
+impl ValidatorBond {
+    pub const LEN: usize = 1 + 1 + 32 + 32 + 8 + 1 + 8;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != ValidatorBond::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != ValidatorBond::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, vote_account: Pubkey, operator: Pubkey) {
+        self.vote_account = vote_account;
+        self.operator = operator;
+        self.bond_lamports = 0;
+        self.status = VALIDATOR_BOND_PENDING;
+        self.unlock_epoch = 0;
+    }
+}
+
+impl AccountHeader for ValidatorBond {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::ValidatorBond;
+    const VERSION: u8 = 1;
+}
+
+/// A validator in the validator set, with the inputs delegation strategies
+/// read to compute its target stake.
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct ValidatorEntry {
+    pub vote_account: [u8; 32],
+    /// Admin-assigned weight used by the weight-based strategy.
+    pub weight: u32,
+    /// Performance score used by the score-based strategy.
+    pub score: u32,
+    /// Lamports delegated in the validator's stake PDA
+    /// (`b"validator_stake"`, vote account) as last recorded by
+    /// `CrankValidatorStake` or a split from it.
+    pub stake_lamports: u64,
+    /// Lamports delegated in the validator's transient stake PDA
+    /// (`b"validator_transient"`, vote account) while `CrankRebalance` moves
+    /// stake to or from the validator.
+    pub transient_lamports: u64,
+    /// Epoch `stake_lamports` and `transient_lamports` were last recorded in.
+    pub recorded_epoch: u64,
+}
+
+impl ValidatorEntry {
+    pub const LEN: usize = 32 + 4 + 4 + 8 + 8 + 8;
+    /// Weight given to newly activated validators.
+    pub const DEFAULT_WEIGHT: u32 = 1;
+
+    pub const EMPTY: ValidatorEntry = ValidatorEntry {
+        vote_account: [0u8; 32],
+        weight: 0,
+        score: 0,
+        stake_lamports: 0,
+        transient_lamports: 0,
+        recorded_epoch: 0,
+    };
+}
+
+/// Validators activated into the validator set.
+#[repr(C, packed)]
+pub struct ValidatorList {
+    /// `AccountDiscriminator::ValidatorList`.
+    pub discriminator: u8,
+    /// Layout version, `ValidatorList::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub len: u64,
+    pub validators: [ValidatorEntry; ValidatorList::CAPACITY],
+}
+
+impl ValidatorList {
+    pub const CAPACITY: usize = 32;
+    pub const LEN: usize = 1 + 1 + 8 + ValidatorEntry::LEN * ValidatorList::CAPACITY;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != ValidatorList::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != ValidatorList::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn entries(&self) -> &[ValidatorEntry] {
+        &self.validators[..self.len as usize]
+    }
+
+    /// Whether every validator holding stake or transient stake was
+    /// recorded in `epoch`.
+    #[inline(always)]
+    pub fn recorded_in(&self, epoch: u64) -> bool {
+        self.entries().iter().all(|entry| {
+            (entry.stake_lamports == 0 && entry.transient_lamports == 0)
+                || entry.recorded_epoch == epoch
+        })
+    }
+
+    #[inline(always)]
+    pub fn find_mut(&mut self, vote_account: &Pubkey) -> Option<&mut ValidatorEntry> {
+        let len = self.len as usize;
+        self.validators[..len]
+            .iter_mut()
+            .find(|entry| entry.vote_account == *vote_account)
+    }
+
+    #[inline(always)]
+    pub fn contains(&self, vote_account: &Pubkey) -> bool {
+        self.entries()
+            .iter()
+            .any(|entry| entry.vote_account == *vote_account)
+    }
+
+    /// Adds `vote_account` with the default weight unless already present.
+    /// Returns false when full.
+    #[inline(always)]
+    pub fn add(&mut self, vote_account: Pubkey) -> bool {
+        if self.contains(&vote_account) {
+            return true;
+        }
+        if self.len as usize >= Self::CAPACITY {
+            return false;
+        }
+        self.validators[self.len as usize] = ValidatorEntry {
+            vote_account,
+            weight: ValidatorEntry::DEFAULT_WEIGHT,
+            score: 0,
+            stake_lamports: 0,
+            transient_lamports: 0,
+            recorded_epoch: 0,
+        };
+        self.len += 1;
+        true
+    }
+
+    /// Removes `vote_account` if present. Fails with
+    /// `ValidatorStakeOutstanding` while its stake or transient stake PDA
+    /// still holds pool stake, which would otherwise drop out of the exchange
+    /// rate.
+    #[inline(always)]
+    pub fn remove(&mut self, vote_account: &Pubkey) -> Result<(), ProgramError> {
+        let len = self.len as usize;
+        if let Some(index) = self.validators[..len]
+            .iter()
+            .position(|entry| entry.vote_account == *vote_account)
+        {
+            let entry = self.validators[index];
+            if entry.stake_lamports != 0 || entry.transient_lamports != 0 {
+                return Err(PinocchioError::ValidatorStakeOutstanding.into());
+            }
+            self.validators[index] = self.validators[len - 1];
+            self.validators[len - 1] = ValidatorEntry::EMPTY;
+            self.len -= 1;
+        }
+        Ok(())
+    }
+}
+
+impl AccountHeader for ValidatorList {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::ValidatorList;
+    const VERSION: u8 = 1;
+}
+
+/// Crank steps tracked in the epoch task checklist, one bit each.
+pub const EPOCH_TASK_INITIALIZE_RESERVE: u8 = 1 << 0;
+pub const EPOCH_TASK_MERGE_RESERVE: u8 = 1 << 1;
+pub const EPOCH_TASK_REBALANCE: u8 = 1 << 2;
+pub const EPOCH_TASK_FEE_ACCRUAL: u8 = 1 << 3;
+pub const EPOCH_TASK_ORACLE_PUSH: u8 = 1 << 4;
+pub const EPOCH_TASK_RATE_UPDATE: u8 = 1 << 5;
+
+#[repr(C, packed)]
+pub struct EpochTasks {
+    /// `AccountDiscriminator::EpochTasks`.
+    pub discriminator: u8,
+    /// Layout version, `EpochTasks::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub epoch: u64,
+    pub completed: u8,
+}
+
+impl EpochTasks {
+    pub const LEN: usize = 1 + 1 + 8 + 1;
+
+    /// Steps keepers are expected to run every epoch. `CrankManagementFee`
+    /// marks fee accrual even while the fee is zero, so neither the oracle
+    /// push nor the rate update records a rate the epoch's fee has not been
+    /// charged against. `CrankRebalance` marks rebalance on any validator's
+    /// step.
+    pub const REQUIRED: u8 = EPOCH_TASK_INITIALIZE_RESERVE
+        | EPOCH_TASK_MERGE_RESERVE
+        | EPOCH_TASK_REBALANCE
+        | EPOCH_TASK_FEE_ACCRUAL
+        | EPOCH_TASK_ORACLE_PUSH
+        | EPOCH_TASK_RATE_UPDATE;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != EpochTasks::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != EpochTasks::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    /// Returns the tasks completed in `epoch`. A checklist last touched in an
+    /// earlier epoch has nothing completed yet.
+    #[inline(always)]
+    pub fn completed(&self, epoch: u64) -> u8 {
+        if self.epoch == epoch {
+            self.completed
+        } else {
+            0
+        }
+    }
+
+    /// Returns the required tasks not yet completed in `epoch`.
+    #[inline(always)]
+    pub fn pending(&self, epoch: u64) -> u8 {
+        Self::REQUIRED & !self.completed(epoch)
+    }
+
+    /// Tasks that must complete earlier in the same epoch before `task` may
+    /// run: fee accrual before the oracle push and the rate update, merge
+    /// before rebalance.
+    #[inline(always)]
+    pub const fn prerequisites(task: u8) -> u8 {
+        match task {
+            EPOCH_TASK_ORACLE_PUSH | EPOCH_TASK_RATE_UPDATE => EPOCH_TASK_FEE_ACCRUAL,
+            EPOCH_TASK_REBALANCE => EPOCH_TASK_MERGE_RESERVE,
+            _ => 0,
+        }
+    }
+
+    /// Returns the prerequisites of `task` still pending in `epoch`. Only
+    /// required tasks are enforced.
+    #[inline(always)]
+    pub fn missing_prerequisites(&self, epoch: u64, task: u8) -> u8 {
+        Self::prerequisites(task) & self.pending(epoch)
+    }
+
+    /// Marks `task` complete for `epoch`, starting a fresh checklist when the
+    /// epoch has rolled over.
+    #[inline(always)]
+    pub fn mark(&mut self, epoch: u64, task: u8) {
+        self.completed = self.completed(epoch) | task;
+        self.epoch = epoch;
+    }
+}
+
+impl AccountHeader for EpochTasks {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::EpochTasks;
+    const VERSION: u8 = 1;
+}
+
+/// Lamports a depositor sent beyond the per-epoch deposit cap, held in the
+/// PDA on top of its rent until `ClaimQueuedDeposit` moves them into the
+/// reserve in a later epoch.
+#[repr(C, packed)]
+pub struct QueuedDeposit {
+    /// `AccountDiscriminator::QueuedDeposit`.
+    pub discriminator: u8,
+    /// Layout version, `QueuedDeposit::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub depositor: [u8; 32],
+    pub lamports: u64,
+    /// Epoch of the last queued deposit. Claims open the epoch after.
+    pub epoch: u64,
+}
+
+impl QueuedDeposit {
+    pub const LEN: usize = 1 + 1 + 32 + 8 + 8;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != QueuedDeposit::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != QueuedDeposit::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+}
+
+impl AccountHeader for QueuedDeposit {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::QueuedDeposit;
+    const VERSION: u8 = 1;
+}
+
+/// A referrer's attribution stats (`b"referrer"`, referrer). Referral fees
+/// are minted into the treasury ATA and held there until the referrer claims
+/// them with `ClaimReferralFees`.
+#[repr(C, packed)]
+pub struct ReferrerRecord {
+    /// `AccountDiscriminator::ReferrerRecord`.
+    pub discriminator: u8,
+    /// Layout version, `ReferrerRecord::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub referrer: [u8; 32],
+    /// Lamports deposited by referred `Deposit`s, excluding queued excess.
+    pub referred_lamports: u64,
+    pub referred_deposits: u64,
+    /// Referral fees in LST, claimed or not.
+    pub fees_earned: u64,
+    pub fees_claimed: u64,
+}
+
+impl ReferrerRecord {
+    pub const LEN: usize = 1 + 1 + 32 + 8 + 8 + 8 + 8;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != ReferrerRecord::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != ReferrerRecord::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, referrer: Pubkey) {
+        self.referrer = referrer;
+        self.referred_lamports = 0;
+        self.referred_deposits = 0;
+        self.fees_earned = 0;
+        self.fees_claimed = 0;
+    }
+
+    /// Referral fees earned but not claimed yet.
+    #[inline(always)]
+    pub fn unclaimed_fees(&self) -> u64 {
+        self.fees_earned - self.fees_claimed
+    }
+}
+
+impl AccountHeader for ReferrerRecord {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::ReferrerRecord;
+    const VERSION: u8 = 1;
+}
+
+/// Records a deposit made with a client-supplied idempotency key, so a retry
+/// with the same key fails instead of depositing twice.
+#[repr(C, packed)]
+pub struct DepositKey {
+    /// `AccountDiscriminator::DepositKey`.
+    pub discriminator: u8,
+    /// Layout version, `DepositKey::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub depositor: [u8; 32],
+    pub created_slot: u64,
+    /// Memo hash passed with the deposit, zero if there was none.
+    pub memo_hash: [u8; 32],
+}
+
+impl DepositKey {
+    pub const LEN: usize = 1 + 1 + 32 + 8 + 32;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != DepositKey::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != DepositKey::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, depositor: Pubkey, created_slot: u64, memo_hash: [u8; 32]) {
+        self.depositor = depositor;
+        self.created_slot = created_slot;
+        self.memo_hash = memo_hash;
+    }
+}
+
+impl AccountHeader for DepositKey {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::DepositKey;
+    const VERSION: u8 = 1;
+}
+
+/// Records a deposit quote's nonce once a deposit used it, so the signed
+/// quote cannot be replayed before it expires.
+#[repr(C, packed)]
+pub struct UsedQuote {
+    /// `AccountDiscriminator::UsedQuote`.
+    pub discriminator: u8,
+    /// Layout version, `UsedQuote::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub depositor: [u8; 32],
+    /// The quote's last valid slot. The record may be closed after it.
+    pub expires_at_slot: u64,
+}
+
+impl UsedQuote {
+    pub const LEN: usize = 1 + 1 + 32 + 8;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != UsedQuote::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != UsedQuote::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+}
+
+impl AccountHeader for UsedQuote {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::UsedQuote;
+    const VERSION: u8 = 1;
+}
+
+/// Per-user stats (`b"user_stats"`, user), created by the user's first
+/// `Deposit` that passes it. The withdrawal fee rebate is measured from
+/// `first_deposit_epoch`.
+#[repr(C, packed)]
+pub struct UserStats {
+    /// `AccountDiscriminator::UserStats`.
+    pub discriminator: u8,
+    /// Layout version, `UserStats::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub user: [u8; 32],
+    /// Epoch of the user's first deposit.
+    pub first_deposit_epoch: u64,
+}
+
+impl UserStats {
+    pub const LEN: usize = 1 + 1 + 32 + 8;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != UserStats::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != UserStats::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+}
+
+impl AccountHeader for UserStats {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::UserStats;
+    const VERSION: u8 = 1;
+}
+
+/// Pool fields written on every user instruction. `Config` holds the admin's
+/// settings and state that cranks update a few times per epoch, so it can
+/// stay read-only for users while this small account takes the write locks.
+#[repr(C, packed)]
+pub struct PoolState {
+    /// `AccountDiscriminator::PoolState`.
+    pub discriminator: u8,
+    /// Layout version, `PoolState::VERSION` for accounts this program reads.
+    pub version: u8,
+    /// Sequence number of the last emitted event, see `crate::events`.
+    pub event_sequence: u64,
+    /// Epoch `inflow_lamports` was counted in.
+    pub inflow_epoch: u64,
+    /// Lamports deposited into the reserve during `inflow_epoch`.
+    pub inflow_lamports: u64,
+    /// Ring buffer of the last `EVENT_LOG_CAPACITY` events, the event with
+    /// sequence number `n` at index `n % EVENT_LOG_CAPACITY`.
+    pub recent_events: [EventRecord; PoolState::EVENT_LOG_CAPACITY],
+    /// Lamports deposited for the LST supply as of the last mint, net of the
+    /// share of LST burned since then, see `principal_at`.
+    pub principal_lamports: u64,
+    /// LST supply right after the mint that last updated `principal_lamports`.
+    pub principal_lst_supply: u64,
+    /// LST minted minus LST burned by this program. The mint's supply can
+    /// only be lower, by what holders burned through the token program
+    /// directly, see `record_lst_issued`.
+    pub lst_issued: u64,
+    /// Lamports backing the LST supply: what deposits brought in, less what
+    /// withdrawals took out, plus the rewards the pool's stake earned. Every
+    /// mint and burn prices against it rather than the stake accounts'
+    /// balances, so lamports sent straight to them do not move the rate.
+    pub total_managed_lamports: u64,
+    /// The pool's delegated stake, see `pool_stake`, as of the last
+    /// instruction that credited rewards or moved stake. Only rewards grow
+    /// it in between, see `accrue_rewards`.
+    pub recorded_stake_lamports: u64,
+}
+
+/// Lamports an instruction moved into or out of the pool, see
+/// `PoolState::record_managed_change`.
+#[derive(Clone, Copy)]
+pub enum ManagedChange {
+    Deposited(u64),
+    Withdrawn(u64),
+    /// Rewards of stake the pool state no longer records, such as the
+    /// outgoing reserve's after `UpdateConfig` moved it out.
+    Rewarded(u64),
+}
+
+/// Compact copy of an emitted event, kept in `PoolState::recent_events` for
+/// clients that poll accounts instead of subscribing to logs.
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct EventRecord {
+    pub sequence: u64,
+    pub slot: u64,
+    /// `crate::events::EVENT_*` kind.
+    pub kind: u8,
+    /// First 32-byte field of the payload, zero if there is none.
+    pub actor: [u8; 32],
+    /// First two other payload fields, as little-endian integers.
+    pub amounts: [u64; 2],
+}
+
+impl EventRecord {
+    pub const LEN: usize = 8 + 8 + 1 + 32 + 8 * 2;
+}
+
+impl PoolState {
+    pub const EVENT_LOG_CAPACITY: usize = 32;
+    pub const LEN: usize =
+        1 + 1 + 8 + 8 + 8 + EventRecord::LEN * PoolState::EVENT_LOG_CAPACITY + 8 + 8 + 8 + 8 + 8;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != PoolState::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != PoolState::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    /// Advances and returns the event sequence number.
+    #[inline(always)]
+    pub fn next_event_sequence(&mut self) -> Result<u64, ProgramError> {
+        self.event_sequence = self
+            .event_sequence
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(self.event_sequence)
+    }
+
+    /// Lamports that may still be deposited in `epoch` under a cap of
+    /// `cap_lamports`, zero meaning no cap.
+    #[inline(always)]
+    pub fn inflow_room(&self, epoch: u64, cap_lamports: u64) -> u64 {
+        if cap_lamports == 0 {
+            return u64::MAX;
+        }
+
+        if self.inflow_epoch != epoch {
+            return cap_lamports;
+        }
+
+        cap_lamports.saturating_sub(self.inflow_lamports)
+    }
+
+    /// Counts `lamports` deposited in `epoch`, starting a new count when the
+    /// epoch changed.
+    #[inline(always)]
+    pub fn record_inflow(&mut self, epoch: u64, lamports: u64) {
+        if self.inflow_epoch != epoch {
+            self.inflow_epoch = epoch;
+            self.inflow_lamports = 0;
+        }
+
+        self.inflow_lamports = self.inflow_lamports.saturating_add(lamports);
+    }
+
+    /// Principal backing `lst_supply` LST. Burns are not recorded as they
+    /// happen, so LST burned since the last mint take their average share of
+    /// principal with them and the rest of what they were worth is yield.
+    #[inline(always)]
+    pub fn principal_at(&self, lst_supply: u64) -> u64 {
+        if lst_supply >= self.principal_lst_supply {
+            return self.principal_lamports;
+        }
+
+        (self.principal_lamports as u128 * lst_supply as u128 / self.principal_lst_supply as u128)
+            as u64
+    }
+
+    /// Adds `lamports` deposited for `lst_minted` LST, minted on top of
+    /// `lst_supply_before`, to the principal.
+    #[inline(always)]
+    pub fn record_principal(
+        &mut self,
+        lst_supply_before: u64,
+        lamports: u64,
+        lst_minted: u64,
+    ) -> Result<(), ProgramError> {
+        self.principal_lamports = self
+            .principal_at(lst_supply_before)
+            .checked_add(lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.principal_lst_supply = lst_supply_before
+            .checked_add(lst_minted)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    /// Yield held by the pool: `pool_lamports` backing `lst_supply` LST beyond
+    /// their principal, zero after a loss. Any fee taken from the pool must
+    /// stay within it so depositors' principal is never skimmed.
+    #[inline(always)]
+    pub fn yield_lamports(&self, pool_lamports: u64, lst_supply: u64) -> u64 {
+        pool_lamports.saturating_sub(self.principal_at(lst_supply))
+    }
+
+    /// Sets the LST issued after a mint or burn that left the mint's supply
+    /// at `lst_supply`. Only this program mints, so a supply above what it
+    /// issued means the token program minted more than it was asked to.
+    #[inline(always)]
+    pub fn record_lst_issued(
+        &mut self,
+        lst_issued: u64,
+        lst_supply: u64,
+    ) -> Result<(), ProgramError> {
+        if lst_supply > lst_issued {
+            return Err(PinocchioError::SupplyMismatch.into());
+        }
+
+        self.lst_issued = lst_issued;
+        Ok(())
+    }
+
+    /// `total_managed_lamports` with the rewards not yet credited: the
+    /// growth of the pool's delegated stake, now `stake_lamports`, since it
+    /// was last recorded. Instructions that move stake record what they
+    /// leave, so only epoch rewards grow a delegation in between; lamports
+    /// sent to a stake account never join its delegation.
+    #[inline(always)]
+    pub fn managed_lamports(&self, stake_lamports: u64) -> Result<u64, ProgramError> {
+        self.total_managed_lamports
+            .checked_add(stake_lamports.saturating_sub(self.recorded_stake_lamports))
+            .ok_or(ProgramError::ArithmeticOverflow)
+    }
+
+    /// Credits the rewards `managed_lamports` counts to
+    /// `total_managed_lamports` and records `stake_lamports`, so a stake
+    /// move recorded next does not lose them.
+    #[inline(always)]
+    pub fn accrue_rewards(&mut self, stake_lamports: u64) -> Result<u64, ProgramError> {
+        self.total_managed_lamports = self.managed_lamports(stake_lamports)?;
+        self.recorded_stake_lamports = stake_lamports;
+
+        Ok(self.total_managed_lamports)
+    }
+
+    /// Moves `total_managed_lamports` by lamports deposited into or paid out
+    /// of the pool.
+    #[inline(always)]
+    pub fn record_managed_change(&mut self, change: ManagedChange) -> Result<(), ProgramError> {
+        self.total_managed_lamports = match change {
+            ManagedChange::Deposited(lamports) | ManagedChange::Rewarded(lamports) => self
+                .total_managed_lamports
+                .checked_add(lamports)
+                .ok_or(ProgramError::ArithmeticOverflow)?,
+            ManagedChange::Withdrawn(lamports) => {
+                self.total_managed_lamports.saturating_sub(lamports)
+            }
+        };
+
+        Ok(())
+    }
+
+    /// Stores `record` in its slot of the ring buffer, overwriting the event
+    /// `EVENT_LOG_CAPACITY` sequence numbers older.
+    #[inline(always)]
+    pub fn record_event(&mut self, record: EventRecord) {
+        self.recent_events[record.sequence as usize % Self::EVENT_LOG_CAPACITY] = record;
+    }
+
+    /// Events still in the ring buffer, oldest first.
+    pub fn recent_events(&self) -> impl Iterator<Item = EventRecord> + '_ {
+        let last = self.event_sequence;
+        let first = last
+            .saturating_sub(Self::EVENT_LOG_CAPACITY as u64)
+            .saturating_add(1)
+            .max(1);
+        (first..=last)
+            .map(move |sequence| self.recent_events[sequence as usize % Self::EVENT_LOG_CAPACITY])
+    }
+}
+
+impl AccountHeader for PoolState {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::PoolState;
+    const VERSION: u8 = 1;
+}
+
+/// LST locked for `beneficiary` in `vault`, the escrow PDA's LST ATA. It
+/// vests linearly from `start_ts` to `end_ts` with nothing claimable before
+/// `cliff_ts`.
+#[repr(C, packed)]
+pub struct VestingEscrow {
+    /// `AccountDiscriminator::VestingEscrow`.
+    pub discriminator: u8,
+    /// Layout version, `VestingEscrow::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub beneficiary: [u8; 32],
+    pub vault: [u8; 32],
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub start_ts: i64,
+    pub cliff_ts: i64,
+    pub end_ts: i64,
+}
+
+impl VestingEscrow {
+    pub const LEN: usize = 1 + 1 + 32 + 32 + 8 + 8 + 8 + 8 + 8;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != VestingEscrow::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != VestingEscrow::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        beneficiary: Pubkey,
+        vault: Pubkey,
+        total_amount: u64,
+        start_ts: i64,
+        cliff_ts: i64,
+        end_ts: i64,
+    ) {
+        self.beneficiary = beneficiary;
+        self.vault = vault;
+        self.total_amount = total_amount;
+        self.claimed_amount = 0;
+        self.start_ts = start_ts;
+        self.cliff_ts = cliff_ts;
+        self.end_ts = end_ts;
+    }
+}
+
+impl AccountHeader for VestingEscrow {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::VestingEscrow;
+    const VERSION: u8 = 1;
+}
+
+/// Lets a session key deposit on a user's behalf. The PDA escrows the SOL
+/// the session may still deposit on top of its own rent.
+#[repr(C, packed)]
+pub struct DepositSession {
+    /// `AccountDiscriminator::DepositSession`.
+    pub discriminator: u8,
+    /// Layout version, `DepositSession::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub user: [u8; 32],
+    pub session_key: [u8; 32],
+    /// Lamports the session key may still deposit.
+    pub budget_lamports: u64,
+    /// Unix timestamp after which the session key can no longer deposit.
+    pub expires_at: i64,
+}
+
+impl DepositSession {
+    pub const LEN: usize = 1 + 1 + 32 + 32 + 8 + 8;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != DepositSession::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != DepositSession::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        user: Pubkey,
+        session_key: Pubkey,
+        budget_lamports: u64,
+        expires_at: i64,
+    ) {
+        self.user = user;
+        self.session_key = session_key;
+        self.budget_lamports = budget_lamports;
+        self.expires_at = expires_at;
+    }
+}
+
+impl AccountHeader for DepositSession {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::DepositSession;
+    const VERSION: u8 = 1;
+}
+
+/// A recurring deposit funded by the user and executed by keepers with
+/// `CrankDca`. The PDA escrows the remaining budget on top of its own rent.
+#[repr(C, packed)]
+pub struct DcaSchedule {
+    /// `AccountDiscriminator::DcaSchedule`.
+    pub discriminator: u8,
+    /// Layout version, `DcaSchedule::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub user: [u8; 32],
+    pub amount_per_interval: u64,
+    pub interval_secs: i64,
+    /// Unix timestamp from which the next deposit may be executed.
+    pub next_execution_ts: i64,
+    /// Lamports left for deposits and keeper tips.
+    pub budget_lamports: u64,
+    /// Lamports paid to the keeper for each executed deposit.
+    pub tip_lamports: u64,
+}
+
+impl DcaSchedule {
+    pub const LEN: usize = 1 + 1 + 32 + 8 + 8 + 8 + 8 + 8;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != DcaSchedule::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != DcaSchedule::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        user: Pubkey,
+        amount_per_interval: u64,
+        interval_secs: i64,
+        next_execution_ts: i64,
+        budget_lamports: u64,
+        tip_lamports: u64,
+    ) {
+        self.user = user;
+        self.amount_per_interval = amount_per_interval;
+        self.interval_secs = interval_secs;
+        self.next_execution_ts = next_execution_ts;
+        self.budget_lamports = budget_lamports;
+        self.tip_lamports = tip_lamports;
+    }
+}
+
+impl AccountHeader for DcaSchedule {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::DcaSchedule;
+    const VERSION: u8 = 1;
+}
+
+/// A validator's vote account figures, copied by `CrankValidatorHealth` so
+/// frontends can show validator quality without querying the validator.
+#[repr(C, packed)]
+pub struct ValidatorHealth {
+    /// `AccountDiscriminator::ValidatorHealth`.
+    pub discriminator: u8,
+    /// Layout version, `ValidatorHealth::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub vote_account: [u8; 32],
+    pub commission: u8,
+    /// Slot of the validator's most recent vote. Zero if it has not voted.
+    pub last_vote_slot: u64,
+    /// Its latest rooted slot. Zero if it has none.
+    pub root_slot: u64,
+    /// Most recent epoch the validator earned credits in.
+    pub last_epoch: u64,
+    /// Credits earned in `last_epoch`.
+    pub last_epoch_credits: u64,
+    /// Epochs, up to `HEALTH_WINDOW_EPOCHS`, summed in `window_credits`.
+    pub window_epochs: u8,
+    /// Credits earned over the last `window_epochs` epochs with credits.
+    pub window_credits: u64,
+    /// Slot of the refresh that wrote these figures.
+    pub refreshed_slot: u64,
+}
+
+impl ValidatorHealth {
+    pub const LEN: usize = 1 + 1 + 32 + 1 + 8 + 8 + 8 + 8 + 1 + 8 + 8;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != ValidatorHealth::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != ValidatorHealth::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+}
+
+impl AccountHeader for ValidatorHealth {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::ValidatorHealth;
+    const VERSION: u8 = 1;
+}
+
+/// Epochs of vote credits `ValidatorHealth` sums up.
+pub const HEALTH_WINDOW_EPOCHS: usize = 5;
+
+/// LST locked by its owner to be unstaked once it fetches at least
+/// `min_lamports_per_lst`. Keepers fill it with `FillUnstakeOrder`; the PDA
+/// escrows their tip on top of its own rent.
+#[repr(C, packed)]
+pub struct UnstakeOrder {
+    /// `AccountDiscriminator::UnstakeOrder`.
+    pub discriminator: u8,
+    /// Layout version, `UnstakeOrder::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub owner: [u8; 32],
+    /// The order PDA's LST ATA holding the locked LST.
+    pub vault: [u8; 32],
+    pub lst_amount: u64,
+    /// Lamports the owner accepts at least per `LAMPORTS_PER_SOL` LST, after
+    /// any fee.
+    pub min_lamports_per_lst: u64,
+    /// Lamports paid to the keeper that fills the order.
+    pub tip_lamports: u64,
+}
+
+impl UnstakeOrder {
+    pub const LEN: usize = 1 + 1 + 32 + 32 + 8 + 8 + 8;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != UnstakeOrder::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != UnstakeOrder::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        owner: Pubkey,
+        vault: Pubkey,
+        lst_amount: u64,
+        min_lamports_per_lst: u64,
+        tip_lamports: u64,
+    ) {
+        self.owner = owner;
+        self.vault = vault;
+        self.lst_amount = lst_amount;
+        self.min_lamports_per_lst = min_lamports_per_lst;
+        self.tip_lamports = tip_lamports;
+    }
+}
+
+impl AccountHeader for UnstakeOrder {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::UnstakeOrder;
+    const VERSION: u8 = 1;
+}
+
+/// Shortest lock `LockLst` accepts, in seconds.
+pub const VE_MIN_LOCK_SECONDS: i64 = 7 * 86_400;
+/// Longest lock `LockLst` accepts, in seconds. A lock this long gets one unit
+/// of voting weight per LST.
+pub const VE_MAX_LOCK_SECONDS: i64 = 4 * 365 * 86_400;
+
+/// LST locked by its owner until `unlock_ts` for governance voting weight.
+/// The PDA is keyed by owner only, so the weight cannot be transferred.
+#[repr(C, packed)]
+pub struct VoteEscrow {
+    /// `AccountDiscriminator::VoteEscrow`.
+    pub discriminator: u8,
+    /// Layout version, `VoteEscrow::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub owner: [u8; 32],
+    /// The escrow PDA's LST ATA holding the locked LST.
+    pub vault: [u8; 32],
+    pub locked_amount: u64,
+    /// Unix timestamp from which `UnlockLst` releases the LST.
+    pub unlock_ts: i64,
+    /// Weight of the lock, fixed by the last `LockLst` from the time left
+    /// until `unlock_ts`.
+    pub voting_weight: u64,
+    /// Unix timestamp of the last `LockLst`.
+    pub locked_at: i64,
+}
+
+impl VoteEscrow {
+    pub const LEN: usize = 1 + 1 + 32 + 32 + 8 + 8 + 8 + 8;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != VoteEscrow::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != VoteEscrow::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        owner: Pubkey,
+        vault: Pubkey,
+        locked_amount: u64,
+        unlock_ts: i64,
+        voting_weight: u64,
+        locked_at: i64,
+    ) {
+        self.owner = owner;
+        self.vault = vault;
+        self.locked_amount = locked_amount;
+        self.unlock_ts = unlock_ts;
+        self.voting_weight = voting_weight;
+        self.locked_at = locked_at;
+    }
+}
+
+impl AccountHeader for VoteEscrow {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::VoteEscrow;
+    const VERSION: u8 = 1;
+}
+
+/// Time between `ProposeSplitHandoff` and the earliest `ExecuteSplitHandoff`,
+/// in seconds. The split's owner can cancel the handoff until then.
+pub const SPLIT_HANDOFF_DELAY_SECONDS: i64 = 7 * 86_400;
+
+/// Pending reassignment of a split record to a new owner, at the PDA
+/// `b"split_handoff"` + split account.
+#[repr(C, packed)]
+pub struct SplitHandoff {
+    /// `AccountDiscriminator::SplitHandoff`.
+    pub discriminator: u8,
+    /// Layout version, `SplitHandoff::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub split_account: [u8; 32],
+    pub new_owner: [u8; 32],
+    /// Admin that proposed the handoff and paid its rent.
+    pub proposer: [u8; 32],
+    /// Unix timestamp from which `ExecuteSplitHandoff` may run.
+    pub executable_at: i64,
+}
+
+impl SplitHandoff {
+    pub const LEN: usize = 1 + 1 + 32 + 32 + 32 + 8;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != SplitHandoff::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != SplitHandoff::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        split_account: Pubkey,
+        new_owner: Pubkey,
+        proposer: Pubkey,
+        executable_at: i64,
+    ) {
+        self.split_account = split_account;
+        self.new_owner = new_owner;
+        self.proposer = proposer;
+        self.executable_at = executable_at;
+    }
+}
+
+impl AccountHeader for SplitHandoff {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::SplitHandoff;
+    const VERSION: u8 = 1;
+}
+
+/// One admin parameter change. Values use the layout of the setter's
+/// instruction data, zero-padded to 32 bytes.
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct ParamChange {
+    /// `DISCRIMINATOR` of the setter that made the change.
+    pub parameter: u8,
+    pub old_value: [u8; 32],
+    pub new_value: [u8; 32],
+    pub slot: u64,
+    pub authority: [u8; 32],
+}
+
+impl ParamChange {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 32;
+}
+
+/// Bounded history of admin parameter changes, so governance can be
+/// reconstructed without an indexer.
+#[repr(C, packed)]
+pub struct Changelog {
+    /// `AccountDiscriminator::Changelog`.
+    pub discriminator: u8,
+    /// Layout version, `Changelog::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub head: u64,
+    pub len: u64,
+    pub changes: [ParamChange; Changelog::CAPACITY],
+}
+
+impl Changelog {
+    pub const CAPACITY: usize = 64;
+    pub const LEN: usize = 1 + 1 + 8 + 8 + ParamChange::LEN * Changelog::CAPACITY;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != Changelog::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != Changelog::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    /// Appends a change, overwriting the oldest one once the buffer is full.
+    #[inline(always)]
+    pub fn push(&mut self, change: ParamChange) {
+        let head = self.head as usize % Self::CAPACITY;
+        self.changes[head] = change;
+        self.head = ((head + 1) % Self::CAPACITY) as u64;
+        if (self.len as usize) < Self::CAPACITY {
+            self.len += 1;
+        }
+    }
+
+    /// Returns the change `index` positions back from the most recent one.
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> Option<ParamChange> {
+        if index >= self.len as usize {
+            return None;
+        }
+        let position = (self.head as usize + Self::CAPACITY - 1 - index) % Self::CAPACITY;
+        Some(self.changes[position])
+    }
+}
+
+impl AccountHeader for Changelog {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::Changelog;
+    const VERSION: u8 = 1;
+}
+
+/// An import of an SPL stake pool (`b"migration"`), see `OpenMigration`.
+#[repr(C, packed)]
+pub struct Migration {
+    /// `AccountDiscriminator::Migration`.
+    pub discriminator: u8,
+    /// Layout version, `Migration::VERSION` for accounts this program reads.
+    pub version: u8,
+    /// Pool token supply of the source pool, which the imported balances
+    /// add up to.
+    pub source_pool_tokens: u64,
+    /// Pool tokens of the balances imported so far.
+    pub imported_pool_tokens: u64,
+    /// Lamports the imported stake accounts added to the pool.
+    pub imported_lamports: u64,
+    /// LST minted into the migration vault for `imported_lamports`.
+    pub minted_lst: u64,
+    /// Set by the first imported balance. No stake is imported after it, so
+    /// every balance is paid from the same `minted_lst`.
+    pub sealed: u8,
+}
+
+impl Migration {
+    pub const LEN: usize = 1 + 1 + 8 + 8 + 8 + 8 + 1;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != Migration::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != Migration::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    /// LST owed for `pool_tokens` of the source pool. The balance that
+    /// completes the import takes whatever is left in `vault_balance`, so
+    /// rounding dust does not stay behind in the vault.
+    #[inline(always)]
+    pub fn lst_for(&self, pool_tokens: u64, vault_balance: u64) -> Result<u64, ProgramError> {
+        let imported = self
+            .imported_pool_tokens
+            .checked_add(pool_tokens)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        if imported == self.source_pool_tokens {
+            return Ok(vault_balance);
+        }
+
+        Ok(
+            (pool_tokens as u128 * self.minted_lst as u128 / self.source_pool_tokens as u128)
+                as u64,
+        )
+    }
+}
+
+impl AccountHeader for Migration {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::Migration;
+    const VERSION: u8 = 1;
+}
+
+/// Marks a depositor's balance as imported (`b"imported_balance"`,
+/// depositor), so the same balance cannot be paid out twice.
+#[repr(C, packed)]
+pub struct ImportedBalance {
+    /// `AccountDiscriminator::ImportedBalance`.
+    pub discriminator: u8,
+    /// Layout version, `ImportedBalance::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub pool_tokens: u64,
+    pub lst_amount: u64,
+}
+
+impl ImportedBalance {
+    pub const LEN: usize = 1 + 1 + 8 + 8;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != ImportedBalance::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != ImportedBalance::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+}
+
+impl AccountHeader for ImportedBalance {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::ImportedBalance;
+    const VERSION: u8 = 1;
+}
+
+/// Fixed-point scale of `RewardPayout::rewards_per_share`.
+pub const REWARDS_PER_SHARE_SCALE: u128 = 1_000_000_000_000;
+
+/// The SOL payout pool (`b"reward_payout"`). Holders who want cash-flow yield
+/// enroll LST in its vault, the PDA's LST ATA, for shares. Each epoch
+/// `CrankRewardPayout` burns `reward_payout_bps` of the rewards earned on
+/// the vault's LST, pays their lamports from the reserve into this PDA and
+/// adds them to `rewards_per_share`. The PDA holds its rent plus
+/// `unclaimed_lamports`.
+#[repr(C, packed)]
+pub struct RewardPayout {
+    /// `AccountDiscriminator::RewardPayout`.
+    pub discriminator: u8,
+    /// Layout version, `RewardPayout::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub total_shares: u64,
+    /// Lamports paid out per share since the PDA was created, scaled by
+    /// `REWARDS_PER_SHARE_SCALE`.
+    pub rewards_per_share: u128,
+    /// Lamports paid out and not yet claimed.
+    pub unclaimed_lamports: u64,
+    /// Pool lamports and LST supply at the last crank, whose rate the next
+    /// crank measures the vault's rewards from.
+    pub checkpoint_lamports: u64,
+    pub checkpoint_lst_supply: u64,
+    pub last_epoch: u64,
+}
+
+impl RewardPayout {
+    pub const LEN: usize = 1 + 1 + 8 + 16 + 8 + 8 + 8 + 8;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != RewardPayout::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != RewardPayout::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    /// Shares for enrolling `lst_amount` into a vault holding
+    /// `vault_balance`. An empty vault gives one share per LST. Rounds down.
+    #[inline(always)]
+    pub fn shares_for(&self, lst_amount: u64, vault_balance: u64) -> u64 {
+        if self.total_shares == 0 || vault_balance == 0 {
+            return lst_amount;
+        }
+        (lst_amount as u128 * self.total_shares as u128 / vault_balance as u128) as u64
+    }
+
+    /// LST in the vault behind `shares`. Rounds down.
+    #[inline(always)]
+    pub fn lst_for(&self, shares: u64, vault_balance: u64) -> u64 {
+        if self.total_shares == 0 {
+            return 0;
+        }
+        (shares as u128 * vault_balance as u128 / self.total_shares as u128) as u64
+    }
+
+    /// Fails with `PayoutNotCranked` while rewards earned since the last
+    /// crank are unpaid, so shares cannot be bought or sold around them.
+    #[inline(always)]
+    pub fn check_cranked(&self, epoch: u64) -> Result<(), ProgramError> {
+        if self.checkpoint_lst_supply != 0 && self.last_epoch < epoch {
+            return Err(PinocchioError::PayoutNotCranked.into());
+        }
+        Ok(())
+    }
+
+    /// Credits `lamports` paid into the PDA to every share.
+    #[inline(always)]
+    pub fn distribute(&mut self, lamports: u64) -> Result<(), ProgramError> {
+        if self.total_shares == 0 {
+            return Ok(());
+        }
+        self.rewards_per_share = self
+            .rewards_per_share
+            .checked_add(lamports as u128 * REWARDS_PER_SHARE_SCALE / self.total_shares as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.unclaimed_lamports = self
+            .unclaimed_lamports
+            .checked_add(lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+impl AccountHeader for RewardPayout {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::RewardPayout;
+    const VERSION: u8 = 1;
+}
+
+/// A holder's enrollment in the SOL payout pool (`b"payout_position"`,
+/// owner).
+#[repr(C, packed)]
+pub struct PayoutPosition {
+    /// `AccountDiscriminator::PayoutPosition`.
+    pub discriminator: u8,
+    /// Layout version, `PayoutPosition::VERSION` for accounts this program reads.
+    pub version: u8,
+    pub owner: [u8; 32],
+    pub shares: u64,
+    /// `shares` times `rewards_per_share` at the last update, scaled down,
+    /// so only payouts after it accrue.
+    pub reward_debt: u128,
+    /// Lamports accrued and not yet claimed.
+    pub claimable_lamports: u64,
+}
+
+impl PayoutPosition {
+    pub const LEN: usize = 1 + 1 + 32 + 8 + 16 + 8;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != PayoutPosition::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != PayoutPosition::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_header(bytes)?;
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    #[inline(always)]
+    fn accrued(&self, rewards_per_share: u128) -> u128 {
+        self.shares as u128 * rewards_per_share / REWARDS_PER_SHARE_SCALE
+    }
+
+    /// Moves what the position earned since its last update into
+    /// `claimable_lamports`.
+    #[inline(always)]
+    pub fn settle(&mut self, rewards_per_share: u128) -> Result<(), ProgramError> {
+        let accrued = self.accrued(rewards_per_share);
+        let pending = u64::try_from(accrued.saturating_sub(self.reward_debt))
+            .map_err(|_| ProgramError::ArithmeticOverflow)?;
+        self.claimable_lamports = self
+            .claimable_lamports
+            .checked_add(pending)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.reward_debt = accrued;
+        Ok(())
+    }
+
+    /// Sets the position's shares after a `settle` at the same
+    /// `rewards_per_share`.
+    #[inline(always)]
+    pub fn set_shares(&mut self, shares: u64, rewards_per_share: u128) {
+        self.shares = shares;
+        self.reward_debt = self.accrued(rewards_per_share);
+    }
+}
+
+impl AccountHeader for PayoutPosition {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::PayoutPosition;
+    const VERSION: u8 = 1;
 }
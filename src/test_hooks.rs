@@ -0,0 +1,30 @@
+//! Simulation-only failure injection, compiled with the `test-hooks` feature
+//! for litesvm builds.
+//!
+//! `StakeStateV2` serializes to 197 bytes, leaving the tail of a 200-byte
+//! stake account unused by the stake program. Tests write a hook marker into
+//! that tail with `LiteSVM::set_account` to force code paths that would
+//! otherwise need multi-epoch warps.
+
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::instructions::helpers::DEFAULT_STAKE_ACCOUNT_SPACE;
+
+/// Offset of the hook marker byte in a stake account.
+pub const HOOK_MARKER_OFFSET: usize = DEFAULT_STAKE_ACCOUNT_SPACE - 1;
+
+/// Marker that makes a stake account report as activating.
+pub const FORCE_ACTIVATING: u8 = 1;
+
+/// Marker that makes a stake account report as fully active.
+pub const FORCE_ACTIVE: u8 = 2;
+
+/// Returns the activation status forced on `account`, if any.
+pub fn forced_activating(account: &AccountInfo) -> Result<Option<bool>, ProgramError> {
+    let data = account.try_borrow_data()?;
+    Ok(match data.get(HOOK_MARKER_OFFSET) {
+        Some(&FORCE_ACTIVATING) => Some(true),
+        Some(&FORCE_ACTIVE) => Some(false),
+        _ => None,
+    })
+}
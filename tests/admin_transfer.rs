@@ -0,0 +1,128 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_accept_admin_ix, build_propose_admin_ix, build_set_main_headroom_ix, run_initialize,
+        send, setup_svm,
+    };
+
+    fn admin_and_pending_admin(svm: &LiteSVM, config_pda: &Pubkey) -> (Pubkey, Pubkey) {
+        let config = svm.get_account(config_pda).unwrap().data;
+        (
+            Pubkey::try_from(&config[2..34]).unwrap(),
+            Pubkey::try_from(&config[333..365]).unwrap(),
+        )
+    }
+
+    fn funded_keypair(svm: &mut LiteSVM) -> Keypair {
+        let keypair = Keypair::new();
+        svm.airdrop(&keypair.pubkey(), 1_000_000_000).unwrap();
+        keypair
+    }
+
+    #[test]
+    fn test_admin_transfer_takes_effect_once_accepted() {
+        let mut svm = setup_svm();
+        let (admin, _, _, config_pda, _, _, _) = run_initialize(&mut svm);
+        let new_admin = funded_keypair(&mut svm);
+        let outsider = funded_keypair(&mut svm);
+
+        assert!(
+            !send(
+                &mut svm,
+                build_accept_admin_ix(&new_admin.pubkey(), &config_pda),
+                &new_admin
+            ),
+            "Nothing is pending yet"
+        );
+        assert!(
+            !send(
+                &mut svm,
+                build_propose_admin_ix(&outsider.pubkey(), &config_pda, &outsider.pubkey()),
+                &outsider,
+            ),
+            "Only the admin may propose"
+        );
+
+        assert!(send(
+            &mut svm,
+            build_propose_admin_ix(&admin.pubkey(), &config_pda, &new_admin.pubkey()),
+            &admin,
+        ));
+        assert_eq!(
+            admin_and_pending_admin(&svm, &config_pda),
+            (admin.pubkey(), new_admin.pubkey())
+        );
+        assert!(
+            send(
+                &mut svm,
+                build_set_main_headroom_ix(&admin.pubkey(), &config_pda, 1),
+                &admin,
+            ),
+            "The admin keeps its rights until the transfer is accepted"
+        );
+        assert!(
+            !send(
+                &mut svm,
+                build_accept_admin_ix(&outsider.pubkey(), &config_pda),
+                &outsider
+            ),
+            "Only the pending admin may accept"
+        );
+
+        assert!(send(
+            &mut svm,
+            build_accept_admin_ix(&new_admin.pubkey(), &config_pda),
+            &new_admin,
+        ));
+        assert_eq!(
+            admin_and_pending_admin(&svm, &config_pda),
+            (new_admin.pubkey(), Pubkey::default())
+        );
+        assert!(!send(
+            &mut svm,
+            build_set_main_headroom_ix(&admin.pubkey(), &config_pda, 2),
+            &admin,
+        ));
+        assert!(send(
+            &mut svm,
+            build_set_main_headroom_ix(&new_admin.pubkey(), &config_pda, 2),
+            &new_admin,
+        ));
+    }
+
+    #[test]
+    fn test_proposing_zero_cancels_the_transfer() {
+        let mut svm = setup_svm();
+        let (admin, _, _, config_pda, _, _, _) = run_initialize(&mut svm);
+        let new_admin = funded_keypair(&mut svm);
+
+        assert!(send(
+            &mut svm,
+            build_propose_admin_ix(&admin.pubkey(), &config_pda, &new_admin.pubkey()),
+            &admin,
+        ));
+        assert!(send(
+            &mut svm,
+            build_propose_admin_ix(&admin.pubkey(), &config_pda, &Pubkey::default()),
+            &admin,
+        ));
+
+        assert!(!send(
+            &mut svm,
+            build_accept_admin_ix(&new_admin.pubkey(), &config_pda),
+            &new_admin,
+        ));
+        assert_eq!(
+            admin_and_pending_admin(&svm, &config_pda),
+            (admin.pubkey(), Pubkey::default())
+        );
+    }
+}
@@ -0,0 +1,131 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
+    use solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_adopt_reserve_ix, build_crank_initialize_reserve_ix, build_get_pending_cranks_ix,
+        decode_pending_cranks, run_crank_initialize_reserve, run_crank_merge_reserve, run_deposit,
+        run_initialize, send, setup_svm, PROGRAM_ID,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    fn pda(seed: &[u8]) -> Pubkey {
+        Pubkey::find_program_address(&[seed], &PROGRAM_ID).0
+    }
+
+    #[test]
+    fn test_initialize_adopts_prefunded_accounts() {
+        let mut svm = setup_svm();
+        for seed in [
+            b"stake_reserve".as_slice(),
+            b"stake_main",
+            b"config",
+            b"pool_state",
+        ] {
+            svm.airdrop(&pda(seed), LAMPORTS_PER_SOL).unwrap();
+        }
+
+        let (_, _, _, _, _, stake_account_reserve, _) = run_initialize(&mut svm);
+
+        let reserve = svm.get_account(&stake_account_reserve).unwrap();
+        assert_eq!(reserve.owner, Pubkey::from(STAKE_PROGRAM_ID));
+        assert!(!reserve.data.is_empty());
+    }
+
+    #[test]
+    fn test_adopts_drained_reserve_after_merge() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = run_initialize(&mut svm);
+        run_crank_initialize_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_reserve,
+            &vote_pubkey,
+        );
+        run_crank_merge_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+
+        // The merge closed the reserve, so the deposit lands on a plain
+        // system account.
+        run_deposit(
+            &mut svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            2 * LAMPORTS_PER_SOL,
+        );
+        let deposited = svm.get_account(&stake_account_reserve).unwrap();
+        assert!(deposited.data.is_empty());
+
+        let crank_ix = build_crank_initialize_reserve_ix(
+            &config_pda,
+            &stake_account_reserve,
+            &vote_pubkey,
+            &solana_program::example_mocks::solana_sdk::system_program::ID,
+            &Pubkey::from(STAKE_PROGRAM_ID),
+        );
+        assert!(
+            !send(&mut svm, crank_ix.clone(), &initializer),
+            "The crank should not initialize an address with no stake account"
+        );
+
+        let pending_ix =
+            build_get_pending_cranks_ix(&config_pda, &stake_account_main, &stake_account_reserve);
+        let tx = Transaction::new_signed_with_payer(
+            &[pending_ix],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+        let pending =
+            decode_pending_cranks(&svm.simulate_transaction(tx).unwrap().meta.return_data.data);
+        assert_eq!(pending.first(), Some(&51));
+
+        let keeper = Keypair::new();
+        svm.airdrop(&keeper.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        let adopt_ix = build_adopt_reserve_ix(
+            &keeper.pubkey(),
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+        assert!(send(&mut svm, adopt_ix.clone(), &keeper));
+
+        let adopted = svm.get_account(&stake_account_reserve).unwrap();
+        assert_eq!(adopted.owner, Pubkey::from(STAKE_PROGRAM_ID));
+        assert_eq!(
+            adopted.lamports - svm.minimum_balance_for_rent_exemption(adopted.data.len()),
+            deposited.lamports,
+            "The keeper should pay the rent, leaving the pool's lamports unchanged"
+        );
+
+        assert!(
+            !send(&mut svm, adopt_ix, &keeper),
+            "An adopted reserve should not be adopted twice"
+        );
+        assert!(send(&mut svm, crank_ix, &initializer));
+    }
+}
@@ -0,0 +1,84 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+
+    use crate::test_helpers::test_helpers::{
+        fee_vault_pda, get_balance_sheet, get_mint_supply, run_deposit, run_initialize, setup_svm,
+    };
+
+    #[test]
+    fn test_balance_sheet_balances_assets_against_liabilities() {
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+
+        run_deposit(
+            &mut svm,
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+            3_000_000_000,
+        );
+        svm.airdrop(&fee_vault_pda(), 1_000_000_000).unwrap();
+
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+        let [active, activating, deactivating, undelegated, buffer, lst_supply, liabilities, fees] =
+            get_balance_sheet(
+                &mut svm,
+                &payer,
+                &config_pda,
+                &mint,
+                &stake_account_main,
+                &stake_account_reserve,
+            )
+            .expect("GetBalanceSheet should succeed");
+
+        assert!(activating > 0, "Main is still warming up");
+        assert_eq!(active, 0);
+        assert_eq!(deactivating, 0);
+        assert_eq!(
+            undelegated, 3_000_000_000,
+            "The deposit sits undelegated in the reserve"
+        );
+        assert_eq!(buffer, 0);
+        assert_eq!(lst_supply, get_mint_supply(&svm, &mint));
+        assert_eq!(
+            active + activating + deactivating + undelegated,
+            liabilities,
+            "Assets should exactly cover the LST supply at the current rate"
+        );
+        assert_eq!(fees, 1_000_000_000);
+    }
+
+    #[test]
+    fn test_balance_sheet_rejects_wrong_mint() {
+        let mut svm = setup_svm();
+        let (_, _, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+        assert!(get_balance_sheet(
+            &mut svm,
+            &payer,
+            &config_pda,
+            &Keypair::new().pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+        )
+        .is_none());
+    }
+}
@@ -0,0 +1,64 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_liquid_staking::build_info::VERSION;
+    use solana_sdk::{
+        instruction::Instruction,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_get_build_info_ix, decode_build_info, setup_svm,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    fn simulate(svm: &mut LiteSVM, ix: Instruction) -> Option<Vec<u8>> {
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        );
+        svm.simulate_transaction(tx)
+            .ok()
+            .map(|result| result.meta.return_data.data)
+    }
+
+    #[test]
+    fn test_returns_build_metadata() {
+        let mut svm = setup_svm();
+
+        let data = simulate(&mut svm, build_get_build_info_ix()).unwrap();
+        let fields = decode_build_info(&data);
+        let [version, commit, _features, toolchain] = fields.as_slice() else {
+            panic!("expected four fields, got {fields:?}");
+        };
+
+        assert_eq!(version, VERSION);
+        let hash = commit.strip_suffix("-dirty").unwrap_or(commit);
+        assert!(
+            commit == "unknown"
+                || (hash.len() == 40 && hash.chars().all(|c| c.is_ascii_hexdigit())),
+            "unexpected commit {commit}"
+        );
+        assert!(
+            toolchain.starts_with("rustc "),
+            "unexpected toolchain {toolchain}"
+        );
+    }
+
+    #[test]
+    fn test_rejects_instruction_data() {
+        let mut svm = setup_svm();
+
+        let mut ix = build_get_build_info_ix();
+        ix.data.push(0);
+        assert!(simulate(&mut svm, ix).is_none());
+    }
+}
@@ -0,0 +1,89 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_liquid_staking::events::EVENT_DONATE;
+    use solana_sdk::{signature::Signer, transaction::Transaction};
+
+    use crate::test_helpers::test_helpers::{
+        build_burn_and_donate_ix, decode_events, get_mint_supply, get_token_balance,
+        print_transaction_logs, run_deposit, run_initialize, setup_svm,
+    };
+
+    #[test]
+    fn test_burn_and_donate_raises_rate() {
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+
+        let (donor, donor_ata) = run_deposit(
+            &mut svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            2_000_000_000,
+        );
+        let (_holder, holder_ata) = run_deposit(
+            &mut svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            2_000_000_000,
+        );
+
+        let pool_lamports = svm.get_balance(&stake_account_main).unwrap()
+            + svm.get_balance(&stake_account_reserve).unwrap();
+        let supply_before = get_mint_supply(&svm, &token_mint.pubkey());
+        let holder_lst = get_token_balance(&svm, &holder_ata);
+        let donated = get_token_balance(&svm, &donor_ata) / 2;
+
+        let ix = build_burn_and_donate_ix(
+            &donor.pubkey(),
+            &donor_ata,
+            &token_mint.pubkey(),
+            &config_pda,
+            donated,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&donor.pubkey()),
+            &[&donor],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        let meta = result.expect("BurnAndDonate should succeed");
+
+        let supply_after = get_mint_supply(&svm, &token_mint.pubkey());
+        assert_eq!(supply_after, supply_before - donated);
+        assert_eq!(
+            svm.get_balance(&stake_account_main).unwrap()
+                + svm.get_balance(&stake_account_reserve).unwrap(),
+            pool_lamports,
+            "No SOL should leave the pool"
+        );
+
+        let claim_before = holder_lst as u128 * pool_lamports as u128 / supply_before as u128;
+        let claim_after = holder_lst as u128 * pool_lamports as u128 / supply_after as u128;
+        assert!(claim_after > claim_before);
+
+        let events = decode_events(&meta.logs);
+        assert_eq!(events.len(), 1);
+        let (kind, _sequence, payload) = &events[0];
+        assert_eq!(*kind, EVENT_DONATE);
+        assert_eq!(&payload[0..32], donor.pubkey().as_ref());
+        assert_eq!(
+            u64::from_le_bytes(payload[32..40].try_into().unwrap()),
+            donated
+        );
+    }
+}
@@ -0,0 +1,92 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::signature::{Keypair, Signer};
+
+    use crate::test_helpers::test_helpers::{
+        build_buyback_ix, build_set_buyback_policy_ix, create_and_fund_ata, fee_vault_pda,
+        get_mint_supply, get_token_balance, run_initialize, send, setup_svm,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+    const BUYBACK_BURN: u8 = 1;
+
+    #[test]
+    fn test_buyback_to_treasury_then_burn() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+        let treasury = create_and_fund_ata(&mut svm, &config_pda, &mint, 0);
+
+        let keeper = Keypair::new();
+        svm.airdrop(&keeper.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        let buyback_ix = build_buyback_ix(
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+
+        assert!(
+            !send(&mut svm, buyback_ix.clone(), &keeper),
+            "An empty fee vault should have nothing to buy back"
+        );
+
+        svm.airdrop(&fee_vault_pda(), 2 * LAMPORTS_PER_SOL).unwrap();
+        let supply_before = get_mint_supply(&svm, &mint);
+        let reserve_before = svm.get_account(&stake_account_reserve).unwrap().lamports;
+
+        assert!(send(&mut svm, buyback_ix.clone(), &keeper));
+
+        let bought = get_token_balance(&svm, &treasury);
+        assert!(bought > 0, "Treasury should receive the bought LST");
+        assert_eq!(get_mint_supply(&svm, &mint), supply_before + bought);
+        assert_eq!(
+            svm.get_account(&stake_account_reserve).unwrap().lamports,
+            reserve_before + 2 * LAMPORTS_PER_SOL
+        );
+        assert!(svm
+            .get_account(&fee_vault_pda())
+            .is_none_or(|account| account.lamports == 0));
+
+        assert!(
+            !send(
+                &mut svm,
+                build_set_buyback_policy_ix(&keeper.pubkey(), &config_pda, BUYBACK_BURN),
+                &keeper
+            ),
+            "Only the admin may set the buyback policy"
+        );
+        assert!(send(
+            &mut svm,
+            build_set_buyback_policy_ix(&initializer.pubkey(), &config_pda, BUYBACK_BURN),
+            &initializer
+        ));
+
+        svm.airdrop(&fee_vault_pda(), LAMPORTS_PER_SOL).unwrap();
+        let supply_before = get_mint_supply(&svm, &mint);
+        let reserve_before = svm.get_account(&stake_account_reserve).unwrap().lamports;
+
+        assert!(send(&mut svm, buyback_ix, &keeper));
+
+        assert_eq!(
+            get_mint_supply(&svm, &mint),
+            supply_before,
+            "Burn policy should mint nothing"
+        );
+        assert_eq!(get_token_balance(&svm, &treasury), bought);
+        assert_eq!(
+            svm.get_account(&stake_account_reserve).unwrap().lamports,
+            reserve_before + LAMPORTS_PER_SOL
+        );
+    }
+}
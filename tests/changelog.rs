@@ -0,0 +1,121 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_liquid_staking::state::{Changelog, ParamChange};
+    use solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_set_deposit_cap_ix, build_set_pool_status_ix, build_set_wrapper_program_ix,
+        get_changelog, run_initialize, send, setup_svm,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    fn value(fields: &[&[u8]]) -> [u8; 32] {
+        let mut value = [0u8; 32];
+        let bytes = fields.concat();
+        value[..bytes.len()].copy_from_slice(&bytes);
+        value
+    }
+
+    #[test]
+    fn test_setters_record_old_and_new_values() {
+        let mut svm = setup_svm();
+        let (admin, _, _, config_pda, _, _, _) = run_initialize(&mut svm);
+        assert!(get_changelog(&svm).is_empty());
+
+        let wrapper_program = Pubkey::new_unique();
+        assert!(send(
+            &mut svm,
+            build_set_deposit_cap_ix(&admin.pubkey(), &config_pda, 5 * LAMPORTS_PER_SOL, 1),
+            &admin,
+        ));
+        assert!(send(
+            &mut svm,
+            build_set_deposit_cap_ix(&admin.pubkey(), &config_pda, 7 * LAMPORTS_PER_SOL, 0),
+            &admin,
+        ));
+        assert!(send(
+            &mut svm,
+            build_set_wrapper_program_ix(&admin.pubkey(), &config_pda, &wrapper_program),
+            &admin,
+        ));
+
+        let changes = get_changelog(&svm);
+        assert_eq!(changes.len(), 3);
+
+        let ParamChange {
+            parameter,
+            old_value,
+            new_value,
+            authority,
+            ..
+        } = changes[1];
+        assert_eq!(parameter, 43);
+        assert_eq!(
+            old_value,
+            value(&[&(5 * LAMPORTS_PER_SOL).to_le_bytes(), &[1]])
+        );
+        assert_eq!(
+            new_value,
+            value(&[&(7 * LAMPORTS_PER_SOL).to_le_bytes(), &[0]])
+        );
+        assert_eq!(authority, admin.pubkey().to_bytes());
+
+        let ParamChange {
+            parameter,
+            old_value,
+            new_value,
+            ..
+        } = changes[2];
+        assert_eq!(parameter, 47);
+        assert_eq!(old_value, [0u8; 32]);
+        assert_eq!(new_value, wrapper_program.to_bytes());
+    }
+
+    #[test]
+    fn test_rejected_change_is_not_recorded() {
+        let mut svm = setup_svm();
+        let (_, _, _, config_pda, _, _, _) = run_initialize(&mut svm);
+
+        let outsider = Keypair::new();
+        svm.airdrop(&outsider.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        assert!(!send(
+            &mut svm,
+            build_set_pool_status_ix(&outsider.pubkey(), &config_pda, 2),
+            &outsider,
+        ));
+
+        assert!(get_changelog(&svm).is_empty());
+    }
+
+    #[test]
+    fn test_history_keeps_the_latest_changes() {
+        let mut svm = setup_svm();
+        let (admin, _, _, config_pda, _, _, _) = run_initialize(&mut svm);
+
+        let total = Changelog::CAPACITY as u64 + 3;
+        for cap in 1..=total {
+            assert!(send(
+                &mut svm,
+                build_set_deposit_cap_ix(&admin.pubkey(), &config_pda, cap, 0),
+                &admin,
+            ));
+        }
+
+        let changes = get_changelog(&svm);
+        assert_eq!(changes.len(), Changelog::CAPACITY);
+        assert_eq!(
+            { changes[0].new_value },
+            value(&[&4u64.to_le_bytes(), &[0]])
+        );
+        assert_eq!(
+            { changes[Changelog::CAPACITY - 1].new_value },
+            value(&[&total.to_le_bytes(), &[0]])
+        );
+    }
+}
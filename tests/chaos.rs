@@ -0,0 +1,300 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        sysvar::clock::Clock,
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_crank_initialize_reserve_ix, build_crank_merge_reserve_ix, build_crank_split_ix,
+        build_deposit_ix, build_liquid_unstake_ix, build_withdraw_ix, get_balance_sheet,
+        get_mint_supply, get_token_balance, send, setup_pool, setup_svm, warp_epoch, Pool,
+        PoolSetup,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+    const USERS: usize = 4;
+    const STEPS: usize = 60;
+    const SEEDS: [u64; 4] = [1, 7, 42, 0x5eed];
+
+    /// xorshift64*, so a failing seed replays the same run without pulling in
+    /// a randomness crate.
+    struct Rng(u64);
+
+    impl Rng {
+        fn new(seed: u64) -> Self {
+            Self(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+        }
+
+        fn below(&mut self, n: u64) -> u64 {
+            self.next_u64() % n
+        }
+    }
+
+    #[derive(Clone, Copy, Debug)]
+    enum Action {
+        Deposit,
+        CrankInitializeReserve,
+        CrankMergeReserve,
+        CrankSplit,
+        Withdraw,
+        LiquidUnstake,
+        WarpEpoch,
+    }
+
+    const ACTIONS: [Action; 7] = [
+        Action::Deposit,
+        Action::CrankInitializeReserve,
+        Action::CrankMergeReserve,
+        Action::CrankSplit,
+        Action::Withdraw,
+        Action::LiquidUnstake,
+        Action::WarpEpoch,
+    ];
+
+    /// Who holds LST, and the splits awaiting withdrawal.
+    struct Activity {
+        /// Every token account that may hold LST.
+        holders: Vec<Pubkey>,
+        users: Vec<(Keypair, Pubkey)>,
+        /// Split accounts not yet withdrawn, as (user index, account, nonce).
+        pending_splits: Vec<(usize, Pubkey, u64)>,
+        next_nonce: u64,
+    }
+
+    /// Initializes the pool, funds the liquidity buffer with 20 SOL and
+    /// gives each user an initial 3 SOL deposit.
+    fn setup(svm: &mut LiteSVM) -> (Pool, Activity) {
+        let pool = setup_pool(
+            svm,
+            PoolSetup {
+                liquidity: 20 * LAMPORTS_PER_SOL,
+                ..PoolSetup::default()
+            },
+        );
+
+        let users: Vec<(Keypair, Pubkey)> = (0..USERS)
+            .map(|_| {
+                let (user, user_ata) = pool.deposit(svm, 3 * LAMPORTS_PER_SOL);
+                svm.airdrop(&user.pubkey(), 100 * LAMPORTS_PER_SOL).unwrap();
+                svm.expire_blockhash();
+                (user, user_ata)
+            })
+            .collect();
+
+        let mut holders = vec![pool.admin_ata, pool.lst_vault];
+        holders.extend(users.iter().map(|(_, user_ata)| *user_ata));
+
+        let activity = Activity {
+            holders,
+            users,
+            pending_splits: Vec::new(),
+            next_nonce: 0,
+        };
+        (pool, activity)
+    }
+
+    /// Runs `action` with random parameters. Returns whether it succeeded;
+    /// actions the pool's state does not allow yet are expected to fail.
+    fn run(
+        svm: &mut LiteSVM,
+        pool: &Pool,
+        activity: &mut Activity,
+        rng: &mut Rng,
+        action: Action,
+    ) -> bool {
+        let stake_program = Pubkey::from(STAKE_PROGRAM_ID);
+        let user_index = rng.below(USERS as u64) as usize;
+        let (user, user_ata) = &activity.users[user_index];
+
+        match action {
+            Action::Deposit => {
+                let lamports = LAMPORTS_PER_SOL + rng.below(3 * LAMPORTS_PER_SOL);
+                let ix = build_deposit_ix(
+                    &pool.config_pda,
+                    &user.pubkey(),
+                    user_ata,
+                    &pool.mint,
+                    &pool.stake_account_main,
+                    &pool.stake_account_reserve,
+                    lamports,
+                    true,
+                );
+                send(svm, ix, user)
+            }
+            Action::CrankInitializeReserve => {
+                let ix = build_crank_initialize_reserve_ix(
+                    &pool.config_pda,
+                    &pool.stake_account_reserve,
+                    &pool.vote_pubkey,
+                    &system_program::ID,
+                    &stake_program,
+                );
+                send(svm, ix, &pool.admin)
+            }
+            Action::CrankMergeReserve => {
+                let ix = build_crank_merge_reserve_ix(
+                    &pool.config_pda,
+                    &pool.stake_account_main,
+                    &pool.stake_account_reserve,
+                    &system_program::ID,
+                    &stake_program,
+                );
+                send(svm, ix, &pool.admin)
+            }
+            Action::CrankSplit => {
+                let lamports = LAMPORTS_PER_SOL + rng.below(LAMPORTS_PER_SOL);
+                let nonce = activity.next_nonce;
+                let (ix, split_account) = build_crank_split_ix(
+                    &user.pubkey(),
+                    user_ata,
+                    &pool.config_pda,
+                    &pool.stake_account_main,
+                    &pool.stake_account_reserve,
+                    &pool.mint,
+                    lamports,
+                    true,
+                    nonce,
+                );
+                let succeeded = send(svm, ix, user);
+                if succeeded {
+                    activity.next_nonce += 1;
+                    activity
+                        .pending_splits
+                        .push((user_index, split_account, nonce));
+                }
+                succeeded
+            }
+            Action::Withdraw => {
+                if activity.pending_splits.is_empty() {
+                    return false;
+                }
+                let index = rng.below(activity.pending_splits.len() as u64) as usize;
+                let (owner_index, split_account, nonce) = activity.pending_splits[index];
+                let owner = &activity.users[owner_index].0;
+                let ix = build_withdraw_ix(
+                    &split_account,
+                    &owner.pubkey(),
+                    &pool.config_pda,
+                    &stake_program,
+                    nonce,
+                    true,
+                );
+                let succeeded = send(svm, ix, owner);
+                if succeeded {
+                    activity.pending_splits.swap_remove(index);
+                }
+                succeeded
+            }
+            Action::LiquidUnstake => {
+                let balance = get_token_balance(svm, user_ata);
+                if balance == 0 {
+                    return false;
+                }
+                let ix = build_liquid_unstake_ix(
+                    &user.pubkey(),
+                    user_ata,
+                    &pool.config_pda,
+                    &pool.mint,
+                    &pool.stake_account_main,
+                    &pool.stake_account_reserve,
+                    1 + rng.below(balance / 2 + 1),
+                    0,
+                );
+                send(svm, ix, user)
+            }
+            Action::WarpEpoch => {
+                let epoch = svm.get_sysvar::<Clock>().epoch;
+                warp_epoch(svm, epoch + 1);
+                true
+            }
+        }
+    }
+
+    /// Returns the pool's (assets, LST supply) after checking that the mint
+    /// supply is exactly what the tracked holders own and that the assets
+    /// cover the liabilities.
+    fn check_invariants(
+        svm: &mut LiteSVM,
+        pool: &Pool,
+        activity: &Activity,
+        context: &str,
+    ) -> (u64, u64) {
+        let supply = get_mint_supply(svm, &pool.mint);
+        let held: u64 = activity
+            .holders
+            .iter()
+            .map(|holder| get_token_balance(svm, holder))
+            .sum();
+        assert_eq!(supply, held, "{context}: LST supply != LST held");
+
+        let lines = get_balance_sheet(
+            svm,
+            &pool.admin,
+            &pool.config_pda,
+            &pool.mint,
+            &pool.stake_account_main,
+            &pool.stake_account_reserve,
+        )
+        .unwrap_or_else(|| panic!("{context}: GetBalanceSheet failed"));
+        assert_eq!(lines[5], supply, "{context}: balance sheet supply");
+
+        let assets: u64 = lines[..4].iter().sum();
+        assert!(
+            assets >= lines[6],
+            "{context}: insolvent, assets {assets} < liabilities {}",
+            lines[6]
+        );
+
+        (assets, supply)
+    }
+
+    #[test]
+    fn test_random_interleavings_keep_the_pool_solvent() {
+        for seed in SEEDS {
+            let mut svm = setup_svm();
+            let (pool, mut activity) = setup(&mut svm);
+            let mut rng = Rng::new(seed);
+
+            let (mut assets, mut supply) = check_invariants(&mut svm, &pool, &activity, "setup");
+            let mut succeeded = 0;
+
+            for step in 0..STEPS {
+                let action = ACTIONS[rng.below(ACTIONS.len() as u64) as usize];
+                if run(&mut svm, &pool, &mut activity, &mut rng, action) {
+                    succeeded += 1;
+                }
+
+                let context = format!("seed {seed}, step {step}, {action:?}");
+                let (new_assets, new_supply) =
+                    check_invariants(&mut svm, &pool, &activity, &context);
+
+                // Conversions round toward the pool except the CrankSplit
+                // burn, which may leave the exchange rate lower by at most one
+                // LST's worth of rounding per step.
+                assert!(
+                    new_assets as u128 * supply as u128
+                        >= assets as u128 * new_supply.saturating_sub(1) as u128,
+                    "{context}: exchange rate fell from {assets}/{supply} to \
+                     {new_assets}/{new_supply}"
+                );
+                (assets, supply) = (new_assets, new_supply);
+            }
+
+            assert!(succeeded > 0, "seed {seed}: no action succeeded");
+        }
+    }
+}
@@ -0,0 +1,167 @@
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use std::{collections::HashMap, str::FromStr};
+
+    use solana_liquid_staking::client::{
+        deploy::{verify_deployment, Deployment, Loader, VerifyError},
+        rate_cache::AccountFetcher,
+    };
+    use solana_sdk::pubkey::Pubkey;
+
+    const PROGRAM_ID: [u8; 32] = [7; 32];
+    const PROGRAM_DATA: [u8; 32] = [8; 32];
+    const AUTHORITY: [u8; 32] = [9; 32];
+    const ELF: &[u8] = b"\x7fELF not really a program";
+
+    struct MapFetcher(HashMap<[u8; 32], Vec<u8>>);
+
+    impl AccountFetcher for MapFetcher {
+        type Error = ();
+
+        fn slot(&mut self) -> Result<u64, ()> {
+            Ok(0)
+        }
+
+        fn account(&mut self, address: &[u8; 32]) -> Result<Option<(u64, Vec<u8>)>, ()> {
+            Ok(self.0.get(address).map(|data| (1, data.clone())))
+        }
+
+        fn minimum_balance(&mut self, _data_len: usize) -> Result<u64, ()> {
+            Ok(0)
+        }
+    }
+
+    /// A loader-v3 program and its program data account, with `padding` spare
+    /// bytes after the ELF.
+    fn loader_v3(elf: &[u8], authority: Option<[u8; 32]>, padding: usize) -> MapFetcher {
+        let mut program = 2u32.to_le_bytes().to_vec();
+        program.extend_from_slice(&PROGRAM_DATA);
+
+        let mut program_data = 3u32.to_le_bytes().to_vec();
+        program_data.extend_from_slice(&42u64.to_le_bytes());
+        match authority {
+            Some(authority) => {
+                program_data.push(1);
+                program_data.extend_from_slice(&authority);
+            }
+            None => program_data.extend_from_slice(&[0; 33]),
+        }
+        program_data.extend_from_slice(elf);
+        program_data.resize(program_data.len() + padding, 0);
+
+        MapFetcher(HashMap::from([
+            (PROGRAM_ID, program),
+            (PROGRAM_DATA, program_data),
+        ]))
+    }
+
+    fn loader_v4(elf: &[u8], status: u64) -> MapFetcher {
+        let mut program = 42u64.to_le_bytes().to_vec();
+        program.extend_from_slice(&AUTHORITY);
+        program.extend_from_slice(&status.to_le_bytes());
+        program.extend_from_slice(elf);
+
+        MapFetcher(HashMap::from([(PROGRAM_ID, program)]))
+    }
+
+    #[test]
+    fn test_loader_ids() {
+        assert_eq!(
+            Pubkey::new_from_array(Loader::V3.id()),
+            Pubkey::from_str("BPFLoaderUpgradeab1e11111111111111111111111").unwrap()
+        );
+        assert_eq!(
+            Pubkey::new_from_array(Loader::V4.id()),
+            Pubkey::from_str("LoaderV411111111111111111111111111111111111").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_verify_loader_v3_deployment() {
+        let deployment = verify_deployment(
+            &mut loader_v3(ELF, Some(AUTHORITY), 64),
+            Loader::V3,
+            &PROGRAM_ID,
+            ELF,
+        );
+        assert_eq!(
+            deployment,
+            Ok(Deployment {
+                loader: Loader::V3,
+                slot: 42,
+                upgrade_authority: Some(AUTHORITY),
+                elf_account: PROGRAM_DATA,
+            })
+        );
+
+        let immutable =
+            verify_deployment(&mut loader_v3(ELF, None, 0), Loader::V3, &PROGRAM_ID, ELF).unwrap();
+        assert_eq!(immutable.upgrade_authority, None);
+
+        let mut other_elf = ELF.to_vec();
+        other_elf[4] ^= 1;
+        assert_eq!(
+            verify_deployment(
+                &mut loader_v3(&other_elf, None, 0),
+                Loader::V3,
+                &PROGRAM_ID,
+                ELF
+            ),
+            Err(VerifyError::ElfMismatch)
+        );
+        assert_eq!(
+            verify_deployment(
+                &mut loader_v3(ELF, None, 0),
+                Loader::V3,
+                &PROGRAM_ID,
+                &ELF[..ELF.len() - 1]
+            ),
+            Err(VerifyError::ElfMismatch),
+            "Trailing bytes that are not padding must not match"
+        );
+    }
+
+    #[test]
+    fn test_verify_loader_v4_deployment() {
+        let deployment = verify_deployment(&mut loader_v4(ELF, 1), Loader::V4, &PROGRAM_ID, ELF);
+        assert_eq!(
+            deployment,
+            Ok(Deployment {
+                loader: Loader::V4,
+                slot: 42,
+                upgrade_authority: Some(AUTHORITY),
+                elf_account: PROGRAM_ID,
+            })
+        );
+
+        let finalized =
+            verify_deployment(&mut loader_v4(ELF, 2), Loader::V4, &PROGRAM_ID, ELF).unwrap();
+        assert_eq!(finalized.upgrade_authority, None);
+
+        assert_eq!(
+            verify_deployment(&mut loader_v4(ELF, 0), Loader::V4, &PROGRAM_ID, ELF),
+            Err(VerifyError::NotDeployed)
+        );
+    }
+
+    #[test]
+    fn test_verify_under_the_wrong_loader_fails() {
+        assert_eq!(
+            verify_deployment(&mut loader_v4(ELF, 1), Loader::V3, &PROGRAM_ID, ELF),
+            Err(VerifyError::InvalidAccount(PROGRAM_ID))
+        );
+        assert_eq!(
+            verify_deployment(&mut loader_v3(ELF, None, 0), Loader::V4, &PROGRAM_ID, ELF),
+            Err(VerifyError::InvalidAccount(PROGRAM_ID))
+        );
+        assert_eq!(
+            verify_deployment(
+                &mut MapFetcher(HashMap::new()),
+                Loader::V4,
+                &PROGRAM_ID,
+                ELF
+            ),
+            Err(VerifyError::InvalidAccount(PROGRAM_ID))
+        );
+    }
+}
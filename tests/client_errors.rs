@@ -0,0 +1,35 @@
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use solana_liquid_staking::client::PinocchioError;
+
+    #[test]
+    fn test_error_codes_round_trip() {
+        for (index, error) in PinocchioError::ALL.iter().enumerate() {
+            assert_eq!(
+                error.clone() as u32,
+                index as u32,
+                "{} is listed out of order",
+                error.code()
+            );
+            assert_eq!(PinocchioError::try_from(index as u32).as_ref(), Ok(error));
+            assert_eq!(
+                PinocchioError::from_code(error.code()).as_ref(),
+                Some(error)
+            );
+        }
+
+        assert_eq!(
+            PinocchioError::try_from(PinocchioError::ALL.len() as u32),
+            Err(PinocchioError::ALL.len() as u32)
+        );
+        assert_eq!(PinocchioError::from_code("NoSuchError"), None);
+    }
+
+    #[test]
+    fn test_maps_custom_program_error() {
+        // `ProgramError::Custom(63)` as seen in a failed transaction.
+        let error = PinocchioError::try_from(63u32).unwrap();
+        assert_eq!(error, PinocchioError::PrerequisiteCrankMissing);
+        assert_eq!(error.code(), "PrerequisiteCrankMissing");
+    }
+}
@@ -0,0 +1,116 @@
+#[cfg(feature = "client")]
+mod test_helpers;
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_liquid_staking::client::{
+        rate_cache::{AccountFetcher, PoolStateCache},
+        PinocchioError,
+    };
+    use solana_sdk::{clock::Clock, pubkey::Pubkey, signature::Signer};
+
+    use crate::test_helpers::test_helpers::{
+        get_token_balance, liquidity_pool_pda, pool_state_pda, run_deposit, run_initialize,
+        setup_svm, warp_slots,
+    };
+
+    /// Reads accounts straight from the SVM and counts the snapshots taken.
+    struct SvmFetcher<'a> {
+        svm: &'a LiteSVM,
+        fetches: u32,
+    }
+
+    impl AccountFetcher for SvmFetcher<'_> {
+        type Error = ();
+
+        fn slot(&mut self) -> Result<u64, ()> {
+            self.fetches += 1;
+            Ok(self.svm.get_sysvar::<Clock>().slot)
+        }
+
+        fn account(&mut self, address: &[u8; 32]) -> Result<Option<(u64, Vec<u8>)>, ()> {
+            Ok(self
+                .svm
+                .get_account(&Pubkey::new_from_array(*address))
+                .map(|account| (account.lamports, account.data)))
+        }
+
+        fn minimum_balance(&mut self, data_len: usize) -> Result<u64, ()> {
+            Ok(self.svm.minimum_balance_for_rent_exemption(data_len))
+        }
+    }
+
+    #[test]
+    fn test_cached_quote_matches_deposit() {
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+
+        run_deposit(
+            &mut svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            3_000_000_000,
+        );
+
+        let mut cache = PoolStateCache::new(
+            config_pda.to_bytes(),
+            pool_state_pda().to_bytes(),
+            liquidity_pool_pda().to_bytes(),
+            10,
+        );
+        let slot = svm.get_sysvar::<Clock>().slot;
+        let (quote, unstake_quote) = {
+            let mut fetcher = SvmFetcher {
+                svm: &svm,
+                fetches: 0,
+            };
+            let snapshot = cache.get(&mut fetcher, slot).unwrap().clone();
+            cache.get(&mut fetcher, slot + 10).unwrap();
+            assert_eq!(fetcher.fetches, 1, "A fresh snapshot should be reused");
+
+            (
+                snapshot.quote_deposit(2_000_000_000).unwrap(),
+                snapshot.quote_unstake(1_000_000_000),
+            )
+        };
+
+        // No liquidity pool yet, so there is nothing to unstake against.
+        assert_eq!(
+            unstake_quote,
+            Err(PinocchioError::InsufficientLiquidity.into())
+        );
+
+        let (_depositor, depositor_ata) = run_deposit(
+            &mut svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            2_000_000_000,
+        );
+        assert_eq!(quote.decimals, 9);
+        assert_eq!(get_token_balance(&svm, &depositor_ata), quote.base_units);
+
+        warp_slots(&mut svm, 11);
+        let slot = svm.get_sysvar::<Clock>().slot;
+        let mut fetcher = SvmFetcher {
+            svm: &svm,
+            fetches: 0,
+        };
+        assert!(cache.is_stale(slot));
+        let snapshot = cache.get(&mut fetcher, slot).unwrap();
+        assert_eq!(fetcher.fetches, 1, "A stale snapshot should be refetched");
+        assert_eq!(snapshot.slot, slot);
+    }
+}
@@ -0,0 +1,62 @@
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use solana_liquid_staking::{
+        client::{
+            rate_cache::AccountFetcher,
+            rent::{
+                account_cost, default_minimum_balance, upfront_cost, ProtocolAccount, WITHDRAW,
+            },
+        },
+        state::SplitRecord,
+    };
+
+    /// Charges the default rent, like every public cluster.
+    struct DefaultRent;
+
+    impl AccountFetcher for DefaultRent {
+        type Error = ();
+
+        fn slot(&mut self) -> Result<u64, ()> {
+            Ok(0)
+        }
+
+        fn account(&mut self, _address: &[u8; 32]) -> Result<Option<(u64, Vec<u8>)>, ()> {
+            Ok(None)
+        }
+
+        fn minimum_balance(&mut self, data_len: usize) -> Result<u64, ()> {
+            Ok(default_minimum_balance(data_len))
+        }
+    }
+
+    #[test]
+    fn test_default_minimum_balance_matches_cluster_rent() {
+        // `solana rent 0` and `solana rent 200` on any public cluster.
+        assert_eq!(default_minimum_balance(0), 890_880);
+        assert_eq!(default_minimum_balance(200), 2_282_880);
+    }
+
+    #[test]
+    fn test_withdraw_cost_covers_split_account_and_record() {
+        let split_record = account_cost(&mut DefaultRent, ProtocolAccount::SplitRecord).unwrap();
+        assert_eq!(split_record.space, SplitRecord::LEN);
+        assert_eq!(split_record.funding, 0);
+
+        let split_account =
+            account_cost(&mut DefaultRent, ProtocolAccount::SplitStakeAccount).unwrap();
+        assert_eq!(split_account.rent, 2_282_880);
+        assert_eq!(split_account.total(), 2_282_880 + 1_000_000_000);
+
+        assert_eq!(
+            upfront_cost(&mut DefaultRent, WITHDRAW).unwrap(),
+            split_account.total() + split_record.total()
+        );
+    }
+
+    #[test]
+    fn test_every_account_has_a_size() {
+        for account in ProtocolAccount::ALL {
+            assert!(account.space() > 0, "{account:?}");
+        }
+    }
+}
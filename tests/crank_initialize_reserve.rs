@@ -8,11 +8,12 @@ mod tests {
     use solana_sdk::transaction::Transaction;
 
     use crate::test_helpers::test_helpers::{
-        build_crank_initialize_reserve_ix, print_transaction_logs, run_crank_initialize_reserve,
-        run_initialize, setup_svm,
+        build_crank_initialize_reserve_ix, build_set_validator_stake_cap_ix,
+        print_transaction_logs, run_crank_initialize_reserve, run_deposit, run_initialize,
+        setup_svm,
     };
 
-    use solana_liquid_staking::instructions::helpers::STAKE_PROGRAM_ID;
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
 
     #[test]
     fn test_crank_initialize_reserve_success() {
@@ -246,4 +247,76 @@ mod tests {
         print_transaction_logs(&result);
         assert!(result.is_err(), "Should fail with wrong stake program");
     }
+
+    #[test]
+    fn test_crank_initialize_reserve_respects_validator_stake_cap() {
+        let mut svm = setup_svm();
+        let (initializer, token_mint, _initializer_ata, config_pda, stake_account_main, stake_account_reserve, vote_pubkey) =
+            run_initialize(&mut svm);
+
+        run_deposit(
+            &mut svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            5_000_000_000,
+        );
+
+        let send = |svm: &mut litesvm::LiteSVM, ix| {
+            let tx = Transaction::new_signed_with_payer(
+                &[ix],
+                Some(&initializer.pubkey()),
+                &[&initializer],
+                svm.latest_blockhash(),
+            );
+            let result = svm.send_transaction(tx);
+            print_transaction_logs(&result);
+            svm.expire_blockhash();
+            result.is_ok()
+        };
+
+        let crank_ix = build_crank_initialize_reserve_ix(
+            &config_pda,
+            &stake_account_reserve,
+            &vote_pubkey,
+            &system_program::ID,
+            &Pubkey::from(STAKE_PROGRAM_ID),
+        );
+
+        let ix = build_set_validator_stake_cap_ix(&initializer.pubkey(), &config_pda, 0, 0);
+        assert!(!send(&mut svm, ix), "A zero share cap should be rejected");
+
+        // With a single validator every delegated lamport is 100% of the pool.
+        let ix = build_set_validator_stake_cap_ix(&initializer.pubkey(), &config_pda, 5_000, 0);
+        assert!(send(&mut svm, ix), "Admin should set the share cap");
+        assert!(
+            !send(&mut svm, crank_ix.clone()),
+            "Delegating past the share cap should fail"
+        );
+
+        let ix = build_set_validator_stake_cap_ix(
+            &initializer.pubkey(),
+            &config_pda,
+            10_000,
+            1_000_000_000,
+        );
+        assert!(send(&mut svm, ix), "Admin should set the absolute cap");
+        assert!(
+            !send(&mut svm, crank_ix.clone()),
+            "Delegating past the absolute cap should fail"
+        );
+
+        let ix = build_set_validator_stake_cap_ix(
+            &initializer.pubkey(),
+            &config_pda,
+            10_000,
+            100_000_000_000,
+        );
+        assert!(send(&mut svm, ix), "Admin should raise the cap");
+        assert!(
+            send(&mut svm, crank_ix),
+            "Delegating within the cap should succeed"
+        );
+    }
 }
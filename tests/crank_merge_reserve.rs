@@ -9,10 +9,10 @@ mod tests {
 
     use crate::test_helpers::test_helpers::{
         build_crank_merge_reserve_ix, print_transaction_logs, run_crank_initialize_reserve,
-        run_crank_merge_reserve, run_initialize, setup_svm,
+        run_crank_merge_reserve, run_initialize, setup_svm, warp_epoch,
     };
 
-    use solana_liquid_staking::instructions::helpers::STAKE_PROGRAM_ID;
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
 
     /// Helper: runs initialize + crank_initialize_reserve to get the pool into
     /// a state where crank_merge_reserve can be attempted.
@@ -177,6 +177,40 @@ mod tests {
         assert!(result.is_err(), "Should fail with wrong stake program");
     }
 
+    #[test]
+    fn test_crank_merge_reserve_wrong_clock_sysvar() {
+        let mut svm = setup_svm();
+        let (initializer, config_pda, stake_account_main, stake_account_reserve, _vote_pubkey) =
+            setup_merge_ready_pool(&mut svm);
+
+        let mut ix = build_crank_merge_reserve_ix(
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &system_program::ID,
+            &Pubkey::from(STAKE_PROGRAM_ID),
+        );
+        ix.accounts[3].pubkey = solana_sdk::sysvar::rent::id();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        let err = result.expect_err("Should fail with the rent sysvar as the clock");
+        assert!(
+            err.meta
+                .logs
+                .iter()
+                .any(|log| log.contains("Invalid sysvar account")),
+            "Should fail with InvalidSysvar"
+        );
+    }
+
     #[test]
     fn test_crank_merge_reserve_wrong_config_pda() {
         let mut svm = setup_svm();
@@ -203,4 +237,87 @@ mod tests {
         print_transaction_logs(&result);
         assert!(result.is_err(), "Should fail with wrong config PDA");
     }
+
+    #[test]
+    fn test_crank_merge_reserve_reserve_still_activating() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            _token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = run_initialize(&mut svm);
+
+        // Main activated in epoch 0, the reserve only in epoch 1.
+        warp_epoch(&mut svm, 1);
+
+        run_crank_initialize_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_reserve,
+            &vote_pubkey,
+        );
+
+        let ix = build_crank_merge_reserve_ix(
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &system_program::ID,
+            &Pubkey::from(STAKE_PROGRAM_ID),
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(
+            result.is_err(),
+            "Should fail while the reserve is activating and main is not"
+        );
+    }
+
+    #[cfg(feature = "test-hooks")]
+    #[test]
+    fn test_crank_merge_reserve_hook_forces_main_active() {
+        use solana_liquid_staking::test_hooks::{FORCE_ACTIVE, HOOK_MARKER_OFFSET};
+
+        let mut svm = setup_svm();
+        let (initializer, config_pda, stake_account_main, stake_account_reserve, _vote_pubkey) =
+            setup_merge_ready_pool(&mut svm);
+
+        let mut main_account = svm.get_account(&stake_account_main).unwrap();
+        main_account.data[HOOK_MARKER_OFFSET] = FORCE_ACTIVE;
+        svm.set_account(stake_account_main, main_account).unwrap();
+
+        let ix = build_crank_merge_reserve_ix(
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &system_program::ID,
+            &Pubkey::from(STAKE_PROGRAM_ID),
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(
+            result.is_err(),
+            "Should fail when main is forced active while the reserve activates"
+        );
+    }
 }
@@ -0,0 +1,187 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signer::Signer;
+    use solana_sdk::transaction::Transaction;
+
+    use crate::test_helpers::test_helpers::{
+        build_crank_record_rate_ix, print_transaction_logs, run_crank_management_fee,
+        run_crank_record_rate, run_initialize, setup_svm, warp_epoch, PROGRAM_ID,
+    };
+
+    #[test]
+    fn test_crank_record_rate_success() {
+        let mut svm = setup_svm();
+        let (initializer, token_mint, _initializer_ata, config_pda, stake_account_main, stake_account_reserve, _vote_pubkey) =
+            run_initialize(&mut svm);
+
+        run_crank_record_rate(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+
+        let rate_history_pda = Pubkey::find_program_address(&[b"rate_history"], &PROGRAM_ID).0;
+        let account = svm.get_account(&rate_history_pda).unwrap();
+        assert_eq!(account.owner, PROGRAM_ID, "Should be owned by program");
+
+        let len = u64::from_le_bytes(account.data[10..18].try_into().unwrap());
+        assert_eq!(len, 1, "Should hold one snapshot");
+    }
+
+    #[test]
+    fn test_crank_record_rate_twice_same_epoch() {
+        let mut svm = setup_svm();
+        let (initializer, token_mint, _initializer_ata, config_pda, stake_account_main, stake_account_reserve, _vote_pubkey) =
+            run_initialize(&mut svm);
+
+        run_crank_record_rate(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+
+        svm.expire_blockhash();
+
+        let ix = build_crank_record_rate_ix(
+            &initializer.pubkey(),
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_err(), "Should fail when rate already recorded this epoch");
+    }
+
+    #[test]
+    fn test_crank_record_rate_next_epoch() {
+        let mut svm = setup_svm();
+        let (initializer, token_mint, _initializer_ata, config_pda, stake_account_main, stake_account_reserve, _vote_pubkey) =
+            run_initialize(&mut svm);
+
+        run_crank_record_rate(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+
+        warp_epoch(&mut svm, 1);
+
+        run_crank_management_fee(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+        run_crank_record_rate(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+
+        let rate_history_pda = Pubkey::find_program_address(&[b"rate_history"], &PROGRAM_ID).0;
+        let account = svm.get_account(&rate_history_pda).unwrap();
+        let len = u64::from_le_bytes(account.data[10..18].try_into().unwrap());
+        assert_eq!(len, 2, "Should hold two snapshots");
+    }
+
+    #[test]
+    fn test_crank_record_rate_before_management_fee() {
+        let mut svm = setup_svm();
+        let (initializer, token_mint, _initializer_ata, config_pda, stake_account_main, stake_account_reserve, _vote_pubkey) =
+            run_initialize(&mut svm);
+
+        warp_epoch(&mut svm, 1);
+
+        let ix = build_crank_record_rate_ix(
+            &initializer.pubkey(),
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(
+            result.is_err(),
+            "Should fail before the epoch's management fee is cranked"
+        );
+
+        run_crank_management_fee(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+        run_crank_record_rate(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+    }
+
+    #[test]
+    fn test_crank_record_rate_wrong_mint() {
+        let mut svm = setup_svm();
+        let (initializer, _token_mint, _initializer_ata, config_pda, stake_account_main, stake_account_reserve, _vote_pubkey) =
+            run_initialize(&mut svm);
+
+        let ix = build_crank_record_rate_ix(
+            &initializer.pubkey(),
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &Pubkey::new_unique(),
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_err(), "Should fail with wrong LST mint");
+    }
+}
@@ -0,0 +1,194 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::instruction::Instruction;
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+
+    use crate::test_helpers::test_helpers::{
+        buffer_stake_pda, build_crank_refill_buffer_ix, build_liquid_unstake_ix,
+        build_set_unstake_fee_params_ix, get_mint_supply, get_token_balance, liquidity_pool_pda,
+        run_crank_initialize_reserve, run_crank_merge_reserve, send, setup_pool, setup_svm, Pool,
+        PoolSetup,
+    };
+
+    const RESERVE: u8 = 0;
+    const UNSTAKE: u8 = 1;
+    const COLLECT: u8 = 2;
+
+    impl Pool {
+        fn refill_ix(&self, keeper: &Keypair, source: u8) -> Instruction {
+            build_crank_refill_buffer_ix(
+                &keeper.pubkey(),
+                &self.config_pda,
+                &self.mint,
+                &self.stake_account_main,
+                &self.stake_account_reserve,
+                source,
+            )
+        }
+    }
+
+    fn pool_balances(svm: &litesvm::LiteSVM) -> (u64, u64) {
+        let data = svm.get_account(&liquidity_pool_pda()).unwrap().data;
+        (
+            u64::from_le_bytes(data[66..74].try_into().unwrap()),
+            u64::from_le_bytes(data[74..82].try_into().unwrap()),
+        )
+    }
+
+    /// Sets up a 10 SOL buffer with a 10 SOL target, deposits 5 SOL into the
+    /// reserve (merged into main when `merge`) and instantly unstakes 3 LST,
+    /// leaving the buffer short and the vault holding LST.
+    fn setup_drained_buffer(svm: &mut litesvm::LiteSVM, merge: bool) -> Pool {
+        let pool = setup_pool(
+            svm,
+            PoolSetup {
+                activate: true,
+                liquidity: 10_000_000_000,
+                deposit: 5_000_000_000,
+                ..PoolSetup::default()
+            },
+        );
+
+        let ix = build_set_unstake_fee_params_ix(
+            &pool.admin.pubkey(),
+            &pool.config_pda,
+            30,
+            300,
+            10_000_000_000,
+        );
+        assert!(send(svm, ix, &pool.admin), "Admin should set fee params");
+
+        if merge {
+            run_crank_initialize_reserve(
+                svm,
+                &pool.admin,
+                &pool.config_pda,
+                &pool.stake_account_reserve,
+                &pool.vote_pubkey,
+            );
+            run_crank_merge_reserve(
+                svm,
+                &pool.admin,
+                &pool.config_pda,
+                &pool.stake_account_main,
+                &pool.stake_account_reserve,
+            );
+        }
+
+        let ix = build_liquid_unstake_ix(
+            &pool.depositor.pubkey(),
+            &pool.depositor_ata,
+            &pool.config_pda,
+            &pool.mint,
+            &pool.stake_account_main,
+            &pool.stake_account_reserve,
+            3_000_000_000,
+            0,
+        );
+        assert!(
+            send(svm, ix, &pool.depositor),
+            "LiquidUnstake should succeed"
+        );
+
+        pool
+    }
+
+    fn keeper(svm: &mut litesvm::LiteSVM) -> Keypair {
+        let keeper = Keypair::new();
+        svm.airdrop(&keeper.pubkey(), 5_000_000_000).unwrap();
+        keeper
+    }
+
+    #[test]
+    fn test_refill_from_reserve() {
+        let mut svm = setup_svm();
+        let pool = setup_drained_buffer(&mut svm, false);
+        let keeper = keeper(&mut svm);
+
+        let (sol_reserves_before, _) = pool_balances(&svm);
+        let reserve_before = svm.get_balance(&pool.stake_account_reserve).unwrap();
+        let vault_before = get_token_balance(&svm, &pool.lst_vault);
+        let supply_before = get_mint_supply(&svm, &pool.mint);
+        assert!(sol_reserves_before < 10_000_000_000);
+
+        let ix = pool.refill_ix(&keeper, RESERVE);
+        assert!(send(&mut svm, ix, &keeper), "Reserve refill should succeed");
+
+        let (sol_reserves_after, pending) = pool_balances(&svm);
+        let refilled = sol_reserves_after - sol_reserves_before;
+        assert!(refilled > 0);
+        assert_eq!(pending, 0);
+        assert!(sol_reserves_after <= 10_000_000_000);
+        assert_eq!(
+            svm.get_balance(&pool.stake_account_reserve).unwrap(),
+            reserve_before - refilled
+        );
+
+        let burned = vault_before - get_token_balance(&svm, &pool.lst_vault);
+        assert!(burned > 0);
+        assert_eq!(get_mint_supply(&svm, &pool.mint), supply_before - burned);
+    }
+
+    #[test]
+    fn test_refill_from_reserve_fails_once_delegated() {
+        let mut svm = setup_svm();
+        let pool = setup_drained_buffer(&mut svm, true);
+        let keeper = keeper(&mut svm);
+
+        let ix = pool.refill_ix(&keeper, RESERVE);
+        assert!(
+            !send(&mut svm, ix, &keeper),
+            "A delegated or merged reserve cannot refill the buffer"
+        );
+    }
+
+    #[test]
+    fn test_refill_by_unstake_then_collect() {
+        let mut svm = setup_svm();
+        let pool = setup_drained_buffer(&mut svm, true);
+        let keeper = keeper(&mut svm);
+
+        let (sol_reserves_before, _) = pool_balances(&svm);
+        let vault_before = get_token_balance(&svm, &pool.lst_vault);
+
+        let ix = pool.refill_ix(&keeper, UNSTAKE);
+        assert!(send(&mut svm, ix, &keeper), "Unstake refill should succeed");
+
+        let (sol_reserves, pending) = pool_balances(&svm);
+        assert_eq!(sol_reserves, sol_reserves_before);
+        assert!(pending > 0);
+        assert!(sol_reserves + pending <= 10_000_000_000);
+        assert!(get_token_balance(&svm, &pool.lst_vault) < vault_before);
+
+        let buffer_stake_lamports = svm.get_balance(&buffer_stake_pda()).unwrap();
+        let funding = buffer_stake_lamports - pending;
+
+        let ix = pool.refill_ix(&keeper, UNSTAKE);
+        assert!(
+            !send(&mut svm, ix, &keeper),
+            "Only one buffer unstake may cool down at a time"
+        );
+
+        let other = Keypair::new();
+        svm.airdrop(&other.pubkey(), 1_000_000_000).unwrap();
+        let ix = pool.refill_ix(&other, COLLECT);
+        assert!(
+            !send(&mut svm, ix, &other),
+            "Only the funding keeper may collect"
+        );
+
+        let keeper_before = svm.get_balance(&keeper.pubkey()).unwrap();
+        let ix = pool.refill_ix(&keeper, COLLECT);
+        assert!(send(&mut svm, ix, &keeper), "Collect should succeed");
+
+        assert_eq!(pool_balances(&svm), (sol_reserves_before + pending, 0));
+        assert_eq!(svm.get_balance(&buffer_stake_pda()).unwrap_or(0), 0);
+        assert_eq!(
+            svm.get_balance(&keeper.pubkey()).unwrap(),
+            keeper_before + funding - 5_000
+        );
+    }
+}
@@ -7,12 +7,20 @@ mod tests {
     use solana_sdk::signer::Signer;
     use solana_sdk::transaction::Transaction;
 
+    use solana_sdk::instruction::AccountMeta;
+
     use crate::test_helpers::test_helpers::{
-        build_crank_split_ix, create_and_fund_ata, print_transaction_logs,
+        build_crank_split_ix, build_crank_split_single_ix, build_deposit_ix,
+        build_set_holding_rebate_ix, build_set_main_headroom_ix, build_set_split_minimum_ix,
+        build_set_withdraw_fee_ix, create_and_fund_ata, get_mint_supply, print_transaction_logs,
         run_crank_initialize_reserve, run_crank_merge_reserve, run_crank_split, run_deposit,
-        run_initialize, setup_svm,
+        run_initialize, set_deposit_account, setup_svm, split_record_pda, user_stats_pda,
+        warp_epoch, DEPOSIT_USER_STATS,
     };
 
+    /// Rent-exempt minimum of a 200-byte stake account under the default rent.
+    const STAKE_ACCOUNT_RENT: u64 = 2_282_880;
+
     /// Sets up a pool ready for crank_split: initialize + deposit + crank_init_reserve + merge.
     /// Returns (initializer, token_mint, depositor, depositor_ata, config_pda,
     ///          stake_account_main, stake_account_reserve, vote_pubkey).
@@ -91,7 +99,7 @@ mod tests {
         ) = setup_split_ready_pool(&mut svm, 2_000_000_000);
 
         let lamports_to_split = 1_500_000_000u64;
-        let _depositor_stake_account = run_crank_split(
+        let depositor_stake_account = run_crank_split(
             &mut svm,
             &depositor,
             &depositor_ata,
@@ -102,6 +110,15 @@ mod tests {
             lamports_to_split,
             123,
         );
+
+        assert_eq!(
+            svm.get_account(&depositor_stake_account)
+                .unwrap()
+                .data
+                .len(),
+            svm.get_account(&stake_account_main).unwrap().data.len(),
+            "Split account should match the main account's layout"
+        );
     }
 
     #[test]
@@ -179,10 +196,7 @@ mod tests {
 
         let result = svm.send_transaction(tx);
         print_transaction_logs(&result);
-        assert!(
-            result.is_err(),
-            "Should fail with wrong stake account main"
-        );
+        assert!(result.is_err(), "Should fail with wrong stake account main");
     }
 
     #[test]
@@ -201,8 +215,7 @@ mod tests {
 
         let wrong_mint =
             crate::test_helpers::test_helpers::create_mock_token_mint(&mut svm, &config_pda);
-        let wrong_ata =
-            create_and_fund_ata(&mut svm, &depositor.pubkey(), &wrong_mint.pubkey(), 0);
+        let wrong_ata = create_and_fund_ata(&mut svm, &depositor.pubkey(), &wrong_mint.pubkey(), 0);
 
         let (ix, _) = build_crank_split_ix(
             &depositor.pubkey(),
@@ -316,7 +329,10 @@ mod tests {
 
         let result = svm.send_transaction(tx);
         print_transaction_logs(&result);
-        assert!(result.is_err(), "Should fail on double withdrawal (same nonce)");
+        assert!(
+            result.is_err(),
+            "Should fail on double withdrawal (same nonce)"
+        );
     }
 
     #[test]
@@ -408,4 +424,622 @@ mod tests {
             "Should fail when withdrawer has insufficient LST"
         );
     }
+
+    #[test]
+    fn test_crank_split_below_minimum_without_tolerance() {
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            token_mint,
+            depositor,
+            depositor_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = setup_split_ready_pool(&mut svm, 2_000_000_000);
+
+        let (ix, _) = build_crank_split_ix(
+            &depositor.pubkey(),
+            &depositor_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            1_000_000_000,
+            true,
+            123,
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(
+            result.is_err(),
+            "Should fail below split minimum when no dust tolerance is given"
+        );
+    }
+
+    #[test]
+    fn test_crank_split_respects_configured_minimum() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            depositor,
+            depositor_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = setup_split_ready_pool(&mut svm, 4_000_000_000);
+
+        let config = svm.get_account(&config_pda).unwrap();
+        assert_eq!(
+            u64::from_le_bytes(config.data[185..193].try_into().unwrap()),
+            1_000_000_000,
+            "The split minimum should default to 1 SOL of stake"
+        );
+
+        let ix = build_set_split_minimum_ix(&depositor.pubkey(), &config_pda, 2_000_000_000);
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_err(), "Only the admin may set the split minimum");
+
+        let ix = build_set_split_minimum_ix(&initializer.pubkey(), &config_pda, 2_000_000_000);
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Admin should set the split minimum");
+
+        let (ix, _) = build_crank_split_ix(
+            &depositor.pubkey(),
+            &depositor_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            1_500_000_000,
+            true,
+            123,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(
+            result.is_err(),
+            "A split below the configured minimum should fail"
+        );
+
+        run_crank_split(
+            &mut svm,
+            &depositor,
+            &depositor_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            2_000_000_000 + STAKE_ACCOUNT_RENT,
+            124,
+        );
+    }
+
+    #[test]
+    fn test_crank_split_rounds_up_within_tolerance() {
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            token_mint,
+            depositor,
+            depositor_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = setup_split_ready_pool(&mut svm, 2_000_000_000);
+
+        let split_minimum = STAKE_ACCOUNT_RENT + 1_000_000_000;
+        let requested = split_minimum - 200_000;
+
+        let (mut ix, depositor_stake_account) = build_crank_split_ix(
+            &depositor.pubkey(),
+            &depositor_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            requested,
+            true,
+            123,
+        );
+        ix.data.extend_from_slice(&500_000u64.to_le_bytes());
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Should round up to the split minimum");
+
+        let record = svm
+            .get_account(&split_record_pda(&depositor_stake_account))
+            .unwrap();
+        let lamports_split = u64::from_le_bytes(record.data[74..82].try_into().unwrap());
+        let dust_lamports = i64::from_le_bytes(record.data[90..98].try_into().unwrap());
+        assert_eq!(lamports_split, split_minimum, "Split should be rounded up");
+        assert_eq!(dust_lamports, -200_000, "Dust should record the round-up");
+    }
+
+    #[test]
+    fn test_crank_split_rounds_down_to_keep_main_minimum() {
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            token_mint,
+            depositor,
+            _depositor_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = setup_split_ready_pool(&mut svm, 5_000_000_000);
+
+        // Give the depositor the whole supply so the burn is never the limit.
+        let supply = get_mint_supply(&svm, &token_mint.pubkey());
+        let depositor_ata =
+            create_and_fund_ata(&mut svm, &depositor.pubkey(), &token_mint.pubkey(), supply);
+
+        let main_lamports = svm.get_account(&stake_account_main).unwrap().lamports;
+        let main_available = main_lamports - STAKE_ACCOUNT_RENT - 1_000_000_000;
+
+        let (mut ix, depositor_stake_account) = build_crank_split_ix(
+            &depositor.pubkey(),
+            &depositor_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            main_available + 1_000,
+            true,
+            123,
+        );
+        ix.data.extend_from_slice(&10_000u64.to_le_bytes());
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(
+            result.is_ok(),
+            "Should round down to keep main above minimum"
+        );
+
+        let record = svm
+            .get_account(&split_record_pda(&depositor_stake_account))
+            .unwrap();
+        let lamports_split = u64::from_le_bytes(record.data[74..82].try_into().unwrap());
+        let dust_lamports = i64::from_le_bytes(record.data[90..98].try_into().unwrap());
+        assert_eq!(
+            lamports_split, main_available,
+            "Split should be rounded down"
+        );
+        assert_eq!(dust_lamports, 1_000, "Dust should record the round-down");
+    }
+
+    #[test]
+    fn test_crank_split_main_below_minimum_beyond_tolerance() {
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            token_mint,
+            depositor,
+            _depositor_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = setup_split_ready_pool(&mut svm, 5_000_000_000);
+
+        let supply = get_mint_supply(&svm, &token_mint.pubkey());
+        let depositor_ata =
+            create_and_fund_ata(&mut svm, &depositor.pubkey(), &token_mint.pubkey(), supply);
+
+        let main_lamports = svm.get_account(&stake_account_main).unwrap().lamports;
+        let main_available = main_lamports - STAKE_ACCOUNT_RENT - 1_000_000_000;
+
+        let (mut ix, _) = build_crank_split_ix(
+            &depositor.pubkey(),
+            &depositor_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            main_available + 100_000,
+            true,
+            123,
+        );
+        ix.data.extend_from_slice(&10_000u64.to_le_bytes());
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(
+            result.is_err(),
+            "Should fail when the round-down exceeds the dust tolerance"
+        );
+    }
+
+    #[test]
+    fn test_crank_split_keeps_main_headroom() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            depositor,
+            _depositor_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = setup_split_ready_pool(&mut svm, 5_000_000_000);
+
+        let supply = get_mint_supply(&svm, &token_mint.pubkey());
+        let depositor_ata =
+            create_and_fund_ata(&mut svm, &depositor.pubkey(), &token_mint.pubkey(), supply);
+
+        let headroom = 2_000_000_000;
+        let ix = build_set_main_headroom_ix(&initializer.pubkey(), &config_pda, headroom);
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Admin should set the main headroom");
+
+        let main_lamports = svm.get_account(&stake_account_main).unwrap().lamports;
+        let main_available = main_lamports - STAKE_ACCOUNT_RENT - 1_000_000_000;
+
+        let (ix, _) = build_crank_split_ix(
+            &depositor.pubkey(),
+            &depositor_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            main_available,
+            true,
+            123,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_err(), "Split should not eat into the headroom");
+
+        let (ix, _) = build_crank_split_ix(
+            &depositor.pubkey(),
+            &depositor_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            main_available - headroom,
+            true,
+            124,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Split should leave exactly the headroom");
+    }
+
+    /// LST burned by a 1.5 SOL split from a fresh pool, with the admin's
+    /// withdrawal fee set to `withdraw_fee_bps`.
+    fn lst_burned_by_split(withdraw_fee_bps: u16) -> u64 {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            depositor,
+            depositor_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = setup_split_ready_pool(&mut svm, 2_000_000_000);
+
+        let ix = build_set_withdraw_fee_ix(&initializer.pubkey(), &config_pda, withdraw_fee_bps);
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Admin should set the withdrawal fee");
+
+        let supply_before = get_mint_supply(&svm, &token_mint.pubkey());
+        run_crank_split(
+            &mut svm,
+            &depositor,
+            &depositor_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            1_500_000_000,
+            123,
+        );
+        supply_before - get_mint_supply(&svm, &token_mint.pubkey())
+    }
+
+    #[test]
+    fn test_crank_split_burns_the_withdraw_fee_on_top() {
+        let burned_without_fee = lst_burned_by_split(0);
+        let burned_with_fee = lst_burned_by_split(100);
+
+        assert_eq!(
+            burned_with_fee,
+            burned_without_fee + burned_without_fee / 100,
+            "A 1% fee burns 1% more LST for the same lamports"
+        );
+    }
+
+    /// LST burned by a 1.5 SOL split `held_epochs` after the depositor's
+    /// first deposit recorded their user stats PDA, with a 1% withdrawal fee
+    /// and half of it rebated after 10 epochs.
+    fn lst_burned_by_rebated_split(held_epochs: u64) -> u64 {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = run_initialize(&mut svm);
+
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 10_000_000_000).unwrap();
+        let depositor_ata =
+            create_and_fund_ata(&mut svm, &depositor.pubkey(), &token_mint.pubkey(), 0);
+        let mut ix = build_deposit_ix(
+            &config_pda,
+            &depositor.pubkey(),
+            &depositor_ata,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            2_000_000_000,
+            true,
+        );
+        set_deposit_account(
+            &mut ix,
+            DEPOSIT_USER_STATS,
+            AccountMeta::new(user_stats_pda(&depositor.pubkey()), false),
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Deposit should create the user stats PDA");
+
+        run_crank_initialize_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_reserve,
+            &vote_pubkey,
+        );
+        run_crank_merge_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+
+        let fee_ix = build_set_withdraw_fee_ix(&initializer.pubkey(), &config_pda, 100);
+        let rebate_ix = build_set_holding_rebate_ix(&initializer.pubkey(), &config_pda, 5_000, 10);
+        let tx = Transaction::new_signed_with_payer(
+            &[fee_ix, rebate_ix],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Admin should set the fee and its rebate");
+
+        if held_epochs > 0 {
+            warp_epoch(&mut svm, held_epochs);
+        }
+
+        let (mut ix, _) = build_crank_split_ix(
+            &depositor.pubkey(),
+            &depositor_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            1_500_000_000,
+            true,
+            123,
+        );
+        ix.accounts.push(AccountMeta::new(depositor.pubkey(), true));
+        ix.accounts.push(AccountMeta::new_readonly(
+            user_stats_pda(&depositor.pubkey()),
+            false,
+        ));
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+
+        let supply_before = get_mint_supply(&svm, &token_mint.pubkey());
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Split with the user stats PDA should succeed");
+        supply_before - get_mint_supply(&svm, &token_mint.pubkey())
+    }
+
+    #[test]
+    fn test_crank_split_rebates_the_withdraw_fee_by_holding_duration() {
+        let burned_without_fee = lst_burned_by_split(0);
+
+        assert_eq!(
+            lst_burned_by_rebated_split(0),
+            burned_without_fee + burned_without_fee / 100,
+            "No rebate in the epoch of the first deposit"
+        );
+        assert_eq!(
+            lst_burned_by_rebated_split(5),
+            burned_without_fee + burned_without_fee * 75 / 10_000,
+            "Half the holding period rebates a quarter of the fee"
+        );
+        assert_eq!(
+            lst_burned_by_rebated_split(20),
+            burned_without_fee + burned_without_fee / 200,
+            "The rebate stops growing after the holding period"
+        );
+    }
+
+    #[test]
+    fn test_crank_split_single_allows_one_pending_split() {
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            token_mint,
+            depositor,
+            depositor_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = setup_split_ready_pool(&mut svm, 5_000_000_000);
+
+        let (ix, split_account) = build_crank_split_single_ix(
+            &depositor.pubkey(),
+            &depositor_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            1_500_000_000,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "The single split should succeed");
+
+        let record = svm.get_account(&split_record_pda(&split_account)).unwrap();
+        assert_eq!(&record.data[2..34], depositor.pubkey().as_ref());
+        assert_eq!(&record.data[34..66], split_account.as_ref());
+
+        svm.expire_blockhash();
+        let (ix, _) = build_crank_split_single_ix(
+            &depositor.pubkey(),
+            &depositor_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            1_500_000_000,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(
+            result.is_err(),
+            "A second single split should wait for the first to be withdrawn"
+        );
+
+        let (ix, _) = build_crank_split_ix(
+            &depositor.pubkey(),
+            &depositor_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            1_500_000_000,
+            true,
+            7,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Nonce splits should stay available");
+    }
 }
@@ -0,0 +1,185 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{pubkey::Pubkey, signature::Signer};
+
+    use crate::test_helpers::test_helpers::{
+        build_crank_split_ix, build_crank_split_next_ix, run_crank_initialize_reserve,
+        run_crank_merge_reserve, run_deposit, run_initialize, send, setup_svm, split_counter_pda,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    fn next_nonce(svm: &LiteSVM, withdrawer: &Pubkey) -> u64 {
+        let data = svm
+            .get_account(&split_counter_pda(withdrawer))
+            .unwrap()
+            .data;
+        assert_eq!(
+            &data[0..2],
+            &[2, 1],
+            "SplitCounter discriminator and version"
+        );
+        u64::from_le_bytes(data[2..10].try_into().unwrap())
+    }
+
+    #[test]
+    fn test_crank_split_next_uses_consecutive_nonces() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            _,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+        let (withdrawer, withdrawer_ata) = run_deposit(
+            &mut svm,
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+            5 * LAMPORTS_PER_SOL,
+        );
+        run_crank_initialize_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_reserve,
+            &vote_pubkey,
+        );
+        run_crank_merge_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+
+        let mut split_accounts = Vec::new();
+        for expected_nonce in 0..2 {
+            let (ix, split_account, nonce) = build_crank_split_next_ix(
+                &svm,
+                &withdrawer.pubkey(),
+                &withdrawer_ata,
+                &config_pda,
+                &stake_account_main,
+                &stake_account_reserve,
+                &mint,
+                LAMPORTS_PER_SOL,
+            );
+            assert_eq!(nonce, expected_nonce);
+            assert!(send(&mut svm, ix, &withdrawer));
+            assert_eq!(next_nonce(&svm, &withdrawer.pubkey()), expected_nonce + 1);
+            split_accounts.push(split_account);
+        }
+
+        assert_ne!(split_accounts[0], split_accounts[1]);
+        for split_account in &split_accounts {
+            assert!(svm.get_account(split_account).unwrap().lamports > LAMPORTS_PER_SOL);
+        }
+    }
+
+    #[test]
+    fn test_crank_split_next_rejects_a_stale_nonce() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            _,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+        let (withdrawer, withdrawer_ata) = run_deposit(
+            &mut svm,
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+            5 * LAMPORTS_PER_SOL,
+        );
+        run_crank_initialize_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_reserve,
+            &vote_pubkey,
+        );
+        run_crank_merge_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+
+        let (ix, _, _) = build_crank_split_next_ix(
+            &svm,
+            &withdrawer.pubkey(),
+            &withdrawer_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &mint,
+            LAMPORTS_PER_SOL,
+        );
+        assert!(send(&mut svm, ix.clone(), &withdrawer));
+
+        // Reusing the instruction passes the split PDA for nonce 0 again.
+        assert!(!send(&mut svm, ix, &withdrawer));
+
+        // A counter PDA that is not the withdrawer's is rejected.
+        let (mut ix, _, _) = build_crank_split_next_ix(
+            &svm,
+            &withdrawer.pubkey(),
+            &withdrawer_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &mint,
+            LAMPORTS_PER_SOL,
+        );
+        ix.accounts[0].pubkey = split_counter_pda(&Pubkey::new_unique());
+        assert!(!send(&mut svm, ix, &withdrawer));
+
+        // A plain CrankSplit with an explicit nonce does not move the counter.
+        let (ix, _) = build_crank_split_ix(
+            &withdrawer.pubkey(),
+            &withdrawer_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &mint,
+            LAMPORTS_PER_SOL,
+            true,
+            7,
+        );
+        assert!(send(&mut svm, ix, &withdrawer));
+        assert_eq!(next_nonce(&svm, &withdrawer.pubkey()), 1);
+
+        // A counter whose header is not the split counter's is rejected.
+        let split_counter = split_counter_pda(&withdrawer.pubkey());
+        let mut account = svm.get_account(&split_counter).unwrap();
+        account.data[0] = 1;
+        svm.set_account(split_counter, account).unwrap();
+        let (ix, _, _) = build_crank_split_next_ix(
+            &svm,
+            &withdrawer.pubkey(),
+            &withdrawer_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &mint,
+            LAMPORTS_PER_SOL,
+        );
+        assert!(!send(&mut svm, ix, &withdrawer));
+    }
+}
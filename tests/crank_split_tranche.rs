@@ -0,0 +1,315 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+    use solana_sdk::sysvar::clock::Clock;
+    use solana_sdk::transaction::Transaction;
+
+    use solana_liquid_staking::{ids::STAKE_PROGRAM_ID, state::SplitRecord};
+
+    use crate::test_helpers::test_helpers::{
+        build_crank_split_tranche_ix, build_withdraw_ix, print_transaction_logs,
+        run_crank_initialize_reserve, run_crank_merge_reserve, run_crank_split, run_deposit,
+        run_initialize, run_withdraw, setup_svm, split_record_pda, warp_epoch,
+    };
+
+    /// Large enough that main can serve a minimum-sized tranche per epoch.
+    const LARGE_DEPOSIT: u64 = 9_900_000_000;
+
+    /// Sets up a pool whose main account holds more than ten times the split
+    /// minimum, and tops the depositor back up to pay for split accounts.
+    /// Returns (token_mint, depositor, depositor_ata, config_pda,
+    ///          stake_account_main, stake_account_reserve).
+    fn setup_large_pool(
+        svm: &mut litesvm::LiteSVM,
+    ) -> (Keypair, Keypair, Pubkey, Pubkey, Pubkey, Pubkey) {
+        let (
+            initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = run_initialize(svm);
+
+        let (depositor, depositor_ata) = run_deposit(
+            svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            LARGE_DEPOSIT,
+        );
+
+        run_crank_initialize_reserve(
+            svm,
+            &initializer,
+            &config_pda,
+            &stake_account_reserve,
+            &vote_pubkey,
+        );
+
+        run_crank_merge_reserve(
+            svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+
+        svm.airdrop(&depositor.pubkey(), 10_000_000_000).unwrap();
+
+        (
+            token_mint,
+            depositor,
+            depositor_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+        )
+    }
+
+    fn read_u64(svm: &litesvm::LiteSVM, account: &Pubkey, offset: usize) -> u64 {
+        let data = svm.get_account(account).unwrap().data;
+        u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+    }
+
+    fn send(
+        svm: &mut litesvm::LiteSVM,
+        ix: solana_sdk::instruction::Instruction,
+        signer: &Keypair,
+    ) -> litesvm::types::TransactionResult {
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&signer.pubkey()),
+            &[signer],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        result
+    }
+
+    #[test]
+    fn test_crank_split_schedules_large_split() {
+        let mut svm = setup_svm();
+        let (token_mint, depositor, depositor_ata, config_pda, main, reserve) =
+            setup_large_pool(&mut svm);
+
+        let lamports_requested = 3_000_000_000u64;
+        let first_split = run_crank_split(
+            &mut svm,
+            &depositor,
+            &depositor_ata,
+            &config_pda,
+            &main,
+            &reserve,
+            &token_mint.pubkey(),
+            lamports_requested,
+            1,
+        );
+
+        let schedule_record = split_record_pda(&first_split);
+        let lamports_split = read_u64(&svm, &schedule_record, 74);
+        let lamports_scheduled = read_u64(&svm, &schedule_record, 98);
+
+        assert!(
+            lamports_split < lamports_requested,
+            "First tranche should be capped"
+        );
+        assert_eq!(
+            lamports_split + lamports_scheduled,
+            lamports_requested,
+            "Split and scheduled lamports should cover the request"
+        );
+        assert_eq!(
+            read_u64(&svm, &schedule_record, 114),
+            1,
+            "One tranche should be split"
+        );
+
+        let epoch = svm.get_sysvar::<Clock>().epoch;
+        warp_epoch(&mut svm, epoch + 1);
+
+        let (ix, tranche_split) = build_crank_split_tranche_ix(
+            &depositor.pubkey(),
+            &depositor_ata,
+            &config_pda,
+            &main,
+            &reserve,
+            &token_mint.pubkey(),
+            1,
+            2,
+        );
+        let result = send(&mut svm, ix, &depositor);
+        assert!(result.is_ok(), "Scheduled tranche should split next epoch");
+
+        let tranche_record = split_record_pda(&tranche_split);
+        assert_eq!(
+            read_u64(&svm, &tranche_record, 74),
+            lamports_scheduled,
+            "Last tranche should split the remaining schedule"
+        );
+        assert_eq!(
+            read_u64(&svm, &schedule_record, 98),
+            0,
+            "Schedule should be exhausted"
+        );
+        assert_eq!(
+            read_u64(&svm, &schedule_record, 114),
+            2,
+            "Two tranches should be split"
+        );
+    }
+
+    #[test]
+    fn test_crank_split_tranche_not_ready() {
+        let mut svm = setup_svm();
+        let (token_mint, depositor, depositor_ata, config_pda, main, reserve) =
+            setup_large_pool(&mut svm);
+
+        run_crank_split(
+            &mut svm,
+            &depositor,
+            &depositor_ata,
+            &config_pda,
+            &main,
+            &reserve,
+            &token_mint.pubkey(),
+            3_000_000_000,
+            1,
+        );
+
+        let (ix, _) = build_crank_split_tranche_ix(
+            &depositor.pubkey(),
+            &depositor_ata,
+            &config_pda,
+            &main,
+            &reserve,
+            &token_mint.pubkey(),
+            1,
+            2,
+        );
+        let result = send(&mut svm, ix, &depositor);
+        assert!(
+            result.is_err(),
+            "Next tranche should not split in the same epoch"
+        );
+    }
+
+    #[test]
+    fn test_crank_split_tranche_nothing_scheduled() {
+        let mut svm = setup_svm();
+        let (token_mint, depositor, depositor_ata, config_pda, main, reserve) =
+            setup_large_pool(&mut svm);
+
+        let first_split = run_crank_split(
+            &mut svm,
+            &depositor,
+            &depositor_ata,
+            &config_pda,
+            &main,
+            &reserve,
+            &token_mint.pubkey(),
+            1_100_000_000,
+            1,
+        );
+        assert_eq!(
+            read_u64(&svm, &split_record_pda(&first_split), 98),
+            0,
+            "Split within the cap should not be scheduled"
+        );
+
+        let epoch = svm.get_sysvar::<Clock>().epoch;
+        warp_epoch(&mut svm, epoch + 1);
+
+        let (ix, _) = build_crank_split_tranche_ix(
+            &depositor.pubkey(),
+            &depositor_ata,
+            &config_pda,
+            &main,
+            &reserve,
+            &token_mint.pubkey(),
+            1,
+            2,
+        );
+        let result = send(&mut svm, ix, &depositor);
+        assert!(
+            result.is_err(),
+            "Should fail when the record schedules nothing"
+        );
+    }
+
+    #[test]
+    fn test_withdraw_keeps_schedule_until_last_tranche() {
+        let mut svm = setup_svm();
+        let (token_mint, depositor, depositor_ata, config_pda, main, reserve) =
+            setup_large_pool(&mut svm);
+
+        let first_split = run_crank_split(
+            &mut svm,
+            &depositor,
+            &depositor_ata,
+            &config_pda,
+            &main,
+            &reserve,
+            &token_mint.pubkey(),
+            3_000_000_000,
+            1,
+        );
+
+        run_withdraw(&mut svm, &depositor, &first_split, &config_pda, 1);
+
+        let schedule_record = split_record_pda(&first_split);
+        assert!(
+            svm.get_account(&schedule_record)
+                .is_some_and(|account| account.lamports > 0),
+            "Record should stay open while tranches are scheduled"
+        );
+        let record = svm.get_account(&schedule_record).unwrap().data;
+        let record = SplitRecord::load(&record).unwrap();
+        assert_eq!({ record.claimed }, 1);
+        assert_eq!({ record.created_epoch }, svm.get_sysvar::<Clock>().epoch);
+
+        let ix = build_withdraw_ix(
+            &first_split,
+            &depositor.pubkey(),
+            &config_pda,
+            &Pubkey::from(STAKE_PROGRAM_ID),
+            1,
+            true,
+        );
+        assert!(
+            send(&mut svm, ix, &depositor).is_err(),
+            "A claimed split cannot be withdrawn again"
+        );
+
+        let epoch = svm.get_sysvar::<Clock>().epoch;
+        warp_epoch(&mut svm, epoch + 1);
+
+        let (ix, tranche_split) = build_crank_split_tranche_ix(
+            &depositor.pubkey(),
+            &depositor_ata,
+            &config_pda,
+            &main,
+            &reserve,
+            &token_mint.pubkey(),
+            1,
+            2,
+        );
+        let result = send(&mut svm, ix, &depositor);
+        assert!(result.is_ok(), "Scheduled tranche should split next epoch");
+
+        assert!(
+            svm.get_account(&schedule_record)
+                .is_none_or(|account| account.lamports == 0),
+            "Record should close after the last tranche once withdrawn"
+        );
+
+        run_withdraw(&mut svm, &depositor, &tranche_split, &config_pda, 2);
+    }
+}
@@ -0,0 +1,128 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_crank_initialize_reserve_ix, build_crank_merge_reserve_ix, build_set_crank_tip_ix,
+        fee_vault_pda, run_initialize, send, setup_svm, with_crank_tip,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+    const TIP: u64 = 1_000_000;
+
+    fn lamports(svm: &LiteSVM, pubkey: &Pubkey) -> u64 {
+        svm.get_account(pubkey)
+            .map_or(0, |account| account.lamports)
+    }
+
+    #[test]
+    fn test_crank_tip_pays_the_cranker_from_the_fee_vault() {
+        let mut svm = setup_svm();
+        let (admin, _, _, config_pda, stake_account_main, stake_account_reserve, vote_pubkey) =
+            run_initialize(&mut svm);
+        let stake_program = Pubkey::from(STAKE_PROGRAM_ID);
+
+        assert!(!send(
+            &mut svm,
+            build_set_crank_tip_ix(&Keypair::new().pubkey(), &config_pda, TIP),
+            &admin,
+        ));
+        assert!(send(
+            &mut svm,
+            build_set_crank_tip_ix(&admin.pubkey(), &config_pda, TIP),
+            &admin,
+        ));
+        svm.airdrop(&fee_vault_pda(), LAMPORTS_PER_SOL).unwrap();
+
+        let keeper = Keypair::new();
+        svm.airdrop(&keeper.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+        let vault_before = lamports(&svm, &fee_vault_pda());
+        let keeper_before = lamports(&svm, &keeper.pubkey());
+        let ix = build_crank_initialize_reserve_ix(
+            &config_pda,
+            &stake_account_reserve,
+            &vote_pubkey,
+            &system_program::ID,
+            &stake_program,
+        );
+        assert!(send(
+            &mut svm,
+            with_crank_tip(ix, &keeper.pubkey()),
+            &keeper
+        ));
+        assert_eq!(lamports(&svm, &fee_vault_pda()), vault_before - TIP);
+        assert!(
+            lamports(&svm, &keeper.pubkey()) > keeper_before,
+            "The tip covers the transaction fee"
+        );
+
+        let ix = build_crank_merge_reserve_ix(
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &system_program::ID,
+            &stake_program,
+        );
+        assert!(send(
+            &mut svm,
+            with_crank_tip(ix, &keeper.pubkey()),
+            &keeper
+        ));
+        assert_eq!(lamports(&svm, &fee_vault_pda()), vault_before - 2 * TIP);
+    }
+
+    #[test]
+    fn test_crank_tip_is_skipped_when_the_fee_vault_cannot_pay() {
+        let mut svm = setup_svm();
+        let (admin, _, _, config_pda, stake_account_main, stake_account_reserve, vote_pubkey) =
+            run_initialize(&mut svm);
+        let stake_program = Pubkey::from(STAKE_PROGRAM_ID);
+
+        assert!(send(
+            &mut svm,
+            build_set_crank_tip_ix(&admin.pubkey(), &config_pda, TIP),
+            &admin,
+        ));
+
+        let keeper = Keypair::new();
+        svm.airdrop(&keeper.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+        let ix = build_crank_initialize_reserve_ix(
+            &config_pda,
+            &stake_account_reserve,
+            &vote_pubkey,
+            &system_program::ID,
+            &stake_program,
+        );
+        let mut wrong_vault = with_crank_tip(ix.clone(), &keeper.pubkey());
+        wrong_vault.accounts.last_mut().unwrap().pubkey = Pubkey::new_unique();
+        assert!(
+            !send(&mut svm, wrong_vault, &keeper),
+            "The fee vault must be the program's PDA"
+        );
+
+        assert!(
+            send(&mut svm, with_crank_tip(ix, &keeper.pubkey()), &keeper),
+            "An empty fee vault does not fail the crank"
+        );
+        assert_eq!(lamports(&svm, &fee_vault_pda()), 0);
+
+        let ix = build_crank_merge_reserve_ix(
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &system_program::ID,
+            &stake_program,
+        );
+        assert!(send(&mut svm, ix, &keeper), "The tip is optional");
+    }
+}
@@ -0,0 +1,165 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{pubkey::Pubkey, signature::Signer};
+
+    use crate::test_helpers::test_helpers::{
+        add_lamports, add_stake_rewards, build_crank_update_rate_ix, get_token_balance,
+        run_crank_management_fee, run_deposit, run_initialize, send, setup_svm, warp_epoch,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    /// Reads the config's cached `(rate_lamports, rate_lst_supply, rate_epoch)`.
+    fn cached_rate(svm: &LiteSVM, config_pda: &Pubkey) -> (u64, u64, u64) {
+        let config = svm.get_account(config_pda).unwrap().data;
+        let field = |start: usize| u64::from_le_bytes(config[start..start + 8].try_into().unwrap());
+        (field(390), field(398), field(406))
+    }
+
+    #[test]
+    fn test_deposit_prices_against_the_cached_rate() {
+        let mut svm = setup_svm();
+        let (admin, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+        warp_epoch(&mut svm, 1);
+        run_crank_management_fee(
+            &mut svm,
+            &admin,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &mint,
+        );
+
+        assert!(send(
+            &mut svm,
+            build_crank_update_rate_ix(
+                &config_pda,
+                &stake_account_main,
+                &stake_account_reserve,
+                &mint,
+            ),
+            &admin,
+        ));
+        let (rate_lamports, rate_lst_supply, rate_epoch) = cached_rate(&svm, &config_pda);
+        assert_eq!(rate_epoch, 1);
+
+        // Rewards landing mid-epoch do not move the rate deposits pay.
+        add_stake_rewards(&mut svm, &stake_account_main, LAMPORTS_PER_SOL);
+        let deposited = 2 * LAMPORTS_PER_SOL;
+        let (_, ata) = run_deposit(
+            &mut svm,
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+            deposited,
+        );
+        let cached_lst = get_token_balance(&svm, &ata);
+        assert_eq!(
+            cached_lst as u128,
+            deposited as u128 * rate_lst_supply as u128 / rate_lamports as u128
+        );
+
+        // The next epoch prices against the live managed lamports until the
+        // crank runs again.
+        warp_epoch(&mut svm, 2);
+        let (_, ata) = run_deposit(
+            &mut svm,
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+            deposited,
+        );
+        assert!(
+            get_token_balance(&svm, &ata) < cached_lst,
+            "The rewards raised the live rate"
+        );
+    }
+
+    #[test]
+    fn test_lamports_sent_to_the_pool_do_not_move_the_rate() {
+        let mut svm = setup_svm();
+        let (_, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+        let deposited = 2 * LAMPORTS_PER_SOL;
+        let (_, ata) = run_deposit(
+            &mut svm,
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+            deposited,
+        );
+        let minted = get_token_balance(&svm, &ata);
+
+        add_lamports(&mut svm, &stake_account_reserve, 5 * LAMPORTS_PER_SOL);
+        add_lamports(&mut svm, &stake_account_main, 5 * LAMPORTS_PER_SOL);
+        let (_, ata) = run_deposit(
+            &mut svm,
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+            deposited,
+        );
+        assert_eq!(
+            get_token_balance(&svm, &ata),
+            minted,
+            "Only deposits and stake rewards count toward the rate"
+        );
+    }
+
+    #[test]
+    fn test_crank_update_rate_runs_once_per_epoch() {
+        let mut svm = setup_svm();
+        let (admin, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+        let update = build_crank_update_rate_ix(
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+        warp_epoch(&mut svm, 1);
+
+        assert!(
+            !send(&mut svm, update.clone(), &admin),
+            "The rate update should wait for the epoch's management fee"
+        );
+        run_crank_management_fee(
+            &mut svm,
+            &admin,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+        assert!(send(&mut svm, update.clone(), &admin));
+        assert!(
+            !send(&mut svm, update.clone(), &admin),
+            "The rate is already cached this epoch"
+        );
+
+        let mut wrong_mint = update.clone();
+        wrong_mint.accounts[3].pubkey = Pubkey::new_unique();
+        warp_epoch(&mut svm, 2);
+        run_crank_management_fee(
+            &mut svm,
+            &admin,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+        assert!(!send(&mut svm, wrong_mint, &admin));
+        assert!(send(&mut svm, update, &admin));
+        assert_eq!(cached_rate(&svm, &config_pda).2, 2);
+    }
+}
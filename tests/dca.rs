@@ -0,0 +1,113 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        signature::{Keypair, Signer},
+        sysvar::clock::Clock,
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_close_dca_schedule_ix, build_crank_dca_ix, build_create_dca_schedule_ix,
+        create_and_fund_ata, dca_schedule_pda, get_token_balance, run_initialize, send, setup_svm,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    fn advance_time(svm: &mut LiteSVM, secs: i64) {
+        let mut clock = svm.get_sysvar::<Clock>();
+        clock.unix_timestamp += secs;
+        svm.set_sysvar::<Clock>(&clock);
+    }
+
+    #[test]
+    fn test_dca_executes_on_schedule_and_tips_keeper() {
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+
+        let user = Keypair::new();
+        let keeper = Keypair::new();
+        svm.airdrop(&user.pubkey(), 10 * LAMPORTS_PER_SOL).unwrap();
+        svm.airdrop(&keeper.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        let user_ata = create_and_fund_ata(&mut svm, &user.pubkey(), &mint, 0);
+
+        let tip = 100_000;
+        let interval = 86_400;
+        assert!(send(
+            &mut svm,
+            build_create_dca_schedule_ix(
+                &user.pubkey(),
+                0,
+                LAMPORTS_PER_SOL,
+                interval,
+                tip,
+                2 * (LAMPORTS_PER_SOL + tip) + LAMPORTS_PER_SOL / 2,
+            ),
+            &user
+        ));
+
+        let crank = |svm: &mut LiteSVM| {
+            let ix = build_crank_dca_ix(
+                &keeper.pubkey(),
+                &user.pubkey(),
+                0,
+                &config_pda,
+                &mint,
+                &stake_account_main,
+                &stake_account_reserve,
+            );
+            send(svm, ix, &keeper)
+        };
+
+        let keeper_before = svm.get_account(&keeper.pubkey()).unwrap().lamports;
+        let reserve_before = svm.get_account(&stake_account_reserve).unwrap().lamports;
+        assert!(crank(&mut svm), "The first deposit is due immediately");
+        assert_eq!(
+            svm.get_account(&keeper.pubkey()).unwrap().lamports,
+            keeper_before + tip - 5_000
+        );
+        assert_eq!(
+            svm.get_account(&stake_account_reserve).unwrap().lamports,
+            reserve_before + LAMPORTS_PER_SOL
+        );
+        let lst_after_first = get_token_balance(&svm, &user_ata);
+        assert!(lst_after_first > 0);
+
+        assert!(!crank(&mut svm), "The next deposit is not due yet");
+
+        advance_time(&mut svm, interval);
+        assert!(crank(&mut svm));
+        assert!(get_token_balance(&svm, &user_ata) > lst_after_first);
+
+        advance_time(&mut svm, interval);
+        assert!(
+            !crank(&mut svm),
+            "The remaining budget cannot cover another deposit and tip"
+        );
+
+        let schedule_balance = svm
+            .get_account(&dca_schedule_pda(&user.pubkey(), 0))
+            .unwrap()
+            .lamports;
+        let user_before = svm.get_account(&user.pubkey()).unwrap().lamports;
+        assert!(send(
+            &mut svm,
+            build_close_dca_schedule_ix(&user.pubkey(), 0),
+            &user
+        ));
+        assert_eq!(
+            svm.get_account(&user.pubkey()).unwrap().lamports,
+            user_before + schedule_balance - 5_000
+        );
+    }
+}
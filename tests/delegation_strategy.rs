@@ -0,0 +1,146 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_liquid_staking::delegation_strategy::{
+        DelegationStrategy, DelegationStrategyKind, EvenSplit, ScoreBased, WeightBased,
+    };
+    use solana_liquid_staking::state::{ValidatorEntry, ValidatorList};
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+
+    use crate::test_helpers::test_helpers::{
+        build_join_validator_set_ix, build_set_delegation_strategy_ix,
+        build_set_validator_metrics_ix, build_set_validator_status_ix, create_vote_account,
+        get_validator_entries, run_initialize, send, setup_svm,
+    };
+
+    fn entry(weight: u32, score: u32) -> ValidatorEntry {
+        ValidatorEntry {
+            vote_account: [0u8; 32],
+            weight,
+            score,
+            stake_lamports: 0,
+            transient_lamports: 0,
+            recorded_epoch: 0,
+        }
+    }
+
+    fn targets(
+        strategy: &dyn DelegationStrategy,
+        validators: &[ValidatorEntry],
+        total_lamports: u64,
+    ) -> Vec<u64> {
+        let mut targets = [u64::MAX; ValidatorList::CAPACITY];
+        strategy.targets(validators, total_lamports, &mut targets);
+        targets[..validators.len()].to_vec()
+    }
+
+    #[test]
+    fn test_even_split_hands_out_remainder() {
+        let validators = [entry(5, 0), entry(1, 0), entry(0, 7)];
+        assert_eq!(targets(&EvenSplit, &validators, 10), vec![4, 3, 3]);
+        assert!(targets(&EvenSplit, &[], 10).is_empty());
+    }
+
+    #[test]
+    fn test_weight_based_is_proportional_to_weight() {
+        let validators = [entry(1, 9), entry(3, 0), entry(0, 9)];
+        assert_eq!(
+            targets(&WeightBased, &validators, 1_000_000_001),
+            vec![250_000_001, 750_000_000, 0]
+        );
+    }
+
+    #[test]
+    fn test_score_based_is_proportional_to_score() {
+        let validators = [entry(1, 9), entry(3, 0), entry(1, 1)];
+        assert_eq!(targets(&ScoreBased, &validators, 100), vec![90, 0, 10]);
+    }
+
+    #[test]
+    fn test_zero_shares_fall_back_to_even_split() {
+        let validators = [entry(0, 0), entry(0, 0)];
+        assert_eq!(targets(&WeightBased, &validators, 7), vec![4, 3]);
+        assert_eq!(targets(&ScoreBased, &validators, 7), vec![4, 3]);
+    }
+
+    #[test]
+    fn test_kind_selects_strategy() {
+        let validators = [entry(1, 3), entry(3, 1)];
+        let mut even = [0u64; 2];
+        let mut weighted = [0u64; 2];
+        let mut scored = [0u64; 2];
+
+        DelegationStrategyKind::try_from(0)
+            .unwrap()
+            .targets(&validators, 400, &mut even);
+        DelegationStrategyKind::try_from(1)
+            .unwrap()
+            .targets(&validators, 400, &mut weighted);
+        DelegationStrategyKind::try_from(2)
+            .unwrap()
+            .targets(&validators, 400, &mut scored);
+
+        assert_eq!(even, [200, 200]);
+        assert_eq!(weighted, [100, 300]);
+        assert_eq!(scored, [300, 100]);
+        assert!(DelegationStrategyKind::try_from(3).is_err());
+    }
+
+    #[test]
+    fn test_admin_sets_strategy_and_metrics() {
+        let mut svm = setup_svm();
+        let (initializer, _, _, config_pda, _, _, _) = run_initialize(&mut svm);
+
+        let config = svm.get_account(&config_pda).unwrap();
+        assert_eq!(config.data[184], DelegationStrategyKind::EvenSplit as u8);
+
+        let ix = build_set_delegation_strategy_ix(&initializer.pubkey(), &config_pda, 3);
+        assert!(
+            !send(&mut svm, ix, &initializer),
+            "Unknown strategies should be rejected"
+        );
+
+        let ix = build_set_delegation_strategy_ix(&initializer.pubkey(), &config_pda, 2);
+        assert!(
+            send(&mut svm, ix, &initializer),
+            "Admin should set strategy"
+        );
+        let config = svm.get_account(&config_pda).unwrap();
+        assert_eq!(config.data[184], DelegationStrategyKind::ScoreBased as u8);
+
+        let operator = Keypair::new();
+        svm.airdrop(&operator.pubkey(), 20_000_000_000).unwrap();
+        let vote_pubkey = create_vote_account(&mut svm, &operator.pubkey());
+
+        let ix =
+            build_set_validator_metrics_ix(&initializer.pubkey(), &config_pda, &vote_pubkey, 5, 80);
+        assert!(
+            !send(&mut svm, ix, &initializer),
+            "Metrics need an existing validator list"
+        );
+
+        let ix = build_join_validator_set_ix(&operator.pubkey(), &vote_pubkey, 10_000_000_000);
+        assert!(send(&mut svm, ix, &operator), "Join should succeed");
+        let ix =
+            build_set_validator_status_ix(&initializer.pubkey(), &config_pda, &vote_pubkey, true);
+        assert!(
+            send(&mut svm, ix, &initializer),
+            "Activation should succeed"
+        );
+        assert_eq!(get_validator_entries(&svm), vec![(vote_pubkey, 1, 0)]);
+
+        let ix =
+            build_set_validator_metrics_ix(&operator.pubkey(), &config_pda, &vote_pubkey, 5, 80);
+        assert!(
+            !send(&mut svm, ix, &operator),
+            "Only the admin may set metrics"
+        );
+
+        let ix =
+            build_set_validator_metrics_ix(&initializer.pubkey(), &config_pda, &vote_pubkey, 5, 80);
+        assert!(send(&mut svm, ix, &initializer), "Admin should set metrics");
+        assert_eq!(get_validator_entries(&svm), vec![(vote_pubkey, 5, 80)]);
+    }
+}
@@ -2,16 +2,80 @@ mod test_helpers;
 
 #[cfg(test)]
 mod tests {
+    use solana_sdk::instruction::AccountMeta;
     use solana_sdk::pubkey::Pubkey;
     use solana_sdk::signature::Keypair;
     use solana_sdk::signer::Signer;
     use solana_sdk::transaction::Transaction;
 
     use crate::test_helpers::test_helpers::{
-        build_deposit_ix, create_and_fund_ata, print_transaction_logs, run_deposit,
-        run_initialize, setup_svm,
+        build_close_deposit_key_ix, build_deposit_ix, build_set_trusted_caller_ix,
+        create_and_fund_ata, deposit_key_pda, get_lst_issued, get_mint_supply, get_token_balance,
+        print_transaction_logs, run_deposit, run_initialize, set_deposit_account, setup_svm,
+        trusted_callers_pda, warp_slots, DEPOSIT_KEY, PROGRAM_ID,
     };
 
+    #[test]
+    fn test_deposit_tracks_issued_lst_past_direct_burns() {
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+        assert_eq!(get_lst_issued(&svm), get_mint_supply(&svm, &mint));
+
+        let (depositor, depositor_ata) = run_deposit(
+            &mut svm,
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+            2_000_000_000,
+        );
+        assert_eq!(get_lst_issued(&svm), get_mint_supply(&svm, &mint));
+
+        // A holder burning through the token program leaves the supply below
+        // what the program issued, which must not block further mints.
+        let burn_ix = spl_token::instruction::burn(
+            &spl_token::ID,
+            &depositor_ata,
+            &mint,
+            &depositor.pubkey(),
+            &[],
+            1_000,
+        )
+        .unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[burn_ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+        svm.send_transaction(tx)
+            .expect("Direct burn should succeed");
+        let issued = get_lst_issued(&svm);
+        assert_eq!(issued, get_mint_supply(&svm, &mint) + 1_000);
+
+        let supply_before = get_mint_supply(&svm, &mint);
+        run_deposit(
+            &mut svm,
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+            1_000_000_000,
+        );
+        let minted = get_mint_supply(&svm, &mint) - supply_before;
+        assert!(minted > 0);
+        assert_eq!(get_lst_issued(&svm), issued + minted);
+    }
+
     #[test]
     fn test_deposit_success() {
         let mut svm = setup_svm();
@@ -346,4 +410,321 @@ mod tests {
             deposit_amount,
         );
     }
+
+    #[test]
+    fn test_deposit_tiny_pool_excludes_rent() {
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+
+        // After initialize the main and reserve stake accounts each hold
+        // 1 SOL on top of their rent-exempt reserve, backing 1 LST. Rent must
+        // not count towards the pool, so 1 SOL deposited mints exactly 0.5 LST.
+        let (_depositor, depositor_ata) = run_deposit(
+            &mut svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            1_000_000_000,
+        );
+
+        assert_eq!(
+            get_token_balance(&svm, &depositor_ata),
+            500_000_000,
+            "Rent-exempt reserves should not inflate the LST price"
+        );
+    }
+
+    /// Builds a deposit requesting the skip-checks path with the trailing
+    /// instructions sysvar and trusted callers PDA.
+    fn build_skip_checks_deposit_ix(
+        config_pda: &Pubkey,
+        depositor: &Pubkey,
+        depositor_ata: &Pubkey,
+        token_mint: &Pubkey,
+        stake_account_main: &Pubkey,
+        stake_account_reserve: &Pubkey,
+    ) -> solana_sdk::instruction::Instruction {
+        let mut ix = build_deposit_ix(
+            config_pda,
+            depositor,
+            depositor_ata,
+            token_mint,
+            stake_account_main,
+            stake_account_reserve,
+            2_000_000_000,
+            true,
+        );
+        let config_bump = Pubkey::find_program_address(&[b"config"], &PROGRAM_ID).1;
+        ix.data.extend_from_slice(&[1, config_bump]);
+        ix.accounts.push(AccountMeta::new_readonly(
+            solana_sdk::sysvar::instructions::id(),
+            false,
+        ));
+        ix.accounts
+            .push(AccountMeta::new_readonly(trusted_callers_pda(), false));
+        ix
+    }
+
+    #[test]
+    fn test_deposit_skip_checks_rejected_for_direct_call() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+
+        // Even an approved program id does not make a top-level call trusted.
+        let approve_ix =
+            build_set_trusted_caller_ix(&initializer.pubkey(), &config_pda, &PROGRAM_ID, true);
+        let tx = Transaction::new_signed_with_payer(
+            &[approve_ix],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+        assert!(svm.send_transaction(tx).is_ok(), "Admin should approve");
+
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 10_000_000_000).unwrap();
+        let depositor_ata =
+            create_and_fund_ata(&mut svm, &depositor.pubkey(), &token_mint.pubkey(), 0);
+
+        let ix = build_skip_checks_deposit_ix(
+            &config_pda,
+            &depositor.pubkey(),
+            &depositor_ata,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(
+            result.is_err(),
+            "Skip-checks path should only be honored for CPI callers"
+        );
+    }
+
+    #[test]
+    fn test_deposit_skip_checks_without_accounts() {
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 10_000_000_000).unwrap();
+        let depositor_ata =
+            create_and_fund_ata(&mut svm, &depositor.pubkey(), &token_mint.pubkey(), 0);
+
+        let mut ix = build_deposit_ix(
+            &config_pda,
+            &depositor.pubkey(),
+            &depositor_ata,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            2_000_000_000,
+            true,
+        );
+        let config_bump = Pubkey::find_program_address(&[b"config"], &PROGRAM_ID).1;
+        ix.data.extend_from_slice(&[1, config_bump]);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(
+            result.is_err(),
+            "Skip-checks path should require the introspection accounts"
+        );
+    }
+
+    #[test]
+    fn test_deposit_idempotency_key_blocks_retry() {
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 10_000_000_000).unwrap();
+        let depositor_ata =
+            create_and_fund_ata(&mut svm, &depositor.pubkey(), &token_mint.pubkey(), 0);
+
+        let idempotency_key = [7u8; 32];
+        let deposit_with_key = |svm: &mut litesvm::LiteSVM| {
+            let mut ix = build_deposit_ix(
+                &config_pda,
+                &depositor.pubkey(),
+                &depositor_ata,
+                &token_mint.pubkey(),
+                &stake_account_main,
+                &stake_account_reserve,
+                2_000_000_000,
+                true,
+            );
+            ix.data.extend_from_slice(&idempotency_key);
+            set_deposit_account(
+                &mut ix,
+                DEPOSIT_KEY,
+                AccountMeta::new(
+                    deposit_key_pda(&depositor.pubkey(), &idempotency_key),
+                    false,
+                ),
+            );
+
+            let tx = Transaction::new_signed_with_payer(
+                &[ix],
+                Some(&depositor.pubkey()),
+                &[&depositor],
+                svm.latest_blockhash(),
+            );
+            let result = svm.send_transaction(tx);
+            print_transaction_logs(&result);
+            svm.expire_blockhash();
+            result.is_ok()
+        };
+
+        assert!(deposit_with_key(&mut svm), "First deposit should succeed");
+        let lst_balance = get_token_balance(&svm, &depositor_ata);
+        let reserve_balance = svm.get_balance(&stake_account_reserve).unwrap();
+
+        assert!(
+            !deposit_with_key(&mut svm),
+            "A retry with the same key should fail"
+        );
+        assert_eq!(get_token_balance(&svm, &depositor_ata), lst_balance);
+        assert_eq!(
+            svm.get_balance(&stake_account_reserve).unwrap(),
+            reserve_balance
+        );
+
+        let close = |svm: &mut litesvm::LiteSVM| {
+            let ix = build_close_deposit_key_ix(&depositor.pubkey(), &idempotency_key);
+            let tx = Transaction::new_signed_with_payer(
+                &[ix],
+                Some(&depositor.pubkey()),
+                &[&depositor],
+                svm.latest_blockhash(),
+            );
+            let result = svm.send_transaction(tx);
+            print_transaction_logs(&result);
+            svm.expire_blockhash();
+            result.is_ok()
+        };
+
+        assert!(
+            !close(&mut svm),
+            "The deposit key should stay until it expires"
+        );
+
+        warp_slots(&mut svm, 300);
+        let key_rent = svm
+            .get_balance(&deposit_key_pda(&depositor.pubkey(), &idempotency_key))
+            .unwrap();
+        let depositor_before = svm.get_balance(&depositor.pubkey()).unwrap();
+        assert!(close(&mut svm), "An expired deposit key should close");
+        assert_eq!(
+            svm.get_balance(&depositor.pubkey()).unwrap(),
+            depositor_before + key_rent - 5_000
+        );
+    }
+
+    #[test]
+    fn test_deposit_min_lst_out_bounds_the_mint() {
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 10_000_000_000).unwrap();
+        let depositor_ata =
+            create_and_fund_ata(&mut svm, &depositor.pubkey(), &token_mint.pubkey(), 0);
+
+        let deposit_with_min = |svm: &mut litesvm::LiteSVM, min_lst_out: u64| {
+            let mut ix = build_deposit_ix(
+                &config_pda,
+                &depositor.pubkey(),
+                &depositor_ata,
+                &token_mint.pubkey(),
+                &stake_account_main,
+                &stake_account_reserve,
+                2_000_000_000,
+                true,
+            );
+            // No idempotency key and no memo before the minimum.
+            ix.data.extend_from_slice(&[0u8; 64]);
+            ix.data.extend_from_slice(&min_lst_out.to_le_bytes());
+
+            let tx = Transaction::new_signed_with_payer(
+                &[ix],
+                Some(&depositor.pubkey()),
+                &[&depositor],
+                svm.latest_blockhash(),
+            );
+            let result = svm.send_transaction(tx);
+            print_transaction_logs(&result);
+            svm.expire_blockhash();
+            result.is_ok()
+        };
+
+        assert!(
+            !deposit_with_min(&mut svm, 2_000_000_000),
+            "A mint below the minimum should fail"
+        );
+        assert_eq!(get_token_balance(&svm, &depositor_ata), 0);
+
+        assert!(
+            deposit_with_min(&mut svm, 500_000_000),
+            "A mint above the minimum should succeed"
+        );
+        assert!(get_token_balance(&svm, &depositor_ata) >= 500_000_000);
+    }
 }
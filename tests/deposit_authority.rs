@@ -0,0 +1,278 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        sysvar::clock::Clock,
+        transaction::Transaction,
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_crank_dca_ix, build_create_dca_schedule_ix, build_create_deposit_session_ix,
+        build_deposit_ix, build_deposit_with_session_ix, build_mint_to_wrapper_ix,
+        build_set_deposit_authority_ix, build_set_wrapper_program_ix, create_and_fund_ata,
+        print_transaction_logs, run_initialize, send_with_unsigned_signers, set_deposit_account,
+        setup_pool, setup_svm, wrapper_authority_pda, Pool, PoolSetup, DEPOSIT_AUTHORITY,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    fn send(svm: &mut LiteSVM, ix: Instruction, signers: &[&Keypair]) -> bool {
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&signers[0].pubkey()),
+            signers,
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        svm.expire_blockhash();
+        result.is_ok()
+    }
+
+    /// Initializes a pool and makes it permissioned, returning the deposit
+    /// authority.
+    fn permissioned_pool(svm: &mut LiteSVM) -> (Pool, Keypair) {
+        let pool = setup_pool(svm, PoolSetup::default());
+        let authority = Keypair::new();
+        svm.airdrop(&authority.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .unwrap();
+        assert!(send(
+            svm,
+            build_set_deposit_authority_ix(
+                &pool.admin.pubkey(),
+                &pool.config_pda,
+                &authority.pubkey()
+            ),
+            &[&pool.admin],
+        ));
+
+        (pool, authority)
+    }
+
+    fn cosigned(mut ix: Instruction, authority: &Keypair) -> Instruction {
+        ix.accounts
+            .push(AccountMeta::new_readonly(authority.pubkey(), true));
+        ix
+    }
+
+    #[test]
+    fn test_deposit_authority_must_cosign_deposits() {
+        let mut svm = setup_svm();
+        let (admin, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+        let authority = Keypair::new();
+        svm.airdrop(&authority.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .unwrap();
+
+        let outsider = Keypair::new();
+        svm.airdrop(&outsider.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        assert!(!send(
+            &mut svm,
+            build_set_deposit_authority_ix(&outsider.pubkey(), &config_pda, &authority.pubkey()),
+            &[&outsider],
+        ));
+        assert!(send(
+            &mut svm,
+            build_set_deposit_authority_ix(&admin.pubkey(), &config_pda, &authority.pubkey()),
+            &[&admin],
+        ));
+
+        let deposit_ix = |svm: &mut LiteSVM, depositor: &Keypair| {
+            svm.airdrop(&depositor.pubkey(), 10 * LAMPORTS_PER_SOL)
+                .unwrap();
+            let depositor_ata = create_and_fund_ata(svm, &depositor.pubkey(), &mint, 0);
+            build_deposit_ix(
+                &config_pda,
+                &depositor.pubkey(),
+                &depositor_ata,
+                &mint,
+                &stake_account_main,
+                &stake_account_reserve,
+                2 * LAMPORTS_PER_SOL,
+                true,
+            )
+        };
+
+        let depositor = Keypair::new();
+        let ix = deposit_ix(&mut svm, &depositor);
+        assert!(
+            !send(&mut svm, ix.clone(), &[&depositor]),
+            "Deposits need the authority's signature"
+        );
+
+        let mut forged = ix.clone();
+        set_deposit_account(
+            &mut forged,
+            DEPOSIT_AUTHORITY,
+            AccountMeta::new_readonly(depositor.pubkey(), true),
+        );
+        assert!(
+            !send(&mut svm, forged, &[&depositor]),
+            "Another signer does not stand in for the authority"
+        );
+
+        let mut misplaced = ix.clone();
+        misplaced
+            .accounts
+            .push(AccountMeta::new_readonly(authority.pubkey(), true));
+        assert!(
+            !send(&mut svm, misplaced, &[&depositor, &authority]),
+            "The authority only counts at its own position"
+        );
+
+        let mut cosigned = ix;
+        set_deposit_account(
+            &mut cosigned,
+            DEPOSIT_AUTHORITY,
+            AccountMeta::new_readonly(authority.pubkey(), true),
+        );
+        assert!(send(&mut svm, cosigned, &[&depositor, &authority]));
+
+        let ix = deposit_ix(&mut svm, &authority);
+        assert!(
+            send(&mut svm, ix, &[&authority]),
+            "The authority deposits for itself without passing itself twice"
+        );
+
+        assert!(send(
+            &mut svm,
+            build_set_deposit_authority_ix(&admin.pubkey(), &config_pda, &Pubkey::default()),
+            &[&admin],
+        ));
+        let depositor = Keypair::new();
+        let ix = deposit_ix(&mut svm, &depositor);
+        assert!(
+            send(&mut svm, ix, &[&depositor]),
+            "The zero key opens deposits again"
+        );
+    }
+
+    #[test]
+    fn test_deposit_authority_must_cosign_session_deposits() {
+        let mut svm = setup_svm();
+        let (pool, authority) = permissioned_pool(&mut svm);
+
+        let user = Keypair::new();
+        let session_key = Keypair::new();
+        svm.airdrop(&user.pubkey(), 10 * LAMPORTS_PER_SOL).unwrap();
+        svm.airdrop(&session_key.pubkey(), LAMPORTS_PER_SOL)
+            .unwrap();
+        create_and_fund_ata(&mut svm, &user.pubkey(), &pool.mint, 0);
+        let expires_at = svm.get_sysvar::<Clock>().unix_timestamp + 3_600;
+        assert!(send(
+            &mut svm,
+            build_create_deposit_session_ix(
+                &user.pubkey(),
+                &session_key.pubkey(),
+                3 * LAMPORTS_PER_SOL,
+                expires_at,
+            ),
+            &[&user],
+        ));
+
+        let ix = build_deposit_with_session_ix(
+            &session_key.pubkey(),
+            &user.pubkey(),
+            &pool.config_pda,
+            &pool.mint,
+            &pool.stake_account_main,
+            &pool.stake_account_reserve,
+            LAMPORTS_PER_SOL,
+        );
+        assert!(
+            !send(&mut svm, ix.clone(), &[&session_key]),
+            "Escrowed session funds need the authority's signature"
+        );
+        assert!(send(
+            &mut svm,
+            cosigned(ix, &authority),
+            &[&session_key, &authority],
+        ));
+    }
+
+    #[test]
+    fn test_deposit_authority_must_cosign_dca_deposits() {
+        let mut svm = setup_svm();
+        let (pool, authority) = permissioned_pool(&mut svm);
+
+        let user = Keypair::new();
+        let keeper = Keypair::new();
+        svm.airdrop(&user.pubkey(), 10 * LAMPORTS_PER_SOL).unwrap();
+        svm.airdrop(&keeper.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        create_and_fund_ata(&mut svm, &user.pubkey(), &pool.mint, 0);
+        assert!(send(
+            &mut svm,
+            build_create_dca_schedule_ix(
+                &user.pubkey(),
+                0,
+                LAMPORTS_PER_SOL,
+                86_400,
+                0,
+                2 * LAMPORTS_PER_SOL,
+            ),
+            &[&user],
+        ));
+
+        let crank = |keeper: &Keypair| {
+            build_crank_dca_ix(
+                &keeper.pubkey(),
+                &user.pubkey(),
+                0,
+                &pool.config_pda,
+                &pool.mint,
+                &pool.stake_account_main,
+                &pool.stake_account_reserve,
+            )
+        };
+        assert!(
+            !send(&mut svm, crank(&keeper), &[&keeper]),
+            "A keeper cannot execute a schedule without the authority"
+        );
+        assert!(send(
+            &mut svm,
+            cosigned(crank(&keeper), &authority),
+            &[&keeper, &authority],
+        ));
+    }
+
+    #[test]
+    fn test_deposit_authority_must_cosign_wrapper_mints() {
+        let mut svm = setup_svm();
+        let (pool, authority) = permissioned_pool(&mut svm);
+
+        let wrapper_program = Pubkey::new_unique();
+        let wrapper_authority = wrapper_authority_pda(&wrapper_program);
+        svm.airdrop(&wrapper_authority, 10 * LAMPORTS_PER_SOL)
+            .unwrap();
+        create_and_fund_ata(&mut svm, &wrapper_authority, &pool.mint, 0);
+        assert!(send(
+            &mut svm,
+            build_set_wrapper_program_ix(&pool.admin.pubkey(), &pool.config_pda, &wrapper_program),
+            &[&pool.admin],
+        ));
+
+        let ix = build_mint_to_wrapper_ix(
+            &wrapper_authority,
+            &pool.config_pda,
+            &pool.mint,
+            &pool.stake_account_main,
+            &pool.stake_account_reserve,
+            2 * LAMPORTS_PER_SOL,
+        );
+        assert!(
+            !send_with_unsigned_signers(&mut svm, ix.clone(), &authority),
+            "The approved wrapper still needs the authority's signature"
+        );
+        assert!(send_with_unsigned_signers(
+            &mut svm,
+            cosigned(ix, &authority),
+            &authority,
+        ));
+    }
+}
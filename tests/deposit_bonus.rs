@@ -0,0 +1,116 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        instruction::AccountMeta,
+        signature::{Keypair, Signer},
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_deposit_ix, build_set_bonus_schedule_ix, create_and_fund_ata, get_mint_supply,
+        get_token_balance, send, set_deposit_account, setup_pool, setup_svm, warp_epoch, Pool,
+        PoolSetup, DEPOSIT_TREASURY_ATA,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    fn setup(svm: &mut LiteSVM, treasury_balance: u64) -> Pool {
+        setup_pool(
+            svm,
+            PoolSetup {
+                treasury_balance: Some(treasury_balance),
+                ..PoolSetup::default()
+            },
+        )
+    }
+
+    /// Deposits `lamports` with the treasury ATA appended and returns
+    /// (LST minted, bonus received).
+    fn deposit_with_bonus(svm: &mut LiteSVM, pool: &Pool, lamports: u64) -> (u64, u64) {
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 20 * LAMPORTS_PER_SOL)
+            .unwrap();
+        let depositor_ata = create_and_fund_ata(svm, &depositor.pubkey(), &pool.mint, 0);
+
+        let mut ix = build_deposit_ix(
+            &pool.config_pda,
+            &depositor.pubkey(),
+            &depositor_ata,
+            &pool.mint,
+            &pool.stake_account_main,
+            &pool.stake_account_reserve,
+            lamports,
+            true,
+        );
+        set_deposit_account(
+            &mut ix,
+            DEPOSIT_TREASURY_ATA,
+            AccountMeta::new(pool.treasury_ata, false),
+        );
+
+        let supply_before = get_mint_supply(svm, &pool.mint);
+        assert!(send(svm, ix, &depositor), "Deposit should succeed");
+
+        let minted = get_mint_supply(svm, &pool.mint) - supply_before;
+        let received = get_token_balance(svm, &depositor_ata);
+        (minted, received - minted)
+    }
+
+    #[test]
+    fn test_deposit_bonus_paid_from_treasury_before_cutoff() {
+        let mut svm = setup_svm();
+        let pool = setup(&mut svm, 100 * LAMPORTS_PER_SOL);
+
+        let ix = build_set_bonus_schedule_ix(&pool.admin.pubkey(), &pool.config_pda, 500, 5);
+        assert!(send(&mut svm, ix, &pool.admin));
+
+        let (minted, bonus) = deposit_with_bonus(&mut svm, &pool, 2 * LAMPORTS_PER_SOL);
+        assert_eq!(bonus, minted * 500 / 10_000);
+        assert_eq!(
+            get_token_balance(&svm, &pool.treasury_ata),
+            100 * LAMPORTS_PER_SOL - bonus
+        );
+
+        warp_epoch(&mut svm, 5);
+        let (_, bonus) = deposit_with_bonus(&mut svm, &pool, 2 * LAMPORTS_PER_SOL);
+        assert_eq!(bonus, 0, "No bonus from the cutoff epoch on");
+    }
+
+    #[test]
+    fn test_deposit_bonus_capped_by_treasury_balance() {
+        let mut svm = setup_svm();
+        let pool = setup(&mut svm, 1_000);
+
+        let ix = build_set_bonus_schedule_ix(&pool.admin.pubkey(), &pool.config_pda, 10_000, 5);
+        assert!(send(&mut svm, ix, &pool.admin));
+
+        let (_, bonus) = deposit_with_bonus(&mut svm, &pool, 2 * LAMPORTS_PER_SOL);
+        assert_eq!(bonus, 1_000);
+        assert_eq!(get_token_balance(&svm, &pool.treasury_ata), 0);
+
+        let (_, bonus) = deposit_with_bonus(&mut svm, &pool, 2 * LAMPORTS_PER_SOL);
+        assert_eq!(bonus, 0, "An empty treasury pays no bonus");
+    }
+
+    #[test]
+    fn test_set_bonus_schedule_rejects_non_admin_and_bad_bps() {
+        let mut svm = setup_svm();
+        let pool = setup(&mut svm, 0);
+
+        let attacker = Keypair::new();
+        svm.airdrop(&attacker.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        let ix = build_set_bonus_schedule_ix(&attacker.pubkey(), &pool.config_pda, 500, 5);
+        assert!(
+            !send(&mut svm, ix, &attacker),
+            "Only the admin can set the bonus schedule"
+        );
+
+        let ix = build_set_bonus_schedule_ix(&pool.admin.pubkey(), &pool.config_pda, 10_001, 5);
+        assert!(
+            !send(&mut svm, ix, &pool.admin),
+            "Bonus bps above 10_000 should be rejected"
+        );
+    }
+}
@@ -0,0 +1,170 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_claim_queued_deposit_ix, build_deposit_ix, build_set_deposit_cap_ix,
+        create_and_fund_ata, get_queued_deposit, get_token_balance, queued_deposit_pda, send,
+        set_deposit_account, setup_pool, setup_svm, warp_epoch, Pool, PoolSetup,
+        DEPOSIT_QUEUED_DEPOSIT,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+    const DEPOSIT_CAP_REJECT: u8 = 0;
+    const DEPOSIT_CAP_QUEUE: u8 = 1;
+
+    fn setup(svm: &mut LiteSVM, cap: u64, mode: u8) -> Pool {
+        let pool = setup_pool(svm, PoolSetup::default());
+        let set_cap_ix =
+            build_set_deposit_cap_ix(&pool.admin.pubkey(), &pool.config_pda, cap, mode);
+        assert!(send(svm, set_cap_ix, &pool.admin));
+        pool
+    }
+
+    fn new_depositor(svm: &mut LiteSVM, pool: &Pool) -> (Keypair, Pubkey) {
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 20 * LAMPORTS_PER_SOL)
+            .unwrap();
+        let depositor_ata = create_and_fund_ata(svm, &depositor.pubkey(), &pool.mint, 0);
+        (depositor, depositor_ata)
+    }
+
+    fn deposit_ix(
+        pool: &Pool,
+        depositor: &Pubkey,
+        depositor_ata: &Pubkey,
+        lamports: u64,
+    ) -> Instruction {
+        build_deposit_ix(
+            &pool.config_pda,
+            depositor,
+            depositor_ata,
+            &pool.mint,
+            &pool.stake_account_main,
+            &pool.stake_account_reserve,
+            lamports,
+            true,
+        )
+    }
+
+    #[test]
+    fn test_deposit_cap_rejects_excess_until_next_epoch() {
+        let mut svm = setup_svm();
+        let pool = setup(&mut svm, 3 * LAMPORTS_PER_SOL, DEPOSIT_CAP_REJECT);
+        let (depositor, depositor_ata) = new_depositor(&mut svm, &pool);
+
+        let ix = deposit_ix(
+            &pool,
+            &depositor.pubkey(),
+            &depositor_ata,
+            2 * LAMPORTS_PER_SOL,
+        );
+        assert!(send(&mut svm, ix.clone(), &depositor));
+        assert!(
+            !send(&mut svm, ix.clone(), &depositor),
+            "A deposit over the epoch's remaining cap should fail"
+        );
+
+        warp_epoch(&mut svm, 1);
+        assert!(
+            send(&mut svm, ix, &depositor),
+            "The cap should reset in a new epoch"
+        );
+    }
+
+    #[test]
+    fn test_deposit_cap_queues_excess_for_next_epoch() {
+        let mut svm = setup_svm();
+        let pool = setup(&mut svm, 3 * LAMPORTS_PER_SOL, DEPOSIT_CAP_QUEUE);
+        let (depositor, depositor_ata) = new_depositor(&mut svm, &pool);
+
+        let mut ix = deposit_ix(
+            &pool,
+            &depositor.pubkey(),
+            &depositor_ata,
+            5 * LAMPORTS_PER_SOL,
+        );
+        assert!(
+            !send(&mut svm, ix.clone(), &depositor),
+            "Queuing needs the queued deposit PDA"
+        );
+
+        set_deposit_account(
+            &mut ix,
+            DEPOSIT_QUEUED_DEPOSIT,
+            AccountMeta::new(queued_deposit_pda(&depositor.pubkey()), false),
+        );
+        let reserve_before = svm.get_balance(&pool.stake_account_reserve).unwrap();
+        assert!(send(&mut svm, ix, &depositor));
+
+        assert_eq!(
+            svm.get_balance(&pool.stake_account_reserve).unwrap(),
+            reserve_before + 3 * LAMPORTS_PER_SOL,
+            "Only the capped amount should reach the reserve"
+        );
+        assert_eq!(
+            get_queued_deposit(&svm, &depositor.pubkey()),
+            2 * LAMPORTS_PER_SOL
+        );
+        let minted_now = get_token_balance(&svm, &depositor_ata);
+        assert!(minted_now > 0);
+
+        let claim_ix = build_claim_queued_deposit_ix(
+            &pool.config_pda,
+            &depositor.pubkey(),
+            &pool.mint,
+            &pool.stake_account_main,
+            &pool.stake_account_reserve,
+        );
+        let keeper = Keypair::new();
+        svm.airdrop(&keeper.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        assert!(
+            !send(&mut svm, claim_ix.clone(), &keeper),
+            "Queued lamports wait for the next epoch"
+        );
+
+        warp_epoch(&mut svm, 1);
+        let depositor_before = svm.get_balance(&depositor.pubkey()).unwrap();
+        assert!(send(&mut svm, claim_ix, &keeper));
+
+        assert_eq!(
+            svm.get_balance(&pool.stake_account_reserve).unwrap(),
+            reserve_before + 5 * LAMPORTS_PER_SOL
+        );
+        assert!(get_token_balance(&svm, &depositor_ata) > minted_now);
+        assert_eq!(get_queued_deposit(&svm, &depositor.pubkey()), 0);
+        assert!(
+            svm.get_balance(&depositor.pubkey()).unwrap() > depositor_before,
+            "The emptied queue's rent goes back to the depositor"
+        );
+    }
+
+    #[test]
+    fn test_set_deposit_cap_requires_admin() {
+        let mut svm = setup_svm();
+        let pool = setup(&mut svm, 0, DEPOSIT_CAP_REJECT);
+
+        let outsider = Keypair::new();
+        svm.airdrop(&outsider.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        assert!(!send(
+            &mut svm,
+            build_set_deposit_cap_ix(&outsider.pubkey(), &pool.config_pda, 1, DEPOSIT_CAP_REJECT),
+            &outsider
+        ));
+        assert!(
+            !send(
+                &mut svm,
+                build_set_deposit_cap_ix(&pool.admin.pubkey(), &pool.config_pda, 1, 2),
+                &pool.admin
+            ),
+            "Unknown cap modes should be rejected"
+        );
+    }
+}
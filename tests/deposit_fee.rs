@@ -0,0 +1,107 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        instruction::AccountMeta,
+        signature::{Keypair, Signer},
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_deposit_ix, build_set_deposit_fee_ix, create_and_fund_ata, get_mint_supply,
+        get_token_balance, send, set_deposit_account, setup_pool, setup_svm, Pool, PoolSetup,
+        DEPOSIT_TREASURY_ATA,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    fn setup(svm: &mut LiteSVM) -> Pool {
+        setup_pool(
+            svm,
+            PoolSetup {
+                treasury_balance: Some(0),
+                ..PoolSetup::default()
+            },
+        )
+    }
+
+    /// Deposits `lamports`, with the treasury ATA passed when
+    /// `with_treasury`. Returns (LST minted, LST received) on success.
+    fn deposit(
+        svm: &mut LiteSVM,
+        pool: &Pool,
+        lamports: u64,
+        with_treasury: bool,
+    ) -> Option<(u64, u64)> {
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 20 * LAMPORTS_PER_SOL)
+            .unwrap();
+        let depositor_ata = create_and_fund_ata(svm, &depositor.pubkey(), &pool.mint, 0);
+
+        let mut ix = build_deposit_ix(
+            &pool.config_pda,
+            &depositor.pubkey(),
+            &depositor_ata,
+            &pool.mint,
+            &pool.stake_account_main,
+            &pool.stake_account_reserve,
+            lamports,
+            true,
+        );
+        if with_treasury {
+            set_deposit_account(
+                &mut ix,
+                DEPOSIT_TREASURY_ATA,
+                AccountMeta::new(pool.treasury_ata, false),
+            );
+        }
+
+        let supply_before = get_mint_supply(svm, &pool.mint);
+        if !send(svm, ix, &depositor) {
+            return None;
+        }
+
+        let minted = get_mint_supply(svm, &pool.mint) - supply_before;
+        Some((minted, get_token_balance(svm, &depositor_ata)))
+    }
+
+    #[test]
+    fn test_deposit_fee_is_minted_to_the_treasury() {
+        let mut svm = setup_svm();
+        let pool = setup(&mut svm);
+
+        let ix = build_set_deposit_fee_ix(&pool.admin.pubkey(), &pool.config_pda, 50);
+        assert!(send(&mut svm, ix, &pool.admin));
+
+        assert!(
+            deposit(&mut svm, &pool, 2 * LAMPORTS_PER_SOL, false).is_none(),
+            "The treasury ATA is required while the fee is on"
+        );
+
+        let (minted, received) =
+            deposit(&mut svm, &pool, 2 * LAMPORTS_PER_SOL, true).expect("Deposit should succeed");
+        let fee = minted * 50 / 10_000;
+        assert!(fee > 0);
+        assert_eq!(received, minted - fee);
+        assert_eq!(get_token_balance(&svm, &pool.treasury_ata), fee);
+    }
+
+    #[test]
+    fn test_deposit_fee_is_bounded_and_admin_only() {
+        let mut svm = setup_svm();
+        let pool = setup(&mut svm);
+
+        let ix = build_set_deposit_fee_ix(&pool.admin.pubkey(), &pool.config_pda, 10_001);
+        assert!(!send(&mut svm, ix, &pool.admin));
+
+        let outsider = Keypair::new();
+        svm.airdrop(&outsider.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        let ix = build_set_deposit_fee_ix(&outsider.pubkey(), &pool.config_pda, 50);
+        assert!(!send(&mut svm, ix, &outsider));
+
+        let (minted, received) = deposit(&mut svm, &pool, 2 * LAMPORTS_PER_SOL, false)
+            .expect("Deposits without a fee need no treasury ATA");
+        assert_eq!(received, minted);
+    }
+}
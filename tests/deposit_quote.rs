@@ -0,0 +1,319 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_close_used_quote_ix, build_deposit_ix, build_deposit_quote_ix,
+        build_set_quote_signer_ix, create_and_fund_ata, get_token_balance, print_transaction_logs,
+        set_deposit_account, setup_pool, setup_svm, used_quote_pda, Pool, PoolSetup,
+        DEPOSIT_USED_QUOTE,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    fn send(svm: &mut LiteSVM, ixs: &[Instruction], signer: &Keypair) -> bool {
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&signer.pubkey()),
+            &[signer],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        svm.expire_blockhash();
+        result.is_ok()
+    }
+
+    fn funded_depositor(svm: &mut LiteSVM, pool: &Pool) -> (Keypair, Pubkey) {
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .unwrap();
+        let depositor_ata = create_and_fund_ata(svm, &depositor.pubkey(), &pool.mint, 0);
+        (depositor, depositor_ata)
+    }
+
+    fn deposit_ix(pool: &Pool, depositor: &Pubkey, depositor_ata: &Pubkey) -> Instruction {
+        build_deposit_ix(
+            &pool.config_pda,
+            depositor,
+            depositor_ata,
+            &pool.mint,
+            &pool.stake_account_main,
+            &pool.stake_account_reserve,
+            LAMPORTS_PER_SOL,
+            true,
+        )
+    }
+
+    /// The Deposit with the quote flag, the instructions sysvar and the used
+    /// quote PDA for `nonce`.
+    fn quoted_deposit_ix(
+        pool: &Pool,
+        depositor: &Pubkey,
+        depositor_ata: &Pubkey,
+        nonce: u64,
+    ) -> Instruction {
+        let mut ix = deposit_ix(pool, depositor, depositor_ata);
+        ix.data.push(1);
+        ix.accounts.push(AccountMeta::new_readonly(
+            solana_sdk::sysvar::instructions::id(),
+            false,
+        ));
+        set_deposit_account(
+            &mut ix,
+            DEPOSIT_USED_QUOTE,
+            AccountMeta::new(used_quote_pda(depositor, nonce), false),
+        );
+        ix
+    }
+
+    /// LST the pool mints for one SOL, read from a plain deposit.
+    fn pool_lst_per_sol(svm: &mut LiteSVM, pool: &Pool) -> u64 {
+        let (depositor, depositor_ata) = funded_depositor(svm, pool);
+        let ix = deposit_ix(pool, &depositor.pubkey(), &depositor_ata);
+        assert!(send(svm, &[ix], &depositor));
+        get_token_balance(svm, &depositor_ata)
+    }
+
+    #[test]
+    fn test_quoted_deposit_mints_at_the_signed_rate() {
+        let mut svm = setup_svm();
+        let pool = setup_pool(&mut svm, PoolSetup::default());
+        let quote_signer = Keypair::new();
+        let ix = build_set_quote_signer_ix(
+            &pool.admin.pubkey(),
+            &pool.config_pda,
+            &quote_signer.pubkey(),
+            100,
+        );
+        assert!(send(&mut svm, &[ix], &pool.admin));
+
+        let rate = pool_lst_per_sol(&mut svm, &pool);
+        let quoted_rate = rate + rate / 200;
+
+        let (depositor, depositor_ata) = funded_depositor(&mut svm, &pool);
+        let quote = build_deposit_quote_ix(
+            &quote_signer,
+            &depositor.pubkey(),
+            quoted_rate,
+            100,
+            LAMPORTS_PER_SOL,
+            1,
+        );
+        let deposit = quoted_deposit_ix(&pool, &depositor.pubkey(), &depositor_ata, 1);
+        assert!(send(&mut svm, &[quote, deposit], &depositor));
+        assert_eq!(get_token_balance(&svm, &depositor_ata), quoted_rate);
+    }
+
+    #[test]
+    fn test_quote_must_be_fresh_and_within_bounds() {
+        let mut svm = setup_svm();
+        let pool = setup_pool(&mut svm, PoolSetup::default());
+        let quote_signer = Keypair::new();
+        let ix = build_set_quote_signer_ix(
+            &pool.admin.pubkey(),
+            &pool.config_pda,
+            &quote_signer.pubkey(),
+            100,
+        );
+        assert!(send(&mut svm, &[ix], &pool.admin));
+        let rate = pool_lst_per_sol(&mut svm, &pool);
+
+        let (depositor, depositor_ata) = funded_depositor(&mut svm, &pool);
+        let deposit = quoted_deposit_ix(&pool, &depositor.pubkey(), &depositor_ata, 1);
+
+        let out_of_bounds = build_deposit_quote_ix(
+            &quote_signer,
+            &depositor.pubkey(),
+            rate + rate / 50,
+            100,
+            LAMPORTS_PER_SOL,
+            1,
+        );
+        assert!(!send(
+            &mut svm,
+            &[out_of_bounds, deposit.clone()],
+            &depositor
+        ));
+
+        svm.warp_to_slot(200);
+        let expired = build_deposit_quote_ix(
+            &quote_signer,
+            &depositor.pubkey(),
+            rate,
+            100,
+            LAMPORTS_PER_SOL,
+            1,
+        );
+        assert!(!send(&mut svm, &[expired, deposit.clone()], &depositor));
+
+        let fresh = build_deposit_quote_ix(
+            &quote_signer,
+            &depositor.pubkey(),
+            rate,
+            300,
+            LAMPORTS_PER_SOL,
+            1,
+        );
+        assert!(send(&mut svm, &[fresh, deposit], &depositor));
+    }
+
+    #[test]
+    fn test_quote_must_come_from_the_signer_for_the_depositor() {
+        let mut svm = setup_svm();
+        let pool = setup_pool(&mut svm, PoolSetup::default());
+        let quote_signer = Keypair::new();
+        let rate = pool_lst_per_sol(&mut svm, &pool);
+
+        let (depositor, depositor_ata) = funded_depositor(&mut svm, &pool);
+        let deposit = quoted_deposit_ix(&pool, &depositor.pubkey(), &depositor_ata, 1);
+
+        // Quotes are off until the admin sets a signer.
+        let quote = build_deposit_quote_ix(
+            &quote_signer,
+            &depositor.pubkey(),
+            rate,
+            100,
+            LAMPORTS_PER_SOL,
+            1,
+        );
+        assert!(!send(
+            &mut svm,
+            &[quote.clone(), deposit.clone()],
+            &depositor
+        ));
+
+        let ix = build_set_quote_signer_ix(
+            &pool.admin.pubkey(),
+            &pool.config_pda,
+            &quote_signer.pubkey(),
+            100,
+        );
+        assert!(send(&mut svm, &[ix], &pool.admin));
+
+        let impostor = build_deposit_quote_ix(
+            &Keypair::new(),
+            &depositor.pubkey(),
+            rate,
+            100,
+            LAMPORTS_PER_SOL,
+            1,
+        );
+        assert!(!send(&mut svm, &[impostor, deposit.clone()], &depositor));
+
+        let other_depositor = build_deposit_quote_ix(
+            &quote_signer,
+            &Pubkey::new_unique(),
+            rate,
+            100,
+            LAMPORTS_PER_SOL,
+            1,
+        );
+        assert!(!send(
+            &mut svm,
+            &[other_depositor, deposit.clone()],
+            &depositor
+        ));
+
+        assert!(
+            !send(&mut svm, &[deposit.clone()], &depositor),
+            "The quote must precede the deposit"
+        );
+
+        assert!(send(&mut svm, &[quote, deposit], &depositor));
+    }
+
+    #[test]
+    fn test_quote_prices_one_deposit_up_to_its_amount() {
+        let mut svm = setup_svm();
+        let pool = setup_pool(&mut svm, PoolSetup::default());
+        let quote_signer = Keypair::new();
+        let ix = build_set_quote_signer_ix(
+            &pool.admin.pubkey(),
+            &pool.config_pda,
+            &quote_signer.pubkey(),
+            100,
+        );
+        assert!(send(&mut svm, &[ix], &pool.admin));
+        let rate = pool_lst_per_sol(&mut svm, &pool);
+
+        let (depositor, depositor_ata) = funded_depositor(&mut svm, &pool);
+        let deposit = quoted_deposit_ix(&pool, &depositor.pubkey(), &depositor_ata, 1);
+
+        let too_small = build_deposit_quote_ix(
+            &quote_signer,
+            &depositor.pubkey(),
+            rate,
+            100,
+            LAMPORTS_PER_SOL - 1,
+            1,
+        );
+        assert!(!send(&mut svm, &[too_small, deposit.clone()], &depositor));
+
+        let quote = build_deposit_quote_ix(
+            &quote_signer,
+            &depositor.pubkey(),
+            rate,
+            100,
+            LAMPORTS_PER_SOL,
+            1,
+        );
+        assert!(send(
+            &mut svm,
+            &[quote.clone(), deposit.clone()],
+            &depositor
+        ));
+        assert!(
+            !send(&mut svm, &[quote.clone(), deposit], &depositor),
+            "A used quote cannot be replayed"
+        );
+
+        // The record stays until the quote has expired.
+        let close = build_close_used_quote_ix(&depositor.pubkey(), 1);
+        assert!(!send(&mut svm, &[close.clone()], &depositor));
+
+        svm.warp_to_slot(101);
+        let lamports_before = svm.get_balance(&depositor.pubkey()).unwrap();
+        assert!(send(&mut svm, &[close], &pool.admin));
+        assert!(svm
+            .get_account(&used_quote_pda(&depositor.pubkey(), 1))
+            .is_none_or(|account| account.lamports == 0));
+        assert!(svm.get_balance(&depositor.pubkey()).unwrap() > lamports_before);
+
+        // Closed after expiry, the quote still cannot price a deposit.
+        let deposit = quoted_deposit_ix(&pool, &depositor.pubkey(), &depositor_ata, 1);
+        assert!(!send(&mut svm, &[quote, deposit], &depositor));
+    }
+
+    #[test]
+    fn test_only_admin_sets_the_quote_signer() {
+        let mut svm = setup_svm();
+        let pool = setup_pool(&mut svm, PoolSetup::default());
+
+        let outsider = Keypair::new();
+        svm.airdrop(&outsider.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        let ix = build_set_quote_signer_ix(
+            &outsider.pubkey(),
+            &pool.config_pda,
+            &outsider.pubkey(),
+            100,
+        );
+        assert!(!send(&mut svm, &[ix], &outsider));
+
+        let ix = build_set_quote_signer_ix(
+            &pool.admin.pubkey(),
+            &pool.config_pda,
+            &outsider.pubkey(),
+            10_001,
+        );
+        assert!(!send(&mut svm, &[ix], &pool.admin));
+    }
+}
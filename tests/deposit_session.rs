@@ -0,0 +1,110 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        signature::{Keypair, Signer},
+        sysvar::clock::Clock,
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_close_deposit_session_ix, build_create_deposit_session_ix,
+        build_deposit_with_session_ix, create_and_fund_ata, deposit_session_pda, get_token_balance,
+        run_initialize, send, setup_svm,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    #[test]
+    fn test_session_key_deposits_within_budget_and_expiry() {
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+
+        let user = Keypair::new();
+        let session_key = Keypair::new();
+        svm.airdrop(&user.pubkey(), 10 * LAMPORTS_PER_SOL).unwrap();
+        svm.airdrop(&session_key.pubkey(), LAMPORTS_PER_SOL)
+            .unwrap();
+        let user_ata = create_and_fund_ata(&mut svm, &user.pubkey(), &mint, 0);
+
+        let now = svm.get_sysvar::<Clock>().unix_timestamp;
+        let expires_at = now + 3_600;
+        assert!(send(
+            &mut svm,
+            build_create_deposit_session_ix(
+                &user.pubkey(),
+                &session_key.pubkey(),
+                3 * LAMPORTS_PER_SOL,
+                expires_at,
+            ),
+            &user
+        ));
+
+        let deposit = |svm: &mut LiteSVM, signer: &Keypair, amount: u64| {
+            let ix = build_deposit_with_session_ix(
+                &signer.pubkey(),
+                &user.pubkey(),
+                &config_pda,
+                &mint,
+                &stake_account_main,
+                &stake_account_reserve,
+                amount,
+            );
+            send(svm, ix, signer)
+        };
+
+        let reserve_before = svm.get_account(&stake_account_reserve).unwrap().lamports;
+        assert!(deposit(&mut svm, &session_key, 2 * LAMPORTS_PER_SOL));
+        assert!(get_token_balance(&svm, &user_ata) > 0);
+        assert_eq!(
+            svm.get_account(&stake_account_reserve).unwrap().lamports,
+            reserve_before + 2 * LAMPORTS_PER_SOL
+        );
+
+        assert!(
+            !deposit(&mut svm, &session_key, 2 * LAMPORTS_PER_SOL),
+            "Only 1 SOL of the budget is left"
+        );
+
+        let stranger = Keypair::new();
+        svm.airdrop(&stranger.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        assert!(
+            !deposit(&mut svm, &stranger, LAMPORTS_PER_SOL),
+            "Only the authorized session key may deposit"
+        );
+
+        let mut clock = svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = expires_at;
+        svm.set_sysvar::<Clock>(&clock);
+        assert!(
+            !deposit(&mut svm, &session_key, LAMPORTS_PER_SOL),
+            "The session has expired"
+        );
+
+        let user_balance_before = svm.get_account(&user.pubkey()).unwrap().lamports;
+        let session_balance = svm
+            .get_account(&deposit_session_pda(&user.pubkey(), &session_key.pubkey()))
+            .unwrap()
+            .lamports;
+        assert!(send(
+            &mut svm,
+            build_close_deposit_session_ix(&user.pubkey(), &session_key.pubkey()),
+            &user
+        ));
+        assert_eq!(
+            svm.get_account(&user.pubkey()).unwrap().lamports,
+            user_balance_before + session_balance - 5_000,
+            "The unspent budget and rent go back to the user"
+        );
+    }
+}
@@ -0,0 +1,170 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_deposit_stake_account_ix, create_and_fund_ata, get_mint_supply, get_token_balance,
+        run_deposit, run_initialize, send, setup_svm, warp_epoch,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    fn lamports(svm: &LiteSVM, pubkey: &Pubkey) -> u64 {
+        svm.get_account(pubkey)
+            .map_or(0, |account| account.lamports)
+    }
+
+    /// Creates a stake account delegating `lamports` beyond rent, with
+    /// `owner` as staker and withdrawer. It copies main's delegation, so it
+    /// is activated in the same epoch as main unless `voter` overrides it.
+    fn user_stake_account(
+        svm: &mut LiteSVM,
+        stake_account_main: &Pubkey,
+        owner: &Pubkey,
+        lamports: u64,
+        voter: Option<Pubkey>,
+    ) -> Pubkey {
+        let mut account = svm.get_account(stake_account_main).unwrap();
+        account.data[12..44].copy_from_slice(owner.as_ref());
+        account.data[44..76].copy_from_slice(owner.as_ref());
+        if let Some(voter) = voter {
+            account.data[124..156].copy_from_slice(voter.as_ref());
+        }
+        account.data[156..164].copy_from_slice(&lamports.to_le_bytes());
+        let rent_exempt_reserve = u64::from_le_bytes(account.data[4..12].try_into().unwrap());
+        account.lamports = rent_exempt_reserve + lamports;
+
+        let stake_account = Pubkey::new_unique();
+        svm.set_account(stake_account, account).unwrap();
+        stake_account
+    }
+
+    #[test]
+    fn test_deposit_stake_account_mints_like_a_sol_deposit() {
+        let deposited = 2 * LAMPORTS_PER_SOL;
+
+        // The same deposit made in SOL, as a reference for the rate.
+        let mut reference_svm = setup_svm();
+        let (_, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut reference_svm);
+        warp_epoch(&mut reference_svm, 1);
+        let (_, reference_ata) = run_deposit(
+            &mut reference_svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            deposited,
+        );
+        let expected_lst = get_token_balance(&reference_svm, &reference_ata);
+
+        let mut svm = setup_svm();
+        let (_, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        let depositor_ata = create_and_fund_ata(&mut svm, &depositor.pubkey(), &mint, 0);
+        let stake_account = user_stake_account(
+            &mut svm,
+            &stake_account_main,
+            &depositor.pubkey(),
+            deposited,
+            None,
+        );
+        warp_epoch(&mut svm, 1);
+
+        let main_before = lamports(&svm, &stake_account_main);
+        let stake_account_lamports = lamports(&svm, &stake_account);
+        let supply_before = get_mint_supply(&svm, &mint);
+        assert!(send(
+            &mut svm,
+            build_deposit_stake_account_ix(
+                &depositor.pubkey(),
+                &config_pda,
+                &stake_account,
+                &stake_account_main,
+                &stake_account_reserve,
+                &mint,
+            ),
+            &depositor,
+        ));
+
+        assert_eq!(lamports(&svm, &stake_account), 0, "Merged into main");
+        assert_eq!(
+            lamports(&svm, &stake_account_main),
+            main_before + stake_account_lamports
+        );
+        let minted = get_token_balance(&svm, &depositor_ata);
+        assert_eq!(get_mint_supply(&svm, &mint), supply_before + minted);
+        // The stake account's rent reserve is deposited too.
+        assert!(minted > expected_lst);
+    }
+
+    #[test]
+    fn test_deposit_stake_account_rejects_stake_the_pool_cannot_merge() {
+        let mut svm = setup_svm();
+        let (_, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        create_and_fund_ata(&mut svm, &depositor.pubkey(), &mint, 0);
+
+        let deposit = |svm: &mut LiteSVM, stake_account: &Pubkey| {
+            send(
+                svm,
+                build_deposit_stake_account_ix(
+                    &depositor.pubkey(),
+                    &config_pda,
+                    stake_account,
+                    &stake_account_main,
+                    &stake_account_reserve,
+                    &mint,
+                ),
+                &depositor,
+            )
+        };
+
+        let other_validator = user_stake_account(
+            &mut svm,
+            &stake_account_main,
+            &depositor.pubkey(),
+            LAMPORTS_PER_SOL,
+            Some(Pubkey::new_unique()),
+        );
+        let not_owned = user_stake_account(
+            &mut svm,
+            &stake_account_main,
+            &Pubkey::new_unique(),
+            LAMPORTS_PER_SOL,
+            None,
+        );
+        let activating = user_stake_account(
+            &mut svm,
+            &stake_account_main,
+            &depositor.pubkey(),
+            LAMPORTS_PER_SOL,
+            None,
+        );
+        assert!(
+            !deposit(&mut svm, &activating),
+            "Stake activated this epoch is still warming up"
+        );
+
+        warp_epoch(&mut svm, 1);
+        assert!(!deposit(&mut svm, &other_validator));
+        assert!(!deposit(&mut svm, &not_owned));
+        assert!(
+            !deposit(&mut svm, &stake_account_main),
+            "Main cannot be deposited into itself"
+        );
+        assert!(deposit(&mut svm, &activating));
+    }
+}
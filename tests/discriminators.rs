@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tests {
+    use solana_liquid_staking::discriminators::{
+        hashed_discriminator, split_hashed, HASHED_DISCRIMINATORS,
+    };
+    use solana_program::hash::hashv;
+
+    #[test]
+    fn test_table_matches_anchor_hashes() {
+        for (name, hashed) in HASHED_DISCRIMINATORS {
+            let hash = hashv(&[b"global:", name.as_bytes()]);
+            let expected = u64::from_be_bytes(hash.to_bytes()[..8].try_into().unwrap());
+            assert_eq!(hashed, expected, "{name}");
+        }
+
+        for (index, (_, hashed)) in HASHED_DISCRIMINATORS.iter().enumerate() {
+            assert!(
+                HASHED_DISCRIMINATORS[index + 1..]
+                    .iter()
+                    .all(|(_, other)| other != hashed),
+                "Hashed discriminators must be unique"
+            );
+        }
+    }
+
+    #[test]
+    fn test_split_hashed_round_trips() {
+        for discriminator in 0..HASHED_DISCRIMINATORS.len() as u8 {
+            let mut data = hashed_discriminator(discriminator).unwrap().to_vec();
+            data.extend_from_slice(&[7, 8, 9]);
+
+            let (single_byte, rest) = split_hashed(&data).unwrap();
+            assert_eq!(*single_byte, discriminator);
+            assert_eq!(rest, &[7, 8, 9]);
+        }
+
+        assert!(hashed_discriminator(HASHED_DISCRIMINATORS.len() as u8).is_none());
+        assert!(
+            split_hashed(&[3]).is_none(),
+            "Single-byte data is not hashed"
+        );
+        assert!(split_hashed(&[3, 0, 0, 0, 0, 0, 0, 0, 1]).is_none());
+    }
+}
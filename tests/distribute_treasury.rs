@@ -0,0 +1,95 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_distribute_treasury_ix, create_and_fund_ata, get_token_balance,
+        print_transaction_logs, run_initialize, setup_svm,
+    };
+
+    #[test]
+    fn test_distribute_treasury() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            _stake_account_main,
+            _stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+
+        let treasury = create_and_fund_ata(&mut svm, &config_pda, &token_mint.pubkey(), 1_000);
+        let recipients: Vec<_> = (0..3)
+            .map(|_| {
+                create_and_fund_ata(&mut svm, &Keypair::new().pubkey(), &token_mint.pubkey(), 0)
+            })
+            .collect();
+        let amounts = [100, 250, 400];
+
+        let send = |svm: &mut litesvm::LiteSVM, signer: &Keypair, amounts: &[u64]| {
+            svm.airdrop(&signer.pubkey(), 1_000_000_000).unwrap();
+            let ix = build_distribute_treasury_ix(
+                &signer.pubkey(),
+                &config_pda,
+                &token_mint.pubkey(),
+                &recipients,
+                amounts,
+            );
+            let tx = Transaction::new_signed_with_payer(
+                &[ix],
+                Some(&signer.pubkey()),
+                &[signer],
+                svm.latest_blockhash(),
+            );
+            let result = svm.send_transaction(tx);
+            print_transaction_logs(&result);
+            svm.expire_blockhash();
+            result.is_ok()
+        };
+
+        assert!(
+            !send(&mut svm, &Keypair::new(), &amounts),
+            "Only the admin may distribute"
+        );
+        assert!(
+            !send(&mut svm, &initializer, &amounts[..2]),
+            "Every recipient needs an amount"
+        );
+
+        let too_many: Vec<_> = (0..17)
+            .map(|_| {
+                create_and_fund_ata(&mut svm, &Keypair::new().pubkey(), &token_mint.pubkey(), 0)
+            })
+            .collect();
+        let ix = build_distribute_treasury_ix(
+            &initializer.pubkey(),
+            &config_pda,
+            &token_mint.pubkey(),
+            &too_many,
+            &[1; 17],
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+        assert!(
+            svm.send_transaction(tx).is_err(),
+            "One call pays at most MAX_DISTRIBUTE_RECIPIENTS recipients"
+        );
+
+        assert!(send(&mut svm, &initializer, &amounts));
+        for (recipient, amount) in recipients.iter().zip(amounts) {
+            assert_eq!(get_token_balance(&svm, recipient), amount);
+        }
+        assert_eq!(get_token_balance(&svm, &treasury), 250);
+    }
+}
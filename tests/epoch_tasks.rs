@@ -0,0 +1,182 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_liquid_staking::state::{
+        AccountDiscriminator, AccountHeader, EpochTasks, EPOCH_TASK_FEE_ACCRUAL,
+        EPOCH_TASK_INITIALIZE_RESERVE, EPOCH_TASK_MERGE_RESERVE, EPOCH_TASK_ORACLE_PUSH,
+        EPOCH_TASK_RATE_UPDATE, EPOCH_TASK_REBALANCE,
+    };
+    use solana_sdk::signer::Signer;
+
+    use crate::test_helpers::test_helpers::{
+        get_epoch_tasks, run_crank_initialize_reserve, run_crank_management_fee,
+        run_crank_merge_reserve, run_crank_record_rate, run_crank_update_rate, run_initialize,
+        setup_svm, warp_epoch,
+    };
+
+    #[test]
+    fn test_checklist_resets_each_epoch() {
+        let mut tasks = EpochTasks {
+            discriminator: AccountDiscriminator::EpochTasks as u8,
+            version: EpochTasks::VERSION,
+            epoch: 0,
+            completed: 0,
+        };
+        assert_eq!(tasks.pending(0), EpochTasks::REQUIRED);
+
+        tasks.mark(3, EPOCH_TASK_MERGE_RESERVE);
+        tasks.mark(3, EPOCH_TASK_ORACLE_PUSH);
+        assert_eq!(
+            tasks.pending(3),
+            EPOCH_TASK_INITIALIZE_RESERVE
+                | EPOCH_TASK_REBALANCE
+                | EPOCH_TASK_FEE_ACCRUAL
+                | EPOCH_TASK_RATE_UPDATE
+        );
+
+        assert_eq!(tasks.completed(4), 0);
+        assert_eq!(tasks.pending(4), EpochTasks::REQUIRED);
+
+        tasks.mark(4, EPOCH_TASK_INITIALIZE_RESERVE);
+        assert_eq!(tasks.completed(4), EPOCH_TASK_INITIALIZE_RESERVE);
+    }
+
+    #[test]
+    fn test_prerequisites_enforce_ordering() {
+        let mut tasks = EpochTasks {
+            discriminator: AccountDiscriminator::EpochTasks as u8,
+            version: EpochTasks::VERSION,
+            epoch: 0,
+            completed: 0,
+        };
+
+        assert_eq!(
+            EpochTasks::prerequisites(EPOCH_TASK_ORACLE_PUSH),
+            EPOCH_TASK_FEE_ACCRUAL
+        );
+        assert_eq!(
+            tasks.missing_prerequisites(5, EPOCH_TASK_REBALANCE),
+            EPOCH_TASK_MERGE_RESERVE
+        );
+
+        tasks.mark(5, EPOCH_TASK_MERGE_RESERVE);
+        assert_eq!(tasks.missing_prerequisites(5, EPOCH_TASK_REBALANCE), 0);
+        assert_eq!(
+            tasks.missing_prerequisites(6, EPOCH_TASK_REBALANCE),
+            EPOCH_TASK_MERGE_RESERVE,
+            "A merge from an earlier epoch should not count"
+        );
+
+        assert_eq!(
+            tasks.missing_prerequisites(6, EPOCH_TASK_ORACLE_PUSH),
+            EPOCH_TASK_FEE_ACCRUAL,
+            "The oracle push should wait for fee accrual"
+        );
+        assert_eq!(
+            tasks.missing_prerequisites(6, EPOCH_TASK_RATE_UPDATE),
+            EPOCH_TASK_FEE_ACCRUAL,
+            "The rate update should wait for fee accrual"
+        );
+        tasks.mark(6, EPOCH_TASK_FEE_ACCRUAL);
+        assert_eq!(tasks.missing_prerequisites(6, EPOCH_TASK_ORACLE_PUSH), 0);
+        assert_eq!(tasks.missing_prerequisites(6, EPOCH_TASK_RATE_UPDATE), 0);
+    }
+
+    #[test]
+    fn test_load_requires_the_epoch_tasks_header() {
+        let mut data = [0u8; EpochTasks::LEN];
+        assert!(EpochTasks::load(&data).is_err());
+
+        EpochTasks::write_header(&mut data);
+        assert!(EpochTasks::load(&data).is_ok());
+
+        // Another state account's header of the same length is rejected.
+        data[0] = AccountDiscriminator::SplitCounter as u8;
+        assert!(EpochTasks::load_mut(&mut data).is_err());
+
+        data[0] = AccountDiscriminator::EpochTasks as u8;
+        data[1] = EpochTasks::VERSION + 1;
+        assert!(EpochTasks::load(&data).is_err());
+    }
+
+    #[test]
+    fn test_cranks_mark_their_tasks() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = run_initialize(&mut svm);
+        assert_eq!(
+            get_epoch_tasks(&svm),
+            (0, EPOCH_TASK_FEE_ACCRUAL),
+            "The creation epoch's fee should count as accrued"
+        );
+
+        run_crank_initialize_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_reserve,
+            &vote_pubkey,
+        );
+        assert_eq!(
+            get_epoch_tasks(&svm),
+            (0, EPOCH_TASK_FEE_ACCRUAL | EPOCH_TASK_INITIALIZE_RESERVE)
+        );
+
+        run_crank_merge_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+        assert_eq!(
+            get_epoch_tasks(&svm),
+            (
+                0,
+                EPOCH_TASK_FEE_ACCRUAL | EPOCH_TASK_INITIALIZE_RESERVE | EPOCH_TASK_MERGE_RESERVE
+            )
+        );
+
+        warp_epoch(&mut svm, 1);
+        run_crank_management_fee(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+        run_crank_record_rate(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+        run_crank_update_rate(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+        assert_eq!(
+            get_epoch_tasks(&svm),
+            (
+                1,
+                EPOCH_TASK_FEE_ACCRUAL | EPOCH_TASK_ORACLE_PUSH | EPOCH_TASK_RATE_UPDATE
+            ),
+            "A new epoch should start a fresh checklist"
+        );
+    }
+}
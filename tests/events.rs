@@ -0,0 +1,289 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_liquid_staking::{
+        events::{DEPOSIT_FLAG_MEMO, EVENT_ADMIN_ACTION, EVENT_DEPOSIT, ROLE_ADMIN},
+        state::PoolState,
+    };
+    use solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_deposit_ix, build_set_buyback_policy_ix, create_and_fund_ata, decode_event_decimals,
+        decode_events, get_event_sequence, get_recent_events, run_deposit, run_initialize,
+        setup_svm,
+    };
+
+    #[test]
+    fn test_events_carry_consecutive_sequence_numbers() {
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+        assert_eq!(get_event_sequence(&svm), 0);
+
+        let mut sequences = Vec::new();
+        for amount in [2_000_000_000u64, 3_000_000_000] {
+            let depositor = Keypair::new();
+            svm.airdrop(&depositor.pubkey(), 10_000_000_000).unwrap();
+            let depositor_ata =
+                create_and_fund_ata(&mut svm, &depositor.pubkey(), &token_mint.pubkey(), 0);
+
+            let ix = build_deposit_ix(
+                &config_pda,
+                &depositor.pubkey(),
+                &depositor_ata,
+                &token_mint.pubkey(),
+                &stake_account_main,
+                &stake_account_reserve,
+                amount,
+                true,
+            );
+            let tx = Transaction::new_signed_with_payer(
+                &[ix],
+                Some(&depositor.pubkey()),
+                &[&depositor],
+                svm.latest_blockhash(),
+            );
+            let meta = svm.send_transaction(tx).expect("Deposit should succeed");
+
+            let events = decode_events(&meta.logs);
+            assert_eq!(events.len(), 1, "Each deposit should emit one event");
+            let (kind, sequence, payload) = &events[0];
+            assert_eq!(*kind, EVENT_DEPOSIT);
+            assert_eq!(&payload[0..32], depositor.pubkey().as_ref());
+            assert_eq!(
+                u64::from_le_bytes(payload[32..40].try_into().unwrap()),
+                amount
+            );
+            sequences.push(*sequence);
+        }
+        assert_eq!(sequences, vec![1, 2]);
+
+        run_deposit(
+            &mut svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            5_000_000_000,
+        );
+        assert_eq!(get_event_sequence(&svm), 3);
+    }
+
+    #[test]
+    fn test_recent_events_ring_buffer_keeps_the_latest_events() {
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+        assert!(get_recent_events(&svm).is_empty());
+
+        let (depositor, _) = run_deposit(
+            &mut svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            2_000_000_000,
+        );
+        let events = get_recent_events(&svm);
+        assert_eq!(events.len(), 1);
+        let event = events[0];
+        let (sequence, kind, actor, amounts) =
+            (event.sequence, event.kind, event.actor, event.amounts);
+        assert_eq!(sequence, 1);
+        assert_eq!(kind, EVENT_DEPOSIT);
+        assert_eq!(actor, depositor.pubkey().to_bytes());
+        assert_eq!(amounts[0], 2_000_000_000);
+
+        let total = PoolState::EVENT_LOG_CAPACITY as u64 + 3;
+        for _ in 1..total {
+            run_deposit(
+                &mut svm,
+                &config_pda,
+                &token_mint.pubkey(),
+                &stake_account_main,
+                &stake_account_reserve,
+                1_000_000_000,
+            );
+            svm.expire_blockhash();
+        }
+
+        let sequences: Vec<u64> = get_recent_events(&svm)
+            .iter()
+            .map(|event| event.sequence)
+            .collect();
+        let expected: Vec<u64> =
+            (total + 1 - PoolState::EVENT_LOG_CAPACITY as u64..=total).collect();
+        assert_eq!(sequences, expected, "Only the latest events should remain");
+    }
+
+    #[test]
+    fn test_deposit_event_carries_memo_hash() {
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 10_000_000_000).unwrap();
+        let depositor_ata =
+            create_and_fund_ata(&mut svm, &depositor.pubkey(), &token_mint.pubkey(), 0);
+
+        // A zeroed key slot carries the memo without an idempotency key, so
+        // no deposit key PDA is passed.
+        let memo_hash = [9u8; 32];
+        let mut ix = build_deposit_ix(
+            &config_pda,
+            &depositor.pubkey(),
+            &depositor_ata,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            2_000_000_000,
+            true,
+        );
+        ix.data.extend_from_slice(&[0u8; 32]);
+        ix.data.extend_from_slice(&memo_hash);
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+        let meta = svm.send_transaction(tx).expect("Deposit should succeed");
+
+        let events = decode_events(&meta.logs);
+        assert_eq!(events.len(), 1);
+        let (kind, _, payload) = &events[0];
+        assert_eq!(*kind, EVENT_DEPOSIT);
+        assert_eq!(payload.len(), 113);
+        assert_eq!(payload[48], DEPOSIT_FLAG_MEMO);
+        assert_eq!(&payload[49..81], &memo_hash);
+        assert_eq!(&payload[81..113], &[0u8; 32], "No referrer was passed");
+    }
+
+    #[test]
+    fn test_admin_instructions_emit_admin_action() {
+        let mut svm = setup_svm();
+        let (initializer, _, _, config_pda, _, _, _) = run_initialize(&mut svm);
+        let set_buyback_policy = 40u8;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[build_set_buyback_policy_ix(
+                &initializer.pubkey(),
+                &config_pda,
+                1,
+            )],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+        let meta = svm
+            .send_transaction(tx)
+            .expect("SetBuybackPolicy should succeed");
+
+        let events = decode_events(&meta.logs);
+        assert_eq!(events.len(), 1);
+        let (kind, sequence, payload) = &events[0];
+        assert_eq!(*kind, EVENT_ADMIN_ACTION);
+        assert_eq!(*sequence, 1, "Admin actions take the next sequence number");
+        assert_eq!(&payload[0..32], initializer.pubkey().as_ref());
+        assert_eq!(payload[32], ROLE_ADMIN);
+        assert_eq!(payload[33], set_buyback_policy);
+        assert_eq!(payload.len(), 42);
+        assert_eq!(get_event_sequence(&svm), 1);
+
+        let intruder = Keypair::new();
+        svm.airdrop(&intruder.pubkey(), 1_000_000_000).unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[build_set_buyback_policy_ix(
+                &intruder.pubkey(),
+                &config_pda,
+                0,
+            )],
+            Some(&intruder.pubkey()),
+            &[&intruder],
+            svm.latest_blockhash(),
+        );
+        let failed = svm
+            .send_transaction(tx)
+            .expect_err("Only the admin may set the buyback policy");
+        assert!(
+            decode_events(&failed.meta.logs).is_empty(),
+            "A rejected signer must not be reported as an admin action"
+        );
+    }
+
+    #[test]
+    fn test_events_carry_lst_decimals() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+        let mint_decimals = svm.get_account(&token_mint.pubkey()).unwrap().data[44];
+
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 10_000_000_000).unwrap();
+        let depositor_ata =
+            create_and_fund_ata(&mut svm, &depositor.pubkey(), &token_mint.pubkey(), 0);
+        let ix = build_deposit_ix(
+            &config_pda,
+            &depositor.pubkey(),
+            &depositor_ata,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            2_000_000_000,
+            true,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+        let meta = svm.send_transaction(tx).expect("Deposit should succeed");
+        assert_eq!(decode_event_decimals(&meta.logs), vec![mint_decimals]);
+
+        let ix = build_set_buyback_policy_ix(&initializer.pubkey(), &config_pda, 1);
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+        let meta = svm
+            .send_transaction(tx)
+            .expect("SetBuybackPolicy should succeed");
+        assert_eq!(decode_event_decimals(&meta.logs), vec![mint_decimals]);
+    }
+}
@@ -0,0 +1,242 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::signer::Signer;
+    use solana_sdk::transaction::Transaction;
+
+    use crate::test_helpers::test_helpers::{
+        build_deposit_ix, build_exit_pool_ix, create_and_fund_ata, get_mint_supply,
+        print_transaction_logs, run_activate_pool, run_crank_initialize_reserve, run_deposit,
+        run_initialize, run_withdraw, setup_svm,
+    };
+
+    #[test]
+    fn test_exit_pool_last_holder_success() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+        run_activate_pool(&mut svm, &initializer, &config_pda);
+
+        let balance_before = svm.get_account(&initializer.pubkey()).unwrap().lamports;
+
+        let (ix, initializer_stake_account) = build_exit_pool_ix(
+            &initializer.pubkey(),
+            &initializer_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            7,
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(
+            result.is_ok(),
+            "Last holder should be able to exit the pool"
+        );
+
+        assert_eq!(
+            get_mint_supply(&svm, &token_mint.pubkey()),
+            0,
+            "Entire supply should be burned"
+        );
+        assert!(
+            svm.get_account(&stake_account_main)
+                .is_none_or(|account| account.lamports == 0),
+            "Main stake account should be emptied"
+        );
+        assert!(
+            svm.get_account(&stake_account_reserve)
+                .is_none_or(|account| account.lamports == 0),
+            "Reserve should be drained"
+        );
+
+        run_withdraw(
+            &mut svm,
+            &initializer,
+            &initializer_stake_account,
+            &config_pda,
+            7,
+        );
+
+        let balance_after = svm.get_account(&initializer.pubkey()).unwrap().lamports;
+        assert!(
+            balance_after > balance_before + 2_000_000_000,
+            "Initializer should recover the seed stake and reserve"
+        );
+    }
+
+    #[test]
+    fn test_exit_pool_not_last_holder() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+        run_activate_pool(&mut svm, &initializer, &config_pda);
+
+        run_deposit(
+            &mut svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            2_000_000_000,
+        );
+
+        let (ix, _) = build_exit_pool_ix(
+            &initializer.pubkey(),
+            &initializer_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            7,
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(
+            result.is_err(),
+            "Should fail when other holders still own LST"
+        );
+    }
+
+    #[test]
+    fn test_exit_pool_reserve_delegated() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = run_initialize(&mut svm);
+        run_activate_pool(&mut svm, &initializer, &config_pda);
+
+        run_crank_initialize_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_reserve,
+            &vote_pubkey,
+        );
+
+        let (ix, _) = build_exit_pool_ix(
+            &initializer.pubkey(),
+            &initializer_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            7,
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(
+            result.is_err(),
+            "Should fail while reserve stake is delegated and unmerged"
+        );
+    }
+
+    #[test]
+    fn test_deposit_after_exit_fails() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+        run_activate_pool(&mut svm, &initializer, &config_pda);
+
+        let (ix, _) = build_exit_pool_ix(
+            &initializer.pubkey(),
+            &initializer_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            7,
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Exit should succeed");
+
+        let depositor = solana_sdk::signature::Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 10_000_000_000).unwrap();
+        let depositor_ata =
+            create_and_fund_ata(&mut svm, &depositor.pubkey(), &token_mint.pubkey(), 0);
+
+        let ix = build_deposit_ix(
+            &config_pda,
+            &depositor.pubkey(),
+            &depositor_ata,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            2_000_000_000,
+            true,
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_err(), "Deposits into an exited pool should fail");
+    }
+}
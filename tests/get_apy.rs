@@ -0,0 +1,112 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::signer::Signer;
+
+    use crate::test_helpers::test_helpers::{
+        add_stake_rewards, get_apy, run_crank_management_fee, run_crank_record_rate, run_initialize,
+        setup_svm, warp_epoch,
+    };
+
+    #[test]
+    fn test_get_apy_insufficient_history() {
+        let mut svm = setup_svm();
+        let (initializer, token_mint, _initializer_ata, config_pda, stake_account_main, stake_account_reserve, _vote_pubkey) =
+            run_initialize(&mut svm);
+
+        run_crank_record_rate(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+
+        let apy = get_apy(&mut svm, &initializer);
+        assert_eq!(apy, vec![(0, 0), (0, 0), (0, 0)], "No window should be available yet");
+    }
+
+    #[test]
+    fn test_get_apy_reports_rewards() {
+        let mut svm = setup_svm();
+        let (initializer, token_mint, _initializer_ata, config_pda, stake_account_main, stake_account_reserve, _vote_pubkey) =
+            run_initialize(&mut svm);
+
+        run_crank_record_rate(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+
+        for epoch in 1..=7 {
+            add_stake_rewards(&mut svm, &stake_account_main, 1_000_000);
+            warp_epoch(&mut svm, epoch);
+            run_crank_management_fee(
+                &mut svm,
+                &initializer,
+                &config_pda,
+                &stake_account_main,
+                &stake_account_reserve,
+                &token_mint.pubkey(),
+            );
+            run_crank_record_rate(
+                &mut svm,
+                &initializer,
+                &config_pda,
+                &stake_account_main,
+                &stake_account_reserve,
+                &token_mint.pubkey(),
+            );
+        }
+
+        let apy = get_apy(&mut svm, &initializer);
+        assert_eq!(apy[0].0, 1, "1-epoch window should cover one epoch");
+        assert_eq!(apy[1].0, 7, "7-epoch window should cover seven epochs");
+        assert_eq!(apy[2].0, 0, "30-epoch window should be unavailable");
+        assert!(apy[0].1 > 0, "1-epoch APY should be positive");
+        assert!(apy[1].1 > 0, "7-epoch APY should be positive");
+    }
+
+    #[test]
+    fn test_get_apy_flat_rate() {
+        let mut svm = setup_svm();
+        let (initializer, token_mint, _initializer_ata, config_pda, stake_account_main, stake_account_reserve, _vote_pubkey) =
+            run_initialize(&mut svm);
+
+        run_crank_record_rate(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+
+        warp_epoch(&mut svm, 1);
+
+        run_crank_management_fee(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+        run_crank_record_rate(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+
+        let apy = get_apy(&mut svm, &initializer);
+        assert_eq!(apy[0], (1, 0), "Flat rate should report zero APY");
+    }
+}
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use solana_sdk::pubkey::Pubkey;
+
+    use solana_liquid_staking::ids::{
+        ASSOCIATED_TOKEN_PROGRAM_ID, CLOCK_SYSVAR_ID, ED25519_PROGRAM_ID, INSTRUCTIONS_SYSVAR_ID,
+        RENT_SYSVAR_ID, STAKE_HISTORY_SYSVAR_ID, STAKE_PROGRAM_ID, SYSTEM_PROGRAM_ID,
+        TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID, VOTE_PROGRAM_ID,
+    };
+
+    #[test]
+    fn test_ids_match_their_published_addresses() {
+        let check = |id: [u8; 32], address: &str| {
+            assert_eq!(
+                Pubkey::new_from_array(id),
+                Pubkey::from_str(address).unwrap(),
+                "{address}"
+            );
+        };
+
+        check(SYSTEM_PROGRAM_ID, "11111111111111111111111111111111");
+        check(
+            TOKEN_PROGRAM_ID,
+            "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+        );
+        check(
+            TOKEN_2022_PROGRAM_ID,
+            "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb",
+        );
+        check(
+            ASSOCIATED_TOKEN_PROGRAM_ID,
+            "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
+        );
+        check(
+            STAKE_PROGRAM_ID,
+            "Stake11111111111111111111111111111111111111",
+        );
+        check(
+            VOTE_PROGRAM_ID,
+            "Vote111111111111111111111111111111111111111",
+        );
+        check(
+            ED25519_PROGRAM_ID,
+            "Ed25519SigVerify111111111111111111111111111",
+        );
+        check(
+            CLOCK_SYSVAR_ID,
+            "SysvarC1ock11111111111111111111111111111111",
+        );
+        check(
+            RENT_SYSVAR_ID,
+            "SysvarRent111111111111111111111111111111111",
+        );
+        check(
+            STAKE_HISTORY_SYSVAR_ID,
+            "SysvarStakeHistory1111111111111111111111111",
+        );
+        check(
+            INSTRUCTIONS_SYSVAR_ID,
+            "Sysvar1nstructions1111111111111111111111111",
+        );
+    }
+}
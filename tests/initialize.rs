@@ -2,7 +2,11 @@ mod test_helpers;
 
 #[cfg(test)]
 mod tests {
-    use solana_liquid_staking::instructions::helpers::STAKE_PROGRAM_ID;
+    use solana_liquid_staking::{
+        ids::STAKE_PROGRAM_ID,
+        instructions::helpers::DEFAULT_STAKE_ACCOUNT_SPACE,
+        state::{AccountDiscriminator, AccountHeader, Config},
+    };
     use solana_program::example_mocks::solana_sdk::system_program;
     use solana_pubkey::Pubkey;
     use solana_sdk::{
@@ -14,15 +18,23 @@ mod tests {
     };
 
     use crate::test_helpers::test_helpers::{
-        build_initialize_ix, create_and_fund_ata, create_mock_token_mint, print_transaction_logs,
-        setup_initialize_accounts, setup_svm, HISTORY_SYSVAR, PROGRAM_ID,
+        build_initialize_ix, build_set_deposit_fee_ix, changelog_pda, create_and_fund_ata,
+        create_mock_token_mint, epoch_tasks_pda, pool_state_pda, print_transaction_logs,
+        run_initialize, setup_initialize_accounts, setup_svm, HISTORY_SYSVAR, PROGRAM_ID,
     };
 
     #[test]
     fn test_initialize_success() {
         let mut svm = setup_svm();
-        let (initializer, token_mint, initializer_ata, config_pda, stake_account_main, stake_account_reserve, vote_pubkey) =
-            setup_initialize_accounts(&mut svm);
+        let (
+            initializer,
+            token_mint,
+            initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = setup_initialize_accounts(&mut svm);
 
         let ix = build_initialize_ix(
             &initializer.pubkey(),
@@ -61,8 +73,15 @@ mod tests {
     #[test]
     fn test_initialize_fail_initializer_not_signer() {
         let mut svm = setup_svm();
-        let (initializer, token_mint, initializer_ata, config_pda, stake_account_main, stake_account_reserve, vote_pubkey) =
-            setup_initialize_accounts(&mut svm);
+        let (
+            initializer,
+            token_mint,
+            initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = setup_initialize_accounts(&mut svm);
 
         // Use a separate fee payer so initializer is NOT automatically a signer
         let fee_payer = Keypair::new();
@@ -91,13 +110,16 @@ mod tests {
                 AccountMeta::new_readonly(rent_sysvar, false),
                 AccountMeta::new_readonly(clock_sysvar, false),
                 AccountMeta::new_readonly(HISTORY_SYSVAR, false),
+                AccountMeta::new(epoch_tasks_pda(), false),
+                AccountMeta::new(pool_state_pda(), false),
+                AccountMeta::new(changelog_pda(), false),
             ],
         };
 
         let tx = Transaction::new_signed_with_payer(
             &[ix],
-            Some(&fee_payer.pubkey()),        // <-- fee payer is NOT the initializer
-            &[&fee_payer, &token_mint],        // <-- initializer not included as signer
+            Some(&fee_payer.pubkey()), // <-- fee payer is NOT the initializer
+            &[&fee_payer, &token_mint], // <-- initializer not included as signer
             svm.latest_blockhash(),
         );
 
@@ -109,8 +131,15 @@ mod tests {
     #[test]
     fn test_initialize_fail_lst_mint_not_signer() {
         let mut svm = setup_svm();
-        let (initializer, token_mint, initializer_ata, config_pda, stake_account_main, stake_account_reserve, vote_pubkey) =
-            setup_initialize_accounts(&mut svm);
+        let (
+            initializer,
+            token_mint,
+            initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = setup_initialize_accounts(&mut svm);
 
         // SCREWING UP: token_mint is_signer = false
         let ix = build_initialize_ix(
@@ -143,8 +172,15 @@ mod tests {
     #[test]
     fn test_initialize_fail_wrong_config_pda() {
         let mut svm = setup_svm();
-        let (initializer, token_mint, initializer_ata, _config_pda, stake_account_main, stake_account_reserve, vote_pubkey) =
-            setup_initialize_accounts(&mut svm);
+        let (
+            initializer,
+            token_mint,
+            initializer_ata,
+            _config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = setup_initialize_accounts(&mut svm);
 
         // SCREWING UP: deriving config PDA with wrong seed
         let wrong_config_pda = Pubkey::find_program_address(&[b"wrong_config"], &PROGRAM_ID).0;
@@ -179,8 +215,15 @@ mod tests {
     #[test]
     fn test_initialize_fail_stake_account_main_already_initialized() {
         let mut svm = setup_svm();
-        let (initializer, token_mint, initializer_ata, config_pda, stake_account_main, stake_account_reserve, vote_pubkey) =
-            setup_initialize_accounts(&mut svm);
+        let (
+            initializer,
+            token_mint,
+            initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = setup_initialize_accounts(&mut svm);
 
         // SCREWING UP: pre-initializing stake_account_main so it's not empty
         svm.set_account(
@@ -220,14 +263,24 @@ mod tests {
 
         let result = svm.send_transaction(tx);
         print_transaction_logs(&result);
-        assert!(result.is_err(), "Should fail: stake_account_main already initialized");
+        assert!(
+            result.is_err(),
+            "Should fail: stake_account_main already initialized"
+        );
     }
 
     #[test]
     fn test_initialize_fail_stake_account_reserve_already_initialized() {
         let mut svm = setup_svm();
-        let (initializer, token_mint, initializer_ata, config_pda, stake_account_main, stake_account_reserve, vote_pubkey) =
-            setup_initialize_accounts(&mut svm);
+        let (
+            initializer,
+            token_mint,
+            initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = setup_initialize_accounts(&mut svm);
 
         // SCREWING UP: pre-initializing stake_account_reserve so it's not empty
         svm.set_account(
@@ -267,14 +320,24 @@ mod tests {
 
         let result = svm.send_transaction(tx);
         print_transaction_logs(&result);
-        assert!(result.is_err(), "Should fail: stake_account_reserve already initialized");
+        assert!(
+            result.is_err(),
+            "Should fail: stake_account_reserve already initialized"
+        );
     }
 
     #[test]
     fn test_initialize_fail_wrong_stake_account_main() {
         let mut svm = setup_svm();
-        let (initializer, token_mint, initializer_ata, config_pda, _stake_account_main, stake_account_reserve, vote_pubkey) =
-            setup_initialize_accounts(&mut svm);
+        let (
+            initializer,
+            token_mint,
+            initializer_ata,
+            config_pda,
+            _stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = setup_initialize_accounts(&mut svm);
 
         // SCREWING UP: deriving stake_account_main with wrong seed
         let wrong_stake_main = Pubkey::find_program_address(&[b"wrong_stake_main"], &PROGRAM_ID).0;
@@ -303,14 +366,24 @@ mod tests {
 
         let result = svm.send_transaction(tx);
         print_transaction_logs(&result);
-        assert!(result.is_err(), "Should fail: wrong stake_account_main derivation");
+        assert!(
+            result.is_err(),
+            "Should fail: wrong stake_account_main derivation"
+        );
     }
 
     #[test]
     fn test_initialize_fail_wrong_stake_account_reserve() {
         let mut svm = setup_svm();
-        let (initializer, token_mint, initializer_ata, config_pda, stake_account_main, _stake_account_reserve, vote_pubkey) =
-            setup_initialize_accounts(&mut svm);
+        let (
+            initializer,
+            token_mint,
+            initializer_ata,
+            config_pda,
+            stake_account_main,
+            _stake_account_reserve,
+            vote_pubkey,
+        ) = setup_initialize_accounts(&mut svm);
 
         // SCREWING UP: deriving stake_account_reserve with wrong seed
         let wrong_stake_reserve = Pubkey::find_program_address(&[b"wrong_reserve"], &PROGRAM_ID).0;
@@ -339,14 +412,24 @@ mod tests {
 
         let result = svm.send_transaction(tx);
         print_transaction_logs(&result);
-        assert!(result.is_err(), "Should fail: wrong stake_account_reserve derivation");
+        assert!(
+            result.is_err(),
+            "Should fail: wrong stake_account_reserve derivation"
+        );
     }
 
     #[test]
     fn test_initialize_fail_wrong_system_program() {
         let mut svm = setup_svm();
-        let (initializer, token_mint, initializer_ata, config_pda, stake_account_main, stake_account_reserve, vote_pubkey) =
-            setup_initialize_accounts(&mut svm);
+        let (
+            initializer,
+            token_mint,
+            initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = setup_initialize_accounts(&mut svm);
 
         // SCREWING UP: passing a fake system program
         let fake_system_program = Pubkey::new_unique();
@@ -381,8 +464,15 @@ mod tests {
     #[test]
     fn test_initialize_fail_wrong_token_program() {
         let mut svm = setup_svm();
-        let (initializer, token_mint, initializer_ata, config_pda, stake_account_main, stake_account_reserve, vote_pubkey) =
-            setup_initialize_accounts(&mut svm);
+        let (
+            initializer,
+            token_mint,
+            initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = setup_initialize_accounts(&mut svm);
 
         // SCREWING UP: passing a fake token program
         let fake_token_program = Pubkey::new_unique();
@@ -417,8 +507,15 @@ mod tests {
     #[test]
     fn test_initialize_fail_wrong_associated_token_program() {
         let mut svm = setup_svm();
-        let (initializer, token_mint, initializer_ata, config_pda, stake_account_main, stake_account_reserve, vote_pubkey) =
-            setup_initialize_accounts(&mut svm);
+        let (
+            initializer,
+            token_mint,
+            initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = setup_initialize_accounts(&mut svm);
 
         // SCREWING UP: passing a fake associated token program
         let fake_ata_program = Pubkey::new_unique();
@@ -447,14 +544,24 @@ mod tests {
 
         let result = svm.send_transaction(tx);
         print_transaction_logs(&result);
-        assert!(result.is_err(), "Should fail: wrong associated token program");
+        assert!(
+            result.is_err(),
+            "Should fail: wrong associated token program"
+        );
     }
 
     #[test]
     fn test_initialize_fail_wrong_stake_program() {
         let mut svm = setup_svm();
-        let (initializer, token_mint, initializer_ata, config_pda, stake_account_main, stake_account_reserve, vote_pubkey) =
-            setup_initialize_accounts(&mut svm);
+        let (
+            initializer,
+            token_mint,
+            initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = setup_initialize_accounts(&mut svm);
 
         // SCREWING UP: passing a fake stake program
         let fake_stake_program = Pubkey::new_unique();
@@ -489,8 +596,15 @@ mod tests {
     #[test]
     fn test_initialize_fail_insufficient_sol() {
         let mut svm = setup_svm();
-        let (_initializer, token_mint, initializer_ata, config_pda, stake_account_main, stake_account_reserve, vote_pubkey) =
-            setup_initialize_accounts(&mut svm);
+        let (
+            _initializer,
+            token_mint,
+            initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = setup_initialize_accounts(&mut svm);
 
         // SCREWING UP: replace initializer with a new keypair that has almost no SOL
         let broke_initializer = Keypair::new();
@@ -520,14 +634,24 @@ mod tests {
 
         let result = svm.send_transaction(tx);
         print_transaction_logs(&result);
-        assert!(result.is_err(), "Should fail: initializer has insufficient SOL");
+        assert!(
+            result.is_err(),
+            "Should fail: initializer has insufficient SOL"
+        );
     }
 
     #[test]
     fn test_initialize_fail_wrong_mint_authority() {
         let mut svm = setup_svm();
-        let (initializer, _token_mint, initializer_ata, config_pda, stake_account_main, stake_account_reserve, vote_pubkey) =
-            setup_initialize_accounts(&mut svm);
+        let (
+            initializer,
+            _token_mint,
+            initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = setup_initialize_accounts(&mut svm);
 
         // SCREWING UP: creating mint with wrong authority (random key instead of config_pda)
         let wrong_authority = Pubkey::new_unique();
@@ -563,13 +687,21 @@ mod tests {
     #[test]
     fn test_initialize_fail_wrong_ata_owner() {
         let mut svm = setup_svm();
-        let (initializer, token_mint, _initializer_ata, config_pda, stake_account_main, stake_account_reserve, vote_pubkey) =
-            setup_initialize_accounts(&mut svm);
+        let (
+            initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = setup_initialize_accounts(&mut svm);
 
         // SCREWING UP: creating an ATA that belongs to a different owner
         let other_owner = Keypair::new();
         svm.airdrop(&other_owner.pubkey(), 1_000_000_000).unwrap();
-        let wrong_ata = create_and_fund_ata(&mut svm, &other_owner.pubkey(), &token_mint.pubkey(), 0); // <-- ATA owned by someone else
+        let wrong_ata =
+            create_and_fund_ata(&mut svm, &other_owner.pubkey(), &token_mint.pubkey(), 0); // <-- ATA owned by someone else
 
         let ix = build_initialize_ix(
             &initializer.pubkey(),
@@ -597,4 +729,131 @@ mod tests {
         print_transaction_logs(&result);
         assert!(result.is_err(), "Should fail: ATA belongs to wrong owner");
     }
+
+    #[test]
+    fn test_initialize_stake_accounts_use_default_space() {
+        let mut svm = setup_svm();
+        let (_, _, _, _, stake_account_main, stake_account_reserve, _) = run_initialize(&mut svm);
+
+        for stake_account in [stake_account_main, stake_account_reserve] {
+            assert_eq!(
+                svm.get_account(&stake_account).unwrap().data.len(),
+                DEFAULT_STAKE_ACCOUNT_SPACE,
+                "Stake accounts should be created with the default space"
+            );
+        }
+    }
+
+    #[test]
+    fn test_default_stake_account_space_matches_stake_program() {
+        let mut svm = setup_svm();
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+        for space in [
+            DEFAULT_STAKE_ACCOUNT_SPACE - 1,
+            DEFAULT_STAKE_ACCOUNT_SPACE,
+            DEFAULT_STAKE_ACCOUNT_SPACE + 1,
+        ] {
+            let stake_account = Pubkey::new_unique();
+            svm.set_account(
+                stake_account,
+                Account {
+                    lamports: 1_000_000_000,
+                    data: vec![0u8; space],
+                    owner: Pubkey::from(STAKE_PROGRAM_ID),
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            )
+            .unwrap();
+
+            // StakeInstruction::Initialize with payer as staker and withdrawer
+            // and no lockup.
+            let mut data = 0u32.to_le_bytes().to_vec();
+            data.extend_from_slice(payer.pubkey().as_ref());
+            data.extend_from_slice(payer.pubkey().as_ref());
+            data.extend_from_slice(&[0u8; 48]);
+
+            let ix = Instruction {
+                program_id: Pubkey::from(STAKE_PROGRAM_ID),
+                data,
+                accounts: vec![
+                    AccountMeta::new(stake_account, false),
+                    AccountMeta::new_readonly(solana_sdk::sysvar::rent::id(), false),
+                ],
+            };
+
+            let tx = Transaction::new_signed_with_payer(
+                &[ix],
+                Some(&payer.pubkey()),
+                &[&payer],
+                svm.latest_blockhash(),
+            );
+
+            let result = svm.send_transaction(tx);
+            print_transaction_logs(&result);
+            assert_eq!(
+                result.is_ok(),
+                space == DEFAULT_STAKE_ACCOUNT_SPACE,
+                "Stake program should only accept {} byte accounts, tried {}",
+                DEFAULT_STAKE_ACCOUNT_SPACE,
+                space
+            );
+        }
+    }
+
+    #[test]
+    fn test_initialize_writes_the_config_header() {
+        let mut svm = setup_svm();
+        let (admin, _, _, config_pda, _, _, _) = run_initialize(&mut svm);
+
+        let mut account = svm.get_account(&config_pda).unwrap();
+        assert_eq!(account.data[0], AccountDiscriminator::Config as u8);
+        assert_eq!(account.data[1], Config::VERSION);
+
+        // A program-owned account of the config's length but another type
+        // or layout version is not read as the config.
+        for header in [
+            [0, Config::VERSION],
+            [AccountDiscriminator::Config as u8, 2],
+        ] {
+            account.data[..2].copy_from_slice(&header);
+            svm.set_account(config_pda, account.clone()).unwrap();
+
+            let ix = build_set_deposit_fee_ix(&admin.pubkey(), &config_pda, 50);
+            let tx = Transaction::new_signed_with_payer(
+                &[ix],
+                Some(&admin.pubkey()),
+                &[&admin],
+                svm.latest_blockhash(),
+            );
+            let result = svm.send_transaction(tx);
+            print_transaction_logs(&result);
+            assert!(result.is_err(), "Header {:?} should be rejected", header);
+            svm.expire_blockhash();
+        }
+    }
+
+    #[test]
+    fn test_initialize_stores_the_pda_bumps() {
+        let mut svm = setup_svm();
+        let (_, _, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+
+        let config = svm.get_account(&config_pda).unwrap().data;
+        for (index, (seed, pda)) in [
+            (&b"config"[..], config_pda),
+            (&b"stake_main"[..], stake_account_main),
+            (&b"stake_reserve"[..], stake_account_reserve),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let (expected_pda, bump) =
+                solana_sdk::pubkey::Pubkey::find_program_address(&[seed], &PROGRAM_ID);
+            assert_eq!(expected_pda, pda);
+            assert_eq!(config[379 + index], bump);
+        }
+    }
 }
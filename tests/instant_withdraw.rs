@@ -0,0 +1,79 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::signature::Signer;
+
+    use crate::test_helpers::test_helpers::{
+        build_instant_withdraw_ix, get_mint_supply, get_token_balance,
+        run_crank_initialize_reserve, send, setup_pool, setup_svm, Pool, PoolSetup,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    impl Pool {
+        fn instant_withdraw(&self, svm: &mut LiteSVM, lst_amount: u64, min_out: u64) -> bool {
+            let ix = build_instant_withdraw_ix(
+                &self.depositor.pubkey(),
+                &self.config_pda,
+                &self.mint,
+                &self.stake_account_main,
+                &self.stake_account_reserve,
+                lst_amount,
+                min_out,
+            );
+            send(svm, ix, &self.depositor)
+        }
+    }
+
+    /// Initializes a pool whose reserve holds an undelegated 4 SOL deposit.
+    fn setup(svm: &mut LiteSVM) -> Pool {
+        setup_pool(
+            svm,
+            PoolSetup {
+                deposit: 4 * LAMPORTS_PER_SOL,
+                ..PoolSetup::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_instant_withdraw_pays_from_the_undelegated_reserve() {
+        let mut svm = setup_svm();
+        let pool = setup(&mut svm);
+
+        let lst = get_token_balance(&svm, &pool.depositor_ata) / 2;
+        let supply_before = get_mint_supply(&svm, &pool.mint);
+        let lamports_before = svm.get_balance(&pool.depositor.pubkey()).unwrap();
+
+        assert!(
+            !pool.instant_withdraw(&mut svm, lst, 3 * LAMPORTS_PER_SOL),
+            "The payout is below the minimum"
+        );
+        assert!(pool.instant_withdraw(&mut svm, lst, LAMPORTS_PER_SOL));
+
+        assert_eq!(get_mint_supply(&svm, &pool.mint), supply_before - lst);
+        let received = svm.get_balance(&pool.depositor.pubkey()).unwrap() - lamports_before;
+        assert!(received > LAMPORTS_PER_SOL && received <= 2 * LAMPORTS_PER_SOL);
+    }
+
+    #[test]
+    fn test_instant_withdraw_needs_an_undelegated_reserve() {
+        let mut svm = setup_svm();
+        let pool = setup(&mut svm);
+        let lst = get_token_balance(&svm, &pool.depositor_ata) / 4;
+
+        run_crank_initialize_reserve(
+            &mut svm,
+            &pool.admin,
+            &pool.config_pda,
+            &pool.stake_account_reserve,
+            &pool.vote_pubkey,
+        );
+        assert!(
+            !pool.instant_withdraw(&mut svm, lst, 0),
+            "A delegated reserve has no plain lamports"
+        );
+    }
+}
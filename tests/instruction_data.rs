@@ -0,0 +1,252 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use std::any::type_name;
+
+    use litesvm::LiteSVM;
+    use solana_liquid_staking::instructions::{
+        add_liquidity::AddLiquidityInstructionData, burn_and_donate::BurnAndDonateInstructionData,
+        burn_from_wrapper::BurnFromWrapperInstructionData,
+        cancel_unstake_order::CancelUnstakeOrderInstructionData,
+        claim_vested::ClaimVestedInstructionData,
+        close_deposit_key::CloseDepositKeyInstructionData,
+        close_used_quote::CloseUsedQuoteInstructionData, crank_dca::CrankDcaInstructionData,
+        crank_refill_buffer::CrankRefillBufferInstructionData,
+        crank_split::CrankSplitInstructionData,
+        crank_split_single::CrankSplitSingleInstructionData,
+        crank_split_tranche::CrankSplitTrancheInstructionData,
+        create_dca_schedule::CreateDcaScheduleInstructionData,
+        create_deposit_session::CreateDepositSessionInstructionData,
+        create_unstake_order::CreateUnstakeOrderInstructionData,
+        create_vesting::CreateVestingInstructionData, deposit::DepositData,
+        deposit_with_session::DepositWithSessionInstructionData,
+        distribute_treasury::DistributeTreasuryInstructionData,
+        enroll_payout::EnrollPayoutInstructionData, exit_pool::ExitPoolInstructionData,
+        fill_unstake_order::FillUnstakeOrderInstructionData,
+        get_stake_distribution::GetStakeDistributionInstructionData, helpers::EmptyInstructionData,
+        import_balance::ImportBalanceInstructionData,
+        instant_withdraw::InstantWithdrawInstructionData,
+        join_validator_set::JoinValidatorSetInstructionData,
+        liquid_unstake::LiquidUnstakeInstructionData,
+        mint_to_wrapper::MintToWrapperInstructionData,
+        open_migration::OpenMigrationInstructionData,
+        remove_liquidity::RemoveLiquidityInstructionData,
+        set_bonus_schedule::SetBonusScheduleInstructionData,
+        set_buyback_policy::SetBuybackPolicyInstructionData,
+        set_crank_tip::SetCrankTipInstructionData,
+        set_delegation_strategy::SetDelegationStrategyInstructionData,
+        set_deposit_authority::SetDepositAuthorityInstructionData,
+        set_deposit_cap::SetDepositCapInstructionData,
+        set_holding_rebate::SetHoldingRebateInstructionData,
+        set_main_headroom::SetMainHeadroomInstructionData, set_pool_cap::SetPoolCapInstructionData,
+        set_pool_status::SetPoolStatusInstructionData,
+        set_quote_signer::SetQuoteSignerInstructionData,
+        set_reward_payout::SetRewardPayoutInstructionData,
+        set_split_minimum::SetSplitMinimumInstructionData,
+        set_trusted_caller::SetTrustedCallerInstructionData,
+        set_unstake_fee_params::SetUnstakeFeeParamsInstructionData,
+        set_validator_metrics::SetValidatorMetricsInstructionData,
+        set_validator_stake_cap::SetValidatorStakeCapInstructionData,
+        set_validator_status::SetValidatorStatusInstructionData,
+        set_wrapper_program::SetWrapperProgramInstructionData,
+        simulate_params::SimulateParamsInstructionData,
+        slash_validator_bond::SlashValidatorBondInstructionData,
+        swap_buffer::SwapBufferInstructionData, withdraw::WithdrawInstructionData,
+        withdraw_payout::WithdrawPayoutInstructionData,
+        withdraw_stake::WithdrawStakeInstructionData,
+    };
+    use solana_sdk::{
+        instruction::Instruction,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_get_pending_cranks_ix, run_initialize, setup_svm,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    fn parses<T: for<'a> TryFrom<&'a [u8]>>(data: &[u8]) -> bool {
+        T::try_from(data).is_ok()
+    }
+
+    /// Asserts `valid` parses while one byte less or one trailing byte more
+    /// does not.
+    fn assert_boundaries<T: for<'a> TryFrom<&'a [u8]>>(valid: &[u8]) {
+        let name = type_name::<T>();
+        assert!(parses::<T>(valid), "{name} rejects {} bytes", valid.len());
+        if let Some((_, short)) = valid.split_last() {
+            assert!(!parses::<T>(short), "{name} accepts {} bytes", short.len());
+        }
+        let mut long = valid.to_vec();
+        long.push(0);
+        assert!(!parses::<T>(&long), "{name} accepts {} bytes", long.len());
+    }
+
+    /// Lengths up to 96 bytes that parse when every byte is `fill`, after the
+    /// first eight set to `first_u64`.
+    fn accepted_lengths<T: for<'a> TryFrom<&'a [u8]>>(first_u64: u64, fill: u8) -> Vec<usize> {
+        let mut data = [fill; 96];
+        data[0..8].copy_from_slice(&first_u64.to_le_bytes());
+        (0..=96).filter(|&len| parses::<T>(&data[..len])).collect()
+    }
+
+    fn le(fields: &[&[u8]]) -> Vec<u8> {
+        fields.concat()
+    }
+
+    #[test]
+    fn test_fixed_length_parsers_reject_short_and_trailing_bytes() {
+        let one = 1u64.to_le_bytes();
+        let sol = LAMPORTS_PER_SOL.to_le_bytes();
+        let key = [7u8; 32];
+
+        assert_boundaries::<EmptyInstructionData>(&[]);
+        assert_boundaries::<AddLiquidityInstructionData>(&one);
+        assert_boundaries::<BurnAndDonateInstructionData>(&one);
+        assert_boundaries::<BurnFromWrapperInstructionData>(&le(&[&one, &one]));
+        assert_boundaries::<CancelUnstakeOrderInstructionData>(&one);
+        assert_boundaries::<ClaimVestedInstructionData>(&one);
+        assert_boundaries::<CloseDepositKeyInstructionData>(&key);
+        assert_boundaries::<CloseUsedQuoteInstructionData>(&one);
+        assert_boundaries::<CrankDcaInstructionData>(&one);
+        assert_boundaries::<CrankRefillBufferInstructionData>(&[0]);
+        assert_boundaries::<CrankSplitTrancheInstructionData>(&le(&[&one, &one]));
+        assert_boundaries::<CreateDcaScheduleInstructionData>(&le(&[
+            &one,
+            &sol,
+            &60i64.to_le_bytes(),
+            &0u64.to_le_bytes(),
+            &sol,
+        ]));
+        assert_boundaries::<CreateDepositSessionInstructionData>(&le(&[&one, &sol]));
+        assert_boundaries::<CreateUnstakeOrderInstructionData>(&le(&[&one, &sol, &sol, &one]));
+        assert_boundaries::<CreateVestingInstructionData>(&le(&[
+            &one,
+            &sol,
+            &0i64.to_le_bytes(),
+            &10i64.to_le_bytes(),
+            &20i64.to_le_bytes(),
+        ]));
+        assert_boundaries::<DepositWithSessionInstructionData>(&sol);
+        assert_boundaries::<EnrollPayoutInstructionData>(&one);
+        assert_boundaries::<ExitPoolInstructionData>(&one);
+        assert_boundaries::<FillUnstakeOrderInstructionData>(&one);
+        assert_boundaries::<GetStakeDistributionInstructionData>(&[0]);
+        assert_boundaries::<ImportBalanceInstructionData>(&one);
+        assert_boundaries::<InstantWithdrawInstructionData>(&le(&[&one, &one]));
+        assert_boundaries::<JoinValidatorSetInstructionData>(&sol);
+        assert_boundaries::<LiquidUnstakeInstructionData>(&le(&[&one, &one]));
+        assert_boundaries::<MintToWrapperInstructionData>(&sol);
+        assert_boundaries::<OpenMigrationInstructionData>(&one);
+        assert_boundaries::<RemoveLiquidityInstructionData>(&one);
+        assert_boundaries::<SetBonusScheduleInstructionData>(&le(&[&500u16.to_le_bytes(), &one]));
+        assert_boundaries::<SetBuybackPolicyInstructionData>(&[1]);
+        assert_boundaries::<SetCrankTipInstructionData>(&sol);
+        assert_boundaries::<SetDelegationStrategyInstructionData>(&[2]);
+        assert_boundaries::<SetDepositAuthorityInstructionData>(&key);
+        assert_boundaries::<SetDepositCapInstructionData>(&le(&[&sol, &[1]]));
+        assert_boundaries::<SetHoldingRebateInstructionData>(&le(&[&5_000u16.to_le_bytes(), &one]));
+        assert_boundaries::<SetMainHeadroomInstructionData>(&sol);
+        assert_boundaries::<SetPoolCapInstructionData>(&sol);
+        assert_boundaries::<SetPoolStatusInstructionData>(&[1]);
+        assert_boundaries::<SetQuoteSignerInstructionData>(&le(&[&key, &100u16.to_le_bytes()]));
+        assert_boundaries::<SetRewardPayoutInstructionData>(&5_000u16.to_le_bytes());
+        assert_boundaries::<SetSplitMinimumInstructionData>(&sol);
+        assert_boundaries::<SetTrustedCallerInstructionData>(&le(&[&key, &[1]]));
+        let fee_params = le(&[&10u16.to_le_bytes(), &300u16.to_le_bytes(), &sol]);
+        assert_boundaries::<SetUnstakeFeeParamsInstructionData>(&fee_params);
+        assert_boundaries::<SetValidatorMetricsInstructionData>(&le(&[
+            &key,
+            &1u32.to_le_bytes(),
+            &1u32.to_le_bytes(),
+        ]));
+        assert_boundaries::<SetValidatorStakeCapInstructionData>(&le(&[
+            &5_000u16.to_le_bytes(),
+            &sol,
+        ]));
+        assert_boundaries::<SetValidatorStatusInstructionData>(&[1]);
+        assert_boundaries::<SetWrapperProgramInstructionData>(&key);
+        assert_boundaries::<SimulateParamsInstructionData>(&le(&[&fee_params, &sol, &sol, &one]));
+        assert_boundaries::<SlashValidatorBondInstructionData>(&one);
+        assert_boundaries::<SwapBufferInstructionData>(&le(&[&[0], &one, &one]));
+        assert_boundaries::<WithdrawPayoutInstructionData>(&one);
+        assert_boundaries::<WithdrawStakeInstructionData>(&le(&[&sol, &one]));
+    }
+
+    #[test]
+    fn test_versioned_parsers_accept_only_their_listed_lengths() {
+        assert_eq!(
+            accepted_lengths::<DepositData>(LAMPORTS_PER_SOL, 1),
+            vec![8, 9, 10, 11, 40, 41, 42, 43, 72, 73, 74, 75, 80, 81, 82, 83]
+        );
+        // Without the skip-checks and quote flags only the lengths without
+        // them parse.
+        assert_eq!(
+            accepted_lengths::<DepositData>(LAMPORTS_PER_SOL, 0),
+            vec![8, 40, 72, 80]
+        );
+        assert_eq!(
+            accepted_lengths::<CrankSplitInstructionData>(1, 1),
+            vec![16, 24]
+        );
+        assert_eq!(
+            accepted_lengths::<CrankSplitSingleInstructionData>(1, 1),
+            vec![8, 16]
+        );
+        assert_eq!(
+            accepted_lengths::<WithdrawInstructionData>(1, 1),
+            vec![8, 9]
+        );
+        // The restake flag is a bool, not any byte.
+        assert_eq!(accepted_lengths::<WithdrawInstructionData>(1, 2), vec![8]);
+    }
+
+    #[test]
+    fn test_distribute_treasury_accepts_whole_amounts_only() {
+        for len in 0..=24 {
+            let data = vec![1u8; len];
+            assert_eq!(
+                DistributeTreasuryInstructionData::try_from(data.as_slice()).is_ok(),
+                len > 0 && len % 8 == 0,
+                "{len} bytes"
+            );
+        }
+    }
+
+    #[test]
+    fn test_instruction_without_data_rejects_trailing_byte() {
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            _token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+        let ix =
+            build_get_pending_cranks_ix(&config_pda, &stake_account_main, &stake_account_reserve);
+
+        let simulate = |svm: &mut LiteSVM, ix: Instruction| {
+            let payer = Keypair::new();
+            svm.airdrop(&payer.pubkey(), LAMPORTS_PER_SOL).unwrap();
+            let tx = Transaction::new_signed_with_payer(
+                &[ix],
+                Some(&payer.pubkey()),
+                &[&payer],
+                svm.latest_blockhash(),
+            );
+            svm.simulate_transaction(tx).is_ok()
+        };
+
+        assert!(simulate(&mut svm, ix.clone()));
+
+        let mut trailing = ix;
+        trailing.data.push(0);
+        assert!(!simulate(&mut svm, trailing));
+    }
+}
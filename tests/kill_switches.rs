@@ -0,0 +1,131 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_liquid_staking::state::ParamChange;
+    use solana_sdk::signature::{Keypair, Signer};
+
+    use crate::test_helpers::test_helpers::{
+        build_crank_update_rate_ix, build_deposit_ix, build_set_deposit_cap_ix,
+        build_set_instruction_enabled_ix, create_and_fund_ata, get_changelog, run_initialize, send,
+        setup_svm,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    const DEPOSIT: u8 = 3;
+    const SET_INSTRUCTION_ENABLED: u8 = 52;
+    const CRANK_UPDATE_RATE: u8 = 95;
+
+    #[test]
+    fn test_disabled_instruction_fails_until_enabled_again() {
+        let mut svm = setup_svm();
+        let (admin, mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .unwrap();
+        let depositor_ata = create_and_fund_ata(&mut svm, &depositor.pubkey(), &mint.pubkey(), 0);
+        let deposit_ix = build_deposit_ix(
+            &config_pda,
+            &depositor.pubkey(),
+            &depositor_ata,
+            &mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            LAMPORTS_PER_SOL,
+            true,
+        );
+
+        assert!(send(
+            &mut svm,
+            build_set_instruction_enabled_ix(&admin.pubkey(), &config_pda, DEPOSIT, false),
+            &admin,
+        ));
+        assert!(!send(&mut svm, deposit_ix.clone(), &depositor));
+
+        // The rest of the pool keeps running.
+        assert!(send(
+            &mut svm,
+            build_set_deposit_cap_ix(&admin.pubkey(), &config_pda, 0, 0),
+            &admin,
+        ));
+
+        assert!(send(
+            &mut svm,
+            build_set_instruction_enabled_ix(&admin.pubkey(), &config_pda, DEPOSIT, true),
+            &admin,
+        ));
+        assert!(send(&mut svm, deposit_ix, &depositor));
+
+        let changes = get_changelog(&svm);
+        let ParamChange {
+            parameter,
+            old_value,
+            new_value,
+            ..
+        } = changes[0];
+        assert_eq!(parameter, SET_INSTRUCTION_ENABLED);
+        assert_eq!(old_value[..2], [DEPOSIT, 1]);
+        assert_eq!(new_value[..2], [DEPOSIT, 0]);
+    }
+
+    #[test]
+    fn test_only_admin_toggles_and_the_switch_stays_on() {
+        let mut svm = setup_svm();
+        let (admin, _, _, config_pda, _, _, _) = run_initialize(&mut svm);
+
+        let outsider = Keypair::new();
+        svm.airdrop(&outsider.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        assert!(!send(
+            &mut svm,
+            build_set_instruction_enabled_ix(&outsider.pubkey(), &config_pda, DEPOSIT, false),
+            &outsider,
+        ));
+
+        assert!(!send(
+            &mut svm,
+            build_set_instruction_enabled_ix(
+                &admin.pubkey(),
+                &config_pda,
+                SET_INSTRUCTION_ENABLED,
+                false,
+            ),
+            &admin,
+        ));
+
+        assert!(get_changelog(&svm).is_empty());
+    }
+
+    #[test]
+    fn test_switch_reads_the_config_at_its_fixed_position() {
+        let mut svm = setup_svm();
+        let (admin, mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+
+        assert!(send(
+            &mut svm,
+            build_set_instruction_enabled_ix(
+                &admin.pubkey(),
+                &config_pda,
+                CRANK_UPDATE_RATE,
+                false,
+            ),
+            &admin,
+        ));
+
+        let crank_ix = build_crank_update_rate_ix(
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &mint.pubkey(),
+        );
+        assert!(!send(&mut svm, crank_ix.clone(), &admin));
+
+        // Moving the config out of its slot does not skip the switch.
+        let mut moved_ix = crank_ix;
+        moved_ix.accounts.swap(0, 1);
+        assert!(!send(&mut svm, moved_ix, &admin));
+    }
+}
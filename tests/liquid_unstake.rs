@@ -0,0 +1,246 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_liquid_staking::math::unstake_fee_bps;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+
+    use crate::test_helpers::test_helpers::{
+        build_add_liquidity_ix, build_liquid_unstake_ix, build_set_unstake_fee_params_ix,
+        create_and_fund_ata, get_token_balance, liquidity_pool_pda, run_activate_pool, run_deposit,
+        run_initialize, run_initialize_liquidity_pool, send, setup_svm,
+    };
+
+    fn sol_reserves(svm: &litesvm::LiteSVM) -> u64 {
+        let data = svm.get_account(&liquidity_pool_pda()).unwrap().data;
+        u64::from_le_bytes(data[66..74].try_into().unwrap())
+    }
+
+    /// Initializes the pool and a liquidity pool holding `liquidity` SOL,
+    /// then deposits 5 SOL for an unstaker.
+    fn setup_liquid_pool(
+        svm: &mut litesvm::LiteSVM,
+        liquidity: u64,
+    ) -> (Keypair, Pubkey, Pubkey, Pubkey, Pubkey, Keypair, Pubkey) {
+        let (initializer, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(svm);
+        run_activate_pool(svm, &initializer, &config_pda);
+
+        let lp_mint =
+            run_initialize_liquidity_pool(svm, &initializer, &config_pda, &token_mint.pubkey());
+
+        let provider = Keypair::new();
+        svm.airdrop(&provider.pubkey(), liquidity + 1_000_000_000)
+            .unwrap();
+        create_and_fund_ata(svm, &provider.pubkey(), &lp_mint.pubkey(), 0);
+
+        let ix = build_add_liquidity_ix(
+            &provider.pubkey(),
+            &config_pda,
+            &lp_mint.pubkey(),
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            liquidity,
+        );
+        assert!(send(svm, ix, &provider), "AddLiquidity should succeed");
+
+        let (unstaker, unstaker_ata) = run_deposit(
+            svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            5_000_000_000,
+        );
+
+        (
+            initializer,
+            token_mint.pubkey(),
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            unstaker,
+            unstaker_ata,
+        )
+    }
+
+    #[test]
+    fn test_unstake_fee_curve() {
+        let target = 100_000_000_000;
+
+        // Buffer at or above target pays the minimum.
+        assert_eq!(unstake_fee_bps(30, 300, target, target), 30);
+        assert_eq!(unstake_fee_bps(30, 300, target, 2 * target), 30);
+        // Drained buffer pays the maximum.
+        assert_eq!(unstake_fee_bps(30, 300, target, 0), 300);
+        // Linear in between.
+        assert_eq!(unstake_fee_bps(30, 300, target, target / 2), 165);
+        assert_eq!(unstake_fee_bps(30, 300, target, target / 4), 232);
+        // Flat fee.
+        assert_eq!(unstake_fee_bps(50, 50, target, 0), 50);
+    }
+
+    #[test]
+    fn test_liquid_unstake_fee_rises_as_buffer_drains() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            lst_mint,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            unstaker,
+            unstaker_ata,
+        ) = setup_liquid_pool(&mut svm, 10_000_000_000);
+
+        let ix = build_set_unstake_fee_params_ix(
+            &initializer.pubkey(),
+            &config_pda,
+            30,
+            300,
+            10_000_000_000,
+        );
+        assert!(
+            send(&mut svm, ix, &initializer),
+            "Admin should set fee params"
+        );
+
+        let lst_vault = spl_associated_token_account::get_associated_token_address(
+            &liquidity_pool_pda(),
+            &lst_mint,
+        );
+        let lst_balance = get_token_balance(&svm, &unstaker_ata);
+
+        let mut payouts = Vec::new();
+        for _ in 0..2 {
+            let reserves_before = sol_reserves(&svm);
+            let lamports_before = svm.get_balance(&unstaker.pubkey()).unwrap();
+
+            let ix = build_liquid_unstake_ix(
+                &unstaker.pubkey(),
+                &unstaker_ata,
+                &config_pda,
+                &lst_mint,
+                &stake_account_main,
+                &stake_account_reserve,
+                1_500_000_000,
+                0,
+            );
+            assert!(
+                send(&mut svm, ix, &unstaker),
+                "LiquidUnstake should succeed"
+            );
+            svm.expire_blockhash();
+
+            let paid = reserves_before - sol_reserves(&svm);
+            assert_eq!(
+                svm.get_balance(&unstaker.pubkey()).unwrap(),
+                lamports_before + paid - 5_000
+            );
+            payouts.push(paid);
+        }
+
+        assert!(
+            payouts[1] < payouts[0],
+            "The second unstake drains the buffer further and pays a higher fee"
+        );
+        assert_eq!(
+            get_token_balance(&svm, &unstaker_ata),
+            lst_balance - 3_000_000_000
+        );
+        assert_eq!(get_token_balance(&svm, &lst_vault), 3_000_000_000);
+    }
+
+    #[test]
+    fn test_liquid_unstake_slippage_and_liquidity_limits() {
+        let mut svm = setup_svm();
+        let (
+            _,
+            lst_mint,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            unstaker,
+            unstaker_ata,
+        ) = setup_liquid_pool(&mut svm, 2_000_000_000);
+
+        let ix = build_liquid_unstake_ix(
+            &unstaker.pubkey(),
+            &unstaker_ata,
+            &config_pda,
+            &lst_mint,
+            &stake_account_main,
+            &stake_account_reserve,
+            1_000_000_000,
+            2_000_000_000,
+        );
+        assert!(
+            !send(&mut svm, ix, &unstaker),
+            "Output below min_lamports_out should be rejected"
+        );
+
+        let ix = build_liquid_unstake_ix(
+            &unstaker.pubkey(),
+            &unstaker_ata,
+            &config_pda,
+            &lst_mint,
+            &stake_account_main,
+            &stake_account_reserve,
+            4_000_000_000,
+            0,
+        );
+        assert!(
+            !send(&mut svm, ix, &unstaker),
+            "Unstake larger than the buffer should be rejected"
+        );
+        assert_eq!(sol_reserves(&svm), 2_000_000_000);
+    }
+
+    #[test]
+    fn test_set_unstake_fee_params_validation() {
+        let mut svm = setup_svm();
+        let (initializer, _, _, config_pda, _, _, _) = run_initialize(&mut svm);
+
+        let attacker = Keypair::new();
+        svm.airdrop(&attacker.pubkey(), 1_000_000_000).unwrap();
+        let ix =
+            build_set_unstake_fee_params_ix(&attacker.pubkey(), &config_pda, 0, 0, 1_000_000_000);
+        assert!(
+            !send(&mut svm, ix, &attacker),
+            "Only the admin may set fee params"
+        );
+
+        let ix = build_set_unstake_fee_params_ix(
+            &initializer.pubkey(),
+            &config_pda,
+            300,
+            30,
+            1_000_000_000,
+        );
+        assert!(
+            !send(&mut svm, ix, &initializer),
+            "Min fee above max fee should be rejected"
+        );
+
+        let ix = build_set_unstake_fee_params_ix(&initializer.pubkey(), &config_pda, 30, 300, 0);
+        assert!(
+            !send(&mut svm, ix, &initializer),
+            "Zero target should be rejected"
+        );
+
+        let ix = build_set_unstake_fee_params_ix(
+            &initializer.pubkey(),
+            &config_pda,
+            30,
+            10_001,
+            1_000_000_000,
+        );
+        assert!(
+            !send(&mut svm, ix, &initializer),
+            "Fee above 100% should be rejected"
+        );
+    }
+}
@@ -0,0 +1,212 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+    use solana_sdk::transaction::Transaction;
+
+    use crate::test_helpers::test_helpers::{
+        build_add_liquidity_ix, build_initialize_liquidity_pool_ix, build_remove_liquidity_ix,
+        create_and_fund_ata, get_mint_supply, get_token_balance, liquidity_pool_pda,
+        print_transaction_logs, run_deposit, run_initialize, run_initialize_liquidity_pool, send,
+        setup_svm,
+    };
+
+    fn sol_reserves(svm: &litesvm::LiteSVM) -> u64 {
+        let data = svm.get_account(&liquidity_pool_pda()).unwrap().data;
+        u64::from_le_bytes(data[66..74].try_into().unwrap())
+    }
+
+    #[test]
+    fn test_initialize_liquidity_pool() {
+        let mut svm = setup_svm();
+        let (initializer, token_mint, _, config_pda, _, _, _) = run_initialize(&mut svm);
+
+        let lp_mint = run_initialize_liquidity_pool(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &token_mint.pubkey(),
+        );
+
+        let data = svm.get_account(&liquidity_pool_pda()).unwrap().data;
+        assert_eq!(&data[2..34], lp_mint.pubkey().as_ref());
+        assert_eq!(sol_reserves(&svm), 0);
+        assert_eq!(get_mint_supply(&svm, &lp_mint.pubkey()), 0);
+
+        let lst_vault = spl_associated_token_account::get_associated_token_address(
+            &liquidity_pool_pda(),
+            &token_mint.pubkey(),
+        );
+        assert_eq!(&data[34..66], lst_vault.as_ref());
+        assert_eq!(get_token_balance(&svm, &lst_vault), 0);
+    }
+
+    #[test]
+    fn test_initialize_liquidity_pool_non_admin_fails() {
+        let mut svm = setup_svm();
+        let (_, token_mint, _, config_pda, _, _, _) = run_initialize(&mut svm);
+
+        let attacker = Keypair::new();
+        svm.airdrop(&attacker.pubkey(), 10_000_000_000).unwrap();
+        let lp_mint = Keypair::new();
+
+        let ix = build_initialize_liquidity_pool_ix(
+            &attacker.pubkey(),
+            &config_pda,
+            &lp_mint.pubkey(),
+            &token_mint.pubkey(),
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&attacker.pubkey()),
+            &[&attacker, &lp_mint],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_err(), "Only the admin may create the pool");
+    }
+
+    #[test]
+    fn test_add_and_remove_liquidity_pro_rata() {
+        let mut svm = setup_svm();
+        let (initializer, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+
+        let lp_mint = run_initialize_liquidity_pool(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &token_mint.pubkey(),
+        );
+
+        let first = Keypair::new();
+        svm.airdrop(&first.pubkey(), 10_000_000_000).unwrap();
+        let first_lp_ata = create_and_fund_ata(&mut svm, &first.pubkey(), &lp_mint.pubkey(), 0);
+
+        let ix = build_add_liquidity_ix(
+            &first.pubkey(),
+            &config_pda,
+            &lp_mint.pubkey(),
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            2_000_000_000,
+        );
+        assert!(
+            send(&mut svm, ix, &first),
+            "First provider should add liquidity"
+        );
+        assert_eq!(get_token_balance(&svm, &first_lp_ata), 2_000_000_000);
+        assert_eq!(sol_reserves(&svm), 2_000_000_000);
+
+        // LST retained by the pool (e.g. from fees) raises the value of each
+        // LP token. Deposit first so the vault balance is backed by supply.
+        let (_, _) = run_deposit(
+            &mut svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            2_000_000_000,
+        );
+        let lst_vault = create_and_fund_ata(
+            &mut svm,
+            &liquidity_pool_pda(),
+            &token_mint.pubkey(),
+            2_000_000_000,
+        );
+
+        let second = Keypair::new();
+        svm.airdrop(&second.pubkey(), 10_000_000_000).unwrap();
+        let second_lp_ata = create_and_fund_ata(&mut svm, &second.pubkey(), &lp_mint.pubkey(), 0);
+
+        let ix = build_add_liquidity_ix(
+            &second.pubkey(),
+            &config_pda,
+            &lp_mint.pubkey(),
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            2_000_000_000,
+        );
+        assert!(
+            send(&mut svm, ix, &second),
+            "Second provider should add liquidity"
+        );
+
+        // The vault's LST makes each LP token worth more than 1 SOL.
+        let second_lp = get_token_balance(&svm, &second_lp_ata);
+        assert!(
+            second_lp > 0 && second_lp < 2_000_000_000,
+            "Second provider should get fewer LP per SOL, got {second_lp}"
+        );
+        assert_eq!(sol_reserves(&svm), 4_000_000_000);
+
+        let first_lst_ata = create_and_fund_ata(&mut svm, &first.pubkey(), &token_mint.pubkey(), 0);
+        let lamports_before = svm.get_balance(&first.pubkey()).unwrap();
+
+        let ix = build_remove_liquidity_ix(
+            &first.pubkey(),
+            &first_lst_ata,
+            &config_pda,
+            &lp_mint.pubkey(),
+            &token_mint.pubkey(),
+            2_000_000_000,
+        );
+        assert!(
+            send(&mut svm, ix, &first),
+            "First provider should remove liquidity"
+        );
+
+        let lp_supply = 2_000_000_000 + second_lp;
+        let expected_sol = (4_000_000_000u128 * 2_000_000_000 / lp_supply as u128) as u64;
+        let expected_lst = (2_000_000_000u128 * 2_000_000_000 / lp_supply as u128) as u64;
+
+        assert_eq!(get_token_balance(&svm, &first_lp_ata), 0);
+        assert_eq!(get_token_balance(&svm, &first_lst_ata), expected_lst);
+        assert_eq!(
+            get_token_balance(&svm, &lst_vault),
+            2_000_000_000 - expected_lst
+        );
+        assert_eq!(sol_reserves(&svm), 4_000_000_000 - expected_sol);
+        assert_eq!(
+            svm.get_balance(&first.pubkey()).unwrap(),
+            lamports_before + expected_sol - 5_000
+        );
+    }
+
+    #[test]
+    fn test_add_liquidity_zero_fails() {
+        let mut svm = setup_svm();
+        let (initializer, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+
+        let lp_mint = run_initialize_liquidity_pool(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &token_mint.pubkey(),
+        );
+
+        let provider = Keypair::new();
+        svm.airdrop(&provider.pubkey(), 10_000_000_000).unwrap();
+        create_and_fund_ata(&mut svm, &provider.pubkey(), &lp_mint.pubkey(), 0);
+
+        let ix = build_add_liquidity_ix(
+            &provider.pubkey(),
+            &config_pda,
+            &lp_mint.pubkey(),
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            0,
+        );
+        assert!(
+            !send(&mut svm, ix, &provider),
+            "Zero liquidity should be rejected"
+        );
+    }
+}
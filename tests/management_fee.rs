@@ -0,0 +1,113 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::signature::{Keypair, Signer};
+
+    use crate::test_helpers::test_helpers::{
+        add_stake_rewards, build_crank_management_fee_ix, build_set_management_fee_ix,
+        get_mint_supply, get_token_balance, send, setup_pool, setup_svm, warp_epoch, Pool,
+        PoolSetup,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    impl Pool {
+        fn crank(&self, svm: &mut LiteSVM) -> bool {
+            let ix = build_crank_management_fee_ix(
+                &self.config_pda,
+                &self.stake_account_main,
+                &self.stake_account_reserve,
+                &self.mint,
+            );
+            send(svm, ix, &self.admin)
+        }
+    }
+
+    /// Initializes a pool holding a 5 SOL deposit with the management fee
+    /// set to `management_fee_bps`.
+    fn setup(svm: &mut LiteSVM, management_fee_bps: u16) -> Pool {
+        let pool = setup_pool(
+            svm,
+            PoolSetup {
+                treasury_balance: Some(0),
+                deposit: 5 * LAMPORTS_PER_SOL,
+                ..PoolSetup::default()
+            },
+        );
+
+        let ix =
+            build_set_management_fee_ix(&pool.admin.pubkey(), &pool.config_pda, management_fee_bps);
+        assert!(send(svm, ix, &pool.admin));
+        pool
+    }
+
+    #[test]
+    fn test_management_fee_is_charged_once_per_epoch() {
+        let mut svm = setup_svm();
+        let pool = setup(&mut svm, 100);
+
+        warp_epoch(&mut svm, 1);
+        assert!(pool.crank(&mut svm));
+        assert_eq!(
+            get_token_balance(&svm, &pool.treasury_ata),
+            0,
+            "Without rewards there is no yield to charge"
+        );
+
+        add_stake_rewards(&mut svm, &pool.stake_account_main, LAMPORTS_PER_SOL);
+        assert!(
+            !pool.crank(&mut svm),
+            "The fee was already charged this epoch"
+        );
+
+        warp_epoch(&mut svm, 2);
+        let supply_before = get_mint_supply(&svm, &pool.mint);
+        assert!(pool.crank(&mut svm));
+        let fee_lst = get_token_balance(&svm, &pool.treasury_ata);
+        assert!(fee_lst > 0);
+        assert_eq!(get_mint_supply(&svm, &pool.mint), supply_before + fee_lst);
+
+        // 1% of the pool, well within the 1 SOL of rewards.
+        assert!(fee_lst > supply_before / 100 && fee_lst < supply_before / 50);
+    }
+
+    #[test]
+    fn test_management_fee_never_exceeds_the_yield() {
+        let mut svm = setup_svm();
+        let pool = setup(&mut svm, 5_000);
+        add_stake_rewards(&mut svm, &pool.stake_account_main, LAMPORTS_PER_SOL / 10);
+
+        warp_epoch(&mut svm, 1);
+        let supply_before = get_mint_supply(&svm, &pool.mint);
+        assert!(pool.crank(&mut svm));
+        let fee_lst = get_token_balance(&svm, &pool.treasury_ata);
+
+        // Half the pool is far more than the 0.1 SOL of rewards, so only the
+        // rewards are charged.
+        assert!(fee_lst > 0 && fee_lst < supply_before / 40);
+
+        warp_epoch(&mut svm, 2);
+        assert!(pool.crank(&mut svm));
+        assert_eq!(
+            get_token_balance(&svm, &pool.treasury_ata),
+            fee_lst,
+            "Yield already charged is not charged again"
+        );
+    }
+
+    #[test]
+    fn test_management_fee_is_bounded_and_admin_only() {
+        let mut svm = setup_svm();
+        let pool = setup(&mut svm, 0);
+
+        let ix = build_set_management_fee_ix(&pool.admin.pubkey(), &pool.config_pda, 10_000);
+        assert!(!send(&mut svm, ix, &pool.admin));
+
+        let outsider = Keypair::new();
+        svm.airdrop(&outsider.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        let ix = build_set_management_fee_ix(&outsider.pubkey(), &pool.config_pda, 100);
+        assert!(!send(&mut svm, ix, &outsider));
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use solana_liquid_staking::{
+        math::{
+            deposit_bonus_lst, deposit_fee_lst, holding_rebate_fee_bps, lamports_to_lst,
+            lst_to_lamports, lst_to_ve_weight, management_fee, reward_payout_lamports,
+            ve_weight_to_lst,
+        },
+        state::VE_MAX_LOCK_SECONDS,
+    };
+
+    #[test]
+    fn test_conversions_round_down_in_pool_favor() {
+        // Empty pool: one LST per lamport.
+        assert_eq!(lamports_to_lst(5_000, 0, 0), Ok(5_000));
+        assert_eq!(lst_to_lamports(5_000, 0, 0), Ok(0));
+
+        // 1.5 lamports per LST.
+        let lst = lamports_to_lst(1_000, 2_000, 3_000).unwrap();
+        assert_eq!(lst, 666);
+        assert_eq!(lst_to_lamports(lst, 2_000, 3_000), Ok(999));
+    }
+
+    #[test]
+    fn test_deposit_bonus_until_cutoff_and_capped_by_treasury() {
+        // 5% bonus before epoch 10.
+        assert_eq!(deposit_bonus_lst(1_000, 500, 9, 10, u64::MAX), 50);
+        assert_eq!(deposit_bonus_lst(1_000, 500, 10, 10, u64::MAX), 0);
+
+        // The treasury caps the bonus.
+        assert_eq!(deposit_bonus_lst(1_000, 500, 0, 10, 20), 20);
+        assert_eq!(deposit_bonus_lst(1_000, 500, 0, 10, 0), 0);
+    }
+
+    #[test]
+    fn test_deposit_fee_rounds_down_for_the_depositor() {
+        assert_eq!(deposit_fee_lst(1_000, 50), 5);
+        assert_eq!(deposit_fee_lst(199, 50), 0);
+        assert_eq!(deposit_fee_lst(u64::MAX, 10_000), u64::MAX);
+        assert_eq!(deposit_fee_lst(1_000, 0), 0);
+    }
+
+    #[test]
+    fn test_holding_rebate_grows_linearly_until_the_holding_period() {
+        // Half of a 1% fee waived after 10 epochs.
+        assert_eq!(holding_rebate_fee_bps(100, 5_000, 10, 0), 100);
+        assert_eq!(holding_rebate_fee_bps(100, 5_000, 10, 5), 75);
+        assert_eq!(holding_rebate_fee_bps(100, 5_000, 10, 10), 50);
+        assert_eq!(holding_rebate_fee_bps(100, 5_000, 10, 1_000), 50);
+
+        // The rebate rounds down, and zero epochs disables it.
+        assert_eq!(holding_rebate_fee_bps(3, 5_000, 10, 5), 3);
+        assert_eq!(holding_rebate_fee_bps(100, 10_000, 0, 1_000), 100);
+        assert_eq!(holding_rebate_fee_bps(10_000, 10_000, 1, u64::MAX), 0);
+    }
+
+    #[test]
+    fn test_management_fee_is_capped_at_yield() {
+        // 1% of 11_000 lamports, diluting the holders of 10_000 LST by 110.
+        assert_eq!(management_fee(11_000, 10_000, 1_000, 100), Ok((110, 101)));
+        assert_eq!(
+            lst_to_lamports(101, 10_101, 11_000),
+            Ok(109),
+            "The fee LST are worth the fee, rounded down"
+        );
+
+        // Only 50 lamports of yield are left to charge.
+        assert_eq!(management_fee(11_000, 10_000, 50, 100), Ok((50, 45)));
+        assert_eq!(management_fee(11_000, 10_000, 0, 100), Ok((0, 0)));
+        assert_eq!(management_fee(1_000, 0, 1_000, 100), Ok((0, 0)));
+    }
+
+    #[test]
+    fn test_ve_weight_conversions_round_against_the_locker() {
+        // Full weight at the maximum lock, a quarter at a quarter of it.
+        assert_eq!(lst_to_ve_weight(1_000, VE_MAX_LOCK_SECONDS), 1_000);
+        assert_eq!(lst_to_ve_weight(1_000, VE_MAX_LOCK_SECONDS / 4), 250);
+        assert_eq!(lst_to_ve_weight(1_000, 2 * VE_MAX_LOCK_SECONDS), 1_000);
+        assert_eq!(lst_to_ve_weight(1_000, -1), 0);
+
+        // One third of the maximum: 333.33 rounds down to 333, and reaching
+        // 333 takes 999 LST while reaching 334 takes 1_002.
+        let lock_seconds = VE_MAX_LOCK_SECONDS / 3;
+        assert_eq!(lst_to_ve_weight(1_000, lock_seconds), 333);
+        assert_eq!(ve_weight_to_lst(333, lock_seconds), Some(999));
+        assert_eq!(ve_weight_to_lst(334, lock_seconds), Some(1_002));
+        assert_eq!(lst_to_ve_weight(1_002, lock_seconds), 334);
+
+        assert_eq!(ve_weight_to_lst(1, 0), None);
+        assert_eq!(ve_weight_to_lst(u64::MAX, 1), None);
+    }
+
+    #[test]
+    fn test_reward_payout_is_the_split_of_the_vault_value_gain() {
+        // The rate rises from 1.0 to 1.1: 100 LST gained 10 lamports, half
+        // of which is paid out.
+        assert_eq!(reward_payout_lamports(100, 1_000, 1_000, 1_100, 1_000, 5_000), 5);
+        assert_eq!(reward_payout_lamports(100, 1_000, 1_000, 1_100, 1_000, 10_000), 10);
+        assert_eq!(reward_payout_lamports(100, 1_000, 1_000, 1_100, 1_000, 0), 0);
+
+        // No checkpoint yet, or a rate that fell, pays nothing.
+        assert_eq!(reward_payout_lamports(100, 0, 0, 1_100, 1_000, 5_000), 0);
+        assert_eq!(reward_payout_lamports(100, 1_000, 1_000, 900, 1_000, 5_000), 0);
+    }
+}
@@ -0,0 +1,198 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_import_balance_ix, build_import_stake_account_ix, build_open_migration_ix,
+        get_mint_supply, get_token_balance, migration_pda, run_initialize, send, setup_svm,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    fn lamports(svm: &LiteSVM, pubkey: &Pubkey) -> u64 {
+        svm.get_account(pubkey)
+            .map_or(0, |account| account.lamports)
+    }
+
+    /// Creates an inactive stake account holding `lamports` beyond rent, with
+    /// main's authorities (the config PDA) unless `staker` overrides them.
+    fn exported_stake_account(
+        svm: &mut LiteSVM,
+        stake_account_main: &Pubkey,
+        lamports: u64,
+        staker: Option<Pubkey>,
+    ) -> Pubkey {
+        let mut account = svm.get_account(stake_account_main).unwrap();
+        // `StakeStateV2::Initialized`, keeping main's `Meta`.
+        account.data[0..4].copy_from_slice(&1u32.to_le_bytes());
+        if let Some(staker) = staker {
+            account.data[12..44].copy_from_slice(staker.as_ref());
+        }
+        let rent_exempt_reserve = u64::from_le_bytes(account.data[4..12].try_into().unwrap());
+        account.lamports = rent_exempt_reserve + lamports;
+
+        let stake_account = Pubkey::new_unique();
+        svm.set_account(stake_account, account).unwrap();
+        stake_account
+    }
+
+    #[test]
+    fn test_migration_imports_stake_and_pays_balances_pro_rata() {
+        let mut svm = setup_svm();
+        let (
+            admin,
+            token_mint,
+            _admin_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+        let vault =
+            spl_associated_token_account::get_associated_token_address(&migration_pda(), &mint);
+
+        assert!(send(
+            &mut svm,
+            build_open_migration_ix(&admin.pubkey(), &config_pda, &mint, 1_000),
+            &admin,
+        ));
+
+        let stake_account =
+            exported_stake_account(&mut svm, &stake_account_main, 5 * LAMPORTS_PER_SOL, None);
+        let imported = lamports(&svm, &stake_account);
+        let reserve_before = lamports(&svm, &stake_account_reserve);
+        let supply_before = get_mint_supply(&svm, &mint);
+        assert!(send(
+            &mut svm,
+            build_import_stake_account_ix(
+                &admin.pubkey(),
+                &config_pda,
+                &mint,
+                &stake_account_main,
+                &stake_account_reserve,
+                &stake_account,
+            ),
+            &admin,
+        ));
+
+        assert_eq!(
+            lamports(&svm, &stake_account_reserve),
+            reserve_before + imported,
+            "Inactive stake is withdrawn into the reserve"
+        );
+        let minted = get_token_balance(&svm, &vault);
+        assert!(minted > 0);
+        assert_eq!(get_mint_supply(&svm, &mint), supply_before + minted);
+
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert!(send(
+            &mut svm,
+            build_import_balance_ix(&admin.pubkey(), &config_pda, &mint, &alice, 333),
+            &admin,
+        ));
+        let alice_ata = spl_associated_token_account::get_associated_token_address(&alice, &mint);
+        assert_eq!(
+            get_token_balance(&svm, &alice_ata),
+            (333 * minted as u128 / 1_000) as u64
+        );
+
+        assert!(
+            !send(
+                &mut svm,
+                build_import_balance_ix(&admin.pubkey(), &config_pda, &mint, &alice, 333),
+                &admin,
+            ),
+            "A depositor is imported once"
+        );
+        assert!(
+            !send(
+                &mut svm,
+                build_import_balance_ix(&admin.pubkey(), &config_pda, &mint, &bob, 668),
+                &admin,
+            ),
+            "Balances cannot exceed the source supply"
+        );
+
+        let late_stake =
+            exported_stake_account(&mut svm, &stake_account_main, LAMPORTS_PER_SOL, None);
+        assert!(
+            !send(
+                &mut svm,
+                build_import_stake_account_ix(
+                    &admin.pubkey(),
+                    &config_pda,
+                    &mint,
+                    &stake_account_main,
+                    &stake_account_reserve,
+                    &late_stake,
+                ),
+                &admin,
+            ),
+            "Importing balances seals the migration"
+        );
+
+        assert!(send(
+            &mut svm,
+            build_import_balance_ix(&admin.pubkey(), &config_pda, &mint, &bob, 667),
+            &admin,
+        ));
+        let bob_ata = spl_associated_token_account::get_associated_token_address(&bob, &mint);
+        assert_eq!(
+            get_token_balance(&svm, &alice_ata) + get_token_balance(&svm, &bob_ata),
+            minted,
+            "The last balance takes the rounding dust"
+        );
+        assert_eq!(get_token_balance(&svm, &vault), 0);
+    }
+
+    #[test]
+    fn test_import_rejects_stake_not_authorized_to_config() {
+        let mut svm = setup_svm();
+        let (admin, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+
+        assert!(send(
+            &mut svm,
+            build_open_migration_ix(&admin.pubkey(), &config_pda, &mint, 1_000),
+            &admin,
+        ));
+
+        let stake_account = exported_stake_account(
+            &mut svm,
+            &stake_account_main,
+            LAMPORTS_PER_SOL,
+            Some(admin.pubkey()),
+        );
+        assert!(!send(
+            &mut svm,
+            build_import_stake_account_ix(
+                &admin.pubkey(),
+                &config_pda,
+                &mint,
+                &stake_account_main,
+                &stake_account_reserve,
+                &stake_account,
+            ),
+            &admin,
+        ));
+
+        let outsider = Keypair::new();
+        svm.airdrop(&outsider.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        assert!(
+            !send(
+                &mut svm,
+                build_open_migration_ix(&outsider.pubkey(), &config_pda, &mint, 1_000),
+                &outsider,
+            ),
+            "Only the admin opens a migration"
+        );
+    }
+}
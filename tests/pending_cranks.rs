@@ -0,0 +1,140 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        instruction::Instruction,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_get_pending_cranks_ix, decode_pending_cranks, run_crank_initialize_reserve,
+        run_crank_management_fee, run_crank_merge_reserve, run_crank_record_rate,
+        run_crank_update_rate, run_initialize, setup_svm, warp_epoch,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    const CRANK_INITIALIZE_RESERVE: u8 = 1;
+    const CRANK_MERGE_RESERVE: u8 = 2;
+    const CRANK_RECORD_RATE: u8 = 6;
+    const CRANK_MANAGEMENT_FEE: u8 = 89;
+    const CRANK_UPDATE_RATE: u8 = 95;
+
+    fn pending_cranks(svm: &mut LiteSVM, ix: Instruction) -> Vec<u8> {
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        );
+        decode_pending_cranks(&svm.simulate_transaction(tx).unwrap().meta.return_data.data)
+    }
+
+    #[test]
+    fn test_lists_cranks_as_the_pool_advances() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = run_initialize(&mut svm);
+        let ix =
+            build_get_pending_cranks_ix(&config_pda, &stake_account_main, &stake_account_reserve);
+
+        assert_eq!(
+            pending_cranks(&mut svm, ix.clone()),
+            vec![
+                CRANK_INITIALIZE_RESERVE,
+                CRANK_UPDATE_RATE,
+                CRANK_RECORD_RATE
+            ]
+        );
+
+        run_crank_initialize_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_reserve,
+            &vote_pubkey,
+        );
+        assert_eq!(
+            pending_cranks(&mut svm, ix.clone()),
+            vec![CRANK_MERGE_RESERVE, CRANK_UPDATE_RATE, CRANK_RECORD_RATE]
+        );
+
+        run_crank_merge_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+        run_crank_record_rate(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+        run_crank_update_rate(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+        assert!(
+            pending_cranks(&mut svm, ix.clone()).is_empty(),
+            "Nothing should be left once every crank has run"
+        );
+
+        warp_epoch(&mut svm, 1);
+        assert_eq!(
+            pending_cranks(&mut svm, ix.clone()),
+            vec![CRANK_MANAGEMENT_FEE],
+            "A new epoch should charge its fee before snapshotting a rate"
+        );
+
+        run_crank_management_fee(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+        assert_eq!(
+            pending_cranks(&mut svm, ix),
+            vec![CRANK_UPDATE_RATE, CRANK_RECORD_RATE],
+            "A new epoch should need a fresh rate"
+        );
+    }
+
+    #[test]
+    fn test_rejects_wrong_reserve() {
+        let mut svm = setup_svm();
+        let (_, _, _, config_pda, stake_account_main, _, _) = run_initialize(&mut svm);
+        let ix = build_get_pending_cranks_ix(&config_pda, &stake_account_main, &stake_account_main);
+
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        );
+        assert!(svm.simulate_transaction(tx).is_err());
+    }
+}
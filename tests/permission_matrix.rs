@@ -0,0 +1,919 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
+    use solana_sdk::account::ReadableAccount;
+    use solana_sdk::instruction::Instruction;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+    use solana_sdk::transaction::Transaction;
+
+    use crate::test_helpers::test_helpers::{
+        build_accept_admin_ix, build_add_liquidity_ix, build_add_validator_ix,
+        build_adopt_reserve_ix, build_burn_and_donate_ix, build_burn_from_wrapper_ix,
+        build_buyback_ix, build_cancel_split_handoff_ix, build_cancel_unstake_order_ix,
+        build_claim_payout_ix, build_claim_queued_deposit_ix, build_claim_referral_fees_ix,
+        build_claim_vested_ix, build_close_dca_schedule_ix, build_close_deposit_key_ix,
+        build_close_deposit_session_ix, build_close_used_quote_ix, build_crank_dca_ix,
+        build_crank_initialize_reserve_ix, build_crank_management_fee_ix,
+        build_crank_merge_reserve_ix, build_crank_rebalance_ix, build_crank_record_rate_ix,
+        build_crank_redelegate_main_ix, build_crank_refill_buffer_ix, build_crank_reward_payout_ix,
+        build_crank_split_ix, build_crank_split_next_ix, build_crank_split_single_ix,
+        build_crank_split_tranche_ix, build_crank_update_rate_ix, build_crank_validator_health_ix,
+        build_crank_validator_stake_ix, build_create_dca_schedule_ix,
+        build_create_deposit_session_ix, build_create_unstake_order_ix, build_create_vesting_ix,
+        build_deposit_ix, build_deposit_stake_account_ix, build_deposit_with_session_ix,
+        build_distribute_treasury_ix, build_enroll_payout_ix, build_execute_split_handoff_ix,
+        build_exit_pool_ix, build_fill_unstake_order_ix, build_get_balance_sheet_ix,
+        build_get_build_info_ix, build_get_pending_cranks_ix, build_get_yield_report_ix,
+        build_import_balance_ix, build_import_stake_account_ix, build_initialize_ix,
+        build_initialize_liquidity_pool_ix, build_instant_withdraw_ix, build_join_validator_set_ix,
+        build_leave_validator_set_ix, build_liquid_unstake_ix, build_lock_lst_ix,
+        build_mint_to_wrapper_ix, build_open_migration_ix, build_propose_admin_ix,
+        build_propose_split_handoff_ix, build_register_referrer_ix, build_remove_liquidity_ix,
+        build_remove_validator_ix, build_set_bonus_schedule_ix, build_set_buyback_policy_ix,
+        build_set_crank_tip_ix, build_set_delegation_strategy_ix, build_set_deposit_authority_ix,
+        build_set_deposit_cap_ix, build_set_deposit_fee_ix, build_set_holding_rebate_ix,
+        build_set_instruction_enabled_ix, build_set_main_headroom_ix, build_set_management_fee_ix,
+        build_set_pool_cap_ix, build_set_pool_status_ix, build_set_quote_signer_ix,
+        build_set_referral_fee_ix, build_set_reward_payout_ix, build_set_split_minimum_ix,
+        build_set_trusted_caller_ix, build_set_unstake_fee_params_ix,
+        build_set_validator_metrics_ix, build_set_validator_stake_cap_ix,
+        build_set_validator_status_ix, build_set_withdraw_fee_ix, build_set_wrapper_program_ix,
+        build_simulate_params_ix, build_slash_validator_bond_ix, build_swap_buffer_ix,
+        build_unlock_lst_ix, build_update_config_ix, build_verify_authorities_ix,
+        build_withdraw_ix, build_withdraw_payout_ix, build_withdraw_stake_ix,
+        run_crank_initialize_reserve, run_crank_merge_reserve, run_crank_split, run_deposit,
+        run_initialize, setup_svm,
+    };
+
+    /// Sends `ix` with a separate fee payer so that only the instruction's
+    /// own metas decide which accounts sign.
+    fn simulate(
+        svm: &mut litesvm::LiteSVM,
+        ix: Instruction,
+        fee_payer: &Keypair,
+        signers: &[&Keypair],
+    ) -> bool {
+        let mut all_signers = vec![fee_payer];
+        all_signers.extend_from_slice(signers);
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&fee_payer.pubkey()),
+            &all_signers,
+            svm.latest_blockhash(),
+        );
+        svm.simulate_transaction(tx).is_ok()
+    }
+
+    /// Asserts `ix` succeeds as built and fails once any single signer meta
+    /// is downgraded to a non-signer.
+    fn assert_signer_downgrades_rejected(
+        svm: &mut litesvm::LiteSVM,
+        name: &str,
+        ix: Instruction,
+        signers: &[&Keypair],
+    ) {
+        let fee_payer = Keypair::new();
+        svm.airdrop(&fee_payer.pubkey(), 10_000_000_000).unwrap();
+
+        assert!(
+            simulate(svm, ix.clone(), &fee_payer, signers),
+            "{name}: instruction should succeed as built"
+        );
+
+        for (index, meta) in ix.accounts.iter().enumerate() {
+            if !meta.is_signer {
+                continue;
+            }
+
+            let mut downgraded = ix.clone();
+            downgraded.accounts[index].is_signer = false;
+            let remaining: Vec<&Keypair> = signers
+                .iter()
+                .copied()
+                .filter(|signer| signer.pubkey() != meta.pubkey)
+                .collect();
+
+            assert!(
+                !simulate(svm, downgraded, &fee_payer, &remaining),
+                "{name}: account {index} ({}) should be required to sign",
+                meta.pubkey
+            );
+        }
+    }
+
+    /// Asserts `ix` fails once any account it writes as built is downgraded
+    /// to read-only. Accounts left unchanged on this path are skipped, since
+    /// nothing shows whether the instruction needs them writable.
+    fn assert_writable_downgrades_rejected(
+        svm: &mut litesvm::LiteSVM,
+        name: &str,
+        ix: Instruction,
+        signers: &[&Keypair],
+    ) {
+        let fee_payer = Keypair::new();
+        svm.airdrop(&fee_payer.pubkey(), 10_000_000_000).unwrap();
+
+        let mut all_signers = vec![&fee_payer];
+        all_signers.extend_from_slice(signers);
+        let tx = Transaction::new_signed_with_payer(
+            std::slice::from_ref(&ix),
+            Some(&fee_payer.pubkey()),
+            &all_signers,
+            svm.latest_blockhash(),
+        );
+        let post_accounts = svm
+            .simulate_transaction(tx)
+            .unwrap_or_else(|_| panic!("{name}: instruction should succeed as built"))
+            .post_accounts;
+
+        for (index, meta) in ix.accounts.iter().enumerate() {
+            if !meta.is_writable {
+                continue;
+            }
+            // A key passed twice stays writable through its other meta.
+            let writable_elsewhere = ix.accounts.iter().enumerate().any(|(other, other_meta)| {
+                other != index && other_meta.pubkey == meta.pubkey && other_meta.is_writable
+            });
+            if writable_elsewhere {
+                continue;
+            }
+
+            let before = svm.get_account(&meta.pubkey);
+            let written = post_accounts.iter().any(|(key, after)| {
+                *key == meta.pubkey
+                    && before.as_ref().is_none_or(|before| {
+                        before.lamports != after.lamports()
+                            || before.data != after.data()
+                            || before.owner != *after.owner()
+                    })
+            });
+            if !written {
+                continue;
+            }
+
+            let mut downgraded = ix.clone();
+            downgraded.accounts[index].is_writable = false;
+
+            assert!(
+                !simulate(svm, downgraded, &fee_payer, signers),
+                "{name}: account {index} ({}) should be required to be writable",
+                meta.pubkey
+            );
+        }
+    }
+
+    /// Asserts both the signer and the writable downgrades of `ix` are
+    /// rejected.
+    fn assert_downgrades_rejected(
+        svm: &mut litesvm::LiteSVM,
+        name: &str,
+        ix: Instruction,
+        signers: &[&Keypair],
+    ) {
+        assert_signer_downgrades_rejected(svm, name, ix.clone(), signers);
+        assert_writable_downgrades_rejected(svm, name, ix, signers);
+    }
+
+    /// An instruction's name, documented (signer, writable) flags and the
+    /// instruction its builder produces.
+    type BuilderCase = (&'static str, Vec<(bool, bool)>, Instruction);
+
+    /// Signer and writable flags, in that order, of the accounts listed
+    /// under "Accounts expected" in an instruction's source.
+    fn documented_flags(source: &str) -> Vec<(bool, bool)> {
+        source
+            .lines()
+            .filter_map(|line| {
+                let (index, rest) = line.strip_prefix("/// ")?.split_once(". `[")?;
+                index.parse::<usize>().ok()?;
+                let (flags, _) = rest.split_once("]`")?;
+                Some((flags.contains("SIGNER"), flags.contains("WRITE")))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_builders_match_documented_accounts() {
+        // Every builder but `build_deposit_quote_ix`, which builds an ed25519
+        // program instruction.
+        let key = Pubkey::new_unique();
+        let cases: Vec<BuilderCase> = vec![
+            (
+                "Deposit",
+                documented_flags(include_str!("../src/instructions/deposit.rs")),
+                build_deposit_ix(&key, &key, &key, &key, &key, &key, 1, true),
+            ),
+            (
+                "CrankMergeReserve",
+                documented_flags(include_str!("../src/instructions/crank_merge_reserve.rs")),
+                build_crank_merge_reserve_ix(&key, &key, &key, &key, &key),
+            ),
+            (
+                "CrankSplit",
+                documented_flags(include_str!("../src/instructions/crank_split.rs")),
+                build_crank_split_ix(&key, &key, &key, &key, &key, &key, 1, true, 1).0,
+            ),
+            (
+                "Withdraw",
+                documented_flags(include_str!("../src/instructions/withdraw.rs")),
+                build_withdraw_ix(&key, &key, &key, &key, 1, true),
+            ),
+            (
+                "CrankInitializeReserve",
+                documented_flags(include_str!(
+                    "../src/instructions/crank_initialize_reserve.rs"
+                )),
+                build_crank_initialize_reserve_ix(&key, &key, &key, &key, &key),
+            ),
+            (
+                "Initialize",
+                documented_flags(include_str!("../src/instructions/initialize.rs")),
+                build_initialize_ix(
+                    &key, &key, &key, &key, &key, &key, true, &key, &key, &key, &key, &key,
+                ),
+            ),
+            (
+                "CrankRecordRate",
+                documented_flags(include_str!("../src/instructions/crank_record_rate.rs")),
+                build_crank_record_rate_ix(&key, &key, &key, &key, &key),
+            ),
+            (
+                "ExitPool",
+                documented_flags(include_str!("../src/instructions/exit_pool.rs")),
+                build_exit_pool_ix(&key, &key, &key, &key, &key, &key, 1).0,
+            ),
+            (
+                "CrankSplitTranche",
+                documented_flags(include_str!("../src/instructions/crank_split_tranche.rs")),
+                build_crank_split_tranche_ix(&key, &key, &key, &key, &key, &key, 1, 1).0,
+            ),
+            (
+                "SetTrustedCaller",
+                documented_flags(include_str!("../src/instructions/set_trusted_caller.rs")),
+                build_set_trusted_caller_ix(&key, &key, &key, true),
+            ),
+            (
+                "InitializeLiquidityPool",
+                documented_flags(include_str!(
+                    "../src/instructions/initialize_liquidity_pool.rs"
+                )),
+                build_initialize_liquidity_pool_ix(&key, &key, &key, &key),
+            ),
+            (
+                "AddLiquidity",
+                documented_flags(include_str!("../src/instructions/add_liquidity.rs")),
+                build_add_liquidity_ix(&key, &key, &key, &key, &key, &key, 1),
+            ),
+            (
+                "RemoveLiquidity",
+                documented_flags(include_str!("../src/instructions/remove_liquidity.rs")),
+                build_remove_liquidity_ix(&key, &key, &key, &key, &key, 1),
+            ),
+            (
+                "LiquidUnstake",
+                documented_flags(include_str!("../src/instructions/liquid_unstake.rs")),
+                build_liquid_unstake_ix(&key, &key, &key, &key, &key, &key, 1, 1),
+            ),
+            (
+                "SetUnstakeFeeParams",
+                documented_flags(include_str!(
+                    "../src/instructions/set_unstake_fee_params.rs"
+                )),
+                build_set_unstake_fee_params_ix(&key, &key, 1, 1, 1),
+            ),
+            (
+                "CrankRefillBuffer",
+                documented_flags(include_str!("../src/instructions/crank_refill_buffer.rs")),
+                build_crank_refill_buffer_ix(&key, &key, &key, &key, &key, 1),
+            ),
+            (
+                "SwapBuffer",
+                documented_flags(include_str!("../src/instructions/swap_buffer.rs")),
+                build_swap_buffer_ix(&key, &key, &key, &key, &key, &key, 1, 1, 1),
+            ),
+            (
+                "JoinValidatorSet",
+                documented_flags(include_str!("../src/instructions/join_validator_set.rs")),
+                build_join_validator_set_ix(&key, &key, 1),
+            ),
+            (
+                "SetValidatorStatus",
+                documented_flags(include_str!("../src/instructions/set_validator_status.rs")),
+                build_set_validator_status_ix(&key, &key, &key, true),
+            ),
+            (
+                "SlashValidatorBond",
+                documented_flags(include_str!("../src/instructions/slash_validator_bond.rs")),
+                build_slash_validator_bond_ix(&key, &key, &key, &key, 1),
+            ),
+            (
+                "LeaveValidatorSet",
+                documented_flags(include_str!("../src/instructions/leave_validator_set.rs")),
+                build_leave_validator_set_ix(&key, &key),
+            ),
+            (
+                "SetValidatorStakeCap",
+                documented_flags(include_str!(
+                    "../src/instructions/set_validator_stake_cap.rs"
+                )),
+                build_set_validator_stake_cap_ix(&key, &key, 1, 1),
+            ),
+            (
+                "SetDelegationStrategy",
+                documented_flags(include_str!(
+                    "../src/instructions/set_delegation_strategy.rs"
+                )),
+                build_set_delegation_strategy_ix(&key, &key, 1),
+            ),
+            (
+                "SetValidatorMetrics",
+                documented_flags(include_str!("../src/instructions/set_validator_metrics.rs")),
+                build_set_validator_metrics_ix(&key, &key, &key, 1, 1),
+            ),
+            (
+                "SetSplitMinimum",
+                documented_flags(include_str!("../src/instructions/set_split_minimum.rs")),
+                build_set_split_minimum_ix(&key, &key, 1),
+            ),
+            (
+                "CloseDepositKey",
+                documented_flags(include_str!("../src/instructions/close_deposit_key.rs")),
+                build_close_deposit_key_ix(&key, &[1; 32]),
+            ),
+            (
+                "BurnAndDonate",
+                documented_flags(include_str!("../src/instructions/burn_and_donate.rs")),
+                build_burn_and_donate_ix(&key, &key, &key, &key, 1),
+            ),
+            (
+                "DistributeTreasury",
+                documented_flags(include_str!("../src/instructions/distribute_treasury.rs")),
+                build_distribute_treasury_ix(&key, &key, &key, &[key], &[1]),
+            ),
+            (
+                "CreateVesting",
+                documented_flags(include_str!("../src/instructions/create_vesting.rs")),
+                build_create_vesting_ix(&key, &key, &key, &key, 1, 1, 1, 1, 1),
+            ),
+            (
+                "ClaimVested",
+                documented_flags(include_str!("../src/instructions/claim_vested.rs")),
+                build_claim_vested_ix(&key, &key, 1),
+            ),
+            (
+                "CreateDepositSession",
+                documented_flags(include_str!(
+                    "../src/instructions/create_deposit_session.rs"
+                )),
+                build_create_deposit_session_ix(&key, &key, 1, 1),
+            ),
+            (
+                "DepositWithSession",
+                documented_flags(include_str!("../src/instructions/deposit_with_session.rs")),
+                build_deposit_with_session_ix(&key, &key, &key, &key, &key, &key, 1),
+            ),
+            (
+                "CloseDepositSession",
+                documented_flags(include_str!("../src/instructions/close_deposit_session.rs")),
+                build_close_deposit_session_ix(&key, &key),
+            ),
+            (
+                "CreateDcaSchedule",
+                documented_flags(include_str!("../src/instructions/create_dca_schedule.rs")),
+                build_create_dca_schedule_ix(&key, 1, 1, 1, 1, 1),
+            ),
+            (
+                "CrankDca",
+                documented_flags(include_str!("../src/instructions/crank_dca.rs")),
+                build_crank_dca_ix(&key, &key, 1, &key, &key, &key, &key),
+            ),
+            (
+                "CloseDcaSchedule",
+                documented_flags(include_str!("../src/instructions/close_dca_schedule.rs")),
+                build_close_dca_schedule_ix(&key, 1),
+            ),
+            (
+                "SetPoolStatus",
+                documented_flags(include_str!("../src/instructions/set_pool_status.rs")),
+                build_set_pool_status_ix(&key, &key, 1),
+            ),
+            (
+                "VerifyAuthorities",
+                documented_flags(include_str!("../src/instructions/verify_authorities.rs")),
+                build_verify_authorities_ix(&key, &key, &key, &[key]),
+            ),
+            (
+                "SetBuybackPolicy",
+                documented_flags(include_str!("../src/instructions/set_buyback_policy.rs")),
+                build_set_buyback_policy_ix(&key, &key, 1),
+            ),
+            (
+                "Buyback",
+                documented_flags(include_str!("../src/instructions/buyback.rs")),
+                build_buyback_ix(&key, &key, &key, &key),
+            ),
+            (
+                "GetBalanceSheet",
+                documented_flags(include_str!("../src/instructions/get_balance_sheet.rs")),
+                build_get_balance_sheet_ix(&key, &key, &key, &key),
+            ),
+            (
+                "GetYieldReport",
+                documented_flags(include_str!("../src/instructions/get_yield_report.rs")),
+                build_get_yield_report_ix(&key, &key, &key, &key),
+            ),
+            (
+                "SetDepositCap",
+                documented_flags(include_str!("../src/instructions/set_deposit_cap.rs")),
+                build_set_deposit_cap_ix(&key, &key, 1, 1),
+            ),
+            (
+                "ClaimQueuedDeposit",
+                documented_flags(include_str!("../src/instructions/claim_queued_deposit.rs")),
+                build_claim_queued_deposit_ix(&key, &key, &key, &key, &key),
+            ),
+            (
+                "SetBonusSchedule",
+                documented_flags(include_str!("../src/instructions/set_bonus_schedule.rs")),
+                build_set_bonus_schedule_ix(&key, &key, 1, 1),
+            ),
+            (
+                "SimulateParams",
+                documented_flags(include_str!("../src/instructions/simulate_params.rs")),
+                build_simulate_params_ix(&key, &key, &key, &key, 1, 1, 1, 1, 1, 1),
+            ),
+            (
+                "SetWrapperProgram",
+                documented_flags(include_str!("../src/instructions/set_wrapper_program.rs")),
+                build_set_wrapper_program_ix(&key, &key, &key),
+            ),
+            (
+                "MintToWrapper",
+                documented_flags(include_str!("../src/instructions/mint_to_wrapper.rs")),
+                build_mint_to_wrapper_ix(&key, &key, &key, &key, &key, 1),
+            ),
+            (
+                "BurnFromWrapper",
+                documented_flags(include_str!("../src/instructions/burn_from_wrapper.rs")),
+                build_burn_from_wrapper_ix(&key, &key, &key, &key, &key, 1, 1),
+            ),
+            (
+                "GetPendingCranks",
+                documented_flags(include_str!("../src/instructions/get_pending_cranks.rs")),
+                build_get_pending_cranks_ix(&key, &key, &key),
+            ),
+            (
+                "AdoptReserve",
+                documented_flags(include_str!("../src/instructions/adopt_reserve.rs")),
+                build_adopt_reserve_ix(&key, &key, &key, &key),
+            ),
+            (
+                "SetInstructionEnabled",
+                documented_flags(include_str!(
+                    "../src/instructions/set_instruction_enabled.rs"
+                )),
+                build_set_instruction_enabled_ix(&key, &key, 1, true),
+            ),
+            (
+                "RegisterReferrer",
+                documented_flags(include_str!("../src/instructions/register_referrer.rs")),
+                build_register_referrer_ix(&key),
+            ),
+            (
+                "ClaimReferralFees",
+                documented_flags(include_str!("../src/instructions/claim_referral_fees.rs")),
+                build_claim_referral_fees_ix(&key, &key, &key, &key),
+            ),
+            (
+                "SetReferralFee",
+                documented_flags(include_str!("../src/instructions/set_referral_fee.rs")),
+                build_set_referral_fee_ix(&key, &key, 1),
+            ),
+            (
+                "SetQuoteSigner",
+                documented_flags(include_str!("../src/instructions/set_quote_signer.rs")),
+                build_set_quote_signer_ix(&key, &key, &key, 1),
+            ),
+            (
+                "CloseUsedQuote",
+                documented_flags(include_str!("../src/instructions/close_used_quote.rs")),
+                build_close_used_quote_ix(&key, 1),
+            ),
+            (
+                "CreateUnstakeOrder",
+                documented_flags(include_str!("../src/instructions/create_unstake_order.rs")),
+                build_create_unstake_order_ix(&key, &key, &key, 1, 1, 1, 1),
+            ),
+            (
+                "FillUnstakeOrder",
+                documented_flags(include_str!("../src/instructions/fill_unstake_order.rs")),
+                build_fill_unstake_order_ix(&key, &key, &key, &key, &key, &key, 1, true),
+            ),
+            (
+                "CancelUnstakeOrder",
+                documented_flags(include_str!("../src/instructions/cancel_unstake_order.rs")),
+                build_cancel_unstake_order_ix(&key, &key, 1),
+            ),
+            (
+                "CrankValidatorHealth",
+                documented_flags(include_str!(
+                    "../src/instructions/crank_validator_health.rs"
+                )),
+                build_crank_validator_health_ix(&key, &key, &key),
+            ),
+            (
+                "LockLst",
+                documented_flags(include_str!("../src/instructions/lock_lst.rs")),
+                build_lock_lst_ix(&key, &key, &key, 1, 1),
+            ),
+            (
+                "UnlockLst",
+                documented_flags(include_str!("../src/instructions/unlock_lst.rs")),
+                build_unlock_lst_ix(&key, &key),
+            ),
+            (
+                "GetBuildInfo",
+                documented_flags(include_str!("../src/instructions/get_build_info.rs")),
+                build_get_build_info_ix(),
+            ),
+            (
+                "ProposeSplitHandoff",
+                documented_flags(include_str!("../src/instructions/propose_split_handoff.rs")),
+                build_propose_split_handoff_ix(&key, &key, &key, &key),
+            ),
+            (
+                "CancelSplitHandoff",
+                documented_flags(include_str!("../src/instructions/cancel_split_handoff.rs")),
+                build_cancel_split_handoff_ix(&key, &key, &key, &key),
+            ),
+            (
+                "ExecuteSplitHandoff",
+                documented_flags(include_str!("../src/instructions/execute_split_handoff.rs")),
+                build_execute_split_handoff_ix(&key, &key, &key),
+            ),
+            (
+                "SetMainHeadroom",
+                documented_flags(include_str!("../src/instructions/set_main_headroom.rs")),
+                build_set_main_headroom_ix(&key, &key, 1),
+            ),
+            (
+                "CrankSplitSingle",
+                documented_flags(include_str!("../src/instructions/crank_split.rs")),
+                build_crank_split_single_ix(&key, &key, &key, &key, &key, &key, 1).0,
+            ),
+            (
+                "OpenMigration",
+                documented_flags(include_str!("../src/instructions/open_migration.rs")),
+                build_open_migration_ix(&key, &key, &key, 1),
+            ),
+            (
+                "ImportStakeAccount",
+                documented_flags(include_str!("../src/instructions/import_stake_account.rs")),
+                build_import_stake_account_ix(&key, &key, &key, &key, &key, &key),
+            ),
+            (
+                "ImportBalance",
+                documented_flags(include_str!("../src/instructions/import_balance.rs")),
+                build_import_balance_ix(&key, &key, &key, &key, 1),
+            ),
+            (
+                "SetRewardPayout",
+                documented_flags(include_str!("../src/instructions/set_reward_payout.rs")),
+                build_set_reward_payout_ix(&key, &key, &key, 1),
+            ),
+            (
+                "EnrollPayout",
+                documented_flags(include_str!("../src/instructions/enroll_payout.rs")),
+                build_enroll_payout_ix(&key, &key, &key, 1),
+            ),
+            (
+                "WithdrawPayout",
+                documented_flags(include_str!("../src/instructions/withdraw_payout.rs")),
+                build_withdraw_payout_ix(&key, &key, &key, 1),
+            ),
+            (
+                "ClaimPayout",
+                documented_flags(include_str!("../src/instructions/claim_payout.rs")),
+                build_claim_payout_ix(&key),
+            ),
+            (
+                "CrankRewardPayout",
+                documented_flags(include_str!("../src/instructions/crank_reward_payout.rs")),
+                build_crank_reward_payout_ix(&key, &key, &key, &key),
+            ),
+            (
+                "CrankValidatorStake",
+                documented_flags(include_str!("../src/instructions/crank_validator_stake.rs")),
+                build_crank_validator_stake_ix(&key, &key, &key, &key, &key),
+            ),
+            (
+                "AddValidator",
+                documented_flags(include_str!("../src/instructions/add_validator.rs")),
+                build_add_validator_ix(&key, &key, &key),
+            ),
+            (
+                "RemoveValidator",
+                documented_flags(include_str!("../src/instructions/remove_validator.rs")),
+                build_remove_validator_ix(&key, &key, &key, &key),
+            ),
+            (
+                "CrankRebalance",
+                documented_flags(include_str!("../src/instructions/crank_rebalance.rs")),
+                build_crank_rebalance_ix(&key, &key, &key, &key, &key),
+            ),
+            (
+                "UpdateConfig",
+                documented_flags(include_str!("../src/instructions/update_config.rs")),
+                build_update_config_ix(&key, &key, &key, &key, &key),
+            ),
+            (
+                "CrankRedelegateMain",
+                documented_flags(include_str!("../src/instructions/crank_redelegate_main.rs")),
+                build_crank_redelegate_main_ix(&key, &key, &key),
+            ),
+            (
+                "ProposeAdmin",
+                documented_flags(include_str!("../src/instructions/propose_admin.rs")),
+                build_propose_admin_ix(&key, &key, &key),
+            ),
+            (
+                "AcceptAdmin",
+                documented_flags(include_str!("../src/instructions/accept_admin.rs")),
+                build_accept_admin_ix(&key, &key),
+            ),
+            (
+                "SetDepositFee",
+                documented_flags(include_str!("../src/instructions/set_deposit_fee.rs")),
+                build_set_deposit_fee_ix(&key, &key, 1),
+            ),
+            (
+                "SetWithdrawFee",
+                documented_flags(include_str!("../src/instructions/set_withdraw_fee.rs")),
+                build_set_withdraw_fee_ix(&key, &key, 1),
+            ),
+            (
+                "SetHoldingRebate",
+                documented_flags(include_str!("../src/instructions/set_holding_rebate.rs")),
+                build_set_holding_rebate_ix(&key, &key, 1, 1),
+            ),
+            (
+                "SetManagementFee",
+                documented_flags(include_str!("../src/instructions/set_management_fee.rs")),
+                build_set_management_fee_ix(&key, &key, 1),
+            ),
+            (
+                "CrankManagementFee",
+                documented_flags(include_str!("../src/instructions/crank_management_fee.rs")),
+                build_crank_management_fee_ix(&key, &key, &key, &key),
+            ),
+            (
+                "DepositStakeAccount",
+                documented_flags(include_str!("../src/instructions/deposit_stake_account.rs")),
+                build_deposit_stake_account_ix(&key, &key, &key, &key, &key, &key),
+            ),
+            (
+                "WithdrawStake",
+                documented_flags(include_str!("../src/instructions/withdraw_stake.rs")),
+                build_withdraw_stake_ix(&key, &key, &key, &key, &key, 1, 1),
+            ),
+            (
+                "InstantWithdraw",
+                documented_flags(include_str!("../src/instructions/instant_withdraw.rs")),
+                build_instant_withdraw_ix(&key, &key, &key, &key, &key, 1, 1),
+            ),
+            (
+                "CrankSplitNext",
+                [
+                    documented_flags(include_str!("../src/instructions/crank_split_next.rs")),
+                    documented_flags(include_str!("../src/instructions/crank_split.rs")),
+                ]
+                .concat(),
+                build_crank_split_next_ix(&LiteSVM::new(), &key, &key, &key, &key, &key, &key, 1).0,
+            ),
+            (
+                "SetCrankTip",
+                documented_flags(include_str!("../src/instructions/set_crank_tip.rs")),
+                build_set_crank_tip_ix(&key, &key, 1),
+            ),
+            (
+                "CrankUpdateRate",
+                documented_flags(include_str!("../src/instructions/crank_update_rate.rs")),
+                build_crank_update_rate_ix(&key, &key, &key, &key),
+            ),
+            (
+                "SetPoolCap",
+                documented_flags(include_str!("../src/instructions/set_pool_cap.rs")),
+                build_set_pool_cap_ix(&key, &key, 1),
+            ),
+            (
+                "SetDepositAuthority",
+                documented_flags(include_str!("../src/instructions/set_deposit_authority.rs")),
+                build_set_deposit_authority_ix(&key, &key, &key),
+            ),
+        ];
+
+        let mut mismatches = Vec::new();
+        for (name, documented, ix) in cases {
+            if documented.is_empty() {
+                assert!(ix.accounts.is_empty(), "{name}: no accounts documented");
+                continue;
+            }
+            for (index, meta) in ix.accounts.iter().enumerate() {
+                // Accounts past the list repeat its last entry.
+                let expected = documented[index.min(documented.len() - 1)];
+                let actual = (meta.is_signer, meta.is_writable);
+                if actual != expected {
+                    mismatches.push(format!(
+                        "{name}: account {index} is (signer, writable) {actual:?}, documented {expected:?}"
+                    ));
+                }
+            }
+        }
+        assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+    }
+
+    #[test]
+    fn test_downgrades_rejected() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = run_initialize(&mut svm);
+
+        let (depositor, depositor_ata) = run_deposit(
+            &mut svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            5_000_000_000,
+        );
+
+        run_crank_initialize_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_reserve,
+            &vote_pubkey,
+        );
+
+        run_crank_merge_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+
+        let split_account = run_crank_split(
+            &mut svm,
+            &depositor,
+            &depositor_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            1_500_000_000,
+            1,
+        );
+
+        let deposit_ix = build_deposit_ix(
+            &config_pda,
+            &depositor.pubkey(),
+            &depositor_ata,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            1_000_000_000,
+            true,
+        );
+        assert_downgrades_rejected(&mut svm, "Deposit", deposit_ix, &[&depositor]);
+
+        let (crank_split_ix, _) = build_crank_split_ix(
+            &depositor.pubkey(),
+            &depositor_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            1_500_000_000,
+            true,
+            2,
+        );
+        assert_downgrades_rejected(&mut svm, "CrankSplit", crank_split_ix, &[&depositor]);
+
+        let withdraw_ix = build_withdraw_ix(
+            &split_account,
+            &depositor.pubkey(),
+            &config_pda,
+            &Pubkey::from(STAKE_PROGRAM_ID),
+            1,
+            true,
+        );
+        assert_downgrades_rejected(&mut svm, "Withdraw", withdraw_ix, &[&depositor]);
+
+        let crank_record_rate_ix = build_crank_record_rate_ix(
+            &depositor.pubkey(),
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+        );
+        assert_downgrades_rejected(
+            &mut svm,
+            "CrankRecordRate",
+            crank_record_rate_ix,
+            &[&depositor],
+        );
+
+        let set_trusted_caller_ix = build_set_trusted_caller_ix(
+            &initializer.pubkey(),
+            &config_pda,
+            &Pubkey::new_unique(),
+            true,
+        );
+        assert_downgrades_rejected(
+            &mut svm,
+            "SetTrustedCaller",
+            set_trusted_caller_ix,
+            &[&initializer],
+        );
+
+        let admin = initializer.pubkey();
+        let admin_setters = [
+            (
+                "SetDepositFee",
+                build_set_deposit_fee_ix(&admin, &config_pda, 100),
+            ),
+            (
+                "SetWithdrawFee",
+                build_set_withdraw_fee_ix(&admin, &config_pda, 100),
+            ),
+            (
+                "SetHoldingRebate",
+                build_set_holding_rebate_ix(&admin, &config_pda, 5_000, 10),
+            ),
+            (
+                "SetManagementFee",
+                build_set_management_fee_ix(&admin, &config_pda, 100),
+            ),
+            (
+                "SetReferralFee",
+                build_set_referral_fee_ix(&admin, &config_pda, 100),
+            ),
+            (
+                "SetCrankTip",
+                build_set_crank_tip_ix(&admin, &config_pda, 1_000),
+            ),
+            (
+                "SetPoolCap",
+                build_set_pool_cap_ix(&admin, &config_pda, 1_000_000_000_000),
+            ),
+            (
+                "SetMainHeadroom",
+                build_set_main_headroom_ix(&admin, &config_pda, 1_000),
+            ),
+            (
+                "SetSplitMinimum",
+                build_set_split_minimum_ix(&admin, &config_pda, 1_000_000_000),
+            ),
+            (
+                "SetDepositCap",
+                build_set_deposit_cap_ix(&admin, &config_pda, 1_000_000_000, 0),
+            ),
+            (
+                "SetBonusSchedule",
+                build_set_bonus_schedule_ix(&admin, &config_pda, 500, 10),
+            ),
+            (
+                "SetUnstakeFeeParams",
+                build_set_unstake_fee_params_ix(&admin, &config_pda, 10, 100, 1_000_000_000),
+            ),
+            (
+                "SetValidatorStakeCap",
+                build_set_validator_stake_cap_ix(&admin, &config_pda, 5_000, 0),
+            ),
+            (
+                "SetDepositAuthority",
+                build_set_deposit_authority_ix(&admin, &config_pda, &Pubkey::new_unique()),
+            ),
+            (
+                "ProposeAdmin",
+                build_propose_admin_ix(&admin, &config_pda, &Pubkey::new_unique()),
+            ),
+        ];
+        for (name, ix) in admin_setters {
+            assert_downgrades_rejected(&mut svm, name, ix, &[&initializer]);
+        }
+    }
+}
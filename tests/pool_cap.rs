@@ -0,0 +1,194 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::{
+        instruction::AccountMeta,
+        signature::{Keypair, Signer},
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_buyback_ix, build_claim_queued_deposit_ix, build_deposit_ix,
+        build_set_buyback_policy_ix, build_set_deposit_cap_ix, build_set_pool_cap_ix,
+        create_and_fund_ata, fee_vault_pda, get_queued_deposit, queued_deposit_pda, run_initialize,
+        send, set_deposit_account, setup_svm, warp_epoch, DEPOSIT_QUEUED_DEPOSIT,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+    const DEPOSIT_CAP_QUEUE: u8 = 1;
+    const BUYBACK_BURN: u8 = 1;
+
+    #[test]
+    fn test_pool_cap_rejects_deposits_past_it_until_raised() {
+        let mut svm = setup_svm();
+        let (admin, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+
+        // A fresh pool holds 2 SOL beyond rent.
+        let cap = 5 * LAMPORTS_PER_SOL;
+        assert!(!send(
+            &mut svm,
+            build_set_pool_cap_ix(&Keypair::new().pubkey(), &config_pda, cap),
+            &admin,
+        ));
+        assert!(send(
+            &mut svm,
+            build_set_pool_cap_ix(&admin.pubkey(), &config_pda, cap),
+            &admin,
+        ));
+
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 20 * LAMPORTS_PER_SOL)
+            .unwrap();
+        let depositor_ata = create_and_fund_ata(&mut svm, &depositor.pubkey(), &mint, 0);
+        let deposit_ix = |lamports: u64| {
+            build_deposit_ix(
+                &config_pda,
+                &depositor.pubkey(),
+                &depositor_ata,
+                &mint,
+                &stake_account_main,
+                &stake_account_reserve,
+                lamports,
+                true,
+            )
+        };
+
+        assert!(send(&mut svm, deposit_ix(2 * LAMPORTS_PER_SOL), &depositor));
+        assert!(
+            !send(&mut svm, deposit_ix(2 * LAMPORTS_PER_SOL), &depositor),
+            "4 + 2 SOL is past the 5 SOL cap"
+        );
+        assert!(
+            send(&mut svm, deposit_ix(LAMPORTS_PER_SOL), &depositor),
+            "Filling the pool up to the cap is allowed"
+        );
+
+        assert!(send(
+            &mut svm,
+            build_set_pool_cap_ix(&admin.pubkey(), &config_pda, 0),
+            &admin,
+        ));
+        assert!(
+            send(&mut svm, deposit_ix(2 * LAMPORTS_PER_SOL), &depositor),
+            "Zero removes the cap"
+        );
+    }
+
+    #[test]
+    fn test_pool_cap_holds_back_queued_deposits() {
+        let mut svm = setup_svm();
+        let (admin, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+
+        assert!(send(
+            &mut svm,
+            build_set_deposit_cap_ix(
+                &admin.pubkey(),
+                &config_pda,
+                LAMPORTS_PER_SOL,
+                DEPOSIT_CAP_QUEUE
+            ),
+            &admin,
+        ));
+        assert!(send(
+            &mut svm,
+            build_set_pool_cap_ix(&admin.pubkey(), &config_pda, 3 * LAMPORTS_PER_SOL),
+            &admin,
+        ));
+
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 20 * LAMPORTS_PER_SOL)
+            .unwrap();
+        let depositor_ata = create_and_fund_ata(&mut svm, &depositor.pubkey(), &mint, 0);
+        let mut ix = build_deposit_ix(
+            &config_pda,
+            &depositor.pubkey(),
+            &depositor_ata,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+            3 * LAMPORTS_PER_SOL,
+            true,
+        );
+        set_deposit_account(
+            &mut ix,
+            DEPOSIT_QUEUED_DEPOSIT,
+            AccountMeta::new(queued_deposit_pda(&depositor.pubkey()), false),
+        );
+        assert!(
+            send(&mut svm, ix, &depositor),
+            "Only the 1 SOL deposited now counts against the pool cap"
+        );
+        assert_eq!(
+            get_queued_deposit(&svm, &depositor.pubkey()),
+            2 * LAMPORTS_PER_SOL
+        );
+
+        warp_epoch(&mut svm, 1);
+        let claim_ix = build_claim_queued_deposit_ix(
+            &config_pda,
+            &depositor.pubkey(),
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+        assert!(
+            !send(&mut svm, claim_ix.clone(), &depositor),
+            "The pool is at its cap"
+        );
+
+        assert!(send(
+            &mut svm,
+            build_set_pool_cap_ix(&admin.pubkey(), &config_pda, 4 * LAMPORTS_PER_SOL),
+            &admin,
+        ));
+        assert!(send(&mut svm, claim_ix, &depositor));
+        assert_eq!(
+            get_queued_deposit(&svm, &depositor.pubkey()),
+            LAMPORTS_PER_SOL
+        );
+    }
+
+    #[test]
+    fn test_pool_cap_applies_to_buybacks_to_the_treasury() {
+        let mut svm = setup_svm();
+        let (admin, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+        create_and_fund_ata(&mut svm, &config_pda, &mint, 0);
+
+        // A fresh pool holds 2 SOL beyond rent.
+        assert!(send(
+            &mut svm,
+            build_set_pool_cap_ix(&admin.pubkey(), &config_pda, 3 * LAMPORTS_PER_SOL),
+            &admin,
+        ));
+        svm.airdrop(&fee_vault_pda(), 2 * LAMPORTS_PER_SOL).unwrap();
+
+        let keeper = Keypair::new();
+        svm.airdrop(&keeper.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        let buyback_ix = build_buyback_ix(
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+        assert!(
+            !send(&mut svm, buyback_ix.clone(), &keeper),
+            "Buying back 2 SOL into the treasury is a deposit past the cap"
+        );
+
+        assert!(send(
+            &mut svm,
+            build_set_buyback_policy_ix(&admin.pubkey(), &config_pda, BUYBACK_BURN),
+            &admin,
+        ));
+        assert!(
+            send(&mut svm, buyback_ix, &keeper),
+            "A burning buyback mints nothing and is exempt"
+        );
+    }
+}
@@ -0,0 +1,331 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_deposit_ix, build_exit_pool_ix, build_set_pool_status_ix, create_and_fund_ata,
+        decode_events, get_pool_status, print_transaction_logs, run_crank_initialize_reserve,
+        run_crank_merge_reserve, run_initialize, setup_svm,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    const BOOTSTRAPPING: u8 = 0;
+    const ACTIVE: u8 = 1;
+    const PAUSED: u8 = 2;
+    const DEACTIVATING: u8 = 3;
+    const CLOSED: u8 = 4;
+    const EVENT_STATUS: u8 = 4;
+    const EVENT_ADMIN_ACTION: u8 = 6;
+
+    fn send(svm: &mut LiteSVM, ix: Instruction, signer: &Keypair) -> Option<Vec<String>> {
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&signer.pubkey()),
+            &[signer],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        svm.expire_blockhash();
+        result.ok().map(|meta| meta.logs)
+    }
+
+    fn deposit(
+        svm: &mut LiteSVM,
+        config_pda: &Pubkey,
+        mint: &Pubkey,
+        stake_account_main: &Pubkey,
+        stake_account_reserve: &Pubkey,
+    ) -> bool {
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .unwrap();
+        let depositor_ata = create_and_fund_ata(svm, &depositor.pubkey(), mint, 0);
+        let ix = build_deposit_ix(
+            config_pda,
+            &depositor.pubkey(),
+            &depositor_ata,
+            mint,
+            stake_account_main,
+            stake_account_reserve,
+            2 * LAMPORTS_PER_SOL,
+            true,
+        );
+        send(svm, ix, &depositor).is_some()
+    }
+
+    #[test]
+    fn test_first_merge_activates_bootstrapping_pool() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            _token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = run_initialize(&mut svm);
+        assert_eq!(get_pool_status(&svm, &config_pda), BOOTSTRAPPING);
+
+        run_crank_initialize_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_reserve,
+            &vote_pubkey,
+        );
+        run_crank_merge_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+
+        assert_eq!(get_pool_status(&svm, &config_pda), ACTIVE);
+    }
+
+    #[test]
+    fn test_bootstrapping_pool_takes_deposits_but_no_unstakes() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+
+        assert!(
+            deposit(
+                &mut svm,
+                &config_pda,
+                &mint,
+                &stake_account_main,
+                &stake_account_reserve
+            ),
+            "Bootstrapping pool should take deposits"
+        );
+
+        let exit = |svm: &mut LiteSVM| {
+            let (ix, _) = build_exit_pool_ix(
+                &initializer.pubkey(),
+                &initializer_ata,
+                &config_pda,
+                &stake_account_main,
+                &stake_account_reserve,
+                &mint,
+                1,
+            );
+            send(svm, ix, &initializer)
+        };
+        assert!(
+            exit(&mut svm).is_none(),
+            "Bootstrapping pool should reject unstakes"
+        );
+
+        assert!(send(
+            &mut svm,
+            build_set_pool_status_ix(&initializer.pubkey(), &config_pda, ACTIVE),
+            &initializer
+        )
+        .is_some());
+        assert!(
+            exit(&mut svm).is_some(),
+            "Unstakes should open once the admin activates the pool"
+        );
+    }
+
+    #[test]
+    fn test_pause_blocks_deposits_until_resumed() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+
+        let stranger = Keypair::new();
+        svm.airdrop(&stranger.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        assert!(
+            send(
+                &mut svm,
+                build_set_pool_status_ix(&stranger.pubkey(), &config_pda, PAUSED),
+                &stranger
+            )
+            .is_none(),
+            "Only the admin may pause the pool"
+        );
+
+        let logs = send(
+            &mut svm,
+            build_set_pool_status_ix(&initializer.pubkey(), &config_pda, PAUSED),
+            &initializer,
+        )
+        .expect("Admin should be able to pause the pool");
+        assert_eq!(get_pool_status(&svm, &config_pda), PAUSED);
+
+        let events = decode_events(&logs);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].0, EVENT_ADMIN_ACTION);
+        assert_eq!(events[1].0, EVENT_STATUS);
+        assert_eq!(events[1].2, vec![BOOTSTRAPPING, PAUSED]);
+
+        let deposited = deposit(
+            &mut svm,
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+        assert!(!deposited, "Deposits should fail while paused");
+
+        assert!(send(
+            &mut svm,
+            build_set_pool_status_ix(&initializer.pubkey(), &config_pda, ACTIVE),
+            &initializer
+        )
+        .is_some());
+        assert_eq!(get_pool_status(&svm, &config_pda), ACTIVE);
+
+        let deposited = deposit(
+            &mut svm,
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+        assert!(deposited, "Deposits should resume once active");
+    }
+
+    #[test]
+    fn test_deactivating_is_one_way_and_blocks_deposits() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+
+        assert!(send(
+            &mut svm,
+            build_set_pool_status_ix(&initializer.pubkey(), &config_pda, DEACTIVATING),
+            &initializer
+        )
+        .is_some());
+        assert_eq!(get_pool_status(&svm, &config_pda), DEACTIVATING);
+
+        let deposited = deposit(
+            &mut svm,
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+        assert!(!deposited, "Deposits should fail while deactivating");
+
+        for status in [ACTIVE, PAUSED] {
+            assert!(
+                send(
+                    &mut svm,
+                    build_set_pool_status_ix(&initializer.pubkey(), &config_pda, status),
+                    &initializer
+                )
+                .is_none(),
+                "Deactivating pool should not move to status {status}"
+            );
+        }
+        assert_eq!(get_pool_status(&svm, &config_pda), DEACTIVATING);
+    }
+
+    #[test]
+    fn test_admin_cannot_set_program_only_statuses() {
+        let mut svm = setup_svm();
+        let (initializer, _, _, config_pda, _, _, _) = run_initialize(&mut svm);
+
+        for status in [BOOTSTRAPPING, CLOSED, 5] {
+            assert!(
+                send(
+                    &mut svm,
+                    build_set_pool_status_ix(&initializer.pubkey(), &config_pda, status),
+                    &initializer
+                )
+                .is_none(),
+                "Admin should not be able to set status {status}"
+            );
+        }
+        assert_eq!(get_pool_status(&svm, &config_pda), BOOTSTRAPPING);
+    }
+
+    #[test]
+    fn test_exit_pool_closes_pool() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+        assert!(send(
+            &mut svm,
+            build_set_pool_status_ix(&initializer.pubkey(), &config_pda, ACTIVE),
+            &initializer
+        )
+        .is_some());
+
+        let (ix, _) = build_exit_pool_ix(
+            &initializer.pubkey(),
+            &initializer_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            1,
+        );
+        let logs = send(&mut svm, ix, &initializer).expect("Last holder should exit the pool");
+        assert_eq!(get_pool_status(&svm, &config_pda), CLOSED);
+
+        let status_events: Vec<_> = decode_events(&logs)
+            .into_iter()
+            .filter(|(kind, _, _)| *kind == EVENT_STATUS)
+            .collect();
+        assert_eq!(status_events.len(), 1);
+        assert_eq!(status_events[0].2, vec![ACTIVE, CLOSED]);
+
+        assert!(
+            send(
+                &mut svm,
+                build_set_pool_status_ix(&initializer.pubkey(), &config_pda, ACTIVE),
+                &initializer
+            )
+            .is_none(),
+            "Closed pool should not reopen"
+        );
+    }
+}
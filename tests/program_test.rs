@@ -0,0 +1,56 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helpers::program_test::{
+        advance_epochs, get_balance_sheet, get_lamports, start_pool,
+    };
+
+    #[tokio::test]
+    async fn test_main_stake_activates_over_an_epoch_boundary() {
+        let mut pool = start_pool().await;
+
+        let [active, activating, ..] = get_balance_sheet(&mut pool).await.unwrap();
+        assert_eq!(active, 0);
+        assert!(activating > 0, "Main is delegated at Initialize");
+
+        let vote_pubkey = pool.vote_pubkey;
+        advance_epochs(&mut pool.context, &vote_pubkey, 1).await;
+
+        let [active, activating, ..] = get_balance_sheet(&mut pool).await.unwrap();
+        assert!(active > 0, "Stake history should record the warmup");
+        assert_eq!(activating, 0);
+    }
+
+    #[tokio::test]
+    async fn test_rewards_accrue_to_main_and_raise_the_rate() {
+        let mut pool = start_pool().await;
+        let vote_pubkey = pool.vote_pubkey;
+        advance_epochs(&mut pool.context, &vote_pubkey, 1).await;
+
+        let main = pool.stake_account_main;
+        let main_before = get_lamports(&mut pool.context, &main).await;
+        let [active_before, _, _, _, _, lst_supply, liabilities_before, _] =
+            get_balance_sheet(&mut pool).await.unwrap();
+
+        advance_epochs(&mut pool.context, &vote_pubkey, 2).await;
+
+        assert!(
+            get_lamports(&mut pool.context, &main).await > main_before,
+            "Inflation rewards should be paid into main"
+        );
+
+        let [active, activating, deactivating, undelegated, _, lst_supply_after, liabilities, _] =
+            get_balance_sheet(&mut pool).await.unwrap();
+        assert!(active > active_before);
+        assert_eq!(lst_supply_after, lst_supply, "Rewards mint no LST");
+        assert!(
+            liabilities > liabilities_before,
+            "Each LST should be worth more after rewards"
+        );
+        assert_eq!(
+            active + activating + deactivating + undelegated,
+            liabilities
+        );
+    }
+}
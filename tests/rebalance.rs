@@ -0,0 +1,282 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    };
+
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
+
+    use crate::test_helpers::test_helpers::{
+        build_add_validator_ix, build_adopt_reserve_ix, build_crank_rebalance_ix,
+        build_crank_validator_stake_ix, build_set_validator_stake_cap_ix, create_vote_account,
+        run_crank_initialize_reserve, run_crank_merge_reserve, send, setup_pool, setup_svm,
+        validator_stake_pda, validator_transient_pda, warp_epoch, Pool, PoolSetup,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    fn lamports(svm: &LiteSVM, pubkey: &Pubkey) -> u64 {
+        svm.get_account(pubkey)
+            .map_or(0, |account| account.lamports)
+    }
+
+    fn validator_stake_lamports(svm: &LiteSVM, config_pda: &Pubkey) -> u64 {
+        let config = svm.get_account(config_pda).unwrap().data;
+        u64::from_le_bytes(config[325..333].try_into().unwrap())
+    }
+
+    impl Pool {
+        /// Deposits into the reserve, adopting it first if a merge closed
+        /// it.
+        fn refill_reserve(&self, svm: &mut LiteSVM, lamports: u64) {
+            self.deposit(svm, lamports);
+            let adopted = svm
+                .get_account(&self.stake_account_reserve)
+                .is_some_and(|account| account.owner == Pubkey::from(STAKE_PROGRAM_ID));
+            if !adopted {
+                assert!(send(
+                    svm,
+                    build_adopt_reserve_ix(
+                        &self.admin.pubkey(),
+                        &self.config_pda,
+                        &self.stake_account_main,
+                        &self.stake_account_reserve,
+                    ),
+                    &self.admin,
+                ));
+            }
+        }
+
+        /// Delegates a fresh deposit through the reserve, so it can be
+        /// merged into main.
+        fn delegate_reserve(&self, svm: &mut LiteSVM) {
+            self.refill_reserve(svm, 2 * LAMPORTS_PER_SOL);
+            run_crank_initialize_reserve(
+                svm,
+                &self.admin,
+                &self.config_pda,
+                &self.stake_account_reserve,
+                &self.vote_pubkey,
+            );
+        }
+
+        fn merge_reserve(&self, svm: &mut LiteSVM) {
+            run_crank_merge_reserve(
+                svm,
+                &self.admin,
+                &self.config_pda,
+                &self.stake_account_main,
+                &self.stake_account_reserve,
+            );
+        }
+
+        fn set_stake_cap(&self, svm: &mut LiteSVM, lamports: u64) {
+            assert!(send(
+                svm,
+                build_set_validator_stake_cap_ix(
+                    &self.admin.pubkey(),
+                    &self.config_pda,
+                    10_000,
+                    lamports,
+                ),
+                &self.admin,
+            ));
+        }
+
+        fn rebalance(&self, svm: &mut LiteSVM, validator: &Pubkey) -> bool {
+            send(
+                svm,
+                build_crank_rebalance_ix(
+                    &self.admin.pubkey(),
+                    &self.config_pda,
+                    validator,
+                    &self.stake_account_main,
+                    &self.stake_account_reserve,
+                ),
+                &self.admin,
+            )
+        }
+    }
+
+    /// Initializes a pool with one listed validator, returned by its vote
+    /// account, whose stake PDA holds the pool's first 10 SOL deposit.
+    fn setup(svm: &mut LiteSVM) -> (Pool, Pubkey) {
+        let pool = setup_pool(svm, PoolSetup::default());
+        let validator = create_vote_account(svm, &Keypair::new().pubkey());
+
+        assert!(send(
+            svm,
+            build_add_validator_ix(&pool.admin.pubkey(), &pool.config_pda, &validator),
+            &pool.admin,
+        ));
+        pool.deposit(svm, 10 * LAMPORTS_PER_SOL);
+        assert!(send(
+            svm,
+            build_crank_validator_stake_ix(
+                &pool.admin.pubkey(),
+                &pool.config_pda,
+                &validator,
+                &pool.stake_account_main,
+                &pool.stake_account_reserve,
+            ),
+            &pool.admin,
+        ));
+        (pool, validator)
+    }
+
+    #[test]
+    fn test_rebalance_waits_for_the_reserve_merge() {
+        let mut svm = setup_svm();
+        let (pool, validator) = setup(&mut svm);
+        pool.delegate_reserve(&mut svm);
+        pool.set_stake_cap(&mut svm, 3 * LAMPORTS_PER_SOL);
+        warp_epoch(&mut svm, 1);
+
+        assert!(
+            !pool.rebalance(&mut svm, &validator),
+            "Rebalancing waits for this epoch's reserve merge"
+        );
+
+        pool.merge_reserve(&mut svm);
+        assert!(
+            pool.rebalance(&mut svm, &validator),
+            "The excess is split off once the reserve has merged"
+        );
+    }
+
+    #[test]
+    fn test_rebalance_tops_up_an_under_target_validator() {
+        let mut svm = setup_svm();
+        let (pool, validator) = setup(&mut svm);
+        pool.delegate_reserve(&mut svm);
+        pool.merge_reserve(&mut svm);
+
+        pool.refill_reserve(&mut svm, 5 * LAMPORTS_PER_SOL);
+        let total_before = validator_stake_lamports(&svm, &pool.config_pda)
+            + lamports(&svm, &pool.stake_account_reserve);
+
+        assert!(
+            pool.rebalance(&mut svm, &validator),
+            "The shortfall moves into a transient"
+        );
+        let transient = validator_transient_pda(&validator);
+        assert!(lamports(&svm, &transient) > 5 * LAMPORTS_PER_SOL);
+        assert_eq!(
+            validator_stake_lamports(&svm, &pool.config_pda)
+                + lamports(&svm, &pool.stake_account_reserve),
+            total_before,
+            "The transient counts toward the pool"
+        );
+        assert!(
+            !pool.rebalance(&mut svm, &validator),
+            "The transient must activate first"
+        );
+
+        let stake_before = lamports(&svm, &validator_stake_pda(&validator));
+        pool.delegate_reserve(&mut svm);
+        warp_epoch(&mut svm, 1);
+        pool.merge_reserve(&mut svm);
+        assert!(
+            pool.rebalance(&mut svm, &validator),
+            "The active transient is merged"
+        );
+        assert_eq!(lamports(&svm, &transient), 0);
+        assert!(
+            lamports(&svm, &validator_stake_pda(&validator)) >= stake_before + 5 * LAMPORTS_PER_SOL
+        );
+    }
+
+    #[test]
+    fn test_rebalance_moves_main_stake_to_an_under_target_validator() {
+        let mut svm = setup_svm();
+        let (pool, validator) = setup(&mut svm);
+        pool.delegate_reserve(&mut svm);
+        warp_epoch(&mut svm, 1);
+        pool.merge_reserve(&mut svm);
+
+        let main_before = lamports(&svm, &pool.stake_account_main);
+        assert!(
+            pool.rebalance(&mut svm, &validator),
+            "With the reserve empty, the shortfall is split off main"
+        );
+        let transient = validator_transient_pda(&validator);
+        let moved = lamports(&svm, &transient);
+        assert!(moved > 0);
+        assert!(lamports(&svm, &pool.stake_account_main) < main_before);
+        assert!(
+            !pool.rebalance(&mut svm, &validator),
+            "The transient must cool down first"
+        );
+
+        pool.delegate_reserve(&mut svm);
+        warp_epoch(&mut svm, 2);
+        pool.merge_reserve(&mut svm);
+        assert!(
+            pool.rebalance(&mut svm, &validator),
+            "The cooled transient is delegated to the validator"
+        );
+        let data = svm.get_account(&transient).unwrap().data;
+        assert_eq!(Pubkey::try_from(&data[124..156]).unwrap(), validator);
+        assert_eq!(lamports(&svm, &transient), moved);
+
+        let stake_before = lamports(&svm, &validator_stake_pda(&validator));
+        pool.delegate_reserve(&mut svm);
+        warp_epoch(&mut svm, 3);
+        pool.merge_reserve(&mut svm);
+        assert!(
+            pool.rebalance(&mut svm, &validator),
+            "The active transient is merged"
+        );
+        assert_eq!(lamports(&svm, &transient), 0);
+        assert!(lamports(&svm, &validator_stake_pda(&validator)) > stake_before);
+    }
+
+    #[test]
+    fn test_rebalance_drains_an_over_target_validator() {
+        let mut svm = setup_svm();
+        let (pool, validator) = setup(&mut svm);
+        pool.delegate_reserve(&mut svm);
+        pool.set_stake_cap(&mut svm, 3 * LAMPORTS_PER_SOL);
+        warp_epoch(&mut svm, 1);
+        pool.merge_reserve(&mut svm);
+
+        assert!(
+            pool.rebalance(&mut svm, &validator),
+            "The excess is split off"
+        );
+        let transient = validator_transient_pda(&validator);
+        let moved = lamports(&svm, &transient);
+        assert!(moved > 0);
+        assert!(
+            !pool.rebalance(&mut svm, &validator),
+            "The transient must cool down first"
+        );
+
+        // Main is over the cap too, so lift it while the next epoch's
+        // reserve is delegated.
+        pool.set_stake_cap(&mut svm, 0);
+        pool.delegate_reserve(&mut svm);
+        pool.set_stake_cap(&mut svm, 3 * LAMPORTS_PER_SOL);
+        warp_epoch(&mut svm, 2);
+        pool.merge_reserve(&mut svm);
+
+        let reserve_before = lamports(&svm, &pool.stake_account_reserve);
+        assert!(
+            pool.rebalance(&mut svm, &validator),
+            "The inactive transient is withdrawn"
+        );
+        assert_eq!(lamports(&svm, &transient), 0);
+        assert_eq!(
+            lamports(&svm, &pool.stake_account_reserve),
+            reserve_before + moved
+        );
+        assert!(
+            !pool.rebalance(&mut svm, &validator),
+            "The validator is within its cap"
+        );
+    }
+}
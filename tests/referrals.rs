@@ -0,0 +1,297 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_liquid_staking::events::{DEPOSIT_FLAG_REFERRER, EVENT_DEPOSIT};
+    use solana_sdk::{
+        instruction::AccountMeta,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_claim_referral_fees_ix, build_deposit_ix, build_register_referrer_ix,
+        build_set_referral_fee_ix, create_and_fund_ata, decode_events, get_mint_supply,
+        get_referrer_stats, get_token_balance, referrer_pda, send, set_deposit_account, setup_pool,
+        setup_svm, Pool, PoolSetup, DEPOSIT_REFERRER, DEPOSIT_REFERRER_ATA, DEPOSIT_TREASURY_ATA,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    fn setup(svm: &mut LiteSVM) -> (Pool, Keypair) {
+        let pool = setup_pool(
+            svm,
+            PoolSetup {
+                treasury_balance: Some(0),
+                ..PoolSetup::default()
+            },
+        );
+
+        let referrer = Keypair::new();
+        svm.airdrop(&referrer.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        assert!(send(
+            svm,
+            build_register_referrer_ix(&referrer.pubkey()),
+            &referrer
+        ));
+
+        (pool, referrer)
+    }
+
+    /// Deposits `lamports` naming `referrer`, with the treasury ATA passed
+    /// when `with_treasury`. Returns (LST minted, LST received) on success.
+    fn referred_deposit(
+        svm: &mut LiteSVM,
+        pool: &Pool,
+        referrer: &Pubkey,
+        lamports: u64,
+        with_treasury: bool,
+    ) -> Option<(u64, u64)> {
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 20 * LAMPORTS_PER_SOL)
+            .unwrap();
+        let depositor_ata = create_and_fund_ata(svm, &depositor.pubkey(), &pool.mint, 0);
+
+        let mut ix = build_deposit_ix(
+            &pool.config_pda,
+            &depositor.pubkey(),
+            &depositor_ata,
+            &pool.mint,
+            &pool.stake_account_main,
+            &pool.stake_account_reserve,
+            lamports,
+            true,
+        );
+        if with_treasury {
+            set_deposit_account(
+                &mut ix,
+                DEPOSIT_TREASURY_ATA,
+                AccountMeta::new(pool.treasury_ata, false),
+            );
+        }
+        set_deposit_account(
+            &mut ix,
+            DEPOSIT_REFERRER,
+            AccountMeta::new(referrer_pda(referrer), false),
+        );
+
+        let supply_before = get_mint_supply(svm, &pool.mint);
+        if !send(svm, ix, &depositor) {
+            return None;
+        }
+
+        let minted = get_mint_supply(svm, &pool.mint) - supply_before;
+        Some((minted, get_token_balance(svm, &depositor_ata)))
+    }
+
+    #[test]
+    fn test_referred_deposits_accrue_fees_the_referrer_claims() {
+        let mut svm = setup_svm();
+        let (pool, referrer) = setup(&mut svm);
+
+        let ix = build_set_referral_fee_ix(&pool.admin.pubkey(), &pool.config_pda, 1_000);
+        assert!(send(&mut svm, ix, &pool.admin));
+
+        let (minted, received) = referred_deposit(
+            &mut svm,
+            &pool,
+            &referrer.pubkey(),
+            2 * LAMPORTS_PER_SOL,
+            true,
+        )
+        .expect("Referred deposit should succeed");
+        let fee = minted * 1_000 / 10_000;
+        assert_eq!(received, minted - fee);
+        assert_eq!(get_token_balance(&svm, &pool.treasury_ata), fee);
+
+        referred_deposit(
+            &mut svm,
+            &pool,
+            &referrer.pubkey(),
+            3 * LAMPORTS_PER_SOL,
+            true,
+        )
+        .expect("Referred deposit should succeed");
+
+        let (referred_lamports, referred_deposits, fees_earned, fees_claimed) =
+            get_referrer_stats(&svm, &referrer.pubkey());
+        assert_eq!(referred_lamports, 5 * LAMPORTS_PER_SOL);
+        assert_eq!(referred_deposits, 2);
+        assert_eq!(fees_earned, get_token_balance(&svm, &pool.treasury_ata));
+        assert_eq!(fees_claimed, 0);
+
+        let referrer_ata = create_and_fund_ata(&mut svm, &referrer.pubkey(), &pool.mint, 0);
+        let claim = build_claim_referral_fees_ix(
+            &referrer.pubkey(),
+            &referrer_ata,
+            &pool.config_pda,
+            &pool.mint,
+        );
+        assert!(send(&mut svm, claim.clone(), &referrer));
+        assert_eq!(get_token_balance(&svm, &referrer_ata), fees_earned);
+        assert_eq!(get_token_balance(&svm, &pool.treasury_ata), 0);
+        assert_eq!(get_referrer_stats(&svm, &referrer.pubkey()).3, fees_earned);
+
+        assert!(
+            !send(&mut svm, claim, &referrer),
+            "Nothing is left to claim"
+        );
+    }
+
+    #[test]
+    fn test_referral_fee_needs_the_treasury_ata() {
+        let mut svm = setup_svm();
+        let (pool, referrer) = setup(&mut svm);
+
+        // Without a fee, referred deposits only count towards the stats.
+        let (minted, received) =
+            referred_deposit(&mut svm, &pool, &referrer.pubkey(), LAMPORTS_PER_SOL, false)
+                .expect("Referred deposit should succeed");
+        assert_eq!(received, minted);
+        assert_eq!(
+            get_referrer_stats(&svm, &referrer.pubkey()),
+            (LAMPORTS_PER_SOL, 1, 0, 0)
+        );
+
+        let ix = build_set_referral_fee_ix(&pool.admin.pubkey(), &pool.config_pda, 500);
+        assert!(send(&mut svm, ix, &pool.admin));
+
+        assert!(
+            referred_deposit(&mut svm, &pool, &referrer.pubkey(), LAMPORTS_PER_SOL, false)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_referred_deposit_event_names_the_referrer() {
+        let mut svm = setup_svm();
+        let (pool, referrer) = setup(&mut svm);
+
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .unwrap();
+        let depositor_ata = create_and_fund_ata(&mut svm, &depositor.pubkey(), &pool.mint, 0);
+        let mut ix = build_deposit_ix(
+            &pool.config_pda,
+            &depositor.pubkey(),
+            &depositor_ata,
+            &pool.mint,
+            &pool.stake_account_main,
+            &pool.stake_account_reserve,
+            LAMPORTS_PER_SOL,
+            true,
+        );
+        set_deposit_account(
+            &mut ix,
+            DEPOSIT_REFERRER,
+            AccountMeta::new(referrer_pda(&referrer.pubkey()), false),
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+        let meta = svm.send_transaction(tx).expect("Deposit should succeed");
+
+        let events = decode_events(&meta.logs);
+        let (kind, _, payload) = &events[0];
+        assert_eq!(*kind, EVENT_DEPOSIT);
+        assert_eq!(payload.len(), 113);
+        assert_eq!(payload[48], DEPOSIT_FLAG_REFERRER);
+        assert_eq!(&payload[49..81], &[0u8; 32], "No memo was passed");
+        assert_eq!(&payload[81..113], referrer.pubkey().as_ref());
+    }
+
+    #[test]
+    fn test_referral_fee_is_minted_to_the_referrer_ata() {
+        let mut svm = setup_svm();
+        let (pool, referrer) = setup(&mut svm);
+        let referrer_ata = create_and_fund_ata(&mut svm, &referrer.pubkey(), &pool.mint, 0);
+
+        let ix = build_set_referral_fee_ix(&pool.admin.pubkey(), &pool.config_pda, 1_000);
+        assert!(send(&mut svm, ix, &pool.admin));
+
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .unwrap();
+        let depositor_ata = create_and_fund_ata(&mut svm, &depositor.pubkey(), &pool.mint, 0);
+        let mut ix = build_deposit_ix(
+            &pool.config_pda,
+            &depositor.pubkey(),
+            &depositor_ata,
+            &pool.mint,
+            &pool.stake_account_main,
+            &pool.stake_account_reserve,
+            2 * LAMPORTS_PER_SOL,
+            true,
+        );
+        set_deposit_account(
+            &mut ix,
+            DEPOSIT_REFERRER,
+            AccountMeta::new(referrer_pda(&referrer.pubkey()), false),
+        );
+
+        let mut wrong_ata = ix.clone();
+        set_deposit_account(
+            &mut wrong_ata,
+            DEPOSIT_REFERRER_ATA,
+            AccountMeta::new(depositor_ata, false),
+        );
+        assert!(
+            !send(&mut svm, wrong_ata, &depositor),
+            "The fee only goes to the referrer's own ATA"
+        );
+
+        set_deposit_account(
+            &mut ix,
+            DEPOSIT_REFERRER_ATA,
+            AccountMeta::new(referrer_ata, false),
+        );
+        let supply_before = get_mint_supply(&svm, &pool.mint);
+        assert!(
+            send(&mut svm, ix, &depositor),
+            "The referrer is paid without the treasury ATA"
+        );
+
+        let minted = get_mint_supply(&svm, &pool.mint) - supply_before;
+        let fee = minted * 1_000 / 10_000;
+        assert_eq!(get_token_balance(&svm, &referrer_ata), fee);
+        assert_eq!(get_token_balance(&svm, &depositor_ata), minted - fee);
+        assert_eq!(get_token_balance(&svm, &pool.treasury_ata), 0);
+        assert_eq!(
+            get_referrer_stats(&svm, &referrer.pubkey()),
+            (2 * LAMPORTS_PER_SOL, 1, fee, fee),
+            "A fee paid out directly counts as claimed"
+        );
+    }
+
+    #[test]
+    fn test_only_the_referrer_claims_and_registers() {
+        let mut svm = setup_svm();
+        let (pool, referrer) = setup(&mut svm);
+
+        assert!(
+            !send(
+                &mut svm,
+                build_register_referrer_ix(&referrer.pubkey()),
+                &referrer
+            ),
+            "A referrer registers once"
+        );
+
+        let outsider = Keypair::new();
+        svm.airdrop(&outsider.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        let outsider_ata = create_and_fund_ata(&mut svm, &outsider.pubkey(), &pool.mint, 0);
+        let mut claim = build_claim_referral_fees_ix(
+            &outsider.pubkey(),
+            &outsider_ata,
+            &pool.config_pda,
+            &pool.mint,
+        );
+        claim.accounts[2] = AccountMeta::new(referrer_pda(&referrer.pubkey()), false);
+        assert!(!send(&mut svm, claim, &outsider));
+    }
+}
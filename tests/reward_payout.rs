@@ -0,0 +1,203 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_liquid_staking::math::reward_payout_lamports;
+    use solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    };
+
+    use crate::test_helpers::test_helpers::{
+        add_stake_rewards, build_claim_payout_ix, build_crank_reward_payout_ix,
+        build_enroll_payout_ix, build_set_reward_payout_ix, build_withdraw_payout_ix,
+        get_mint_supply, get_token_balance, payout_vault, reward_payout_pda, send, setup_pool,
+        setup_svm, warp_epoch, Pool, PoolSetup,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    fn lamports(svm: &LiteSVM, pubkey: &Pubkey) -> u64 {
+        svm.get_account(pubkey)
+            .map_or(0, |account| account.lamports)
+    }
+
+    impl Pool {
+        fn crank(&self, svm: &mut LiteSVM, keeper: &Keypair) -> bool {
+            send(
+                svm,
+                build_crank_reward_payout_ix(
+                    &self.config_pda,
+                    &self.mint,
+                    &self.stake_account_main,
+                    &self.stake_account_reserve,
+                ),
+                keeper,
+            )
+        }
+
+        /// Pool lamports beyond the two stake accounts' rent, read the way
+        /// the program reads them.
+        fn pool_lamports(&self, svm: &LiteSVM) -> u64 {
+            let rent = svm.minimum_balance_for_rent_exemption(200);
+            lamports(svm, &self.stake_account_main) + lamports(svm, &self.stake_account_reserve)
+                - 2 * rent
+        }
+    }
+
+    fn setup(svm: &mut LiteSVM, reward_payout_bps: u16) -> Pool {
+        let pool = setup_pool(svm, PoolSetup::default());
+
+        assert!(send(
+            svm,
+            build_set_reward_payout_ix(
+                &pool.admin.pubkey(),
+                &pool.config_pda,
+                &pool.mint,
+                reward_payout_bps
+            ),
+            &pool.admin,
+        ));
+        pool
+    }
+
+    #[test]
+    fn test_crank_pays_the_configured_share_of_rewards_as_sol() {
+        let mut svm = setup_svm();
+        let pool = setup(&mut svm, 5_000);
+        let (holder, holder_ata) = pool.deposit(&mut svm, 10 * LAMPORTS_PER_SOL);
+        let (other, _) = pool.deposit(&mut svm, 10 * LAMPORTS_PER_SOL);
+        let enrolled = get_token_balance(&svm, &holder_ata);
+
+        assert!(send(
+            &mut svm,
+            build_enroll_payout_ix(&holder.pubkey(), &pool.mint, &pool.config_pda, enrolled),
+            &holder,
+        ));
+        assert_eq!(get_token_balance(&svm, &payout_vault(&pool.mint)), enrolled);
+
+        assert!(pool.crank(&mut svm, &holder), "The first crank checkpoints");
+        assert!(
+            !pool.crank(&mut svm, &holder),
+            "The crank runs once per epoch"
+        );
+
+        let lamports_before = pool.pool_lamports(&svm);
+        let supply_before = get_mint_supply(&svm, &pool.mint);
+        add_stake_rewards(&mut svm, &pool.stake_account_main, LAMPORTS_PER_SOL);
+        warp_epoch(&mut svm, 1);
+
+        let enroll_other = |svm: &mut LiteSVM| {
+            send(
+                svm,
+                build_enroll_payout_ix(&other.pubkey(), &pool.mint, &pool.config_pda, 1_000),
+                &other,
+            )
+        };
+        assert!(
+            !enroll_other(&mut svm),
+            "Enrolling waits for this epoch's crank"
+        );
+
+        let expected = reward_payout_lamports(
+            enrolled,
+            lamports_before,
+            supply_before,
+            pool.pool_lamports(&svm),
+            supply_before,
+            5_000,
+        );
+        assert!(expected > 0);
+        let rate_before = pool.pool_lamports(&svm) as u128 * 1_000_000_000 / supply_before as u128;
+
+        assert!(pool.crank(&mut svm, &holder));
+
+        let supply_after = get_mint_supply(&svm, &pool.mint);
+        assert_eq!(
+            lamports(&svm, &reward_payout_pda()),
+            svm.minimum_balance_for_rent_exemption(56) + expected,
+            "The payout moves from the reserve into the payout PDA"
+        );
+        assert!(get_token_balance(&svm, &payout_vault(&pool.mint)) < enrolled);
+        assert!(
+            pool.pool_lamports(&svm) as u128 * 1_000_000_000 / supply_after as u128 >= rate_before,
+            "Holders outside the vault keep their rate"
+        );
+
+        assert!(enroll_other(&mut svm));
+
+        let payout_before = lamports(&svm, &reward_payout_pda());
+        assert!(send(
+            &mut svm,
+            build_claim_payout_ix(&holder.pubkey()),
+            &holder
+        ));
+        let claimed = payout_before - lamports(&svm, &reward_payout_pda());
+        assert!(
+            claimed + 1 >= expected && claimed <= expected,
+            "Shares enrolled after the crank take nothing of its payout"
+        );
+        assert!(
+            !send(&mut svm, build_claim_payout_ix(&holder.pubkey()), &holder),
+            "Nothing is left to claim"
+        );
+    }
+
+    #[test]
+    fn test_withdraw_returns_the_vault_share_and_keeps_the_claim() {
+        let mut svm = setup_svm();
+        let pool = setup(&mut svm, 10_000);
+        let (holder, holder_ata) = pool.deposit(&mut svm, 10 * LAMPORTS_PER_SOL);
+        let enrolled = get_token_balance(&svm, &holder_ata);
+
+        assert!(send(
+            &mut svm,
+            build_enroll_payout_ix(&holder.pubkey(), &pool.mint, &pool.config_pda, enrolled),
+            &holder,
+        ));
+        assert!(pool.crank(&mut svm, &holder));
+
+        add_stake_rewards(&mut svm, &pool.stake_account_main, LAMPORTS_PER_SOL);
+        warp_epoch(&mut svm, 1);
+        assert!(pool.crank(&mut svm, &holder));
+        let vault_balance = get_token_balance(&svm, &payout_vault(&pool.mint));
+
+        assert!(
+            !send(
+                &mut svm,
+                build_withdraw_payout_ix(
+                    &holder.pubkey(),
+                    &pool.mint,
+                    &pool.config_pda,
+                    enrolled + 1
+                ),
+                &holder,
+            ),
+            "A position cannot redeem more shares than it holds"
+        );
+        assert!(send(
+            &mut svm,
+            build_withdraw_payout_ix(&holder.pubkey(), &pool.mint, &pool.config_pda, enrolled),
+            &holder,
+        ));
+        assert_eq!(get_token_balance(&svm, &holder_ata), vault_balance);
+        assert_eq!(get_token_balance(&svm, &payout_vault(&pool.mint)), 0);
+
+        assert!(
+            send(&mut svm, build_claim_payout_ix(&holder.pubkey()), &holder),
+            "SOL paid before the withdrawal stays claimable"
+        );
+
+        let outsider = Keypair::new();
+        svm.airdrop(&outsider.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        assert!(
+            !send(
+                &mut svm,
+                build_set_reward_payout_ix(&outsider.pubkey(), &pool.config_pda, &pool.mint, 0),
+                &outsider,
+            ),
+            "Only the admin sets the payout share"
+        );
+    }
+}
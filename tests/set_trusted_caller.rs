@@ -0,0 +1,84 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+    use solana_sdk::transaction::Transaction;
+
+    use crate::test_helpers::test_helpers::{
+        build_set_trusted_caller_ix, print_transaction_logs, run_initialize, setup_svm,
+        trusted_callers_pda,
+    };
+
+    fn trusted_callers(svm: &litesvm::LiteSVM) -> Vec<Pubkey> {
+        let data = svm.get_account(&trusted_callers_pda()).unwrap().data;
+        let len = u64::from_le_bytes(data[2..10].try_into().unwrap()) as usize;
+        (0..len)
+            .map(|index| {
+                let offset = 10 + index * 32;
+                Pubkey::new_from_array(data[offset..offset + 32].try_into().unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_set_trusted_caller_add_and_remove() {
+        let mut svm = setup_svm();
+        let (initializer, _, _, config_pda, _, _, _) = run_initialize(&mut svm);
+
+        let program = Pubkey::new_unique();
+
+        let ix = build_set_trusted_caller_ix(&initializer.pubkey(), &config_pda, &program, true);
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Admin should be able to approve a caller");
+        assert_eq!(trusted_callers(&svm), vec![program]);
+
+        svm.expire_blockhash();
+
+        let ix = build_set_trusted_caller_ix(&initializer.pubkey(), &config_pda, &program, false);
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Admin should be able to remove a caller");
+        assert!(trusted_callers(&svm).is_empty());
+    }
+
+    #[test]
+    fn test_set_trusted_caller_not_admin() {
+        let mut svm = setup_svm();
+        let (_, _, _, config_pda, _, _, _) = run_initialize(&mut svm);
+
+        let impostor = Keypair::new();
+        svm.airdrop(&impostor.pubkey(), 1_000_000_000).unwrap();
+
+        let ix = build_set_trusted_caller_ix(
+            &impostor.pubkey(),
+            &config_pda,
+            &Pubkey::new_unique(),
+            true,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&impostor.pubkey()),
+            &[&impostor],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_err(), "Only the admin may approve callers");
+    }
+}
@@ -0,0 +1,126 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        instruction::Instruction,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_liquid_unstake_ix, build_simulate_params_ix, decode_simulate_params,
+        liquidity_pool_pda, send, setup_pool, setup_svm, warp_epoch, Pool, PoolSetup,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    fn simulate(svm: &mut LiteSVM, ix: Instruction) -> Option<Vec<u8>> {
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        );
+        Some(svm.simulate_transaction(tx).ok()?.meta.return_data.data)
+    }
+
+    fn sol_reserves(svm: &LiteSVM) -> u64 {
+        let data = svm.get_account(&liquidity_pool_pda()).unwrap().data;
+        u64::from_le_bytes(data[66..74].try_into().unwrap())
+    }
+
+    /// A pool with 10 SOL of instant-unstake liquidity and a depositor
+    /// holding the LST of a 5 SOL deposit, in a fresh epoch.
+    fn setup(svm: &mut LiteSVM) -> Pool {
+        let pool = setup_pool(
+            svm,
+            PoolSetup {
+                activate: true,
+                liquidity: 10 * LAMPORTS_PER_SOL,
+                deposit: 5 * LAMPORTS_PER_SOL,
+                ..PoolSetup::default()
+            },
+        );
+        warp_epoch(svm, 1);
+        pool
+    }
+
+    #[test]
+    fn test_simulate_params_projects_current_and_proposed() {
+        let mut svm = setup_svm();
+        let pool = setup(&mut svm);
+        let unstake_lst = 3 * LAMPORTS_PER_SOL / 2;
+
+        let ix = build_simulate_params_ix(
+            &pool.config_pda,
+            &pool.mint,
+            &pool.stake_account_main,
+            &pool.stake_account_reserve,
+            100,
+            1_000,
+            20 * LAMPORTS_PER_SOL,
+            LAMPORTS_PER_SOL,
+            2 * LAMPORTS_PER_SOL,
+            unstake_lst,
+        );
+        let data = simulate(&mut svm, ix).expect("SimulateParams should succeed");
+        let (current, proposed) = decode_simulate_params(&data);
+
+        assert_eq!(current[0], 2 * LAMPORTS_PER_SOL, "No cap is set today");
+        assert_eq!(proposed[0], LAMPORTS_PER_SOL, "The proposed cap binds");
+        assert!(proposed[1] < current[1]);
+
+        assert!(proposed[2] > current[2], "The proposed curve is steeper");
+        assert_eq!(
+            current[3] + current[4],
+            proposed[3] + proposed[4],
+            "Both scenarios value the LST at the same rate"
+        );
+        assert!(proposed[3] < current[3]);
+
+        let reserves_before = sol_reserves(&svm);
+        let ix = build_liquid_unstake_ix(
+            &pool.depositor.pubkey(),
+            &pool.depositor_ata,
+            &pool.config_pda,
+            &pool.mint,
+            &pool.stake_account_main,
+            &pool.stake_account_reserve,
+            unstake_lst,
+            0,
+        );
+        assert!(send(&mut svm, ix, &pool.depositor));
+        assert_eq!(
+            reserves_before - sol_reserves(&svm),
+            current[3],
+            "The current projection should match what LiquidUnstake pays"
+        );
+    }
+
+    #[test]
+    fn test_simulate_params_rejects_invalid_fee_params() {
+        let mut svm = setup_svm();
+        let pool = setup(&mut svm);
+
+        let ix = build_simulate_params_ix(
+            &pool.config_pda,
+            &pool.mint,
+            &pool.stake_account_main,
+            &pool.stake_account_reserve,
+            500,
+            100,
+            20 * LAMPORTS_PER_SOL,
+            0,
+            LAMPORTS_PER_SOL,
+            LAMPORTS_PER_SOL,
+        );
+        assert!(
+            simulate(&mut svm, ix).is_none(),
+            "A minimum fee above the maximum should be rejected"
+        );
+    }
+}
@@ -0,0 +1,222 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_liquid_staking::{
+        events::{EVENT_SPLIT_HANDOFF, HANDOFF_EXECUTED, HANDOFF_PROPOSED},
+        ids::STAKE_PROGRAM_ID,
+        state::SPLIT_HANDOFF_DELAY_SECONDS,
+    };
+    use solana_sdk::{
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_cancel_split_handoff_ix, build_execute_split_handoff_ix,
+        build_propose_split_handoff_ix, build_withdraw_ix, decode_events, print_transaction_logs,
+        run_crank_initialize_reserve, run_crank_merge_reserve, run_crank_split, run_deposit,
+        run_initialize, setup_svm, split_handoff_pda, warp_time,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+    const NOW: i64 = 1_000_000;
+    const NONCE: u64 = 7;
+
+    fn send(svm: &mut LiteSVM, ix: Instruction, signers: &[&Keypair]) -> Option<Vec<String>> {
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&signers[0].pubkey()),
+            signers,
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        svm.expire_blockhash();
+        result.ok().map(|meta| meta.logs)
+    }
+
+    /// Returns (admin, config PDA, split owner, split account) for a pool
+    /// with one split pending withdrawal.
+    fn setup(svm: &mut LiteSVM) -> (Keypair, Pubkey, Keypair, Pubkey) {
+        let (admin, token_mint, _, config_pda, main, reserve, vote_pubkey) = run_initialize(svm);
+        let (owner, owner_ata) = run_deposit(
+            svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &main,
+            &reserve,
+            2 * LAMPORTS_PER_SOL,
+        );
+        run_crank_initialize_reserve(svm, &admin, &config_pda, &reserve, &vote_pubkey);
+        run_crank_merge_reserve(svm, &admin, &config_pda, &main, &reserve);
+        let split_account = run_crank_split(
+            svm,
+            &owner,
+            &owner_ata,
+            &config_pda,
+            &main,
+            &reserve,
+            &token_mint.pubkey(),
+            3 * LAMPORTS_PER_SOL / 2,
+            NONCE,
+        );
+        warp_time(svm, NOW);
+
+        (admin, config_pda, owner, split_account)
+    }
+
+    fn withdraw(
+        svm: &mut LiteSVM,
+        withdrawer: &Keypair,
+        config_pda: &Pubkey,
+        split: &Pubkey,
+    ) -> bool {
+        let ix = build_withdraw_ix(
+            split,
+            &withdrawer.pubkey(),
+            config_pda,
+            &Pubkey::from(STAKE_PROGRAM_ID),
+            NONCE,
+            true,
+        );
+        send(svm, ix, &[withdrawer]).is_some()
+    }
+
+    #[test]
+    fn test_handoff_lets_the_new_owner_withdraw_after_the_timelock() {
+        let mut svm = setup_svm();
+        let (admin, config_pda, owner, split_account) = setup(&mut svm);
+        let new_owner = Keypair::new();
+        svm.airdrop(&new_owner.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+        let stranger = Keypair::new();
+        svm.airdrop(&stranger.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        assert!(
+            send(
+                &mut svm,
+                build_propose_split_handoff_ix(
+                    &stranger.pubkey(),
+                    &new_owner.pubkey(),
+                    &config_pda,
+                    &split_account,
+                ),
+                &[&stranger, &new_owner],
+            )
+            .is_none(),
+            "Only the admin can propose a handoff"
+        );
+
+        let logs = send(
+            &mut svm,
+            build_propose_split_handoff_ix(
+                &admin.pubkey(),
+                &new_owner.pubkey(),
+                &config_pda,
+                &split_account,
+            ),
+            &[&admin, &new_owner],
+        )
+        .expect("Admin and new owner should propose the handoff");
+        let handoff_events: Vec<_> = decode_events(&logs)
+            .into_iter()
+            .filter(|(kind, _, _)| *kind == EVENT_SPLIT_HANDOFF)
+            .collect();
+        assert_eq!(handoff_events.len(), 1);
+        let payload = &handoff_events[0].2;
+        assert_eq!(&payload[0..32], split_account.as_ref());
+        assert_eq!(&payload[32..64], new_owner.pubkey().as_ref());
+        assert_eq!(payload[64], HANDOFF_PROPOSED);
+
+        let execute =
+            build_execute_split_handoff_ix(&new_owner.pubkey(), &admin.pubkey(), &split_account);
+        warp_time(&mut svm, NOW + SPLIT_HANDOFF_DELAY_SECONDS - 1);
+        assert!(
+            send(&mut svm, execute.clone(), &[&new_owner]).is_none(),
+            "The handoff is timelocked"
+        );
+
+        warp_time(&mut svm, NOW + SPLIT_HANDOFF_DELAY_SECONDS);
+        let logs = send(&mut svm, execute, &[&new_owner]).expect("Handoff should execute");
+        let (kind, _, payload) = decode_events(&logs).pop().unwrap();
+        assert_eq!(kind, EVENT_SPLIT_HANDOFF);
+        assert_eq!(payload[64], HANDOFF_EXECUTED);
+        assert!(svm
+            .get_account(&split_handoff_pda(&split_account))
+            .is_none_or(|account| account.lamports == 0));
+
+        assert!(
+            !withdraw(&mut svm, &owner, &config_pda, &split_account),
+            "The previous owner can no longer withdraw"
+        );
+        let balance = svm.get_balance(&new_owner.pubkey()).unwrap();
+        assert!(withdraw(&mut svm, &new_owner, &config_pda, &split_account));
+        assert!(svm.get_balance(&new_owner.pubkey()).unwrap() > balance + LAMPORTS_PER_SOL);
+    }
+
+    #[test]
+    fn test_owner_can_cancel_a_handoff() {
+        let mut svm = setup_svm();
+        let (admin, config_pda, owner, split_account) = setup(&mut svm);
+        let new_owner = Keypair::new();
+        svm.airdrop(&new_owner.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+        assert!(send(
+            &mut svm,
+            build_propose_split_handoff_ix(
+                &admin.pubkey(),
+                &new_owner.pubkey(),
+                &config_pda,
+                &split_account,
+            ),
+            &[&admin, &new_owner],
+        )
+        .is_some());
+
+        assert!(
+            send(
+                &mut svm,
+                build_cancel_split_handoff_ix(
+                    &new_owner.pubkey(),
+                    &admin.pubkey(),
+                    &config_pda,
+                    &split_account,
+                ),
+                &[&new_owner],
+            )
+            .is_none(),
+            "Only the owner or the admin can cancel"
+        );
+
+        assert!(send(
+            &mut svm,
+            build_cancel_split_handoff_ix(
+                &owner.pubkey(),
+                &admin.pubkey(),
+                &config_pda,
+                &split_account,
+            ),
+            &[&owner],
+        )
+        .is_some());
+
+        warp_time(&mut svm, NOW + SPLIT_HANDOFF_DELAY_SECONDS);
+        assert!(
+            send(
+                &mut svm,
+                build_execute_split_handoff_ix(
+                    &new_owner.pubkey(),
+                    &admin.pubkey(),
+                    &split_account
+                ),
+                &[&new_owner],
+            )
+            .is_none(),
+            "A cancelled handoff cannot execute"
+        );
+        assert!(withdraw(&mut svm, &owner, &config_pda, &split_account));
+    }
+}
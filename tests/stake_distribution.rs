@@ -0,0 +1,166 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+
+    use crate::test_helpers::test_helpers::{
+        build_join_validator_set_ix, build_set_validator_status_ix, create_vote_account,
+        get_stake_distribution, run_deposit, run_initialize, send, setup_svm,
+    };
+
+    #[test]
+    fn test_distribution_reports_config_validator_and_reserve() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = run_initialize(&mut svm);
+
+        let (row_count, page_count, undelegated, rows) = get_stake_distribution(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            0,
+        )
+        .expect("Report should succeed");
+        assert_eq!((row_count, page_count), (1, 1));
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].vote_account, vote_pubkey);
+        let delegated = rows[0].active + rows[0].activating;
+        assert!(delegated > 0, "Main stake should be delegated");
+        assert_eq!(rows[0].deactivating, 0);
+        assert_eq!(rows[0].target, delegated + undelegated);
+
+        run_deposit(
+            &mut svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            5_000_000_000,
+        );
+
+        let (_, _, undelegated_after, rows) = get_stake_distribution(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            0,
+        )
+        .expect("Report should succeed");
+        assert_eq!(
+            undelegated_after,
+            undelegated + 5_000_000_000,
+            "Undelegated reserve SOL should be reported"
+        );
+        assert_eq!(rows[0].target, delegated + undelegated_after);
+
+        assert!(
+            get_stake_distribution(
+                &mut svm,
+                &initializer,
+                &config_pda,
+                &stake_account_main,
+                &stake_account_reserve,
+                1,
+            )
+            .is_none(),
+            "Pages past the last row should be rejected"
+        );
+    }
+
+    #[test]
+    fn test_distribution_pages_through_validator_set() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            _token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = run_initialize(&mut svm);
+
+        let mut listed = Vec::new();
+        for _ in 0..16 {
+            let operator = Keypair::new();
+            svm.airdrop(&operator.pubkey(), 20_000_000_000).unwrap();
+            let listed_vote = create_vote_account(&mut svm, &operator.pubkey());
+
+            let ix = build_join_validator_set_ix(&operator.pubkey(), &listed_vote, 10_000_000_000);
+            assert!(send(&mut svm, ix, &operator), "Join should succeed");
+            let ix = build_set_validator_status_ix(
+                &initializer.pubkey(),
+                &config_pda,
+                &listed_vote,
+                true,
+            );
+            assert!(
+                send(&mut svm, ix, &initializer),
+                "Activation should succeed"
+            );
+            listed.push(listed_vote);
+        }
+
+        let (row_count, page_count, undelegated, first_page) = get_stake_distribution(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            0,
+        )
+        .expect("First page should succeed");
+        assert_eq!((row_count, page_count), (17, 2));
+        assert_eq!(first_page.len(), 15);
+
+        let (_, _, _, second_page) = get_stake_distribution(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            1,
+        )
+        .expect("Second page should succeed");
+        assert_eq!(second_page.len(), 2);
+
+        let rows: Vec<_> = first_page.into_iter().chain(second_page).collect();
+        assert_eq!(rows[0].vote_account, vote_pubkey);
+        assert_eq!(
+            rows[1..]
+                .iter()
+                .map(|row| row.vote_account)
+                .collect::<Vec<_>>(),
+            listed
+        );
+
+        // The config validator is not listed, so the even split goes to the
+        // listed validators while its existing stake is still reported.
+        let pool_lamports = rows[0].active + rows[0].activating + undelegated;
+        assert_eq!(rows[0].target, 0);
+        assert!(rows[0].active + rows[0].activating > 0);
+        assert_eq!(
+            rows[1..].iter().map(|row| row.target).sum::<u64>(),
+            pool_lamports
+        );
+        let even_share = pool_lamports / 16;
+        assert!(rows[1..]
+            .iter()
+            .all(|row| row.target == even_share || row.target == even_share + 1));
+        assert!(rows[1..]
+            .iter()
+            .all(|row| row.active + row.activating + row.deactivating == 0));
+    }
+}
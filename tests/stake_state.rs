@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use solana_liquid_staking::stake_state::{StakeState, STAKE_STATE_STAKE};
+
+    const RENT: u64 = 2_282_880;
+
+    /// A delegated stake account's data with `stake` delegated, activated in
+    /// `activation_epoch` and deactivated in `deactivation_epoch`.
+    fn delegated(stake: u64, activation_epoch: u64, deactivation_epoch: u64) -> Vec<u8> {
+        let mut data = vec![0u8; 200];
+        data[0..4].copy_from_slice(&STAKE_STATE_STAKE.to_le_bytes());
+        data[4..12].copy_from_slice(&RENT.to_le_bytes());
+        data[12..44].copy_from_slice(&[1; 32]);
+        data[44..76].copy_from_slice(&[2; 32]);
+        data[124..156].copy_from_slice(&[3; 32]);
+        data[156..164].copy_from_slice(&stake.to_le_bytes());
+        data[164..172].copy_from_slice(&activation_epoch.to_le_bytes());
+        data[172..180].copy_from_slice(&deactivation_epoch.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_parse_reads_meta_and_delegation() {
+        let state = StakeState::parse(&delegated(5_000, 3, u64::MAX)).unwrap();
+
+        let meta = state.meta().unwrap();
+        assert_eq!(meta.rent_exempt_reserve, RENT);
+        assert_eq!((meta.staker, meta.withdrawer), ([1; 32], [2; 32]));
+
+        let delegation = state.delegation().unwrap();
+        assert_eq!(delegation.voter, [3; 32]);
+        assert_eq!(delegation.stake, 5_000);
+        assert!(state.is_activating(3));
+        assert!(!state.is_activating(4));
+
+        assert_eq!(StakeState::parse(&[]), Ok(StakeState::Closed));
+        assert_eq!(StakeState::parse(&[0; 200]), Ok(StakeState::Uninitialized));
+        assert!(StakeState::parse(&delegated(5_000, 3, u64::MAX)[..150]).is_err());
+    }
+
+    #[test]
+    fn test_rent_exempt_reserve_whatever_the_warmup_state() {
+        for deactivation_epoch in [u64::MAX, 7] {
+            let state = StakeState::parse(&delegated(5_000, 3, deactivation_epoch)).unwrap();
+            assert_eq!(state.rent_exempt_reserve(0), RENT);
+        }
+
+        // Uninitialized accounts keep the rent minimum; closed ones nothing.
+        assert_eq!(StakeState::Uninitialized.rent_exempt_reserve(RENT), RENT);
+        assert_eq!(StakeState::Closed.rent_exempt_reserve(RENT), 0);
+    }
+}
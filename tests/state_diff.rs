@@ -0,0 +1,189 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        instruction::Instruction,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+
+    use crate::test_helpers::snapshot::{assert_diff, pool_accounts, ExpectedChange, Snapshot};
+    use crate::test_helpers::test_helpers::{
+        build_deposit_ix, build_set_buyback_policy_ix, changelog_pda, create_and_fund_ata,
+        pool_state_pda, run_deposit, run_initialize, setup_svm,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    fn send(svm: &mut LiteSVM, ix: Instruction, payer: &Keypair, signers: &[&Keypair]) -> bool {
+        let mut all_signers = vec![payer];
+        all_signers.extend_from_slice(signers);
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &all_signers,
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        svm.expire_blockhash();
+        result.is_ok()
+    }
+
+    #[test]
+    fn test_deposit_touches_only_reserve_mint_and_depositor() {
+        let mut svm = setup_svm();
+        let (initializer, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+        run_deposit(
+            &mut svm,
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+            LAMPORTS_PER_SOL,
+        );
+
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 5 * LAMPORTS_PER_SOL)
+            .unwrap();
+        let depositor_ata = create_and_fund_ata(&mut svm, &depositor.pubkey(), &mint, 0);
+
+        let mut tracked = pool_accounts(
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+        tracked.extend([depositor.pubkey(), depositor_ata]);
+
+        let before = Snapshot::take(&svm, &tracked);
+        assert!(send(
+            &mut svm,
+            build_deposit_ix(
+                &config_pda,
+                &depositor.pubkey(),
+                &depositor_ata,
+                &mint,
+                &stake_account_main,
+                &stake_account_reserve,
+                2 * LAMPORTS_PER_SOL,
+                true,
+            ),
+            &initializer,
+            &[&depositor],
+        ));
+        let after = Snapshot::take(&svm, &tracked);
+
+        assert_diff(
+            &before.diff(&after),
+            &[
+                ExpectedChange {
+                    pubkey: depositor.pubkey(),
+                    lamport_delta: -2 * LAMPORTS_PER_SOL as i128,
+                    fields: &[],
+                },
+                ExpectedChange {
+                    pubkey: stake_account_reserve,
+                    lamport_delta: 2 * LAMPORTS_PER_SOL as i128,
+                    fields: &[],
+                },
+                ExpectedChange {
+                    pubkey: mint,
+                    lamport_delta: 0,
+                    fields: &["supply"],
+                },
+                ExpectedChange {
+                    pubkey: depositor_ata,
+                    lamport_delta: 0,
+                    fields: &["amount"],
+                },
+                ExpectedChange {
+                    pubkey: pool_state_pda(),
+                    lamport_delta: 0,
+                    fields: &[
+                        "event_sequence",
+                        "inflow_lamports",
+                        "recent_events",
+                        "principal_lamports",
+                        "principal_lst_supply",
+                        "lst_issued",
+                        "total_managed_lamports",
+                    ],
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_admin_setter_writes_a_single_config_field_the_changelog_and_its_event() {
+        let mut svm = setup_svm();
+        let (initializer, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+        let tracked = pool_accounts(
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+
+        let before = Snapshot::take(&svm, &tracked);
+        assert!(send(
+            &mut svm,
+            build_set_buyback_policy_ix(&initializer.pubkey(), &config_pda, 1),
+            &initializer,
+            &[],
+        ));
+        let after = Snapshot::take(&svm, &tracked);
+
+        assert_diff(
+            &before.diff(&after),
+            &[
+                ExpectedChange {
+                    pubkey: config_pda,
+                    lamport_delta: 0,
+                    fields: &["buyback_policy"],
+                },
+                ExpectedChange {
+                    pubkey: changelog_pda(),
+                    lamport_delta: 0,
+                    fields: &["head", "len", "changes"],
+                },
+                ExpectedChange {
+                    pubkey: pool_state_pda(),
+                    lamport_delta: 0,
+                    fields: &["event_sequence", "recent_events"],
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_failed_instruction_has_no_side_effects() {
+        let mut svm = setup_svm();
+        let (_, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+        let tracked = pool_accounts(
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+
+        let outsider = Keypair::new();
+        svm.airdrop(&outsider.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+        let before = Snapshot::take(&svm, &tracked);
+        assert!(!send(
+            &mut svm,
+            build_set_buyback_policy_ix(&outsider.pubkey(), &config_pda, 1),
+            &outsider,
+            &[],
+        ));
+        let after = Snapshot::take(&svm, &tracked);
+
+        assert_diff(&before.diff(&after), &[]);
+    }
+}
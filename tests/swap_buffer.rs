@@ -0,0 +1,184 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::instruction::Instruction;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+
+    use crate::test_helpers::test_helpers::{
+        build_liquid_unstake_ix, build_set_unstake_fee_params_ix, build_swap_buffer_ix,
+        create_and_fund_ata, get_token_balance, liquidity_pool_pda, send, setup_pool, setup_svm,
+        Pool, PoolSetup,
+    };
+
+    const SOL_FOR_LST: u8 = 0;
+    const LST_FOR_SOL: u8 = 1;
+
+    impl Pool {
+        fn swap_ix(
+            &self,
+            trader: &Keypair,
+            trader_ata: &Pubkey,
+            direction: u8,
+            amount_in: u64,
+            min_amount_out: u64,
+        ) -> Instruction {
+            build_swap_buffer_ix(
+                &trader.pubkey(),
+                trader_ata,
+                &self.config_pda,
+                &self.mint,
+                &self.stake_account_main,
+                &self.stake_account_reserve,
+                direction,
+                amount_in,
+                min_amount_out,
+            )
+        }
+    }
+
+    fn sol_reserves(svm: &litesvm::LiteSVM) -> u64 {
+        let data = svm.get_account(&liquidity_pool_pda()).unwrap().data;
+        u64::from_le_bytes(data[66..74].try_into().unwrap())
+    }
+
+    /// Sets up a 10 SOL buffer with a 10 SOL target and instantly unstakes
+    /// 3 LST, leaving the buffer about 3 SOL short and the vault holding LST.
+    fn setup_drained_buffer(svm: &mut litesvm::LiteSVM) -> Pool {
+        let pool = setup_pool(
+            svm,
+            PoolSetup {
+                activate: true,
+                liquidity: 10_000_000_000,
+                deposit: 5_000_000_000,
+                ..PoolSetup::default()
+            },
+        );
+
+        let ix = build_set_unstake_fee_params_ix(
+            &pool.admin.pubkey(),
+            &pool.config_pda,
+            30,
+            300,
+            10_000_000_000,
+        );
+        assert!(send(svm, ix, &pool.admin), "Admin should set fee params");
+
+        let ix = build_liquid_unstake_ix(
+            &pool.depositor.pubkey(),
+            &pool.depositor_ata,
+            &pool.config_pda,
+            &pool.mint,
+            &pool.stake_account_main,
+            &pool.stake_account_reserve,
+            3_000_000_000,
+            0,
+        );
+        assert!(
+            send(svm, ix, &pool.depositor),
+            "LiquidUnstake should succeed"
+        );
+
+        pool
+    }
+
+    #[test]
+    fn test_swap_sol_for_lst_refills_buffer() {
+        let mut svm = setup_svm();
+        let pool = setup_drained_buffer(&mut svm);
+
+        let trader = Keypair::new();
+        svm.airdrop(&trader.pubkey(), 10_000_000_000).unwrap();
+        let trader_ata = create_and_fund_ata(&mut svm, &trader.pubkey(), &pool.mint, 0);
+
+        let sol_reserves_before = sol_reserves(&svm);
+        let vault_before = get_token_balance(&svm, &pool.lst_vault);
+
+        let ix = pool.swap_ix(&trader, &trader_ata, SOL_FOR_LST, 1_000_000_000, 1);
+        assert!(
+            send(&mut svm, ix, &trader),
+            "Swap into a short buffer should succeed"
+        );
+
+        let lst_out = get_token_balance(&svm, &trader_ata);
+        assert!(lst_out > 0);
+        assert_eq!(sol_reserves(&svm), sol_reserves_before + 1_000_000_000);
+        assert_eq!(
+            get_token_balance(&svm, &pool.lst_vault),
+            vault_before - lst_out
+        );
+
+        let ix = pool.swap_ix(&trader, &trader_ata, SOL_FOR_LST, 5_000_000_000, 0);
+        assert!(
+            !send(&mut svm, ix, &trader),
+            "Swap beyond the buffer's deficit should be rejected"
+        );
+
+        let ix = pool.swap_ix(&trader, &trader_ata, LST_FOR_SOL, lst_out, 0);
+        assert!(
+            !send(&mut svm, ix, &trader),
+            "Draining a buffer below target should be rejected"
+        );
+    }
+
+    #[test]
+    fn test_swap_lst_for_sol_drains_excess() {
+        let mut svm = setup_svm();
+        let pool = setup_drained_buffer(&mut svm);
+
+        // Lowering the target leaves the buffer with excess SOL.
+        let ix = build_set_unstake_fee_params_ix(
+            &pool.admin.pubkey(),
+            &pool.config_pda,
+            30,
+            300,
+            5_000_000_000,
+        );
+        assert!(send(&mut svm, ix, &pool.admin));
+
+        let (trader, trader_ata) = pool.deposit(&mut svm, 5_000_000_000);
+
+        let sol_reserves_before = sol_reserves(&svm);
+        let excess = sol_reserves_before - 5_000_000_000;
+        let vault_before = get_token_balance(&svm, &pool.lst_vault);
+
+        let ix = pool.swap_ix(
+            &trader,
+            &trader_ata,
+            LST_FOR_SOL,
+            1_000_000_000,
+            2_000_000_000,
+        );
+        assert!(
+            !send(&mut svm, ix, &trader),
+            "Output below min_amount_out should be rejected"
+        );
+
+        let lamports_before = svm.get_balance(&trader.pubkey()).unwrap();
+
+        let ix = pool.swap_ix(&trader, &trader_ata, LST_FOR_SOL, 1_000_000_000, 1);
+        assert!(
+            send(&mut svm, ix, &trader),
+            "Swap out of excess should succeed"
+        );
+
+        let lamports_out = sol_reserves_before - sol_reserves(&svm);
+        assert!(lamports_out > 0);
+        assert_eq!(
+            svm.get_balance(&trader.pubkey()).unwrap(),
+            lamports_before + lamports_out - 5_000
+        );
+        assert_eq!(
+            get_token_balance(&svm, &pool.lst_vault),
+            vault_before + 1_000_000_000
+        );
+
+        let ix = pool.swap_ix(&trader, &trader_ata, LST_FOR_SOL, excess, 0);
+        assert!(
+            !send(&mut svm, ix, &trader),
+            "Swap beyond the buffer's excess should be rejected"
+        );
+    }
+}
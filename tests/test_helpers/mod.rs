@@ -1 +1,5 @@
+pub mod snapshot;
 pub mod test_helpers;
+
+#[cfg(feature = "program-test")]
+pub mod program_test;
@@ -0,0 +1,264 @@
+//! `solana-program-test` harness, built with the `program-test` feature.
+//!
+//! LiteSVM only moves the clock when a test warps, so stake never really
+//! warms up, no rewards are paid and the stake history sysvar stays empty.
+//! This harness runs the program inside a full bank instead. Warping to an
+//! epoch goes through the epoch boundary, which updates stake history and
+//! pays inflation rewards to stake delegated to vote accounts that earned
+//! credits.
+
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    clock::Clock,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program,
+    transaction::Transaction,
+};
+use solana_vote_interface::{
+    instruction::{create_account_with_config, CreateVoteAccountConfig},
+    state::VoteInit,
+};
+use spl_token::solana_program::program_option::COption;
+use spl_token::solana_program::program_pack::Pack;
+use spl_token::state::{Account as TokenAccount, Mint};
+
+use super::test_helpers::{
+    build_get_balance_sheet_ix, build_initialize_ix, decode_balance_sheet, PROGRAM_ID,
+};
+
+/// A pool initialized inside a `ProgramTestContext`.
+pub struct TestPool {
+    pub context: ProgramTestContext,
+    pub initializer: Keypair,
+    pub token_mint: Pubkey,
+    pub config_pda: Pubkey,
+    pub stake_account_main: Pubkey,
+    pub stake_account_reserve: Pubkey,
+    pub vote_pubkey: Pubkey,
+}
+
+/// Starts a bank with the program loaded from `target/deploy`, creates a real
+/// vote account and runs Initialize against it.
+pub async fn start_pool() -> TestPool {
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
+
+    let mut program_test = ProgramTest::new("solana_liquid_staking", PROGRAM_ID, None);
+    program_test.prefer_bpf(true);
+
+    let initializer = Keypair::new();
+    program_test.add_account(
+        initializer.pubkey(),
+        Account {
+            lamports: 100_000_000_000,
+            owner: system_program::ID,
+            ..Account::default()
+        },
+    );
+
+    let config_pda = Pubkey::find_program_address(&[b"config"], &PROGRAM_ID).0;
+    let token_mint = Keypair::new();
+    let mut mint_data = vec![0u8; Mint::LEN];
+    Mint::pack(
+        Mint {
+            mint_authority: COption::Some(config_pda),
+            supply: 0,
+            decimals: 9,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        },
+        &mut mint_data,
+    )
+    .unwrap();
+    program_test.add_account(
+        token_mint.pubkey(),
+        Account {
+            lamports: 10_000_000,
+            data: mint_data,
+            owner: spl_token::ID,
+            ..Account::default()
+        },
+    );
+
+    let mut context = program_test.start_with_context().await;
+
+    let initializer_ata =
+        set_token_account(&mut context, &initializer.pubkey(), &token_mint.pubkey(), 0);
+    let vote_pubkey = create_vote_account(&mut context).await;
+
+    let stake_account_main = Pubkey::find_program_address(&[b"stake_main"], &PROGRAM_ID).0;
+    let stake_account_reserve = Pubkey::find_program_address(&[b"stake_reserve"], &PROGRAM_ID).0;
+
+    let ix = build_initialize_ix(
+        &initializer.pubkey(),
+        &initializer_ata,
+        &config_pda,
+        &stake_account_main,
+        &stake_account_reserve,
+        &token_mint.pubkey(),
+        true,
+        &vote_pubkey,
+        &system_program::ID,
+        &Pubkey::from(STAKE_PROGRAM_ID),
+        &spl_token::ID,
+        &spl_associated_token_account::ID,
+    );
+    send(&mut context, &[ix], &[&initializer, &token_mint])
+        .await
+        .expect("Initialize transaction should succeed");
+
+    TestPool {
+        context,
+        initializer,
+        token_mint: token_mint.pubkey(),
+        config_pda,
+        stake_account_main,
+        stake_account_reserve,
+        vote_pubkey,
+    }
+}
+
+/// Creates a vote account through the vote program, so it can earn credits
+/// and rewards.
+pub async fn create_vote_account(context: &mut ProgramTestContext) -> Pubkey {
+    let node = Keypair::new();
+    let vote = Keypair::new();
+
+    let ixs = create_account_with_config(
+        &context.payer.pubkey(),
+        &vote.pubkey(),
+        &VoteInit {
+            node_pubkey: node.pubkey(),
+            authorized_voter: node.pubkey(),
+            authorized_withdrawer: node.pubkey(),
+            commission: 0,
+        },
+        10_000_000_000,
+        CreateVoteAccountConfig::default(),
+    );
+    send(context, &ixs, &[&vote, &node])
+        .await
+        .expect("Vote account creation should succeed");
+
+    vote.pubkey()
+}
+
+/// Writes an initialized token account at `owner`'s ATA for `mint`.
+pub fn set_token_account(
+    context: &mut ProgramTestContext,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> Pubkey {
+    let ata = spl_associated_token_account::get_associated_token_address(owner, mint);
+
+    let mut data = vec![0u8; TokenAccount::LEN];
+    TokenAccount::pack(
+        TokenAccount {
+            mint: *mint,
+            owner: *owner,
+            amount,
+            delegate: COption::None,
+            state: spl_token::state::AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        },
+        &mut data,
+    )
+    .unwrap();
+
+    context.set_account(
+        &ata,
+        &AccountSharedData::from(Account {
+            lamports: 10_000_000,
+            data,
+            owner: spl_token::ID,
+            ..Account::default()
+        }),
+    );
+    ata
+}
+
+/// Sends `instructions` paid for by the context payer. `signers` are the
+/// extra signers.
+pub async fn send(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let blockhash = context.get_new_latest_blockhash().await?;
+
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+/// Simulates `ix` and returns its return data, or None if it fails.
+pub async fn simulate_return_data(
+    context: &mut ProgramTestContext,
+    ix: Instruction,
+) -> Option<Vec<u8>> {
+    let blockhash = context.get_new_latest_blockhash().await.ok()?;
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+
+    let simulation = context.banks_client.simulate_transaction(tx).await.ok()?;
+    simulation.result?.ok()?;
+    Some(simulation.simulation_details?.return_data?.data)
+}
+
+/// Simulates GetBalanceSheet for the pool.
+pub async fn get_balance_sheet(pool: &mut TestPool) -> Option<[u64; 8]> {
+    let ix = build_get_balance_sheet_ix(
+        &pool.config_pda,
+        &pool.token_mint,
+        &pool.stake_account_main,
+        &pool.stake_account_reserve,
+    );
+    let data = simulate_return_data(&mut pool.context, ix).await?;
+    Some(decode_balance_sheet(&data))
+}
+
+pub async fn current_epoch(context: &mut ProgramTestContext) -> u64 {
+    context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .epoch
+}
+
+/// Credits `vote_pubkey` with votes for the current epoch, then crosses
+/// `epochs` epoch boundaries. Rewards for the credited epoch are paid at the
+/// first boundary, and stake history records every epoch crossed.
+pub async fn advance_epochs(context: &mut ProgramTestContext, vote_pubkey: &Pubkey, epochs: u64) {
+    for _ in 0..epochs {
+        context.increment_vote_account_credits(vote_pubkey, 1_000);
+        let next_epoch = current_epoch(context).await + 1;
+        context.warp_to_epoch(next_epoch).unwrap();
+    }
+    context.warp_forward_force_reward_interval_end().unwrap();
+}
+
+pub async fn get_lamports(context: &mut ProgramTestContext, pubkey: &Pubkey) -> u64 {
+    context
+        .banks_client
+        .get_account(*pubkey)
+        .await
+        .unwrap()
+        .map_or(0, |account| account.lamports)
+}
@@ -0,0 +1,337 @@
+//! Before/after account snapshots for asserting an instruction's exact side
+//! effects.
+//!
+//! `Snapshot::take` records a set of accounts, and `Snapshot::diff` compares
+//! two snapshots account by account: the lamport delta and the names of the
+//! fields whose bytes changed. Known layouts (the program's state accounts,
+//! SPL mints and token accounts, stake accounts) are decoded into field
+//! names. Other accounts report changed byte ranges such as `data[8..16]`.
+//!
+//! `assert_diff` fails unless exactly the expected accounts changed, so a
+//! regression test also catches writes it did not expect.
+
+use std::collections::BTreeMap;
+
+use litesvm::LiteSVM;
+use solana_liquid_staking::{
+    ids::STAKE_PROGRAM_ID,
+    state::{Changelog, Config, EpochTasks, PoolState},
+};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use spl_token::solana_program::program_pack::Pack;
+use spl_token::state::{Account as TokenAccount, Mint};
+
+use super::test_helpers::{
+    buffer_stake_pda, changelog_pda, epoch_tasks_pda, fee_vault_pda, liquidity_pool_pda,
+    pool_state_pda, trusted_callers_pda, validator_list_pda, PROGRAM_ID,
+};
+
+type Layout = &'static [(&'static str, usize, usize)];
+
+const CONFIG_FIELDS: Layout = &[
+    ("discriminator", 0, 1),
+    ("version", 1, 2),
+    ("admin", 2, 34),
+    ("lst_mint", 34, 66),
+    ("stake_account_main", 66, 98),
+    ("stake_account_reserve", 98, 130),
+    ("validator_vote_pubkey", 130, 162),
+    ("unstake_fee_min_bps", 162, 164),
+    ("unstake_fee_max_bps", 164, 166),
+    ("liquidity_target_lamports", 166, 174),
+    ("max_validator_stake_bps", 174, 176),
+    ("max_validator_stake_lamports", 176, 184),
+    ("delegation_strategy", 184, 185),
+    ("min_split_stake_lamports", 185, 193),
+    ("status", 193, 194),
+    ("authority_drift", 194, 195),
+    ("buyback_policy", 195, 196),
+    ("deposit_cap_lamports", 196, 204),
+    ("deposit_cap_mode", 204, 205),
+    ("bonus_bps", 205, 207),
+    ("bonus_cutoff_epoch", 207, 215),
+    ("wrapper_program", 215, 247),
+    ("disabled_instructions", 247, 279),
+    ("referral_fee_bps", 279, 281),
+    ("quote_signer", 281, 313),
+    ("quote_max_deviation_bps", 313, 315),
+    ("main_headroom_lamports", 315, 323),
+    ("reward_payout_bps", 323, 325),
+    ("validator_stake_lamports", 325, 333),
+    ("pending_admin", 333, 365),
+    ("deposit_fee_bps", 365, 367),
+    ("withdraw_fee_bps", 367, 369),
+    ("management_fee_bps", 369, 371),
+    ("last_fee_epoch", 371, 379),
+    ("config_bump", 379, 380),
+    ("stake_main_bump", 380, 381),
+    ("stake_reserve_bump", 381, 382),
+    ("crank_tip_lamports", 382, 390),
+    ("rate_lamports", 390, 398),
+    ("rate_lst_supply", 398, 406),
+    ("rate_epoch", 406, 414),
+    ("max_pool_lamports", 414, 422),
+    ("deposit_authority", 422, 454),
+    ("validator_stake_epoch", 454, 462),
+    ("outgoing_stake_lamports", 462, 470),
+    ("holding_rebate_bps", 470, 472),
+    ("holding_rebate_epochs", 472, 480),
+];
+
+const POOL_STATE_FIELDS: Layout = &[
+    ("discriminator", 0, 1),
+    ("version", 1, 2),
+    ("event_sequence", 2, 10),
+    ("inflow_epoch", 10, 18),
+    ("inflow_lamports", 18, 26),
+    ("recent_events", 26, 2106),
+    ("principal_lamports", 2106, 2114),
+    ("principal_lst_supply", 2114, 2122),
+    ("lst_issued", 2122, 2130),
+    ("total_managed_lamports", 2130, 2138),
+    ("recorded_stake_lamports", 2138, 2146),
+];
+
+const EPOCH_TASKS_FIELDS: Layout = &[
+    ("discriminator", 0, 1),
+    ("version", 1, 2),
+    ("epoch", 2, 10),
+    ("completed", 10, 11),
+];
+
+const CHANGELOG_FIELDS: Layout = &[
+    ("discriminator", 0, 1),
+    ("version", 1, 2),
+    ("head", 2, 10),
+    ("len", 10, 18),
+    ("changes", 18, 6738),
+];
+
+const MINT_FIELDS: Layout = &[
+    ("mint_authority", 0, 36),
+    ("supply", 36, 44),
+    ("decimals", 44, 45),
+    ("is_initialized", 45, 46),
+    ("freeze_authority", 46, 82),
+];
+
+const TOKEN_ACCOUNT_FIELDS: Layout = &[
+    ("mint", 0, 32),
+    ("owner", 32, 64),
+    ("amount", 64, 72),
+    ("delegate", 72, 108),
+    ("state", 108, 109),
+    ("is_native", 109, 121),
+    ("delegated_amount", 121, 129),
+    ("close_authority", 129, 165),
+];
+
+const STAKE_FIELDS: Layout = &[
+    ("state", 0, 4),
+    ("rent_exempt_reserve", 4, 12),
+    ("staker", 12, 44),
+    ("withdrawer", 44, 76),
+    ("lockup", 76, 124),
+    ("voter", 124, 156),
+    ("stake", 156, 164),
+    ("activation_epoch", 164, 172),
+    ("deactivation_epoch", 172, 180),
+    ("warmup_cooldown_rate", 180, 188),
+    ("credits_observed", 188, 196),
+];
+
+/// Accounts recorded at one point in a test. Missing accounts are `None`.
+pub struct Snapshot {
+    accounts: BTreeMap<Pubkey, Option<Account>>,
+}
+
+/// How one account changed between two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountDiff {
+    pub lamport_delta: i128,
+    /// Names of the changed fields in layout order. `created` or `closed`
+    /// when the account appeared or disappeared, `owner` when it was
+    /// reassigned.
+    pub fields: Vec<String>,
+}
+
+/// An account expected to change, for `assert_diff`.
+pub struct ExpectedChange<'a> {
+    pub pubkey: Pubkey,
+    pub lamport_delta: i128,
+    pub fields: &'a [&'a str],
+}
+
+impl Snapshot {
+    pub fn take(svm: &LiteSVM, pubkeys: &[Pubkey]) -> Self {
+        Self {
+            accounts: pubkeys
+                .iter()
+                .map(|pubkey| (*pubkey, svm.get_account(pubkey)))
+                .collect(),
+        }
+    }
+
+    /// Accounts that differ from `self` in `after`. Only accounts recorded in
+    /// both snapshots are compared.
+    pub fn diff(&self, after: &Snapshot) -> BTreeMap<Pubkey, AccountDiff> {
+        let mut diffs = BTreeMap::new();
+
+        for (pubkey, before) in &self.accounts {
+            let Some(after) = after.accounts.get(pubkey) else {
+                continue;
+            };
+
+            let lamports = |account: &Option<Account>| {
+                account
+                    .as_ref()
+                    .map_or(0, |account| account.lamports as i128)
+            };
+            let lamport_delta = lamports(after) - lamports(before);
+
+            let fields = match (before, after) {
+                (None, None) => Vec::new(),
+                (None, Some(_)) => vec!["created".to_string()],
+                (Some(_), None) => vec!["closed".to_string()],
+                (Some(before), Some(after)) => changed_fields(before, after),
+            };
+
+            if lamport_delta != 0 || !fields.is_empty() {
+                diffs.insert(
+                    *pubkey,
+                    AccountDiff {
+                        lamport_delta,
+                        fields,
+                    },
+                );
+            }
+        }
+
+        diffs
+    }
+}
+
+/// The program's singleton accounts plus the pool's main, reserve and mint.
+/// Tests append the user accounts they care about.
+pub fn pool_accounts(
+    config_pda: &Pubkey,
+    token_mint: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+) -> Vec<Pubkey> {
+    vec![
+        *config_pda,
+        *token_mint,
+        *stake_account_main,
+        *stake_account_reserve,
+        spl_associated_token_account::get_associated_token_address(config_pda, token_mint),
+        pool_state_pda(),
+        epoch_tasks_pda(),
+        changelog_pda(),
+        liquidity_pool_pda(),
+        buffer_stake_pda(),
+        fee_vault_pda(),
+        trusted_callers_pda(),
+        validator_list_pda(),
+    ]
+}
+
+/// Asserts that exactly the `expected` accounts changed, each by its lamport
+/// delta and field list.
+pub fn assert_diff(diffs: &BTreeMap<Pubkey, AccountDiff>, expected: &[ExpectedChange]) {
+    let expected: BTreeMap<Pubkey, AccountDiff> = expected
+        .iter()
+        .map(|change| {
+            (
+                change.pubkey,
+                AccountDiff {
+                    lamport_delta: change.lamport_delta,
+                    fields: change
+                        .fields
+                        .iter()
+                        .map(|field| field.to_string())
+                        .collect(),
+                },
+            )
+        })
+        .collect();
+
+    assert_eq!(diffs, &expected, "Unexpected state diff");
+}
+
+fn changed_fields(before: &Account, after: &Account) -> Vec<String> {
+    let mut fields = Vec::new();
+
+    if before.owner != after.owner {
+        fields.push("owner".to_string());
+    }
+
+    if before.data == after.data {
+        return fields;
+    }
+
+    if before.data.len() != after.data.len() {
+        fields.push("data_len".to_string());
+        return fields;
+    }
+
+    match layout(after) {
+        Some(layout) => fields.extend(
+            layout
+                .iter()
+                .filter(|(_, start, end)| before.data[*start..*end] != after.data[*start..*end])
+                .map(|(name, _, _)| name.to_string()),
+        ),
+        None => fields.extend(changed_ranges(&before.data, &after.data)),
+    }
+
+    fields
+}
+
+fn layout(account: &Account) -> Option<Layout> {
+    let len = account.data.len();
+
+    if account.owner == PROGRAM_ID {
+        return match len {
+            Config::LEN => Some(CONFIG_FIELDS),
+            PoolState::LEN => Some(POOL_STATE_FIELDS),
+            EpochTasks::LEN => Some(EPOCH_TASKS_FIELDS),
+            Changelog::LEN => Some(CHANGELOG_FIELDS),
+            _ => None,
+        };
+    }
+
+    if account.owner == spl_token::ID {
+        return match len {
+            Mint::LEN => Some(MINT_FIELDS),
+            TokenAccount::LEN => Some(TOKEN_ACCOUNT_FIELDS),
+            _ => None,
+        };
+    }
+
+    if account.owner == Pubkey::from(STAKE_PROGRAM_ID) && len >= 196 {
+        return Some(STAKE_FIELDS);
+    }
+
+    None
+}
+
+/// Maximal runs of changed bytes, as `data[start..end]`.
+fn changed_ranges(before: &[u8], after: &[u8]) -> Vec<String> {
+    let mut ranges = Vec::new();
+    let mut start = None;
+
+    for index in 0..=before.len() {
+        let changed = index < before.len() && before[index] != after[index];
+        match (changed, start) {
+            (true, None) => start = Some(index),
+            (false, Some(range_start)) => {
+                ranges.push(format!("data[{range_start}..{index}]"));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    ranges
+}
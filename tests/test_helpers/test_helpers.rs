@@ -11,15 +11,11 @@ use spl_token::solana_program::program_pack::Pack;
 use spl_token::state::{Account as TokenAccount, Mint};
 use spl_token::ID as TOKEN_PROGRAM_ID;
 
-pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array([
-    0x0f, 0x1e, 0x6b, 0x14, 0x21, 0xc0, 0x4a, 0x07, 0x04, 0x31, 0x26, 0x5c, 0x19, 0xc5, 0xbb, 0xee,
-    0x19, 0x92, 0xba, 0xe8, 0xaf, 0xd1, 0xcd, 0x07, 0x8e, 0xf8, 0xaf, 0x70, 0x47, 0xdc, 0x11, 0xf7,
-]);
+use solana_liquid_staking::ids::STAKE_HISTORY_SYSVAR_ID;
 
-pub const HISTORY_SYSVAR: Pubkey = Pubkey::new_from_array([
-    6, 167, 213, 23, 25, 53, 132, 208, 254, 237, 155, 179, 67, 29, 19, 32, 107, 229, 68, 40, 27,
-    87, 184, 86, 108, 197, 55, 95, 244, 0, 0, 0,
-]);
+pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(solana_liquid_staking::ID);
+
+pub const HISTORY_SYSVAR: Pubkey = Pubkey::new_from_array(STAKE_HISTORY_SYSVAR_ID);
 
 pub fn setup_svm() -> LiteSVM {
     let mut svm = LiteSVM::new().with_builtins().with_sigverify(false);
@@ -56,6 +52,23 @@ pub fn print_transaction_logs(
     }
 }
 
+/// Sends `ix` signed and paid for by `signer`, then expires the blockhash so
+/// an identical instruction can be sent again. Returns whether it succeeded.
+pub fn send(svm: &mut LiteSVM, ix: solana_sdk::instruction::Instruction, signer: &Keypair) -> bool {
+    use solana_sdk::transaction::Transaction;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&signer.pubkey()),
+        &[signer],
+        svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    print_transaction_logs(&result);
+    svm.expire_blockhash();
+    result.is_ok()
+}
+
 pub fn create_mock_token_mint(svm: &mut LiteSVM, authority: &Pubkey) -> Keypair {
     let mint_keypair = Keypair::new();
     let mint_pubkey = mint_keypair.pubkey();
@@ -135,7 +148,7 @@ pub fn setup_initialize_accounts(
     Pubkey,  // stake_account_reserve
     Pubkey,  // vote_pubkey
 ) {
-    use solana_liquid_staking::instructions::helpers::VOTE_PROGRAM_ID;
+    use solana_liquid_staking::ids::VOTE_PROGRAM_ID;
 
     let initializer = Keypair::new();
     svm.airdrop(&initializer.pubkey(), 10_000_000_000).unwrap();
@@ -192,7 +205,7 @@ pub fn run_initialize(
     Pubkey,  // stake_account_reserve
     Pubkey,  // vote_pubkey
 ) {
-    use solana_liquid_staking::instructions::helpers::STAKE_PROGRAM_ID;
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
     use solana_program::example_mocks::solana_sdk::system_program;
     use solana_sdk::transaction::Transaction;
 
@@ -255,7 +268,7 @@ pub fn build_deposit_ix(
     deposit_amount: u64,
     depositor_is_signer: bool,
 ) -> solana_sdk::instruction::Instruction {
-    use solana_liquid_staking::instructions::helpers::STAKE_PROGRAM_ID;
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
     use solana_program::example_mocks::solana_sdk::system_program;
     use solana_sdk::instruction::{AccountMeta, Instruction};
 
@@ -268,20 +281,49 @@ pub fn build_deposit_ix(
         program_id: PROGRAM_ID,
         data,
         accounts: vec![
-            AccountMeta::new(*config_pda, false),
+            AccountMeta::new_readonly(*config_pda, false),
             AccountMeta::new(*depositor, depositor_is_signer),
             AccountMeta::new(*depositor_ata, false),
             AccountMeta::new(*token_mint, false),
-            AccountMeta::new(*stake_account_main, false),
+            AccountMeta::new_readonly(*stake_account_main, false),
             AccountMeta::new(*stake_account_reserve, false),
             AccountMeta::new_readonly(Pubkey::from(STAKE_PROGRAM_ID), false),
             AccountMeta::new_readonly(spl_token::ID, false),
             AccountMeta::new_readonly(system_program::ID, false),
             AccountMeta::new_readonly(rent_sysvar, false),
+            AccountMeta::new(pool_state_pda(), false),
         ],
     }
 }
 
+/// Positions of Deposit's optional accounts.
+pub const DEPOSIT_INSTRUCTIONS_SYSVAR: usize = 11;
+pub const DEPOSIT_TRUSTED_CALLERS: usize = 12;
+pub const DEPOSIT_KEY: usize = 13;
+pub const DEPOSIT_TREASURY_ATA: usize = 14;
+pub const DEPOSIT_REFERRER: usize = 15;
+pub const DEPOSIT_REFERRER_ATA: usize = 16;
+pub const DEPOSIT_AUTHORITY: usize = 17;
+pub const DEPOSIT_QUEUED_DEPOSIT: usize = 18;
+pub const DEPOSIT_USED_QUOTE: usize = 19;
+pub const DEPOSIT_USER_STATS: usize = 20;
+
+/// Puts `account` at `position` among a Deposit instruction's optional
+/// accounts, filling the positions left out before it with the program ID.
+pub fn set_deposit_account(
+    ix: &mut solana_sdk::instruction::Instruction,
+    position: usize,
+    account: solana_sdk::instruction::AccountMeta,
+) {
+    use solana_sdk::instruction::AccountMeta;
+
+    while ix.accounts.len() <= position {
+        ix.accounts
+            .push(AccountMeta::new_readonly(PROGRAM_ID, false));
+    }
+    ix.accounts[position] = account;
+}
+
 /// Sends a Deposit transaction. Returns the depositor keypair and depositor_ata.
 pub fn run_deposit(
     svm: &mut LiteSVM,
@@ -291,7 +333,7 @@ pub fn run_deposit(
     stake_account_reserve: &Pubkey,
     deposit_amount: u64,
 ) -> (Keypair, Pubkey) {
-    use solana_liquid_staking::instructions::helpers::STAKE_PROGRAM_ID;
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
     use solana_program::example_mocks::solana_sdk::system_program;
     use solana_sdk::instruction::{AccountMeta, Instruction};
     use solana_sdk::transaction::Transaction;
@@ -309,16 +351,17 @@ pub fn run_deposit(
         program_id: PROGRAM_ID,
         data: deposit_data,
         accounts: vec![
-            AccountMeta::new(*config_pda, false),
+            AccountMeta::new_readonly(*config_pda, false),
             AccountMeta::new(depositor.pubkey(), true),
             AccountMeta::new(depositor_ata, false),
             AccountMeta::new(*token_mint_pubkey, false),
-            AccountMeta::new(*stake_account_main, false),
+            AccountMeta::new_readonly(*stake_account_main, false),
             AccountMeta::new(*stake_account_reserve, false),
             AccountMeta::new_readonly(Pubkey::from(STAKE_PROGRAM_ID), false),
             AccountMeta::new_readonly(spl_token::ID, false),
             AccountMeta::new_readonly(system_program::ID, false),
             AccountMeta::new_readonly(rent_sysvar, false),
+            AccountMeta::new(pool_state_pda(), false),
         ],
     };
 
@@ -345,13 +388,14 @@ pub fn run_crank_initialize_reserve(
     stake_account_reserve: &Pubkey,
     vote_pubkey: &Pubkey,
 ) {
-    use solana_liquid_staking::instructions::helpers::STAKE_PROGRAM_ID;
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
     use solana_program::example_mocks::solana_sdk::system_program;
     use solana_sdk::instruction::{AccountMeta, Instruction};
     use solana_sdk::transaction::Transaction;
 
     let rent_sysvar = solana_sdk::sysvar::rent::id();
     let clock_sysvar = solana_sdk::sysvar::clock::id();
+    let stake_account_main = Pubkey::find_program_address(&[b"stake_main"], &PROGRAM_ID).0;
 
     let ix = Instruction {
         program_id: PROGRAM_ID,
@@ -366,6 +410,9 @@ pub fn run_crank_initialize_reserve(
             AccountMeta::new_readonly(HISTORY_SYSVAR, false),
             AccountMeta::new_readonly(system_program::ID, false),
             AccountMeta::new_readonly(Pubkey::from(STAKE_PROGRAM_ID), false),
+            AccountMeta::new_readonly(stake_account_main, false),
+            AccountMeta::new(epoch_tasks_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
         ],
     };
 
@@ -408,6 +455,8 @@ pub fn build_crank_merge_reserve_ix(
             AccountMeta::new_readonly(HISTORY_SYSVAR, false),
             AccountMeta::new_readonly(*system_program_id, false),
             AccountMeta::new_readonly(*stake_program_id, false),
+            AccountMeta::new(epoch_tasks_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
         ],
     }
 }
@@ -420,7 +469,7 @@ pub fn run_crank_merge_reserve(
     stake_account_main: &Pubkey,
     stake_account_reserve: &Pubkey,
 ) {
-    use solana_liquid_staking::instructions::helpers::STAKE_PROGRAM_ID;
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
     use solana_program::example_mocks::solana_sdk::system_program;
     use solana_sdk::instruction::{AccountMeta, Instruction};
     use solana_sdk::transaction::Transaction;
@@ -438,6 +487,8 @@ pub fn run_crank_merge_reserve(
             AccountMeta::new_readonly(HISTORY_SYSVAR, false),
             AccountMeta::new_readonly(system_program::ID, false),
             AccountMeta::new_readonly(Pubkey::from(STAKE_PROGRAM_ID), false),
+            AccountMeta::new(epoch_tasks_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
         ],
     };
 
@@ -457,6 +508,11 @@ pub fn run_crank_merge_reserve(
     );
 }
 
+/// Derives the split record PDA companion of a split stake account.
+pub fn split_record_pda(split_account: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"split_record", split_account.as_ref()], &PROGRAM_ID).0
+}
+
 /// Builds a CrankSplit instruction with the given accounts.
 /// Returns (instruction, depositor_stake_account_pda).
 pub fn build_crank_split_ix(
@@ -470,7 +526,7 @@ pub fn build_crank_split_ix(
     depositor_is_signer: bool,
     nonce: u64,
 ) -> (solana_sdk::instruction::Instruction, Pubkey) {
-    use solana_liquid_staking::instructions::helpers::STAKE_PROGRAM_ID;
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
     use solana_program::example_mocks::solana_sdk::system_program;
     use solana_sdk::instruction::{AccountMeta, Instruction};
 
@@ -504,6 +560,8 @@ pub fn build_crank_split_ix(
             AccountMeta::new_readonly(spl_token::ID, false),
             AccountMeta::new_readonly(Pubkey::from(STAKE_PROGRAM_ID), false),
             AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(split_record_pda(&depositor_stake_account), false),
+            AccountMeta::new(pool_state_pda(), false),
         ],
     };
 
@@ -522,7 +580,7 @@ pub fn run_crank_split(
     lamports_to_split: u64,
     nonce: u64,
 ) -> Pubkey {
-    use solana_liquid_staking::instructions::helpers::STAKE_PROGRAM_ID;
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
     use solana_program::example_mocks::solana_sdk::system_program;
     use solana_sdk::instruction::{AccountMeta, Instruction};
     use solana_sdk::transaction::Transaction;
@@ -558,6 +616,8 @@ pub fn run_crank_split(
             AccountMeta::new_readonly(spl_token::ID, false),
             AccountMeta::new_readonly(Pubkey::from(STAKE_PROGRAM_ID), false),
             AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(split_record_pda(&depositor_stake_account), false),
+            AccountMeta::new(pool_state_pda(), false),
         ],
     };
 
@@ -602,6 +662,8 @@ pub fn build_withdraw_ix(
             AccountMeta::new_readonly(HISTORY_SYSVAR, false),
             AccountMeta::new(*config_pda, false),
             AccountMeta::new_readonly(*stake_program_id, false),
+            AccountMeta::new(split_record_pda(depositor_stake_account), false),
+            AccountMeta::new(pool_state_pda(), false),
         ],
     }
 }
@@ -614,7 +676,7 @@ pub fn run_withdraw(
     config_pda: &Pubkey,
     nonce: u64,
 ) {
-    use solana_liquid_staking::instructions::helpers::STAKE_PROGRAM_ID;
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
     use solana_sdk::instruction::{AccountMeta, Instruction};
     use solana_sdk::transaction::Transaction;
 
@@ -634,6 +696,8 @@ pub fn run_withdraw(
             AccountMeta::new_readonly(HISTORY_SYSVAR, false),
             AccountMeta::new(*config_pda, false),
             AccountMeta::new_readonly(Pubkey::from(STAKE_PROGRAM_ID), false),
+            AccountMeta::new(split_record_pda(depositor_stake_account), false),
+            AccountMeta::new(pool_state_pda(), false),
         ],
     };
 
@@ -662,6 +726,7 @@ pub fn build_crank_initialize_reserve_ix(
 
     let rent_sysvar = solana_sdk::sysvar::rent::id();
     let clock_sysvar = solana_sdk::sysvar::clock::id();
+    let stake_account_main = Pubkey::find_program_address(&[b"stake_main"], &PROGRAM_ID).0;
 
     Instruction {
         program_id: PROGRAM_ID,
@@ -676,6 +741,9 @@ pub fn build_crank_initialize_reserve_ix(
             AccountMeta::new_readonly(HISTORY_SYSVAR, false),
             AccountMeta::new_readonly(*system_program_id, false),
             AccountMeta::new_readonly(*stake_program_id, false),
+            AccountMeta::new_readonly(stake_account_main, false),
+            AccountMeta::new(epoch_tasks_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
         ],
     }
 }
@@ -719,6 +787,3782 @@ pub fn build_initialize_ix(
             AccountMeta::new_readonly(rent_sysvar, false),
             AccountMeta::new_readonly(clock_sysvar, false),
             AccountMeta::new_readonly(HISTORY_SYSVAR, false),
+            AccountMeta::new(epoch_tasks_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+            AccountMeta::new(changelog_pda(), false),
+        ],
+    }
+}
+
+/// Moves the clock to the first slot of `epoch` and expires the blockhash so
+/// identical cranks can be resent.
+pub fn warp_epoch(svm: &mut LiteSVM, epoch: u64) {
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.epoch = epoch;
+    clock.slot = epoch * 432_000;
+    svm.set_sysvar(&clock);
+    svm.expire_blockhash();
+}
+
+/// Builds a CrankRecordRate instruction with the given accounts.
+pub fn build_crank_record_rate_ix(
+    payer: &Pubkey,
+    config_pda: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    token_mint: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let rate_history_pda = Pubkey::find_program_address(&[b"rate_history"], &PROGRAM_ID).0;
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![6u8],
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(rate_history_pda, false),
+            AccountMeta::new_readonly(*stake_account_main, false),
+            AccountMeta::new_readonly(*stake_account_reserve, false),
+            AccountMeta::new_readonly(*token_mint, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(epoch_tasks_pda(), false),
+            AccountMeta::new_readonly(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Sends a CrankRecordRate transaction.
+pub fn run_crank_record_rate(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    config_pda: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    token_mint: &Pubkey,
+) {
+    use solana_sdk::transaction::Transaction;
+
+    let ix = build_crank_record_rate_ix(
+        &payer.pubkey(),
+        config_pda,
+        stake_account_main,
+        stake_account_reserve,
+        token_mint,
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    );
+
+    let result = svm.send_transaction(tx);
+    println!("PRINTING CRANK RECORD RATE TRANSACTION LOGS");
+    print_transaction_logs(&result);
+    assert!(result.is_ok(), "CrankRecordRate transaction should succeed");
+}
+
+/// Simulates a GetApy call and decodes the return data into
+/// `(epochs_covered, apy_bps)` pairs for the 1/7/30-epoch windows.
+pub fn get_apy(svm: &mut LiteSVM, payer: &Keypair) -> Vec<(u64, i64)> {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+    use solana_sdk::transaction::Transaction;
+
+    let rate_history_pda = Pubkey::find_program_address(&[b"rate_history"], &PROGRAM_ID).0;
+
+    let ix = Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![7u8],
+        accounts: vec![AccountMeta::new_readonly(rate_history_pda, false)],
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    );
+
+    let result = svm
+        .simulate_transaction(tx)
+        .expect("GetApy simulation should succeed");
+    let data = result.meta.return_data.data;
+
+    data.chunks(16)
+        .map(|entry| {
+            (
+                u64::from_le_bytes(entry[0..8].try_into().unwrap()),
+                i64::from_le_bytes(entry[8..16].try_into().unwrap()),
+            )
+        })
+        .collect()
+}
+
+/// Adds `lamports` to an account, as a donation would. Lamports sent to a
+/// stake account never join its delegation, so the pool does not credit them.
+pub fn add_lamports(svm: &mut LiteSVM, pubkey: &Pubkey, lamports: u64) {
+    let mut account = svm.get_account(pubkey).unwrap();
+    account.lamports += lamports;
+    svm.set_account(*pubkey, account.into()).unwrap();
+}
+
+/// Pays `lamports` of epoch rewards to a delegated stake account, adding them
+/// to its lamports and its delegation's stake as the runtime does.
+pub fn add_stake_rewards(svm: &mut LiteSVM, stake_account: &Pubkey, lamports: u64) {
+    const DELEGATION_STAKE_OFFSET: usize = 156;
+
+    let mut account = svm.get_account(stake_account).unwrap();
+    let stake = &mut account.data[DELEGATION_STAKE_OFFSET..DELEGATION_STAKE_OFFSET + 8];
+    let delegated = u64::from_le_bytes(stake.try_into().unwrap());
+    assert!(delegated > 0, "Rewards are only paid to delegated stake");
+    stake.copy_from_slice(&(delegated + lamports).to_le_bytes());
+    account.lamports += lamports;
+    svm.set_account(*stake_account, account).unwrap();
+}
+
+/// Reads the token amount held by a token account.
+pub fn get_token_balance(svm: &LiteSVM, token_account: &Pubkey) -> u64 {
+    let account = svm.get_account(token_account).unwrap();
+    TokenAccount::unpack(&account.data).unwrap().amount
+}
+
+/// Reads the supply of a mint.
+pub fn get_mint_supply(svm: &LiteSVM, mint: &Pubkey) -> u64 {
+    let account = svm.get_account(mint).unwrap();
+    Mint::unpack(&account.data).unwrap().supply
+}
+
+/// Builds an ExitPool instruction with the given accounts.
+/// Returns (instruction, withdrawer_stake_account_pda).
+pub fn build_exit_pool_ix(
+    withdrawer: &Pubkey,
+    withdrawer_ata: &Pubkey,
+    config_pda: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    token_mint_pubkey: &Pubkey,
+    nonce: u64,
+) -> (solana_sdk::instruction::Instruction, Pubkey) {
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let clock_sysvar = solana_sdk::sysvar::clock::id();
+
+    let nonce_bytes = nonce.to_le_bytes();
+    let withdrawer_stake_account = Pubkey::find_program_address(
+        &[b"split_account", withdrawer.as_ref(), &nonce_bytes],
+        &PROGRAM_ID,
+    )
+    .0;
+
+    let mut data = vec![8u8];
+    data.extend_from_slice(&nonce_bytes);
+
+    let ix = Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*stake_account_main, false),
+            AccountMeta::new(*stake_account_reserve, false),
+            AccountMeta::new(*withdrawer, true),
+            AccountMeta::new(withdrawer_stake_account, false),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(*withdrawer_ata, false),
+            AccountMeta::new(*token_mint_pubkey, false),
+            AccountMeta::new_readonly(clock_sysvar, false),
+            AccountMeta::new_readonly(HISTORY_SYSVAR, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(Pubkey::from(STAKE_PROGRAM_ID), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(split_record_pda(&withdrawer_stake_account), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    };
+
+    (ix, withdrawer_stake_account)
+}
+
+/// Builds a CrankSplitTranche instruction splitting the next tranche of the
+/// schedule created by the split at `schedule_nonce` into the split at `nonce`.
+/// Returns (instruction, withdrawer_stake_account_pda).
+pub fn build_crank_split_tranche_ix(
+    withdrawer: &Pubkey,
+    withdrawer_ata: &Pubkey,
+    config_pda: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    token_mint_pubkey: &Pubkey,
+    schedule_nonce: u64,
+    nonce: u64,
+) -> (solana_sdk::instruction::Instruction, Pubkey) {
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let clock_sysvar = solana_sdk::sysvar::clock::id();
+
+    let schedule_stake_account = Pubkey::find_program_address(
+        &[
+            b"split_account",
+            withdrawer.as_ref(),
+            &schedule_nonce.to_le_bytes(),
+        ],
+        &PROGRAM_ID,
+    )
+    .0;
+    let withdrawer_stake_account = Pubkey::find_program_address(
+        &[b"split_account", withdrawer.as_ref(), &nonce.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+    .0;
+
+    let mut data = vec![9u8];
+    data.extend_from_slice(&schedule_nonce.to_le_bytes());
+    data.extend_from_slice(&nonce.to_le_bytes());
+
+    let ix = Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*stake_account_main, false),
+            AccountMeta::new_readonly(*stake_account_reserve, false),
+            AccountMeta::new(*withdrawer, true),
+            AccountMeta::new(withdrawer_stake_account, false),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(*withdrawer_ata, false),
+            AccountMeta::new(*token_mint_pubkey, false),
+            AccountMeta::new_readonly(clock_sysvar, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(Pubkey::from(STAKE_PROGRAM_ID), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(schedule_stake_account, false),
+            AccountMeta::new(split_record_pda(&schedule_stake_account), false),
+            AccountMeta::new(split_record_pda(&withdrawer_stake_account), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    };
+
+    (ix, withdrawer_stake_account)
+}
+
+/// Derives the trusted callers PDA.
+pub fn trusted_callers_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"trusted_callers"], &PROGRAM_ID).0
+}
+
+/// Builds a SetTrustedCaller instruction approving or removing `program`.
+pub fn build_set_trusted_caller_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    program: &Pubkey,
+    approved: bool,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![10u8];
+    data.extend_from_slice(program.as_ref());
+    data.push(approved as u8);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(trusted_callers_pda(), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Derives the liquidity pool PDA.
+pub fn liquidity_pool_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"liquidity_pool"], &PROGRAM_ID).0
+}
+
+/// Builds an InitializeLiquidityPool instruction. The LP mint must sign.
+pub fn build_initialize_liquidity_pool_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    lp_mint: &Pubkey,
+    lst_mint: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let liquidity_pool = liquidity_pool_pda();
+    let lst_vault =
+        spl_associated_token_account::get_associated_token_address(&liquidity_pool, lst_mint);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![11u8],
+        accounts: vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(liquidity_pool, false),
+            AccountMeta::new(*lp_mint, true),
+            AccountMeta::new(lst_vault, false),
+            AccountMeta::new_readonly(*lst_mint, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Sends an InitializeLiquidityPool transaction and returns the LP mint.
+pub fn run_initialize_liquidity_pool(
+    svm: &mut LiteSVM,
+    admin: &Keypair,
+    config_pda: &Pubkey,
+    lst_mint: &Pubkey,
+) -> Keypair {
+    use solana_sdk::transaction::Transaction;
+
+    let lp_mint = Keypair::new();
+    let ix = build_initialize_liquidity_pool_ix(
+        &admin.pubkey(),
+        config_pda,
+        &lp_mint.pubkey(),
+        lst_mint,
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&admin.pubkey()),
+        &[admin, &lp_mint],
+        svm.latest_blockhash(),
+    );
+
+    let result = svm.send_transaction(tx);
+    print_transaction_logs(&result);
+    assert!(
+        result.is_ok(),
+        "InitializeLiquidityPool transaction should succeed"
+    );
+
+    lp_mint
+}
+
+/// Builds an AddLiquidity instruction for `lamports` of SOL.
+#[allow(clippy::too_many_arguments)]
+pub fn build_add_liquidity_ix(
+    provider: &Pubkey,
+    config_pda: &Pubkey,
+    lp_mint: &Pubkey,
+    lst_mint: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    lamports: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let liquidity_pool = liquidity_pool_pda();
+    let mut data = vec![12u8];
+    data.extend_from_slice(&lamports.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*provider, true),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(provider, lp_mint),
+                false,
+            ),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(liquidity_pool, false),
+            AccountMeta::new(*lp_mint, false),
+            AccountMeta::new_readonly(
+                spl_associated_token_account::get_associated_token_address(
+                    &liquidity_pool,
+                    lst_mint,
+                ),
+                false,
+            ),
+            AccountMeta::new_readonly(*stake_account_main, false),
+            AccountMeta::new_readonly(*stake_account_reserve, false),
+            AccountMeta::new_readonly(*lst_mint, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(pool_state_pda(), false),
         ],
     }
 }
+
+/// Builds a RemoveLiquidity instruction burning `lp_amount` LP tokens.
+pub fn build_remove_liquidity_ix(
+    provider: &Pubkey,
+    provider_lst_ata: &Pubkey,
+    config_pda: &Pubkey,
+    lp_mint: &Pubkey,
+    lst_mint: &Pubkey,
+    lp_amount: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let liquidity_pool = liquidity_pool_pda();
+    let mut data = vec![13u8];
+    data.extend_from_slice(&lp_amount.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*provider, true),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(provider, lp_mint),
+                false,
+            ),
+            AccountMeta::new(*provider_lst_ata, false),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(liquidity_pool, false),
+            AccountMeta::new(*lp_mint, false),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(
+                    &liquidity_pool,
+                    lst_mint,
+                ),
+                false,
+            ),
+            AccountMeta::new_readonly(*lst_mint, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+    }
+}
+
+/// Builds a LiquidUnstake instruction swapping `lst_amount` LST for SOL.
+#[allow(clippy::too_many_arguments)]
+pub fn build_liquid_unstake_ix(
+    unstaker: &Pubkey,
+    unstaker_ata: &Pubkey,
+    config_pda: &Pubkey,
+    lst_mint: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    lst_amount: u64,
+    min_lamports_out: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let liquidity_pool = liquidity_pool_pda();
+    let mut data = vec![14u8];
+    data.extend_from_slice(&lst_amount.to_le_bytes());
+    data.extend_from_slice(&min_lamports_out.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*unstaker, true),
+            AccountMeta::new(*unstaker_ata, false),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(liquidity_pool, false),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(
+                    &liquidity_pool,
+                    lst_mint,
+                ),
+                false,
+            ),
+            AccountMeta::new_readonly(*stake_account_main, false),
+            AccountMeta::new_readonly(*stake_account_reserve, false),
+            AccountMeta::new_readonly(*lst_mint, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a SetUnstakeFeeParams instruction.
+pub fn build_set_unstake_fee_params_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    unstake_fee_min_bps: u16,
+    unstake_fee_max_bps: u16,
+    liquidity_target_lamports: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![15u8];
+    data.extend_from_slice(&unstake_fee_min_bps.to_le_bytes());
+    data.extend_from_slice(&unstake_fee_max_bps.to_le_bytes());
+    data.extend_from_slice(&liquidity_target_lamports.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Derives the liquidity buffer's stake account PDA.
+pub fn buffer_stake_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"buffer_stake"], &PROGRAM_ID).0
+}
+
+/// Builds a CrankRefillBuffer instruction. `source` is 0 (reserve),
+/// 1 (unstake) or 2 (collect).
+pub fn build_crank_refill_buffer_ix(
+    keeper: &Pubkey,
+    config_pda: &Pubkey,
+    lst_mint: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    source: u8,
+) -> solana_sdk::instruction::Instruction {
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let liquidity_pool = liquidity_pool_pda();
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![16u8, source],
+        accounts: vec![
+            AccountMeta::new(*keeper, true),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(liquidity_pool, false),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(
+                    &liquidity_pool,
+                    lst_mint,
+                ),
+                false,
+            ),
+            AccountMeta::new(*lst_mint, false),
+            AccountMeta::new(*stake_account_main, false),
+            AccountMeta::new(*stake_account_reserve, false),
+            AccountMeta::new(buffer_stake_pda(), false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(HISTORY_SYSVAR, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(Pubkey::from(STAKE_PROGRAM_ID), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a SwapBuffer instruction. `direction` is 0 (SOL for LST) or
+/// 1 (LST for SOL).
+#[allow(clippy::too_many_arguments)]
+pub fn build_swap_buffer_ix(
+    trader: &Pubkey,
+    trader_ata: &Pubkey,
+    config_pda: &Pubkey,
+    lst_mint: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    direction: u8,
+    amount_in: u64,
+    min_amount_out: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let liquidity_pool = liquidity_pool_pda();
+    let mut data = vec![17u8, direction];
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&min_amount_out.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*trader, true),
+            AccountMeta::new(*trader_ata, false),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(liquidity_pool, false),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(
+                    &liquidity_pool,
+                    lst_mint,
+                ),
+                false,
+            ),
+            AccountMeta::new_readonly(*stake_account_main, false),
+            AccountMeta::new_readonly(*stake_account_reserve, false),
+            AccountMeta::new_readonly(*lst_mint, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Creates a mock vote account whose validator identity is `node_pubkey`.
+pub fn create_vote_account(svm: &mut LiteSVM, node_pubkey: &Pubkey) -> Pubkey {
+    use solana_liquid_staking::ids::VOTE_PROGRAM_ID;
+
+    let vote_pubkey = Keypair::new().pubkey();
+
+    let mut data = vec![0u8; 3762];
+    data[0..4].copy_from_slice(&1u32.to_le_bytes());
+    data[4..36].copy_from_slice(node_pubkey.as_ref());
+    data[36..68].copy_from_slice(node_pubkey.as_ref());
+
+    svm.set_account(
+        vote_pubkey,
+        Account {
+            lamports: 10_000_000_000,
+            data,
+            owner: Pubkey::from(VOTE_PROGRAM_ID),
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    )
+    .unwrap();
+
+    vote_pubkey
+}
+
+/// Derives the validator bond PDA for a vote account.
+pub fn validator_bond_pda(vote_pubkey: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"validator_bond", vote_pubkey.as_ref()], &PROGRAM_ID).0
+}
+
+/// Derives the validator list PDA.
+pub fn validator_list_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"validator_list"], &PROGRAM_ID).0
+}
+
+/// Builds a JoinValidatorSet instruction. The operator must sign.
+pub fn build_join_validator_set_ix(
+    operator: &Pubkey,
+    vote_pubkey: &Pubkey,
+    bond_lamports: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![18u8];
+    data.extend_from_slice(&bond_lamports.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*operator, true),
+            AccountMeta::new_readonly(*vote_pubkey, false),
+            AccountMeta::new(validator_bond_pda(vote_pubkey), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    }
+}
+
+/// Builds a SetValidatorStatus instruction. The admin must sign.
+pub fn build_set_validator_status_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    vote_pubkey: &Pubkey,
+    active: bool,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![19u8, active as u8],
+        accounts: vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(validator_bond_pda(vote_pubkey), false),
+            AccountMeta::new(validator_list_pda(), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a SlashValidatorBond instruction. The admin must sign.
+pub fn build_slash_validator_bond_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    vote_pubkey: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    lamports: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![20u8];
+    data.extend_from_slice(&lamports.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(validator_bond_pda(vote_pubkey), false),
+            AccountMeta::new(validator_list_pda(), false),
+            AccountMeta::new(*stake_account_reserve, false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a LeaveValidatorSet instruction. The operator must sign.
+pub fn build_leave_validator_set_ix(
+    operator: &Pubkey,
+    vote_pubkey: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![21u8],
+        accounts: vec![
+            AccountMeta::new(*operator, true),
+            AccountMeta::new(validator_bond_pda(vote_pubkey), false),
+            AccountMeta::new(validator_list_pda(), false),
+        ],
+    }
+}
+
+/// Returns (vote_account, weight, score) for each validator in the validator
+/// list, or none if it does not exist yet.
+pub fn get_validator_entries(svm: &LiteSVM) -> Vec<(Pubkey, u32, u32)> {
+    let Some(account) = svm.get_account(&validator_list_pda()) else {
+        return Vec::new();
+    };
+    let len = u64::from_le_bytes(account.data[2..10].try_into().unwrap()) as usize;
+    (0..len)
+        .map(|index| {
+            let entry = &account.data[10 + index * 64..10 + (index + 1) * 64];
+            (
+                Pubkey::new_from_array(entry[0..32].try_into().unwrap()),
+                u32::from_le_bytes(entry[32..36].try_into().unwrap()),
+                u32::from_le_bytes(entry[36..40].try_into().unwrap()),
+            )
+        })
+        .collect()
+}
+
+/// Returns the vote accounts in the validator list, or none if it does not exist yet.
+pub fn get_validator_list(svm: &LiteSVM) -> Vec<Pubkey> {
+    get_validator_entries(svm)
+        .into_iter()
+        .map(|(vote_account, _, _)| vote_account)
+        .collect()
+}
+
+/// Builds a SetValidatorStakeCap instruction. The admin must sign.
+pub fn build_set_validator_stake_cap_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    max_validator_stake_bps: u16,
+    max_validator_stake_lamports: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![22u8];
+    data.extend_from_slice(&max_validator_stake_bps.to_le_bytes());
+    data.extend_from_slice(&max_validator_stake_lamports.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a SetDelegationStrategy instruction. The admin must sign.
+pub fn build_set_delegation_strategy_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    delegation_strategy: u8,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![23u8, delegation_strategy],
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a SetValidatorMetrics instruction. The admin must sign.
+pub fn build_set_validator_metrics_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    vote_pubkey: &Pubkey,
+    weight: u32,
+    score: u32,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![24u8];
+    data.extend_from_slice(vote_pubkey.as_ref());
+    data.extend_from_slice(&weight.to_le_bytes());
+    data.extend_from_slice(&score.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(validator_list_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// One validator row of the GetStakeDistribution report.
+#[derive(Debug, PartialEq)]
+pub struct DistributionRow {
+    pub vote_account: Pubkey,
+    pub active: u64,
+    pub activating: u64,
+    pub deactivating: u64,
+    pub target: u64,
+}
+
+/// Simulates GetStakeDistribution for `page`. Returns (row_count, page_count,
+/// undelegated_lamports, rows), or None if the simulation fails.
+pub fn get_stake_distribution(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    config_pda: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    page: u8,
+) -> Option<(u32, u32, u64, Vec<DistributionRow>)> {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+    use solana_sdk::transaction::Transaction;
+
+    let ix = Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![25u8, page],
+        accounts: vec![
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new_readonly(validator_list_pda(), false),
+            AccountMeta::new_readonly(*stake_account_main, false),
+            AccountMeta::new_readonly(*stake_account_reserve, false),
+            AccountMeta::new_readonly(buffer_stake_pda(), false),
+        ],
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    );
+
+    let data = svm.simulate_transaction(tx).ok()?.meta.return_data.data;
+    let read_u64 = |bytes: &[u8]| u64::from_le_bytes(bytes.try_into().unwrap());
+
+    let rows = data[16..]
+        .chunks(64)
+        .map(|row| DistributionRow {
+            vote_account: Pubkey::new_from_array(row[0..32].try_into().unwrap()),
+            active: read_u64(&row[32..40]),
+            activating: read_u64(&row[40..48]),
+            deactivating: read_u64(&row[48..56]),
+            target: read_u64(&row[56..64]),
+        })
+        .collect();
+
+    Some((
+        u32::from_le_bytes(data[0..4].try_into().unwrap()),
+        u32::from_le_bytes(data[4..8].try_into().unwrap()),
+        read_u64(&data[8..16]),
+        rows,
+    ))
+}
+
+/// Derives the epoch tasks PDA.
+pub fn changelog_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"changelog"], &PROGRAM_ID).0
+}
+
+pub fn epoch_tasks_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"epoch_tasks"], &PROGRAM_ID).0
+}
+
+/// Derives the pool state PDA.
+pub fn pool_state_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"pool_state"], &PROGRAM_ID).0
+}
+
+/// The outgoing reserve `UpdateConfig` rotates a delegated reserve into.
+pub fn stake_outgoing_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"stake_outgoing"], &PROGRAM_ID).0
+}
+
+/// Returns (epoch, completed) from the epoch tasks PDA.
+pub fn get_epoch_tasks(svm: &LiteSVM) -> (u64, u8) {
+    let data = svm.get_account(&epoch_tasks_pda()).unwrap().data;
+    (
+        u64::from_le_bytes(data[2..10].try_into().unwrap()),
+        data[10],
+    )
+}
+
+/// Builds a SetSplitMinimum instruction. The admin must sign.
+pub fn build_set_split_minimum_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    min_split_stake_lamports: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![26u8];
+    data.extend_from_slice(&min_split_stake_lamports.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Advances the clock by `slots` and expires the blockhash.
+pub fn warp_slots(svm: &mut LiteSVM, slots: u64) {
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.slot += slots;
+    svm.set_sysvar(&clock);
+    svm.expire_blockhash();
+}
+
+/// Derives the deposit key PDA recording `depositor`'s idempotency key.
+pub fn deposit_key_pda(depositor: &Pubkey, idempotency_key: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"deposit_key", depositor.as_ref(), idempotency_key],
+        &PROGRAM_ID,
+    )
+    .0
+}
+
+/// Builds a CloseDepositKey instruction. Anyone may sign as fee payer.
+pub fn build_close_deposit_key_ix(
+    depositor: &Pubkey,
+    idempotency_key: &[u8; 32],
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![27u8];
+    data.extend_from_slice(idempotency_key);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*depositor, false),
+            AccountMeta::new(deposit_key_pda(depositor, idempotency_key), false),
+        ],
+    }
+}
+
+/// Reads the event sequence number from the pool state PDA.
+pub fn get_event_sequence(svm: &LiteSVM) -> u64 {
+    let data = svm.get_account(&pool_state_pda()).unwrap().data;
+    u64::from_le_bytes(data[2..10].try_into().unwrap())
+}
+
+/// Reads the LST the program has minted minus what it burned.
+pub fn get_lst_issued(svm: &LiteSVM) -> u64 {
+    use solana_liquid_staking::state::PoolState;
+
+    let data = svm.get_account(&pool_state_pda()).unwrap().data;
+    PoolState::load(&data).unwrap().lst_issued
+}
+
+/// Reads the events still in the pool state's ring buffer, oldest first.
+pub fn get_recent_events(svm: &LiteSVM) -> Vec<solana_liquid_staking::state::EventRecord> {
+    use solana_liquid_staking::state::PoolState;
+
+    let data = svm.get_account(&pool_state_pda()).unwrap().data;
+    PoolState::load(&data).unwrap().recent_events().collect()
+}
+
+/// Reads the changelog PDA's parameter changes, oldest first.
+pub fn get_changelog(svm: &LiteSVM) -> Vec<solana_liquid_staking::state::ParamChange> {
+    use solana_liquid_staking::state::Changelog;
+
+    let data = svm.get_account(&changelog_pda()).unwrap().data;
+    let changelog = Changelog::load(&data).unwrap();
+    (0..changelog.len as usize)
+        .rev()
+        .map(|index| changelog.get(index).unwrap())
+        .collect()
+}
+
+/// Decodes the program's events from transaction logs as
+/// `(kind, sequence, payload)`.
+pub fn decode_events(logs: &[String]) -> Vec<(u8, u64, Vec<u8>)> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .map(|fields| {
+            let fields: Vec<Vec<u8>> = fields.split(' ').map(decode_base64).collect();
+            (
+                fields[0][0],
+                u64::from_le_bytes(fields[1][..].try_into().unwrap()),
+                fields[2].clone(),
+            )
+        })
+        .collect()
+}
+
+/// Decodes the LST decimals byte logged after each event's payload.
+pub fn decode_event_decimals(logs: &[String]) -> Vec<u8> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .map(|fields| decode_base64(fields.split(' ').nth(3).unwrap())[0])
+        .collect()
+}
+
+fn decode_base64(encoded: &str) -> Vec<u8> {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut bytes = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in encoded.bytes().take_while(|&c| c != b'=') {
+        let value = ALPHABET.iter().position(|&a| a == c).unwrap() as u32;
+        buffer = (buffer << 6) | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    bytes
+}
+
+/// Builds a BurnAndDonate instruction burning `lst_amount` from `donor_ata`.
+pub fn build_burn_and_donate_ix(
+    donor: &Pubkey,
+    donor_ata: &Pubkey,
+    token_mint_pubkey: &Pubkey,
+    config_pda: &Pubkey,
+    lst_amount: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![28u8];
+    data.extend_from_slice(&lst_amount.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*donor, true),
+            AccountMeta::new(*donor_ata, false),
+            AccountMeta::new(*token_mint_pubkey, false),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a DistributeTreasury instruction paying `amounts[i]` LST from the
+/// config's treasury ATA to `recipients[i]`.
+pub fn build_distribute_treasury_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    token_mint_pubkey: &Pubkey,
+    recipients: &[Pubkey],
+    amounts: &[u64],
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![29u8];
+    for amount in amounts {
+        data.extend_from_slice(&amount.to_le_bytes());
+    }
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*admin, true),
+        AccountMeta::new_readonly(*config_pda, false),
+        AccountMeta::new(
+            spl_associated_token_account::get_associated_token_address(
+                config_pda,
+                token_mint_pubkey,
+            ),
+            false,
+        ),
+        AccountMeta::new_readonly(spl_token::ID, false),
+        AccountMeta::new(pool_state_pda(), false),
+    ];
+    accounts.extend(
+        recipients
+            .iter()
+            .map(|recipient| AccountMeta::new(*recipient, false)),
+    );
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts,
+    }
+}
+
+pub fn vesting_pda(beneficiary: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"vesting", beneficiary.as_ref(), &nonce.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+    .0
+}
+
+/// Builds a CreateVesting instruction locking `amount` LST from the funder's
+/// ATA for `beneficiary`.
+#[allow(clippy::too_many_arguments)]
+pub fn build_create_vesting_ix(
+    funder: &Pubkey,
+    beneficiary: &Pubkey,
+    token_mint_pubkey: &Pubkey,
+    config_pda: &Pubkey,
+    nonce: u64,
+    amount: u64,
+    start_ts: i64,
+    cliff_ts: i64,
+    end_ts: i64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let vesting = vesting_pda(beneficiary, nonce);
+
+    let mut data = vec![30u8];
+    data.extend_from_slice(&nonce.to_le_bytes());
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&start_ts.to_le_bytes());
+    data.extend_from_slice(&cliff_ts.to_le_bytes());
+    data.extend_from_slice(&end_ts.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*funder, true),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(
+                    funder,
+                    token_mint_pubkey,
+                ),
+                false,
+            ),
+            AccountMeta::new_readonly(*beneficiary, false),
+            AccountMeta::new(vesting, false),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(
+                    &vesting,
+                    token_mint_pubkey,
+                ),
+                false,
+            ),
+            AccountMeta::new_readonly(*token_mint_pubkey, false),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    }
+}
+
+/// Builds a ClaimVested instruction paying everything vested so far to the
+/// beneficiary's LST ATA.
+pub fn build_claim_vested_ix(
+    beneficiary: &Pubkey,
+    token_mint_pubkey: &Pubkey,
+    nonce: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let vesting = vesting_pda(beneficiary, nonce);
+
+    let mut data = vec![31u8];
+    data.extend_from_slice(&nonce.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*beneficiary, true),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(
+                    beneficiary,
+                    token_mint_pubkey,
+                ),
+                false,
+            ),
+            AccountMeta::new(vesting, false),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(
+                    &vesting,
+                    token_mint_pubkey,
+                ),
+                false,
+            ),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+    }
+}
+
+pub fn deposit_session_pda(user: &Pubkey, session_key: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"deposit_session", user.as_ref(), session_key.as_ref()],
+        &PROGRAM_ID,
+    )
+    .0
+}
+
+/// Builds a CreateDepositSession instruction escrowing `budget_lamports`
+/// for `session_key` until `expires_at`.
+pub fn build_create_deposit_session_ix(
+    user: &Pubkey,
+    session_key: &Pubkey,
+    budget_lamports: u64,
+    expires_at: i64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![32u8];
+    data.extend_from_slice(&budget_lamports.to_le_bytes());
+    data.extend_from_slice(&expires_at.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(*session_key, false),
+            AccountMeta::new(deposit_session_pda(user, session_key), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    }
+}
+
+/// Builds a DepositWithSession instruction signed by `session_key`.
+pub fn build_deposit_with_session_ix(
+    session_key: &Pubkey,
+    user: &Pubkey,
+    config_pda: &Pubkey,
+    token_mint_pubkey: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    amount_in_lamports: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![33u8];
+    data.extend_from_slice(&amount_in_lamports.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*session_key, true),
+            AccountMeta::new(deposit_session_pda(user, session_key), false),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(user, token_mint_pubkey),
+                false,
+            ),
+            AccountMeta::new(*token_mint_pubkey, false),
+            AccountMeta::new_readonly(*stake_account_main, false),
+            AccountMeta::new(*stake_account_reserve, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a CloseDepositSession instruction returning the unspent budget.
+pub fn build_close_deposit_session_ix(
+    user: &Pubkey,
+    session_key: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![34u8],
+        accounts: vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(deposit_session_pda(user, session_key), false),
+        ],
+    }
+}
+
+pub fn dca_schedule_pda(user: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"dca_schedule", user.as_ref(), &nonce.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+    .0
+}
+
+/// Builds a CreateDcaSchedule instruction escrowing `budget_lamports`.
+pub fn build_create_dca_schedule_ix(
+    user: &Pubkey,
+    nonce: u64,
+    amount_per_interval: u64,
+    interval_secs: i64,
+    tip_lamports: u64,
+    budget_lamports: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![35u8];
+    data.extend_from_slice(&nonce.to_le_bytes());
+    data.extend_from_slice(&amount_per_interval.to_le_bytes());
+    data.extend_from_slice(&interval_secs.to_le_bytes());
+    data.extend_from_slice(&tip_lamports.to_le_bytes());
+    data.extend_from_slice(&budget_lamports.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(dca_schedule_pda(user, nonce), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    }
+}
+
+/// Builds a CrankDca instruction executing `user`'s schedule `nonce`.
+pub fn build_crank_dca_ix(
+    keeper: &Pubkey,
+    user: &Pubkey,
+    nonce: u64,
+    config_pda: &Pubkey,
+    token_mint_pubkey: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![36u8];
+    data.extend_from_slice(&nonce.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*keeper, true),
+            AccountMeta::new(dca_schedule_pda(user, nonce), false),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(user, token_mint_pubkey),
+                false,
+            ),
+            AccountMeta::new(*token_mint_pubkey, false),
+            AccountMeta::new_readonly(*stake_account_main, false),
+            AccountMeta::new(*stake_account_reserve, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a CloseDcaSchedule instruction returning the remaining budget.
+pub fn build_close_dca_schedule_ix(
+    user: &Pubkey,
+    nonce: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![37u8],
+        accounts: vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(dca_schedule_pda(user, nonce), false),
+        ],
+    }
+}
+
+/// Builds a SetPoolStatus instruction moving the pool to `status`. The admin
+/// must sign.
+pub fn build_set_pool_status_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    status: u8,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![38u8, status],
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(pool_state_pda(), false),
+            AccountMeta::new(changelog_pda(), false),
+        ],
+    }
+}
+
+/// Reads the `PoolStatus` byte from the config PDA.
+pub fn get_pool_status(svm: &LiteSVM, config_pda: &Pubkey) -> u8 {
+    svm.get_account(config_pda).unwrap().data[193]
+}
+
+/// Ends the bootstrap phase of a freshly initialized pool through
+/// `SetPoolStatus`, so unstake paths can be tested without warming up and
+/// merging the reserve first.
+pub fn run_activate_pool(svm: &mut LiteSVM, admin: &Keypair, config_pda: &Pubkey) {
+    use solana_sdk::transaction::Transaction;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[build_set_pool_status_ix(&admin.pubkey(), config_pda, 1)],
+        Some(&admin.pubkey()),
+        &[admin],
+        svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    print_transaction_logs(&result);
+    assert!(result.is_ok(), "SetPoolStatus should activate the pool");
+    svm.expire_blockhash();
+}
+
+/// Builds a VerifyAuthorities instruction auditing main, the reserve, the
+/// buffer stake account and each `(split account, split record)` pair.
+pub fn build_verify_authorities_ix(
+    config_pda: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    split_accounts: &[Pubkey],
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let buffer_stake_account = Pubkey::find_program_address(&[b"buffer_stake"], &PROGRAM_ID).0;
+
+    let mut accounts = vec![
+        AccountMeta::new(*config_pda, false),
+        AccountMeta::new_readonly(*stake_account_main, false),
+        AccountMeta::new_readonly(*stake_account_reserve, false),
+        AccountMeta::new_readonly(buffer_stake_account, false),
+    ];
+    for split_account in split_accounts {
+        accounts.push(AccountMeta::new_readonly(*split_account, false));
+        accounts.push(AccountMeta::new_readonly(
+            split_record_pda(split_account),
+            false,
+        ));
+    }
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![39u8],
+        accounts,
+    }
+}
+
+/// Reads the `AUTHORITY_DRIFT_*` bits from the config PDA.
+pub fn get_authority_drift(svm: &LiteSVM, config_pda: &Pubkey) -> u8 {
+    svm.get_account(config_pda).unwrap().data[194]
+}
+
+/// Derives the fee vault PDA holding protocol SOL fees.
+pub fn fee_vault_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"fee_vault"], &PROGRAM_ID).0
+}
+
+/// Builds a SetBuybackPolicy instruction. The admin must sign.
+pub fn build_set_buyback_policy_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    buyback_policy: u8,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![40u8, buyback_policy],
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a Buyback instruction spending the fee vault's SOL on LST.
+pub fn build_buyback_ix(
+    config_pda: &Pubkey,
+    token_mint: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let treasury_ata =
+        spl_associated_token_account::get_associated_token_address(config_pda, token_mint);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![41u8],
+        accounts: vec![
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(fee_vault_pda(), false),
+            AccountMeta::new(treasury_ata, false),
+            AccountMeta::new(*token_mint, false),
+            AccountMeta::new_readonly(*stake_account_main, false),
+            AccountMeta::new(*stake_account_reserve, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a GetBalanceSheet instruction.
+pub fn build_get_balance_sheet_ix(
+    config_pda: &Pubkey,
+    token_mint: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![42u8],
+        accounts: vec![
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new_readonly(*stake_account_main, false),
+            AccountMeta::new_readonly(*stake_account_reserve, false),
+            AccountMeta::new_readonly(*token_mint, false),
+            AccountMeta::new_readonly(liquidity_pool_pda(), false),
+            AccountMeta::new_readonly(fee_vault_pda(), false),
+            AccountMeta::new_readonly(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Decodes GetBalanceSheet return data into its eight `u64` lines.
+pub fn decode_balance_sheet(data: &[u8]) -> [u64; 8] {
+    let mut lines = [0u64; 8];
+    for (line, bytes) in lines.iter_mut().zip(data.chunks(8)) {
+        *line = u64::from_le_bytes(bytes.try_into().unwrap());
+    }
+    lines
+}
+
+/// Simulates GetBalanceSheet. Returns its eight `u64` lines in order (active,
+/// activating, deactivating, undelegated, buffer, LST supply, liabilities,
+/// fees accrued), or None if the simulation fails.
+pub fn get_balance_sheet(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    config_pda: &Pubkey,
+    token_mint: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+) -> Option<[u64; 8]> {
+    use solana_sdk::transaction::Transaction;
+
+    let ix = build_get_balance_sheet_ix(
+        config_pda,
+        token_mint,
+        stake_account_main,
+        stake_account_reserve,
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    );
+
+    let data = svm.simulate_transaction(tx).ok()?.meta.return_data.data;
+    Some(decode_balance_sheet(&data))
+}
+
+/// Builds a GetYieldReport instruction.
+pub fn build_get_yield_report_ix(
+    config_pda: &Pubkey,
+    token_mint: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![69u8],
+        accounts: vec![
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new_readonly(*stake_account_main, false),
+            AccountMeta::new_readonly(*stake_account_reserve, false),
+            AccountMeta::new_readonly(*token_mint, false),
+            AccountMeta::new_readonly(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Simulates GetYieldReport. Returns its four `u64` lines in order
+/// (principal, yield, pool lamports, LST supply), or None if the simulation
+/// fails.
+pub fn get_yield_report(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    config_pda: &Pubkey,
+    token_mint: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+) -> Option<[u64; 4]> {
+    use solana_sdk::transaction::Transaction;
+
+    let ix = build_get_yield_report_ix(
+        config_pda,
+        token_mint,
+        stake_account_main,
+        stake_account_reserve,
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    );
+
+    let data = svm.simulate_transaction(tx).ok()?.meta.return_data.data;
+    let mut lines = [0u64; 4];
+    for (line, bytes) in lines.iter_mut().zip(data.chunks(8)) {
+        *line = u64::from_le_bytes(bytes.try_into().unwrap());
+    }
+    Some(lines)
+}
+
+/// Builds a SetDepositCap instruction. The admin must sign.
+pub fn build_set_deposit_cap_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    deposit_cap_lamports: u64,
+    deposit_cap_mode: u8,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![43u8];
+    data.extend_from_slice(&deposit_cap_lamports.to_le_bytes());
+    data.push(deposit_cap_mode);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+pub fn queued_deposit_pda(depositor: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"queued_deposit", depositor.as_ref()], &PROGRAM_ID).0
+}
+
+/// Builds a ClaimQueuedDeposit instruction for `depositor`'s queue.
+pub fn build_claim_queued_deposit_ix(
+    config_pda: &Pubkey,
+    depositor: &Pubkey,
+    token_mint: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let depositor_ata =
+        spl_associated_token_account::get_associated_token_address(depositor, token_mint);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![44u8],
+        accounts: vec![
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(queued_deposit_pda(depositor), false),
+            AccountMeta::new(*depositor, false),
+            AccountMeta::new(depositor_ata, false),
+            AccountMeta::new(*token_mint, false),
+            AccountMeta::new_readonly(*stake_account_main, false),
+            AccountMeta::new(*stake_account_reserve, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Lamports waiting in `depositor`'s queued deposit PDA, zero if none.
+pub fn get_queued_deposit(svm: &LiteSVM, depositor: &Pubkey) -> u64 {
+    svm.get_account(&queued_deposit_pda(depositor))
+        .filter(|account| account.data.len() == 50)
+        .map_or(0, |account| {
+            u64::from_le_bytes(account.data[34..42].try_into().unwrap())
+        })
+}
+
+/// Builds a SetBonusSchedule instruction. The admin must sign.
+pub fn build_set_bonus_schedule_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    bonus_bps: u16,
+    bonus_cutoff_epoch: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![45u8];
+    data.extend_from_slice(&bonus_bps.to_le_bytes());
+    data.extend_from_slice(&bonus_cutoff_epoch.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a SimulateParams instruction projecting the given fee curve and
+/// deposit cap for a deposit of `deposit_lamports` and an instant unstake of
+/// `unstake_lst`.
+#[allow(clippy::too_many_arguments)]
+pub fn build_simulate_params_ix(
+    config_pda: &Pubkey,
+    token_mint: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    unstake_fee_min_bps: u16,
+    unstake_fee_max_bps: u16,
+    liquidity_target_lamports: u64,
+    deposit_cap_lamports: u64,
+    deposit_lamports: u64,
+    unstake_lst: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![46u8];
+    data.extend_from_slice(&unstake_fee_min_bps.to_le_bytes());
+    data.extend_from_slice(&unstake_fee_max_bps.to_le_bytes());
+    data.extend_from_slice(&liquidity_target_lamports.to_le_bytes());
+    data.extend_from_slice(&deposit_cap_lamports.to_le_bytes());
+    data.extend_from_slice(&deposit_lamports.to_le_bytes());
+    data.extend_from_slice(&unstake_lst.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new_readonly(*stake_account_main, false),
+            AccountMeta::new_readonly(*stake_account_reserve, false),
+            AccountMeta::new_readonly(*token_mint, false),
+            AccountMeta::new_readonly(liquidity_pool_pda(), false),
+            AccountMeta::new_readonly(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Decodes SimulateParams return data into the current and proposed
+/// projections, each (deposit accepted, deposit LST out, unstake fee bps,
+/// unstake lamports out, unstake fee lamports).
+pub fn decode_simulate_params(data: &[u8]) -> ([u64; 5], [u64; 5]) {
+    let mut current = [0u64; 5];
+    let mut proposed = [0u64; 5];
+    for (line, bytes) in current
+        .iter_mut()
+        .chain(proposed.iter_mut())
+        .zip(data.chunks(8))
+    {
+        *line = u64::from_le_bytes(bytes.try_into().unwrap());
+    }
+    (current, proposed)
+}
+
+/// Builds a SetWrapperProgram instruction. The admin must sign.
+pub fn build_set_wrapper_program_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    wrapper_program: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![47u8];
+    data.extend_from_slice(wrapper_program.as_ref());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// The PDA a wrapper program signs `MintToWrapper` and `BurnFromWrapper`
+/// with.
+pub fn wrapper_authority_pda(wrapper_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"lst_wrapper"], wrapper_program).0
+}
+
+/// Builds a MintToWrapper instruction. The wrapper authority must sign.
+pub fn build_mint_to_wrapper_ix(
+    wrapper_authority: &Pubkey,
+    config_pda: &Pubkey,
+    token_mint: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    lamports: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![48u8];
+    data.extend_from_slice(&lamports.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*wrapper_authority, true),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(
+                    wrapper_authority,
+                    token_mint,
+                ),
+                false,
+            ),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(*token_mint, false),
+            AccountMeta::new_readonly(*stake_account_main, false),
+            AccountMeta::new(*stake_account_reserve, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a BurnFromWrapper instruction. The wrapper authority must sign.
+#[allow(clippy::too_many_arguments)]
+pub fn build_burn_from_wrapper_ix(
+    wrapper_authority: &Pubkey,
+    config_pda: &Pubkey,
+    token_mint: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    lst_amount: u64,
+    min_lamports_out: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![49u8];
+    data.extend_from_slice(&lst_amount.to_le_bytes());
+    data.extend_from_slice(&min_lamports_out.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*wrapper_authority, true),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(
+                    wrapper_authority,
+                    token_mint,
+                ),
+                false,
+            ),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(*token_mint, false),
+            AccountMeta::new_readonly(*stake_account_main, false),
+            AccountMeta::new(*stake_account_reserve, false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(HISTORY_SYSVAR, false),
+            AccountMeta::new_readonly(Pubkey::from(STAKE_PROGRAM_ID), false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Sends `ix` with `payer` as the only real signer. Other signer metas, such
+/// as a wrapper program's PDA, are left unsigned, which `setup_svm` accepts
+/// because it disables signature verification. Stands in for the wrapper
+/// program's CPI.
+pub fn send_with_unsigned_signers(
+    svm: &mut LiteSVM,
+    ix: solana_sdk::instruction::Instruction,
+    payer: &Keypair,
+) -> bool {
+    use solana_sdk::{message::Message, transaction::Transaction};
+
+    let mut tx = Transaction::new_unsigned(Message::new(&[ix], Some(&payer.pubkey())));
+    tx.partial_sign(&[payer], svm.latest_blockhash());
+    let result = svm.send_transaction(tx);
+    print_transaction_logs(&result);
+    svm.expire_blockhash();
+    result.is_ok()
+}
+
+/// Builds a GetPendingCranks instruction.
+pub fn build_get_pending_cranks_ix(
+    config_pda: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let rate_history_pda = Pubkey::find_program_address(&[b"rate_history"], &PROGRAM_ID).0;
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![50u8],
+        accounts: vec![
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new_readonly(*stake_account_main, false),
+            AccountMeta::new_readonly(*stake_account_reserve, false),
+            AccountMeta::new_readonly(epoch_tasks_pda(), false),
+            AccountMeta::new_readonly(rate_history_pda, false),
+        ],
+    }
+}
+
+/// Decodes GetPendingCranks return data into the crank discriminators, in the
+/// order they should run.
+pub fn decode_pending_cranks(data: &[u8]) -> Vec<u8> {
+    data[1..1 + data[0] as usize].to_vec()
+}
+
+/// Builds an AdoptReserve instruction. The payer funds the reserve's rent.
+pub fn build_adopt_reserve_ix(
+    payer: &Pubkey,
+    config_pda: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![51u8],
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new_readonly(*stake_account_main, false),
+            AccountMeta::new(*stake_account_reserve, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    }
+}
+
+/// Builds a SetInstructionEnabled instruction. The admin must sign.
+pub fn build_set_instruction_enabled_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    discriminator: u8,
+    enabled: bool,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![52u8, discriminator, enabled as u8],
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// The referrer registry PDA of `referrer`.
+pub fn referrer_pda(referrer: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"referrer", referrer.as_ref()], &PROGRAM_ID).0
+}
+
+/// Builds a RegisterReferrer instruction. The referrer signs and pays.
+pub fn build_register_referrer_ix(referrer: &Pubkey) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![53u8],
+        accounts: vec![
+            AccountMeta::new(*referrer, true),
+            AccountMeta::new(referrer_pda(referrer), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    }
+}
+
+/// Builds a ClaimReferralFees instruction paying into `referrer_ata`.
+pub fn build_claim_referral_fees_ix(
+    referrer: &Pubkey,
+    referrer_ata: &Pubkey,
+    config_pda: &Pubkey,
+    token_mint: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let treasury_ata =
+        spl_associated_token_account::get_associated_token_address(config_pda, token_mint);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![54u8],
+        accounts: vec![
+            AccountMeta::new_readonly(*referrer, true),
+            AccountMeta::new(*referrer_ata, false),
+            AccountMeta::new(referrer_pda(referrer), false),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(treasury_ata, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+    }
+}
+
+/// Builds a SetReferralFee instruction. The admin must sign.
+pub fn build_set_referral_fee_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    referral_fee_bps: u16,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![55u8];
+    data.extend_from_slice(&referral_fee_bps.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a SetQuoteSigner instruction. The admin must sign.
+pub fn build_set_quote_signer_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    quote_signer: &Pubkey,
+    quote_max_deviation_bps: u16,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![56u8];
+    data.extend_from_slice(quote_signer.as_ref());
+    data.extend_from_slice(&quote_max_deviation_bps.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+pub fn used_quote_pda(depositor: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"used_quote", depositor.as_ref(), &nonce.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+    .0
+}
+
+pub fn user_stats_pda(user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"user_stats", user.as_ref()], &PROGRAM_ID).0
+}
+
+/// Builds the ed25519 program instruction carrying a deposit quote signed by
+/// `quote_signer`. It goes right before the quoted Deposit, which must pass
+/// `used_quote_pda(depositor, nonce)`.
+pub fn build_deposit_quote_ix(
+    quote_signer: &Keypair,
+    depositor: &Pubkey,
+    lst_per_sol: u64,
+    expires_at_slot: u64,
+    max_lamports: u64,
+    nonce: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::Instruction;
+
+    let mut message = PROGRAM_ID.to_bytes().to_vec();
+    message.extend_from_slice(depositor.as_ref());
+    message.extend_from_slice(&lst_per_sol.to_le_bytes());
+    message.extend_from_slice(&expires_at_slot.to_le_bytes());
+    message.extend_from_slice(&max_lamports.to_le_bytes());
+    message.extend_from_slice(&nonce.to_le_bytes());
+    let signature = quote_signer.sign_message(&message);
+
+    // One signature, then the offsets of the key (16), signature (48) and
+    // message (112), all in this instruction's own data.
+    let this_instruction = u16::MAX.to_le_bytes();
+    let mut data = vec![1u8, 0];
+    for field in [
+        48u16.to_le_bytes(),
+        this_instruction,
+        16u16.to_le_bytes(),
+        this_instruction,
+        112u16.to_le_bytes(),
+        (message.len() as u16).to_le_bytes(),
+        this_instruction,
+    ] {
+        data.extend_from_slice(&field);
+    }
+    data.extend_from_slice(quote_signer.pubkey().as_ref());
+    data.extend_from_slice(signature.as_ref());
+    data.extend_from_slice(&message);
+
+    Instruction {
+        program_id: "Ed25519SigVerify111111111111111111111111111"
+            .parse()
+            .unwrap(),
+        data,
+        accounts: vec![],
+    }
+}
+
+/// Builds a CloseUsedQuote instruction. Anyone may sign as fee payer.
+pub fn build_close_used_quote_ix(
+    depositor: &Pubkey,
+    nonce: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![98u8];
+    data.extend_from_slice(&nonce.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*depositor, false),
+            AccountMeta::new(used_quote_pda(depositor, nonce), false),
+        ],
+    }
+}
+
+/// Reads `referrer`'s registry entry as (referred lamports, referred
+/// deposits, fees earned, fees claimed).
+pub fn get_referrer_stats(svm: &LiteSVM, referrer: &Pubkey) -> (u64, u64, u64, u64) {
+    let account = svm
+        .get_account(&referrer_pda(referrer))
+        .expect("Referrer PDA should exist");
+    let field =
+        |offset: usize| u64::from_le_bytes(account.data[offset..offset + 8].try_into().unwrap());
+    (field(34), field(42), field(50), field(58))
+}
+
+pub fn unstake_order_pda(owner: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"unstake_order", owner.as_ref(), &nonce.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+    .0
+}
+
+/// Builds a CreateUnstakeOrder instruction locking `lst_amount` LST from the
+/// owner's ATA until it fetches `min_lamports_per_lst`.
+pub fn build_create_unstake_order_ix(
+    owner: &Pubkey,
+    token_mint: &Pubkey,
+    config_pda: &Pubkey,
+    nonce: u64,
+    lst_amount: u64,
+    min_lamports_per_lst: u64,
+    tip_lamports: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let order = unstake_order_pda(owner, nonce);
+
+    let mut data = vec![57u8];
+    data.extend_from_slice(&nonce.to_le_bytes());
+    data.extend_from_slice(&lst_amount.to_le_bytes());
+    data.extend_from_slice(&min_lamports_per_lst.to_le_bytes());
+    data.extend_from_slice(&tip_lamports.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(owner, token_mint),
+                false,
+            ),
+            AccountMeta::new(order, false),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(&order, token_mint),
+                false,
+            ),
+            AccountMeta::new_readonly(*token_mint, false),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    }
+}
+
+/// Builds a FillUnstakeOrder instruction, through the liquidity buffer when
+/// `via_buffer` and from the reserve otherwise.
+#[allow(clippy::too_many_arguments)]
+pub fn build_fill_unstake_order_ix(
+    keeper: &Pubkey,
+    owner: &Pubkey,
+    token_mint: &Pubkey,
+    config_pda: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    nonce: u64,
+    via_buffer: bool,
+) -> solana_sdk::instruction::Instruction {
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let order = unstake_order_pda(owner, nonce);
+
+    let mut data = vec![58u8];
+    data.extend_from_slice(&nonce.to_le_bytes());
+
+    let mut accounts = vec![
+        AccountMeta::new(*keeper, true),
+        AccountMeta::new(*owner, false),
+        AccountMeta::new(order, false),
+        AccountMeta::new(
+            spl_associated_token_account::get_associated_token_address(&order, token_mint),
+            false,
+        ),
+        AccountMeta::new_readonly(*config_pda, false),
+        AccountMeta::new(*token_mint, false),
+        AccountMeta::new_readonly(*stake_account_main, false),
+        AccountMeta::new(*stake_account_reserve, false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+    ];
+    if via_buffer {
+        let liquidity_pool = liquidity_pool_pda();
+        accounts.push(AccountMeta::new(liquidity_pool, false));
+        accounts.push(AccountMeta::new(
+            spl_associated_token_account::get_associated_token_address(&liquidity_pool, token_mint),
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(pool_state_pda(), false));
+    } else {
+        accounts.push(AccountMeta::new_readonly(
+            solana_sdk::sysvar::clock::id(),
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(HISTORY_SYSVAR, false));
+        accounts.push(AccountMeta::new_readonly(
+            Pubkey::from(STAKE_PROGRAM_ID),
+            false,
+        ));
+        accounts.push(AccountMeta::new(pool_state_pda(), false));
+    }
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts,
+    }
+}
+
+/// Builds a CancelUnstakeOrder instruction returning the LST to the owner's
+/// ATA.
+pub fn build_cancel_unstake_order_ix(
+    owner: &Pubkey,
+    token_mint: &Pubkey,
+    nonce: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let order = unstake_order_pda(owner, nonce);
+
+    let mut data = vec![59u8];
+    data.extend_from_slice(&nonce.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(owner, token_mint),
+                false,
+            ),
+            AccountMeta::new(order, false),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(&order, token_mint),
+                false,
+            ),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+    }
+}
+
+/// Overwrites a vote account with a current (tag 2) vote state holding
+/// `commission`, landed votes for `vote_slots`, `root_slot` and
+/// `epoch_credits` as (epoch, credits, prev_credits).
+pub fn write_vote_state(
+    svm: &mut LiteSVM,
+    vote_pubkey: &Pubkey,
+    commission: u8,
+    vote_slots: &[u64],
+    root_slot: Option<u64>,
+    epoch_credits: &[(u64, u64, u64)],
+) {
+    let mut account = svm.get_account(vote_pubkey).unwrap();
+    let node_pubkey: [u8; 32] = account.data[4..36].try_into().unwrap();
+
+    let mut data = Vec::new();
+    data.extend_from_slice(&2u32.to_le_bytes());
+    data.extend_from_slice(&node_pubkey);
+    data.extend_from_slice(&node_pubkey);
+    data.push(commission);
+    data.extend_from_slice(&(vote_slots.len() as u64).to_le_bytes());
+    for (i, slot) in vote_slots.iter().enumerate() {
+        data.push(0);
+        data.extend_from_slice(&slot.to_le_bytes());
+        data.extend_from_slice(&((vote_slots.len() - i) as u32).to_le_bytes());
+    }
+    match root_slot {
+        Some(slot) => {
+            data.push(1);
+            data.extend_from_slice(&slot.to_le_bytes());
+        }
+        None => data.push(0),
+    }
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.extend_from_slice(&[0u8; 32 * 48 + 8 + 1]);
+    data.extend_from_slice(&(epoch_credits.len() as u64).to_le_bytes());
+    for (epoch, credits, prev_credits) in epoch_credits {
+        data.extend_from_slice(&epoch.to_le_bytes());
+        data.extend_from_slice(&credits.to_le_bytes());
+        data.extend_from_slice(&prev_credits.to_le_bytes());
+    }
+    data.resize(3762, 0);
+
+    account.data = data;
+    svm.set_account(*vote_pubkey, account).unwrap();
+}
+
+/// Derives the validator health PDA for a vote account.
+pub fn validator_health_pda(vote_pubkey: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"validator_health", vote_pubkey.as_ref()], &PROGRAM_ID).0
+}
+
+pub fn build_crank_validator_health_ix(
+    payer: &Pubkey,
+    vote_pubkey: &Pubkey,
+    config_pda: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![60u8],
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*vote_pubkey, false),
+            AccountMeta::new(validator_health_pda(vote_pubkey), false),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new_readonly(validator_list_pda(), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    }
+}
+
+pub fn vote_escrow_pda(owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"vote_escrow", owner.as_ref()], &PROGRAM_ID).0
+}
+
+/// Builds a LockLst instruction locking `lst_amount` LST from the owner's ATA
+/// in their vote escrow until `unlock_ts`.
+pub fn build_lock_lst_ix(
+    owner: &Pubkey,
+    token_mint: &Pubkey,
+    config_pda: &Pubkey,
+    lst_amount: u64,
+    unlock_ts: i64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let escrow = vote_escrow_pda(owner);
+
+    let mut data = vec![61u8];
+    data.extend_from_slice(&lst_amount.to_le_bytes());
+    data.extend_from_slice(&unlock_ts.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(owner, token_mint),
+                false,
+            ),
+            AccountMeta::new(escrow, false),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(&escrow, token_mint),
+                false,
+            ),
+            AccountMeta::new_readonly(*token_mint, false),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    }
+}
+
+/// Builds an UnlockLst instruction returning the escrowed LST to the owner's
+/// ATA.
+pub fn build_unlock_lst_ix(
+    owner: &Pubkey,
+    token_mint: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let escrow = vote_escrow_pda(owner);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![62u8],
+        accounts: vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(owner, token_mint),
+                false,
+            ),
+            AccountMeta::new(escrow, false),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(&escrow, token_mint),
+                false,
+            ),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+    }
+}
+
+/// Reads `(locked_amount, unlock_ts, voting_weight)` from `owner`'s vote
+/// escrow.
+pub fn get_vote_escrow(svm: &LiteSVM, owner: &Pubkey) -> (u64, i64, u64) {
+    let account = svm
+        .get_account(&vote_escrow_pda(owner))
+        .expect("Vote escrow PDA should exist");
+    let field = |offset: usize| account.data[offset..offset + 8].try_into().unwrap();
+    (
+        u64::from_le_bytes(field(66)),
+        i64::from_le_bytes(field(74)),
+        u64::from_le_bytes(field(82)),
+    )
+}
+
+/// Builds a GetBuildInfo view instruction.
+pub fn build_get_build_info_ix() -> solana_sdk::instruction::Instruction {
+    solana_sdk::instruction::Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![63u8],
+        accounts: vec![],
+    }
+}
+
+/// Splits GetBuildInfo return data into its length-prefixed fields.
+pub fn decode_build_info(mut data: &[u8]) -> Vec<String> {
+    let mut fields = Vec::new();
+    while let Some((len, rest)) = data.split_first() {
+        let (field, rest) = rest.split_at(*len as usize);
+        fields.push(String::from_utf8(field.to_vec()).unwrap());
+        data = rest;
+    }
+    fields
+}
+
+/// Derives the split handoff PDA of a split stake account.
+pub fn split_handoff_pda(split_account: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"split_handoff", split_account.as_ref()], &PROGRAM_ID).0
+}
+
+/// Builds a ProposeSplitHandoff instruction, co-signed by the admin and the
+/// new owner.
+pub fn build_propose_split_handoff_ix(
+    admin: &Pubkey,
+    new_owner: &Pubkey,
+    config_pda: &Pubkey,
+    split_account: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![64u8],
+        accounts: vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(*new_owner, true),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new_readonly(*split_account, false),
+            AccountMeta::new_readonly(split_record_pda(split_account), false),
+            AccountMeta::new(split_handoff_pda(split_account), false),
+            AccountMeta::new(pool_state_pda(), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    }
+}
+
+/// Builds a CancelSplitHandoff instruction signed by `authority`, the split's
+/// owner or the admin.
+pub fn build_cancel_split_handoff_ix(
+    authority: &Pubkey,
+    proposer: &Pubkey,
+    config_pda: &Pubkey,
+    split_account: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![65u8],
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*proposer, false),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new_readonly(split_record_pda(split_account), false),
+            AccountMeta::new(split_handoff_pda(split_account), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds an ExecuteSplitHandoff instruction signed by the new owner.
+pub fn build_execute_split_handoff_ix(
+    new_owner: &Pubkey,
+    proposer: &Pubkey,
+    split_account: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![66u8],
+        accounts: vec![
+            AccountMeta::new_readonly(*new_owner, true),
+            AccountMeta::new(*proposer, false),
+            AccountMeta::new(split_record_pda(split_account), false),
+            AccountMeta::new(split_handoff_pda(split_account), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a SetMainHeadroom instruction. The admin must sign.
+pub fn build_set_main_headroom_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    main_headroom_lamports: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![67u8];
+    data.extend_from_slice(&main_headroom_lamports.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a CrankSplitSingle instruction, splitting into the withdrawer's
+/// nonce-less split PDA. Returns the instruction and that PDA.
+pub fn build_crank_split_single_ix(
+    withdrawer: &Pubkey,
+    withdrawer_ata: &Pubkey,
+    config_pda: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    token_mint_pubkey: &Pubkey,
+    lamports_to_split: u64,
+) -> (solana_sdk::instruction::Instruction, Pubkey) {
+    let (mut ix, _) = build_crank_split_ix(
+        withdrawer,
+        withdrawer_ata,
+        config_pda,
+        stake_account_main,
+        stake_account_reserve,
+        token_mint_pubkey,
+        lamports_to_split,
+        true,
+        0,
+    );
+
+    let split_account =
+        Pubkey::find_program_address(&[b"split_account", withdrawer.as_ref()], &PROGRAM_ID).0;
+    ix.data = vec![68u8];
+    ix.data.extend_from_slice(&lamports_to_split.to_le_bytes());
+    ix.accounts[3].pubkey = split_account;
+    ix.accounts[12].pubkey = split_record_pda(&split_account);
+
+    (ix, split_account)
+}
+
+pub fn migration_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"migration"], &PROGRAM_ID).0
+}
+
+pub fn imported_balance_pda(depositor: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"imported_balance", depositor.as_ref()], &PROGRAM_ID).0
+}
+
+/// Builds an OpenMigration instruction for a source pool with
+/// `source_pool_tokens` pool tokens. The admin must sign.
+pub fn build_open_migration_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    token_mint: &Pubkey,
+    source_pool_tokens: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![70u8];
+    data.extend_from_slice(&source_pool_tokens.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(migration_pda(), false),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(
+                    &migration_pda(),
+                    token_mint,
+                ),
+                false,
+            ),
+            AccountMeta::new_readonly(*token_mint, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds an ImportStakeAccount instruction adopting `stake_account`. The
+/// admin must sign.
+pub fn build_import_stake_account_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    token_mint: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    stake_account: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![71u8],
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(migration_pda(), false),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(
+                    &migration_pda(),
+                    token_mint,
+                ),
+                false,
+            ),
+            AccountMeta::new(*stake_account, false),
+            AccountMeta::new(*stake_account_main, false),
+            AccountMeta::new(*stake_account_reserve, false),
+            AccountMeta::new(*token_mint, false),
+            AccountMeta::new(pool_state_pda(), false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(HISTORY_SYSVAR, false),
+            AccountMeta::new_readonly(Pubkey::from(STAKE_PROGRAM_ID), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+    }
+}
+
+/// Builds an ImportBalance instruction paying `depositor` for
+/// `pool_tokens` source pool tokens. The admin must sign.
+pub fn build_import_balance_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    token_mint: &Pubkey,
+    depositor: &Pubkey,
+    pool_tokens: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![72u8];
+    data.extend_from_slice(&pool_tokens.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(migration_pda(), false),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(
+                    &migration_pda(),
+                    token_mint,
+                ),
+                false,
+            ),
+            AccountMeta::new_readonly(*depositor, false),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(depositor, token_mint),
+                false,
+            ),
+            AccountMeta::new(imported_balance_pda(depositor), false),
+            AccountMeta::new_readonly(*token_mint, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+pub fn reward_payout_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"reward_payout"], &PROGRAM_ID).0
+}
+
+pub fn payout_position_pda(owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"payout_position", owner.as_ref()], &PROGRAM_ID).0
+}
+
+pub fn payout_vault(token_mint: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(&reward_payout_pda(), token_mint)
+}
+
+/// Builds a SetRewardPayout instruction paying `reward_payout_bps` of the
+/// enrolled LST's rewards as SOL. The admin must sign.
+pub fn build_set_reward_payout_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    token_mint: &Pubkey,
+    reward_payout_bps: u16,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![73u8];
+    data.extend_from_slice(&reward_payout_bps.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(reward_payout_pda(), false),
+            AccountMeta::new(payout_vault(token_mint), false),
+            AccountMeta::new_readonly(*token_mint, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds an EnrollPayout instruction moving `lst_amount` LST from the
+/// owner's ATA into the payout vault.
+pub fn build_enroll_payout_ix(
+    owner: &Pubkey,
+    token_mint: &Pubkey,
+    config_pda: &Pubkey,
+    lst_amount: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![74u8];
+    data.extend_from_slice(&lst_amount.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(owner, token_mint),
+                false,
+            ),
+            AccountMeta::new(payout_position_pda(owner), false),
+            AccountMeta::new(reward_payout_pda(), false),
+            AccountMeta::new(payout_vault(token_mint), false),
+            AccountMeta::new_readonly(*token_mint, false),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    }
+}
+
+/// Builds a WithdrawPayout instruction redeeming `shares` of the owner's
+/// payout position into their ATA.
+pub fn build_withdraw_payout_ix(
+    owner: &Pubkey,
+    token_mint: &Pubkey,
+    config_pda: &Pubkey,
+    shares: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![75u8];
+    data.extend_from_slice(&shares.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(owner, token_mint),
+                false,
+            ),
+            AccountMeta::new(payout_position_pda(owner), false),
+            AccountMeta::new(reward_payout_pda(), false),
+            AccountMeta::new(payout_vault(token_mint), false),
+            AccountMeta::new_readonly(*token_mint, false),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+    }
+}
+
+/// Builds a ClaimPayout instruction paying the owner's accrued SOL.
+pub fn build_claim_payout_ix(owner: &Pubkey) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![76u8],
+        accounts: vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(payout_position_pda(owner), false),
+            AccountMeta::new(reward_payout_pda(), false),
+        ],
+    }
+}
+
+/// Builds a CrankRewardPayout instruction.
+pub fn build_crank_reward_payout_ix(
+    config_pda: &Pubkey,
+    token_mint: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![77u8],
+        accounts: vec![
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(reward_payout_pda(), false),
+            AccountMeta::new(payout_vault(token_mint), false),
+            AccountMeta::new_readonly(*stake_account_main, false),
+            AccountMeta::new(*stake_account_reserve, false),
+            AccountMeta::new(*token_mint, false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(HISTORY_SYSVAR, false),
+            AccountMeta::new_readonly(Pubkey::from(STAKE_PROGRAM_ID), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+pub fn validator_stake_pda(vote_pubkey: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"validator_stake", vote_pubkey.as_ref()], &PROGRAM_ID).0
+}
+
+/// Builds a CrankValidatorStake instruction for a listed validator.
+pub fn build_crank_validator_stake_ix(
+    payer: &Pubkey,
+    config_pda: &Pubkey,
+    vote_pubkey: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let stake_program_id = Pubkey::from(STAKE_PROGRAM_ID);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![78u8],
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(validator_list_pda(), false),
+            AccountMeta::new(validator_stake_pda(vote_pubkey), false),
+            AccountMeta::new_readonly(*vote_pubkey, false),
+            AccountMeta::new_readonly(*stake_account_main, false),
+            AccountMeta::new(*stake_account_reserve, false),
+            AccountMeta::new_readonly(stake_program_id, false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(HISTORY_SYSVAR, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(stake_program_id, false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds an AddValidator instruction. The admin must sign.
+pub fn build_add_validator_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    vote_pubkey: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![79u8],
+        accounts: vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(validator_list_pda(), false),
+            AccountMeta::new(validator_stake_pda(vote_pubkey), false),
+            AccountMeta::new_readonly(*vote_pubkey, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a RemoveValidator instruction. The admin must sign.
+pub fn build_remove_validator_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    vote_pubkey: &Pubkey,
+    stake_account_reserve: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![80u8],
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(validator_list_pda(), false),
+            AccountMeta::new(validator_stake_pda(vote_pubkey), false),
+            AccountMeta::new_readonly(*vote_pubkey, false),
+            AccountMeta::new(*stake_account_reserve, false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(HISTORY_SYSVAR, false),
+            AccountMeta::new_readonly(Pubkey::from(STAKE_PROGRAM_ID), false),
+            AccountMeta::new(pool_state_pda(), false),
+            AccountMeta::new_readonly(
+                Pubkey::find_program_address(&[b"stake_main"], &PROGRAM_ID).0,
+                false,
+            ),
+        ],
+    }
+}
+
+pub fn validator_transient_pda(vote_pubkey: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"validator_transient", vote_pubkey.as_ref()], &PROGRAM_ID).0
+}
+
+/// Builds a CrankRebalance instruction for a listed validator.
+pub fn build_crank_rebalance_ix(
+    payer: &Pubkey,
+    config_pda: &Pubkey,
+    vote_pubkey: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let stake_program_id = Pubkey::from(STAKE_PROGRAM_ID);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![81u8],
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(validator_list_pda(), false),
+            AccountMeta::new(validator_stake_pda(vote_pubkey), false),
+            AccountMeta::new(validator_transient_pda(vote_pubkey), false),
+            AccountMeta::new_readonly(*vote_pubkey, false),
+            AccountMeta::new(*stake_account_main, false),
+            AccountMeta::new(*stake_account_reserve, false),
+            AccountMeta::new_readonly(stake_program_id, false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(HISTORY_SYSVAR, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(stake_program_id, false),
+            AccountMeta::new(epoch_tasks_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds an UpdateConfig instruction moving the pool to `vote_pubkey`.
+pub fn build_update_config_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    vote_pubkey: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![82u8],
+        accounts: vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new_readonly(*vote_pubkey, false),
+            AccountMeta::new(*stake_account_main, false),
+            AccountMeta::new(*stake_account_reserve, false),
+            AccountMeta::new_readonly(validator_list_pda(), false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(Pubkey::from(STAKE_PROGRAM_ID), false),
+            AccountMeta::new(pool_state_pda(), false),
+            AccountMeta::new(stake_outgoing_pda(), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    }
+}
+
+/// Builds a CrankRedelegateMain instruction delegating main to `vote_pubkey`.
+pub fn build_crank_redelegate_main_ix(
+    config_pda: &Pubkey,
+    stake_account_main: &Pubkey,
+    vote_pubkey: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let stake_program_id = Pubkey::from(STAKE_PROGRAM_ID);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![83u8],
+        accounts: vec![
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(*stake_account_main, false),
+            AccountMeta::new_readonly(*vote_pubkey, false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(HISTORY_SYSVAR, false),
+            AccountMeta::new_readonly(stake_program_id, false),
+            AccountMeta::new_readonly(stake_program_id, false),
+            AccountMeta::new_readonly(
+                Pubkey::find_program_address(&[b"stake_reserve"], &PROGRAM_ID).0,
+                false,
+            ),
+            AccountMeta::new(pool_state_pda(), false),
+            AccountMeta::new(stake_outgoing_pda(), false),
+        ],
+    }
+}
+
+pub fn build_propose_admin_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    pending_admin: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![84u8];
+    data.extend_from_slice(pending_admin.as_ref());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+pub fn build_accept_admin_ix(
+    pending_admin: &Pubkey,
+    config_pda: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![85u8],
+        accounts: vec![
+            AccountMeta::new_readonly(*pending_admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a SetDepositFee instruction. The admin must sign.
+pub fn build_set_deposit_fee_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    deposit_fee_bps: u16,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![86u8];
+    data.extend_from_slice(&deposit_fee_bps.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a SetWithdrawFee instruction. The admin must sign.
+pub fn build_set_withdraw_fee_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    withdraw_fee_bps: u16,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![87u8];
+    data.extend_from_slice(&withdraw_fee_bps.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a SetHoldingRebate instruction. The admin must sign.
+pub fn build_set_holding_rebate_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    holding_rebate_bps: u16,
+    holding_rebate_epochs: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![99u8];
+    data.extend_from_slice(&holding_rebate_bps.to_le_bytes());
+    data.extend_from_slice(&holding_rebate_epochs.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a SetManagementFee instruction. The admin must sign.
+pub fn build_set_management_fee_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    management_fee_bps: u16,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![88u8];
+    data.extend_from_slice(&management_fee_bps.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a CrankManagementFee instruction minting to the config PDA's
+/// treasury ATA.
+pub fn build_crank_management_fee_ix(
+    config_pda: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    lst_mint: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![89u8],
+        accounts: vec![
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new_readonly(*stake_account_main, false),
+            AccountMeta::new_readonly(*stake_account_reserve, false),
+            AccountMeta::new(*lst_mint, false),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(config_pda, lst_mint),
+                false,
+            ),
+            AccountMeta::new(pool_state_pda(), false),
+            AccountMeta::new(epoch_tasks_pda(), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+    }
+}
+
+/// Sends a CrankManagementFee transaction.
+pub fn run_crank_management_fee(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    config_pda: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    lst_mint: &Pubkey,
+) {
+    use solana_sdk::transaction::Transaction;
+
+    let ix = build_crank_management_fee_ix(
+        config_pda,
+        stake_account_main,
+        stake_account_reserve,
+        lst_mint,
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    );
+
+    let result = svm.send_transaction(tx);
+    print_transaction_logs(&result);
+    assert!(
+        result.is_ok(),
+        "CrankManagementFee transaction should succeed"
+    );
+}
+
+pub fn build_deposit_stake_account_ix(
+    depositor: &Pubkey,
+    config_pda: &Pubkey,
+    stake_account: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    lst_mint: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![90u8],
+        accounts: vec![
+            AccountMeta::new(*depositor, true),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(depositor, lst_mint),
+                false,
+            ),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(*stake_account, false),
+            AccountMeta::new(*stake_account_main, false),
+            AccountMeta::new_readonly(*stake_account_reserve, false),
+            AccountMeta::new(*lst_mint, false),
+            AccountMeta::new(pool_state_pda(), false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(HISTORY_SYSVAR, false),
+            AccountMeta::new_readonly(Pubkey::from(STAKE_PROGRAM_ID), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+    }
+}
+
+pub fn build_withdraw_stake_ix(
+    withdrawer: &Pubkey,
+    config_pda: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    lst_mint: &Pubkey,
+    lamports: u64,
+    nonce: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
+    use solana_program::example_mocks::solana_sdk::system_program;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let new_stake_account = Pubkey::find_program_address(
+        &[b"split_account", withdrawer.as_ref(), &nonce.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+    .0;
+
+    let mut data = vec![91u8];
+    data.extend_from_slice(&lamports.to_le_bytes());
+    data.extend_from_slice(&nonce.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*withdrawer, true),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(withdrawer, lst_mint),
+                false,
+            ),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(new_stake_account, false),
+            AccountMeta::new(*stake_account_main, false),
+            AccountMeta::new_readonly(*stake_account_reserve, false),
+            AccountMeta::new(*lst_mint, false),
+            AccountMeta::new(pool_state_pda(), false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(Pubkey::from(STAKE_PROGRAM_ID), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    }
+}
+
+pub fn build_instant_withdraw_ix(
+    withdrawer: &Pubkey,
+    config_pda: &Pubkey,
+    lst_mint: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    lst_amount: u64,
+    min_lamports_out: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![92u8];
+    data.extend_from_slice(&lst_amount.to_le_bytes());
+    data.extend_from_slice(&min_lamports_out.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new(*withdrawer, true),
+            AccountMeta::new(
+                spl_associated_token_account::get_associated_token_address(withdrawer, lst_mint),
+                false,
+            ),
+            AccountMeta::new_readonly(*config_pda, false),
+            AccountMeta::new(*lst_mint, false),
+            AccountMeta::new_readonly(*stake_account_main, false),
+            AccountMeta::new(*stake_account_reserve, false),
+            AccountMeta::new_readonly(solana_sdk::sysvar::clock::id(), false),
+            AccountMeta::new_readonly(HISTORY_SYSVAR, false),
+            AccountMeta::new_readonly(Pubkey::from(STAKE_PROGRAM_ID), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+pub fn split_counter_pda(withdrawer: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"split_counter", withdrawer.as_ref()], &PROGRAM_ID).0
+}
+
+/// Builds a CrankSplitNext instruction, reading the withdrawer's next nonce
+/// from their split counter PDA (zero while it does not exist).
+/// Returns (instruction, split account, nonce).
+pub fn build_crank_split_next_ix(
+    svm: &LiteSVM,
+    withdrawer: &Pubkey,
+    withdrawer_ata: &Pubkey,
+    config_pda: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    token_mint_pubkey: &Pubkey,
+    lamports_to_split: u64,
+) -> (solana_sdk::instruction::Instruction, Pubkey, u64) {
+    use solana_sdk::instruction::AccountMeta;
+
+    let split_counter = split_counter_pda(withdrawer);
+    let nonce = svm
+        .get_account(&split_counter)
+        .filter(|account| account.data.len() == 10)
+        .map_or(0, |account| {
+            u64::from_le_bytes(account.data[2..10].try_into().unwrap())
+        });
+
+    let (mut ix, split_account) = build_crank_split_ix(
+        withdrawer,
+        withdrawer_ata,
+        config_pda,
+        stake_account_main,
+        stake_account_reserve,
+        token_mint_pubkey,
+        lamports_to_split,
+        true,
+        nonce,
+    );
+    ix.data = vec![93u8];
+    ix.data.extend_from_slice(&lamports_to_split.to_le_bytes());
+    ix.accounts
+        .insert(0, AccountMeta::new(split_counter, false));
+
+    (ix, split_account, nonce)
+}
+
+/// Builds a SetCrankTip instruction.
+pub fn build_set_crank_tip_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    crank_tip_lamports: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![94u8];
+    data.extend_from_slice(&crank_tip_lamports.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Appends the optional cranker and fee vault accounts to a
+/// CrankInitializeReserve or CrankMergeReserve instruction, claiming the
+/// crank tip for `cranker`.
+pub fn with_crank_tip(
+    mut ix: solana_sdk::instruction::Instruction,
+    cranker: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::AccountMeta;
+
+    ix.accounts.push(AccountMeta::new(*cranker, true));
+    ix.accounts.push(AccountMeta::new(fee_vault_pda(), false));
+    ix
+}
+
+/// Builds a CrankUpdateRate instruction.
+pub fn build_crank_update_rate_ix(
+    config_pda: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    lst_mint: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data: vec![95u8],
+        accounts: vec![
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new_readonly(*stake_account_main, false),
+            AccountMeta::new_readonly(*stake_account_reserve, false),
+            AccountMeta::new_readonly(*lst_mint, false),
+            AccountMeta::new(epoch_tasks_pda(), false),
+            AccountMeta::new_readonly(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Runs the CrankUpdateRate instruction, caching the epoch's rate.
+pub fn run_crank_update_rate(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    config_pda: &Pubkey,
+    stake_account_main: &Pubkey,
+    stake_account_reserve: &Pubkey,
+    lst_mint: &Pubkey,
+) {
+    use solana_sdk::transaction::Transaction;
+
+    let ix = build_crank_update_rate_ix(
+        config_pda,
+        stake_account_main,
+        stake_account_reserve,
+        lst_mint,
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    );
+
+    let result = svm.send_transaction(tx);
+    print_transaction_logs(&result);
+    assert!(result.is_ok(), "CrankUpdateRate transaction should succeed");
+}
+
+/// Builds a SetPoolCap instruction.
+pub fn build_set_pool_cap_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    max_pool_lamports: u64,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![96u8];
+    data.extend_from_slice(&max_pool_lamports.to_le_bytes());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// Builds a SetDepositAuthority instruction. The zero key opens deposits.
+pub fn build_set_deposit_authority_ix(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    deposit_authority: &Pubkey,
+) -> solana_sdk::instruction::Instruction {
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![97u8];
+    data.extend_from_slice(deposit_authority.as_ref());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        data,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*config_pda, false),
+            AccountMeta::new(changelog_pda(), false),
+            AccountMeta::new(pool_state_pda(), false),
+        ],
+    }
+}
+
+/// A pool set up by `setup_pool`.
+pub struct Pool {
+    pub admin: Keypair,
+    pub admin_ata: Pubkey,
+    pub config_pda: Pubkey,
+    pub mint: Pubkey,
+    pub stake_account_main: Pubkey,
+    pub stake_account_reserve: Pubkey,
+    /// Vote account main is delegated to.
+    pub vote_pubkey: Pubkey,
+    /// The config PDA's LST account, which collects fees. Only exists if
+    /// `PoolSetup::treasury_balance` is set.
+    pub treasury_ata: Pubkey,
+    /// The liquidity pool's LST account. Only exists if
+    /// `PoolSetup::liquidity` is set.
+    pub lst_vault: Pubkey,
+    /// User who made the `PoolSetup::deposit`, and their LST account.
+    pub depositor: Keypair,
+    pub depositor_ata: Pubkey,
+}
+
+impl Pool {
+    /// Deposits `lamports` from a fresh user, returning the user and their
+    /// LST account.
+    pub fn deposit(&self, svm: &mut LiteSVM, lamports: u64) -> (Keypair, Pubkey) {
+        run_deposit(
+            svm,
+            &self.config_pda,
+            &self.mint,
+            &self.stake_account_main,
+            &self.stake_account_reserve,
+            lamports,
+        )
+    }
+}
+
+/// What `setup_pool` does after `run_initialize`, in field order.
+#[derive(Default)]
+pub struct PoolSetup {
+    /// Activates the pool through `run_activate_pool`.
+    pub activate: bool,
+    /// Creates the treasury ATA holding this much LST.
+    pub treasury_balance: Option<u64>,
+    /// Initializes the liquidity pool and adds this many lamports from a
+    /// fresh provider. Zero leaves the liquidity pool uninitialized.
+    pub liquidity: u64,
+    /// Deposits this many lamports from `Pool::depositor`. Zero leaves the
+    /// depositor unfunded.
+    pub deposit: u64,
+}
+
+/// Initializes a pool and prepares it as `setup` describes.
+pub fn setup_pool(svm: &mut LiteSVM, setup: PoolSetup) -> Pool {
+    let (
+        admin,
+        token_mint,
+        admin_ata,
+        config_pda,
+        stake_account_main,
+        stake_account_reserve,
+        vote_pubkey,
+    ) = run_initialize(svm);
+    let mint = token_mint.pubkey();
+    let mut pool = Pool {
+        admin,
+        admin_ata,
+        config_pda,
+        mint,
+        stake_account_main,
+        stake_account_reserve,
+        vote_pubkey,
+        treasury_ata: spl_associated_token_account::get_associated_token_address(
+            &config_pda,
+            &mint,
+        ),
+        lst_vault: spl_associated_token_account::get_associated_token_address(
+            &liquidity_pool_pda(),
+            &mint,
+        ),
+        depositor: Keypair::new(),
+        depositor_ata: Pubkey::default(),
+    };
+
+    if setup.activate {
+        run_activate_pool(svm, &pool.admin, &pool.config_pda);
+    }
+    if let Some(balance) = setup.treasury_balance {
+        create_and_fund_ata(svm, &pool.config_pda, &pool.mint, balance);
+    }
+    if setup.liquidity > 0 {
+        let lp_mint = run_initialize_liquidity_pool(svm, &pool.admin, &pool.config_pda, &pool.mint);
+        let provider = Keypair::new();
+        svm.airdrop(&provider.pubkey(), setup.liquidity + 1_000_000_000)
+            .unwrap();
+        create_and_fund_ata(svm, &provider.pubkey(), &lp_mint.pubkey(), 0);
+        let ix = build_add_liquidity_ix(
+            &provider.pubkey(),
+            &pool.config_pda,
+            &lp_mint.pubkey(),
+            &pool.mint,
+            &pool.stake_account_main,
+            &pool.stake_account_reserve,
+            setup.liquidity,
+        );
+        assert!(send(svm, ix, &provider), "AddLiquidity should succeed");
+    }
+    if setup.deposit > 0 {
+        (pool.depositor, pool.depositor_ata) = pool.deposit(svm, setup.deposit);
+    }
+
+    pool
+}
@@ -0,0 +1,181 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        instruction::Instruction,
+        signature::{Keypair, Signer},
+    };
+
+    use crate::test_helpers::test_helpers::{
+        add_stake_rewards, build_add_liquidity_ix, build_cancel_unstake_order_ix,
+        build_create_unstake_order_ix, build_fill_unstake_order_ix, create_and_fund_ata,
+        get_token_balance, run_initialize_liquidity_pool, send, setup_pool, setup_svm,
+        unstake_order_pda, Pool, PoolSetup,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+    const TIP: u64 = 5_000_000;
+
+    impl Pool {
+        fn create_ix(&self, nonce: u64, lst_amount: u64, min_lamports_per_lst: u64) -> Instruction {
+            build_create_unstake_order_ix(
+                &self.depositor.pubkey(),
+                &self.mint,
+                &self.config_pda,
+                nonce,
+                lst_amount,
+                min_lamports_per_lst,
+                TIP,
+            )
+        }
+
+        fn fill_ix(&self, keeper: &Keypair, nonce: u64, via_buffer: bool) -> Instruction {
+            build_fill_unstake_order_ix(
+                &keeper.pubkey(),
+                &self.depositor.pubkey(),
+                &self.mint,
+                &self.config_pda,
+                &self.stake_account_main,
+                &self.stake_account_reserve,
+                nonce,
+                via_buffer,
+            )
+        }
+    }
+
+    /// Initializes the pool, deposits 5 SOL for the depositor, who owns the
+    /// orders, and funds a keeper.
+    fn setup(svm: &mut LiteSVM) -> (Pool, Keypair) {
+        let pool = setup_pool(
+            svm,
+            PoolSetup {
+                activate: true,
+                deposit: 5 * LAMPORTS_PER_SOL,
+                ..PoolSetup::default()
+            },
+        );
+        let keeper = Keypair::new();
+        svm.airdrop(&keeper.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+        (pool, keeper)
+    }
+
+    #[test]
+    fn test_order_fills_from_the_reserve_once_the_rate_is_met() {
+        let mut svm = setup_svm();
+        let (pool, keeper) = setup(&mut svm);
+        let lst_amount = 2 * LAMPORTS_PER_SOL;
+        let owner_lst = get_token_balance(&svm, &pool.depositor_ata);
+
+        assert!(send(
+            &mut svm,
+            pool.create_ix(0, lst_amount, 3 * LAMPORTS_PER_SOL / 2),
+            &pool.depositor,
+        ));
+        assert_eq!(
+            get_token_balance(&svm, &pool.depositor_ata),
+            owner_lst - lst_amount
+        );
+
+        assert!(
+            !send(&mut svm, pool.fill_ix(&keeper, 0, false), &keeper),
+            "The rate is not met yet"
+        );
+
+        // Rewards double the pool's value, and with it the LST's rate.
+        let pool_value = svm.get_balance(&pool.stake_account_main).unwrap()
+            + svm.get_balance(&pool.stake_account_reserve).unwrap();
+        add_stake_rewards(&mut svm, &pool.stake_account_main, pool_value);
+
+        let owner_before = svm.get_balance(&pool.depositor.pubkey()).unwrap();
+        let keeper_before = svm.get_balance(&keeper.pubkey()).unwrap();
+        assert!(send(&mut svm, pool.fill_ix(&keeper, 0, false), &keeper));
+
+        assert!(
+            svm.get_balance(&pool.depositor.pubkey()).unwrap()
+                >= owner_before + 3 * LAMPORTS_PER_SOL
+        );
+        assert!(svm.get_balance(&keeper.pubkey()).unwrap() > keeper_before);
+        let order = unstake_order_pda(&pool.depositor.pubkey(), 0);
+        assert_eq!(svm.get_balance(&order).unwrap_or(0), 0);
+    }
+
+    #[test]
+    fn test_order_fills_through_the_liquidity_buffer() {
+        let mut svm = setup_svm();
+        let (pool, keeper) = setup(&mut svm);
+
+        let lp_mint =
+            run_initialize_liquidity_pool(&mut svm, &pool.admin, &pool.config_pda, &pool.mint);
+        let provider = Keypair::new();
+        svm.airdrop(&provider.pubkey(), 11 * LAMPORTS_PER_SOL)
+            .unwrap();
+        create_and_fund_ata(&mut svm, &provider.pubkey(), &lp_mint.pubkey(), 0);
+        let ix = build_add_liquidity_ix(
+            &provider.pubkey(),
+            &pool.config_pda,
+            &lp_mint.pubkey(),
+            &pool.mint,
+            &pool.stake_account_main,
+            &pool.stake_account_reserve,
+            10 * LAMPORTS_PER_SOL,
+        );
+        assert!(send(&mut svm, ix, &provider));
+
+        // The buffer charges a fee, so an order at the full rate waits.
+        assert!(send(
+            &mut svm,
+            pool.create_ix(0, LAMPORTS_PER_SOL, LAMPORTS_PER_SOL),
+            &pool.depositor,
+        ));
+        assert!(!send(&mut svm, pool.fill_ix(&keeper, 0, true), &keeper));
+        assert!(send(
+            &mut svm,
+            build_cancel_unstake_order_ix(&pool.depositor.pubkey(), &pool.mint, 0),
+            &pool.depositor,
+        ));
+
+        assert!(send(
+            &mut svm,
+            pool.create_ix(1, LAMPORTS_PER_SOL, LAMPORTS_PER_SOL * 9 / 10),
+            &pool.depositor,
+        ));
+        let owner_before = svm.get_balance(&pool.depositor.pubkey()).unwrap();
+        assert!(send(&mut svm, pool.fill_ix(&keeper, 1, true), &keeper));
+        assert_eq!(get_token_balance(&svm, &pool.lst_vault), LAMPORTS_PER_SOL);
+        assert!(
+            svm.get_balance(&pool.depositor.pubkey()).unwrap()
+                >= owner_before + LAMPORTS_PER_SOL * 9 / 10
+        );
+    }
+
+    #[test]
+    fn test_only_the_owner_cancels() {
+        let mut svm = setup_svm();
+        let (pool, keeper) = setup(&mut svm);
+        let owner_lst = get_token_balance(&svm, &pool.depositor_ata);
+
+        assert!(send(
+            &mut svm,
+            pool.create_ix(7, LAMPORTS_PER_SOL, 10 * LAMPORTS_PER_SOL),
+            &pool.depositor,
+        ));
+
+        let mut cancel = build_cancel_unstake_order_ix(&keeper.pubkey(), &pool.mint, 7);
+        cancel.accounts[2].pubkey = unstake_order_pda(&pool.depositor.pubkey(), 7);
+        assert!(!send(&mut svm, cancel, &keeper));
+
+        assert!(send(
+            &mut svm,
+            build_cancel_unstake_order_ix(&pool.depositor.pubkey(), &pool.mint, 7),
+            &pool.depositor,
+        ));
+        assert_eq!(get_token_balance(&svm, &pool.depositor_ata), owner_lst);
+        assert!(
+            !send(&mut svm, pool.fill_ix(&keeper, 7, false), &keeper),
+            "A cancelled order cannot be filled"
+        );
+    }
+}
@@ -0,0 +1,159 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_liquid_staking::state::ParamChange;
+    use solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_crank_redelegate_main_ix, build_update_config_ix, create_vote_account, get_changelog,
+        run_crank_initialize_reserve, run_initialize, send, setup_svm, stake_outgoing_pda,
+        warp_epoch,
+    };
+
+    fn config_vote_pubkey(svm: &LiteSVM, config_pda: &Pubkey) -> Pubkey {
+        let config = svm.get_account(config_pda).unwrap().data;
+        Pubkey::try_from(&config[130..162]).unwrap()
+    }
+
+    fn config_outgoing_stake_lamports(svm: &LiteSVM, config_pda: &Pubkey) -> u64 {
+        let config = svm.get_account(config_pda).unwrap().data;
+        u64::from_le_bytes(config[462..470].try_into().unwrap())
+    }
+
+    fn main_voter_and_deactivation_epoch(
+        svm: &LiteSVM,
+        stake_account_main: &Pubkey,
+    ) -> (Pubkey, u64) {
+        let main = svm.get_account(stake_account_main).unwrap().data;
+        (
+            Pubkey::try_from(&main[124..156]).unwrap(),
+            u64::from_le_bytes(main[172..180].try_into().unwrap()),
+        )
+    }
+
+    #[test]
+    fn test_update_config_moves_main_to_the_new_validator() {
+        let mut svm = setup_svm();
+        let (admin, _, _, config_pda, stake_account_main, stake_account_reserve, old_vote) =
+            run_initialize(&mut svm);
+        let new_vote = create_vote_account(&mut svm, &Keypair::new().pubkey());
+        warp_epoch(&mut svm, 1);
+
+        let update_ix = |signer: &Pubkey| {
+            build_update_config_ix(
+                signer,
+                &config_pda,
+                &new_vote,
+                &stake_account_main,
+                &stake_account_reserve,
+            )
+        };
+        let redelegate_ix =
+            || build_crank_redelegate_main_ix(&config_pda, &stake_account_main, &new_vote);
+
+        let outsider = Keypair::new();
+        svm.airdrop(&outsider.pubkey(), 1_000_000_000).unwrap();
+        assert!(
+            !send(&mut svm, update_ix(&outsider.pubkey()), &outsider),
+            "Only the admin may move the pool"
+        );
+        assert!(
+            !send(&mut svm, redelegate_ix(), &admin),
+            "Main is not redelegated before the pool moves"
+        );
+
+        assert!(send(&mut svm, update_ix(&admin.pubkey()), &admin));
+        assert_eq!(config_vote_pubkey(&svm, &config_pda), new_vote);
+        assert_eq!(
+            main_voter_and_deactivation_epoch(&svm, &stake_account_main),
+            (old_vote, 1),
+            "Main cools down from the old validator"
+        );
+        let ParamChange {
+            parameter,
+            old_value,
+            new_value,
+            ..
+        } = get_changelog(&svm)[0];
+        assert_eq!(parameter, 82);
+        assert_eq!(old_value, old_vote.to_bytes());
+        assert_eq!(new_value, new_vote.to_bytes());
+
+        assert!(
+            !send(&mut svm, redelegate_ix(), &admin),
+            "Main must cool down first"
+        );
+
+        warp_epoch(&mut svm, 2);
+        assert!(send(&mut svm, redelegate_ix(), &admin));
+        assert_eq!(
+            main_voter_and_deactivation_epoch(&svm, &stake_account_main),
+            (new_vote, u64::MAX)
+        );
+    }
+
+    #[test]
+    fn test_update_config_rotates_a_delegated_reserve_out() {
+        let mut svm = setup_svm();
+        let (admin, _, _, config_pda, stake_account_main, stake_account_reserve, old_vote) =
+            run_initialize(&mut svm);
+        run_crank_initialize_reserve(
+            &mut svm,
+            &admin,
+            &config_pda,
+            &stake_account_reserve,
+            &old_vote,
+        );
+        let new_vote = create_vote_account(&mut svm, &Keypair::new().pubkey());
+        warp_epoch(&mut svm, 1);
+
+        let reserve_stake = svm.get_account(&stake_account_reserve).unwrap().lamports;
+        assert!(send(
+            &mut svm,
+            build_update_config_ix(
+                &admin.pubkey(),
+                &config_pda,
+                &new_vote,
+                &stake_account_main,
+                &stake_account_reserve,
+            ),
+            &admin
+        ));
+        assert_eq!(
+            svm.get_account(&stake_account_reserve)
+                .map_or(0, |account| account.lamports),
+            0,
+            "The reserve's stake moves out, leaving it for new deposits"
+        );
+        assert_eq!(
+            main_voter_and_deactivation_epoch(&svm, &stake_outgoing_pda()),
+            (old_vote, 1),
+            "The outgoing reserve cools down from the old validator"
+        );
+        assert!(svm.get_account(&stake_outgoing_pda()).unwrap().lamports > reserve_stake);
+        assert!(config_outgoing_stake_lamports(&svm, &config_pda) > 0);
+
+        warp_epoch(&mut svm, 2);
+        assert!(send(
+            &mut svm,
+            build_crank_redelegate_main_ix(&config_pda, &stake_account_main, &new_vote),
+            &admin
+        ));
+        assert_eq!(
+            svm.get_account(&stake_outgoing_pda())
+                .map_or(0, |account| account.lamports),
+            0,
+            "The outgoing reserve is merged into main"
+        );
+        assert_eq!(config_outgoing_stake_lamports(&svm, &config_pda), 0);
+        assert_eq!(
+            main_voter_and_deactivation_epoch(&svm, &stake_account_main),
+            (new_vote, u64::MAX)
+        );
+    }
+}
@@ -0,0 +1,99 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_liquid_staking::state::ValidatorHealth;
+    use solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_crank_validator_health_ix, create_vote_account, run_initialize, send, setup_svm,
+        validator_health_pda, write_vote_state,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    /// Reads (commission, last vote, root, last epoch, last epoch credits,
+    /// window epochs, window credits, refreshed slot) from a health PDA.
+    fn health(svm: &LiteSVM, vote_pubkey: &Pubkey) -> (u8, u64, u64, u64, u64, u8, u64, u64) {
+        let account = svm.get_account(&validator_health_pda(vote_pubkey)).unwrap();
+        let health = ValidatorHealth::load(&account.data).unwrap();
+        assert_eq!(health.vote_account, vote_pubkey.to_bytes());
+        (
+            health.commission,
+            health.last_vote_slot,
+            health.root_slot,
+            health.last_epoch,
+            health.last_epoch_credits,
+            health.window_epochs,
+            health.window_credits,
+            health.refreshed_slot,
+        )
+    }
+
+    #[test]
+    fn test_crank_copies_vote_account_figures() {
+        let mut svm = setup_svm();
+        let (_, _, _, config_pda, _, _, vote_pubkey) = run_initialize(&mut svm);
+        let cranker = Keypair::new();
+        svm.airdrop(&cranker.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+        let epoch_credits: Vec<(u64, u64, u64)> = (0..7)
+            .map(|epoch| (epoch, (epoch + 1) * 1_000, epoch * 1_000))
+            .collect();
+        write_vote_state(
+            &mut svm,
+            &vote_pubkey,
+            7,
+            &[90, 91, 95],
+            Some(60),
+            &epoch_credits,
+        );
+        svm.warp_to_slot(120);
+
+        let ix = build_crank_validator_health_ix(&cranker.pubkey(), &vote_pubkey, &config_pda);
+        assert!(send(&mut svm, ix, &cranker));
+        assert_eq!(
+            health(&svm, &vote_pubkey),
+            (7, 95, 60, 6, 1_000, 5, 5_000, 120)
+        );
+
+        // Later refreshes overwrite the figures in place.
+        write_vote_state(&mut svm, &vote_pubkey, 10, &[], None, &epoch_credits[..2]);
+        svm.warp_to_slot(240);
+        let ix = build_crank_validator_health_ix(&cranker.pubkey(), &vote_pubkey, &config_pda);
+        assert!(send(&mut svm, ix, &cranker));
+        assert_eq!(
+            health(&svm, &vote_pubkey),
+            (10, 0, 0, 1, 1_000, 2, 2_000, 240)
+        );
+    }
+
+    #[test]
+    fn test_crank_only_tracks_pool_validators() {
+        let mut svm = setup_svm();
+        let (_, _, _, config_pda, _, _, _) = run_initialize(&mut svm);
+        let cranker = Keypair::new();
+        svm.airdrop(&cranker.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+        let outsider = create_vote_account(&mut svm, &Pubkey::new_unique());
+        let ix = build_crank_validator_health_ix(&cranker.pubkey(), &outsider, &config_pda);
+        assert!(!send(&mut svm, ix, &cranker));
+        assert!(svm.get_account(&validator_health_pda(&outsider)).is_none());
+    }
+
+    #[test]
+    fn test_crank_rejects_a_health_pda_for_another_validator() {
+        let mut svm = setup_svm();
+        let (_, _, _, config_pda, _, _, vote_pubkey) = run_initialize(&mut svm);
+        let cranker = Keypair::new();
+        svm.airdrop(&cranker.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+        let mut ix = build_crank_validator_health_ix(&cranker.pubkey(), &vote_pubkey, &config_pda);
+        ix.accounts[2].pubkey = validator_health_pda(&Pubkey::new_unique());
+        assert!(!send(&mut svm, ix, &cranker));
+    }
+}
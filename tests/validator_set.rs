@@ -0,0 +1,399 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+
+    use crate::test_helpers::test_helpers::{
+        build_add_validator_ix, build_crank_validator_stake_ix, build_deposit_ix,
+        build_join_validator_set_ix, build_leave_validator_set_ix, build_remove_validator_ix,
+        build_set_validator_status_ix, build_slash_validator_bond_ix, create_and_fund_ata,
+        create_vote_account, get_validator_list, run_deposit, run_initialize, send, setup_svm,
+        validator_bond_pda, validator_list_pda, validator_stake_pda, warp_epoch,
+    };
+
+    /// Returns (bond_lamports, status) of a validator's bond.
+    fn bond_state(svm: &litesvm::LiteSVM, vote_pubkey: &Pubkey) -> (u64, u8) {
+        let data = svm
+            .get_account(&validator_bond_pda(vote_pubkey))
+            .unwrap()
+            .data;
+        (
+            u64::from_le_bytes(data[66..74].try_into().unwrap()),
+            data[74],
+        )
+    }
+
+    fn operator_with_vote_account(svm: &mut litesvm::LiteSVM) -> (Keypair, Pubkey) {
+        let operator = Keypair::new();
+        svm.airdrop(&operator.pubkey(), 20_000_000_000).unwrap();
+        let vote_pubkey = create_vote_account(svm, &operator.pubkey());
+        (operator, vote_pubkey)
+    }
+
+    #[test]
+    fn test_join_activate_and_leave() {
+        let mut svm = setup_svm();
+        let (initializer, _, _, config_pda, _, _, _) = run_initialize(&mut svm);
+        let (operator, vote_pubkey) = operator_with_vote_account(&mut svm);
+
+        let impostor = Keypair::new();
+        svm.airdrop(&impostor.pubkey(), 20_000_000_000).unwrap();
+        let ix = build_join_validator_set_ix(&impostor.pubkey(), &vote_pubkey, 10_000_000_000);
+        assert!(
+            !send(&mut svm, ix, &impostor),
+            "Only the validator identity may post its bond"
+        );
+
+        let ix = build_join_validator_set_ix(&operator.pubkey(), &vote_pubkey, 5_000_000_000);
+        assert!(send(&mut svm, ix, &operator), "Join should succeed");
+        assert_eq!(bond_state(&svm, &vote_pubkey), (5_000_000_000, 0));
+
+        let ix =
+            build_set_validator_status_ix(&initializer.pubkey(), &config_pda, &vote_pubkey, true);
+        assert!(
+            !send(&mut svm, ix, &initializer),
+            "Activation should require the minimum bond"
+        );
+
+        let ix = build_join_validator_set_ix(&operator.pubkey(), &vote_pubkey, 5_000_000_000);
+        assert!(send(&mut svm, ix, &operator), "Top-up should succeed");
+
+        let ix = build_set_validator_status_ix(&operator.pubkey(), &config_pda, &vote_pubkey, true);
+        assert!(
+            !send(&mut svm, ix, &operator),
+            "Only the admin may activate a validator"
+        );
+
+        let ix =
+            build_set_validator_status_ix(&initializer.pubkey(), &config_pda, &vote_pubkey, true);
+        assert!(
+            send(&mut svm, ix, &initializer),
+            "Activation should succeed"
+        );
+        assert_eq!(bond_state(&svm, &vote_pubkey), (10_000_000_000, 1));
+        assert_eq!(get_validator_list(&svm), vec![vote_pubkey]);
+
+        let ix = build_leave_validator_set_ix(&operator.pubkey(), &vote_pubkey);
+        assert!(send(&mut svm, ix, &operator), "Leaving should succeed");
+        assert_eq!(bond_state(&svm, &vote_pubkey), (10_000_000_000, 2));
+        assert!(get_validator_list(&svm).is_empty());
+
+        let ix = build_leave_validator_set_ix(&operator.pubkey(), &vote_pubkey);
+        assert!(
+            !send(&mut svm, ix, &operator),
+            "The bond should stay locked while exiting"
+        );
+
+        warp_epoch(&mut svm, 2);
+
+        let bond_account_lamports = svm.get_balance(&validator_bond_pda(&vote_pubkey)).unwrap();
+        let operator_before = svm.get_balance(&operator.pubkey()).unwrap();
+        let ix = build_leave_validator_set_ix(&operator.pubkey(), &vote_pubkey);
+        assert!(
+            send(&mut svm, ix, &operator),
+            "The bond should be refunded once unlocked"
+        );
+
+        assert_eq!(
+            svm.get_balance(&validator_bond_pda(&vote_pubkey))
+                .unwrap_or(0),
+            0
+        );
+        assert_eq!(
+            svm.get_balance(&operator.pubkey()).unwrap(),
+            operator_before + bond_account_lamports - 5_000
+        );
+    }
+
+    #[test]
+    fn test_slash_bond_into_reserve() {
+        let mut svm = setup_svm();
+        let (initializer, _, _, config_pda, _, stake_account_reserve, _) = run_initialize(&mut svm);
+        let (operator, vote_pubkey) = operator_with_vote_account(&mut svm);
+
+        let ix = build_join_validator_set_ix(&operator.pubkey(), &vote_pubkey, 10_000_000_000);
+        assert!(send(&mut svm, ix, &operator), "Join should succeed");
+
+        let ix = build_slash_validator_bond_ix(
+            &initializer.pubkey(),
+            &config_pda,
+            &vote_pubkey,
+            &stake_account_reserve,
+            1_000_000_000,
+        );
+        assert!(
+            !send(&mut svm, ix, &initializer),
+            "A pending validator cannot be slashed"
+        );
+
+        let ix =
+            build_set_validator_status_ix(&initializer.pubkey(), &config_pda, &vote_pubkey, true);
+        assert!(
+            send(&mut svm, ix, &initializer),
+            "Activation should succeed"
+        );
+
+        let ix = build_slash_validator_bond_ix(
+            &operator.pubkey(),
+            &config_pda,
+            &vote_pubkey,
+            &stake_account_reserve,
+            4_000_000_000,
+        );
+        assert!(
+            !send(&mut svm, ix, &operator),
+            "Only the admin may slash a bond"
+        );
+
+        let ix = build_slash_validator_bond_ix(
+            &initializer.pubkey(),
+            &config_pda,
+            &vote_pubkey,
+            &stake_account_reserve,
+            11_000_000_000,
+        );
+        assert!(
+            !send(&mut svm, ix, &initializer),
+            "A slash cannot exceed the bond"
+        );
+
+        let reserve_before = svm.get_balance(&stake_account_reserve).unwrap();
+        let ix = build_slash_validator_bond_ix(
+            &initializer.pubkey(),
+            &config_pda,
+            &vote_pubkey,
+            &stake_account_reserve,
+            4_000_000_000,
+        );
+        assert!(send(&mut svm, ix, &initializer), "Slash should succeed");
+
+        assert_eq!(
+            svm.get_balance(&stake_account_reserve).unwrap(),
+            reserve_before + 4_000_000_000
+        );
+        assert_eq!(bond_state(&svm, &vote_pubkey), (6_000_000_000, 0));
+        assert!(get_validator_list(&svm).is_empty());
+
+        let ix = build_leave_validator_set_ix(&operator.pubkey(), &vote_pubkey);
+        assert!(
+            send(&mut svm, ix, &operator),
+            "A removed validator may withdraw what is left of its bond"
+        );
+        assert_eq!(
+            svm.get_balance(&validator_bond_pda(&vote_pubkey))
+                .unwrap_or(0),
+            0
+        );
+    }
+
+    #[test]
+    fn test_crank_validator_stake_spreads_and_records_stake() {
+        let mut svm = setup_svm();
+        let (initializer, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+        let (operator, vote_pubkey) = operator_with_vote_account(&mut svm);
+
+        let crank = |svm: &mut litesvm::LiteSVM, vote_pubkey: &Pubkey| {
+            let ix = build_crank_validator_stake_ix(
+                &initializer.pubkey(),
+                &config_pda,
+                vote_pubkey,
+                &stake_account_main,
+                &stake_account_reserve,
+            );
+            send(svm, ix, &initializer)
+        };
+
+        let ix = build_join_validator_set_ix(&operator.pubkey(), &vote_pubkey, 10_000_000_000);
+        assert!(send(&mut svm, ix, &operator));
+        assert!(
+            !crank(&mut svm, &vote_pubkey),
+            "Only listed validators are staked"
+        );
+
+        let ix =
+            build_set_validator_status_ix(&initializer.pubkey(), &config_pda, &vote_pubkey, true);
+        assert!(send(&mut svm, ix, &initializer));
+
+        run_deposit(
+            &mut svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            10_000_000_000,
+        );
+        let reserve_before = svm.get_balance(&stake_account_reserve).unwrap();
+
+        assert!(crank(&mut svm, &vote_pubkey), "The first crank delegates");
+        let validator_stake = svm
+            .get_account(&validator_stake_pda(&vote_pubkey))
+            .expect("The validator stake PDA is created");
+        let staked = validator_stake.lamports
+            - svm.minimum_balance_for_rent_exemption(validator_stake.data.len());
+        assert!(staked > 0);
+        assert_eq!(
+            svm.get_balance(&stake_account_reserve).unwrap(),
+            reserve_before - staked,
+            "The stake comes out of the reserve"
+        );
+
+        let config = svm.get_account(&config_pda).unwrap().data;
+        assert_eq!(
+            u64::from_le_bytes(config[325..333].try_into().unwrap()),
+            staked
+        );
+        let list = svm.get_account(&validator_list_pda()).unwrap().data;
+        assert_eq!(u64::from_le_bytes(list[50..58].try_into().unwrap()), staked);
+
+        warp_epoch(&mut svm, 1);
+        assert!(crank(&mut svm, &vote_pubkey), "Later cranks record");
+
+        let ix = build_leave_validator_set_ix(&operator.pubkey(), &vote_pubkey);
+        assert!(
+            !send(&mut svm, ix, &operator),
+            "A validator holding pool stake cannot leave"
+        );
+    }
+
+    #[test]
+    fn test_add_and_remove_validator_drains_its_stake() {
+        let mut svm = setup_svm();
+        let (admin, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+        let (operator, vote_pubkey) = operator_with_vote_account(&mut svm);
+
+        let ix = build_add_validator_ix(&operator.pubkey(), &config_pda, &vote_pubkey);
+        assert!(
+            !send(&mut svm, ix, &operator),
+            "Only the admin may add a validator"
+        );
+
+        let ix = build_add_validator_ix(&admin.pubkey(), &config_pda, &vote_pubkey);
+        assert!(send(&mut svm, ix, &admin), "Adding should succeed");
+        assert_eq!(get_validator_list(&svm), vec![vote_pubkey]);
+        assert!(svm
+            .get_account(&validator_stake_pda(&vote_pubkey))
+            .is_some());
+
+        let ix = build_add_validator_ix(&admin.pubkey(), &config_pda, &vote_pubkey);
+        assert!(
+            !send(&mut svm, ix, &admin),
+            "A listed validator cannot be added twice"
+        );
+
+        run_deposit(
+            &mut svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            10_000_000_000,
+        );
+        let ix = build_crank_validator_stake_ix(
+            &admin.pubkey(),
+            &config_pda,
+            &vote_pubkey,
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+        assert!(send(&mut svm, ix, &admin));
+        let validator_stake = svm.get_balance(&validator_stake_pda(&vote_pubkey)).unwrap();
+        let reserve_before = svm.get_balance(&stake_account_reserve).unwrap();
+
+        let remove = |svm: &mut litesvm::LiteSVM| {
+            let ix = build_remove_validator_ix(
+                &admin.pubkey(),
+                &config_pda,
+                &vote_pubkey,
+                &stake_account_reserve,
+            );
+            send(svm, ix, &admin)
+        };
+
+        assert!(remove(&mut svm), "The first call deactivates");
+        assert_eq!(
+            get_validator_list(&svm),
+            vec![vote_pubkey],
+            "The validator stays listed while cooling down"
+        );
+        assert!(!remove(&mut svm), "Draining waits for the cooldown");
+
+        warp_epoch(&mut svm, 1);
+        assert!(remove(&mut svm), "The cooled-down stake is drained");
+        assert!(get_validator_list(&svm).is_empty());
+        assert_eq!(
+            svm.get_balance(&stake_account_reserve).unwrap(),
+            reserve_before + validator_stake
+        );
+
+        let config = svm.get_account(&config_pda).unwrap().data;
+        assert_eq!(u64::from_le_bytes(config[325..333].try_into().unwrap()), 0);
+    }
+
+    #[test]
+    fn test_pricing_waits_for_the_epochs_validator_stake_record() {
+        let mut svm = setup_svm();
+        let (admin, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+        let (_, vote_pubkey) = operator_with_vote_account(&mut svm);
+
+        let ix = build_add_validator_ix(&admin.pubkey(), &config_pda, &vote_pubkey);
+        assert!(send(&mut svm, ix, &admin));
+        run_deposit(
+            &mut svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            10_000_000_000,
+        );
+        let crank = |svm: &mut litesvm::LiteSVM| {
+            let ix = build_crank_validator_stake_ix(
+                &admin.pubkey(),
+                &config_pda,
+                &vote_pubkey,
+                &stake_account_main,
+                &stake_account_reserve,
+            );
+            send(svm, ix, &admin)
+        };
+        assert!(crank(&mut svm));
+
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 10_000_000_000).unwrap();
+        let depositor_ata =
+            create_and_fund_ata(&mut svm, &depositor.pubkey(), &token_mint.pubkey(), 0);
+        let deposit = |svm: &mut litesvm::LiteSVM| {
+            let ix = build_deposit_ix(
+                &config_pda,
+                &depositor.pubkey(),
+                &depositor_ata,
+                &token_mint.pubkey(),
+                &stake_account_main,
+                &stake_account_reserve,
+                1_000_000_000,
+                true,
+            );
+            send(svm, ix, &depositor)
+        };
+        assert!(deposit(&mut svm), "The stake was recorded this epoch");
+
+        warp_epoch(&mut svm, 1);
+        assert!(
+            !deposit(&mut svm),
+            "Last epoch's validator stake leaves out the rewards since"
+        );
+
+        assert!(crank(&mut svm));
+        let config = svm.get_account(&config_pda).unwrap().data;
+        assert_eq!(u64::from_le_bytes(config[454..462].try_into().unwrap()), 1);
+        assert!(
+            deposit(&mut svm),
+            "Deposits price again once every staked validator is recorded"
+        );
+    }
+}
@@ -0,0 +1,147 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_verify_authorities_ix, get_authority_drift, print_transaction_logs,
+        run_crank_initialize_reserve, run_crank_merge_reserve, run_crank_split, run_deposit,
+        run_initialize, setup_svm,
+    };
+
+    const AUTHORITY_DRIFT_MAIN: u8 = 1 << 0;
+    const AUTHORITY_DRIFT_SPLIT: u8 = 1 << 3;
+
+    fn send(svm: &mut LiteSVM, ix: Instruction) -> bool {
+        let fee_payer = Keypair::new();
+        svm.airdrop(&fee_payer.pubkey(), 1_000_000_000).unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&fee_payer.pubkey()),
+            &[&fee_payer],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        svm.expire_blockhash();
+        result.is_ok()
+    }
+
+    /// Overwrites the staker authority in a stake account's `Meta`.
+    fn set_staker(svm: &mut LiteSVM, stake_account: &Pubkey, staker: &Pubkey) {
+        let mut account = svm.get_account(stake_account).unwrap();
+        account.data[12..44].copy_from_slice(staker.as_ref());
+        svm.set_account(*stake_account, account).unwrap();
+    }
+
+    #[test]
+    fn test_verify_authorities_flags_and_clears_main_drift() {
+        let mut svm = setup_svm();
+        let (_, _, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+
+        let ix = build_verify_authorities_ix(
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &[],
+        );
+        assert!(send(&mut svm, ix.clone()));
+        assert_eq!(get_authority_drift(&svm, &config_pda), 0);
+
+        set_staker(&mut svm, &stake_account_main, &Pubkey::new_unique());
+        assert!(
+            send(&mut svm, ix.clone()),
+            "The audit should record drift, not fail"
+        );
+        assert_eq!(get_authority_drift(&svm, &config_pda), AUTHORITY_DRIFT_MAIN);
+
+        set_staker(&mut svm, &stake_account_main, &config_pda);
+        assert!(send(&mut svm, ix));
+        assert_eq!(get_authority_drift(&svm, &config_pda), 0);
+    }
+
+    #[test]
+    fn test_verify_authorities_checks_pending_splits() {
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = run_initialize(&mut svm);
+        let (depositor, depositor_ata) = run_deposit(
+            &mut svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            2_000_000_000,
+        );
+        run_crank_initialize_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_reserve,
+            &vote_pubkey,
+        );
+        run_crank_merge_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+        let split_account = run_crank_split(
+            &mut svm,
+            &depositor,
+            &depositor_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            1_500_000_000,
+            1,
+        );
+
+        let ix = build_verify_authorities_ix(
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &[split_account],
+        );
+        assert!(send(&mut svm, ix.clone()));
+        assert_eq!(get_authority_drift(&svm, &config_pda), 0);
+
+        set_staker(&mut svm, &split_account, &depositor.pubkey());
+        assert!(send(&mut svm, ix));
+        assert_eq!(
+            get_authority_drift(&svm, &config_pda),
+            AUTHORITY_DRIFT_SPLIT
+        );
+
+        // An account without a split record is not program-controlled.
+        let mut bogus = build_verify_authorities_ix(
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &[],
+        );
+        bogus.accounts.push(bogus.accounts[1].clone());
+        bogus.accounts.push(bogus.accounts[2].clone());
+        assert!(
+            !send(&mut svm, bogus),
+            "Unrecorded splits should be rejected"
+        );
+    }
+}
@@ -0,0 +1,129 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        signature::{Keypair, Signer},
+        sysvar::clock::Clock,
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_claim_vested_ix, build_create_vesting_ix, create_and_fund_ata, get_token_balance,
+        run_initialize, send, setup_svm, vesting_pda,
+    };
+
+    fn set_unix_timestamp(svm: &mut LiteSVM, unix_timestamp: i64) {
+        let mut clock = svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = unix_timestamp;
+        svm.set_sysvar::<Clock>(&clock);
+    }
+
+    #[test]
+    fn test_vesting_cliff_and_linear_release() {
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            _stake_account_main,
+            _stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+
+        let funder = Keypair::new();
+        let beneficiary = Keypair::new();
+        svm.airdrop(&funder.pubkey(), 1_000_000_000).unwrap();
+        svm.airdrop(&beneficiary.pubkey(), 1_000_000_000).unwrap();
+        let funder_ata = create_and_fund_ata(&mut svm, &funder.pubkey(), &mint, 1_000);
+        let beneficiary_ata = create_and_fund_ata(&mut svm, &beneficiary.pubkey(), &mint, 0);
+
+        let (start, cliff, end) = (1_000, 1_250, 2_000);
+        set_unix_timestamp(&mut svm, 500);
+
+        let bad_schedule = build_create_vesting_ix(
+            &funder.pubkey(),
+            &beneficiary.pubkey(),
+            &mint,
+            &config_pda,
+            0,
+            1_000,
+            start,
+            end + 1,
+            end,
+        );
+        assert!(
+            !send(&mut svm, bad_schedule, &funder),
+            "Cliff after end must be rejected"
+        );
+
+        let create = build_create_vesting_ix(
+            &funder.pubkey(),
+            &beneficiary.pubkey(),
+            &mint,
+            &config_pda,
+            0,
+            1_000,
+            start,
+            cliff,
+            end,
+        );
+        assert!(send(&mut svm, create, &funder));
+        assert_eq!(get_token_balance(&svm, &funder_ata), 0);
+        let vault = spl_associated_token_account::get_associated_token_address(
+            &vesting_pda(&beneficiary.pubkey(), 0),
+            &mint,
+        );
+        assert_eq!(get_token_balance(&svm, &vault), 1_000);
+
+        set_unix_timestamp(&mut svm, cliff - 1);
+        assert!(
+            !send(
+                &mut svm,
+                build_claim_vested_ix(&beneficiary.pubkey(), &mint, 0),
+                &beneficiary
+            ),
+            "Nothing is claimable before the cliff"
+        );
+
+        set_unix_timestamp(&mut svm, 1_500);
+        assert!(send(
+            &mut svm,
+            build_claim_vested_ix(&beneficiary.pubkey(), &mint, 0),
+            &beneficiary
+        ));
+        assert_eq!(get_token_balance(&svm, &beneficiary_ata), 500);
+
+        assert!(
+            !send(
+                &mut svm,
+                build_claim_vested_ix(&beneficiary.pubkey(), &mint, 0),
+                &beneficiary
+            ),
+            "A second claim at the same time has nothing new to release"
+        );
+
+        let outsider = Keypair::new();
+        svm.airdrop(&outsider.pubkey(), 1_000_000_000).unwrap();
+        create_and_fund_ata(&mut svm, &outsider.pubkey(), &mint, 0);
+        set_unix_timestamp(&mut svm, end);
+        assert!(
+            !send(
+                &mut svm,
+                build_claim_vested_ix(&outsider.pubkey(), &mint, 0),
+                &outsider
+            ),
+            "Only the beneficiary's escrow can be claimed"
+        );
+
+        assert!(send(
+            &mut svm,
+            build_claim_vested_ix(&beneficiary.pubkey(), &mint, 0),
+            &beneficiary
+        ));
+        assert_eq!(get_token_balance(&svm, &beneficiary_ata), 1_000);
+        assert_eq!(get_token_balance(&svm, &vault), 0);
+    }
+}
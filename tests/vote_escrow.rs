@@ -0,0 +1,145 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_liquid_staking::state::{VE_MAX_LOCK_SECONDS, VE_MIN_LOCK_SECONDS};
+    use solana_sdk::signature::Signer;
+
+    use crate::test_helpers::test_helpers::{
+        build_lock_lst_ix, build_unlock_lst_ix, get_token_balance, get_vote_escrow, run_deposit,
+        run_initialize, send, setup_svm, vote_escrow_pda, warp_time,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+    const NOW: i64 = 1_000_000;
+
+    #[test]
+    fn test_lock_extend_and_unlock() {
+        let mut svm = setup_svm();
+        let (_, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+        let (owner, owner_ata) = run_deposit(
+            &mut svm,
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+            5 * LAMPORTS_PER_SOL,
+        );
+        let balance = get_token_balance(&svm, &owner_ata);
+        warp_time(&mut svm, NOW);
+
+        let lock = |svm: &mut LiteSVM, lst_amount: u64, unlock_ts: i64| {
+            send(
+                svm,
+                build_lock_lst_ix(&owner.pubkey(), &mint, &config_pda, lst_amount, unlock_ts),
+                &owner,
+            )
+        };
+
+        assert!(
+            !lock(&mut svm, LAMPORTS_PER_SOL, NOW + VE_MIN_LOCK_SECONDS - 1),
+            "A lock shorter than the minimum must be rejected"
+        );
+        assert!(
+            !lock(&mut svm, LAMPORTS_PER_SOL, NOW + VE_MAX_LOCK_SECONDS + 1),
+            "A lock longer than the maximum must be rejected"
+        );
+
+        // A one-year lock is a quarter of the maximum.
+        let one_year = NOW + VE_MAX_LOCK_SECONDS / 4;
+        assert!(lock(&mut svm, 2 * LAMPORTS_PER_SOL, one_year));
+        assert_eq!(
+            get_token_balance(&svm, &owner_ata),
+            balance - 2 * LAMPORTS_PER_SOL
+        );
+        assert_eq!(
+            get_vote_escrow(&svm, &owner.pubkey()),
+            (2 * LAMPORTS_PER_SOL, one_year, LAMPORTS_PER_SOL / 2)
+        );
+
+        assert!(
+            !lock(&mut svm, 0, one_year - 1),
+            "The unlock time cannot be brought forward"
+        );
+
+        // Extending to the maximum gives one unit of weight per LST.
+        let four_years = NOW + VE_MAX_LOCK_SECONDS;
+        assert!(lock(&mut svm, LAMPORTS_PER_SOL, four_years));
+        assert_eq!(
+            get_vote_escrow(&svm, &owner.pubkey()),
+            (3 * LAMPORTS_PER_SOL, four_years, 3 * LAMPORTS_PER_SOL)
+        );
+
+        warp_time(&mut svm, four_years - 1);
+        assert!(
+            !send(
+                &mut svm,
+                build_unlock_lst_ix(&owner.pubkey(), &mint),
+                &owner
+            ),
+            "The LST stays locked until the unlock time"
+        );
+
+        warp_time(&mut svm, four_years);
+        assert!(send(
+            &mut svm,
+            build_unlock_lst_ix(&owner.pubkey(), &mint),
+            &owner
+        ));
+        assert_eq!(get_token_balance(&svm, &owner_ata), balance);
+        assert!(svm
+            .get_account(&vote_escrow_pda(&owner.pubkey()))
+            .is_none_or(|account| account.lamports == 0));
+    }
+
+    #[test]
+    fn test_only_the_owner_can_unlock() {
+        let mut svm = setup_svm();
+        let (_, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+        let (owner, _) = run_deposit(
+            &mut svm,
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+            2 * LAMPORTS_PER_SOL,
+        );
+        let (other, _) = run_deposit(
+            &mut svm,
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+            2 * LAMPORTS_PER_SOL,
+        );
+        warp_time(&mut svm, NOW);
+
+        assert!(send(
+            &mut svm,
+            build_lock_lst_ix(
+                &owner.pubkey(),
+                &mint,
+                &config_pda,
+                LAMPORTS_PER_SOL,
+                NOW + VE_MIN_LOCK_SECONDS,
+            ),
+            &owner
+        ));
+        warp_time(&mut svm, NOW + VE_MIN_LOCK_SECONDS);
+
+        // Another holder passing the owner's escrow with their own ATA.
+        let mut ix = build_unlock_lst_ix(&owner.pubkey(), &mint);
+        ix.accounts[0].pubkey = other.pubkey();
+        ix.accounts[1].pubkey =
+            spl_associated_token_account::get_associated_token_address(&other.pubkey(), &mint);
+        assert!(
+            !send(&mut svm, ix, &other),
+            "Only the owner can unlock an escrow"
+        );
+    }
+}
@@ -7,12 +7,12 @@ mod tests {
     use solana_sdk::signer::Signer;
     use solana_sdk::transaction::Transaction;
 
-    use solana_liquid_staking::instructions::helpers::STAKE_PROGRAM_ID;
+    use solana_liquid_staking::ids::STAKE_PROGRAM_ID;
 
     use crate::test_helpers::test_helpers::{
-        build_withdraw_ix, print_transaction_logs, run_crank_initialize_reserve,
-        run_crank_merge_reserve, run_crank_split, run_deposit, run_initialize, run_withdraw,
-        setup_svm, PROGRAM_ID,
+        build_crank_split_ix, build_withdraw_ix, get_token_balance, print_transaction_logs,
+        run_crank_initialize_reserve, run_crank_merge_reserve, run_crank_split, run_deposit,
+        run_initialize, run_withdraw, setup_svm, split_record_pda, PROGRAM_ID,
     };
 
     /// Sets up a pool ready for withdraw: initialize + deposit + crank_init_reserve + merge + split.
@@ -492,24 +492,235 @@ mod tests {
             .get_account(&depositor_stake_account)
             .unwrap()
             .lamports;
+        let split_record_balance = svm
+            .get_account(&split_record_pda(&depositor_stake_account))
+            .unwrap()
+            .lamports;
         let withdrawer_balance_before = svm.get_account(&depositor.pubkey()).unwrap().lamports;
 
         run_withdraw(&mut svm, &depositor, &depositor_stake_account, &config_pda, 123);
 
         let withdrawer_balance_after = svm.get_account(&depositor.pubkey()).unwrap().lamports;
 
-        // The withdrawer pays a tx fee, so the increase should be
-        // split_account_balance minus the transaction fee.
+        // The withdrawer pays a tx fee and gets the split record rent back, so
+        // the increase should be split_account_balance plus the record rent
+        // minus the transaction fee.
         let balance_increase = withdrawer_balance_after - withdrawer_balance_before;
         let tx_fee = 5000u64; // standard Solana tx fee
+        let expected_increase = split_account_balance + split_record_balance - tx_fee;
 
         assert_eq!(
             balance_increase,
-            split_account_balance - tx_fee,
-            "Withdrawer should receive the full split account balance minus tx fee. \
+            expected_increase,
+            "Withdrawer should receive the full split account balance and record rent minus tx fee. \
              Expected increase: {}, actual increase: {}",
-            split_account_balance - tx_fee,
+            expected_increase,
             balance_increase,
         );
+
+        assert!(
+            svm.get_account(&split_record_pda(&depositor_stake_account))
+                .is_none_or(|account| account.lamports == 0),
+            "Split record should be closed"
+        );
+    }
+
+    #[test]
+    fn test_sponsored_split_refunds_payer_on_withdraw() {
+        use solana_sdk::instruction::AccountMeta;
+
+        let mut svm = setup_svm();
+        let (
+            initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            vote_pubkey,
+        ) = run_initialize(&mut svm);
+
+        let (depositor, depositor_ata) = run_deposit(
+            &mut svm,
+            &config_pda,
+            &token_mint.pubkey(),
+            &stake_account_main,
+            &stake_account_reserve,
+            2_000_000_000,
+        );
+        run_crank_initialize_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_reserve,
+            &vote_pubkey,
+        );
+        run_crank_merge_reserve(
+            &mut svm,
+            &initializer,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+        );
+
+        let sponsor = Keypair::new();
+        svm.airdrop(&sponsor.pubkey(), 10_000_000_000).unwrap();
+        let sponsor_balance_before = svm.get_account(&sponsor.pubkey()).unwrap().lamports;
+        let depositor_balance_before = svm.get_account(&depositor.pubkey()).unwrap().lamports;
+
+        let (mut split_ix, split_account) = build_crank_split_ix(
+            &depositor.pubkey(),
+            &depositor_ata,
+            &config_pda,
+            &stake_account_main,
+            &stake_account_reserve,
+            &token_mint.pubkey(),
+            1_500_000_000,
+            true,
+            7,
+        );
+        split_ix
+            .accounts
+            .push(AccountMeta::new(sponsor.pubkey(), true));
+        let tx = Transaction::new_signed_with_payer(
+            &[split_ix],
+            Some(&sponsor.pubkey()),
+            &[&sponsor, &depositor],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Sponsored CrankSplit should succeed");
+
+        assert_eq!(
+            svm.get_account(&depositor.pubkey()).unwrap().lamports,
+            depositor_balance_before,
+            "The sponsor pays the fee, rent, and funding of the split"
+        );
+
+        let split_account_balance = svm.get_account(&split_account).unwrap().lamports;
+        let split_record_balance = svm
+            .get_account(&split_record_pda(&split_account))
+            .unwrap()
+            .lamports;
+        let sponsor_balance_after_split = svm.get_account(&sponsor.pubkey()).unwrap().lamports;
+        let split_funding =
+            sponsor_balance_before - sponsor_balance_after_split - split_record_balance - 10_000;
+
+        let withdraw_ix = build_withdraw_ix(
+            &split_account,
+            &depositor.pubkey(),
+            &config_pda,
+            &Pubkey::from(STAKE_PROGRAM_ID),
+            7,
+            true,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[withdraw_ix.clone()],
+            Some(&sponsor.pubkey()),
+            &[&sponsor, &depositor],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(
+            result.is_err(),
+            "Withdraw must refund the sponsor named in the split record"
+        );
+        svm.expire_blockhash();
+
+        let sponsor_balance_before_withdraw = svm.get_account(&sponsor.pubkey()).unwrap().lamports;
+        let mut withdraw_ix = withdraw_ix;
+        withdraw_ix
+            .accounts
+            .push(AccountMeta::new(sponsor.pubkey(), false));
+        let tx = Transaction::new_signed_with_payer(
+            &[withdraw_ix],
+            Some(&sponsor.pubkey()),
+            &[&sponsor, &depositor],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Sponsored Withdraw should succeed");
+
+        assert_eq!(
+            svm.get_account(&sponsor.pubkey()).unwrap().lamports,
+            sponsor_balance_before_withdraw - 10_000 + split_funding,
+            "The sponsor gets the split account's rent and funding back"
+        );
+        assert_eq!(
+            svm.get_account(&depositor.pubkey()).unwrap().lamports,
+            depositor_balance_before + split_account_balance - split_funding + split_record_balance,
+            "The withdrawer gets the split stake and the record rent"
+        );
+    }
+
+    #[test]
+    fn test_withdraw_restake_redeposits_proceeds() {
+        use solana_sdk::instruction::AccountMeta;
+
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            token_mint,
+            depositor,
+            depositor_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            depositor_stake_account,
+            _vote_pubkey,
+        ) = setup_withdraw_ready_pool(&mut svm, 2_000_000_000, 1_500_000_000);
+
+        let split_account_balance = svm.get_account(&depositor_stake_account).unwrap().lamports;
+        let split_record_balance = svm
+            .get_account(&split_record_pda(&depositor_stake_account))
+            .unwrap()
+            .lamports;
+        let reserve_before = svm.get_account(&stake_account_reserve).unwrap().lamports;
+        let withdrawer_before = svm.get_account(&depositor.pubkey()).unwrap().lamports;
+        let lst_before = get_token_balance(&svm, &depositor_ata);
+
+        let mut ix = build_withdraw_ix(
+            &depositor_stake_account,
+            &depositor.pubkey(),
+            &config_pda,
+            &Pubkey::from(STAKE_PROGRAM_ID),
+            123,
+            true,
+        );
+        ix.data.push(1);
+        ix.accounts.extend([
+            AccountMeta::new(depositor_ata, false),
+            AccountMeta::new(token_mint.pubkey(), false),
+            AccountMeta::new_readonly(stake_account_main, false),
+            AccountMeta::new(stake_account_reserve, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ]);
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Restaking Withdraw should succeed");
+
+        assert_eq!(
+            svm.get_account(&stake_account_reserve).unwrap().lamports,
+            reserve_before + split_account_balance,
+            "The withdrawn SOL goes straight to the reserve"
+        );
+        assert_eq!(
+            svm.get_account(&depositor.pubkey()).unwrap().lamports,
+            withdrawer_before + split_record_balance - 5_000,
+            "Only the split record rent reaches the wallet"
+        );
+        assert!(
+            get_token_balance(&svm, &depositor_ata) > lst_before,
+            "LST is minted for the restaked SOL"
+        );
     }
 }
@@ -0,0 +1,113 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{pubkey::Pubkey, signature::Signer};
+
+    use crate::test_helpers::test_helpers::{
+        build_withdraw_stake_ix, get_mint_supply, get_token_balance, run_crank_initialize_reserve,
+        run_crank_merge_reserve, send, setup_pool, setup_svm, Pool, PoolSetup, PROGRAM_ID,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    impl Pool {
+        fn withdraw_stake(&self, svm: &mut LiteSVM, lamports: u64, nonce: u64) -> bool {
+            let ix = build_withdraw_stake_ix(
+                &self.depositor.pubkey(),
+                &self.config_pda,
+                &self.stake_account_main,
+                &self.stake_account_reserve,
+                &self.mint,
+                lamports,
+                nonce,
+            );
+            send(svm, ix, &self.depositor)
+        }
+    }
+
+    /// Initializes a pool whose 5 SOL deposit has been merged into main.
+    fn setup(svm: &mut LiteSVM) -> Pool {
+        let pool = setup_pool(
+            svm,
+            PoolSetup {
+                deposit: 5 * LAMPORTS_PER_SOL,
+                ..PoolSetup::default()
+            },
+        );
+        run_crank_initialize_reserve(
+            svm,
+            &pool.admin,
+            &pool.config_pda,
+            &pool.stake_account_reserve,
+            &pool.vote_pubkey,
+        );
+        run_crank_merge_reserve(
+            svm,
+            &pool.admin,
+            &pool.config_pda,
+            &pool.stake_account_main,
+            &pool.stake_account_reserve,
+        );
+        pool
+    }
+
+    #[test]
+    fn test_withdraw_stake_hands_over_an_active_stake_account() {
+        let mut svm = setup_svm();
+        let pool = setup(&mut svm);
+
+        let balance_before = get_token_balance(&svm, &pool.depositor_ata);
+        let supply_before = get_mint_supply(&svm, &pool.mint);
+        assert!(pool.withdraw_stake(&mut svm, 2 * LAMPORTS_PER_SOL, 1));
+
+        let burned = balance_before - get_token_balance(&svm, &pool.depositor_ata);
+        assert!(burned > 0);
+        assert_eq!(get_mint_supply(&svm, &pool.mint), supply_before - burned);
+
+        let stake_account = Pubkey::find_program_address(
+            &[
+                b"split_account",
+                pool.depositor.pubkey().as_ref(),
+                &1u64.to_le_bytes(),
+            ],
+            &PROGRAM_ID,
+        )
+        .0;
+        let data = svm.get_account(&stake_account).unwrap().data;
+        assert_eq!(&data[12..44], pool.depositor.pubkey().as_ref(), "Staker");
+        assert_eq!(
+            &data[44..76],
+            pool.depositor.pubkey().as_ref(),
+            "Withdrawer"
+        );
+        assert_eq!(&data[124..156], pool.vote_pubkey.as_ref());
+        assert_eq!(
+            u64::from_le_bytes(data[156..164].try_into().unwrap()),
+            2 * LAMPORTS_PER_SOL
+        );
+        assert_eq!(
+            u64::from_le_bytes(data[172..180].try_into().unwrap()),
+            u64::MAX,
+            "The stake is not deactivated"
+        );
+    }
+
+    #[test]
+    fn test_withdraw_stake_keeps_the_split_minimums() {
+        let mut svm = setup_svm();
+        let pool = setup(&mut svm);
+
+        assert!(!pool.withdraw_stake(&mut svm, LAMPORTS_PER_SOL / 2, 1));
+        assert!(
+            !pool.withdraw_stake(&mut svm, 20 * LAMPORTS_PER_SOL, 1),
+            "Main must keep its minimum"
+        );
+        assert!(pool.withdraw_stake(&mut svm, LAMPORTS_PER_SOL, 1));
+        assert!(
+            !pool.withdraw_stake(&mut svm, LAMPORTS_PER_SOL, 1),
+            "The split PDA is taken"
+        );
+    }
+}
@@ -0,0 +1,214 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    };
+
+    use crate::test_helpers::test_helpers::{
+        build_burn_from_wrapper_ix, build_mint_to_wrapper_ix, build_set_wrapper_program_ix,
+        create_and_fund_ata, get_mint_supply, get_token_balance, run_crank_management_fee,
+        run_crank_update_rate, send, send_with_unsigned_signers, setup_pool, setup_svm, warp_epoch,
+        wrapper_authority_pda, Pool, PoolSetup,
+    };
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    /// A stand-in wrapper program with its funded authority PDA, the
+    /// authority's LST vault and a payer for its transactions.
+    struct Wrapper {
+        program: Pubkey,
+        authority: Pubkey,
+        vault: Pubkey,
+        payer: Keypair,
+    }
+
+    impl Wrapper {
+        fn mint_ix(&self, pool: &Pool, lamports: u64) -> Instruction {
+            build_mint_to_wrapper_ix(
+                &self.authority,
+                &pool.config_pda,
+                &pool.mint,
+                &pool.stake_account_main,
+                &pool.stake_account_reserve,
+                lamports,
+            )
+        }
+
+        fn burn_ix(&self, pool: &Pool, lst_amount: u64, min_lamports_out: u64) -> Instruction {
+            build_burn_from_wrapper_ix(
+                &self.authority,
+                &pool.config_pda,
+                &pool.mint,
+                &pool.stake_account_main,
+                &pool.stake_account_reserve,
+                lst_amount,
+                min_lamports_out,
+            )
+        }
+    }
+
+    fn setup(svm: &mut LiteSVM) -> (Pool, Wrapper) {
+        let pool = setup_pool(
+            svm,
+            PoolSetup {
+                activate: true,
+                ..PoolSetup::default()
+            },
+        );
+
+        let program = Pubkey::new_unique();
+        let authority = wrapper_authority_pda(&program);
+        svm.airdrop(&authority, 20 * LAMPORTS_PER_SOL).unwrap();
+        let vault = create_and_fund_ata(svm, &authority, &pool.mint, 0);
+
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), LAMPORTS_PER_SOL).unwrap();
+
+        let wrapper = Wrapper {
+            program,
+            authority,
+            vault,
+            payer,
+        };
+        (pool, wrapper)
+    }
+
+    fn approve(svm: &mut LiteSVM, pool: &Pool, wrapper_program: &Pubkey) {
+        let ix =
+            build_set_wrapper_program_ix(&pool.admin.pubkey(), &pool.config_pda, wrapper_program);
+        assert!(send(svm, ix, &pool.admin), "Admin should set the wrapper");
+    }
+
+    #[test]
+    fn test_wrapper_mints_and_burns_at_the_exchange_rate() {
+        let mut svm = setup_svm();
+        let (pool, wrapper) = setup(&mut svm);
+        approve(&mut svm, &pool, &wrapper.program);
+
+        let supply_before = get_mint_supply(&svm, &pool.mint);
+        let reserve_before = svm.get_balance(&pool.stake_account_reserve).unwrap();
+
+        let ix = wrapper.mint_ix(&pool, 5 * LAMPORTS_PER_SOL);
+        assert!(
+            send_with_unsigned_signers(&mut svm, ix, &wrapper.payer),
+            "MintToWrapper should succeed"
+        );
+
+        let minted = get_token_balance(&svm, &wrapper.vault);
+        assert!(minted > 0);
+        assert_eq!(get_mint_supply(&svm, &pool.mint), supply_before + minted);
+        assert_eq!(
+            svm.get_balance(&pool.stake_account_reserve).unwrap(),
+            reserve_before + 5 * LAMPORTS_PER_SOL
+        );
+
+        let authority_before = svm.get_balance(&wrapper.authority).unwrap();
+        let ix = wrapper.burn_ix(&pool, minted / 2, 0);
+        assert!(
+            send_with_unsigned_signers(&mut svm, ix, &wrapper.payer),
+            "BurnFromWrapper should succeed"
+        );
+
+        let paid = svm.get_balance(&wrapper.authority).unwrap() - authority_before;
+        assert!(paid > 0 && paid <= 5 * LAMPORTS_PER_SOL / 2);
+        assert_eq!(get_token_balance(&svm, &wrapper.vault), minted - minted / 2);
+        assert_eq!(
+            svm.get_balance(&pool.stake_account_reserve).unwrap(),
+            reserve_before + 5 * LAMPORTS_PER_SOL - paid
+        );
+
+        let ix = wrapper.burn_ix(&pool, 1_000, u64::MAX);
+        assert!(
+            !send_with_unsigned_signers(&mut svm, ix, &wrapper.payer),
+            "A payout below min_lamports_out should fail"
+        );
+    }
+
+    #[test]
+    fn test_wrapper_mints_at_the_cached_rate() {
+        let mut svm = setup_svm();
+        let (pool, wrapper) = setup(&mut svm);
+        approve(&mut svm, &pool, &wrapper.program);
+
+        warp_epoch(&mut svm, 1);
+        run_crank_management_fee(
+            &mut svm,
+            &pool.admin,
+            &pool.config_pda,
+            &pool.stake_account_main,
+            &pool.stake_account_reserve,
+            &pool.mint,
+        );
+        run_crank_update_rate(
+            &mut svm,
+            &pool.admin,
+            &pool.config_pda,
+            &pool.stake_account_main,
+            &pool.stake_account_reserve,
+            &pool.mint,
+        );
+        let config = svm.get_account(&pool.config_pda).unwrap().data;
+        let field = |start: usize| u64::from_le_bytes(config[start..start + 8].try_into().unwrap());
+        let (rate_lamports, rate_lst_supply) = (field(390), field(398));
+
+        // Lamports landing mid-epoch do not move the rate the wrapper mints at.
+        let mut reserve = svm.get_account(&pool.stake_account_reserve).unwrap();
+        reserve.lamports += LAMPORTS_PER_SOL;
+        svm.set_account(pool.stake_account_reserve, reserve)
+            .unwrap();
+
+        let ix = wrapper.mint_ix(&pool, 5 * LAMPORTS_PER_SOL);
+        assert!(
+            send_with_unsigned_signers(&mut svm, ix, &wrapper.payer),
+            "MintToWrapper should succeed"
+        );
+        assert_eq!(
+            get_token_balance(&svm, &wrapper.vault) as u128,
+            5 * LAMPORTS_PER_SOL as u128 * rate_lst_supply as u128 / rate_lamports as u128
+        );
+    }
+
+    #[test]
+    fn test_wrapper_instructions_require_the_approved_program() {
+        let mut svm = setup_svm();
+        let (pool, wrapper) = setup(&mut svm);
+
+        let ix = wrapper.mint_ix(&pool, 5 * LAMPORTS_PER_SOL);
+        assert!(
+            !send_with_unsigned_signers(&mut svm, ix, &wrapper.payer),
+            "No wrapper is approved yet"
+        );
+
+        approve(&mut svm, &pool, &Pubkey::new_unique());
+        let ix = wrapper.mint_ix(&pool, 5 * LAMPORTS_PER_SOL);
+        assert!(
+            !send_with_unsigned_signers(&mut svm, ix, &wrapper.payer),
+            "Another program's authority PDA should be rejected"
+        );
+
+        approve(&mut svm, &pool, &wrapper.program);
+        let ix = wrapper.mint_ix(&pool, 5 * LAMPORTS_PER_SOL);
+        assert!(send_with_unsigned_signers(&mut svm, ix, &wrapper.payer));
+
+        approve(&mut svm, &pool, &Pubkey::default());
+        let ix = wrapper.burn_ix(&pool, 1_000, 0);
+        assert!(
+            !send_with_unsigned_signers(&mut svm, ix, &wrapper.payer),
+            "Revoking the wrapper should block burns"
+        );
+
+        let attacker = Keypair::new();
+        svm.airdrop(&attacker.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        let ix =
+            build_set_wrapper_program_ix(&attacker.pubkey(), &pool.config_pda, &wrapper.program);
+        assert!(
+            !send(&mut svm, ix, &attacker),
+            "Only the admin can approve a wrapper"
+        );
+    }
+}
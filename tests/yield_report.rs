@@ -0,0 +1,136 @@
+mod test_helpers;
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+
+    use crate::test_helpers::test_helpers::{
+        add_stake_rewards, build_burn_and_donate_ix, get_mint_supply, get_token_balance,
+        get_yield_report, run_deposit, run_initialize, setup_svm,
+    };
+
+    #[test]
+    fn test_yield_report_separates_rewards_from_deposits() {
+        let mut svm = setup_svm();
+        let (
+            _initializer,
+            token_mint,
+            _initializer_ata,
+            config_pda,
+            stake_account_main,
+            stake_account_reserve,
+            _vote_pubkey,
+        ) = run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+        let report = |svm: &mut litesvm::LiteSVM| {
+            get_yield_report(
+                svm,
+                &payer,
+                &config_pda,
+                &mint,
+                &stake_account_main,
+                &stake_account_reserve,
+            )
+            .expect("GetYieldReport should succeed")
+        };
+
+        let [principal, yield_lamports, pool, supply] = report(&mut svm);
+        assert_eq!(principal, pool, "Initialization is all principal");
+        assert_eq!(yield_lamports, 0);
+        assert_eq!(supply, get_mint_supply(&svm, &mint));
+
+        run_deposit(
+            &mut svm,
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+            3_000_000_000,
+        );
+        let [after_deposit, yield_lamports, _, _] = report(&mut svm);
+        assert_eq!(after_deposit, principal + 3_000_000_000);
+        assert_eq!(yield_lamports, 0);
+
+        add_stake_rewards(&mut svm, &stake_account_main, 500_000_000);
+        let [after_rewards, yield_lamports, pool, _] = report(&mut svm);
+        assert_eq!(after_rewards, after_deposit, "Rewards are not principal");
+        assert_eq!(yield_lamports, 500_000_000);
+        assert_eq!(after_rewards + yield_lamports, pool);
+
+        run_deposit(
+            &mut svm,
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+            2_000_000_000,
+        );
+        let [principal, yield_lamports, _, _] = report(&mut svm);
+        assert_eq!(principal, after_rewards + 2_000_000_000);
+        assert_eq!(
+            yield_lamports, 500_000_000,
+            "A deposit at the new rate adds no yield"
+        );
+    }
+
+    #[test]
+    fn test_yield_report_releases_principal_of_burned_lst() {
+        let mut svm = setup_svm();
+        let (_, token_mint, _, config_pda, stake_account_main, stake_account_reserve, _) =
+            run_initialize(&mut svm);
+        let mint = token_mint.pubkey();
+
+        let (donor, donor_ata) = run_deposit(
+            &mut svm,
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+            2_000_000_000,
+        );
+
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+        let [principal, _, _, supply] = get_yield_report(
+            &mut svm,
+            &payer,
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+        )
+        .unwrap();
+
+        let donated = get_token_balance(&svm, &donor_ata) / 2;
+        let ix = build_burn_and_donate_ix(&donor.pubkey(), &donor_ata, &mint, &config_pda, donated);
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&donor.pubkey()),
+            &[&donor],
+            svm.latest_blockhash(),
+        );
+        svm.send_transaction(tx)
+            .expect("BurnAndDonate should succeed");
+
+        let [after_burn, yield_lamports, pool, _] = get_yield_report(
+            &mut svm,
+            &payer,
+            &config_pda,
+            &mint,
+            &stake_account_main,
+            &stake_account_reserve,
+        )
+        .unwrap();
+        let kept = (principal as u128 * (supply - donated) as u128 / supply as u128) as u64;
+        assert_eq!(after_burn, kept);
+        assert_eq!(
+            yield_lamports,
+            pool - kept,
+            "The donated principal becomes yield for the remaining holders"
+        );
+        assert!(yield_lamports > 0);
+    }
+}